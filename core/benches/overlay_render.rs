@@ -0,0 +1,48 @@
+//! Overlay render benchmark: `cargo bench --bench overlay_render`
+//! Times tinting, premultiplying and blending a crosshair and a full-screen layer at 1080p,
+//! 1440p and 4K with criterion, which compares each run against the last one saved under
+//! target/criterion, so new overlay features (HUD, animation) can't quietly make frames more
+//! expensive. The blend results themselves are checked by the unit tests in `overlay_render`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use gaming_optimizer_core::overlay_render::{clear_frame, render_centered, synthetic_crosshair, SYNTHETIC_SIZE};
+
+/// Screen sizes every case is timed at
+const RESOLUTIONS: [(&str, u32, u32); 3] = [("1080p", 1920, 1080), ("1440p", 2560, 1440), ("4K", 3840, 2160)];
+
+/// Tint applied in every run (tinting is the slower path)
+const TINT: Option<[u8; 4]> = Some([0, 255, 0, 255]);
+
+/// The crosshair alone; it's redrawn on every swap
+fn crosshair(c: &mut Criterion) {
+    let image = synthetic_crosshair(SYNTHETIC_SIZE, SYNTHETIC_SIZE);
+    let mut group = c.benchmark_group("crosshair");
+    group.throughput(Throughput::Elements(u64::from(SYNTHETIC_SIZE * SYNTHETIC_SIZE)));
+    for (label, width, height) in RESOLUTIONS {
+        let mut frame = Vec::new();
+        clear_frame((width, height), &mut frame);
+        group.bench_function(BenchmarkId::from_parameter(label), |b| {
+            b.iter(|| render_centered(black_box(&image), SYNTHETIC_SIZE, SYNTHETIC_SIZE, TINT, width, &mut frame))
+        });
+    }
+    group.finish();
+}
+
+/// A screen-sized layer, standing in for the HUD and other full-screen overlays
+fn full_screen(c: &mut Criterion) {
+    let mut group = c.benchmark_group("full-screen");
+    group.sample_size(20);
+    for (label, width, height) in RESOLUTIONS {
+        let layer = synthetic_crosshair(width, height);
+        let mut frame = Vec::new();
+        clear_frame((width, height), &mut frame);
+        group.throughput(Throughput::Elements(u64::from(width * height)));
+        group.bench_function(BenchmarkId::from_parameter(label), |b| {
+            b.iter(|| render_centered(black_box(&layer), width, height, TINT, width, &mut frame))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, crosshair, full_screen);
+criterion_main!(benches);
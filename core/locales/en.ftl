@@ -0,0 +1,632 @@
+# Gaming Optimizer - English (built in)
+#
+# Copy this file to <data folder>\locales\<language>.ftl (e.g. de.ftl or pt-BR.ftl) and
+# translate the text after each "=". Keep { $name } placeables as they are; ids missing
+# from a translation are shown in English.
+
+language-name = English
+
+## Tray menu and flyout
+
+tray-tooltip-active = Gaming Optimizer - { $profile }
+tray-tooltip-inactive = Gaming Optimizer - Inactive
+tray-open-settings = Open Settings
+tray-next-crosshair-hotkey = Next Crosshair ({ $hotkey })
+tray-next-crosshair = Next Crosshair
+tray-adjust-crosshair = Adjust Crosshair Position
+tray-crosshair-offset = Crosshair Offset
+tray-crosshair-offset-default = Profile default ({ $x }, { $y })
+tray-pause = Pause Optimizer
+tray-documentation = Documentation
+tray-report-bug = Report Bug
+tray-open-logs = Open Logs
+tray-start-with-windows = Start with Windows
+tray-exit = Exit
+tray-tooltip-status = Gaming Optimizer - { $profile } ({ $status })
+tray-tooltip-paused = Gaming Optimizer - Paused
+tray-tooltip-no-profile = Inactive
+app-name = Gaming Optimizer
+tray-profiles = Profiles
+tray-overlay-visible = Overlay Visible
+tray-no-profiles = (No profiles - open Settings)
+tray-no-profile = (None)
+flyout-title = Gaming Profiles
+flyout-subtitle = Click to activate a profile
+flyout-active = Active
+flyout-no-profiles = No gaming profiles configured
+flyout-processes = { $count } processes to manage
+flyout-activate = Activate
+flyout-stop = Stop
+flyout-last-used = Used { $age } ago
+flyout-never-used = Never used
+flyout-more = + { $count } more in the app
+flyout-open-gui = Open GUI
+flyout-adjust = Adjust crosshair
+flyout-exit = Exit
+
+## Notifications
+
+toast-category-profile-activated = Profile activated
+toast-category-processes-killed = Processes closed
+toast-category-overlay-error = Overlay errors
+toast-category-update-available = Update available
+
+## Activation results
+
+tray-status-rolled-back = rolled back
+tray-status-applied = { $applied } applied
+tray-status-applied-failed = { $applied } applied, { $failed } failed
+report-closed = Closed
+report-failed = Failed to close
+report-timed-out = Still closing (timed out)
+report-not-started = Not tried (ran out of time)
+report-not-running = Not running
+report-protected = Protected
+copy-report = 📋 Copy report
+overlay-conflict = { $overlay } overlay is running and can hide the crosshair or upset anti-cheat. { $hint }
+overlay-hint-geforce = Turn off the in-game overlay in GeForce Experience (Alt+Z), or close it for this session.
+overlay-hint-discord = Turn off "Enable in-game overlay" in Discord's Game Overlay settings.
+overlay-hint-rivatuner = Close RivaTuner or set its application detection to "None" for the game.
+overlay-hint-game-bar = Turn Game Bar off in this profile's Windows game features.
+overlay-fix-close = Close it
+overlay-fix-game-bar = Turn off Game Bar
+activation-needs-admin = 🔒 Skipped without administrator rights: { $features }
+preflight-missing-crosshair = Crosshair image { $path } is missing; the crosshair won't show
+preflight-monitor-disconnected = Monitor { $monitor } isn't connected; its display mode can't be set
+preflight-needs-admin = Needs administrator rights: { $features }
+preflight-game-not-installed = { $game } isn't installed here, so launching it won't switch to this profile
+admin-feature-services = stopping Windows services
+admin-feature-network-policies = bandwidth limits
+admin-feature-maintenance-tasks = pausing maintenance tasks
+admin-feature-standby-purge = clearing the standby list
+admin-feature-elevated-processes = closing apps that run as administrator
+
+## Status bar
+
+status-activation-rolled-back = ❌ Profile '{ $profile }' not activated: { $step } failed, system restored.
+status-activation-rollback-incomplete = ❌ Profile '{ $profile }' not activated: { $step } failed, and some changes couldn't be undone (see the report).
+status-activation-done = ✅ Profile '{ $profile }' activated!
+
+## Main window
+
+profiles = 📋 Profiles
+dashboard = 🏠 Dashboard
+new-profile = + New Profile
+stats = 📊 Stats
+settings = ⚙ Settings
+edit-profile = ✏️ Edit Profile
+tab-general = General
+tab-processes = Processes
+tab-crosshair = Crosshair
+tab-system-tweaks = System Tweaks
+unsaved-changes = ● Unsaved changes
+profile-name = Profile Name
+fan-speed = 🌀 Fan Speed
+alert-limits = 🔥 Alert limits
+cpu-c = CPU °C
+gpu-c = GPU °C
+alert-limits-hint = (blank = global limits)
+processes-to-kill = 🔪 Processes to Kill
+refresh = 🔄 Refresh
+processes-to-kill-hint = Select running applications to close when activating:
+import-kill-list = 📥 Import List…
+game-suggestions-title = 💡 Suggestions for { $game }
+apply = Apply
+suggest-kill = Close { $process }
+suggest-close-overlay = Close { $overlay } ({ $process }), its overlay conflicts with the game
+suggest-power-plan = Switch to the { $plan } power plan
+suggest-high-precision-timer = Use the high precision timer
+kill-list-import-dialog-title = Import a kill list
+crosshair-overlay = 🎯 Crosshair Overlay
+crosshair-overlay-hint = Crosshair will be centered on screen. Use arrows for pixel-perfect adjustment.
+select-image = 📁 Select Image
+clear = ❌ Clear
+crosshair-no-image = No image (100x100 PNG, or drop one onto the window)
+import = 📋 Import
+position-adjustment = Position Adjustment
+manual = Manual:
+save-profile = 💾 Save Profile
+delete = 🗑️ Delete
+activate = ⚡ ACTIVATE
+benchmark = ⏱ Benchmark
+check-profiles = 🔍 Check Profiles
+lint-title = { $count } problem(s) to look at
+lint-protected-kill = { $process } is a protected Windows process and is never closed; remove it from the kill list
+lint-missing-crosshair = Crosshair image { $path } is missing; pick the image again
+lint-missing-preset-crosshair = Crosshair image of preset { $preset } ({ $path }) is missing; pick the image again
+lint-offset-out-of-range = Crosshair offset { $x }, { $y } is beyond ±500 px; center it or move it back in range
+lint-preset-offset-out-of-range = Offset { $x }, { $y } of preset { $preset } is beyond ±500 px; move it back in range
+lint-anchor-out-of-range = Crosshair position is outside 0-100% of the screen; pick an anchor again
+lint-duplicate-name = Only the case differs from profile { $profile }; rename one so commands and the tray can tell them apart
+tint-color = Color:
+timer-unavailable = Timer: unavailable
+diagnostics = 🩺 Diagnostics
+diagnostics-refresh = Refresh
+log-level = Log level:
+open-logs = Open logs
+appearance = 🎨 Appearance
+theme-mode = Theme:
+accent = Accent:
+hotkeys = ⌨ Hotkeys
+next-crosshair = Next crosshair
+save = Save
+hotkey-example = e.g. Ctrl+Alt+C, Shift+F9
+gamepad = 🎮 Controller
+gamepad-enabled = Enable controller combos (Xbox/XInput)
+gamepad-toggle-overlay = Show/hide crosshair
+gamepad-hint = At least two buttons, e.g. Back+RightStick or LB+RB+A. Buttons: A, B, X, Y, LB, RB, LT, RT, Back, Start, LS, RS, Up, Down, Left, Right. Leave empty to turn one off.
+tray-clicks = 🖱 Tray icon clicks
+tray-single-click = Single click:
+tray-double-click = Double click:
+tray-middle-click = Middle click:
+tray-click-nothing = Nothing
+tray-click-flyout = Show profile flyout
+tray-click-window = Open window
+tray-click-overlay = Show/hide crosshair
+tray-click-cycle-crosshair = Next crosshair
+tray-click-adjust-crosshair = Adjust crosshair position
+tray-click-pause = Pause/resume optimizer
+tray-click-deactivate = Deactivate profile
+tray-tooltip = 💬 Tray tooltip
+tray-tooltip-hint = Refreshed with every hardware sample. Placeholders: { $placeholders }. Leave empty for the profile name.
+tray-tooltip-unknown = Unknown placeholders (shown as typed): { $placeholders }
+safety = 🛡 Safety
+safety-protected-processes = Never close (on top of the built-in system list):
+add = Add
+choose-folder = Choose folder
+sync-now = Sync now
+stop-syncing = Stop syncing
+profile-sync = 🔄 Profile Sync
+profile-sync-hint = Keeps the profile file in step with a folder such as OneDrive or Dropbox; the newer copy wins.
+backup = 💾 Backup
+backup-export = Export backup…
+backup-import = Import backup…
+backup-hint = One zip with your profiles, settings, hotkeys and crosshair images, for a new PC or a fresh Windows install.
+data-folder = 📁 Data Folder
+data-folder-default = Use default folder
+data-folder-hint = Where profiles, settings, crosshairs and logs are kept, e.g. a second drive or a synced folder. Your data is copied over; the old folder keeps its files. Logs move after a restart.
+data-folder-portable = Portable mode keeps the data next to the app.
+startup = 🚀 Startup and exit
+startup-hint = Starts minimized to the tray. Administrator start uses a scheduled task and needs the app to run as administrator once to set up.
+session-stats = 📊 Session Stats
+last-7-days = Last 7 days
+per-game = Per game
+stats-no-sessions = No sessions yet – activate a profile and play
+recent-sessions = Recent sessions
+benchmarks = Benchmarks
+check-now = Check now
+install = Install
+release-notes = Release notes
+updates = ⬆ Updates
+update-every = every
+notifications = 🔔 Notifications
+remote-api = 🌐 Remote API
+port = Port
+new-token = New token
+remote-api-hint = Only reachable from this PC (127.0.0.1)
+alerts = 🔥 Alerts
+switch-to-profile = Switch to profile
+waiting-for-first-sample = Waiting for first sample…
+hardware = 🌡 Hardware
+monitor-every = Every
+game-features = 🎮 Windows
+game-features-hint = Changes apply on activation and are restored afterwards; running games and the Game Bar may need a restart
+tweaks = 🧹 On activation
+tweaks-hint = One-time actions, not undone on deactivation; clearing the standby list and restarting audio need administrator rights
+audio = 🔊 Audio
+master = Master %
+add-app-volume = ➕ Add app volume
+gpu = 🎮 GPU
+keyboard = ⌨ Keyboard
+pin-layout = Pin layout
+network = 🌐 Network
+add-bandwidth-limit = ➕ Add bandwidth limit
+storage = 💽 Storage
+storage-hint = Stops background disk activity that hitches games on HDDs
+lighting = 💡 Lighting
+lighting-connect-hint = Connect to OpenRGB (left panel) to pick its profiles
+openrgb = 💡 OpenRGB
+save-connect = Save & connect
+openrgb-hint = Start the SDK server in OpenRGB first
+obs = 🎥 OBS
+obs-hint = Turn on the WebSocket server in OBS (Tools > WebSocket Server Settings) first
+obs-scene = 🎥 OBS scene
+obs-record = Record
+obs-replay-buffer = Replay buffer
+obs-connect-hint = Connect to OBS (left panel) to pick its scenes
+captures = 📸 Captures
+captures-sort = Move new screenshots and clips into a folder named after the profile
+captures-folder = Folder is made in: { $folder }
+captures-next-to-them = the folder they were saved to
+captures-folder-reset = Next to them
+captures-folder-dialog-title = Folder for this profile's captures
+captures-hint = Watched while the profile is active: { $folders }
+captures-no-folders = No Screenshots or Game Bar Captures folder on this PC yet
+password = Password
+discord = 💬 Discord
+discord-hint = Uses the name of your Discord application (developer portal)
+event-log = 📜 Windows Event Log
+event-log-enabled = Log activations to the Event Log
+event-log-hint = Application log, source GamingOptimizer: 1000 activated, 1001 activated with failed steps, 1002 not activated, 1003 deactivated, 1004 deactivated with errors
+plugins = 🧩 Plugins
+plugins-none = No plugins yet. Put .rhai scripts or programs (.exe, .bat, .cmd) in the plugins folder.
+plugins-open-folder = Open plugins folder
+plugins-reload = Reload plugins
+plugins-hint = Scripts define on_activate, on_deactivate, on_game_start or on_temp_alert (ctx) and can call activate, deactivate, overlay and log. Programs get each event as a JSON line on stdin and have 5 seconds to answer. A plugin runs once it's ticked; reload after editing one.
+display = 🖥 Display
+power-plan = ⚡ Power Plan
+anchor = Anchor:
+overlay-note-hint = Overlay note (optional, e.g. "DON'T TILT" or keybinds):
+size = Size
+corner = Corner
+note-color = Color
+overlay-hardware-readings = 🌡 Hardware readings (CPU/GPU load, clocks, temps)
+weapon-presets = Weapon Presets
+weapon-presets-hint = Cycle with Ctrl+Alt+C or the tray menu while the profile is active
+load = Load
+save-current-as-preset = ➕ Save current as preset
+offset-presets = Offset Presets
+offset-presets-hint = Offsets for other resolutions; switch with the tray menu's Crosshair Offset while the profile is active
+offset-preset-use = Use
+offset-preset-stop-using = Stop using
+offset-preset-default = Profile default
+save-offsets-as-preset = ➕ Save offsets as preset
+next = ⏭ Next
+no-processes-found-matching-filter = No processes found matching filter
+app-category-launchers = Game launchers
+app-category-chat = Chat
+app-category-streaming = Streaming & recording
+app-category-media = Media & music
+app-category-browsers = Browsers
+app-category-cloud = Cloud sync
+app-category-updaters = Updaters
+app-category-rgb = RGB & peripheral suites
+app-category-security = Antivirus & security
+app-category-development = Development
+app-category-system = System
+app-category-other = Other processes
+do-not-disturb = 🔕 Do Not Disturb (hide notification popups)
+mute-notification-sounds = Mute notification sounds
+high-precision-timer = High-precision timer (helps old games and frame limiters)
+best-performance-visuals = Best performance visual effects (no animations, shadows or transparency)
+skip-kill-confirmation = Don't ask before closing these apps
+rollback-on-failure = ↩ Undo all changes if any step fails to apply
+enable-crosshair-overlay = Enable crosshair overlay
+exclude-from-capture = Hide crosshair from screen capture (OBS, screenshots)
+enable-global-hotkeys = Enable global hotkeys
+ask-before-closing-running-apps = Ask before closing running apps
+start-with-windows = Start with Windows
+start-as-administrator = Start as administrator
+exit-behavior = On exit with a profile active:
+resume-last-session = Resume the last session on start (active profile, crosshair and pause)
+auto-activate-games = Activate a game's profile when the game starts
+auto-activate-games-hint = Works for the games in the suggestions list; the profile needs the game in its name, e.g. "CS2 ranked"
+safe-mode-banner = 🛟 Safe mode — hotkeys, file watching, the remote API, the keyboard hook, the crosshair and automatic activation are off.
+safe-mode-restart = Restart normally
+admin-badge = 🔒 Needs administrator
+restart-as-admin = Restart as administrator
+exit-deactivate = Deactivate the profile
+exit-ask = Ask every time
+exit-leave-active = Leave the system as it is
+exit-confirm-title = Exit Gaming Optimizer
+exit-confirm-body =
+    { $profile } is still active. Deactivate it before exiting?
+
+    Yes restores the power plan, services and other settings it changed and starts the apps it closed, No leaves the system as it is, Cancel keeps the app running.
+check-for-updates = Check for updates
+allow-remote-control = Allow remote control
+warn-when-too-hot = Warn when too hot
+max-fans = Max fans
+prefer-maximum-performance = Prefer maximum performance
+block-win-key = Block Win key
+no-sticky-keys-popup = No Sticky Keys popup
+keyboard-anti-cheat-safe = Win key block and layout pinning are off in anti-cheat safe mode
+pause-delivery-optimization = Pause Windows Update downloads (Delivery Optimization)
+pause-search-indexing = Pause search indexing
+pause-defrag-and-maintenance-tasks = Pause defrag and maintenance tasks
+show-active-profile = Show active profile
+profile-name-placeholder = Enter profile name...
+process-filter-placeholder = Filter processes...
+crosshair-code-placeholder = Paste code (Valorant or CS2 CSGO-...)
+alert-limit-off = off
+volume-unchanged = unchanged
+app-volume-placeholder = App (e.g. Discord.exe)
+nvidia-profile-placeholder = NVIDIA driver profile (optional)
+bandwidth-limit-placeholder = App (e.g. steam.exe)
+kbps = Kbps
+host = Host
+application-id = Application ID
+preset-name-placeholder = Preset name (e.g. Sniper)
+offset-preset-name-placeholder = Preset name (e.g. 1440p ultrawide)
+set-to-max-when-active = Set to MAX when active
+monitor-placeholder = Monitor
+install-update-title = Install update
+unsaved-changes-title = Unsaved changes
+sync-folder-dialog-title = Sync profiles with
+backup-export-dialog-title = Save backup as
+backup-import-dialog-title = Restore backup
+backup-import-title = Import backup
+data-folder-dialog-title = Keep the app's data in
+data-folder-title = Data folder
+data-folder-has-data =
+    { $folder } already has Gaming Optimizer data.
+
+    Yes: use the data that's there
+    No: replace it with your current data
+backup-import-body =
+    Add the { $count } profiles from the backup?
+
+    Your settings, hotkeys and tray clicks are replaced by the backup's.
+backup-import-conflicts =
+    { $count } profiles in the backup have the same name as yours: { $names }
+
+    Yes: replace yours with the backup's
+    No: keep yours and add only the others
+
+    Your settings, hotkeys and tray clicks are replaced by the backup's.
+toast-not-activated-title = { $profile } not activated
+toast-not-activated-body = { $step } failed, so all changes were undone
+toast-activated-title = { $profile } activated
+toast-processes-closed-title = Processes closed
+toast-processes-closed-body = Closed { $count } background processes for { $profile }
+toast-crosshair-failed-title = Crosshair failed to start
+toast-tray-closed = Closed { $closed } apps, skipped { $skipped }
+toast-tray-nothing-closed = Nothing to close
+toast-tray-crosshair-started = Crosshair started
+toast-tray-crosshair-failed = Crosshair didn't start
+toast-tray-steps-failed = { $failed } step(s) failed, open the window for details
+toast-update-title = Update available
+toast-update-body = Gaming Optimizer { $version } is ready to install
+unsaved-new-profile = the new profile
+unsaved-changes-body =
+    Save your changes to { $profile }?
+
+    Yes saves them, No discards them, Cancel keeps editing.
+install-update-body =
+    Download and install Gaming Optimizer { $version }?
+
+    The app will close to finish the update.
+install-update-body-active =
+    Download and install Gaming Optimizer { $version }?
+
+    The app will close and undo the changes made by '{ $profile }' to finish the update.
+alert-balloon-title = Temperature alert
+overlay-click-through-failed = Crosshair disabled: click-through could not be guaranteed, the overlay would block mouse input
+toast-crosshair-disabled-title = Crosshair disabled
+window-title = Gaming Optimizer - Profile Manager
+choice-dont-change = Don't change
+choice-solid-color = Solid color
+choice-dont-pin = Don't pin
+choice-desktop-mode = Desktop mode
+choice-dont-switch = Don't switch
+choice-off = Off
+choice-on = On
+tint-original = Original
+tint-green = Green
+tint-cyan = Cyan
+tint-magenta = Magenta
+tint-red = Red
+tint-yellow = Yellow
+timer-current = Timer: { $value }
+timer-range = Range: { $finest } – { $coarsest }
+overlay-render-time = Crosshair render: { $ms } ms at { $width }×{ $height }
+stats-recording = Recording: { $profile } (ends when the profile is deactivated)
+stats-week-summary = { $sessions } sessions, { $played } played, longest { $longest }
+stats-most-played = Most played: { $game }
+stats-processes-killed = { $count } killed
+stats-session-line = { $profile } – { $game } · { $duration } · CPU { $cpu_load } { $cpu_temp } · GPU { $gpu_load } { $gpu_temp } · Ping { $ping } (jitter { $jitter })
+stats-benchmarks-hint = Before activation vs. { $minutes } min after (start one with ⏱ Benchmark in the editor)
+update-downloading = Downloading update...
+update-checking = Checking...
+update-available = { $version } is available
+update-current-version = Version { $version }
+remote-api-token = Token: { $token }
+latency-loss = { $endpoint } ({ $loss }% loss)
+hardware-sensors = Sensors: { $provider }
+hardware-basic-sensors = Basic sensors only – run LibreHardwareMonitor for GPU/fan readings
+game-mode = Game Mode
+game-bar = Game Bar
+background-recording = Background recording
+gpu-none-detected = no GPU detected
+keyboard-layout-not-installed = Not installed
+bandwidth-limit-entry = { $process } - max { $kbps } Kbps
+anchor-position = → ({ $x }, { $y }) at { $width }x{ $height }
+preset-no-image = no image
+crosshair-offset = Offset: X={ $x }, Y={ $y }
+process-column-name = Process
+process-column-cpu = CPU
+process-column-memory = Memory
+process-not-running-short = not running
+process-selected-only = Selected only
+process-list-count = { $count } shown, { $selected } selected
+profile-sync-off = Not synced
+crosshair-settings = Crosshair Settings
+x-offset-pixels = X Offset (pixels)
+y-offset-pixels = Y Offset (pixels)
+crosshair-image-hint = Crosshair Image (100x100 PNG)
+editor-select-image = Select Image
+no-image-selected = No image selected
+editor-profile-name-placeholder = Enter profile name
+offset-range-placeholder = -500 to 500
+language = 🌍 Language
+language-open-folder = Translations folder
+language-hint = Add a translation by dropping a Fluent file such as de.ftl into the translations folder. The tray menu changes after a restart.
+language-system = Windows language
+
+## Quick switcher
+
+profile-search-placeholder = Search (Ctrl+K)
+no-matching-profiles = No matching profiles
+archived-profiles = Archived ({ $count })
+restore-profile = Restore
+quick-switch-placeholder = Find a profile…
+quick-switch-edit = ✏ Edit
+quick-switch-hint = ↑↓ to choose · Enter to activate · Ctrl+Enter to edit · Esc to close
+
+## Close apps confirmation
+
+kill-confirm-title = Activate { $profile }?
+kill-confirm-hint = These running apps will be closed. Unsaved work in them will be lost.
+kill-confirm-instances = { $process } ({ $count } processes)
+kill-confirm-memory = { $memory } MB
+kill-confirm-total = Memory freed: about { $memory } MB
+kill-confirm-dont-ask-again = Don't ask again for this profile
+kill-confirm-close-and-activate = Close apps and activate
+kill-confirm-problems = This profile doesn't fully fit this PC right now:
+kill-confirm-activate-anyway = Activate anyway
+cancel = Cancel
+
+## Status bar
+
+status-bar-inactive = No active profile | 📌 Tray
+
+## Dashboard
+
+dashboard-active = 🟢 { $profile } is active
+dashboard-inactive = No profile active
+dashboard-overlay-on = Crosshair overlay shown
+dashboard-overlay-off = Crosshair overlay off
+deactivate = Deactivate
+dashboard-load = System load
+dashboard-top-processes = Busiest processes
+dashboard-favorites = ★ Favorites
+dashboard-no-favorites = Tick "Favorite" in a profile's editor to get a quick-activate button here.
+favorite-profile = ★ Favorite (quick-activate button on the dashboard)
+archived-profile = Archived (hidden from the tray, the quick switcher and the dashboard)
+anti-cheat-safe = 🛡 Anti-cheat safe (Vanguard, EAC, BattlEye)
+anti-cheat-safe-hint = Only the crosshair's own window is used: no keyboard hook and no messages to the game while this profile is active.
+status-profiles-saved = Profiles saved successfully
+status-no-profile-selected = ⚠️ No profile selected to activate
+status-benchmark-running = ⏱ A benchmark is already running
+status-benchmark-no-profile = ⚠️ Select a profile to benchmark
+status-benchmark-cancelled = ⏱ Benchmark cancelled (profile changed)
+status-invalid-api-port = ❌ Enter a port between 1024 and 65535
+status-invalid-monitor-interval = ❌ Enter the sampling interval in seconds (e.g. 2)
+status-cycle-no-profile = ⚠️ Activate a profile before cycling crosshairs
+status-creating-profile = Creating new profile
+status-empty-profile-name = ❌ Error: Profile name cannot be empty
+status-crosshair-centered = Crosshair centered
+status-empty-preset-name = ❌ Enter a name for the crosshair preset
+status-empty-offset-preset-name = ❌ Enter a name for the offset preset
+status-invalid-app-volume = ❌ App volume must be between 0 and 100%
+status-empty-app-volume-process = ❌ Enter the app's executable name (e.g. Discord.exe)
+status-invalid-bandwidth-limit = ❌ Bandwidth limit must be a number of Kbps above 0
+status-empty-bandwidth-limit-process = ❌ Enter the executable to throttle (e.g. steam.exe)
+status-invalid-openrgb-port = ⚠️ Invalid OpenRGB port
+status-invalid-obs-port = ⚠️ Invalid OBS port
+status-crosshair-image-cleared = Cleared crosshair image
+status-profiles-loaded = Loaded { $count } profiles
+status-profiles-load-failed = Failed to load profiles: { $error }
+status-profiles-quarantined = ⚠ Skipped { $count } broken profile entries (moved to { $file }): { $errors }
+status-backup-exported = 💾 Backup saved to { $path } ({ $profiles } profiles, { $images } crosshair images)
+status-backup-export-failed = ❌ Backup failed: { $error }
+status-backup-restored = 💾 Restored { $added } new and { $replaced } replaced profiles ({ $kept } kept), { $images } crosshair images and the settings
+status-backup-restore-failed = ❌ Couldn't restore the backup: { $error }
+status-profile-restored = ↩ { $profile } is back in the tray and the quick switcher
+status-data-folder-moved = 📁 Data folder is now { $folder } ({ $files } files copied)
+status-data-folder-failed = ❌ Couldn't move the data folder: { $error }
+status-safe-mode = 🛟 Safe mode: nothing was activated, fix or delete the profile that caused trouble
+status-safe-mode-no-crosshair = 🛟 No crosshair in safe mode
+status-lint-clean = ✅ No problems found in { $count } profile(s)
+status-lint-found = ⚠ { $count } problem(s) found, see the list under the editor
+status-restart-failed = ❌ Couldn't restart: { $error }
+status-profiles-reloaded = 🔄 The profile file changed on disk, reloaded { $count } profiles
+status-config-reloaded = 🔄 Settings reloaded from config.json
+status-config-reload-failed = ⚠️ config.json changed but couldn't be read: { $error }
+status-profiles-saved-sync-failed = Profiles saved, but sync failed: { $error }
+status-profiles-save-failed = Failed to save profiles: { $error }
+status-activation-cancelled = Activation of '{ $profile }' cancelled
+status-previous-profile-error = Previous profile: { $error }
+status-benchmark-baseline = ⏱ Benchmarking '{ $profile }': measuring the baseline…
+status-benchmark-settling = ⏱ Benchmarking '{ $profile }': profile activated, measuring again in { $minutes } min
+status-benchmark-finished = ⏱ Benchmark '{ $profile }': { $result }
+status-benchmark-save-failed = Failed to save benchmark: { $error }
+status-benchmark-failed = ⏱ Benchmark failed: { $error }
+status-settings-save-failed = Failed to save settings: { $error }
+status-event-log-registered = ✅ Registered the GamingOptimizer source in the Event Log
+status-event-log-register-failed = ⚠ Activations are logged, but the Event Log source couldn't be registered: { $error }
+status-event-log-not-registered = ⚠ Activations are logged; run the app as administrator once and toggle this again so Event Viewer shows their text
+status-plugins-open-failed = Failed to open the plugins folder: { $error }
+status-plugins-reloaded = 🧩 Reloaded plugins ({ $count } enabled)
+status-overlay-shown = 🎯 Crosshair shown
+status-overlay-hidden = Crosshair hidden
+status-adjust-started = 🎯 Adjusting the crosshair: arrow keys move it (Shift for 10 px), Enter or Escape saves
+status-adjust-saved = 🎯 Crosshair position saved ({ $x }, { $y })
+status-adjust-cancelled = Crosshair adjustment stopped, the crosshair changed
+status-adjust-failed = Can't adjust the crosshair: { $error }
+status-adjust-no-crosshair = Show a profile's crosshair before adjusting it
+status-paused = ⏸ Paused: hotkeys, monitoring and the crosshair are suspended, the profile stays applied
+status-resumed = ▶ Resumed
+status-update-available = ⬆ Gaming Optimizer { $version } is available
+status-update-check-failed = ❌ Update check failed: { $error }
+status-update-failed = ❌ Update failed: { $error }
+status-update-downloading = ⬆ Downloading { $file }...
+status-hotkey-failed = ❌ Hotkey: { $error }
+status-gamepad-failed = ❌ Controller: { $error }
+status-sync-pulled = 🔄 Loaded { $count } profiles from { $folder }
+status-sync-pushed = 🔄 Profiles copied to { $folder }
+status-sync-failed = ❌ Profile sync: { $error }
+status-autostart-failed = ❌ Start with Windows: { $error }
+status-remote-api-listening = 🌐 Remote API listening on 127.0.0.1:{ $port }
+status-remote-api-failed = ❌ Remote API: { $error }
+status-alert-fan-max = Fan: MAX ({ $backend })
+status-alert-fan-failed = Fan: { $error }
+status-alert-switched = Switched to '{ $profile }'
+status-alert-profile-missing = Profile '{ $profile }' not found
+status-profile-deactivated = Profile deactivated
+status-crosshair-error = Crosshair error: { $error }
+status-cycle-no-presets = Profile '{ $profile }' has no crosshair presets to cycle
+status-crosshair-shown = 🎯 Crosshair: { $crosshair }
+status-crosshair-no-image = Crosshair '{ $crosshair }' has no image
+status-welcome = Welcome to Gaming Optimizer
+status-editing-profile = Editing profile: { $profile }
+status-profile-updated = ✅ Updated profile: { $profile }
+status-profile-created = ✅ Created profile: { $profile }
+status-profile-deleted = 🗑️ Deleted profile: { $profile }
+status-processes-refreshed = 🔄 Refreshed: { $count } processes found
+status-kill-list-imported = 📥 { $count } process(es) imported, save the profile to keep them
+status-kill-list-imported-protected = 📥 { $count } process(es) imported; left out as protected: { $protected }
+status-kill-list-import-failed = ❌ Couldn't import the kill list: { $error }
+status-suggestion-applied = ✓ { $suggestion }, save the profile to keep it
+status-overlay-closed = ✓ Closed { $overlay }
+status-overlay-close-failed = ❌ Couldn't close { $overlay }: { $processes }
+status-overlay-game-bar-off = ✓ Game Bar turns off the next time { $profile } is activated
+status-preset-added = Preset '{ $preset }' added (save the profile to keep it)
+status-preset-loaded = Loaded preset '{ $preset }' into the editor
+status-preset-removed = Removed preset '{ $preset }'
+status-offset-preset-selected = Crosshair offset: { $preset } ({ $x }, { $y })
+status-openrgb-connected = 💡 Connected to OpenRGB ({ $count } profiles)
+status-openrgb-failed = OpenRGB: { $error }
+status-obs-connected = 🎥 Connected to OBS ({ $count } scenes)
+status-obs-failed = OBS: { $error }
+status-release-notes-failed = Failed to open release notes: { $error }
+status-image-selected = 📁 Selected image: { $path }
+status-image-dropped = 📁 Crosshair set to { $path } (save the profile to keep it)
+status-image-invalid = ❌ Invalid image: { $error }
+status-crosshair-code-imported = 🎯 Imported crosshair code: { $path }
+status-crosshair-code-invalid = ❌ Invalid crosshair code: { $error }
+status-bar-active = 🟢 Active: { $profile }{ $fan }{ $dnd }{ $anti_cheat } | 📌 Tray
+status-bar-fan-max = 🌀 Fan MAX
+status-bar-dnd = 🔕 DND
+status-bar-anti-cheat-safe = 🛡 Anti-cheat safe
+status-translations-open-failed = Failed to open the translations folder: { $error }
+status-report-copied = 📋 Activation report copied to the clipboard
+
+## Settings choices
+
+log-level-error = Errors
+log-level-warn = Warnings
+log-level-info = Info
+log-level-debug = Debug
+log-level-trace = Trace
+update-channel-stable = Stable
+update-channel-beta = Beta
+theme-mode-system = Windows setting
+theme-mode-dark = Dark
+theme-mode-light = Light
+accent-blue = Blue
+accent-purple = Purple
+accent-green = Green
+accent-orange = Orange
+accent-red = Red
+accent-pink = Pink
@@ -0,0 +1,158 @@
+//! Activating and deactivating profiles, shared by every app mode
+//!
+//! The window, --tray-only and --headless all go through `ActivationManager`, which keeps the
+//! switchers' saved state and the steps of the active profile so they can be undone later.
+//! Profiles are applied with this machine's entries from overrides.json.
+
+use super::steps::{self, Backends, SystemState};
+use super::{ActivationSummary, AppliedPlan};
+use crate::config::get_data_directory;
+use crate::i18n::t;
+use crate::obs::ObsConfig;
+use crate::openrgb::OpenRgbConfig;
+use crate::overlay::OverlayOptions;
+use crate::power::PowerPlan;
+use crate::process::{KillReport, ProcessBackend};
+use crate::profile::{machine_name, MachineOverrides, Profile, OVERRIDES_FILE};
+use tracing::{info, warn};
+
+/// Result of `ActivationManager::activate`
+pub struct Activation {
+    pub summary: ActivationSummary,
+    /// What the process step closed, missed and skipped (None without one)
+    pub kills: Option<KillReport>,
+    /// Steps of the previous profile that couldn't be undone
+    pub revert_errors: Vec<String>,
+}
+
+/// Everything needed to apply a profile and put the system back afterwards
+#[derive(Default)]
+pub struct ActivationManager {
+    pub system: SystemState,
+    applied: Option<AppliedPlan<SystemState>>,
+}
+
+/// `profile` with this machine's overrides, or as it is when overrides.json can't be used
+fn with_machine_overrides(profile: &Profile) -> Profile {
+    let machine = machine_name();
+    let overrides = match get_data_directory().and_then(|dir| MachineOverrides::load(&dir)) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            warn!("[Profile] Ignoring {}: {}", OVERRIDES_FILE, e);
+            return profile.clone();
+        }
+    };
+    match overrides.apply(profile, &machine) {
+        Ok(applied) => {
+            let fields = overrides.overridden_fields(&profile.name, &machine);
+            if !fields.is_empty() {
+                info!("[Profile] {} overrides {} of {}", machine, fields.join(", "), profile.name);
+            }
+            applied
+        }
+        Err(e) => {
+            warn!("[Profile] Ignoring {}: {}", OVERRIDES_FILE, e);
+            profile.clone()
+        }
+    }
+}
+
+impl ActivationManager {
+    /// Activation through `backends` (the app passes its crosshair overlay, tests pass fakes)
+    pub fn new(backends: Backends) -> Self {
+        ActivationManager { system: SystemState { backends, ..Default::default() }, applied: None }
+    }
+
+    /// Apply `profile`, undoing the previous one first so it starts from the user's settings
+    pub fn activate(&mut self, profile: &Profile, power_plans: &[PowerPlan], openrgb: &OpenRgbConfig, obs: &ObsConfig) -> Activation {
+        let plan = steps::plan_for_profile(&with_machine_overrides(profile), power_plans, openrgb, obs);
+        // Keep the crosshair running through the revert so the new profile's can be swapped in
+        self.system.parked_overlay = self.system.overlay.take();
+        let revert_errors = self.revert();
+
+        self.system.processes_killed = 0;
+        self.system.kill_report = None;
+        let (applied, summary) = plan.execute(&mut self.system);
+        summary.log();
+        // Still parked: the new profile shows no crosshair
+        if let Some(handle) = self.system.parked_overlay.take() {
+            handle.stop();
+        }
+        self.applied = Some(applied);
+
+        Activation { summary, kills: self.system.kill_report.take(), revert_errors }
+    }
+
+    /// The processes activation closes, also used to list and close apps outside of a profile
+    pub fn processes(&mut self) -> &mut dyn ProcessBackend {
+        self.system.backends.processes.as_mut()
+    }
+
+    /// Undo the active profile's steps, last first ("Name: error" for each that failed)
+    pub fn revert(&mut self) -> Vec<String> {
+        match self.applied.take() {
+            Some(plan) => plan.revert(&mut self.system),
+            None => Vec::new(),
+        }
+    }
+
+    /// Undo the active profile (starting the apps it closed again) and release fans a
+    /// temperature alert forced to max
+    pub fn deactivate(&mut self) -> Vec<String> {
+        let mut errors = self.revert();
+        if let Err(e) = self.system.fan.restore() {
+            errors.push(e.to_string());
+        }
+        errors
+    }
+
+    /// Show one of the profile's crosshair slots, in the running overlay if there is one;
+    /// returns the crosshair name
+    pub fn show_crosshair(&mut self, profile: &Profile, slot: usize) -> Result<String, String> {
+        let profile = &with_machine_overrides(profile);
+        let crosshair = profile.crosshair_for_slot(slot);
+
+        if crate::config::is_safe_mode() {
+            self.hide_crosshair();
+            return Err(t!("status-safe-mode-no-crosshair"));
+        }
+
+        let Some(path) = crosshair.image_path else {
+            self.hide_crosshair();
+            return Err(t!("status-crosshair-no-image", crosshair = crosshair.name));
+        };
+        let options = OverlayOptions {
+            exclude_from_capture: profile.exclude_from_capture,
+            tint: crosshair.tint,
+            note: profile.overlay_note.clone(),
+            anchor: profile.crosshair_anchor,
+            hud: profile.hardware_hud,
+        };
+        if let Some(handle) = self.system.overlay.as_mut() {
+            if handle.swap(&path, crosshair.x_offset, crosshair.y_offset, &options) {
+                return Ok(crosshair.name);
+            }
+        }
+        self.hide_crosshair();
+        let handle = self.system.backends.overlay.start(path, crosshair.x_offset, crosshair.y_offset, &options)
+            .map_err(|e| t!("status-crosshair-error", error = e))?;
+        self.system.overlay = Some(handle);
+        Ok(crosshair.name)
+    }
+
+    pub fn hide_crosshair(&mut self) {
+        if let Some(handle) = self.system.overlay.take() {
+            handle.stop();
+        }
+    }
+
+    /// Clean up before the app exits. Without `deactivate` the profile's changes stay, but the
+    /// crosshair goes since nothing could hide it once the app is gone.
+    pub fn shutdown(&mut self, deactivate: bool) {
+        if deactivate {
+            let _ = self.deactivate();
+        } else {
+            self.hide_crosshair();
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! Profile activation as an ordered plan of reversible steps
+//!
+//! A profile is turned into an `ActivationPlan` (see `steps::plan_for_profile`). Running it
+//! applies each step in order and records a per-step result; the steps that ran are kept in an
+//! `AppliedPlan` and undone in reverse order when the profile is deactivated. With
+//! `rollback_on_failure` the first failed step undoes everything before it instead; so does a
+//! failed essential step (the crosshair, the power plan), since the profile is no use without it.
+
+pub mod manager;
+pub mod steps;
+
+use crate::i18n::t;
+use crate::process::KillReport;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::{info, warn};
+
+/// One reversible part of an activation. `S` is the state the steps share (the switchers that
+/// remember what to put back).
+pub trait Step<S> {
+    /// Short label used in summaries and logs ("Power", "Display", ...)
+    fn name(&self) -> &'static str;
+
+    /// Make the change. Returns status lines to show (empty when there is nothing to report).
+    fn apply(&mut self, state: &mut S) -> Result<Vec<String>>;
+
+    /// Undo `apply`. Also called after a failed `apply`, so it must cope with a half-applied
+    /// change. One-shot steps (killing processes, ...) have nothing to undo.
+    fn revert(&mut self, _state: &mut S) -> Result<()> {
+        Ok(())
+    }
+
+    /// Whether the profile is no use when this step fails, so the plan is rolled back even
+    /// without `rollback_on_failure`
+    fn essential(&self) -> bool {
+        false
+    }
+}
+
+/// What happened to a step
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StepStatus {
+    /// Applied, with its status lines
+    Applied(Vec<String>),
+    Failed(String),
+    /// Applied, then undone because a later step failed
+    RolledBack,
+    /// Not run because an earlier step failed
+    Skipped,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepResult {
+    pub name: &'static str,
+    pub status: StepStatus,
+}
+
+/// Outcome of running a plan, shown in the GUI report panel, the tray tooltip and the log
+#[derive(Clone, Debug)]
+pub struct ActivationSummary {
+    pub profile: String,
+    /// One entry per step, in plan order
+    pub results: Vec<StepResult>,
+    /// A step failed and everything was undone
+    pub rolled_back: bool,
+    /// Steps that failed to undo during a rollback ("Name: error")
+    pub revert_errors: Vec<String>,
+}
+
+impl ActivationSummary {
+    pub fn applied_count(&self) -> usize {
+        self.results.iter().filter(|r| matches!(r.status, StepStatus::Applied(_))).count()
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.results.iter().filter(|r| matches!(r.status, StepStatus::Failed(_))).count()
+    }
+
+    /// Step whose failure rolled the plan back
+    pub fn failed_step(&self) -> Option<&'static str> {
+        self.results.iter().find(|r| matches!(r.status, StepStatus::Failed(_))).map(|r| r.name)
+    }
+
+    /// "Profile activated" or "not activated: Crosshair failed, system restored"
+    pub fn headline(&self) -> String {
+        if !self.rolled_back {
+            return t!("status-activation-done", profile = self.profile);
+        }
+        let step = self.failed_step().unwrap_or_default();
+        if self.revert_errors.is_empty() {
+            t!("status-activation-rolled-back", profile = self.profile, step = step)
+        } else {
+            t!("status-activation-rollback-incomplete", profile = self.profile, step = step)
+        }
+    }
+
+    /// Short result for the tray tooltip, e.g. "12 applied, 1 failed"
+    pub fn tray_status(&self) -> String {
+        if self.rolled_back {
+            return t!("tray-status-rolled-back");
+        }
+        match self.failed_count() {
+            0 => t!("tray-status-applied", applied = self.applied_count()),
+            failed => t!("tray-status-applied-failed", applied = self.applied_count(), failed = failed),
+        }
+    }
+
+    /// Notification body for a tray activation (no status bar to read there): what was closed
+    /// or skipped, whether the crosshair started and how many steps failed
+    pub fn tray_notification(&self, kills: Option<&KillReport>) -> String {
+        let mut lines = Vec::new();
+        if let Some(report) = kills {
+            let skipped = report.failed.len() + report.timed_out.len() + report.not_started.len() + report.blocklist_skipped.len();
+            lines.push(if report.killed.is_empty() && skipped == 0 {
+                t!("toast-tray-nothing-closed")
+            } else {
+                t!("toast-tray-closed", closed = report.killed.len(), skipped = skipped)
+            });
+        }
+        match self.results.iter().find(|r| r.name == "Crosshair").map(|r| &r.status) {
+            Some(StepStatus::Applied(_)) => lines.push(t!("toast-tray-crosshair-started")),
+            Some(StepStatus::Failed(_)) => lines.push(t!("toast-tray-crosshair-failed")),
+            _ => {}
+        }
+        if self.failed_count() > 0 {
+            lines.push(t!("toast-tray-steps-failed", failed = self.failed_count()));
+        }
+        if lines.is_empty() {
+            return self.tray_status();
+        }
+        lines.join("\n")
+    }
+
+    /// Plain-text report with one line per step, for pasting into bug reports
+    pub fn report_text(&self) -> String {
+        let mut lines = vec![format!("Profile: {}{}", self.profile, if self.rolled_back { " (rolled back)" } else { "" })];
+        for result in &self.results {
+            let status = match result.status {
+                StepStatus::Applied(ref status_lines) if status_lines.is_empty() => "applied".to_string(),
+                StepStatus::Applied(ref status_lines) => status_lines.join("; "),
+                StepStatus::Failed(ref error) => format!("FAILED: {}", error),
+                StepStatus::RolledBack => "rolled back".to_string(),
+                StepStatus::Skipped => "skipped".to_string(),
+            };
+            lines.push(format!("{}: {}", result.name, status));
+        }
+        lines.extend(self.revert_errors.iter().map(|error| format!("Rollback failed: {}", error)));
+        lines.join("\n")
+    }
+
+    /// Print one line per step
+    pub fn log(&self) {
+        for result in &self.results {
+            match result.status {
+                StepStatus::Applied(ref lines) if lines.is_empty() => {
+                    info!("[Activation] {}: applied", result.name);
+                }
+                StepStatus::Applied(ref lines) => {
+                    info!("[Activation] {}: {}", result.name, lines.join(", "));
+                }
+                StepStatus::Failed(ref error) => warn!("[Activation] {}: FAILED: {}", result.name, error),
+                StepStatus::RolledBack => info!("[Activation] {}: rolled back", result.name),
+                StepStatus::Skipped => info!("[Activation] {}: skipped", result.name),
+            }
+        }
+        for error in &self.revert_errors {
+            warn!("[Activation] Rollback failed: {}", error);
+        }
+        info!("[Activation] Profile '{}': {}", self.profile, self.tray_status());
+    }
+}
+
+/// What happens to the active profile when the app exits (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ExitBehavior {
+    /// Undo the profile's changes
+    #[default]
+    Deactivate,
+    /// Ask whether to undo them or leave the system as it is
+    Ask,
+    /// Keep the changes (the crosshair still closes with the app)
+    LeaveActive,
+}
+
+impl ExitBehavior {
+    pub const ALL: [ExitBehavior; 3] = [ExitBehavior::Deactivate, ExitBehavior::Ask, ExitBehavior::LeaveActive];
+}
+
+impl fmt::Display for ExitBehavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExitBehavior::Deactivate => write!(f, "{}", t!("exit-deactivate")),
+            ExitBehavior::Ask => write!(f, "{}", t!("exit-ask")),
+            ExitBehavior::LeaveActive => write!(f, "{}", t!("exit-leave-active")),
+        }
+    }
+}
+
+/// Ordered steps for one profile
+pub struct ActivationPlan<S> {
+    profile: String,
+    steps: Vec<Box<dyn Step<S>>>,
+    /// Undo everything as soon as a step fails (otherwise the remaining steps still run)
+    pub rollback_on_failure: bool,
+}
+
+impl<S> ActivationPlan<S> {
+    pub fn new(profile: &str) -> Self {
+        ActivationPlan {
+            profile: profile.to_string(),
+            steps: Vec::new(),
+            rollback_on_failure: false,
+        }
+    }
+
+    pub fn push(&mut self, step: impl Step<S> + 'static) {
+        self.steps.push(Box::new(step));
+    }
+
+    /// Names of the steps, in the order they run
+    pub fn step_names(&self) -> Vec<&'static str> {
+        self.steps.iter().map(|step| step.name()).collect()
+    }
+
+    /// Run the steps in order. Returns the steps to revert on deactivation and the summary.
+    pub fn execute(self, state: &mut S) -> (AppliedPlan<S>, ActivationSummary) {
+        let mut applied = AppliedPlan { steps: Vec::new() };
+        let mut summary = ActivationSummary {
+            profile: self.profile,
+            results: Vec::new(),
+            rolled_back: false,
+            revert_errors: Vec::new(),
+        };
+
+        let mut steps = self.steps.into_iter();
+        for mut step in steps.by_ref() {
+            let status = match step.apply(state) {
+                Ok(lines) => StepStatus::Applied(lines),
+                Err(e) => StepStatus::Failed(e.to_string()),
+            };
+            let failed = matches!(status, StepStatus::Failed(_));
+            let essential = step.essential();
+            summary.results.push(StepResult { name: step.name(), status });
+
+            // A failed step may be half-applied, so it is reverted along with the others
+            applied.steps.push(step);
+
+            if failed && (self.rollback_on_failure || essential) {
+                summary.rolled_back = true;
+                break;
+            }
+        }
+
+        if summary.rolled_back {
+            summary.results.extend(steps.map(|step| StepResult { name: step.name(), status: StepStatus::Skipped }));
+            summary.revert_errors = std::mem::replace(&mut applied, AppliedPlan { steps: Vec::new() }).revert(state);
+            for result in &mut summary.results {
+                if matches!(result.status, StepStatus::Applied(_)) {
+                    result.status = StepStatus::RolledBack;
+                }
+            }
+        }
+
+        (applied, summary)
+    }
+}
+
+/// Steps that ran for the active profile, kept so they can be undone
+pub struct AppliedPlan<S> {
+    steps: Vec<Box<dyn Step<S>>>,
+}
+
+impl<S> AppliedPlan<S> {
+    /// Undo every step, last first. Keeps going after a failure and returns
+    /// "Name: error" for each step that couldn't be undone.
+    pub fn revert(self, state: &mut S) -> Vec<String> {
+        let mut errors = Vec::new();
+        for mut step in self.steps.into_iter().rev() {
+            if let Err(e) = step.revert(state) {
+                errors.push(format!("{}: {}", step.name(), e));
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    /// Records apply/revert calls in the shared state
+    struct Recorder {
+        name: &'static str,
+        fail: bool,
+        essential: bool,
+    }
+
+    impl Step<Vec<String>> for Recorder {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn apply(&mut self, log: &mut Vec<String>) -> Result<Vec<String>> {
+            log.push(format!("apply {}", self.name));
+            if self.fail {
+                return Err(anyhow!("boom"));
+            }
+            Ok(vec![format!("{} on", self.name)])
+        }
+
+        fn revert(&mut self, log: &mut Vec<String>) -> Result<()> {
+            log.push(format!("revert {}", self.name));
+            Ok(())
+        }
+
+        fn essential(&self) -> bool {
+            self.essential
+        }
+    }
+
+    fn plan(steps: &[(&'static str, bool)], rollback_on_failure: bool) -> ActivationPlan<Vec<String>> {
+        let mut plan = ActivationPlan::new("Test");
+        plan.rollback_on_failure = rollback_on_failure;
+        for &(name, fail) in steps {
+            plan.push(Recorder { name, fail, essential: false });
+        }
+        plan
+    }
+
+    #[test]
+    fn test_execute_and_revert_order() {
+        let mut log = Vec::new();
+        let (applied, summary) = plan(&[("A", false), ("B", false)], false).execute(&mut log);
+        assert_eq!(summary.headline(), "✅ Profile 'Test' activated!");
+        assert_eq!(summary.report_text(), "Profile: Test\nA: A on\nB: B on");
+        assert_eq!(summary.tray_status(), "2 applied");
+
+        assert!(applied.revert(&mut log).is_empty());
+        assert_eq!(log, ["apply A", "apply B", "revert B", "revert A"]);
+    }
+
+    #[test]
+    fn test_failure_without_rollback_continues() {
+        let mut log = Vec::new();
+        let (_, summary) = plan(&[("A", false), ("B", true), ("C", false)], false).execute(&mut log);
+        assert_eq!(log, ["apply A", "apply B", "apply C"]);
+        assert_eq!(summary.results[1].status, StepStatus::Failed("boom".to_string()));
+        assert_eq!(summary.report_text(), "Profile: Test\nA: A on\nB: FAILED: boom\nC: C on");
+        assert_eq!(summary.tray_status(), "2 applied, 1 failed");
+    }
+
+    #[test]
+    fn test_tray_notification() {
+        let mut log = Vec::new();
+        let (_, summary) = plan(&[("Processes", false), ("Crosshair", true)], false).execute(&mut log);
+        let kills = KillReport {
+            killed: vec!["Discord.exe".to_string(), "Spotify.exe".to_string()],
+            failed: vec!["Steam.exe".to_string()],
+            not_found: vec!["Overwolf.exe".to_string()],
+            blocklist_skipped: Vec::new(),
+            timed_out: vec!["Teams.exe".to_string()],
+            not_started: Vec::new(),
+            closed_exes: Vec::new(),
+        };
+        assert_eq!(
+            summary.tray_notification(Some(&kills)),
+            "Closed 2 apps, skipped 2\nCrosshair didn't start\n1 step(s) failed, open the window for details"
+        );
+
+        let (_, summary) = plan(&[("Processes", false), ("Crosshair", false)], false).execute(&mut log);
+        let nothing = KillReport { killed: Vec::new(), failed: Vec::new(), not_found: Vec::new(), blocklist_skipped: Vec::new(), timed_out: Vec::new(), not_started: Vec::new(), closed_exes: Vec::new() };
+        assert_eq!(summary.tray_notification(Some(&nothing)), "Nothing to close\nCrosshair started");
+
+        // Nothing worth listing falls back to the step counts
+        let (_, summary) = plan(&[("Power", false)], false).execute(&mut log);
+        assert_eq!(summary.tray_notification(None), "1 applied");
+    }
+
+    #[test]
+    fn test_failure_with_rollback() {
+        let mut log = Vec::new();
+        let (applied, summary) = plan(&[("A", false), ("B", true), ("C", false)], true).execute(&mut log);
+        assert_eq!(log, ["apply A", "apply B", "revert B", "revert A"]);
+        assert!(summary.rolled_back);
+        assert_eq!(summary.report_text(), "Profile: Test (rolled back)\nA: rolled back\nB: FAILED: boom\nC: skipped");
+        let statuses: Vec<_> = summary.results.iter().map(|r| r.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            [StepStatus::RolledBack, StepStatus::Failed("boom".to_string()), StepStatus::Skipped]
+        );
+
+        // Nothing left to undo on deactivation
+        assert!(applied.revert(&mut log).is_empty());
+        assert_eq!(log.len(), 4);
+    }
+
+    #[test]
+    fn test_essential_failure_rolls_back() {
+        let mut log = Vec::new();
+        let mut steps = plan(&[("Processes", false), ("Power", false)], false);
+        steps.push(Recorder { name: "Crosshair", fail: true, essential: true });
+        steps.push(Recorder { name: "Keyboard", fail: false, essential: false });
+
+        let (_, summary) = steps.execute(&mut log);
+        assert!(summary.rolled_back);
+        assert_eq!(log, ["apply Processes", "apply Power", "apply Crosshair", "revert Crosshair", "revert Power", "revert Processes"]);
+        assert_eq!(summary.failed_step(), Some("Crosshair"));
+        assert_eq!(summary.headline(), "❌ Profile 'Test' not activated: Crosshair failed, system restored.");
+
+        // A non-essential failure still only fails its own step
+        let (_, summary) = plan(&[("A", true), ("B", false)], false).execute(&mut log);
+        assert!(!summary.rolled_back);
+    }
+}
@@ -0,0 +1,618 @@
+//! The steps a profile activation is built from
+//!
+//! Each step wraps one of the switchers in `SystemState`; the switchers remember the user's
+//! previous settings, the steps decide what a profile asks for and how it's reported.
+
+use super::{ActivationPlan, Step};
+use anyhow::{anyhow, Result};
+use crate::audio::{AudioSettings, AudioSwitcher};
+use crate::captures::{CaptureSettings, CaptureSorter};
+use crate::display::{DisplayModeSwitcher, DisplaySetting};
+use crate::fan::FanController;
+use crate::game_features::{self, GameFeatureSwitcher, GameFeatureToggles};
+use crate::gpu::{GpuSettings, GpuSwitcher};
+use crate::keyboard::{KeyboardLock, KeyboardSettings};
+use crate::network::{NetworkSettings, NetworkSwitcher};
+use crate::obs::{ObsConfig, ObsSettings, ObsSwitcher};
+use crate::notifications::DoNotDisturb;
+use crate::openrgb::{OpenRgbConfig, RgbScene, RgbSwitcher};
+use crate::overlay::{NoOverlay, Overlay, OverlayBackend, OverlayOptions};
+use crate::power::{self, LivePowerPlans, PowerBackend, PowerPlan, PowerPlanSwitcher};
+use crate::process::{kill_processes_with, KillReport, LiveProcesses, ProcessBackend};
+use crate::profile::Profile;
+use crate::services::{ScServices, ServiceBackend};
+use crate::storage::{StorageSettings, StorageSwitcher};
+use crate::timer::{self, TimerResolutionSwitcher};
+use crate::tweaks::{self, SystemTweak};
+use crate::visual_effects::VisualEffectsSwitcher;
+use std::path::PathBuf;
+use tracing::info;
+
+/// What the steps close processes, stop services, switch power plans and start the crosshair
+/// through: this machine by default, fakes in the integration tests. The crosshair overlay is the
+/// app's, so there's none until it hands one in.
+pub struct Backends {
+    pub processes: Box<dyn ProcessBackend>,
+    pub services: Box<dyn ServiceBackend>,
+    pub power: Box<dyn PowerBackend>,
+    pub overlay: Box<dyn OverlayBackend>,
+}
+
+impl Default for Backends {
+    fn default() -> Self {
+        Backends {
+            processes: Box::new(LiveProcesses),
+            services: Box::new(ScServices),
+            power: Box::new(LivePowerPlans),
+            overlay: Box::new(NoOverlay),
+        }
+    }
+}
+
+/// Everything an active profile has changed, kept across activations so it can be put back
+#[derive(Default)]
+pub struct SystemState {
+    pub backends: Backends,
+    /// Fans forced to max
+    pub fan: FanController,
+    pub power: PowerPlanSwitcher,
+    /// Game Mode / Game Bar registry values
+    pub game_features: GameFeatureSwitcher,
+    pub timer: TimerResolutionSwitcher,
+    pub visual_effects: VisualEffectsSwitcher,
+    /// Monitor whose desktop mode to restore
+    pub display: DisplayModeSwitcher,
+    pub audio: AudioSwitcher,
+    /// Delivery Optimization pause and QoS policies
+    pub network: NetworkSwitcher,
+    /// Paused search indexing and maintenance tasks
+    pub storage: StorageSwitcher,
+    pub rgb: RgbSwitcher,
+    /// OBS scene and outputs
+    pub obs: ObsSwitcher,
+    /// Watcher moving new screenshots and clips into the profile's folder
+    pub captures: Option<CaptureSorter>,
+    pub do_not_disturb: DoNotDisturb,
+    /// NVIDIA/AMD driver settings
+    pub gpu: GpuSwitcher,
+    /// Keyboard hook, layout pin and Sticky Keys shortcut
+    pub keyboard: KeyboardLock,
+    /// Crosshair overlay (also restarted by the editor and the crosshair hotkey)
+    pub overlay: Option<Box<dyn Overlay>>,
+    /// Previous profile's overlay, kept running through a profile switch so the next crosshair
+    /// step can swap its image in instead of restarting it
+    pub parked_overlay: Option<Box<dyn Overlay>>,
+    /// Processes killed by the last activation (for session statistics)
+    pub processes_killed: usize,
+    /// What the last activation's process step closed, missed and skipped (GUI report panel)
+    pub kill_report: Option<KillReport>,
+}
+
+/// Start `exes` again, skipping any the user already restarted ("exe: error" for each that
+/// failed)
+fn relaunch_apps(processes: &mut dyn ProcessBackend, exes: &[PathBuf]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for exe in exes {
+        let Some(name) = exe.file_name().map(|name| name.to_string_lossy().into_owned()) else { continue };
+        if processes.find(std::slice::from_ref(&name)).iter().any(|pids| !pids.is_empty()) {
+            continue;
+        }
+        match processes.launch(exe) {
+            Ok(()) => info!("[Process] Relaunched {}", name),
+            Err(e) => errors.push(format!("{}: {}", name, e)),
+        }
+    }
+    errors
+}
+
+/// Build the plan for a profile. Features the profile doesn't use get no step.
+pub fn plan_for_profile(profile: &Profile, power_plans: &[PowerPlan], openrgb: &OpenRgbConfig, obs: &ObsConfig) -> ActivationPlan<SystemState> {
+    let mut plan = ActivationPlan::new(&profile.name);
+    plan.rollback_on_failure = profile.rollback_on_failure;
+
+    if !profile.processes_to_kill.is_empty() {
+        plan.push(KillProcesses { names: profile.processes_to_kill.clone(), closed: Vec::new() });
+    }
+    if !profile.system_tweaks.is_empty() {
+        plan.push(RunTweaks(profile.system_tweaks.clone()));
+    }
+    if profile.fan_speed_max {
+        plan.push(MaxFans);
+    }
+    if let Some(ref guid) = profile.power_plan {
+        plan.push(SetPowerPlan { guid: guid.clone(), name: power::plan_name(power_plans, guid) });
+    }
+    if !profile.game_features.is_empty() {
+        plan.push(ToggleGameFeatures(profile.game_features));
+    }
+    if profile.high_precision_timer {
+        plan.push(HighPrecisionTimer);
+    }
+    if profile.best_performance_visuals {
+        plan.push(BestPerformanceVisuals);
+    }
+    // Switch modes before the overlay starts so it centers on the new resolution
+    if let Some(ref setting) = profile.display_mode {
+        plan.push(SetDisplayMode(setting.clone()));
+    }
+    if !profile.audio.is_empty() {
+        plan.push(SetVolumes(profile.audio.clone()));
+    }
+    if !profile.network.is_empty() {
+        plan.push(ShapeNetwork(profile.network.clone()));
+    }
+    if !profile.storage.is_empty() {
+        plan.push(PauseStorageWork(profile.storage));
+    }
+    if let Some(ref scene) = profile.rgb_scene {
+        plan.push(SetRgbScene { config: openrgb.clone(), scene: scene.clone() });
+    }
+    if !profile.obs.is_empty() {
+        plan.push(SetObsScene { config: obs.clone(), settings: profile.obs.clone() });
+    }
+    if profile.captures.sort {
+        plan.push(SortCaptures { profile: profile.name.clone(), settings: profile.captures.clone() });
+    }
+    if profile.do_not_disturb {
+        plan.push(EnableDoNotDisturb { mute_sounds: profile.mute_notification_sounds });
+    }
+    if !profile.gpu.is_empty() {
+        plan.push(ApplyGpuSettings(profile.gpu.clone()));
+    }
+    // Safe mode leaves out the keyboard hook and the crosshair, the parts most likely to get in
+    // the way of fixing a bad profile
+    let safe_mode = crate::config::is_safe_mode();
+    // Anti-cheat safe profiles never hook input or post messages to the game's window. Nothing
+    // else here touches the game: the crosshair is its own click-through window, and it follows
+    // the foreground with an out-of-context WinEvent hook, which loads nothing into the game.
+    let keyboard = if profile.anti_cheat_safe { profile.keyboard.without_hooks() } else { profile.keyboard.clone() };
+    if !keyboard.is_empty() && !safe_mode {
+        plan.push(LockKeyboard(keyboard));
+    }
+    if profile.overlay_enabled && !safe_mode {
+        let (x_offset, y_offset) = profile.base_offset();
+        plan.push(StartCrosshair {
+            image_path: profile.crosshair_image_path.clone(),
+            x_offset,
+            y_offset,
+            options: OverlayOptions {
+                exclude_from_capture: profile.exclude_from_capture,
+                tint: profile.crosshair_tint,
+                note: profile.overlay_note.clone(),
+                anchor: profile.crosshair_anchor,
+                hud: profile.hardware_hud,
+            },
+        });
+    }
+
+    plan
+}
+
+struct KillProcesses {
+    names: Vec<String>,
+    /// Executables of the apps it closed, started again on revert
+    closed: Vec<PathBuf>,
+}
+
+impl Step<SystemState> for KillProcesses {
+    fn name(&self) -> &'static str {
+        "Processes"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let report = kill_processes_with(state.backends.processes.as_mut(), &self.names);
+        state.processes_killed = report.killed.len();
+        self.closed = report.closed_exes.clone();
+        let mut lines = Vec::new();
+        if !report.killed.is_empty() {
+            lines.push(format!("Killed: {}", report.killed.join(", ")));
+        }
+        if !report.failed.is_empty() {
+            lines.push(format!("Failed to close: {}", report.failed.join(", ")));
+        }
+        if !report.timed_out.is_empty() {
+            lines.push(format!("Timed out: {}", report.timed_out.join(", ")));
+        }
+        if !report.not_started.is_empty() {
+            lines.push(format!("Not tried (out of time): {}", report.not_started.join(", ")));
+        }
+        if !report.not_found.is_empty() {
+            lines.push(format!("Not running: {}", report.not_found.join(", ")));
+        }
+        if !report.blocklist_skipped.is_empty() {
+            lines.push(format!("Protected: {}", report.blocklist_skipped.join(", ")));
+        }
+        state.kill_report = Some(report);
+        Ok(lines)
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        let errors = relaunch_apps(state.backends.processes.as_mut(), &std::mem::take(&mut self.closed));
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!("Failed to restart {}", errors.join(", ")))
+        }
+    }
+}
+
+/// One-shot actions; each failure is reported on its own
+struct RunTweaks(Vec<SystemTweak>);
+
+impl Step<SystemState> for RunTweaks {
+    fn name(&self) -> &'static str {
+        "Tweaks"
+    }
+
+    fn apply(&mut self, _state: &mut SystemState) -> Result<Vec<String>> {
+        let report = tweaks::run_tweaks(&self.0);
+        let mut lines = Vec::new();
+        if !report.done.is_empty() {
+            lines.push(format!("🧹 {}", report.done.join(", ")));
+        }
+        for (tweak, e) in &report.failed {
+            lines.push(format!("{}: {}", tweak, e));
+        }
+        Ok(lines)
+    }
+}
+
+struct MaxFans;
+
+impl Step<SystemState> for MaxFans {
+    fn name(&self) -> &'static str {
+        "Fan"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let backend = state.fan.apply_max()?;
+        Ok(vec![format!("Fan: MAX ({})", backend)])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.fan.restore()
+    }
+}
+
+struct SetPowerPlan {
+    guid: String,
+    /// Display name from the installed plans
+    name: String,
+}
+
+impl Step<SystemState> for SetPowerPlan {
+    fn name(&self) -> &'static str {
+        "Power"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        state.power.apply(state.backends.power.as_mut(), &self.guid)?;
+        Ok(vec![format!("⚡ Power: {}", self.name)])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.power.restore(state.backends.power.as_mut())
+    }
+
+    fn essential(&self) -> bool {
+        true
+    }
+}
+
+struct ToggleGameFeatures(GameFeatureToggles);
+
+impl Step<SystemState> for ToggleGameFeatures {
+    fn name(&self) -> &'static str {
+        "Game features"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        state.game_features.apply(&self.0)?;
+        Ok(vec![format!(
+            "🎮 {} (restart affected apps to take effect)",
+            game_features::describe(&self.0).join(", ")
+        )])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.game_features.restore()
+    }
+}
+
+struct HighPrecisionTimer;
+
+impl Step<SystemState> for HighPrecisionTimer {
+    fn name(&self) -> &'static str {
+        "Timer"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let resolution = state.timer.apply()?;
+        Ok(vec![format!("⏱ Timer: {}", timer::format_100ns(resolution.current))])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.timer.restore()
+    }
+}
+
+struct BestPerformanceVisuals;
+
+impl Step<SystemState> for BestPerformanceVisuals {
+    fn name(&self) -> &'static str {
+        "Visual effects"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let changed = state.visual_effects.apply()?;
+        Ok(vec![format!("🪶 Visual effects off ({} settings)", changed)])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.visual_effects.restore()
+    }
+}
+
+struct SetDisplayMode(DisplaySetting);
+
+impl Step<SystemState> for SetDisplayMode {
+    fn name(&self) -> &'static str {
+        "Display"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        state.display.apply(&self.0)?;
+        Ok(vec![format!("🖥 {}", self.0.mode)])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.display.restore()
+    }
+}
+
+struct SetVolumes(AudioSettings);
+
+impl Step<SystemState> for SetVolumes {
+    fn name(&self) -> &'static str {
+        "Audio"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let report = state.audio.apply(&self.0)?;
+        let mut lines = Vec::new();
+        if !report.applied.is_empty() {
+            lines.push(format!("🔊 {}", report.applied.join(", ")));
+        }
+        if !report.not_playing.is_empty() {
+            lines.push(format!("No audio: {}", report.not_playing.join(", ")));
+        }
+        Ok(lines)
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.audio.restore()
+    }
+}
+
+struct ShapeNetwork(NetworkSettings);
+
+impl Step<SystemState> for ShapeNetwork {
+    fn name(&self) -> &'static str {
+        "Network"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        // Every network rule stops a service or adds a QoS policy
+        if let Err(e) = state.backends.services.check_access() {
+            return Ok(vec![format!("Network: {}", e)]);
+        }
+        let report = state.network.apply(&self.0, state.backends.services.as_mut())?;
+        let mut lines = Vec::new();
+        if !report.applied.is_empty() {
+            lines.push(format!("🌐 {}", report.applied.join(", ")));
+        }
+        if !report.failed.is_empty() {
+            lines.push(format!("Network: {}", report.failed.join("; ")));
+        }
+        Ok(lines)
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.network.restore(state.backends.services.as_mut())
+    }
+}
+
+struct PauseStorageWork(StorageSettings);
+
+impl Step<SystemState> for PauseStorageWork {
+    fn name(&self) -> &'static str {
+        "Storage"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        if let Err(e) = state.backends.services.check_access() {
+            return Ok(vec![format!("Storage: {}", e)]);
+        }
+        let report = state.storage.apply(&self.0, state.backends.services.as_mut())?;
+        let mut lines = Vec::new();
+        if !report.applied.is_empty() {
+            lines.push(format!("💽 {}", report.applied.join(", ")));
+        }
+        if !report.failed.is_empty() {
+            lines.push(format!("Storage: {}", report.failed.join("; ")));
+        }
+        Ok(lines)
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.storage.restore(state.backends.services.as_mut())
+    }
+}
+
+struct SetRgbScene {
+    config: OpenRgbConfig,
+    scene: RgbScene,
+}
+
+impl Step<SystemState> for SetRgbScene {
+    fn name(&self) -> &'static str {
+        "RGB"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let summary = state.rgb.apply(&self.config, &self.scene)?;
+        Ok(vec![format!("💡 {}", summary)])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.rgb.restore()
+    }
+}
+
+struct SetObsScene {
+    config: ObsConfig,
+    settings: ObsSettings,
+}
+
+impl Step<SystemState> for SetObsScene {
+    fn name(&self) -> &'static str {
+        "OBS"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let summary = state.obs.apply(&self.config, &self.settings)?;
+        Ok(vec![format!("🎥 OBS: {}", summary)])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.obs.restore()
+    }
+}
+
+struct SortCaptures {
+    profile: String,
+    settings: CaptureSettings,
+}
+
+impl Step<SystemState> for SortCaptures {
+    fn name(&self) -> &'static str {
+        "Captures"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let sorter = CaptureSorter::start(&self.profile, &self.settings)?;
+        let applied = vec![format!("📸 Captures go to {}", sorter.destination)];
+        state.captures = Some(sorter);
+        Ok(applied)
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.captures = None;
+        Ok(())
+    }
+}
+
+struct EnableDoNotDisturb {
+    mute_sounds: bool,
+}
+
+impl Step<SystemState> for EnableDoNotDisturb {
+    fn name(&self) -> &'static str {
+        "Do Not Disturb"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        state.do_not_disturb.enable(self.mute_sounds)?;
+        Ok(vec!["🔕 Do Not Disturb".to_string()])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.do_not_disturb.restore()
+    }
+}
+
+struct ApplyGpuSettings(GpuSettings);
+
+impl Step<SystemState> for ApplyGpuSettings {
+    fn name(&self) -> &'static str {
+        "GPU"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let applied = state.gpu.apply(&self.0)?;
+        if applied.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(vec![format!("🎮 GPU: {}", applied.join(", "))])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.gpu.restore()
+    }
+}
+
+struct LockKeyboard(KeyboardSettings);
+
+impl Step<SystemState> for LockKeyboard {
+    fn name(&self) -> &'static str {
+        "Keyboard"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let applied = state.keyboard.apply(&self.0)?;
+        if applied.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(vec![format!("⌨ {}", applied.join(", "))])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        state.keyboard.restore()
+    }
+}
+
+struct StartCrosshair {
+    image_path: Option<String>,
+    x_offset: i32,
+    y_offset: i32,
+    options: OverlayOptions,
+}
+
+impl Step<SystemState> for StartCrosshair {
+    fn name(&self) -> &'static str {
+        "Crosshair"
+    }
+
+    fn apply(&mut self, state: &mut SystemState) -> Result<Vec<String>> {
+        let running = state.overlay.take().or_else(|| state.parked_overlay.take());
+        let Some(path) = self.image_path.clone() else {
+            if let Some(handle) = running {
+                handle.stop();
+            }
+            return Err(anyhow!("No image"));
+        };
+        if let Some(mut handle) = running {
+            if handle.swap(&path, self.x_offset, self.y_offset, &self.options) {
+                state.overlay = Some(handle);
+                return Ok(vec!["🎯 Crosshair ON".to_string()]);
+            }
+            handle.stop();
+        }
+        let handle = state.backends.overlay.start(path, self.x_offset, self.y_offset, &self.options)
+            .map_err(|e| anyhow!(e))?;
+        state.overlay = Some(handle);
+        Ok(vec!["🎯 Crosshair ON".to_string()])
+    }
+
+    fn revert(&mut self, state: &mut SystemState) -> Result<()> {
+        if let Some(handle) = state.overlay.take() {
+            handle.stop();
+        }
+        Ok(())
+    }
+
+    // A profile without a picked image just shows no crosshair
+    fn essential(&self) -> bool {
+        self.image_path.is_some()
+    }
+}
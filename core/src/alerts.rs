@@ -0,0 +1,289 @@
+//! Temperature alerts
+//!
+//! Compares hardware monitor readings against CPU/GPU temperature limits (global, with
+//! per-profile overrides). Crossing a limit shows a tray balloon once and can max the fans or
+//! switch to a less demanding profile; the alert re-arms after the temperature has dropped
+//! `HYSTERESIS_C` below the limit.
+
+use crate::monitor::HardwareSnapshot;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::{info, warn};
+
+/// Degrees below the limit a sensor has to cool down to before it can alert again
+pub const HYSTERESIS_C: f32 = 5.0;
+
+/// Temperature limits in degrees Celsius (None = no alert for that sensor)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct AlertThresholds {
+    pub cpu_temp_c: Option<f32>,
+    pub gpu_temp_c: Option<f32>,
+}
+
+impl AlertThresholds {
+    /// These limits, with the unset ones taken from `global`
+    pub fn or(self, global: AlertThresholds) -> AlertThresholds {
+        AlertThresholds {
+            cpu_temp_c: self.cpu_temp_c.or(global.cpu_temp_c),
+            gpu_temp_c: self.gpu_temp_c.or(global.gpu_temp_c),
+        }
+    }
+}
+
+/// Alert settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AlertConfig {
+    pub enabled: bool,
+    pub thresholds: AlertThresholds,
+    /// Force the fans to max when an alert fires (restored on deactivation)
+    pub max_fans: bool,
+    /// Profile to switch to when an alert fires (e.g. one with a frame cap or a quieter plan)
+    pub switch_to_profile: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlertSensor {
+    Cpu,
+    Gpu,
+}
+
+impl fmt::Display for AlertSensor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertSensor::Cpu => write!(f, "CPU"),
+            AlertSensor::Gpu => write!(f, "GPU"),
+        }
+    }
+}
+
+/// A limit that was just crossed
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemperatureAlert {
+    pub sensor: AlertSensor,
+    pub temp_c: f32,
+    pub limit_c: f32,
+}
+
+impl TemperatureAlert {
+    /// e.g. "GPU at 89°C (limit 87°C)"
+    pub fn message(&self) -> String {
+        format!("{} at {:.0}°C (limit {:.0}°C)", self.sensor, self.temp_c, self.limit_c)
+    }
+}
+
+/// Remembers which sensors have alerted so each crossing is reported once
+#[derive(Default)]
+pub struct AlertTracker {
+    cpu_fired: bool,
+    gpu_fired: bool,
+}
+
+impl AlertTracker {
+    /// Alerts newly raised by this snapshot
+    pub fn check(&mut self, snapshot: &HardwareSnapshot, thresholds: &AlertThresholds) -> Vec<TemperatureAlert> {
+        let mut alerts = Vec::new();
+        let sensors = [
+            (AlertSensor::Cpu, snapshot.cpu_temp, thresholds.cpu_temp_c, &mut self.cpu_fired),
+            (AlertSensor::Gpu, snapshot.gpu_temp, thresholds.gpu_temp_c, &mut self.gpu_fired),
+        ];
+
+        for (sensor, temp, limit, fired) in sensors {
+            let Some(limit_c) = limit else {
+                *fired = false;
+                continue;
+            };
+            // A missing reading keeps the current state
+            let Some(temp_c) = temp else { continue };
+
+            if !*fired && temp_c > limit_c {
+                *fired = true;
+                alerts.push(TemperatureAlert { sensor, temp_c, limit_c });
+            } else if *fired && temp_c < limit_c - HYSTERESIS_C {
+                *fired = false;
+            }
+        }
+        alerts
+    }
+
+    /// Some sensor is still above (or not yet cooled down from) its limit
+    pub fn any_fired(&self) -> bool {
+        self.cpu_fired || self.gpu_fired
+    }
+
+    /// Forget fired alerts (e.g. after the thresholds changed)
+    pub fn reset(&mut self) {
+        *self = AlertTracker::default();
+    }
+}
+
+/// Warning icon in the notification area that shows alert balloons. The icon stays while an
+/// alert is active and is removed by `clear` (or on drop).
+#[cfg(windows)]
+pub struct AlertBalloon {
+    window: windows::Win32::Foundation::HWND,
+    shown: bool,
+}
+
+#[cfg(windows)]
+impl AlertBalloon {
+    /// Icon id; the window is our own, so it only has to be unique per window
+    const ICON_ID: u32 = 1;
+
+    pub fn new() -> Self {
+        use windows::core::w;
+        use windows::Win32::UI::WindowsAndMessaging::{CreateWindowExW, HWND_MESSAGE, WINDOW_EX_STYLE, WINDOW_STYLE};
+
+        // Message-only window to own the icon; the predefined STATIC class needs no registration
+        let window = unsafe {
+            CreateWindowExW(
+                WINDOW_EX_STYLE::default(),
+                w!("STATIC"),
+                w!("Gaming Optimizer alerts"),
+                WINDOW_STYLE::default(),
+                0,
+                0,
+                0,
+                0,
+                HWND_MESSAGE,
+                None,
+                None,
+                None,
+            )
+        };
+        AlertBalloon { window, shown: false }
+    }
+
+    /// Show `text` in a balloon (adds the icon the first time)
+    pub fn show(&mut self, title: &str, text: &str) {
+        use windows::Win32::UI::Shell::{
+            Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_TIP, NIIF_WARNING, NIM_ADD, NIM_MODIFY, NOTIFYICONDATAW,
+        };
+        use windows::Win32::UI::WindowsAndMessaging::{LoadIconW, IDI_WARNING};
+
+        if self.window.0 == 0 {
+            info!("[Alerts] {}: {}", title, text);
+            return;
+        }
+
+        let mut data = NOTIFYICONDATAW {
+            cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+            hWnd: self.window,
+            uID: Self::ICON_ID,
+            uFlags: NIF_ICON | NIF_TIP | NIF_INFO,
+            hIcon: unsafe { LoadIconW(None, IDI_WARNING) }.unwrap_or_default(),
+            dwInfoFlags: NIIF_WARNING,
+            ..Default::default()
+        };
+        copy_wide(&mut data.szTip, &format!("Gaming Optimizer: {}", text));
+        copy_wide(&mut data.szInfoTitle, title);
+        copy_wide(&mut data.szInfo, text);
+
+        let action = if self.shown { NIM_MODIFY } else { NIM_ADD };
+        if unsafe { Shell_NotifyIconW(action, &data) }.as_bool() {
+            self.shown = true;
+        } else {
+            warn!("[Alerts] Failed to show the alert balloon: {}: {}", title, text);
+        }
+    }
+
+    /// Remove the warning icon
+    pub fn clear(&mut self) {
+        use windows::Win32::UI::Shell::{Shell_NotifyIconW, NIM_DELETE, NOTIFYICONDATAW};
+
+        if !self.shown {
+            return;
+        }
+        let data = NOTIFYICONDATAW {
+            cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+            hWnd: self.window,
+            uID: Self::ICON_ID,
+            ..Default::default()
+        };
+        unsafe {
+            let _ = Shell_NotifyIconW(NIM_DELETE, &data);
+        }
+        self.shown = false;
+    }
+}
+
+#[cfg(windows)]
+impl Default for AlertBalloon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(windows)]
+impl Drop for AlertBalloon {
+    fn drop(&mut self) {
+        self.clear();
+        if self.window.0 != 0 {
+            unsafe {
+                let _ = windows::Win32::UI::WindowsAndMessaging::DestroyWindow(self.window);
+            }
+        }
+    }
+}
+
+/// Copy `text` into a fixed-size, NUL-terminated UTF-16 buffer, truncating if needed
+#[cfg(windows)]
+fn copy_wide(buffer: &mut [u16], text: &str) {
+    let wide: Vec<u16> = text.encode_utf16().take(buffer.len() - 1).collect();
+    buffer[..wide.len()].copy_from_slice(&wide);
+    buffer[wide.len()] = 0;
+}
+
+#[cfg(not(windows))]
+pub struct AlertBalloon;
+
+#[cfg(not(windows))]
+impl AlertBalloon {
+    pub fn new() -> Self {
+        AlertBalloon
+    }
+
+    pub fn show(&mut self, title: &str, text: &str) {
+        info!("[Alerts] {}: {}", title, text);
+    }
+
+    pub fn clear(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temps(cpu: Option<f32>, gpu: Option<f32>) -> HardwareSnapshot {
+        HardwareSnapshot { cpu_temp: cpu, gpu_temp: gpu, ..Default::default() }
+    }
+
+    #[test]
+    fn test_profile_overrides_global_thresholds() {
+        let global = AlertThresholds { cpu_temp_c: Some(90.0), gpu_temp_c: Some(87.0) };
+        let profile = AlertThresholds { cpu_temp_c: None, gpu_temp_c: Some(80.0) };
+        assert_eq!(profile.or(global), AlertThresholds { cpu_temp_c: Some(90.0), gpu_temp_c: Some(80.0) });
+    }
+
+    #[test]
+    fn test_alert_fires_once_and_rearms() {
+        let thresholds = AlertThresholds { cpu_temp_c: None, gpu_temp_c: Some(87.0) };
+        let mut tracker = AlertTracker::default();
+
+        assert!(tracker.check(&temps(Some(95.0), Some(80.0)), &thresholds).is_empty());
+
+        let alerts = tracker.check(&temps(None, Some(89.0)), &thresholds);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].message(), "GPU at 89°C (limit 87°C)");
+
+        // Still hot, then cooled a little, then a missing reading: no repeat
+        assert!(tracker.check(&temps(None, Some(90.0)), &thresholds).is_empty());
+        assert!(tracker.check(&temps(None, Some(84.0)), &thresholds).is_empty());
+        assert!(tracker.check(&temps(None, None), &thresholds).is_empty());
+        assert!(tracker.any_fired());
+
+        // Cooled below the hysteresis band, so the next crossing alerts again
+        assert!(tracker.check(&temps(None, Some(81.0)), &thresholds).is_empty());
+        assert!(!tracker.any_fired());
+        assert_eq!(tracker.check(&temps(None, Some(88.0)), &thresholds).len(), 1);
+    }
+}
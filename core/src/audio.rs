@@ -0,0 +1,274 @@
+//! Per-profile audio levels
+//!
+//! Sets the master volume of the default playback device and the volume of individual
+//! applications' audio sessions (e.g. Discord at 40%, the game at 100%) through the Windows
+//! Core Audio API, and puts the previous levels back on deactivation.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Volume for every audio session owned by a process
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AppVolume {
+    /// Executable name, e.g. "Discord.exe"
+    pub process: String,
+    /// 0-100
+    pub percent: u8,
+}
+
+/// Audio levels applied while a profile is active
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AudioSettings {
+    /// Master volume of the default playback device, 0-100 (None = leave it alone)
+    #[serde(default)]
+    pub master_percent: Option<u8>,
+    #[serde(default)]
+    pub app_volumes: Vec<AppVolume>,
+}
+
+impl AudioSettings {
+    /// Whether any level is set to be changed
+    pub fn is_empty(&self) -> bool {
+        self.master_percent.is_none() && self.app_volumes.is_empty()
+    }
+}
+
+/// What `AudioSwitcher::apply` changed
+#[derive(Debug, Default)]
+pub struct AudioReport {
+    /// e.g. "Master 80%", "Discord.exe 40%"
+    pub applied: Vec<String>,
+    /// Configured apps that had no audio session
+    pub not_playing: Vec<String>,
+}
+
+/// Whether a process name matches a configured app (case-insensitive, ".exe" optional)
+fn matches_process(process_name: &str, configured: &str) -> bool {
+    let strip = |name: &str| {
+        let name = name.trim().to_lowercase();
+        name.strip_suffix(".exe").map(str::to_string).unwrap_or(name)
+    };
+    strip(process_name) == strip(configured)
+}
+
+fn to_scalar(percent: u8) -> f32 {
+    percent.min(100) as f32 / 100.0
+}
+
+#[cfg(windows)]
+mod core_audio {
+    use anyhow::{anyhow, Result};
+    use windows::core::ComInterface;
+    use windows::Win32::Media::Audio::Endpoints::IAudioEndpointVolume;
+    use windows::Win32::Media::Audio::{
+        eConsole, eRender, IAudioSessionControl2, IAudioSessionManager2, IMMDevice,
+        IMMDeviceEnumerator, ISimpleAudioVolume, MMDeviceEnumerator,
+    };
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
+    };
+
+    /// Run `f` with COM initialized on this thread
+    pub fn with_com<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+        // Fails with RPC_E_CHANGED_MODE if the thread already uses another apartment,
+        // in which case COM is usable but must not be uninitialized by us
+        let initialized = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }.is_ok();
+        let result = f();
+        if initialized {
+            unsafe { CoUninitialize() };
+        }
+        result
+    }
+
+    fn default_device() -> Result<IMMDevice> {
+        unsafe {
+            let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)
+                .map_err(|e| anyhow!("Failed to open audio devices: {}", e))?;
+            enumerator
+                .GetDefaultAudioEndpoint(eRender, eConsole)
+                .map_err(|e| anyhow!("No default playback device: {}", e))
+        }
+    }
+
+    pub fn endpoint_volume() -> Result<IAudioEndpointVolume> {
+        unsafe {
+            default_device()?
+                .Activate(CLSCTX_ALL, None)
+                .map_err(|e| anyhow!("Failed to open master volume: {}", e))
+        }
+    }
+
+    /// (process id, volume control) for every audio session on the default device
+    pub fn sessions() -> Result<Vec<(u32, ISimpleAudioVolume)>> {
+        unsafe {
+            let manager: IAudioSessionManager2 = default_device()?
+                .Activate(CLSCTX_ALL, None)
+                .map_err(|e| anyhow!("Failed to open audio sessions: {}", e))?;
+            let enumerator = manager
+                .GetSessionEnumerator()
+                .map_err(|e| anyhow!("Failed to list audio sessions: {}", e))?;
+
+            let mut sessions = Vec::new();
+            for index in 0..enumerator.GetCount().unwrap_or(0) {
+                let Ok(control) = enumerator.GetSession(index) else {
+                    continue;
+                };
+                let (Ok(control2), Ok(volume)) = (
+                    control.cast::<IAudioSessionControl2>(),
+                    control.cast::<ISimpleAudioVolume>(),
+                ) else {
+                    continue;
+                };
+                // The system sounds session has process id 0
+                if let Ok(pid) = control2.GetProcessId() {
+                    if pid != 0 {
+                        sessions.push((pid, volume));
+                    }
+                }
+            }
+            Ok(sessions)
+        }
+    }
+}
+
+/// Applies a profile's audio levels and remembers the ones they replaced
+#[derive(Default)]
+pub struct AudioSwitcher {
+    /// Master volume scalar before `apply`
+    previous_master: Option<f32>,
+    /// Session volume scalars before `apply`, by process id
+    previous_apps: Vec<(u32, f32)>,
+}
+
+impl AudioSwitcher {
+    /// Restore any previous profile's levels, then apply the new ones
+    pub fn apply(&mut self, settings: &AudioSettings) -> Result<AudioReport> {
+        self.restore()?;
+        if settings.is_empty() {
+            return Ok(AudioReport::default());
+        }
+
+        #[cfg(windows)]
+        {
+            core_audio::with_com(|| {
+                let mut report = AudioReport::default();
+
+                if let Some(percent) = settings.master_percent {
+                    let endpoint = core_audio::endpoint_volume()?;
+                    unsafe {
+                        let previous = endpoint
+                            .GetMasterVolumeLevelScalar()
+                            .map_err(|e| anyhow!("Failed to read master volume: {}", e))?;
+                        endpoint
+                            .SetMasterVolumeLevelScalar(to_scalar(percent), std::ptr::null())
+                            .map_err(|e| anyhow!("Failed to set master volume: {}", e))?;
+                        self.previous_master = Some(previous);
+                    }
+                    report.applied.push(format!("Master {}%", percent));
+                }
+
+                if !settings.app_volumes.is_empty() {
+                    let names: std::collections::HashMap<u32, String> = crate::process::list_processes()
+                        .into_iter()
+                        .map(|p| (p.pid, p.name))
+                        .collect();
+                    let sessions = core_audio::sessions()?;
+
+                    for app in &settings.app_volumes {
+                        let mut found = false;
+                        for (pid, volume) in &sessions {
+                            let Some(name) = names.get(pid) else {
+                                continue;
+                            };
+                            if !matches_process(name, &app.process) {
+                                continue;
+                            }
+                            unsafe {
+                                let Ok(previous) = volume.GetMasterVolume() else {
+                                    continue;
+                                };
+                                if volume.SetMasterVolume(to_scalar(app.percent), std::ptr::null()).is_ok() {
+                                    if !self.previous_apps.iter().any(|(saved, _)| saved == pid) {
+                                        self.previous_apps.push((*pid, previous));
+                                    }
+                                    found = true;
+                                }
+                            }
+                        }
+
+                        if found {
+                            report.applied.push(format!("{} {}%", app.process, app.percent));
+                        } else {
+                            report.not_playing.push(app.process.clone());
+                        }
+                    }
+                }
+
+                info!("[Audio] Applied {}", report.applied.join(", "));
+                Ok(report)
+            })
+        }
+
+        #[cfg(not(windows))]
+        Err(anyhow!("Audio control is only available on Windows"))
+    }
+
+    /// Put back the levels replaced by `apply` (no-op if nothing was changed)
+    pub fn restore(&mut self) -> Result<()> {
+        if self.previous_master.is_none() && self.previous_apps.is_empty() {
+            return Ok(());
+        }
+        let previous_master = self.previous_master.take();
+        let previous_apps = std::mem::take(&mut self.previous_apps);
+
+        #[cfg(windows)]
+        {
+            core_audio::with_com(|| {
+                if let Some(level) = previous_master {
+                    let endpoint = core_audio::endpoint_volume()?;
+                    unsafe { endpoint.SetMasterVolumeLevelScalar(level, std::ptr::null()) }
+                        .map_err(|e| anyhow!("Failed to restore master volume: {}", e))?;
+                }
+
+                // Apps that closed in the meantime simply have no session left
+                if !previous_apps.is_empty() {
+                    for (pid, volume) in core_audio::sessions()? {
+                        if let Some((_, level)) = previous_apps.iter().find(|(saved, _)| *saved == pid) {
+                            let _ = unsafe { volume.SetMasterVolume(*level, std::ptr::null()) };
+                        }
+                    }
+                }
+
+                info!("[Audio] Restored previous volume levels");
+                Ok(())
+            })
+        }
+
+        #[cfg(not(windows))]
+        {
+            let _ = (previous_master, previous_apps);
+            Err(anyhow!("Audio control is only available on Windows"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_process() {
+        assert!(matches_process("Discord.exe", "discord.exe"));
+        assert!(matches_process("Discord.exe", "Discord"));
+        assert!(matches_process("cs2.exe", " CS2.EXE "));
+        assert!(!matches_process("DiscordPTB.exe", "Discord.exe"));
+    }
+
+    #[test]
+    fn test_to_scalar_clamps() {
+        assert_eq!(to_scalar(40), 0.4);
+        assert_eq!(to_scalar(100), 1.0);
+        assert_eq!(to_scalar(150), 1.0);
+    }
+}
@@ -0,0 +1,93 @@
+//! Start with Windows
+//!
+//! Registers the app to start at logon with `--minimized`, so it goes straight to the tray.
+//! Normal mode uses the HKCU Run key; elevated mode uses a logon scheduled task with highest
+//! privileges instead, since Run entries can't start elevated without a UAC prompt (creating
+//! that task needs the app itself to be running as administrator).
+
+use crate::config::{load_config, save_config};
+use crate::registry::{self, Hive};
+use crate::shell::run_hidden;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::info;
+
+/// Command line flag for a logon start: no window, just the tray icon
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+const RUN_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+const RUN_VALUE: &str = "GamingOptimizer";
+const TASK_NAME: &str = "GamingOptimizer";
+
+/// Auto-start settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AutostartConfig {
+    pub enabled: bool,
+    /// Start through a scheduled task with highest privileges instead of the Run key
+    pub elevated: bool,
+}
+
+/// What Windows runs at logon
+fn launch_command(exe: &Path, extra_args: &[&str]) -> String {
+    let mut command = format!("\"{}\" {}", exe.display(), MINIMIZED_ARG);
+    for arg in extra_args {
+        command.push(' ');
+        command.push_str(arg);
+    }
+    command
+}
+
+fn create_task_args(command: &str) -> Vec<&str> {
+    vec!["/Create", "/TN", TASK_NAME, "/TR", command, "/SC", "ONLOGON", "/RL", "HIGHEST", "/F"]
+}
+
+fn task_exists() -> bool {
+    run_hidden("schtasks.exe", &["/Query", "/TN", TASK_NAME]).is_ok()
+}
+
+/// Make the Run key and scheduled task match `config`
+pub fn apply(config: &AutostartConfig) -> Result<()> {
+    let exe = std::env::current_exe().map_err(|e| anyhow!("Failed to locate the executable: {}", e))?;
+    let command = launch_command(&exe, &crate::config::relaunch_args());
+
+    if config.enabled && !config.elevated {
+        registry::write_string(Hive::CurrentUser, RUN_KEY, RUN_VALUE, &command)?;
+    } else {
+        registry::delete_value(Hive::CurrentUser, RUN_KEY, RUN_VALUE)?;
+    }
+
+    if config.enabled && config.elevated {
+        run_hidden("schtasks.exe", &create_task_args(&command))
+            .map_err(|e| anyhow!("Failed to create the startup task (run as administrator): {}", e))?;
+    } else if task_exists() {
+        run_hidden("schtasks.exe", &["/Delete", "/TN", TASK_NAME, "/F"])
+            .map_err(|e| anyhow!("Failed to remove the startup task (run as administrator): {}", e))?;
+    }
+
+    info!("[Autostart] Enabled: {}, elevated: {}", config.enabled, config.elevated);
+    Ok(())
+}
+
+/// Register or unregister, then store the setting. The stored setting only changes if Windows
+/// accepted it, so the switches never claim a state that isn't registered.
+pub fn save(config: &AutostartConfig) -> Result<()> {
+    apply(config)?;
+    let mut app_config = load_config();
+    app_config.autostart = config.clone();
+    save_config(&app_config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_command_quotes_path() {
+        let command = launch_command(Path::new("C:\\Program Files\\Gaming Optimizer\\gaming_optimizer.exe"), &[]);
+        assert_eq!(command, "\"C:\\Program Files\\Gaming Optimizer\\gaming_optimizer.exe\" --minimized");
+        assert_eq!(launch_command(Path::new("E:\\go.exe"), &["--portable"]), "\"E:\\go.exe\" --minimized --portable");
+        assert_eq!(&create_task_args(&command)[3..5], ["/TR", command.as_str()]);
+    }
+}
@@ -0,0 +1,295 @@
+//! Backup and restore of all app data
+//!
+//! One zip with profiles.json, config.json (settings, hotkeys, tray clicks) and the crosshair
+//! library plus any other image a profile uses. Image paths are stored as `crosshairs/<file>`
+//! inside the zip, so after a restore they point at the copies in the new data folder.
+
+use crate::config::AppConfig;
+use crate::profile::{load_profiles, save_profiles, Profile};
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const PROFILES_ENTRY: &str = "profiles.json";
+const CONFIG_ENTRY: &str = "config.json";
+/// Folder of the images in the zip, and of the crosshair library in the data folder
+const IMAGES_DIR: &str = "crosshairs";
+
+/// Contents of a backup zip
+pub struct Backup {
+    pub profiles: Vec<Profile>,
+    pub config: Option<AppConfig>,
+    /// File name in `crosshairs/` and the image bytes
+    images: BTreeMap<String, Vec<u8>>,
+}
+
+/// What `Backup::restore` did
+#[derive(Debug, Default, PartialEq)]
+pub struct RestoreSummary {
+    pub added: usize,
+    pub replaced: usize,
+    /// Profiles kept as they were because one with the same name already existed
+    pub kept: usize,
+    pub images: usize,
+}
+
+/// First 4 bytes of the SHA-256 (stable across toolchains, unlike `DefaultHasher`)
+fn short_hash(bytes: &[u8]) -> u32 {
+    let digest = Sha256::digest(bytes);
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// `name`, or `name` with a hash suffix if `taken` says a different image already has it
+fn free_name(name: &str, bytes: &[u8], taken: impl Fn(&str) -> Option<Vec<u8>>) -> String {
+    match taken(name) {
+        Some(existing) if existing != bytes => {
+            let path = Path::new(name);
+            let stem = path.file_stem().map_or("crosshair".into(), |stem| stem.to_string_lossy());
+            let extension = path.extension().map_or("png".into(), |ext| ext.to_string_lossy());
+            format!("{}_{:08x}.{}", stem, short_hash(bytes), extension)
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Write everything in `data_dir` to the zip at `target`; returns the backup that was written
+pub fn export(data_dir: &Path, target: &Path) -> Result<Backup> {
+    let mut profiles = load_profiles(data_dir)?;
+    let config = match fs::read_to_string(data_dir.join(CONFIG_ENTRY)) {
+        Ok(contents) => Some(serde_json::from_str(&contents).map_err(|e| anyhow!("Failed to parse config.json: {}", e))?),
+        Err(_) => None,
+    };
+
+    // The whole library, including images no profile uses right now
+    let mut images = BTreeMap::new();
+    if let Ok(entries) = fs::read_dir(data_dir.join(IMAGES_DIR)) {
+        for path in entries.flatten().map(|entry| entry.path()).filter(|path| path.is_file()) {
+            if let (Some(name), Ok(bytes)) = (path.file_name(), fs::read(&path)) {
+                images.insert(name.to_string_lossy().to_string(), bytes);
+            }
+        }
+    }
+
+    // Images from elsewhere go in too; a missing file keeps its old path
+    for path in profiles.iter_mut().flat_map(Profile::image_paths_mut) {
+        let Some(ref original) = *path else {
+            continue;
+        };
+        let (Some(name), Ok(bytes)) = (Path::new(original).file_name(), fs::read(original)) else {
+            continue;
+        };
+        let name = free_name(&name.to_string_lossy(), &bytes, |name| images.get(name).cloned());
+        *path = Some(format!("{}/{}", IMAGES_DIR, name));
+        images.insert(name, bytes);
+    }
+
+    let backup = Backup { profiles, config, images };
+    backup.write(target)?;
+    Ok(backup)
+}
+
+impl Backup {
+    /// Read a zip made by `export`
+    pub fn read(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+        let mut archive = ZipArchive::new(file).map_err(|e| anyhow!("Not a backup file: {}", e))?;
+
+        let mut profiles = None;
+        let mut config = None;
+        let mut images = BTreeMap::new();
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index).map_err(|e| anyhow!("Failed to read backup: {}", e))?;
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).map_err(|e| anyhow!("Failed to read {}: {}", entry.name(), e))?;
+
+            let name = entry.name().to_string();
+            if name == PROFILES_ENTRY {
+                profiles = Some(serde_json::from_slice(&bytes).map_err(|e| anyhow!("Failed to parse profiles.json: {}", e))?);
+            } else if name == CONFIG_ENTRY {
+                config = Some(serde_json::from_slice(&bytes).map_err(|e| anyhow!("Failed to parse config.json: {}", e))?);
+            } else if let Some(file_name) = name.strip_prefix(&format!("{}/", IMAGES_DIR)) {
+                // Only plain file names; nothing may end up outside the library
+                if !file_name.is_empty() && Path::new(file_name).file_name() == Some(file_name.as_ref()) {
+                    images.insert(file_name.to_string(), bytes);
+                }
+            }
+        }
+
+        let profiles = profiles.ok_or_else(|| anyhow!("The backup has no profiles.json"))?;
+        Ok(Backup { profiles, config, images })
+    }
+
+    fn write(&self, target: &Path) -> Result<()> {
+        let file = File::create(target).map_err(|e| anyhow!("Failed to create {}: {}", target.display(), e))?;
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let mut add = |name: &str, bytes: &[u8]| -> Result<()> {
+            zip.start_file(name, options).map_err(|e| anyhow!("Failed to write backup: {}", e))?;
+            zip.write_all(bytes).map_err(|e| anyhow!("Failed to write backup: {}", e))
+        };
+        let profiles = serde_json::to_vec_pretty(&self.profiles).map_err(|e| anyhow!("Failed to serialize profiles: {}", e))?;
+        add(PROFILES_ENTRY, &profiles)?;
+        if let Some(ref config) = self.config {
+            let config = serde_json::to_vec_pretty(config).map_err(|e| anyhow!("Failed to serialize config: {}", e))?;
+            add(CONFIG_ENTRY, &config)?;
+        }
+        for (name, bytes) in &self.images {
+            add(&format!("{}/{}", IMAGES_DIR, name), bytes)?;
+        }
+
+        zip.finish().map_err(|e| anyhow!("Failed to write backup: {}", e))?;
+        Ok(())
+    }
+
+    pub fn image_count(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Names of the backup's profiles that `existing` already has
+    pub fn conflicts(&self, existing: &[Profile]) -> Vec<String> {
+        self.profiles
+            .iter()
+            .filter(|profile| existing.iter().any(|p| p.name == profile.name))
+            .map(|profile| profile.name.clone())
+            .collect()
+    }
+
+    /// Restore into `data_dir` on top of `existing`. Profiles with a name that's taken replace
+    /// the existing ones with `replace_existing`, otherwise the existing ones stay. Images never
+    /// overwrite a different image; they get a suffix instead. The settings are replaced.
+    pub fn restore(self, data_dir: &Path, existing: &[Profile], replace_existing: bool) -> Result<RestoreSummary> {
+        let library = data_dir.join(IMAGES_DIR);
+        fs::create_dir_all(&library).map_err(|e| anyhow!("Failed to create crosshair folder: {}", e))?;
+
+        let mut restored_paths = BTreeMap::new();
+        for (name, bytes) in &self.images {
+            let target = library.join(free_name(name, bytes, |name| fs::read(library.join(name)).ok()));
+            fs::write(&target, bytes).map_err(|e| anyhow!("Failed to restore {}: {}", name, e))?;
+            restored_paths.insert(format!("{}/{}", IMAGES_DIR, name), target.to_string_lossy().to_string());
+        }
+
+        let mut summary = RestoreSummary { images: self.images.len(), ..RestoreSummary::default() };
+        let mut profiles = existing.to_vec();
+        for mut profile in self.profiles {
+            for path in profile.image_paths_mut() {
+                if let Some(restored) = path.as_ref().and_then(|path| restored_paths.get(path)) {
+                    *path = Some(restored.clone());
+                }
+            }
+            match profiles.iter().position(|p| p.name == profile.name) {
+                Some(index) if replace_existing => {
+                    profiles[index] = profile;
+                    summary.replaced += 1;
+                }
+                Some(_) => summary.kept += 1,
+                None => {
+                    profiles.push(profile);
+                    summary.added += 1;
+                }
+            }
+        }
+        save_profiles(&profiles, data_dir)?;
+
+        if let Some(mut config) = self.config {
+            // Nothing is applied on this machine yet
+            config.active_profile = None;
+            let json = serde_json::to_string_pretty(&config).map_err(|e| anyhow!("Failed to serialize config: {}", e))?;
+            fs::write(data_dir.join(CONFIG_ENTRY), json).map_err(|e| anyhow!("Failed to write config.json: {}", e))?;
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::{create_profile, CrosshairPreset};
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("gaming_optimizer_backup_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_export_and_restore() {
+        let old = temp_dir("old");
+        let new = temp_dir("new");
+        let zip_path = old.join("backup.zip");
+
+        // Old machine: one image in the library, one on the desktop
+        fs::create_dir_all(old.join(IMAGES_DIR)).unwrap();
+        let library_image = old.join(IMAGES_DIR).join("dot.png");
+        fs::write(&library_image, b"dot").unwrap();
+        let desktop_image = old.join("cross.png");
+        fs::write(&desktop_image, b"cross").unwrap();
+
+        let mut fps = create_profile("FPS".to_string());
+        fps.crosshair_image_path = Some(library_image.to_string_lossy().to_string());
+        fps.crosshair_presets.push(CrosshairPreset {
+            name: "Sniper".to_string(),
+            image_path: Some(desktop_image.to_string_lossy().to_string()),
+            x_offset: 0,
+            y_offset: 0,
+            tint: None,
+        });
+        let mut moba = create_profile("MOBA".to_string());
+        moba.processes_to_kill = vec!["Discord.exe".to_string()];
+        save_profiles(&[fps, moba], &old).unwrap();
+        let config = AppConfig { active_profile: Some("FPS".to_string()), ..AppConfig::default() };
+        fs::write(old.join(CONFIG_ENTRY), serde_json::to_string(&config).unwrap()).unwrap();
+
+        let exported = export(&old, &zip_path).unwrap();
+        assert_eq!(exported.image_count(), 2);
+
+        // New machine: its own MOBA profile and a different dot.png
+        fs::create_dir_all(new.join(IMAGES_DIR)).unwrap();
+        fs::write(new.join(IMAGES_DIR).join("dot.png"), b"other dot").unwrap();
+        let existing = vec![create_profile("MOBA".to_string())];
+
+        let backup = Backup::read(&zip_path).unwrap();
+        assert_eq!(backup.conflicts(&existing), vec!["MOBA".to_string()]);
+        let summary = backup.restore(&new, &existing, false).unwrap();
+        assert_eq!(summary, RestoreSummary { added: 1, replaced: 0, kept: 1, images: 2 });
+
+        let restored = load_profiles(&new).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert!(restored[0].processes_to_kill.is_empty());
+        // The different dot.png already there is left alone
+        let dot = restored[1].crosshair_image_path.clone().unwrap();
+        assert!(dot.starts_with(&*new.join(IMAGES_DIR).to_string_lossy()));
+        assert_ne!(Path::new(&dot), new.join(IMAGES_DIR).join("dot.png"));
+        assert_eq!(fs::read(&dot).unwrap(), b"dot");
+        let cross = restored[1].crosshair_presets[0].image_path.clone().unwrap();
+        assert_eq!(fs::read(cross).unwrap(), b"cross");
+        assert_eq!(fs::read(new.join(IMAGES_DIR).join("dot.png")).unwrap(), b"other dot");
+
+        let restored_config: AppConfig = serde_json::from_str(&fs::read_to_string(new.join(CONFIG_ENTRY)).unwrap()).unwrap();
+        assert_eq!(restored_config.active_profile, None);
+
+        // Restoring again replaces instead, and finds the images already in place
+        let summary = Backup::read(&zip_path).unwrap().restore(&new, &restored, true).unwrap();
+        assert_eq!(summary, RestoreSummary { added: 0, replaced: 2, kept: 0, images: 2 });
+        let restored = load_profiles(&new).unwrap();
+        assert_eq!(restored[0].processes_to_kill, vec!["Discord.exe".to_string()]);
+        assert_eq!(restored[1].crosshair_image_path, Some(dot));
+
+        fs::remove_dir_all(&old).unwrap();
+        fs::remove_dir_all(&new).unwrap();
+    }
+
+    #[test]
+    fn test_free_name() {
+        assert_eq!(free_name("dot.png", b"a", |_| None), "dot.png");
+        assert_eq!(free_name("dot.png", b"a", |_| Some(b"a".to_vec())), "dot.png");
+        // sha256("a") starts with ca978112
+        assert_eq!(free_name("dot.png", b"a", |_| Some(b"b".to_vec())), "dot_ca978112.png");
+    }
+}
@@ -0,0 +1,208 @@
+//! Before/after benchmark of a profile
+//!
+//! Measures CPU load, free memory and the process count with no profile active, activates the
+//! profile, lets the system settle for a few minutes and measures again. Measurements run on
+//! background threads; the GUI polls the run and stores finished results in benchmarks.json
+//! next to the session stats.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::time::Duration;
+use sysinfo::System;
+
+/// CPU load samples averaged per measurement, one per second
+const SAMPLE_COUNT: u32 = 10;
+/// Time between activation and the second measurement
+pub const SETTLE_SECS: u64 = 180;
+
+/// System state at one point of the benchmark
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    /// Average total CPU load in percent
+    pub cpu_load: f32,
+    pub free_memory_mb: u64,
+    pub process_count: usize,
+}
+
+/// Sample the system for `SAMPLE_COUNT` seconds
+fn measure() -> Measurement {
+    let mut system = System::new();
+    system.refresh_cpu();
+
+    let mut total = 0.0;
+    for _ in 0..SAMPLE_COUNT {
+        std::thread::sleep(Duration::from_secs(1).max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL));
+        system.refresh_cpu();
+        total += system.global_cpu_info().cpu_usage();
+    }
+
+    system.refresh_memory();
+    system.refresh_processes();
+    Measurement {
+        cpu_load: total / SAMPLE_COUNT as f32,
+        free_memory_mb: system.available_memory() / (1024 * 1024),
+        process_count: system.processes().len(),
+    }
+}
+
+/// Take a measurement on a background thread after `delay`
+fn spawn_measurement(delay: Duration) -> Receiver<Measurement> {
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        std::thread::sleep(delay);
+        let _ = tx.send(measure());
+    });
+    rx
+}
+
+/// A finished benchmark as stored in benchmarks.json
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BenchmarkResult {
+    pub profile: String,
+    /// Unix time (seconds) the baseline was taken
+    pub started: u64,
+    pub before: Measurement,
+    pub after: Measurement,
+    /// Seconds between activation and the second measurement
+    pub settle_secs: u64,
+}
+
+impl BenchmarkResult {
+    /// "CPU load: 23% → 12% (-11 pts)" etc.
+    pub fn comparison_lines(&self) -> Vec<String> {
+        let (before, after) = (&self.before, &self.after);
+        vec![
+            format!(
+                "CPU load: {:.0}% → {:.0}% ({:+.0} pts)",
+                before.cpu_load,
+                after.cpu_load,
+                after.cpu_load - before.cpu_load
+            ),
+            format!(
+                "Free memory: {} MB → {} MB ({:+} MB)",
+                before.free_memory_mb,
+                after.free_memory_mb,
+                after.free_memory_mb as i64 - before.free_memory_mb as i64
+            ),
+            format!(
+                "Processes: {} → {} ({:+})",
+                before.process_count,
+                after.process_count,
+                after.process_count as i64 - before.process_count as i64
+            ),
+        ]
+    }
+}
+
+/// What a poll of the run found
+pub enum BenchmarkStage {
+    /// Baseline taken; activate the profile now
+    BaselineTaken,
+    Finished(BenchmarkResult),
+}
+
+/// Benchmark in progress
+pub struct BenchmarkRun {
+    profile: String,
+    started: u64,
+    before: Option<Measurement>,
+    measurement: Receiver<Measurement>,
+}
+
+impl BenchmarkRun {
+    /// Start measuring the baseline. The profile must not be active yet.
+    pub fn start(profile: &str) -> Self {
+        BenchmarkRun {
+            profile: profile.to_string(),
+            started: crate::sessions::unix_time(),
+            before: None,
+            measurement: spawn_measurement(Duration::ZERO),
+        }
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// The baseline is taken and the run waits for the system to settle
+    pub fn is_settling(&self) -> bool {
+        self.before.is_some()
+    }
+
+    /// Check for a finished measurement (call regularly from the GUI thread)
+    pub fn poll(&mut self) -> Result<Option<BenchmarkStage>> {
+        let measurement = match self.measurement.try_recv() {
+            Ok(measurement) => measurement,
+            Err(TryRecvError::Empty) => return Ok(None),
+            Err(TryRecvError::Disconnected) => return Err(anyhow!("Benchmark measurement stopped unexpectedly")),
+        };
+
+        match self.before {
+            None => {
+                self.before = Some(measurement);
+                self.measurement = spawn_measurement(Duration::from_secs(SETTLE_SECS));
+                Ok(Some(BenchmarkStage::BaselineTaken))
+            }
+            Some(before) => Ok(Some(BenchmarkStage::Finished(BenchmarkResult {
+                profile: self.profile.clone(),
+                started: self.started,
+                before,
+                after: measurement,
+                settle_secs: SETTLE_SECS,
+            }))),
+        }
+    }
+}
+
+/// Load finished benchmarks from benchmarks.json (empty if there are none yet)
+pub fn load_benchmarks(data_dir: &Path) -> Result<Vec<BenchmarkResult>> {
+    let benchmarks_path = data_dir.join("benchmarks.json");
+    if !benchmarks_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&benchmarks_path)
+        .map_err(|e| anyhow!("Failed to read benchmarks.json: {}", e))?;
+
+    serde_json::from_str(&contents)
+        .map_err(|e| anyhow!("Failed to parse benchmarks.json: {}", e))
+}
+
+/// Save all benchmarks to benchmarks.json
+pub fn save_benchmarks(benchmarks: &[BenchmarkResult], data_dir: &Path) -> Result<()> {
+    fs::create_dir_all(data_dir)
+        .map_err(|e| anyhow!("Failed to create data directory: {}", e))?;
+
+    let json = serde_json::to_string_pretty(benchmarks)
+        .map_err(|e| anyhow!("Failed to serialize benchmarks: {}", e))?;
+
+    fs::write(data_dir.join("benchmarks.json"), json)
+        .map_err(|e| anyhow!("Failed to write benchmarks.json: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparison_lines() {
+        let result = BenchmarkResult {
+            profile: "FPS".to_string(),
+            started: 0,
+            before: Measurement { cpu_load: 23.4, free_memory_mb: 6100, process_count: 212 },
+            after: Measurement { cpu_load: 12.2, free_memory_mb: 7400, process_count: 187 },
+            settle_secs: SETTLE_SECS,
+        };
+        assert_eq!(
+            result.comparison_lines(),
+            [
+                "CPU load: 23% → 12% (-11 pts)",
+                "Free memory: 6100 MB → 7400 MB (+1300 MB)",
+                "Processes: 212 → 187 (-25)",
+            ]
+        );
+    }
+}
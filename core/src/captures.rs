@@ -0,0 +1,213 @@
+//! Sort new screenshots and clips into a folder per profile
+//!
+//! While a profile with sorting on is active, the Windows screenshots folder (Win+PrtScn,
+//! Snipping Tool) and the Game Bar captures folder are watched, and captures that appear there
+//! are moved into a subfolder named after the profile: next to them, or in the profile's target
+//! folder. A file is only moved once it has been quiet for `SETTLE`, so a clip that is still
+//! being recorded stays where it is until it's finished.
+
+use anyhow::{anyhow, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Screenshot and video files that are moved
+pub const EXTENSIONS: [&str; 10] = ["png", "jpg", "jpeg", "bmp", "gif", "webp", "jxr", "mp4", "mkv", "webm"];
+
+/// Quiet time after the last write before a capture counts as finished
+const SETTLE: Duration = Duration::from_secs(2);
+/// Times a capture that can't be moved (still open in the recorder) is tried again
+const MAX_ATTEMPTS: u32 = 30;
+
+/// Where a profile's captures go
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct CaptureSettings {
+    /// Move new captures into a folder named after the profile while it's active
+    pub sort: bool,
+    /// Folder the profile's folder is made in (None = the folder the capture landed in)
+    pub target_folder: Option<String>,
+}
+
+/// Screenshots and Game Bar captures folders that exist on this PC
+pub fn capture_folders() -> Vec<PathBuf> {
+    let Some(dirs) = directories::UserDirs::new() else {
+        return Vec::new();
+    };
+    [dirs.picture_dir().map(|dir| dir.join("Screenshots")), dirs.video_dir().map(|dir| dir.join("Captures"))]
+        .into_iter()
+        .flatten()
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn is_capture(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| EXTENSIONS.iter().any(|known| ext.eq_ignore_ascii_case(known)))
+}
+
+/// `profile` as a folder name (characters Windows doesn't allow become "_")
+pub fn folder_name(profile: &str) -> String {
+    let name: String = profile
+        .chars()
+        .map(|c| if c.is_control() || r#"<>:"/\|?*"#.contains(c) { '_' } else { c })
+        .collect();
+    let name = name.trim().trim_end_matches('.').trim_end();
+    if name.is_empty() {
+        "Profile".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// `folder`/`file_name`, or "name (2).ext", "name (3).ext", ... when that is taken
+fn unique_destination(folder: &Path, file_name: &str) -> PathBuf {
+    let path = folder.join(file_name);
+    if !path.exists() {
+        return path;
+    }
+    let (stem, ext) = match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{}", ext)),
+        _ => (file_name, String::new()),
+    };
+    (2..)
+        .map(|n| folder.join(format!("{} ({}){}", stem, n, ext)))
+        .find(|path| !path.exists())
+        .unwrap_or(path)
+}
+
+/// Move `file` into `folder` (made if missing); where it ended up
+fn move_capture(file: &Path, folder: &Path) -> Result<PathBuf> {
+    let file_name = file.file_name().and_then(|name| name.to_str()).ok_or_else(|| anyhow!("Invalid file name: {}", file.display()))?;
+    fs::create_dir_all(folder).map_err(|e| anyhow!("Failed to create {}: {}", folder.display(), e))?;
+    let destination = unique_destination(folder, file_name);
+    match fs::rename(file, &destination) {
+        Ok(()) => return Ok(destination),
+        // ERROR_NOT_SAME_DEVICE / EXDEV: renaming can't cross drives, so it's copied over
+        Err(e) if e.raw_os_error() == Some(if cfg!(windows) { 17 } else { 18 }) => {}
+        // Most likely still open in the recorder
+        Err(e) => return Err(anyhow!("Failed to move {}: {}", file.display(), e)),
+    }
+
+    fs::copy(file, &destination).map_err(|e| anyhow!("Failed to move {}: {}", file.display(), e))?;
+    if let Err(e) = fs::remove_file(file) {
+        let _ = fs::remove_file(&destination);
+        return Err(anyhow!("Failed to move {}: {}", file.display(), e));
+    }
+    Ok(destination)
+}
+
+/// Watches the capture folders and sorts new captures until dropped
+pub struct CaptureSorter {
+    _watcher: RecommendedWatcher,
+    /// Where the captures go, for the activation report
+    pub destination: String,
+}
+
+impl CaptureSorter {
+    pub fn start(profile: &str, settings: &CaptureSettings) -> Result<Self> {
+        let folders = capture_folders();
+        if folders.is_empty() {
+            return Err(anyhow!("No screenshots or captures folder found"));
+        }
+        let name = folder_name(profile);
+        let target = settings.target_folder.as_ref().map(PathBuf::from);
+        let destination = match target {
+            Some(ref target) => target.join(&name).display().to_string(),
+            None => format!(r"Screenshots\{0}, Captures\{0}", name),
+        };
+
+        let (tx, rx) = channel::<PathBuf>();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| match result {
+            Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                for path in event.paths.into_iter().filter(|path| is_capture(path)) {
+                    let _ = tx.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("[Captures] {}", e),
+        })
+        .map_err(|e| anyhow!("Failed to create file watcher: {}", e))?;
+        for folder in &folders {
+            watcher
+                .watch(folder, RecursiveMode::NonRecursive)
+                .map_err(|e| anyhow!("Failed to watch {}: {}", folder.display(), e))?;
+        }
+
+        std::thread::spawn(move || {
+            // Path -> last write and failed moves
+            let mut pending: HashMap<PathBuf, (Instant, u32)> = HashMap::new();
+            loop {
+                // Dropping the watcher closes the channel
+                match rx.recv_timeout(SETTLE) {
+                    Ok(path) => {
+                        let attempts = pending.get(&path).map_or(0, |(_, attempts)| *attempts);
+                        pending.insert(path, (Instant::now(), attempts));
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+
+                let now = Instant::now();
+                let settled: Vec<PathBuf> = pending
+                    .iter()
+                    .filter(|(_, (last, _))| now.duration_since(*last) >= SETTLE)
+                    .map(|(path, _)| path.clone())
+                    .collect();
+                for path in settled {
+                    let Some((_, attempts)) = pending.remove(&path) else { continue };
+                    let Some(parent) = path.parent().filter(|_| path.is_file()) else { continue };
+                    let folder = target.as_deref().unwrap_or(parent).join(&name);
+                    match move_capture(&path, &folder) {
+                        Ok(moved) => info!("[Captures] Moved {} to {}", path.display(), moved.display()),
+                        Err(e) if attempts + 1 < MAX_ATTEMPTS => {
+                            pending.insert(path, (now, attempts + 1));
+                            debug!("[Captures] {} (trying again)", e);
+                        }
+                        Err(e) => warn!("[Captures] {}", e),
+                    }
+                }
+            }
+        });
+
+        info!("[Captures] Sorting new captures into {}", destination);
+        Ok(CaptureSorter { _watcher: watcher, destination })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_name() {
+        assert_eq!(folder_name("Valorant"), "Valorant");
+        assert_eq!(folder_name("CS2: Competitive / 4:3"), "CS2_ Competitive _ 4_3");
+        assert_eq!(folder_name("Apex... "), "Apex");
+        assert_eq!(folder_name(" ?? "), "__");
+        assert_eq!(folder_name(". "), "Profile");
+    }
+
+    #[test]
+    fn test_move_capture() {
+        let dir = std::env::temp_dir().join(format!("go_captures_{}", std::process::id()));
+        let folder = dir.join("Valorant");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("Clip.mp4"), "old").unwrap();
+        fs::write(dir.join("Clip.mp4"), "new").unwrap();
+        fs::write(dir.join("Screenshot"), "").unwrap();
+
+        assert_eq!(move_capture(&dir.join("Clip.mp4"), &folder).unwrap(), folder.join("Clip (2).mp4"));
+        assert_eq!(fs::read_to_string(folder.join("Clip (2).mp4")).unwrap(), "new");
+        assert!(!dir.join("Clip.mp4").exists());
+        assert_eq!(unique_destination(&folder, "Clip.mp4"), folder.join("Clip (3).mp4"));
+        assert_eq!(unique_destination(&folder, "Screenshot"), folder.join("Screenshot"));
+        assert!(is_capture(Path::new("Screenshot 2024-05-01.PNG")));
+        assert!(!is_capture(&dir.join("Screenshot")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,636 @@
+//! Command-line control
+//!
+//! `gaming_optimizer activate <profile>`, `deactivate`, `list`, `kill [--dry-run]`,
+//! `overlay on|off` and `quit` for scripts, Stream Deck buttons and scheduled tasks. Commands go
+//! to the running instance over a named pipe (one JSON line each way); without one they run on
+//! their own where that makes sense, and `activate` starts the app with the profile.
+
+use crate::config::get_data_directory;
+use crate::ipc::{ConnectionLimit, RequestSender};
+use crate::process::{self, KillReport, LiveProcesses, ProcessBackend};
+use crate::profile::{load_profiles, Profile};
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::Sender;
+use tracing::{info, warn};
+
+/// Pipe the running instance listens on
+const PIPE_NAME: &str = r"\\.\pipe\GamingOptimizer";
+/// Wait before reopening the pipe after the server failed
+const PIPE_RESTART_DELAY_SECS: u64 = 5;
+/// Clients served at once; more are told to try again
+const MAX_PIPE_CLIENTS: usize = 8;
+
+#[derive(Parser, Debug)]
+#[command(name = "gaming_optimizer", version, about = "Game profiles, crosshair overlay and system tweaks")]
+pub struct Cli {
+    /// Run the profile manager window with its tray icon (the default)
+    #[arg(long, conflicts_with_all = ["tray_only", "headless"])]
+    pub gui: bool,
+
+    /// Run only the tray icon, without the profile manager window
+    #[arg(long, conflicts_with = "headless")]
+    pub tray_only: bool,
+
+    /// Run without any window or tray icon, controlled through commands and the remote API
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Start in the tray without opening the window (used when starting with Windows)
+    #[arg(long)]
+    pub minimized: bool,
+
+    /// Keep config and profiles next to the exe instead of %APPDATA% (e.g. on a USB stick)
+    #[arg(long, global = true)]
+    pub portable: bool,
+
+    /// Recover from a bad profile: no hotkeys, file watching, remote API, keyboard hook,
+    /// crosshair or automatic activation
+    #[arg(long)]
+    pub safe_mode: bool,
+
+    /// Profile to activate once the window is up (crash restarts and `activate`)
+    #[arg(long = "restore-profile", value_name = "PROFILE", hide = true)]
+    pub restore_profile: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+/// How the app runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppMode {
+    Gui,
+    TrayOnly,
+    Headless,
+}
+
+impl Cli {
+    pub fn mode(&self) -> AppMode {
+        if self.headless {
+            AppMode::Headless
+        } else if self.tray_only {
+            AppMode::TrayOnly
+        } else {
+            AppMode::Gui
+        }
+    }
+}
+
+#[derive(Subcommand, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum CliCommand {
+    /// Activate a profile
+    Activate { profile: String },
+    /// Deactivate the active profile and undo its changes
+    Deactivate,
+    /// List profiles (the active one is marked with *)
+    List,
+    /// Close the processes on a profile's kill list
+    Kill {
+        /// Profile whose kill list to use (default: the active profile)
+        profile: Option<String>,
+        /// Only show what would be closed
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Bring the profile manager window to the front
+    Show,
+    /// Show the active profile and live stats
+    Status,
+    /// Show or hide the crosshair overlay of the active profile
+    Overlay {
+        #[arg(value_enum)]
+        state: OverlayState,
+    },
+    /// Exit the running instance (the exit setting decides whether the profile is undone)
+    Quit,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverlayState {
+    On,
+    Off,
+}
+
+/// Result of a command, printed by the CLI
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CliReply {
+    pub ok: bool,
+    pub message: String,
+    /// Structured result for API clients (profile list, live status)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl CliReply {
+    pub fn ok(message: impl Into<String>) -> Self {
+        CliReply { ok: true, message: message.into(), data: None }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        CliReply { ok: false, message: message.into(), data: None }
+    }
+
+    pub fn with_data(mut self, data: impl Serialize) -> Self {
+        self.data = serde_json::to_value(data).ok();
+        self
+    }
+}
+
+/// Entry of the `list` result
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProfileEntry {
+    pub name: String,
+    pub active: bool,
+}
+
+pub fn profile_entries(profiles: &[Profile], active: Option<&str>) -> Vec<ProfileEntry> {
+    profiles
+        .iter()
+        .map(|profile| ProfileEntry { name: profile.name.clone(), active: active == Some(profile.name.as_str()) })
+        .collect()
+}
+
+/// Result of `status`; None = no reading (or nothing active)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct LiveStatus {
+    pub active_profile: Option<String>,
+    pub overlay_visible: bool,
+    /// Game the current session is following
+    pub game: Option<String>,
+    pub cpu_load: Option<f32>,
+    pub gpu_load: Option<f32>,
+    pub cpu_temp: Option<f32>,
+    pub gpu_temp: Option<f32>,
+    pub ping_ms: Option<f32>,
+}
+
+impl LiveStatus {
+    /// e.g. "FPS active, crosshair on | CPU 34% 61°C | GPU 97% 72°C | Ping 23 ms"
+    pub fn summary(&self) -> String {
+        let percent = |value: Option<f32>| value.map(|v| format!(" {:.0}%", v)).unwrap_or_default();
+        let degrees = |value: Option<f32>| value.map(|v| format!(" {:.0}°C", v)).unwrap_or_default();
+
+        let mut parts = vec![match self.active_profile {
+            Some(ref name) => format!(
+                "{} active, crosshair {}",
+                name,
+                if self.overlay_visible { "on" } else { "off" }
+            ),
+            None => "No profile active".to_string(),
+        }];
+        if self.cpu_load.is_some() || self.cpu_temp.is_some() {
+            parts.push(format!("CPU{}{}", percent(self.cpu_load), degrees(self.cpu_temp)));
+        }
+        if self.gpu_load.is_some() || self.gpu_temp.is_some() {
+            parts.push(format!("GPU{}{}", percent(self.gpu_load), degrees(self.gpu_temp)));
+        }
+        if let Some(ping) = self.ping_ms {
+            parts.push(format!("Ping {:.0} ms", ping));
+        }
+        parts.join(" | ")
+    }
+}
+
+/// A command received over the pipe or the remote API, answered through `reply`
+pub struct CliRequest {
+    /// Numbers requests in the logs
+    pub id: u64,
+    pub command: CliCommand,
+    pub reply: Sender<CliReply>,
+}
+
+/// One line per profile, "* " in front of the active one
+pub fn format_profile_list(profiles: &[Profile], active: Option<&str>) -> String {
+    if profiles.is_empty() {
+        return "No profiles".to_string();
+    }
+    profiles
+        .iter()
+        .map(|profile| {
+            let marker = if active == Some(profile.name.as_str()) { "* " } else { "  " };
+            format!("{}{}", marker, profile.name)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Run `kill` for `profile` (or the active one) through `processes` and describe the outcome
+pub fn kill_for_profile(
+    processes: &mut dyn ProcessBackend,
+    profiles: &[Profile],
+    profile: Option<&str>,
+    active: Option<&str>,
+    dry_run: bool,
+) -> CliReply {
+    let Some(name) = profile.or(active) else {
+        return CliReply::error("No profile given and none is active");
+    };
+    let Some(profile) = profiles.iter().find(|p| p.name == name) else {
+        return CliReply::error(format!("No profile named '{}'", name));
+    };
+
+    let report = if dry_run {
+        process::preview_kill_with(processes, &profile.processes_to_kill)
+    } else {
+        process::kill_processes_with(processes, &profile.processes_to_kill)
+    };
+    CliReply::ok(format_kill_report(&report, dry_run))
+}
+
+fn format_kill_report(report: &KillReport, dry_run: bool) -> String {
+    let mut lines = Vec::new();
+    let closed = if dry_run { "Would close" } else { "Closed" };
+    if report.killed.is_empty() {
+        lines.push(format!("{}: nothing", closed));
+    } else {
+        lines.push(format!("{}: {}", closed, report.killed.join(", ")));
+    }
+    if !report.failed.is_empty() {
+        lines.push(format!("Failed: {}", report.failed.join(", ")));
+    }
+    if !report.timed_out.is_empty() {
+        lines.push(format!("Timed out: {}", report.timed_out.join(", ")));
+    }
+    if !report.not_started.is_empty() {
+        lines.push(format!("Not tried (out of time): {}", report.not_started.join(", ")));
+    }
+    if !report.not_found.is_empty() {
+        lines.push(format!("Not running: {}", report.not_found.join(", ")));
+    }
+    if !report.blocklist_skipped.is_empty() {
+        lines.push(format!("Protected: {}", report.blocklist_skipped.join(", ")));
+    }
+    lines.join("\n")
+}
+
+/// Run a command and return the process exit code
+pub fn run(command: CliCommand) -> i32 {
+    let reply = match send_to_running_instance(&command) {
+        Ok(Some(reply)) => reply,
+        Ok(None) => run_one_shot(command),
+        Err(e) => CliReply::error(format!("Failed to talk to Gaming Optimizer: {}", e)),
+    };
+    print_reply(&reply)
+}
+
+/// Hand a command to the instance that is already running (used by a second launch).
+/// Never falls back to running it here, which could start yet another instance.
+pub fn forward(command: CliCommand) -> i32 {
+    let reply = match send_to_running_instance(&command) {
+        Ok(Some(reply)) => reply,
+        Ok(None) => CliReply::error("Gaming Optimizer is already running"),
+        Err(e) => CliReply::error(format!("Failed to talk to Gaming Optimizer: {}", e)),
+    };
+    print_reply(&reply)
+}
+
+fn print_reply(reply: &CliReply) -> i32 {
+    if reply.ok {
+        println!("{}", reply.message);
+        0
+    } else {
+        eprintln!("{}", reply.message);
+        1
+    }
+}
+
+/// Handle a command without a running instance
+fn run_one_shot(command: CliCommand) -> CliReply {
+    let profiles = match get_data_directory().and_then(|dir| load_profiles(&dir)) {
+        Ok(profiles) => profiles,
+        Err(e) => return CliReply::error(format!("Failed to load profiles: {}", e)),
+    };
+
+    match command {
+        CliCommand::List => {
+            CliReply::ok(format_profile_list(&profiles, None)).with_data(profile_entries(&profiles, None))
+        }
+        CliCommand::Kill { profile, dry_run } => {
+            kill_for_profile(&mut LiveProcesses, &profiles, profile.as_deref(), None, dry_run)
+        }
+        CliCommand::Activate { profile } => {
+            if !profiles.iter().any(|p| p.name == profile) {
+                return CliReply::error(format!("No profile named '{}'", profile));
+            }
+            // The app has to keep running to undo the profile later, so start it
+            let started = std::env::current_exe()
+                .and_then(|exe| {
+                    std::process::Command::new(exe)
+                        .args(crate::config::relaunch_args())
+                        .args([crate::crash::RESTORE_PROFILE_ARG, &profile])
+                        .spawn()
+                });
+            match started {
+                Ok(_) => CliReply::ok(format!("Started Gaming Optimizer with '{}'", profile)),
+                Err(e) => CliReply::error(format!("Failed to start Gaming Optimizer: {}", e)),
+            }
+        }
+        CliCommand::Deactivate | CliCommand::Show | CliCommand::Status | CliCommand::Overlay { .. } | CliCommand::Quit => {
+            CliReply::error("Gaming Optimizer isn't running")
+        }
+    }
+}
+
+/// Send `command` to the running instance (Ok(None) if there is none)
+#[cfg(windows)]
+fn send_to_running_instance(command: &CliCommand) -> Result<Option<CliReply>> {
+    use std::io::{ErrorKind, Read, Write};
+
+    const ERROR_PIPE_BUSY: i32 = 231;
+
+    // All instances may be busy for a moment
+    let mut attempts = 0;
+    let mut pipe = loop {
+        match std::fs::OpenOptions::new().read(true).write(true).open(PIPE_NAME) {
+            Ok(pipe) => break pipe,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) && attempts < 20 => {
+                attempts += 1;
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+            Err(e) => return Err(anyhow!("Failed to open pipe: {}", e)),
+        }
+    };
+
+    let request = serde_json::to_string(command).map_err(|e| anyhow!("Failed to serialize command: {}", e))?;
+    writeln!(pipe, "{}", request).map_err(|e| anyhow!("Failed to send command: {}", e))?;
+
+    let mut response = String::new();
+    pipe.read_to_string(&mut response).map_err(|e| anyhow!("Failed to read reply: {}", e))?;
+    serde_json::from_str(&response)
+        .map(Some)
+        .map_err(|e| anyhow!("Failed to parse reply: {}", e))
+}
+
+#[cfg(not(windows))]
+fn send_to_running_instance(_command: &CliCommand) -> Result<Option<CliReply>> {
+    Ok(None)
+}
+
+/// Listen for CLI commands on the named pipe and pass them to `requests` (read by the running
+/// mode). The server is reopened if it fails, so the CLI doesn't silently stop reaching the app.
+pub fn spawn_pipe_server(requests: RequestSender) {
+    std::thread::spawn(move || {
+        while let Err(e) = serve_pipe(&requests) {
+            warn!("[CLI] Pipe server stopped: {}", e);
+            if !cfg!(windows) {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_secs(PIPE_RESTART_DELAY_SECS));
+        }
+    });
+}
+
+/// SID of the user the app runs as, e.g. "S-1-5-21-..."
+#[cfg(windows)]
+fn current_user_sid() -> Result<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::{CloseHandle, LocalFree, HANDLE, HLOCAL};
+    use windows::Win32::Security::Authorization::ConvertSidToStringSidW;
+    use windows::Win32::Security::{GetTokenInformation, TokenUser, TOKEN_QUERY, TOKEN_USER};
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    unsafe {
+        let mut token = HANDLE::default();
+        OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token)
+            .map_err(|e| anyhow!("Failed to open the process token: {}", e))?;
+        let mut size = 0u32;
+        let _ = GetTokenInformation(token, TokenUser, None, 0, &mut size);
+        // u64s keep the TOKEN_USER aligned
+        let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+        let result = GetTokenInformation(token, TokenUser, Some(buffer.as_mut_ptr() as *mut _), size, &mut size);
+        let _ = CloseHandle(token);
+        result.map_err(|e| anyhow!("Failed to read the process user: {}", e))?;
+
+        let user = &*(buffer.as_ptr() as *const TOKEN_USER);
+        let mut sid = PWSTR::null();
+        ConvertSidToStringSidW(user.User.Sid, &mut sid).map_err(|e| anyhow!("Failed to format the user SID: {}", e))?;
+        let text = sid.to_string();
+        let _ = LocalFree(HLOCAL(sid.0 as _));
+        text.map_err(|e| anyhow!("Failed to format the user SID: {}", e))
+    }
+}
+
+/// Security descriptor that only lets the current user open the pipe, so processes of other
+/// users can't send commands; freed when dropped
+#[cfg(windows)]
+struct UserOnlyDescriptor(windows::Win32::Security::PSECURITY_DESCRIPTOR);
+
+#[cfg(windows)]
+impl UserOnlyDescriptor {
+    fn new() -> Result<Self> {
+        use windows::core::HSTRING;
+        use windows::Win32::Security::Authorization::{
+            ConvertStringSecurityDescriptorToSecurityDescriptorW, SDDL_REVISION_1,
+        };
+        use windows::Win32::Security::PSECURITY_DESCRIPTOR;
+
+        // Protected DACL with one entry: full access for the user
+        let sddl = HSTRING::from(format!("D:P(A;;GA;;;{})", current_user_sid()?));
+        let mut descriptor = PSECURITY_DESCRIPTOR::default();
+        unsafe { ConvertStringSecurityDescriptorToSecurityDescriptorW(&sddl, SDDL_REVISION_1, &mut descriptor, None) }
+            .map_err(|e| anyhow!("Failed to create the pipe's security descriptor: {}", e))?;
+        Ok(UserOnlyDescriptor(descriptor))
+    }
+}
+
+#[cfg(windows)]
+impl Drop for UserOnlyDescriptor {
+    fn drop(&mut self) {
+        use windows::Win32::Foundation::{LocalFree, HLOCAL};
+
+        let _ = unsafe { LocalFree(HLOCAL(self.0 .0)) };
+    }
+}
+
+/// Open an instance of the pipe. The first one fails if the name is already taken, so a
+/// process that created it before the app can't receive the CLI's commands.
+#[cfg(windows)]
+fn create_pipe_instance(descriptor: &UserOnlyDescriptor, first: bool) -> Result<windows::Win32::Foundation::HANDLE> {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::{FALSE, INVALID_HANDLE_VALUE};
+    use windows::Win32::Security::SECURITY_ATTRIBUTES;
+    use windows::Win32::Storage::FileSystem::{FILE_FLAGS_AND_ATTRIBUTES, FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX};
+    use windows::Win32::System::Pipes::{
+        CreateNamedPipeW, PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES,
+        PIPE_WAIT,
+    };
+
+    let attributes = SECURITY_ATTRIBUTES {
+        nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+        lpSecurityDescriptor: descriptor.0 .0,
+        bInheritHandle: FALSE,
+    };
+    let first_flag = if first { FILE_FLAG_FIRST_PIPE_INSTANCE } else { FILE_FLAGS_AND_ATTRIBUTES(0) };
+    let handle = unsafe {
+        CreateNamedPipeW(
+            &HSTRING::from(PIPE_NAME),
+            PIPE_ACCESS_DUPLEX | first_flag,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            PIPE_UNLIMITED_INSTANCES,
+            4096,
+            4096,
+            0,
+            Some(&attributes),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        return Err(anyhow!("Failed to create pipe: {}", std::io::Error::last_os_error()));
+    }
+    Ok(handle)
+}
+
+#[cfg(windows)]
+fn serve_pipe(requests: &RequestSender) -> Result<()> {
+    use std::fs::File;
+    use std::os::windows::io::FromRawHandle;
+    use windows::Win32::Foundation::ERROR_PIPE_CONNECTED;
+    use windows::Win32::System::Pipes::ConnectNamedPipe;
+
+    let descriptor = UserOnlyDescriptor::new()?;
+    let limit = ConnectionLimit::new(MAX_PIPE_CLIENTS);
+    let mut next = create_pipe_instance(&descriptor, true)?;
+    info!("[CLI] Listening on {}", PIPE_NAME);
+    loop {
+        let handle = next;
+        // A client that connected before this call is reported as ERROR_PIPE_CONNECTED
+        let connected = match unsafe { ConnectNamedPipe(handle, None) } {
+            Ok(()) => true,
+            Err(e) => e.code() == ERROR_PIPE_CONNECTED.to_hresult(),
+        };
+        // The File owns the handle from here and closes it (ending the client's read)
+        let mut pipe = unsafe { File::from_raw_handle(handle.0 as _) };
+
+        // Open the next instance first so clients never find the pipe missing and fall back
+        // to running the command on their own
+        next = create_pipe_instance(&descriptor, false)?;
+        if !connected {
+            continue;
+        }
+
+        // Each client gets a thread of its own, so one that connects and never sends a line
+        // doesn't hold up the commands after it
+        let Some(slot) = limit.try_acquire() else {
+            send_reply(&mut pipe, &CliReply::error("Too many commands at once, try again"));
+            continue;
+        };
+        let requests = requests.clone();
+        std::thread::spawn(move || {
+            let _slot = slot;
+            serve_client(pipe, &requests);
+        });
+    }
+}
+
+/// Read one command from a connected client and send back the reply
+#[cfg(windows)]
+fn serve_client(mut pipe: std::fs::File, requests: &RequestSender) {
+    use std::io::{BufRead, BufReader};
+
+    let mut line = String::new();
+    if BufReader::new(&pipe).read_line(&mut line).is_err() {
+        return;
+    }
+
+    let reply = match serde_json::from_str::<CliCommand>(line.trim()) {
+        Ok(command) => {
+            info!("[CLI] {:?}", command);
+            requests.request(command)
+        }
+        Err(e) => CliReply::error(format!("Invalid command: {}", e)),
+    };
+    send_reply(&mut pipe, &reply);
+}
+
+#[cfg(windows)]
+fn send_reply(pipe: &mut std::fs::File, reply: &CliReply) {
+    use std::io::Write;
+
+    if let Ok(json) = serde_json::to_string(reply) {
+        let _ = pipe.write_all(json.as_bytes());
+        // Wait for the client to read the reply before the handle is closed
+        let _ = pipe.sync_all();
+    }
+}
+
+#[cfg(not(windows))]
+fn serve_pipe(_requests: &RequestSender) -> Result<()> {
+    let _ = (PIPE_NAME, ConnectionLimit::new(MAX_PIPE_CLIENTS));
+    Err(anyhow!("CLI control is only available on Windows"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str) -> Profile {
+        crate::profile::create_profile(name.to_string())
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let cli = Cli::try_parse_from(["gaming_optimizer", "kill", "FPS", "--dry-run"]).unwrap();
+        assert_eq!(cli.command, Some(CliCommand::Kill { profile: Some("FPS".to_string()), dry_run: true }));
+
+        let cli = Cli::try_parse_from(["gaming_optimizer", "overlay", "off"]).unwrap();
+        assert_eq!(cli.command, Some(CliCommand::Overlay { state: OverlayState::Off }));
+
+        let cli = Cli::try_parse_from(["gaming_optimizer", "--tray-only"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.mode(), AppMode::TrayOnly);
+        assert_eq!(Cli::try_parse_from(["gaming_optimizer", "--headless"]).unwrap().mode(), AppMode::Headless);
+        assert_eq!(Cli::try_parse_from(["gaming_optimizer"]).unwrap().mode(), AppMode::Gui);
+        assert!(Cli::try_parse_from(["gaming_optimizer", "--gui", "--headless"]).is_err());
+
+        assert!(Cli::try_parse_from(["gaming_optimizer", "activate"]).is_err());
+    }
+
+    #[test]
+    fn test_command_round_trips_over_the_pipe_format() {
+        let command = CliCommand::Activate { profile: "FPS".to_string() };
+        let json = serde_json::to_string(&command).unwrap();
+        assert_eq!(serde_json::from_str::<CliCommand>(&json).unwrap(), command);
+    }
+
+    #[test]
+    fn test_format_profile_list_marks_active() {
+        let profiles = [profile("FPS"), profile("Strategy")];
+        assert_eq!(format_profile_list(&profiles, Some("Strategy")), "  FPS\n* Strategy");
+        assert_eq!(format_profile_list(&[], None), "No profiles");
+    }
+
+    #[test]
+    fn test_format_kill_report() {
+        let report = KillReport {
+            killed: vec!["discord.exe".to_string()],
+            failed: Vec::new(),
+            not_found: vec!["steam.exe".to_string()],
+            blocklist_skipped: Vec::new(),
+            timed_out: Vec::new(),
+            not_started: Vec::new(),
+            closed_exes: Vec::new(),
+        };
+        assert_eq!(format_kill_report(&report, true), "Would close: discord.exe\nNot running: steam.exe");
+    }
+
+    #[test]
+    fn test_kill_dry_run_closes_nothing() {
+        let mut processes = process::FakeProcesses::new(&[("Discord.exe", 10)]);
+        let mut fps = profile("FPS");
+        fps.processes_to_kill = vec!["discord.exe".to_string(), "steam.exe".to_string()];
+        let profiles = [fps];
+
+        let reply = kill_for_profile(&mut processes, &profiles, None, Some("FPS"), true);
+        assert!(reply.ok);
+        assert_eq!(reply.message, "Would close: discord.exe\nNot running: steam.exe");
+        assert_eq!(processes.running(), ["Discord.exe"]);
+
+        let reply = kill_for_profile(&mut processes, &profiles, Some("FPS"), None, false);
+        assert_eq!(reply.message, "Closed: discord.exe\nNot running: steam.exe");
+        assert!(processes.running().is_empty());
+
+        assert!(!kill_for_profile(&mut processes, &profiles, None, None, true).ok);
+    }
+}
@@ -0,0 +1,134 @@
+//! Common applications selector for process management
+//!
+//! The list ships as resources/common_apps.json. A `common_apps.json` in the data directory
+//! (same format) adds apps or replaces bundled entries with the same executable, so the list can
+//! grow without a new build.
+
+use crate::i18n::t;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+/// Built-in list
+const BUNDLED: &str = include_str!("../resources/common_apps.json");
+
+/// User additions in the data directory
+pub const USER_FILE: &str = "common_apps.json";
+
+/// Group an app is listed under in the process selector (in display order)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppCategory {
+    Launchers,
+    Chat,
+    Streaming,
+    Media,
+    Browsers,
+    Cloud,
+    Updaters,
+    Rgb,
+    Security,
+    Development,
+    System,
+    /// Unknown categories and processes that aren't common apps
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+impl fmt::Display for AppCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            AppCategory::Launchers => t!("app-category-launchers"),
+            AppCategory::Chat => t!("app-category-chat"),
+            AppCategory::Streaming => t!("app-category-streaming"),
+            AppCategory::Media => t!("app-category-media"),
+            AppCategory::Browsers => t!("app-category-browsers"),
+            AppCategory::Cloud => t!("app-category-cloud"),
+            AppCategory::Updaters => t!("app-category-updaters"),
+            AppCategory::Rgb => t!("app-category-rgb"),
+            AppCategory::Security => t!("app-category-security"),
+            AppCategory::Development => t!("app-category-development"),
+            AppCategory::System => t!("app-category-system"),
+            AppCategory::Other => t!("app-category-other"),
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommonApp {
+    pub name: String,
+    pub executable: String,
+    #[serde(default)]
+    pub category: AppCategory,
+}
+
+fn parse(contents: &str) -> Result<Vec<CommonApp>> {
+    serde_json::from_str(contents).map_err(|e| anyhow!("Failed to parse common apps: {}", e))
+}
+
+/// Add `extra` to `apps`; an entry for an executable already listed replaces it
+fn merge(apps: &mut Vec<CommonApp>, extra: Vec<CommonApp>) {
+    for app in extra {
+        match apps.iter_mut().find(|known| known.executable.eq_ignore_ascii_case(&app.executable)) {
+            Some(known) => *known = app,
+            None => apps.push(app),
+        }
+    }
+}
+
+/// Bundled apps plus the user's `common_apps.json` from `data_dir` (skipped with a warning if
+/// it can't be read)
+pub fn load_common_apps(data_dir: Option<&Path>) -> Vec<CommonApp> {
+    let mut apps = parse(BUNDLED).unwrap_or_else(|e| {
+        warn!("[Apps] Bundled list: {}", e);
+        Vec::new()
+    });
+    let Some(path) = data_dir.map(|dir| dir.join(USER_FILE)).filter(|path| path.exists()) else {
+        return apps;
+    };
+    match fs::read_to_string(&path).map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e)).and_then(|contents| parse(&contents)) {
+        Ok(extra) => merge(&mut apps, extra),
+        Err(e) => warn!("[Apps] {}", e),
+    }
+    apps
+}
+
+pub fn find_app_by_executable<'a>(apps: &'a [CommonApp], executable: &str) -> Option<&'a CommonApp> {
+    apps.iter().find(|app| app.executable.eq_ignore_ascii_case(executable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_apps() {
+        let apps = parse(BUNDLED).unwrap();
+        assert!(apps.len() > 30);
+        assert!(apps.iter().all(|app| app.category != AppCategory::Other));
+        assert_eq!(find_app_by_executable(&apps, "discord.exe").map(|app| app.category), Some(AppCategory::Chat));
+    }
+
+    #[test]
+    fn test_merge_user_apps() {
+        let mut apps = parse(BUNDLED).unwrap();
+        let count = apps.len();
+        let extra = parse(r#"[
+            { "name": "Discord (PTB)", "executable": "DISCORD.exe", "category": "chat" },
+            { "name": "Wallpaper Engine", "executable": "wallpaper64.exe", "category": "desktop" },
+            { "name": "Overwolf", "executable": "Overwolf.exe" }
+        ]"#).unwrap();
+        merge(&mut apps, extra);
+
+        assert_eq!(apps.len(), count + 2);
+        assert_eq!(find_app_by_executable(&apps, "Discord.exe").unwrap().name, "Discord (PTB)");
+        // Unknown or missing categories end up under "Other"
+        assert_eq!(find_app_by_executable(&apps, "wallpaper64.exe").unwrap().category, AppCategory::Other);
+        assert_eq!(find_app_by_executable(&apps, "overwolf.exe").unwrap().category, AppCategory::Other);
+    }
+}
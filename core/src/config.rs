@@ -0,0 +1,504 @@
+use anyhow::{anyhow, Result};
+use directories::ProjectDirs;
+use crate::activation::ExitBehavior;
+use crate::alerts::AlertConfig;
+use crate::integrations::discord::DiscordConfig;
+use crate::integrations::event_log::EventLogConfig;
+use crate::plugins::PluginConfig;
+use crate::logging::LogLevel;
+use crate::migration::CURRENT_DATA_VERSION;
+use crate::toasts::ToastConfig;
+use crate::remote_api::ApiConfig;
+use crate::autostart::AutostartConfig;
+use crate::updater::UpdateConfig;
+use crate::hotkeys::HotkeyConfig;
+use crate::gamepad::GamepadConfig;
+use crate::process::SafetyConfig;
+use crate::theme::ThemeConfig;
+use crate::monitor::MonitorConfig;
+use crate::obs::ObsConfig;
+use crate::openrgb::OpenRgbConfig;
+use crate::profile::{ProfileUsage, PROFILE_FILES};
+use crate::tray_clicks::TrayClickConfig;
+use crate::tray_tooltip::TrayTooltipConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+/// Application configuration storing current state
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AppConfig {
+    /// Name of currently active profile (None = inactive)
+    pub active_profile: Option<String>,
+    /// Whether overlay is currently visible
+    pub overlay_visible: bool,
+    /// Whether the optimizer is paused
+    #[serde(default)]
+    pub paused: bool,
+    /// Bring back the active profile, overlay and pause state of the last run on start
+    #[serde(default)]
+    pub resume_last_session: bool,
+    /// Activate the profile named after a game when that game starts
+    #[serde(default)]
+    pub auto_activate_games: bool,
+    /// Size and position of the main window when it was last closed
+    #[serde(default)]
+    pub window: WindowLayout,
+    /// OpenRGB SDK server used for profile lighting
+    #[serde(default)]
+    pub openrgb: OpenRgbConfig,
+    /// obs-websocket server used for profile scenes and recording
+    #[serde(default)]
+    pub obs: ObsConfig,
+    /// Discord Rich Presence for the active profile
+    #[serde(default)]
+    pub discord: DiscordConfig,
+    /// Activation entries in the Windows Event Log
+    #[serde(default)]
+    pub event_log: EventLogConfig,
+    /// Plugin scripts that are enabled
+    #[serde(default)]
+    pub plugins: PluginConfig,
+    /// Hardware sensor sampling
+    #[serde(default)]
+    pub monitor: MonitorConfig,
+    /// Temperature alerts and what to do when they fire
+    #[serde(default)]
+    pub alerts: AlertConfig,
+    /// Most verbose level written to the log files
+    #[serde(default)]
+    pub log_level: LogLevel,
+    /// Which events raise a Windows notification
+    #[serde(default)]
+    pub toasts: ToastConfig,
+    /// Local HTTP/WebSocket remote control API
+    #[serde(default)]
+    pub api: ApiConfig,
+    /// Start at logon (Run key or elevated scheduled task)
+    #[serde(default)]
+    pub autostart: AutostartConfig,
+    /// Update checks (channel and interval)
+    #[serde(default)]
+    pub updates: UpdateConfig,
+    /// Global hotkey bindings
+    #[serde(default)]
+    pub hotkeys: HotkeyConfig,
+    /// Controller button combos
+    #[serde(default)]
+    pub gamepad: GamepadConfig,
+    /// Folder the profile file is kept in sync with (None = no sync)
+    #[serde(default)]
+    pub sync_folder: Option<PathBuf>,
+    /// Process closing safeguards
+    #[serde(default)]
+    pub safety: SafetyConfig,
+    /// Dark/light mode and accent color of the GUI
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    /// UI language tag such as "de" (None = Windows display language)
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Activation counts and times by profile name (favorites and recent profiles in the tray)
+    #[serde(default)]
+    pub profile_usage: HashMap<String, ProfileUsage>,
+    /// Actions of single, double and middle clicks on the tray icon
+    #[serde(default)]
+    pub tray_clicks: TrayClickConfig,
+    /// Tray tooltip template with live readings
+    #[serde(default)]
+    pub tray_tooltip: TrayTooltipConfig,
+    /// Whether exiting undoes the active profile, leaves it applied or asks
+    #[serde(default)]
+    pub exit_behavior: ExitBehavior,
+    /// Layout version of the data folder (0 = written before versions were recorded); see
+    /// `migration`
+    #[serde(default)]
+    pub data_version: u32,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            active_profile: None,
+            overlay_visible: false,
+            paused: false,
+            resume_last_session: false,
+            auto_activate_games: false,
+            window: WindowLayout::default(),
+            openrgb: OpenRgbConfig::default(),
+            obs: ObsConfig::default(),
+            discord: DiscordConfig::default(),
+            event_log: EventLogConfig::default(),
+            plugins: PluginConfig::default(),
+            monitor: MonitorConfig::default(),
+            alerts: AlertConfig::default(),
+            log_level: LogLevel::default(),
+            toasts: ToastConfig::default(),
+            api: ApiConfig::default(),
+            autostart: AutostartConfig::default(),
+            updates: UpdateConfig::default(),
+            hotkeys: HotkeyConfig::default(),
+            gamepad: GamepadConfig::default(),
+            sync_folder: None,
+            safety: SafetyConfig::default(),
+            theme: ThemeConfig::default(),
+            language: None,
+            profile_usage: HashMap::new(),
+            tray_clicks: TrayClickConfig::default(),
+            tray_tooltip: TrayTooltipConfig::default(),
+            exit_behavior: ExitBehavior::default(),
+            data_version: CURRENT_DATA_VERSION,
+        }
+    }
+}
+
+/// Main window size and position in logical pixels
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct WindowLayout {
+    pub width: f32,
+    pub height: f32,
+    /// Top-left corner (None = let Windows place the window)
+    #[serde(default)]
+    pub position: Option<(i32, i32)>,
+}
+
+impl Default for WindowLayout {
+    fn default() -> Self {
+        WindowLayout { width: 1000.0, height: 750.0, position: None }
+    }
+}
+
+/// What was running, as remembered for `resume_last_session`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SessionState {
+    pub active_profile: Option<String>,
+    pub overlay_visible: bool,
+    pub paused: bool,
+}
+
+impl AppConfig {
+    pub fn session_state(&self) -> SessionState {
+        SessionState {
+            active_profile: self.active_profile.clone(),
+            overlay_visible: self.overlay_visible,
+            paused: self.paused,
+        }
+    }
+}
+
+/// Remember what's running for the next start
+pub fn save_session_state(state: &SessionState) -> Result<()> {
+    let mut config = load_config();
+    config.active_profile = state.active_profile.clone();
+    config.overlay_visible = state.overlay_visible;
+    config.paused = state.paused;
+    save_config(&config)
+}
+
+/// Command line flag for portable mode
+pub const PORTABLE_ARG: &str = "--portable";
+/// File next to the exe that turns on portable mode without the flag
+pub const PORTABLE_MARKER: &str = "portable.txt";
+
+/// Set by `--portable` (see `set_portable`)
+static PORTABLE_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Force portable mode for this run; call before anything loads the config
+pub fn set_portable(portable: bool) {
+    PORTABLE_FLAG.store(portable, Ordering::Relaxed);
+}
+
+/// Set by `--safe-mode` (see `set_safe_mode`)
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Start without hotkeys, file watching, the remote API, the keyboard hook, the crosshair and
+/// automatic activation; call before the app mode starts
+pub fn set_safe_mode(safe_mode: bool) {
+    SAFE_MODE.store(safe_mode, Ordering::Relaxed);
+}
+
+pub fn is_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+}
+
+/// Arguments a copy of the app started by this one needs to use the same data directory
+/// (a marker file carries over by itself, the flag doesn't)
+pub fn relaunch_args() -> Vec<&'static str> {
+    if PORTABLE_FLAG.load(Ordering::Relaxed) {
+        vec![PORTABLE_ARG]
+    } else {
+        Vec::new()
+    }
+}
+
+/// The exe's folder when running portable: forced by the flag, or a marker file next to the exe
+fn portable_directory(flag: bool, exe_dir: Option<&Path>) -> Option<PathBuf> {
+    let exe_dir = exe_dir?;
+    (flag || exe_dir.join(PORTABLE_MARKER).exists()).then(|| exe_dir.to_path_buf())
+}
+
+/// File in the default data directory that points at a custom one
+const LOCATION_FILE: &str = "data_location.txt";
+
+/// %APPDATA%/GamingOptimizer/, created if it doesn't exist
+fn default_data_directory() -> Result<PathBuf> {
+    let project_dirs = ProjectDirs::from("", "", "GamingOptimizer")
+        .ok_or_else(|| anyhow!("Failed to determine user data directory"))?;
+
+    let data_dir = project_dirs.data_dir();
+
+    // Create directory if it doesn't exist
+    fs::create_dir_all(data_dir)
+        .map_err(|e| anyhow!("Failed to create data directory: {}", e))?;
+
+    Ok(data_dir.to_path_buf())
+}
+
+/// Folder named in a location file (None without one, or when it's empty)
+fn read_location(file: &Path) -> Option<PathBuf> {
+    let contents = fs::read_to_string(file).ok()?;
+    let path = contents.trim();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Whether the data directory comes from `--portable` or portable.txt (it can't be moved then)
+pub fn is_portable() -> bool {
+    let exe = std::env::current_exe().ok();
+    portable_directory(PORTABLE_FLAG.load(Ordering::Relaxed), exe.as_deref().and_then(Path::parent)).is_some()
+}
+
+/// Custom data directory chosen in the settings (None = default location)
+pub fn data_directory_override() -> Option<PathBuf> {
+    read_location(&default_data_directory().ok()?.join(LOCATION_FILE))
+}
+
+/// Get the application's data directory
+/// Resolution order: `--portable`, a portable.txt marker next to the exe (both use the exe's
+/// folder, e.g. on a USB stick), the folder chosen in the settings, then
+/// %APPDATA%/GamingOptimizer/
+/// Creates directory if it doesn't exist
+pub fn get_data_directory() -> Result<PathBuf> {
+    let exe = std::env::current_exe().ok();
+    let exe_dir = exe.as_deref().and_then(Path::parent);
+    if let Some(dir) = portable_directory(PORTABLE_FLAG.load(Ordering::Relaxed), exe_dir) {
+        return Ok(dir);
+    }
+
+    let default_dir = default_data_directory()?;
+    if let Some(dir) = read_location(&default_dir.join(LOCATION_FILE)) {
+        // A drive that isn't plugged in shouldn't keep the app from starting
+        match fs::create_dir_all(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) => warn!("[Config] Data folder {} isn't available, using the default one: {}", dir.display(), e),
+        }
+    }
+    Ok(default_dir)
+}
+
+/// Whether `dir` already holds app data of its own
+pub fn has_app_data(dir: &Path) -> bool {
+    PROFILE_FILES.iter().any(|name| dir.join(name).exists()) || dir.join("config.json").exists()
+}
+
+/// Copy everything in `from` to `to`, replacing files that are there already; returns the number
+/// of files copied
+fn copy_data(from: &Path, to: &Path) -> Result<usize> {
+    fs::create_dir_all(to).map_err(|e| anyhow!("Failed to create {}: {}", to.display(), e))?;
+    let entries = fs::read_dir(from).map_err(|e| anyhow!("Failed to read {}: {}", from.display(), e))?;
+
+    let mut copied = 0;
+    for entry in entries.flatten() {
+        let (path, name) = (entry.path(), entry.file_name());
+        if name == LOCATION_FILE {
+            continue;
+        }
+        if path.is_dir() {
+            copied += copy_data(&path, &to.join(&name))?;
+        } else {
+            fs::copy(&path, to.join(&name)).map_err(|e| anyhow!("Failed to copy {}: {}", path.display(), e))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Keep the app's data in `target` from now on (None = back to the default location). With
+/// `copy_files` the current data is copied there first; otherwise the data already in `target`
+/// is used. The old folder keeps its files. Returns the new data directory and the number of
+/// files copied.
+pub fn set_data_directory(target: Option<&Path>, copy_files: bool) -> Result<(PathBuf, usize)> {
+    if is_portable() {
+        return Err(anyhow!("The data folder can't be moved in portable mode"));
+    }
+    let current = get_data_directory()?;
+    let default_dir = default_data_directory()?;
+    let target = target.map_or_else(|| default_dir.clone(), Path::to_path_buf);
+    if target == current {
+        return Ok((current, 0));
+    }
+    if target.starts_with(&current) {
+        return Err(anyhow!("The new data folder can't be inside the current one"));
+    }
+
+    let copied = if copy_files { copy_data(&current, &target)? } else { 0 };
+    let location = default_dir.join(LOCATION_FILE);
+    if target == default_dir {
+        fs::remove_file(&location).map_err(|e| anyhow!("Failed to remove {}: {}", LOCATION_FILE, e))?;
+    } else {
+        fs::create_dir_all(&target).map_err(|e| anyhow!("Failed to create {}: {}", target.display(), e))?;
+        fs::write(&location, target.to_string_lossy().as_bytes())
+            .map_err(|e| anyhow!("Failed to write {}: {}", LOCATION_FILE, e))?;
+    }
+
+    info!("[Config] Data folder moved from {} to {} ({} files copied)", current.display(), target.display(), copied);
+    Ok((target, copied))
+}
+
+/// Load application configuration from config.json
+/// Returns default config if file doesn't exist or on error
+pub fn load_config() -> AppConfig {
+    let Ok(data_dir) = get_data_directory() else {
+        return AppConfig::default();
+    };
+    
+    let config_path = data_dir.join("config.json");
+
+    // If file doesn't exist, return default config
+    if !config_path.exists() {
+        return AppConfig::default();
+    }
+
+    // Read and parse JSON
+    let Ok(contents) = fs::read_to_string(&config_path) else {
+        return AppConfig::default();
+    };
+
+    serde_json::from_str(&contents).unwrap_or_default()
+}
+
+/// Read config.json, failing instead of falling back to defaults (for reloading a file that was
+/// just edited, where a typo shouldn't reset every setting)
+pub fn read_config() -> Result<AppConfig> {
+    let config_path = get_data_directory()?.join("config.json");
+    let contents = fs::read_to_string(&config_path)
+        .map_err(|e| anyhow!("Failed to read config.json: {}", e))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| anyhow!("Failed to parse config.json: {}", e))
+}
+
+/// Save application configuration to config.json
+pub fn save_config(config: &AppConfig) -> Result<()> {
+    let data_dir = get_data_directory()?;
+    let config_path = data_dir.join("config.json");
+
+    // Serialize to pretty-printed JSON
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|e| anyhow!("Failed to serialize config: {}", e))?;
+
+    // Write to file
+    fs::write(&config_path, json)
+        .map_err(|e| anyhow!("Failed to write config.json: {}", e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::ThemeMode;
+
+    #[test]
+    fn test_default_config() {
+        let config = AppConfig::default();
+        assert_eq!(config.active_profile, None);
+        assert_eq!(config.overlay_visible, false);
+    }
+
+    #[test]
+    fn test_config_without_openrgb() {
+        let config: AppConfig = serde_json::from_str(r#"{"active_profile":null,"overlay_visible":true}"#).unwrap();
+        assert_eq!(config.openrgb, OpenRgbConfig::default());
+        assert_eq!(config.obs.port, crate::obs::DEFAULT_PORT);
+        assert!(!config.discord.enabled);
+        assert!(!config.event_log.enabled);
+        assert!(config.plugins.enabled.is_empty());
+        assert_eq!(config.monitor.interval_ms, 2000);
+        assert!(!config.alerts.enabled);
+        assert_eq!(config.log_level, LogLevel::Info);
+        assert!(config.toasts.overlay_error);
+        assert!(!config.api.enabled);
+        assert!(!config.autostart.enabled);
+        assert!(!config.updates.enabled);
+        assert_eq!(config.hotkeys.cycle_crosshair, "Ctrl+Alt+C");
+        assert!(!config.gamepad.enabled);
+        assert_eq!(config.sync_folder, None);
+        assert!(!config.safety.confirm_kill);
+        assert_eq!(config.theme.mode, ThemeMode::System);
+        assert_eq!(config.language, None);
+        assert!(!config.paused);
+        assert!(!config.resume_last_session);
+        assert_eq!(config.window, WindowLayout::default());
+    }
+
+    #[test]
+    fn test_portable_directory() {
+        let exe_dir = std::env::temp_dir().join(format!("gaming_optimizer_portable_{}", std::process::id()));
+        fs::create_dir_all(&exe_dir).unwrap();
+
+        assert_eq!(portable_directory(false, Some(&exe_dir)), None);
+        assert_eq!(portable_directory(true, Some(&exe_dir)), Some(exe_dir.clone()));
+        fs::write(exe_dir.join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(portable_directory(false, Some(&exe_dir)), Some(exe_dir.clone()));
+        assert_eq!(portable_directory(true, None), None);
+
+        fs::remove_dir_all(&exe_dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_location() {
+        let dir = std::env::temp_dir().join(format!("gaming_optimizer_location_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join(LOCATION_FILE);
+
+        assert_eq!(read_location(&file), None);
+        fs::write(&file, "  \n").unwrap();
+        assert_eq!(read_location(&file), None);
+        fs::write(&file, "D:\\Games\\Optimizer\r\n").unwrap();
+        assert_eq!(read_location(&file), Some(PathBuf::from("D:\\Games\\Optimizer")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_copy_data() {
+        let base = std::env::temp_dir().join(format!("gaming_optimizer_copy_{}", std::process::id()));
+        let (from, to) = (base.join("old"), base.join("new"));
+        fs::create_dir_all(from.join("crosshairs")).unwrap();
+        fs::write(from.join("profiles.json"), "[]").unwrap();
+        fs::write(from.join("crosshairs").join("dot.png"), [1, 2, 3]).unwrap();
+        fs::write(from.join(LOCATION_FILE), "elsewhere").unwrap();
+        fs::create_dir_all(&to).unwrap();
+        fs::write(to.join("profiles.json"), "stale").unwrap();
+
+        assert!(!has_app_data(&base));
+        assert_eq!(copy_data(&from, &to).unwrap(), 2);
+        assert!(has_app_data(&to));
+        assert_eq!(fs::read_to_string(to.join("profiles.json")).unwrap(), "[]");
+        assert_eq!(fs::read(to.join("crosshairs").join("dot.png")).unwrap(), [1, 2, 3]);
+        assert!(!to.join(LOCATION_FILE).exists());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_get_data_directory() {
+        let result = get_data_directory();
+        assert!(result.is_ok());
+
+        let path = result.unwrap();
+        assert!(path.to_string_lossy().contains("GamingOptimizer"));
+    }
+}
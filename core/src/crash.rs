@@ -0,0 +1,305 @@
+//! Crash reports
+//!
+//! A panic hook (and, on Windows, an unhandled exception filter) that writes a report with the
+//! panic message, backtrace, app version and active profile to crashes/ in the data directory,
+//! next to a minidump. When the main thread goes down a message box offers to restart the app,
+//! optionally reactivating the profile that was active.
+
+use crate::config::get_data_directory;
+use once_cell::sync::Lazy;
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use tracing::error;
+
+/// Command line flag that makes a restarted GUI reactivate a profile (parsed in `cli`)
+pub const RESTORE_PROFILE_ARG: &str = "--restore-profile";
+
+/// Profile active right now, kept here so the hook doesn't have to reach into the GUI
+static ACTIVE_PROFILE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// Remember the active profile for crash reports and restarts
+pub fn set_active_profile(profile: Option<&str>) {
+    if let Ok(mut guard) = ACTIVE_PROFILE.lock() {
+        *guard = profile.map(str::to_string);
+    }
+}
+
+fn active_profile() -> Option<String> {
+    // The panic may have happened while the lock was held
+    match ACTIVE_PROFILE.try_lock() {
+        Ok(guard) => guard.clone(),
+        Err(_) => None,
+    }
+}
+
+/// Folder crash reports and minidumps are written to
+pub fn crash_dir() -> Option<PathBuf> {
+    get_data_directory().ok().map(|dir| dir.join("crashes"))
+}
+
+/// Everything written to crash-<time>.txt
+struct CrashReport {
+    message: String,
+    location: Option<String>,
+    thread: String,
+    profile: Option<String>,
+    backtrace: String,
+}
+
+impl CrashReport {
+    fn to_text(&self) -> String {
+        format!(
+            "Gaming Optimizer {} crashed\n\nTime: {} (unix)\nThread: {}\nActive profile: {}\nMessage: {}\nLocation: {}\n\nBacktrace:\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            crate::sessions::unix_time(),
+            self.thread,
+            self.profile.as_deref().unwrap_or("none"),
+            self.message,
+            self.location.as_deref().unwrap_or("unknown"),
+            self.backtrace,
+        )
+    }
+}
+
+/// The string a panic was raised with (`panic!` payloads are `&str` or `String`)
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic".to_string())
+}
+
+#[cfg(windows)]
+type ExceptionPointers = *const windows::Win32::System::Diagnostics::Debug::EXCEPTION_POINTERS;
+#[cfg(not(windows))]
+type ExceptionPointers = ();
+
+/// Write the report (and a minidump where supported); returns the report path
+fn write_report(report: &CrashReport, exception: Option<ExceptionPointers>) -> Option<PathBuf> {
+    let dir = crash_dir()?;
+    fs::create_dir_all(&dir).ok()?;
+
+    let stem = format!("crash-{}", crate::sessions::unix_time());
+    let report_path = dir.join(format!("{}.txt", stem));
+    if let Err(e) = fs::write(&report_path, report.to_text()) {
+        error!("[Crash] Failed to write crash report: {}", e);
+        return None;
+    }
+    if let Err(e) = write_minidump(&dir.join(format!("{}.dmp", stem)), exception) {
+        error!("[Crash] Failed to write minidump: {}", e);
+    }
+    Some(report_path)
+}
+
+/// Install the crash handlers; call once at startup after logging is set up
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current().name().unwrap_or("unnamed").to_string();
+        let report = CrashReport {
+            message: panic_message(info),
+            location: info.location().map(|location| location.to_string()),
+            thread,
+            profile: active_profile(),
+            backtrace: Backtrace::force_capture().to_string(),
+        };
+
+        error!("[Crash] Panic on thread '{}': {}", report.thread, report.message);
+        let report_path = write_report(&report, None);
+        default_hook(info);
+
+        // A panic on a helper thread only stops that thread; the app keeps running
+        if report.thread == "main" {
+            offer_restart(report_path.as_deref(), report.profile);
+        }
+    }));
+
+    #[cfg(windows)]
+    unsafe {
+        windows::Win32::System::Diagnostics::Debug::SetUnhandledExceptionFilter(Some(on_unhandled_exception));
+    }
+}
+
+/// Last chance for crashes that aren't panics (access violations in FFI code and the like)
+#[cfg(windows)]
+unsafe extern "system" fn on_unhandled_exception(exception: ExceptionPointers) -> i32 {
+    const EXCEPTION_EXECUTE_HANDLER: i32 = 1;
+
+    let code = exception
+        .as_ref()
+        .and_then(|pointers| pointers.ExceptionRecord.as_ref())
+        .map(|record| record.ExceptionCode.0 as u32)
+        .unwrap_or(0);
+    let report = CrashReport {
+        message: format!("Unhandled exception 0x{:08X}", code),
+        location: None,
+        thread: std::thread::current().name().unwrap_or("unnamed").to_string(),
+        profile: active_profile(),
+        backtrace: Backtrace::force_capture().to_string(),
+    };
+    error!("[Crash] {}", report.message);
+
+    let report_path = write_report(&report, Some(exception));
+    offer_restart(report_path.as_deref(), report.profile);
+    EXCEPTION_EXECUTE_HANDLER
+}
+
+#[cfg(windows)]
+fn write_minidump(path: &Path, exception: Option<ExceptionPointers>) -> anyhow::Result<()> {
+    use anyhow::anyhow;
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::{BOOL, HANDLE};
+    use windows::Win32::System::Diagnostics::Debug::{
+        MiniDumpNormal, MiniDumpWithThreadInfo, MiniDumpWriteDump, MINIDUMP_EXCEPTION_INFORMATION,
+    };
+    use windows::Win32::System::Threading::{GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId};
+
+    let file = fs::File::create(path).map_err(|e| anyhow!("Failed to create minidump file: {}", e))?;
+    let exception_info = exception.map(|pointers| MINIDUMP_EXCEPTION_INFORMATION {
+        ThreadId: unsafe { GetCurrentThreadId() },
+        ExceptionPointers: pointers as *mut _,
+        ClientPointers: BOOL(0),
+    });
+
+    unsafe {
+        MiniDumpWriteDump(
+            GetCurrentProcess(),
+            GetCurrentProcessId(),
+            HANDLE(file.as_raw_handle() as isize),
+            MiniDumpNormal | MiniDumpWithThreadInfo,
+            exception_info.as_ref().map(|info| info as *const _),
+            None,
+            None,
+        )
+    }
+    .map_err(|e| anyhow!("MiniDumpWriteDump failed: {}", e))
+}
+
+#[cfg(not(windows))]
+fn write_minidump(_path: &Path, _exception: Option<ExceptionPointers>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// How the user answered the crash dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RestartChoice {
+    Close,
+    Restart,
+    RestartWithProfile,
+}
+
+#[cfg(windows)]
+fn ask_restart(report_path: Option<&Path>, profile: Option<&str>) -> RestartChoice {
+    use windows::core::HSTRING;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        MessageBoxW, IDNO, IDYES, MB_ICONERROR, MB_SETFOREGROUND, MB_TOPMOST, MB_YESNO, MB_YESNOCANCEL,
+    };
+
+    let mut text = "Gaming Optimizer stopped unexpectedly.".to_string();
+    if let Some(path) = report_path {
+        text.push_str(&format!("\n\nA crash report was saved to:\n{}", path.display()));
+    }
+    let buttons = match profile {
+        Some(name) => {
+            text.push_str(&format!(
+                "\n\nRestart and reactivate \"{}\"?\n\nYes: restart with the profile\nNo: restart without it\nCancel: close",
+                name
+            ));
+            MB_YESNOCANCEL
+        }
+        None => {
+            text.push_str("\n\nRestart Gaming Optimizer?");
+            MB_YESNO
+        }
+    };
+
+    let answer = unsafe {
+        MessageBoxW(
+            None,
+            &HSTRING::from(text),
+            &HSTRING::from("Gaming Optimizer crashed"),
+            buttons | MB_ICONERROR | MB_SETFOREGROUND | MB_TOPMOST,
+        )
+    };
+    match (answer, profile) {
+        (IDYES, Some(_)) => RestartChoice::RestartWithProfile,
+        (IDYES, None) | (IDNO, Some(_)) => RestartChoice::Restart,
+        _ => RestartChoice::Close,
+    }
+}
+
+#[cfg(not(windows))]
+fn ask_restart(_report_path: Option<&Path>, _profile: Option<&str>) -> RestartChoice {
+    RestartChoice::Close
+}
+
+/// Arguments for the restarted process: ours minus any earlier restore flag, plus the new one
+fn restart_args(args: &[String], profile: Option<&str>) -> Vec<String> {
+    let mut restarted = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == RESTORE_PROFILE_ARG {
+            args.next();
+        } else {
+            restarted.push(arg.clone());
+        }
+    }
+    if let Some(name) = profile {
+        restarted.push(RESTORE_PROFILE_ARG.to_string());
+        restarted.push(name.to_string());
+    }
+    restarted
+}
+
+/// Ask the user and start a fresh instance; this process is on its way out either way
+fn offer_restart(report_path: Option<&Path>, profile: Option<String>) {
+    let profile = match ask_restart(report_path, profile.as_deref()) {
+        RestartChoice::Close => return,
+        RestartChoice::Restart => None,
+        RestartChoice::RestartWithProfile => profile,
+    };
+
+    // Otherwise the new copy would find this one still running and hand over to it
+    crate::single_instance::release();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let started = std::env::current_exe().and_then(|exe| Command::new(exe).args(restart_args(&args, profile.as_deref())).spawn());
+    if let Err(e) = started {
+        error!("[Crash] Failed to restart: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_restart_args_replace_restore_flag() {
+        let previous = args(&["--tray-only", RESTORE_PROFILE_ARG, "Old"]);
+        assert_eq!(restart_args(&previous, Some("FPS")), args(&["--tray-only", RESTORE_PROFILE_ARG, "FPS"]));
+        assert_eq!(restart_args(&previous, None), args(&["--tray-only"]));
+    }
+
+    #[test]
+    fn test_report_text() {
+        let report = CrashReport {
+            message: "index out of bounds".to_string(),
+            location: Some("src/gui/mod.rs:10:5".to_string()),
+            thread: "main".to_string(),
+            profile: Some("FPS".to_string()),
+            backtrace: "disabled".to_string(),
+        };
+        let text = report.to_text();
+        assert!(text.starts_with(&format!("Gaming Optimizer {} crashed", env!("CARGO_PKG_VERSION"))));
+        assert!(text.contains("Active profile: FPS\nMessage: index out of bounds\nLocation: src/gui/mod.rs:10:5"));
+    }
+}
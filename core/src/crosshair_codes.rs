@@ -0,0 +1,309 @@
+//! Crosshair share code import
+//!
+//! Converts Valorant crosshair profile codes and CS2 crosshair share codes into
+//! parametric crosshair settings. Sizes are mapped to pixels at 1080p.
+
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use crate::crosshair_gen::{CrosshairLines, CrosshairParams};
+
+/// Alphabet used by CS2 (CS:GO) share codes
+const CS2_DICTIONARY: &[u8] = b"ABCDEFGHJKLMNOPQRSTUVWXYZabcdefhijkmnopqrstuvwxyz23456789";
+
+/// Number of bytes encoded in a CS2 share code
+const CS2_CODE_BYTES: usize = 18;
+
+/// Parse a crosshair code from either game, detecting the format automatically
+pub fn parse_crosshair_code(code: &str) -> Result<CrosshairParams> {
+    let code = code.trim();
+    if code.is_empty() {
+        return Err(anyhow!("Crosshair code is empty"));
+    }
+
+    if code.starts_with("CSGO-") {
+        parse_cs2_code(code)
+    } else if code.contains(';') {
+        parse_valorant_code(code)
+    } else {
+        Err(anyhow!("Unrecognized crosshair code (expected a Valorant or CS2 code)"))
+    }
+}
+
+/// File name an imported code is saved under. The first 16 hex digits of the trimmed code's
+/// SHA-256, so the same code maps to the same file on every build.
+pub fn imported_file_name(code: &str) -> String {
+    let digest = Sha256::digest(code.trim().as_bytes());
+    let hex: String = digest[..8].iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("imported_{}.png", hex)
+}
+
+/// Parse a Valorant crosshair profile code (e.g. `0;P;c;5;h;0;0l;4;0o;2;0a;1;0f;0;1b;0`)
+/// Only the primary crosshair section is used
+pub fn parse_valorant_code(code: &str) -> Result<CrosshairParams> {
+    let mut tokens = code.trim().split(';').map(str::trim).peekable();
+
+    // Leading version marker
+    if tokens.peek() == Some(&"0") {
+        tokens.next();
+    }
+
+    let mut section = "";
+    let mut primary: HashMap<&str, &str> = HashMap::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            "" => continue,
+            "P" | "A" | "S" => section = token,
+            key => {
+                let value = tokens
+                    .next()
+                    .ok_or_else(|| anyhow!("Valorant code is missing a value for '{}'", key))?;
+                if section == "P" {
+                    primary.insert(key, value);
+                }
+            }
+        }
+    }
+
+    let number = |key: &str, default: f32| -> Result<f32> {
+        match primary.get(key) {
+            Some(value) => value
+                .parse::<f32>()
+                .map_err(|_| anyhow!("Invalid value '{}' for '{}' in Valorant code", value, key)),
+            None => Ok(default),
+        }
+    };
+    let flag = |key: &str, default: bool| -> Result<bool> {
+        Ok(number(key, if default { 1.0 } else { 0.0 })? != 0.0)
+    };
+    let px = |value: f32| value.max(0.0).round() as u32;
+
+    let color = match number("c", 0.0)? as u32 {
+        0 => [255, 255, 255],
+        1 => [0, 255, 0],
+        2 => [127, 255, 0],
+        3 => [223, 255, 0],
+        4 => [255, 255, 0],
+        5 => [0, 255, 255],
+        6 => [255, 0, 255],
+        7 => [255, 0, 0],
+        _ => {
+            let hex = primary
+                .get("u")
+                .ok_or_else(|| anyhow!("Valorant code uses a custom color without a value"))?;
+            let rgba = crate::overlay::parse_hex_color(hex)
+                .ok_or_else(|| anyhow!("Invalid custom color '{}' in Valorant code", hex))?;
+            [rgba[0], rgba[1], rgba[2]]
+        }
+    };
+
+    let lines = |prefix: &str, length: f32, thickness: f32, gap: f32, opacity: f32| -> Result<Option<CrosshairLines>> {
+        if !flag(&format!("{}b", prefix), true)? {
+            return Ok(None);
+        }
+        Ok(Some(CrosshairLines {
+            length: px(number(&format!("{}l", prefix), length)?),
+            thickness: px(number(&format!("{}t", prefix), thickness)?),
+            gap: px(number(&format!("{}o", prefix), gap)?),
+            opacity: number(&format!("{}a", prefix), opacity)?.clamp(0.0, 1.0),
+        }))
+    };
+
+    let outlines = flag("h", true)?;
+
+    Ok(CrosshairParams {
+        color,
+        inner_lines: lines("0", 6.0, 2.0, 3.0, 0.8)?,
+        outer_lines: lines("1", 2.0, 2.0, 10.0, 0.35)?,
+        dot_size: if flag("d", false)? { px(number("z", 2.0)?) } else { 0 },
+        dot_opacity: number("a", 1.0)?.clamp(0.0, 1.0),
+        outline_thickness: if outlines { px(number("t", 1.0)?) } else { 0 },
+        outline_opacity: number("o", 0.5)?.clamp(0.0, 1.0),
+        t_style: false,
+    })
+}
+
+/// Decode the 18 raw bytes of a `CSGO-xxxxx-xxxxx-xxxxx-xxxxx-xxxxx` share code
+fn decode_cs2_bytes(code: &str) -> Result<[u8; CS2_CODE_BYTES]> {
+    let body: Vec<u8> = code
+        .trim()
+        .strip_prefix("CSGO-")
+        .ok_or_else(|| anyhow!("CS2 share codes start with 'CSGO-'"))?
+        .bytes()
+        .filter(|&b| b != b'-')
+        .collect();
+
+    if body.len() != 25 {
+        return Err(anyhow!("CS2 share code has the wrong length"));
+    }
+
+    // Base-57 number with the least significant digit first, stored big-endian
+    let mut bytes = [0u8; CS2_CODE_BYTES];
+    for &ch in body.iter().rev() {
+        let digit = CS2_DICTIONARY
+            .iter()
+            .position(|&d| d == ch)
+            .ok_or_else(|| anyhow!("Invalid character '{}' in CS2 share code", ch as char))?;
+
+        let mut carry = digit as u32;
+        for byte in bytes.iter_mut().rev() {
+            let value = *byte as u32 * 57 + carry;
+            *byte = (value & 0xFF) as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(anyhow!("CS2 share code is out of range"));
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Parse a CS2 crosshair share code (`CSGO-...`)
+pub fn parse_cs2_code(code: &str) -> Result<CrosshairParams> {
+    let bytes = decode_cs2_bytes(code)?;
+
+    let checksum = bytes[1..].iter().map(|&b| b as u32).sum::<u32>() % 256;
+    if checksum != bytes[0] as u32 {
+        return Err(anyhow!("CS2 share code checksum mismatch (not a crosshair code?)"));
+    }
+
+    let gap = bytes[2] as i8 as f32 / 10.0;
+    let outline_thickness = bytes[3] as f32 / 2.0;
+    let custom = [bytes[4], bytes[5], bytes[6]];
+    let alpha = bytes[7];
+    let color_index = bytes[10] & 0b111;
+    let outline_enabled = bytes[10] & 0b1000 != 0;
+    let thickness = bytes[12] as f32 / 10.0;
+    let flags = bytes[13] >> 4;
+    let center_dot = flags & 1 != 0;
+    let alpha_enabled = flags & 4 != 0;
+    let t_style = flags & 8 != 0;
+    let length = ((((bytes[15] & 0x1F) as u32) << 8) + bytes[14] as u32) as f32 / 10.0;
+
+    let color = match color_index {
+        0 => [250, 50, 50],
+        1 => [50, 250, 50],
+        2 => [250, 250, 50],
+        3 => [50, 50, 250],
+        4 => [50, 250, 250],
+        _ => custom,
+    };
+
+    // CS2 units are roughly two pixels at 1080p; a gap of 0 still leaves ~4px
+    let line_thickness = ((thickness * 2.0).round() as u32).max(1);
+    let opacity = if alpha_enabled { alpha as f32 / 255.0 } else { 1.0 };
+
+    Ok(CrosshairParams {
+        color,
+        inner_lines: Some(CrosshairLines {
+            length: (length * 2.0).round().max(0.0) as u32,
+            thickness: line_thickness,
+            gap: (gap + 4.0).round().max(0.0) as u32,
+            opacity,
+        }),
+        outer_lines: None,
+        dot_size: if center_dot { line_thickness } else { 0 },
+        dot_opacity: opacity,
+        outline_thickness: if outline_enabled { (outline_thickness.round() as u32).max(1) } else { 0 },
+        outline_opacity: opacity,
+        t_style,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode raw bytes back into a share code (inverse of decode_cs2_bytes)
+    fn encode_cs2_bytes(bytes: [u8; CS2_CODE_BYTES]) -> String {
+        let mut number = bytes.to_vec();
+        let mut chars = Vec::new();
+        for _ in 0..25 {
+            let mut remainder = 0u32;
+            for byte in number.iter_mut() {
+                let value = (remainder << 8) + *byte as u32;
+                *byte = (value / 57) as u8;
+                remainder = value % 57;
+            }
+            chars.push(CS2_DICTIONARY[remainder as usize] as char);
+        }
+        let code: String = chars.into_iter().collect();
+        format!("CSGO-{}-{}-{}-{}-{}", &code[0..5], &code[5..10], &code[10..15], &code[15..20], &code[20..25])
+    }
+
+    #[test]
+    fn test_imported_file_name() {
+        // SHA-256("abc") = ba7816bf8f01cfea...
+        assert_eq!(imported_file_name(" abc\n"), "imported_ba7816bf8f01cfea.png");
+    }
+
+    #[test]
+    fn test_parse_valorant_code() {
+        let params = parse_valorant_code("0;P;c;5;h;0;0l;4;0o;2;0a;1;0f;0;1b;0").unwrap();
+        assert_eq!(params.color, [0, 255, 255]);
+        assert_eq!(params.outline_thickness, 0);
+        assert!(params.outer_lines.is_none());
+
+        let inner = params.inner_lines.unwrap();
+        assert_eq!(inner.length, 4);
+        assert_eq!(inner.gap, 2);
+        assert_eq!(inner.opacity, 1.0);
+    }
+
+    #[test]
+    fn test_parse_valorant_custom_color_and_dot() {
+        let params = parse_valorant_code("0;P;c;8;u;FF8000FF;d;1;z;3;0b;0;1b;0").unwrap();
+        assert_eq!(params.color, [255, 128, 0]);
+        assert_eq!(params.dot_size, 3);
+        assert!(params.inner_lines.is_none());
+    }
+
+    #[test]
+    fn test_parse_valorant_ignores_other_sections() {
+        let params = parse_valorant_code("0;s;1;P;c;1;A;c;7;S;c;4").unwrap();
+        assert_eq!(params.color, [0, 255, 0]);
+    }
+
+    #[test]
+    fn test_parse_valorant_invalid_value() {
+        assert!(parse_valorant_code("0;P;0l;abc").is_err());
+        assert!(parse_valorant_code("0;P;c").is_err());
+    }
+
+    #[test]
+    fn test_parse_cs2_code_roundtrip() {
+        let mut bytes = [0u8; CS2_CODE_BYTES];
+        bytes[1] = 1;
+        bytes[2] = (-20i8) as u8; // gap -2.0
+        bytes[3] = 2; // outline 1.0
+        bytes[4] = 0;
+        bytes[5] = 200;
+        bytes[6] = 255;
+        bytes[7] = 255;
+        bytes[10] = 5 | 0b1000; // custom color, outline on
+        bytes[12] = 10; // thickness 1.0
+        bytes[13] = 0x10; // center dot
+        bytes[14] = 25; // length 2.5
+        bytes[0] = (bytes[1..].iter().map(|&b| b as u32).sum::<u32>() % 256) as u8;
+
+        let code = encode_cs2_bytes(bytes);
+        assert_eq!(decode_cs2_bytes(&code).unwrap(), bytes);
+
+        let params = parse_crosshair_code(&code).unwrap();
+        assert_eq!(params.color, [0, 200, 255]);
+        assert_eq!(params.dot_size, 2);
+        assert_eq!(params.outline_thickness, 1);
+        let inner = params.inner_lines.unwrap();
+        assert_eq!(inner.length, 5);
+        assert_eq!(inner.thickness, 2);
+        assert_eq!(inner.gap, 2);
+    }
+
+    #[test]
+    fn test_parse_cs2_code_rejects_bad_input() {
+        assert!(parse_cs2_code("CSGO-aaaaa-bbbbb").is_err());
+        assert!(parse_cs2_code("CSGO-IIIII-IIIII-IIIII-IIIII-IIIII").is_err()); // 'I' not in alphabet
+        assert!(parse_crosshair_code("hello").is_err());
+    }
+}
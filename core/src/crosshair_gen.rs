@@ -0,0 +1,203 @@
+//! Parametric crosshair generator
+//!
+//! Renders a crosshair described by simple line/dot parameters into a 100x100 PNG,
+//! so it can be fed to the same overlay pipeline as hand-made crosshair images.
+
+use anyhow::{anyhow, Result};
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Size of generated crosshair images (matches the overlay's 100x100 requirement)
+pub const CROSSHAIR_SIZE: u32 = 100;
+
+/// One set of four crosshair arms
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CrosshairLines {
+    /// Arm length in pixels
+    pub length: u32,
+    /// Arm thickness in pixels
+    pub thickness: u32,
+    /// Distance from the center to the start of each arm
+    pub gap: u32,
+    /// Opacity from 0.0 to 1.0
+    pub opacity: f32,
+}
+
+/// Parametric crosshair settings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CrosshairParams {
+    /// RGB color of lines and dot
+    pub color: [u8; 3],
+    pub inner_lines: Option<CrosshairLines>,
+    pub outer_lines: Option<CrosshairLines>,
+    /// Center dot size in pixels (0 = no dot)
+    pub dot_size: u32,
+    pub dot_opacity: f32,
+    /// Outline thickness in pixels (0 = no outline)
+    pub outline_thickness: u32,
+    pub outline_opacity: f32,
+    /// Omit the top arm (T-shaped crosshair)
+    pub t_style: bool,
+}
+
+impl Default for CrosshairParams {
+    fn default() -> Self {
+        CrosshairParams {
+            color: [0, 255, 0],
+            inner_lines: Some(CrosshairLines {
+                length: 6,
+                thickness: 2,
+                gap: 3,
+                opacity: 1.0,
+            }),
+            outer_lines: None,
+            dot_size: 0,
+            dot_opacity: 1.0,
+            outline_thickness: 1,
+            outline_opacity: 0.5,
+            t_style: false,
+        }
+    }
+}
+
+/// Axis-aligned rectangle in image coordinates
+struct Rect {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+    opacity: f32,
+}
+
+impl CrosshairParams {
+    /// Collect the filled rectangles making up the crosshair
+    fn shapes(&self) -> Vec<Rect> {
+        let center = CROSSHAIR_SIZE as i32 / 2;
+        let mut rects = Vec::new();
+
+        for lines in [&self.inner_lines, &self.outer_lines].into_iter().flatten() {
+            if lines.length == 0 || lines.thickness == 0 {
+                continue;
+            }
+            let len = lines.length as i32;
+            let thick = lines.thickness as i32;
+            let gap = lines.gap as i32;
+            // Center odd and even thicknesses on the middle pixel
+            let across = center - thick / 2;
+
+            // Right, left, bottom, top
+            rects.push(Rect { x: center + gap, y: across, w: len, h: thick, opacity: lines.opacity });
+            rects.push(Rect { x: center - gap - len, y: across, w: len, h: thick, opacity: lines.opacity });
+            rects.push(Rect { x: across, y: center + gap, w: thick, h: len, opacity: lines.opacity });
+            if !self.t_style {
+                rects.push(Rect { x: across, y: center - gap - len, w: thick, h: len, opacity: lines.opacity });
+            }
+        }
+
+        if self.dot_size > 0 {
+            let size = self.dot_size as i32;
+            rects.push(Rect {
+                x: center - size / 2,
+                y: center - size / 2,
+                w: size,
+                h: size,
+                opacity: self.dot_opacity,
+            });
+        }
+
+        rects
+    }
+
+    /// Render the crosshair into a transparent 100x100 RGBA image
+    pub fn render(&self) -> RgbaImage {
+        let mut img = RgbaImage::new(CROSSHAIR_SIZE, CROSSHAIR_SIZE);
+        let shapes = self.shapes();
+
+        // Outlines first so the colored lines are drawn on top
+        if self.outline_thickness > 0 {
+            let t = self.outline_thickness as i32;
+            for rect in &shapes {
+                let alpha = (self.outline_opacity * rect.opacity.min(1.0) * 255.0) as u8;
+                fill_rect(&mut img, rect.x - t, rect.y - t, rect.w + t * 2, rect.h + t * 2, [0, 0, 0, alpha]);
+            }
+        }
+
+        for rect in &shapes {
+            let [r, g, b] = self.color;
+            let alpha = (rect.opacity.clamp(0.0, 1.0) * 255.0) as u8;
+            fill_rect(&mut img, rect.x, rect.y, rect.w, rect.h, [r, g, b, alpha]);
+        }
+
+        img
+    }
+
+    /// Render and save the crosshair as a PNG file
+    pub fn save_png(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| anyhow!("Failed to create crosshair directory: {}", e))?;
+        }
+
+        self.render()
+            .save(path)
+            .map_err(|e| anyhow!("Failed to save generated crosshair: {}", e))
+    }
+}
+
+/// Alpha-blend a solid rectangle onto the image (clipped to bounds)
+fn fill_rect(img: &mut RgbaImage, x: i32, y: i32, w: i32, h: i32, color: [u8; 4]) {
+    let (width, height) = (img.width() as i32, img.height() as i32);
+    let src_a = color[3] as f32 / 255.0;
+    if src_a <= 0.0 {
+        return;
+    }
+
+    for py in y.max(0)..(y + h).min(height) {
+        for px in x.max(0)..(x + w).min(width) {
+            let dst = img.get_pixel_mut(px as u32, py as u32);
+            let dst_a = dst[3] as f32 / 255.0;
+            let out_a = src_a + dst_a * (1.0 - src_a);
+
+            let blend = |s: u8, d: u8| {
+                ((s as f32 * src_a + d as f32 * dst_a * (1.0 - src_a)) / out_a) as u8
+            };
+            *dst = Rgba([
+                blend(color[0], dst[0]),
+                blend(color[1], dst[1]),
+                blend(color[2], dst[2]),
+                (out_a * 255.0) as u8,
+            ]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_default_crosshair() {
+        let params = CrosshairParams::default();
+        let img = params.render();
+        assert_eq!(img.dimensions(), (CROSSHAIR_SIZE, CROSSHAIR_SIZE));
+
+        // Center stays empty because of the gap, arm pixels are the line color
+        assert_eq!(img.get_pixel(50, 50)[3], 0);
+        assert_eq!(img.get_pixel(55, 50).0, [0, 255, 0, 255]);
+        // Outline surrounds the arm
+        assert_eq!(img.get_pixel(55, 48).0[0..3], [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_t_style_skips_top_arm() {
+        let params = CrosshairParams {
+            t_style: true,
+            outline_thickness: 0,
+            ..CrosshairParams::default()
+        };
+        let img = params.render();
+        assert_eq!(img.get_pixel(50, 44)[3], 0);
+        assert_eq!(img.get_pixel(50, 55)[3], 255);
+    }
+}
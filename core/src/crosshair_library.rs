@@ -0,0 +1,112 @@
+//! Crosshair image checks, loading and the crosshair library
+
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use image::GenericImageView;
+
+/// Validate that the selected image is 100x100 pixels
+pub fn validate_crosshair_image(path: &PathBuf) -> Result<()> {
+    let reader = image::io::Reader::open(path)
+        .map_err(|e| anyhow!("Failed to open image: {}", e))?;
+    
+    let image = reader.decode()
+        .map_err(|e| anyhow!("Failed to decode image: {}", e))?;
+    
+    let (width, height) = image.dimensions();
+    
+    if width != 100 || height != 100 {
+        return Err(anyhow!(
+            "Invalid image dimensions: {}x{} (expected 100x100)",
+            width, height
+        ));
+    }
+    
+    Ok(())
+}
+
+/// Validate a crosshair image and copy it into `library` (the data folder's crosshairs).
+/// A different image already stored under the same name is kept; the copy gets a suffix.
+pub fn import_to_library(path: &Path, library: &Path) -> Result<PathBuf> {
+    use std::hash::{Hash, Hasher};
+
+    if !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("png")) {
+        return Err(anyhow!("{} is not a PNG image", path.display()));
+    }
+    validate_crosshair_image(&path.to_path_buf())?;
+    if path.parent() == Some(library) {
+        return Ok(path.to_path_buf());
+    }
+
+    let bytes = std::fs::read(path).map_err(|e| anyhow!("Failed to read image: {}", e))?;
+    let stem = path.file_stem().map_or("crosshair".into(), |stem| stem.to_string_lossy());
+    let mut target = library.join(format!("{}.png", stem));
+    if target.exists() && std::fs::read(&target).ok().as_deref() != Some(bytes.as_slice()) {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        target = library.join(format!("{}_{:08x}.png", stem, hasher.finish() as u32));
+    }
+
+    std::fs::create_dir_all(library).map_err(|e| anyhow!("Failed to create crosshair folder: {}", e))?;
+    std::fs::write(&target, &bytes).map_err(|e| anyhow!("Failed to copy image: {}", e))?;
+    Ok(target)
+}
+
+/// Load and convert image to RGBA8 for preview/rendering
+pub fn load_crosshair_image(path: &PathBuf) -> Result<(Vec<u32>, u32, u32)> {
+    validate_crosshair_image(path)?;
+    
+    let reader = image::io::Reader::open(path)
+        .map_err(|e| anyhow!("Failed to open image: {}", e))?;
+    
+    let image = reader.decode()
+        .map_err(|e| anyhow!("Failed to decode image: {}", e))?;
+    
+    let rgba_image = image.to_rgba8();
+    let (width, height) = rgba_image.dimensions();
+    
+    // Convert RGBA8 to ARGB32 (u32) format for softbuffer
+    let pixels: Vec<u32> = rgba_image
+        .chunks_exact(4)
+        .map(|chunk| {
+            let r = chunk[0] as u32;
+            let g = chunk[1] as u32;
+            let b = chunk[2] as u32;
+            let a = chunk[3] as u32;
+            (a << 24) | (r << 16) | (g << 8) | b
+        })
+        .collect();
+    
+    Ok((pixels, width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_to_library() {
+        let dir = std::env::temp_dir().join(format!("gaming_optimizer_library_{}", std::process::id()));
+        let library = dir.join("crosshairs");
+        std::fs::create_dir_all(&dir).unwrap();
+        let dropped = dir.join("dot.png");
+        image::RgbaImage::new(100, 100).save(&dropped).unwrap();
+
+        let imported = import_to_library(&dropped, &library).unwrap();
+        assert_eq!(imported, library.join("dot.png"));
+        // Same image again: reused, not duplicated
+        assert_eq!(import_to_library(&dropped, &library).unwrap(), imported);
+        // Already in the library: used in place
+        assert_eq!(import_to_library(&imported, &library).unwrap(), imported);
+
+        // Different image with the same name gets its own file
+        image::RgbaImage::from_pixel(100, 100, image::Rgba([255, 0, 0, 255])).save(&dropped).unwrap();
+        let renamed = import_to_library(&dropped, &library).unwrap();
+        assert_ne!(renamed, imported);
+        assert!(renamed.starts_with(&library));
+
+        image::RgbaImage::new(64, 64).save(&dropped).unwrap();
+        assert!(import_to_library(&dropped, &library).is_err());
+        assert!(import_to_library(&dir.join("notes.txt"), &library).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
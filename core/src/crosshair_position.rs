@@ -0,0 +1,158 @@
+//! Resolution-independent crosshair placement
+//!
+//! Anchors the crosshair at a named spot or at percentage coordinates of the screen, so the
+//! same profile lands in the same place on 1080p and 1440p monitors. Pixel offsets are
+//! applied on top for fine-tuning. Shared with crosshair.exe.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Named anchor points
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamedAnchor {
+    Center,
+    TopCenter,
+    BottomCenter,
+    LeftCenter,
+    RightCenter,
+}
+
+impl NamedAnchor {
+    /// Anchor point as (x, y) percentages of the screen size
+    pub fn percent(&self) -> (f32, f32) {
+        match self {
+            NamedAnchor::Center => (50.0, 50.0),
+            NamedAnchor::TopCenter => (50.0, 0.0),
+            NamedAnchor::BottomCenter => (50.0, 100.0),
+            NamedAnchor::LeftCenter => (0.0, 50.0),
+            NamedAnchor::RightCenter => (100.0, 50.0),
+        }
+    }
+}
+
+/// Where the crosshair is anchored before pixel offsets are applied
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum CrosshairAnchor {
+    Named(NamedAnchor),
+    /// Crosshair center at these percentages (0-100) of the screen width and height
+    Percent { x: f32, y: f32 },
+}
+
+impl Default for CrosshairAnchor {
+    fn default() -> Self {
+        CrosshairAnchor::Named(NamedAnchor::Center)
+    }
+}
+
+impl CrosshairAnchor {
+    /// Anchor point as (x, y) percentages of the screen size
+    pub fn percent(&self) -> (f32, f32) {
+        match self {
+            CrosshairAnchor::Named(anchor) => anchor.percent(),
+            CrosshairAnchor::Percent { x, y } => (*x, *y),
+        }
+    }
+
+    /// Check that percentage coordinates are within the screen
+    pub fn validate(&self) -> Result<(), String> {
+        let (x, y) = self.percent();
+        let in_range = |v: f32| v.is_finite() && (0.0..=100.0).contains(&v);
+        if !in_range(x) || !in_range(y) {
+            return Err("Crosshair position percentages must be between 0 and 100".to_string());
+        }
+        Ok(())
+    }
+
+    /// Top-left window position for a `width` x `height` crosshair on a
+    /// `screen_w` x `screen_h` display. The anchored crosshair is kept fully on screen
+    /// (e.g. top-center sits flush with the top edge) before the offsets are added.
+    pub fn window_position(
+        &self,
+        screen_w: i32,
+        screen_h: i32,
+        width: i32,
+        height: i32,
+        x_offset: i32,
+        y_offset: i32,
+    ) -> (i32, i32) {
+        let (px, py) = self.percent();
+        let place = |screen: i32, size: i32, percent: f32| {
+            let center = (screen as f32 * percent / 100.0).round() as i32;
+            (center - size / 2).clamp(0, (screen - size).max(0))
+        };
+
+        (
+            place(screen_w, width, px) + x_offset,
+            place(screen_h, height, py) + y_offset,
+        )
+    }
+}
+
+impl fmt::Display for CrosshairAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrosshairAnchor::Named(NamedAnchor::Center) => write!(f, "Center"),
+            CrosshairAnchor::Named(NamedAnchor::TopCenter) => write!(f, "Top center"),
+            CrosshairAnchor::Named(NamedAnchor::BottomCenter) => write!(f, "Bottom center"),
+            CrosshairAnchor::Named(NamedAnchor::LeftCenter) => write!(f, "Left center"),
+            CrosshairAnchor::Named(NamedAnchor::RightCenter) => write!(f, "Right center"),
+            CrosshairAnchor::Percent { .. } => write!(f, "Custom %"),
+        }
+    }
+}
+
+impl FromStr for CrosshairAnchor {
+    type Err = ();
+
+    /// Parse the `x,y` percentage form passed to crosshair.exe
+    fn from_str(arg: &str) -> Result<Self, Self::Err> {
+        let (x, y) = arg.split_once(',').ok_or(())?;
+        let anchor = CrosshairAnchor::Percent {
+            x: x.trim().parse().map_err(|_| ())?,
+            y: y.trim().parse().map_err(|_| ())?,
+        };
+        anchor.validate().map_err(|_| ())?;
+        Ok(anchor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_center_matches_offset_positioning() {
+        let anchor = CrosshairAnchor::default();
+        assert_eq!(anchor.window_position(1920, 1080, 100, 100, 0, 0), (910, 490));
+        assert_eq!(anchor.window_position(1920, 1080, 100, 100, 5, -3), (915, 487));
+    }
+
+    #[test]
+    fn test_percent_scales_with_resolution() {
+        let anchor = CrosshairAnchor::Percent { x: 50.0, y: 45.0 };
+        // Crosshair center lands on 45% of the height on both monitors
+        assert_eq!(anchor.window_position(1920, 1080, 100, 100, 0, 0), (910, 436));
+        assert_eq!(anchor.window_position(2560, 1440, 100, 100, 0, 0), (1230, 598));
+    }
+
+    #[test]
+    fn test_named_anchors_stay_on_screen() {
+        let top = CrosshairAnchor::Named(NamedAnchor::TopCenter);
+        assert_eq!(top.window_position(1920, 1080, 100, 100, 0, 0), (910, 0));
+        let right = CrosshairAnchor::Named(NamedAnchor::RightCenter);
+        assert_eq!(right.window_position(1920, 1080, 100, 100, 0, 0), (1820, 490));
+    }
+
+    #[test]
+    fn test_validate_and_parse() {
+        assert!(CrosshairAnchor::Percent { x: 101.0, y: 50.0 }.validate().is_err());
+        assert!(CrosshairAnchor::Percent { x: f32::NAN, y: 50.0 }.validate().is_err());
+        assert_eq!(
+            "50,45.5".parse::<CrosshairAnchor>(),
+            Ok(CrosshairAnchor::Percent { x: 50.0, y: 45.5 })
+        );
+        assert!("50".parse::<CrosshairAnchor>().is_err());
+        assert!("50,-1".parse::<CrosshairAnchor>().is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! Display resolution and refresh-rate switching
+//!
+//! Lets a profile force a specific mode (e.g. 1280x960 @ 240 Hz for stretched CS) on a chosen
+//! monitor. Modes are applied with CDS_FULLSCREEN so they are never written to the registry;
+//! restoring (or this process exiting) brings back the desktop mode.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::info;
+
+/// A display mode (32-bit color is assumed)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DisplayMode {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_hz: u32,
+}
+
+impl fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{} @ {} Hz", self.width, self.height, self.refresh_hz)
+    }
+}
+
+/// Mode a profile forces on a monitor
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisplaySetting {
+    /// GDI device name, e.g. `\\.\DISPLAY1`
+    pub device: String,
+    pub mode: DisplayMode,
+}
+
+/// A monitor attached to the desktop
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Monitor {
+    /// GDI device name, e.g. `\\.\DISPLAY1`
+    pub device: String,
+    /// Adapter description shown next to the device name
+    pub description: String,
+    pub primary: bool,
+}
+
+impl fmt::Display for Monitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.device.trim_start_matches("\\\\.\\");
+        write!(f, "{} ({})", name, self.description)?;
+        if self.primary {
+            write!(f, " - primary")?;
+        }
+        Ok(())
+    }
+}
+
+/// Sort modes from highest resolution/refresh rate down and drop duplicates
+/// (drivers list each mode once per color depth and scaling option)
+fn normalize_modes(mut modes: Vec<DisplayMode>) -> Vec<DisplayMode> {
+    modes.sort_unstable_by(|a, b| b.cmp(a));
+    modes.dedup();
+    modes
+}
+
+#[cfg(windows)]
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+#[cfg(windows)]
+fn from_wide(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
+
+#[cfg(windows)]
+fn empty_devmode() -> windows::Win32::Graphics::Gdi::DEVMODEW {
+    windows::Win32::Graphics::Gdi::DEVMODEW {
+        dmSize: std::mem::size_of::<windows::Win32::Graphics::Gdi::DEVMODEW>() as u16,
+        ..Default::default()
+    }
+}
+
+/// Monitors attached to the desktop, primary first
+pub fn list_monitors() -> Result<Vec<Monitor>> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::Graphics::Gdi::{
+            EnumDisplayDevicesW, DISPLAY_DEVICEW, DISPLAY_DEVICE_ATTACHED_TO_DESKTOP,
+            DISPLAY_DEVICE_PRIMARY_DEVICE,
+        };
+
+        let mut monitors = Vec::new();
+        for index in 0.. {
+            let mut device = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+            if !unsafe { EnumDisplayDevicesW(PCWSTR::null(), index, &mut device, 0) }.as_bool() {
+                break;
+            }
+            if device.StateFlags & DISPLAY_DEVICE_ATTACHED_TO_DESKTOP == 0 {
+                continue;
+            }
+            monitors.push(Monitor {
+                device: from_wide(&device.DeviceName),
+                description: from_wide(&device.DeviceString),
+                primary: device.StateFlags & DISPLAY_DEVICE_PRIMARY_DEVICE != 0,
+            });
+        }
+
+        if monitors.is_empty() {
+            return Err(anyhow!("No monitors found"));
+        }
+        monitors.sort_by_key(|monitor| !monitor.primary);
+        Ok(monitors)
+    }
+
+    #[cfg(not(windows))]
+    Err(anyhow!("Display modes are only available on Windows"))
+}
+
+/// Modes supported by a monitor, highest first
+pub fn list_modes(device: &str) -> Result<Vec<DisplayMode>> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::Graphics::Gdi::{EnumDisplaySettingsW, ENUM_DISPLAY_SETTINGS_MODE};
+
+        let device_w = wide(device);
+        let mut modes = Vec::new();
+        for index in 0.. {
+            let mut devmode = empty_devmode();
+            let found = unsafe {
+                EnumDisplaySettingsW(PCWSTR(device_w.as_ptr()), ENUM_DISPLAY_SETTINGS_MODE(index), &mut devmode)
+            };
+            if !found.as_bool() {
+                break;
+            }
+            if devmode.dmBitsPerPel == 32 {
+                modes.push(DisplayMode {
+                    width: devmode.dmPelsWidth,
+                    height: devmode.dmPelsHeight,
+                    refresh_hz: devmode.dmDisplayFrequency,
+                });
+            }
+        }
+
+        if modes.is_empty() {
+            return Err(anyhow!("No display modes found for {}", device));
+        }
+        Ok(normalize_modes(modes))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = device;
+        Err(anyhow!("Display modes are only available on Windows"))
+    }
+}
+
+/// Switch a monitor to the given mode without saving it to the registry
+pub fn set_mode(device: &str, mode: DisplayMode) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::Graphics::Gdi::{
+            ChangeDisplaySettingsExW, CDS_FULLSCREEN, DISP_CHANGE_SUCCESSFUL, DM_BITSPERPEL,
+            DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
+        };
+
+        let device_w = wide(device);
+        let mut devmode = empty_devmode();
+        devmode.dmFields = DM_PELSWIDTH | DM_PELSHEIGHT | DM_DISPLAYFREQUENCY | DM_BITSPERPEL;
+        devmode.dmPelsWidth = mode.width;
+        devmode.dmPelsHeight = mode.height;
+        devmode.dmDisplayFrequency = mode.refresh_hz;
+        devmode.dmBitsPerPel = 32;
+
+        let result = unsafe {
+            ChangeDisplaySettingsExW(PCWSTR(device_w.as_ptr()), Some(&devmode), HWND::default(), CDS_FULLSCREEN, None)
+        };
+        if result != DISP_CHANGE_SUCCESSFUL {
+            return Err(anyhow!("Failed to set {} on {} (error {})", mode, device, result.0));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (device, mode);
+        Err(anyhow!("Display modes are only available on Windows"))
+    }
+}
+
+/// Return a monitor to the mode saved in the registry (the desktop mode)
+pub fn restore_desktop_mode(device: &str) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::Graphics::Gdi::{ChangeDisplaySettingsExW, CDS_TYPE, DISP_CHANGE_SUCCESSFUL};
+
+        let device_w = wide(device);
+        let result = unsafe {
+            ChangeDisplaySettingsExW(PCWSTR(device_w.as_ptr()), None, HWND::default(), CDS_TYPE(0), None)
+        };
+        if result != DISP_CHANGE_SUCCESSFUL {
+            return Err(anyhow!("Failed to restore desktop mode on {} (error {})", device, result.0));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = device;
+        Err(anyhow!("Display modes are only available on Windows"))
+    }
+}
+
+/// Applies a profile's display mode and remembers which monitor to restore
+#[derive(Default)]
+pub struct DisplayModeSwitcher {
+    /// Monitor whose mode was changed (None = nothing to restore)
+    changed_device: Option<String>,
+}
+
+impl DisplayModeSwitcher {
+    /// Switch to the profile's mode, restoring any other monitor changed earlier
+    pub fn apply(&mut self, setting: &DisplaySetting) -> Result<()> {
+        if self.changed_device.as_deref().is_some_and(|device| device != setting.device) {
+            self.restore()?;
+        }
+        set_mode(&setting.device, setting.mode)?;
+        self.changed_device = Some(setting.device.clone());
+        info!("[Display] Set {} to {}", setting.device, setting.mode);
+        Ok(())
+    }
+
+    /// Restore the desktop mode (no-op if no mode was changed)
+    pub fn restore(&mut self) -> Result<()> {
+        let Some(device) = self.changed_device.take() else {
+            return Ok(());
+        };
+        restore_desktop_mode(&device)?;
+        info!("[Display] Restored desktop mode on {}", device);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mode(width: u32, height: u32, refresh_hz: u32) -> DisplayMode {
+        DisplayMode { width, height, refresh_hz }
+    }
+
+    #[test]
+    fn test_normalize_modes() {
+        let modes = vec![
+            mode(1280, 960, 240),
+            mode(1920, 1080, 60),
+            mode(1920, 1080, 240),
+            mode(1280, 960, 240),
+        ];
+        assert_eq!(
+            normalize_modes(modes),
+            vec![mode(1920, 1080, 240), mode(1920, 1080, 60), mode(1280, 960, 240)]
+        );
+    }
+
+    #[test]
+    fn test_display_labels() {
+        assert_eq!(mode(1280, 960, 240).to_string(), "1280x960 @ 240 Hz");
+        let monitor = Monitor {
+            device: "\\\\.\\DISPLAY1".to_string(),
+            description: "NVIDIA GeForce RTX 3070".to_string(),
+            primary: true,
+        };
+        assert_eq!(monitor.to_string(), "DISPLAY1 (NVIDIA GeForce RTX 3070) - primary");
+    }
+}
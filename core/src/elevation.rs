@@ -0,0 +1,210 @@
+//! Administrator rights
+//!
+//! Whether the app runs elevated is checked once at startup. Features that only work elevated
+//! (stopping services, QoS policies, the standby list purge, closing apps that run as
+//! administrator, the elevated logon task) are gated on it, so the editor can badge them and
+//! activation can skip them with a clear reason instead of an access-denied error. Timer
+//! resolution is requested per process and works without elevation.
+
+use crate::i18n::t;
+use crate::profile::Profile;
+use crate::tweaks::SystemTweak;
+use anyhow::{anyhow, Result};
+use once_cell::sync::OnceCell;
+use std::fmt;
+use tracing::info;
+
+/// Reason given for features skipped without elevation
+pub const NEEDS_ADMIN: &str = "Needs administrator rights (restart as administrator)";
+
+/// What this run of the app is allowed to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub elevated: bool,
+}
+
+/// A feature that only works when the app runs elevated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdminFeature {
+    /// Stopping Delivery Optimization, Windows Search and Windows Audio
+    ServiceControl,
+    /// Bandwidth limits (QoS policies)
+    NetworkPolicies,
+    /// Disabling the defrag and maintenance scheduled tasks
+    MaintenanceTasks,
+    StandbyPurge,
+    /// Closing apps that run as administrator
+    ElevatedProcesses,
+}
+
+impl fmt::Display for AdminFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            AdminFeature::ServiceControl => t!("admin-feature-services"),
+            AdminFeature::NetworkPolicies => t!("admin-feature-network-policies"),
+            AdminFeature::MaintenanceTasks => t!("admin-feature-maintenance-tasks"),
+            AdminFeature::StandbyPurge => t!("admin-feature-standby-purge"),
+            AdminFeature::ElevatedProcesses => t!("admin-feature-elevated-processes"),
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl AdminFeature {
+    /// What a tweak needs elevation for, if anything
+    pub fn for_tweak(tweak: SystemTweak) -> Option<AdminFeature> {
+        match tweak {
+            SystemTweak::PurgeStandbyList => Some(AdminFeature::StandbyPurge),
+            SystemTweak::RestartAudioService => Some(AdminFeature::ServiceControl),
+            SystemTweak::FlushDns => None,
+        }
+    }
+
+    /// Admin-only features a profile uses, in activation order without duplicates
+    pub fn required_by(profile: &Profile) -> Vec<AdminFeature> {
+        let mut features = Vec::new();
+        let mut add = |feature| {
+            if !features.contains(&feature) {
+                features.push(feature);
+            }
+        };
+        for feature in profile.system_tweaks.iter().filter_map(|&tweak| AdminFeature::for_tweak(tweak)) {
+            add(feature);
+        }
+        if profile.network.pause_delivery_optimization {
+            add(AdminFeature::ServiceControl);
+        }
+        if !profile.network.bandwidth_limits.is_empty() {
+            add(AdminFeature::NetworkPolicies);
+        }
+        if profile.storage.pause_search_indexing {
+            add(AdminFeature::ServiceControl);
+        }
+        if profile.storage.pause_maintenance_tasks {
+            add(AdminFeature::MaintenanceTasks);
+        }
+        features
+    }
+}
+
+impl Capabilities {
+    fn detect() -> Self {
+        let elevated = is_elevated();
+        info!("[Elevation] Running as administrator: {}", elevated);
+        Capabilities { elevated }
+    }
+
+    /// Admin-only features of `profile` that this run can't use
+    pub fn blocked(&self, profile: &Profile) -> Vec<AdminFeature> {
+        if self.elevated {
+            Vec::new()
+        } else {
+            AdminFeature::required_by(profile)
+        }
+    }
+}
+
+static CAPABILITIES: OnceCell<Capabilities> = OnceCell::new();
+
+/// This run's capabilities, detected on first use (main calls this at startup)
+pub fn capabilities() -> Capabilities {
+    *CAPABILITIES.get_or_init(Capabilities::detect)
+}
+
+/// Err with `NEEDS_ADMIN` when not elevated
+pub fn require_admin() -> Result<()> {
+    if capabilities().elevated {
+        Ok(())
+    } else {
+        Err(anyhow!(NEEDS_ADMIN))
+    }
+}
+
+#[cfg(windows)]
+fn is_elevated() -> bool {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    unsafe {
+        let mut token = HANDLE::default();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
+            return false;
+        }
+        let mut elevation = TOKEN_ELEVATION::default();
+        let mut size = 0u32;
+        let result = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut TOKEN_ELEVATION as *mut _),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut size,
+        );
+        let _ = CloseHandle(token);
+        result.is_ok() && elevation.TokenIsElevated != 0
+    }
+}
+
+#[cfg(not(windows))]
+fn is_elevated() -> bool {
+    false
+}
+
+/// Start a copy of the app through the UAC prompt with `args`; fails when the prompt is declined
+pub fn restart_as_admin(args: &[&str]) -> Result<()> {
+    let exe = std::env::current_exe().map_err(|e| anyhow!("Failed to find the app's exe: {}", e))?;
+
+    #[cfg(windows)]
+    unsafe {
+        use windows::core::{HSTRING, PCWSTR};
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::Shell::ShellExecuteW;
+        use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+        let result = ShellExecuteW(
+            HWND::default(),
+            &HSTRING::from("runas"),
+            &HSTRING::from(exe.as_os_str()),
+            &HSTRING::from(args.join(" ")),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        );
+        // Values up to 32 are error codes (the declined prompt is one of them)
+        if result.0 <= 32 {
+            return Err(anyhow!("Failed to start as administrator (code {})", result.0));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (exe, args);
+        Err(anyhow!("Only available on Windows"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::BandwidthLimit;
+    use crate::profile::create_profile;
+
+    #[test]
+    fn test_required_by() {
+        let mut profile = create_profile("FPS".to_string());
+        profile.system_tweaks = vec![SystemTweak::FlushDns];
+        assert!(AdminFeature::required_by(&profile).is_empty());
+
+        profile.system_tweaks.push(SystemTweak::RestartAudioService);
+        profile.network.pause_delivery_optimization = true;
+        profile.storage.pause_search_indexing = true;
+        profile.network.bandwidth_limits.push(BandwidthLimit { process: "steam.exe".to_string(), limit_kbps: 2000 });
+        assert_eq!(
+            AdminFeature::required_by(&profile),
+            [AdminFeature::ServiceControl, AdminFeature::NetworkPolicies]
+        );
+
+        assert!(Capabilities { elevated: true }.blocked(&profile).is_empty());
+        assert_eq!(Capabilities { elevated: false }.blocked(&profile).len(), 2);
+    }
+}
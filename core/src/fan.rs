@@ -0,0 +1,336 @@
+//! Fan control with pluggable hardware backends
+//!
+//! Laptops expose fan control through vendor-specific interfaces, so each one is a
+//! `FanBackend`. The first backend that detects its interface is used to force maximum
+//! cooling on profile activation and to restore the previous behaviour on deactivation.
+
+use anyhow::{anyhow, Result};
+use crate::shell::{run_hidden, run_powershell};
+use std::path::PathBuf;
+use tracing::info;
+
+/// A hardware-specific way of driving the fans
+pub trait FanBackend {
+    /// Human-readable backend name shown in the status bar
+    fn name(&self) -> &'static str;
+
+    /// Whether this backend's interface is present on the current machine
+    fn detect(&mut self) -> bool;
+
+    /// Force the fans to maximum (remembering anything needed for `restore`)
+    fn set_max(&mut self) -> Result<()>;
+
+    /// Return the fans to the behaviour they had before `set_max`
+    fn restore(&mut self) -> Result<()>;
+}
+
+/// Picks a supported backend and tracks whether fans are currently forced to max
+pub struct FanController {
+    backends: Vec<Box<dyn FanBackend>>,
+    /// Index of the backend that applied max speed (None = fans untouched)
+    applied: Option<usize>,
+}
+
+impl FanController {
+    /// Controller with all built-in backends, in detection order
+    pub fn new() -> Self {
+        Self::with_backends(vec![
+            Box::new(NbfcBackend::default()),
+            Box::new(DellBackend::default()),
+            Box::new(LenovoBackend),
+            Box::new(AsusBackend),
+        ])
+    }
+
+    fn with_backends(backends: Vec<Box<dyn FanBackend>>) -> Self {
+        FanController {
+            backends,
+            applied: None,
+        }
+    }
+
+    /// Whether the fans are currently forced to maximum by us
+    pub fn is_applied(&self) -> bool {
+        self.applied.is_some()
+    }
+
+    /// Force fans to maximum using the first detected backend
+    /// Returns the backend name on success
+    pub fn apply_max(&mut self) -> Result<&'static str> {
+        if let Some(index) = self.applied {
+            return Ok(self.backends[index].name());
+        }
+
+        let index = self
+            .backends
+            .iter_mut()
+            .position(|backend| backend.detect())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Fan control is not supported on this hardware (no NBFC, Dell, Lenovo or ASUS fan interface found)"
+                )
+            })?;
+
+        let backend = &mut self.backends[index];
+        backend
+            .set_max()
+            .map_err(|e| anyhow!("{} fan control failed: {}", backend.name(), e))?;
+
+        info!("[Fan] Max fan speed applied via {}", backend.name());
+        self.applied = Some(index);
+        Ok(backend.name())
+    }
+
+    /// Undo `apply_max` (no-op if the fans were never touched)
+    pub fn restore(&mut self) -> Result<()> {
+        let Some(index) = self.applied.take() else {
+            return Ok(());
+        };
+
+        let backend = &mut self.backends[index];
+        backend
+            .restore()
+            .map_err(|e| anyhow!("Failed to restore fans via {}: {}", backend.name(), e))?;
+
+        info!("[Fan] Fan control restored via {}", backend.name());
+        Ok(())
+    }
+}
+
+impl Default for FanController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a WMI class exists in the root\WMI namespace
+fn wmi_class_exists(class: &str) -> bool {
+    run_powershell(&format!(
+        "Get-CimInstance -Namespace root/WMI -ClassName {} -ErrorAction Stop | Out-Null",
+        class
+    ))
+    .is_ok()
+}
+
+/// First existing path among candidates under the Program Files directories
+fn find_program(relative_paths: &[&str]) -> Option<PathBuf> {
+    ["ProgramFiles", "ProgramFiles(x86)"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .flat_map(|root| relative_paths.iter().map(move |rel| PathBuf::from(&root).join(rel)))
+        .find(|path| path.exists())
+}
+
+/// NoteBook FanControl (NBFC) - works on many laptops through community EC configs
+#[derive(Default)]
+struct NbfcBackend {
+    exe: Option<PathBuf>,
+}
+
+impl FanBackend for NbfcBackend {
+    fn name(&self) -> &'static str {
+        "NBFC"
+    }
+
+    fn detect(&mut self) -> bool {
+        self.exe = find_program(&["NoteBook FanControl\\nbfc.exe"]);
+        // `status` only succeeds when the service is running with a config loaded
+        match self.exe {
+            Some(ref exe) => run_hidden(&exe.to_string_lossy(), &["status"]).is_ok(),
+            None => false,
+        }
+    }
+
+    fn set_max(&mut self) -> Result<()> {
+        let exe = self.exe.as_ref().ok_or_else(|| anyhow!("nbfc.exe not found"))?;
+        run_hidden(&exe.to_string_lossy(), &["set", "--speed", "100"]).map(|_| ())
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        let exe = self.exe.as_ref().ok_or_else(|| anyhow!("nbfc.exe not found"))?;
+        run_hidden(&exe.to_string_lossy(), &["set", "--auto"]).map(|_| ())
+    }
+}
+
+/// Dell thermal management through Dell Command | Configure (cctk)
+#[derive(Default)]
+struct DellBackend {
+    exe: Option<PathBuf>,
+    /// Thermal mode that was active before switching to UltraPerformance
+    previous_mode: Option<String>,
+}
+
+impl DellBackend {
+    fn cctk(&self, arg: &str) -> Result<String> {
+        let exe = self.exe.as_ref().ok_or_else(|| anyhow!("cctk.exe not found"))?;
+        run_hidden(&exe.to_string_lossy(), &[arg])
+    }
+}
+
+impl FanBackend for DellBackend {
+    fn name(&self) -> &'static str {
+        "Dell Command | Configure"
+    }
+
+    fn detect(&mut self) -> bool {
+        self.exe = find_program(&[
+            "Dell\\Command Configure\\X86_64\\cctk.exe",
+            "Dell\\Command Configure\\X86\\cctk.exe",
+        ]);
+        self.exe.is_some() && self.cctk("--ThermalManagement").is_ok()
+    }
+
+    fn set_max(&mut self) -> Result<()> {
+        let current = self.cctk("--ThermalManagement")?;
+        self.previous_mode = parse_cctk_value(&current, "ThermalManagement");
+        self.cctk("--ThermalManagement=UltraPerformance").map(|_| ())
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        let mode = self.previous_mode.take().unwrap_or_else(|| "Optimized".to_string());
+        self.cctk(&format!("--ThermalManagement={}", mode)).map(|_| ())
+    }
+}
+
+/// Parse `Name=Value` from cctk output
+fn parse_cctk_value(output: &str, name: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.trim().split_once('=')?;
+        (key.trim() == name && !value.trim().is_empty()).then(|| value.trim().to_string())
+    })
+}
+
+/// Lenovo Legion/LOQ full-speed fan switch (LENOVO_FAN_METHOD WMI ACPI method)
+struct LenovoBackend;
+
+impl LenovoBackend {
+    fn set_full_speed(enabled: bool) -> Result<()> {
+        run_powershell(&format!(
+            "Invoke-CimMethod -Namespace root/WMI -ClassName LENOVO_FAN_METHOD -MethodName Fan_Set_FullSpeed -Arguments @{{Status={}}} -ErrorAction Stop | Out-Null",
+            if enabled { 1 } else { 0 }
+        ))
+        .map(|_| ())
+    }
+}
+
+impl FanBackend for LenovoBackend {
+    fn name(&self) -> &'static str {
+        "Lenovo WMI"
+    }
+
+    fn detect(&mut self) -> bool {
+        wmi_class_exists("LENOVO_FAN_METHOD")
+    }
+
+    fn set_max(&mut self) -> Result<()> {
+        Self::set_full_speed(true)
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        Self::set_full_speed(false)
+    }
+}
+
+/// ASUS ATK ACPI device - throttle thermal policy (0 = default, 1 = turbo/overboost)
+struct AsusBackend;
+
+/// ATK device ID of the thermal policy control
+const ASUS_DEVID_THERMAL_POLICY: u32 = 0x0012_0075;
+
+impl AsusBackend {
+    fn set_policy(policy: u32) -> Result<()> {
+        run_powershell(&format!(
+            "$atk = Get-CimInstance -Namespace root/WMI -ClassName AsusAtkWmi_WMNB -ErrorAction Stop; \
+             Invoke-CimMethod -InputObject $atk -MethodName DEVS -Arguments @{{Device_ID={}; Control_Status={}}} -ErrorAction Stop | Out-Null",
+            ASUS_DEVID_THERMAL_POLICY, policy
+        ))
+        .map(|_| ())
+    }
+}
+
+impl FanBackend for AsusBackend {
+    fn name(&self) -> &'static str {
+        "ASUS ATK WMI"
+    }
+
+    fn detect(&mut self) -> bool {
+        wmi_class_exists("AsusAtkWmi_WMNB")
+    }
+
+    fn set_max(&mut self) -> Result<()> {
+        Self::set_policy(1)
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        Self::set_policy(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Backend that records calls instead of touching hardware
+    struct FakeBackend {
+        present: bool,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl FanBackend for FakeBackend {
+        fn name(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn detect(&mut self) -> bool {
+            self.present
+        }
+
+        fn set_max(&mut self) -> Result<()> {
+            self.log.borrow_mut().push("max".to_string());
+            Ok(())
+        }
+
+        fn restore(&mut self) -> Result<()> {
+            self.log.borrow_mut().push("restore".to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_unsupported_hardware_error() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut controller = FanController::with_backends(vec![Box::new(FakeBackend { present: false, log })]);
+        let err = controller.apply_max().unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+        assert!(!controller.is_applied());
+        // Restoring untouched fans is a no-op
+        assert!(controller.restore().is_ok());
+    }
+
+    #[test]
+    fn test_apply_and_restore_use_detected_backend() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut controller = FanController::with_backends(vec![
+            Box::new(FakeBackend { present: false, log: log.clone() }),
+            Box::new(FakeBackend { present: true, log: log.clone() }),
+        ]);
+
+        assert_eq!(controller.apply_max().unwrap(), "Fake");
+        // Applying twice doesn't re-run the backend
+        controller.apply_max().unwrap();
+        assert!(controller.is_applied());
+        controller.restore().unwrap();
+        controller.restore().unwrap();
+
+        assert_eq!(*log.borrow(), vec!["max", "restore"]);
+    }
+
+    #[test]
+    fn test_parse_cctk_value() {
+        let output = "ThermalManagement=Optimized\r\n";
+        assert_eq!(parse_cctk_value(output, "ThermalManagement"), Some("Optimized".to_string()));
+        assert_eq!(parse_cctk_value("Error: not supported", "ThermalManagement"), None);
+    }
+}
@@ -0,0 +1,130 @@
+//! Reload the profile file and config.json when they change on disk
+//!
+//! Watches the data directory so edits from a text editor or a sync tool show up in the running
+//! app. Saving a file usually fires several events in a row, so a change is only reported once
+//! the file has been quiet for `DEBOUNCE`.
+
+use crate::ipc::{self, DataFile};
+use crate::profile::PROFILE_FILES;
+use anyhow::{anyhow, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Quiet time after the last event before a file counts as written
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches the data directory until dropped
+pub struct DataWatcher {
+    _watcher: RecommendedWatcher,
+    events: Receiver<DataFile>,
+    debouncer: Debouncer,
+}
+
+impl DataWatcher {
+    pub fn spawn(data_dir: &Path) -> Result<Self> {
+        if crate::config::is_safe_mode() {
+            return Err(anyhow!("File watching is off in safe mode"));
+        }
+        let (tx, events) = channel();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| match result {
+            Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                for file in event.paths.iter().filter_map(|path| data_file(path)) {
+                    let _ = tx.send(file);
+                    ipc::wake();
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("[Watch] {}", e),
+        })
+        .map_err(|e| anyhow!("Failed to create file watcher: {}", e))?;
+        watcher
+            .watch(data_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| anyhow!("Failed to watch {}: {}", data_dir.display(), e))?;
+
+        info!("[Watch] Watching {}", data_dir.display());
+        Ok(DataWatcher { _watcher: watcher, events, debouncer: Debouncer::default() })
+    }
+
+    /// Whether a change is waiting to settle (poll again within `DEBOUNCE`)
+    pub fn is_settling(&self) -> bool {
+        !self.debouncer.pending.is_empty()
+    }
+
+    /// Files that changed and have settled since the last call
+    pub fn poll(&mut self) -> Vec<DataFile> {
+        let now = Instant::now();
+        while let Ok(file) = self.events.try_recv() {
+            self.debouncer.event(file, now);
+        }
+        self.debouncer.settled(now)
+    }
+}
+
+fn data_file(path: &Path) -> Option<DataFile> {
+    match path.file_name()?.to_str()? {
+        "config.json" => Some(DataFile::Config),
+        name if PROFILE_FILES.contains(&name) => Some(DataFile::Profiles),
+        _ => None,
+    }
+}
+
+/// Time of the latest event per file, until it's been quiet long enough
+#[derive(Default)]
+struct Debouncer {
+    pending: HashMap<DataFile, Instant>,
+}
+
+impl Debouncer {
+    fn event(&mut self, file: DataFile, now: Instant) {
+        self.pending.insert(file, now);
+    }
+
+    fn settled(&mut self, now: Instant) -> Vec<DataFile> {
+        let mut settled: Vec<DataFile> = self
+            .pending
+            .iter()
+            .filter(|(_, &last)| now.duration_since(last) >= DEBOUNCE)
+            .map(|(&file, _)| file)
+            .collect();
+        settled.sort_by_key(|file| *file as u8);
+        for file in &settled {
+            self.pending.remove(file);
+        }
+        settled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_file() {
+        assert_eq!(data_file(Path::new("Data/profiles.json")), Some(DataFile::Profiles));
+        assert_eq!(data_file(Path::new("profiles.toml")), Some(DataFile::Profiles));
+        assert_eq!(data_file(Path::new("config.json")), Some(DataFile::Config));
+        assert_eq!(data_file(Path::new("profiles.json.tmp")), None);
+        assert_eq!(data_file(Path::new("sessions.json")), None);
+    }
+
+    #[test]
+    fn test_debounce_rapid_writes() {
+        let mut debouncer = Debouncer::default();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        // Three writes in quick succession are one change, reported after the last settles
+        debouncer.event(DataFile::Profiles, at(0));
+        debouncer.event(DataFile::Profiles, at(100));
+        debouncer.event(DataFile::Config, at(150));
+        debouncer.event(DataFile::Profiles, at(200));
+        assert!(debouncer.settled(at(400)).is_empty());
+        assert_eq!(debouncer.settled(at(460)), vec![DataFile::Config]);
+        assert_eq!(debouncer.settled(at(500)), vec![DataFile::Profiles]);
+        assert!(debouncer.settled(at(2000)).is_empty());
+    }
+}
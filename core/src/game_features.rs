@@ -0,0 +1,116 @@
+//! Windows Game Mode / Game Bar / background recording toggles
+//!
+//! Each feature maps to a few per-user registry values. The original values are captured
+//! on activation and written back (or deleted again) on deactivation.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use crate::registry::{Hive, RegistryChanges};
+use tracing::info;
+
+/// Per-profile feature switches (None = leave the user's setting alone)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GameFeatureToggles {
+    #[serde(default)]
+    pub game_mode: Option<bool>,
+    #[serde(default)]
+    pub game_bar: Option<bool>,
+    #[serde(default)]
+    pub background_recording: Option<bool>,
+}
+
+impl GameFeatureToggles {
+    /// Whether any feature is set to be changed
+    pub fn is_empty(&self) -> bool {
+        self.game_mode.is_none() && self.game_bar.is_none() && self.background_recording.is_none()
+    }
+}
+
+/// A registry DWORD backing a feature (1 = enabled, 0 = disabled)
+struct RegistryValue {
+    subkey: &'static str,
+    name: &'static str,
+}
+
+const GAME_MODE_VALUES: &[RegistryValue] = &[
+    RegistryValue { subkey: "Software\\Microsoft\\GameBar", name: "AutoGameModeEnabled" },
+    RegistryValue { subkey: "Software\\Microsoft\\GameBar", name: "AllowAutoGameMode" },
+];
+
+const GAME_BAR_VALUES: &[RegistryValue] = &[
+    RegistryValue { subkey: "Software\\Microsoft\\GameBar", name: "UseNexusForGameBarEnabled" },
+    RegistryValue { subkey: "Software\\Microsoft\\Windows\\CurrentVersion\\GameDVR", name: "AppCaptureEnabled" },
+    RegistryValue { subkey: "System\\GameConfigStore", name: "GameDVR_Enabled" },
+];
+
+const BACKGROUND_RECORDING_VALUES: &[RegistryValue] = &[
+    RegistryValue { subkey: "Software\\Microsoft\\Windows\\CurrentVersion\\GameDVR", name: "HistoricalCaptureEnabled" },
+];
+
+/// Human-readable summary of the requested changes, e.g. ["Game Mode ON", "Game Bar OFF"]
+pub fn describe(toggles: &GameFeatureToggles) -> Vec<String> {
+    let on_off = |enabled: bool| if enabled { "ON" } else { "OFF" };
+    [
+        ("Game Mode", toggles.game_mode),
+        ("Game Bar", toggles.game_bar),
+        ("Background recording", toggles.background_recording),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| value.map(|enabled| format!("{} {}", label, on_off(enabled))))
+    .collect()
+}
+
+/// Applies feature toggles and remembers the values they replaced
+#[derive(Default)]
+pub struct GameFeatureSwitcher {
+    changes: RegistryChanges,
+}
+
+impl GameFeatureSwitcher {
+    /// Restore any previous profile's changes, then apply the new toggles
+    pub fn apply(&mut self, toggles: &GameFeatureToggles) -> Result<()> {
+        self.restore()?;
+
+        let features = [
+            (GAME_MODE_VALUES, toggles.game_mode),
+            (GAME_BAR_VALUES, toggles.game_bar),
+            (BACKGROUND_RECORDING_VALUES, toggles.background_recording),
+        ];
+        for (values, enabled) in features {
+            let Some(enabled) = enabled else {
+                continue;
+            };
+            for value in values {
+                self.changes.set_dword(Hive::CurrentUser, value.subkey, value.name, enabled as u32)?;
+            }
+        }
+
+        if !self.changes.is_empty() {
+            info!("[GameFeatures] Applied {}", describe(toggles).join(", "));
+        }
+        Ok(())
+    }
+
+    /// Put back every value changed by `apply` (no-op if nothing was changed)
+    pub fn restore(&mut self) -> Result<()> {
+        self.changes.restore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_toggles() {
+        let toggles = GameFeatureToggles {
+            game_mode: Some(true),
+            game_bar: Some(false),
+            background_recording: None,
+        };
+        assert!(!toggles.is_empty());
+        assert_eq!(describe(&toggles), vec!["Game Mode ON", "Game Bar OFF"]);
+        assert!(GameFeatureToggles::default().is_empty());
+        assert!(describe(&GameFeatureToggles::default()).is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+//! Per-game recommendations
+//!
+//! A curated list (resources/game_knowledge.json) of what helps popular games: launchers and
+//! sync clients to close, the power plan and timer resolution to use, and overlays known to
+//! conflict with the game or its anti-cheat. A profile is matched to a game by its name or by one
+//! of the game's executables running, and the editor offers the missing pieces as one-click
+//! suggestions. `game_knowledge.json` in the data directory adds games or replaces entries with
+//! the same name.
+
+use crate::profile::Profile;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use tracing::warn;
+
+/// Built-in list
+const BUNDLED: &str = include_str!("../resources/game_knowledge.json");
+
+/// User additions in the data directory
+pub const USER_FILE: &str = "game_knowledge.json";
+
+/// Overlay that is known to cause trouble with a game
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConflictingOverlay {
+    pub name: String,
+    pub executable: String,
+    /// Why it conflicts, shown next to the suggestion
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// Recommendations for one game
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GameEntry {
+    pub name: String,
+    /// Words in a profile name that mean this game (the game name always counts)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Game executables; the game counts as matched while one is running
+    #[serde(default)]
+    pub executables: Vec<String>,
+    /// Processes worth closing while playing
+    #[serde(default)]
+    pub kill: Vec<String>,
+    /// Power plan GUID to switch to
+    #[serde(default)]
+    pub power_plan: Option<String>,
+    #[serde(default)]
+    pub high_precision_timer: bool,
+    #[serde(default)]
+    pub conflicting_overlays: Vec<ConflictingOverlay>,
+}
+
+/// A change the editor can apply with one click
+#[derive(Debug, Clone, PartialEq)]
+pub enum Suggestion {
+    /// Add a process to the kill list
+    Kill(String),
+    /// Add a conflicting overlay's process to the kill list
+    CloseOverlay(ConflictingOverlay),
+    /// Switch to this power plan while active
+    PowerPlan(String),
+    HighPrecisionTimer,
+}
+
+/// Whether `needle` appears in `haystack` as whole words (both lowercase)
+fn contains_words(haystack: &str, needle: &str) -> bool {
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+impl GameEntry {
+    /// Whether a profile called `profile_name` is for this game
+    pub fn matches_name(&self, profile_name: &str) -> bool {
+        let profile_name = profile_name.to_lowercase();
+        std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .any(|alias| !alias.trim().is_empty() && contains_words(&profile_name, &alias.to_lowercase()))
+    }
+
+    /// Whether one of the game's executables is among `running`
+    pub fn is_running<'a>(&self, mut running: impl Iterator<Item = &'a str>) -> bool {
+        running.any(|name| self.executables.iter().any(|exe| exe.eq_ignore_ascii_case(name)))
+    }
+
+    /// Recommendations `profile` doesn't follow yet; `power_plans` are the installed plan GUIDs
+    pub fn suggestions(&self, profile: &Profile, power_plans: &[String]) -> Vec<Suggestion> {
+        let listed = |exe: &str| profile.processes_to_kill.iter().any(|name| name.eq_ignore_ascii_case(exe));
+        let mut suggestions: Vec<Suggestion> = self
+            .kill
+            .iter()
+            .filter(|exe| !listed(exe))
+            .map(|exe| Suggestion::Kill(exe.clone()))
+            .collect();
+        suggestions.extend(
+            self.conflicting_overlays
+                .iter()
+                .filter(|overlay| !listed(&overlay.executable))
+                .map(|overlay| Suggestion::CloseOverlay(overlay.clone())),
+        );
+        if let Some(ref guid) = self.power_plan {
+            let installed = power_plans.iter().any(|plan| plan.eq_ignore_ascii_case(guid));
+            if installed && !profile.power_plan.as_ref().is_some_and(|plan| plan.eq_ignore_ascii_case(guid)) {
+                suggestions.push(Suggestion::PowerPlan(guid.to_lowercase()));
+            }
+        }
+        if self.high_precision_timer && !profile.high_precision_timer {
+            suggestions.push(Suggestion::HighPrecisionTimer);
+        }
+        suggestions
+    }
+}
+
+fn parse(contents: &str) -> Result<Vec<GameEntry>> {
+    serde_json::from_str(contents).map_err(|e| anyhow!("Failed to parse game recommendations: {}", e))
+}
+
+/// Bundled games plus the user's `game_knowledge.json` from `data_dir`; a user entry replaces
+/// the bundled one with the same name (any case)
+pub fn load_games(data_dir: Option<&Path>) -> Vec<GameEntry> {
+    let mut games = parse(BUNDLED).unwrap_or_else(|e| {
+        warn!("[Games] Bundled list: {}", e);
+        Vec::new()
+    });
+    let Some(path) = data_dir.map(|dir| dir.join(USER_FILE)).filter(|path| path.exists()) else {
+        return games;
+    };
+    match fs::read_to_string(&path).map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e)).and_then(|contents| parse(&contents)) {
+        Ok(extra) => {
+            for game in extra {
+                match games.iter_mut().find(|known| known.name.eq_ignore_ascii_case(&game.name)) {
+                    Some(known) => *known = game,
+                    None => games.push(game),
+                }
+            }
+        }
+        Err(e) => warn!("[Games] {}", e),
+    }
+    games
+}
+
+/// The game a profile is for: by profile name first, then by a running game executable
+pub fn find_game<'a>(games: &'a [GameEntry], profile_name: &str, running: &[&str]) -> Option<&'a GameEntry> {
+    games
+        .iter()
+        .find(|game| game.matches_name(profile_name))
+        .or_else(|| games.iter().find(|game| game.is_running(running.iter().copied())))
+}
+
+/// The game `exe` belongs to
+pub fn game_for_exe<'a>(games: &'a [GameEntry], exe: &str) -> Option<&'a GameEntry> {
+    games.iter().find(|game| game.is_running(std::iter::once(exe)))
+}
+
+/// Profile to activate when `exe` starts: the first one named after a game `exe` belongs to
+pub fn profile_for_launch<'a>(games: &[GameEntry], profiles: &'a [Profile], exe: &str) -> Option<&'a Profile> {
+    let game = game_for_exe(games, exe)?;
+    profiles.iter().find(|profile| !profile.archived && game.matches_name(&profile.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::create_profile;
+
+    const HIGH_PERFORMANCE: &str = "8c5e7fda-e8bf-4a96-9a85-cf48b21d3f87";
+
+    #[test]
+    fn test_find_game() {
+        let games = parse(BUNDLED).unwrap();
+        let name = |game: Option<&GameEntry>| game.map(|game| game.name.clone());
+
+        assert_eq!(name(find_game(&games, "CS2 ranked", &[])), Some("Counter-Strike 2".to_string()));
+        assert_eq!(name(find_game(&games, "valorant", &[])), Some("Valorant".to_string()));
+        // Whole words only: "Code" isn't Call of Duty
+        assert_eq!(name(find_game(&games, "Code", &[])), None);
+        assert_eq!(name(find_game(&games, "Tryhard", &["explorer.exe", "R5Apex.exe"])), Some("Apex Legends".to_string()));
+        assert_eq!(name(find_game(&games, "Work", &["explorer.exe"])), None);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let games = parse(BUNDLED).unwrap();
+        let valorant = find_game(&games, "Valorant", &[]).unwrap();
+        let mut profile = create_profile("Valorant".to_string());
+        profile.processes_to_kill = vec!["steam.exe".to_string()];
+
+        let suggestions = valorant.suggestions(&profile, &[HIGH_PERFORMANCE.to_uppercase()]);
+        assert!(!suggestions.contains(&Suggestion::Kill("Steam.exe".to_string())));
+        assert!(suggestions.contains(&Suggestion::Kill("EpicGamesLauncher.exe".to_string())));
+        assert!(suggestions.iter().any(|s| matches!(s, Suggestion::CloseOverlay(overlay) if overlay.executable == "RTSS.exe")));
+        assert!(suggestions.contains(&Suggestion::PowerPlan(HIGH_PERFORMANCE.to_string())));
+        assert!(suggestions.contains(&Suggestion::HighPrecisionTimer));
+
+        // Followed recommendations and plans that aren't installed aren't suggested
+        profile.high_precision_timer = true;
+        let suggestions = valorant.suggestions(&profile, &[]);
+        assert!(!suggestions.iter().any(|s| matches!(s, Suggestion::PowerPlan(_) | Suggestion::HighPrecisionTimer)));
+    }
+
+    #[test]
+    fn test_profile_for_launch() {
+        let games = parse(BUNDLED).unwrap();
+        let mut profiles = vec![create_profile("Work".to_string()), create_profile("CS2 ranked".to_string())];
+        let name = |profile: Option<&Profile>| profile.map(|profile| profile.name.clone());
+
+        assert_eq!(name(profile_for_launch(&games, &profiles, "cs2.exe")), Some("CS2 ranked".to_string()));
+        assert_eq!(name(profile_for_launch(&games, &profiles, "notepad.exe")), None);
+        // A game without a profile, and archived profiles, activate nothing
+        assert_eq!(name(profile_for_launch(&games, &profiles, "VALORANT-Win64-Shipping.exe")), None);
+        profiles[1].archived = true;
+        assert_eq!(name(profile_for_launch(&games, &profiles, "cs2.exe")), None);
+    }
+}
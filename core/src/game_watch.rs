@@ -0,0 +1,178 @@
+//! Game launch detection
+//!
+//! Reports processes as they start, so a game's profile can be activated within about a
+//! second of launch. A WMI event subscription does the waiting: `Win32_ProcessStartTrace` when
+//! running as administrator (kernel start events, nothing polled), otherwise an instance
+//! creation event that WMI checks once a second. Where WMI can't be subscribed to, the process
+//! list is polled instead.
+
+use crate::ipc;
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, System};
+use tracing::{info, warn};
+
+/// Interval of the polling fallback
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Longest wait for an event before checking whether the watcher was dropped
+#[cfg(windows)]
+const EVENT_WAIT: Duration = Duration::from_millis(500);
+
+/// Watches for new processes until dropped
+pub struct GameWatcher {
+    started: Receiver<String>,
+    running: Arc<AtomicBool>,
+}
+
+impl GameWatcher {
+    pub fn spawn() -> Result<Self> {
+        if crate::config::is_safe_mode() {
+            return Err(anyhow!("Game detection is off in safe mode"));
+        }
+        let (tx, started) = channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        std::thread::spawn(move || {
+            #[cfg(windows)]
+            {
+                let _com = unsafe {
+                    windows::Win32::System::Com::CoInitializeEx(None, windows::Win32::System::Com::COINIT_MULTITHREADED)
+                };
+                match events::subscribe() {
+                    Ok(subscription) => match events::watch(subscription, &tx, &thread_running) {
+                        Ok(()) => return,
+                        Err(e) => warn!("[Games] {}; polling the process list instead", e),
+                    },
+                    Err(e) => warn!("[Games] {}; polling the process list instead", e),
+                }
+            }
+            poll_processes(&tx, &thread_running);
+        });
+        Ok(GameWatcher { started, running })
+    }
+
+    /// Names of the processes that started since the last call
+    pub fn poll(&self) -> Vec<String> {
+        self.started.try_iter().collect()
+    }
+}
+
+impl Drop for GameWatcher {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+fn report(tx: &Sender<String>, name: String) -> bool {
+    let sent = tx.send(name).is_ok();
+    ipc::wake();
+    sent
+}
+
+/// Names of the processes in `current` that weren't in `known`; `known` becomes `current`
+fn new_processes<'a>(known: &mut HashSet<Pid>, current: impl Iterator<Item = (Pid, &'a str)>) -> Vec<String> {
+    let mut started = Vec::new();
+    let mut seen = HashSet::new();
+    for (pid, name) in current {
+        if !known.contains(&pid) {
+            started.push(name.to_string());
+        }
+        seen.insert(pid);
+    }
+    *known = seen;
+    started
+}
+
+/// Fallback without WMI: compare the process list every POLL_INTERVAL
+fn poll_processes(tx: &Sender<String>, running: &AtomicBool) {
+    info!("[Games] Watching for game launches by polling");
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessRefreshKind::new());
+    let mut known: HashSet<Pid> = sys.processes().keys().copied().collect();
+    while running.load(Ordering::Relaxed) {
+        std::thread::sleep(POLL_INTERVAL);
+        sys.refresh_processes_specifics(ProcessRefreshKind::new());
+        let current = sys.processes().iter().map(|(pid, process)| (*pid, process.name()));
+        for name in new_processes(&mut known, current) {
+            if !report(tx, name) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod events {
+    use super::{report, EVENT_WAIT};
+    use crate::wmi::{self, Connection, EventQuery};
+    use anyhow::Result;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc::Sender;
+    use tracing::info;
+
+    /// Kernel start events; subscribing needs administrator rights
+    const START_TRACE: &str = "SELECT ProcessName FROM Win32_ProcessStartTrace";
+    /// Works for everyone, checked by WMI once a second
+    const INSTANCE_CREATION: &str =
+        "SELECT TargetInstance FROM __InstanceCreationEvent WITHIN 1 WHERE TargetInstance ISA 'Win32_Process'";
+
+    pub struct Subscription {
+        // The query needs its connection open
+        _connection: Connection,
+        query: EventQuery,
+        start_trace: bool,
+    }
+
+    pub fn subscribe() -> Result<Subscription> {
+        let connection = Connection::open("root\\CIMV2")?;
+        if crate::elevation::capabilities().elevated {
+            if let Ok(query) = connection.subscribe(START_TRACE) {
+                info!("[Games] Watching for game launches with process start events");
+                return Ok(Subscription { _connection: connection, query, start_trace: true });
+            }
+        }
+        let query = connection.subscribe(INSTANCE_CREATION)?;
+        info!("[Games] Watching for game launches with process creation events");
+        Ok(Subscription { _connection: connection, query, start_trace: false })
+    }
+
+    /// Report started processes until the watcher is dropped (Ok) or the subscription fails
+    pub fn watch(subscription: Subscription, tx: &Sender<String>, running: &AtomicBool) -> Result<()> {
+        while running.load(Ordering::Relaxed) {
+            let Some(event) = subscription.query.next(EVENT_WAIT)? else { continue };
+            let name = if subscription.start_trace {
+                wmi::get_string(&event, "ProcessName")
+            } else {
+                wmi::get_object(&event, "TargetInstance").and_then(|process| wmi::get_string(&process, "Name"))
+            };
+            if let Some(name) = name {
+                if !report(tx, name) {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_processes() {
+        let pid = Pid::from_u32;
+        let mut known = HashSet::from([pid(4), pid(100)]);
+
+        let started = new_processes(&mut known, [(pid(4), "System"), (pid(200), "cs2.exe")].into_iter());
+        assert_eq!(started, ["cs2.exe"]);
+        // Exited processes are forgotten, so a reused pid counts as a new start
+        assert_eq!(known, HashSet::from([pid(4), pid(200)]));
+        assert!(new_processes(&mut known, [(pid(4), "System"), (pid(200), "cs2.exe")].into_iter()).is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+//! Controller button combos for the crosshair
+//!
+//! Keyboard hotkeys are no use from the couch, so XInput controllers are polled on a thread of
+//! their own and a button combo such as "Back+RightStick" shows or hides the crosshair, or
+//! switches to the next preset. A combo fires once when its last button goes down and has to
+//! be let go before it fires again. Polling only reads the controller, so the game still gets
+//! every press; combos need at least two buttons so normal play doesn't set them off.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Actions a controller combo can trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadAction {
+    /// Show or hide the active profile's crosshair
+    ToggleOverlay,
+    /// Switch to the next crosshair preset of the active profile
+    CycleCrosshair,
+}
+
+pub const DEFAULT_TOGGLE_OVERLAY: &str = "Back+RightStick";
+pub const DEFAULT_CYCLE_CROSSHAIR: &str = "Back+RightShoulder";
+
+/// Time between two reads of the connected controllers
+const POLL_INTERVAL: Duration = Duration::from_millis(30);
+/// Time between looking for newly connected controllers (reading an empty slot is slow)
+const RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+/// XInput supports four controllers
+const MAX_CONTROLLERS: u32 = 4;
+/// How far a trigger has to be pulled to count as pressed (XINPUT_GAMEPAD_TRIGGER_THRESHOLD)
+const TRIGGER_THRESHOLD: u8 = 30;
+
+// Bits of a button set: XInput's wButtons, plus the triggers above them
+const LEFT_TRIGGER: u32 = 1 << 16;
+const RIGHT_TRIGGER: u32 = 1 << 17;
+
+/// Button names a combo can use, in the order combos are written, and their bits
+const BUTTONS: [(&str, u32); 16] = [
+    ("Back", 0x0020),
+    ("Start", 0x0010),
+    ("LeftShoulder", 0x0100),
+    ("RightShoulder", 0x0200),
+    ("LeftTrigger", LEFT_TRIGGER),
+    ("RightTrigger", RIGHT_TRIGGER),
+    ("LeftStick", 0x0040),
+    ("RightStick", 0x0080),
+    ("Up", 0x0001),
+    ("Down", 0x0002),
+    ("Left", 0x0004),
+    ("Right", 0x0008),
+    ("A", 0x1000),
+    ("B", 0x2000),
+    ("X", 0x4000),
+    ("Y", 0x8000),
+];
+
+/// Other names for the buttons (Xbox and PlayStation style)
+const ALIASES: [(&str, &str); 11] = [
+    ("View", "Back"),
+    ("Select", "Back"),
+    ("Menu", "Start"),
+    ("LB", "LeftShoulder"),
+    ("RB", "RightShoulder"),
+    ("LT", "LeftTrigger"),
+    ("RT", "RightTrigger"),
+    ("LS", "LeftStick"),
+    ("RS", "RightStick"),
+    ("L3", "LeftStick"),
+    ("R3", "RightStick"),
+];
+
+/// Controller combo settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct GamepadConfig {
+    pub enabled: bool,
+    /// e.g. "Back+RightStick" (empty = not bound)
+    pub toggle_overlay: String,
+    pub cycle_crosshair: String,
+}
+
+impl Default for GamepadConfig {
+    fn default() -> Self {
+        GamepadConfig {
+            enabled: false,
+            toggle_overlay: DEFAULT_TOGGLE_OVERLAY.to_string(),
+            cycle_crosshair: DEFAULT_CYCLE_CROSSHAIR.to_string(),
+        }
+    }
+}
+
+/// A set of buttons that are held together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Combo(u32);
+
+impl Combo {
+    /// Parse "Back+RightStick", "lb + rb" etc. (names from `BUTTONS` or `ALIASES`)
+    pub fn parse(text: &str) -> Result<Combo> {
+        let mut buttons = 0;
+        for part in text.split('+').map(str::trim) {
+            let name = ALIASES
+                .iter()
+                .find(|(alias, _)| alias.eq_ignore_ascii_case(part))
+                .map_or(part, |(_, name)| name);
+            let (_, bit) = BUTTONS
+                .iter()
+                .find(|(button, _)| button.eq_ignore_ascii_case(name))
+                .ok_or_else(|| anyhow!("Unknown controller button '{}'", part))?;
+            buttons |= bit;
+        }
+        if buttons.count_ones() < 2 {
+            return Err(anyhow!("'{}' needs at least two buttons", text));
+        }
+        Ok(Combo(buttons))
+    }
+
+    /// Whether the combo went down between two reads: all its buttons held now, not before
+    fn pressed(self, previous: u32, current: u32) -> bool {
+        current & self.0 == self.0 && previous & self.0 != self.0
+    }
+}
+
+impl std::fmt::Display for Combo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = BUTTONS.iter().filter(|(_, bit)| self.0 & bit != 0).map(|(name, _)| *name).collect();
+        write!(f, "{}", names.join("+"))
+    }
+}
+
+/// The bound combos of `config` and what they do (fails on the first invalid one)
+fn bindings(config: &GamepadConfig) -> Result<Vec<(Combo, GamepadAction)>> {
+    [(&config.toggle_overlay, GamepadAction::ToggleOverlay), (&config.cycle_crosshair, GamepadAction::CycleCrosshair)]
+        .into_iter()
+        .filter(|(text, _)| !text.trim().is_empty())
+        .map(|(text, action)| Combo::parse(text).map(|combo| (combo, action)))
+        .collect()
+}
+
+/// Buttons held on controller `index` (None = not connected)
+fn read_buttons(index: u32) -> Option<u32> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::UI::Input::XboxController::{XInputGetState, XINPUT_STATE};
+
+        let mut state = XINPUT_STATE::default();
+        // ERROR_SUCCESS; anything else means no controller in that slot
+        if unsafe { XInputGetState(index, &mut state) } != 0 {
+            return None;
+        }
+        let pad = state.Gamepad;
+        let mut buttons = pad.wButtons.0 as u32;
+        if pad.bLeftTrigger > TRIGGER_THRESHOLD {
+            buttons |= LEFT_TRIGGER;
+        }
+        if pad.bRightTrigger > TRIGGER_THRESHOLD {
+            buttons |= RIGHT_TRIGGER;
+        }
+        Some(buttons)
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = index;
+        None
+    }
+}
+
+/// Running poll thread; stops when dropped
+pub struct GamepadThread {
+    pub actions: Receiver<GamepadAction>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl GamepadThread {
+    /// No polling (disabled in the settings or nothing bound)
+    pub fn disabled() -> Self {
+        GamepadThread { actions: channel().1, stop: Arc::default(), thread: None }
+    }
+}
+
+impl Drop for GamepadThread {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Start polling the controllers; fails if a combo is invalid
+pub fn spawn_gamepad_thread(config: &GamepadConfig) -> Result<GamepadThread> {
+    if !config.enabled || crate::config::is_safe_mode() {
+        return Ok(GamepadThread::disabled());
+    }
+    let bindings = bindings(config)?;
+    if bindings.is_empty() {
+        return Ok(GamepadThread::disabled());
+    }
+    for (combo, action) in &bindings {
+        info!("[Gamepad] {} -> {:?}", combo, action);
+    }
+
+    let (tx, rx) = channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopped = Arc::clone(&stop);
+    let thread = std::thread::spawn(move || {
+        // Buttons held per slot at the last read (None = no controller there)
+        let mut held: [Option<u32>; MAX_CONTROLLERS as usize] = [None; MAX_CONTROLLERS as usize];
+        let mut last_scan: Option<Instant> = None;
+        while !stopped.load(Ordering::Relaxed) {
+            let rescan = last_scan.is_none_or(|scan| scan.elapsed() >= RESCAN_INTERVAL);
+            if rescan {
+                last_scan = Some(Instant::now());
+            }
+            for index in 0..MAX_CONTROLLERS {
+                let slot = &mut held[index as usize];
+                if slot.is_none() && !rescan {
+                    continue;
+                }
+                let previous = *slot;
+                *slot = read_buttons(index);
+                match (previous, *slot) {
+                    (None, Some(_)) => info!("[Gamepad] Controller {} connected", index + 1),
+                    (Some(_), None) => info!("[Gamepad] Controller {} disconnected", index + 1),
+                    _ => {}
+                }
+                // Buttons already held when a controller is found don't fire
+                let (Some(previous), Some(current)) = (previous, *slot) else { continue };
+                for (combo, action) in &bindings {
+                    if combo.pressed(previous, current) {
+                        if tx.send(*action).is_err() {
+                            return;
+                        }
+                        crate::ipc::wake();
+                    }
+                }
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+    Ok(GamepadThread { actions: rx, stop, thread: Some(thread) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_combo() {
+        let combo = Combo::parse("Back+RightStick").unwrap();
+        assert_eq!(combo, Combo(0x0020 | 0x0080));
+        assert_eq!(Combo::parse(" select + r3 ").unwrap(), combo);
+        assert_eq!(Combo::parse("RB+LT").unwrap().to_string(), "RightShoulder+LeftTrigger");
+        assert_eq!(Combo::parse("a+b+x").unwrap().to_string(), "A+B+X");
+        assert!(Combo::parse("A").is_err());
+        assert!(Combo::parse("A+A").is_err());
+        assert!(Combo::parse("Back+Home").is_err());
+        assert!(Combo::parse("").is_err());
+    }
+
+    #[test]
+    fn test_combo_pressed_once() {
+        let combo = Combo::parse("Back+RightStick").unwrap();
+        let back = 0x0020;
+        let both = 0x0020 | 0x0080;
+        assert!(!combo.pressed(0, back));
+        assert!(combo.pressed(back, both));
+        // Held, or with more buttons added: still the same press
+        assert!(!combo.pressed(both, both));
+        assert!(!combo.pressed(both, both | 0x1000));
+        // Let go of one and press it again
+        assert!(combo.pressed(back, both | 0x1000));
+    }
+
+    #[test]
+    fn test_bindings() {
+        let config = GamepadConfig { enabled: true, cycle_crosshair: String::new(), ..Default::default() };
+        assert_eq!(bindings(&config).unwrap(), [(Combo::parse(DEFAULT_TOGGLE_OVERLAY).unwrap(), GamepadAction::ToggleOverlay)]);
+        let invalid = GamepadConfig { toggle_overlay: "Back".to_string(), ..Default::default() };
+        assert!(bindings(&invalid).is_err());
+        assert_eq!(bindings(&GamepadConfig::default()).unwrap().len(), 2);
+    }
+}
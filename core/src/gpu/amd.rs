@@ -0,0 +1,235 @@
+/// AMD GPU tuning presets through ADLX
+///
+/// ADLX (amdadlx64.dll, shipped with the Adrenalin driver) exposes C++-style interfaces; they
+/// are called here through their vtables in declaration order from the ADLX SDK headers.
+/// "Prefer maximum performance" maps to the Rage preset, or Turbo where Rage isn't offered.
+
+use super::{GpuBackend, GpuSettings};
+use anyhow::{anyhow, Result};
+use std::ffi::c_void;
+
+type AdlxResult = i32;
+type AdlxBool = u8;
+type Interface = *mut c_void;
+
+/// ADLX_OK, ADLX_ALREADY_ENABLED, ADLX_ALREADY_INITIALIZED
+fn succeeded(result: AdlxResult) -> bool {
+    (0..=2).contains(&result)
+}
+
+fn check(result: AdlxResult, what: &str) -> Result<()> {
+    if succeeded(result) {
+        Ok(())
+    } else {
+        Err(anyhow!("{} failed (ADLX result {})", what, result))
+    }
+}
+
+/// Vtable slots (IADLXInterface: Acquire 0, Release 1, QueryInterface 2)
+const RELEASE: usize = 1;
+const QUERY_INTERFACE: usize = 2;
+/// IADLXSystem (not an IADLXInterface)
+const SYSTEM_GET_GPUS: usize = 1;
+const SYSTEM_GET_GPU_TUNING_SERVICES: usize = 8;
+/// IADLXList / IADLXGPUList
+const LIST_SIZE: usize = 3;
+const LIST_BEGIN: usize = 5;
+const GPU_LIST_AT: usize = 11;
+/// IADLXGPUTuningServices
+const TUNING_IS_SUPPORTED_PRESET: usize = 7;
+const TUNING_GET_PRESET: usize = 13;
+/// IADLXGPUPresetTuning: IsSupported* 3-7, IsCurrent* 8-12, Set* 13-17
+/// (PowerSaver, Quiet, Balanced, Turbo, Rage in each group)
+const PRESET_IS_SUPPORTED_BASE: usize = 3;
+const PRESET_IS_CURRENT_BASE: usize = 8;
+const PRESET_SET_BASE: usize = 13;
+
+/// GPU tuning presets in vtable order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Preset {
+    PowerSaver,
+    Quiet,
+    Balanced,
+    Turbo,
+    Rage,
+}
+
+const PRESETS: [Preset; 5] = [Preset::PowerSaver, Preset::Quiet, Preset::Balanced, Preset::Turbo, Preset::Rage];
+
+impl Preset {
+    fn slot_offset(self) -> usize {
+        self as usize
+    }
+}
+
+/// Function pointer in vtable slot `slot` of an ADLX object
+unsafe fn method<F: Copy>(object: Interface, slot: usize) -> F {
+    let vtable = *(object as *const *const *const c_void);
+    std::mem::transmute_copy(&*vtable.add(slot))
+}
+
+/// Owned reference to an IADLXInterface object (released on drop)
+struct Object(Interface);
+
+impl Drop for Object {
+    fn drop(&mut self) {
+        unsafe {
+            let release: unsafe extern "system" fn(Interface) -> i32 = method(self.0, RELEASE);
+            release(self.0);
+        }
+    }
+}
+
+/// An initialized ADLX instance (terminated on drop)
+struct Adlx {
+    system: Interface,
+    terminate: unsafe extern "system" fn() -> AdlxResult,
+}
+
+impl Adlx {
+    fn initialize() -> Result<Self> {
+        use windows::core::{s, w};
+        use windows::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryW};
+
+        unsafe {
+            let module = LoadLibraryW(w!("amdadlx64.dll"))
+                .map_err(|e| anyhow!("AMD Adrenalin driver not installed (amdadlx64.dll: {})", e))?;
+            let query_version = GetProcAddress(module, s!("ADLXQueryFullVersion"))
+                .ok_or_else(|| anyhow!("ADLXQueryFullVersion not found"))?;
+            let initialize = GetProcAddress(module, s!("ADLXInitialize"))
+                .ok_or_else(|| anyhow!("ADLXInitialize not found"))?;
+            let terminate = GetProcAddress(module, s!("ADLXTerminate"))
+                .ok_or_else(|| anyhow!("ADLXTerminate not found"))?;
+
+            let query_version: unsafe extern "system" fn(*mut u64) -> AdlxResult = std::mem::transmute(query_version);
+            let initialize: unsafe extern "system" fn(u64, *mut Interface) -> AdlxResult = std::mem::transmute(initialize);
+            let terminate: unsafe extern "system" fn() -> AdlxResult = std::mem::transmute(terminate);
+
+            // Initialize with the version the installed driver provides
+            let mut version = 0u64;
+            check(query_version(&mut version), "ADLXQueryFullVersion")?;
+            let mut system: Interface = std::ptr::null_mut();
+            check(initialize(version, &mut system), "ADLXInitialize")?;
+
+            Ok(Adlx { system, terminate })
+        }
+    }
+
+    /// Preset tuning interface of the first GPU that supports it
+    fn preset_tuning(&self) -> Result<Object> {
+        unsafe {
+            let get_gpus: unsafe extern "system" fn(Interface, *mut Interface) -> AdlxResult =
+                method(self.system, SYSTEM_GET_GPUS);
+            let get_tuning: unsafe extern "system" fn(Interface, *mut Interface) -> AdlxResult =
+                method(self.system, SYSTEM_GET_GPU_TUNING_SERVICES);
+
+            let mut list: Interface = std::ptr::null_mut();
+            check(get_gpus(self.system, &mut list), "GetGPUs")?;
+            let list = Object(list);
+            let mut tuning: Interface = std::ptr::null_mut();
+            check(get_tuning(self.system, &mut tuning), "GetGPUTuningServices")?;
+            let tuning = Object(tuning);
+
+            let size: unsafe extern "system" fn(Interface) -> u32 = method(list.0, LIST_SIZE);
+            let begin: unsafe extern "system" fn(Interface) -> u32 = method(list.0, LIST_BEGIN);
+            let at: unsafe extern "system" fn(Interface, u32, *mut Interface) -> AdlxResult = method(list.0, GPU_LIST_AT);
+            let is_supported: unsafe extern "system" fn(Interface, Interface, *mut AdlxBool) -> AdlxResult =
+                method(tuning.0, TUNING_IS_SUPPORTED_PRESET);
+            let get_preset: unsafe extern "system" fn(Interface, Interface, *mut Interface) -> AdlxResult =
+                method(tuning.0, TUNING_GET_PRESET);
+
+            let first = begin(list.0);
+            for index in first..first + size(list.0) {
+                let mut gpu: Interface = std::ptr::null_mut();
+                if !succeeded(at(list.0, index, &mut gpu)) {
+                    continue;
+                }
+                let gpu = Object(gpu);
+
+                let mut supported: AdlxBool = 0;
+                if !succeeded(is_supported(tuning.0, gpu.0, &mut supported)) || supported == 0 {
+                    continue;
+                }
+
+                let mut preset: Interface = std::ptr::null_mut();
+                check(get_preset(tuning.0, gpu.0, &mut preset), "GetPresetTuning")?;
+                let preset = Object(preset);
+
+                // GetPresetTuning hands out a generic interface
+                let query: unsafe extern "system" fn(Interface, *const u16, *mut Interface) -> AdlxResult =
+                    method(preset.0, QUERY_INTERFACE);
+                let id: Vec<u16> = "IADLXGPUPresetTuning".encode_utf16().chain(std::iter::once(0)).collect();
+                let mut preset_tuning: Interface = std::ptr::null_mut();
+                check(query(preset.0, id.as_ptr(), &mut preset_tuning), "QueryInterface(IADLXGPUPresetTuning)")?;
+                return Ok(Object(preset_tuning));
+            }
+
+            Err(anyhow!("No AMD GPU supports preset tuning"))
+        }
+    }
+}
+
+impl Drop for Adlx {
+    fn drop(&mut self) {
+        unsafe {
+            (self.terminate)();
+        }
+    }
+}
+
+fn preset_flag(tuning: &Object, base: usize, preset: Preset) -> bool {
+    unsafe {
+        let get: unsafe extern "system" fn(Interface, *mut AdlxBool) -> AdlxResult =
+            method(tuning.0, base + preset.slot_offset());
+        let mut value: AdlxBool = 0;
+        succeeded(get(tuning.0, &mut value)) && value != 0
+    }
+}
+
+fn set_preset(tuning: &Object, preset: Preset) -> Result<()> {
+    unsafe {
+        let set: unsafe extern "system" fn(Interface) -> AdlxResult = method(tuning.0, PRESET_SET_BASE + preset.slot_offset());
+        check(set(tuning.0), &format!("Setting {:?} preset", preset))
+    }
+}
+
+/// AMD backend; remembers the tuning preset it replaced
+#[derive(Default)]
+pub struct AdlxBackend {
+    previous_preset: Option<Preset>,
+}
+
+impl GpuBackend for AdlxBackend {
+    fn apply(&mut self, settings: &GpuSettings) -> Result<Vec<String>> {
+        if settings.nvidia_profile.is_some() && !settings.prefer_max_performance {
+            return Err(anyhow!("Driver profiles are only supported on NVIDIA GPUs"));
+        }
+        if !settings.prefer_max_performance {
+            return Ok(Vec::new());
+        }
+
+        let adlx = Adlx::initialize()?;
+        let tuning = adlx.preset_tuning()?;
+
+        let target = [Preset::Rage, Preset::Turbo]
+            .into_iter()
+            .find(|&preset| preset_flag(&tuning, PRESET_IS_SUPPORTED_BASE, preset))
+            .ok_or_else(|| anyhow!("GPU offers no Rage or Turbo tuning preset"))?;
+        let previous = PRESETS
+            .into_iter()
+            .find(|&preset| preset_flag(&tuning, PRESET_IS_CURRENT_BASE, preset));
+
+        set_preset(&tuning, target)?;
+        self.previous_preset = Some(previous.unwrap_or(Preset::Balanced));
+        Ok(vec![format!("{:?} tuning preset", target)])
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        let Some(previous) = self.previous_preset.take() else {
+            return Ok(());
+        };
+        let adlx = Adlx::initialize()?;
+        let tuning = adlx.preset_tuning()?;
+        set_preset(&tuning, previous)
+    }
+}
@@ -0,0 +1,181 @@
+/// GPU vendor driver settings
+///
+/// Detects the installed GPU vendor at runtime and applies driver-level settings through the
+/// vendor SDK: NVAPI driver settings for NVIDIA (`nvidia` feature) and ADLX tuning presets for
+/// AMD (`amd` feature). Builds without either feature still detect the vendor and report that
+/// support isn't compiled in.
+
+#[cfg(all(windows, feature = "amd"))]
+mod amd;
+#[cfg(all(windows, feature = "nvidia"))]
+mod nvidia;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::info;
+
+/// GPU settings applied while a profile is active
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GpuSettings {
+    /// NVIDIA "Prefer maximum performance" power mode / AMD Rage (or Turbo) tuning preset
+    #[serde(default)]
+    pub prefer_max_performance: bool,
+    /// NVIDIA global driver profile to switch to, e.g. "Base Profile" (NVIDIA only)
+    #[serde(default)]
+    pub nvidia_profile: Option<String>,
+}
+
+impl GpuSettings {
+    /// Whether any setting is configured
+    pub fn is_empty(&self) -> bool {
+        !self.prefer_max_performance && self.nvidia_profile.is_none()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+}
+
+impl fmt::Display for GpuVendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuVendor::Nvidia => write!(f, "NVIDIA"),
+            GpuVendor::Amd => write!(f, "AMD"),
+            GpuVendor::Intel => write!(f, "Intel"),
+        }
+    }
+}
+
+/// Vendor of a display adapter from its PnP id, e.g. `PCI\VEN_10DE&DEV_2484&...`
+fn vendor_from_device_id(device_id: &str) -> Option<GpuVendor> {
+    let upper = device_id.to_ascii_uppercase();
+    let vendor_id = upper.split_once("VEN_")?.1.get(..4)?;
+    match vendor_id {
+        "10DE" => Some(GpuVendor::Nvidia),
+        "1002" => Some(GpuVendor::Amd),
+        "8086" => Some(GpuVendor::Intel),
+        _ => None,
+    }
+}
+
+/// Vendors of all display adapters, including ones not driving a monitor (laptop dGPUs)
+pub fn detect_vendors() -> Vec<GpuVendor> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::Graphics::Gdi::{EnumDisplayDevicesW, DISPLAY_DEVICEW};
+
+        let mut vendors = Vec::new();
+        for index in 0.. {
+            let mut device = DISPLAY_DEVICEW {
+                cb: std::mem::size_of::<DISPLAY_DEVICEW>() as u32,
+                ..Default::default()
+            };
+            if !unsafe { EnumDisplayDevicesW(PCWSTR::null(), index, &mut device, 0) }.as_bool() {
+                break;
+            }
+            let len = device.DeviceID.iter().position(|&c| c == 0).unwrap_or(device.DeviceID.len());
+            let device_id = String::from_utf16_lossy(&device.DeviceID[..len]);
+            if let Some(vendor) = vendor_from_device_id(&device_id) {
+                if !vendors.contains(&vendor) {
+                    vendors.push(vendor);
+                }
+            }
+        }
+        vendors
+    }
+
+    #[cfg(not(windows))]
+    Vec::new()
+}
+
+/// A vendor SDK that can apply `GpuSettings`
+trait GpuBackend {
+    /// Apply the settings (remembering anything needed for `restore`)
+    /// Returns a summary of what was changed
+    fn apply(&mut self, settings: &GpuSettings) -> Result<Vec<String>>;
+
+    /// Undo `apply`
+    fn restore(&mut self) -> Result<()>;
+}
+
+/// Backend for a vendor, or why there isn't one
+fn backend_for(vendor: GpuVendor) -> Result<Box<dyn GpuBackend>> {
+    match vendor {
+        #[cfg(all(windows, feature = "nvidia"))]
+        GpuVendor::Nvidia => Ok(Box::new(nvidia::NvapiBackend::default())),
+        #[cfg(not(all(windows, feature = "nvidia")))]
+        GpuVendor::Nvidia => Err(anyhow!("NVIDIA support not built in (enable the `nvidia` feature)")),
+        #[cfg(all(windows, feature = "amd"))]
+        GpuVendor::Amd => Ok(Box::new(amd::AdlxBackend::default())),
+        #[cfg(not(all(windows, feature = "amd")))]
+        GpuVendor::Amd => Err(anyhow!("AMD support not built in (enable the `amd` feature)")),
+        GpuVendor::Intel => Err(anyhow!("Intel GPUs have no driver settings integration")),
+    }
+}
+
+/// Applies a profile's GPU settings and undoes them afterwards
+#[derive(Default)]
+pub struct GpuSwitcher {
+    /// Backend that applied settings (None = nothing to restore)
+    active: Option<Box<dyn GpuBackend>>,
+}
+
+impl GpuSwitcher {
+    /// Restore any previous profile's settings, then apply the new ones using the first
+    /// detected vendor with a backend. Returns a summary of what was changed.
+    pub fn apply(&mut self, settings: &GpuSettings) -> Result<Vec<String>> {
+        self.restore()?;
+        if settings.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let vendors = detect_vendors();
+        let mut last_error = anyhow!("No NVIDIA or AMD GPU found");
+        for vendor in vendors.into_iter().filter(|v| *v != GpuVendor::Intel) {
+            match backend_for(vendor) {
+                Ok(mut backend) => {
+                    let applied = backend
+                        .apply(settings)
+                        .map_err(|e| anyhow!("{} driver settings failed: {}", vendor, e))?;
+                    info!("[GPU] Applied {} via {}", applied.join(", "), vendor);
+                    self.active = Some(backend);
+                    return Ok(applied);
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Undo `apply` (no-op if nothing was applied)
+    pub fn restore(&mut self) -> Result<()> {
+        let Some(mut backend) = self.active.take() else {
+            return Ok(());
+        };
+        backend.restore()?;
+        info!("[GPU] Driver settings restored");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vendor_from_device_id() {
+        assert_eq!(
+            vendor_from_device_id("PCI\\VEN_10DE&DEV_2484&SUBSYS_146B10DE&REV_A1"),
+            Some(GpuVendor::Nvidia)
+        );
+        assert_eq!(vendor_from_device_id("pci\\ven_1002&dev_73bf"), Some(GpuVendor::Amd));
+        assert_eq!(vendor_from_device_id("PCI\\VEN_8086&DEV_9A49"), Some(GpuVendor::Intel));
+        assert_eq!(vendor_from_device_id("ROOT\\BasicDisplay"), None);
+        assert_eq!(vendor_from_device_id("PCI\\VEN_10"), None);
+    }
+}
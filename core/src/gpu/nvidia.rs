@@ -0,0 +1,268 @@
+/// NVIDIA driver settings through NVAPI
+///
+/// nvapi64.dll only exports `nvapi_QueryInterface`; every other entry point is looked up by
+/// its published interface id. Settings are written to the driver settings store (DRS), the
+/// same place NVIDIA Control Panel uses.
+
+use super::{GpuBackend, GpuSettings};
+use anyhow::{anyhow, Result};
+use std::ffi::c_void;
+
+type NvStatus = i32;
+type Session = *mut c_void;
+type ProfileHandle = *mut c_void;
+
+const NVAPI_OK: NvStatus = 0;
+
+/// Interface ids passed to nvapi_QueryInterface
+const NVAPI_INITIALIZE: u32 = 0x0150_E828;
+const NVAPI_DRS_CREATE_SESSION: u32 = 0x0694_D52E;
+const NVAPI_DRS_DESTROY_SESSION: u32 = 0xDAD9_CFF8;
+const NVAPI_DRS_LOAD_SETTINGS: u32 = 0x375D_BD6B;
+const NVAPI_DRS_SAVE_SETTINGS: u32 = 0xFCBC_7E14;
+const NVAPI_DRS_GET_BASE_PROFILE: u32 = 0xDA84_66A0;
+const NVAPI_DRS_GET_SETTING: u32 = 0x73BF_8338;
+const NVAPI_DRS_SET_SETTING: u32 = 0x577D_D202;
+const NVAPI_DRS_RESTORE_PROFILE_DEFAULT_SETTING: u32 = 0x53F0_381E;
+const NVAPI_DRS_GET_CURRENT_GLOBAL_PROFILE: u32 = 0x617B_FF9F;
+const NVAPI_DRS_SET_CURRENT_GLOBAL_PROFILE: u32 = 0x1C89_C5DF;
+const NVAPI_DRS_GET_PROFILE_INFO: u32 = 0x61CD_6FD6;
+
+/// "Power management mode" driver setting and its "Prefer maximum performance" value
+const PREFERRED_PSTATE_ID: u32 = 0x1057_EB71;
+const PREFERRED_PSTATE_PREFER_MAX: u32 = 1;
+
+/// NvAPI_UnicodeString length in UTF-16 units
+const NVAPI_UNICODE_STRING_MAX: usize = 2048;
+
+/// NVDRS_SETTING_V1 (values are unions of u32 / binary / string; only the u32 form is used)
+#[repr(C)]
+struct NvdrsSetting {
+    version: u32,
+    setting_name: [u16; NVAPI_UNICODE_STRING_MAX],
+    setting_id: u32,
+    setting_type: u32,
+    setting_location: u32,
+    is_current_predefined: u32,
+    is_predefined_valid: u32,
+    predefined_value: [u32; 1025],
+    current_value: [u32; 1025],
+}
+
+/// NVDRS_PROFILE_V1
+#[repr(C)]
+struct NvdrsProfile {
+    version: u32,
+    profile_name: [u16; NVAPI_UNICODE_STRING_MAX],
+    gpu_support: u32,
+    is_predefined: u32,
+    num_of_apps: u32,
+    num_of_settings: u32,
+}
+
+/// NVAPI_VERSION: struct size in the low word, struct version in the high word
+const fn struct_version<T>(version: u32) -> u32 {
+    std::mem::size_of::<T>() as u32 | (version << 16)
+}
+
+fn to_unicode_string(text: &str) -> [u16; NVAPI_UNICODE_STRING_MAX] {
+    let mut buffer = [0u16; NVAPI_UNICODE_STRING_MAX];
+    for (slot, unit) in buffer.iter_mut().zip(text.encode_utf16().take(NVAPI_UNICODE_STRING_MAX - 1)) {
+        *slot = unit;
+    }
+    buffer
+}
+
+fn check(status: NvStatus, what: &str) -> Result<()> {
+    if status == NVAPI_OK {
+        Ok(())
+    } else {
+        Err(anyhow!("{} failed (NVAPI status {})", what, status))
+    }
+}
+
+/// Loaded nvapi64.dll
+struct Nvapi {
+    query_interface: unsafe extern "C" fn(u32) -> *const c_void,
+}
+
+impl Nvapi {
+    fn load() -> Result<Self> {
+        use windows::core::{s, w};
+        use windows::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryW};
+
+        unsafe {
+            let module = LoadLibraryW(w!("nvapi64.dll"))
+                .map_err(|e| anyhow!("NVIDIA driver not installed (nvapi64.dll: {})", e))?;
+            let proc = GetProcAddress(module, s!("nvapi_QueryInterface"))
+                .ok_or_else(|| anyhow!("nvapi_QueryInterface not found"))?;
+            let query_interface: unsafe extern "C" fn(u32) -> *const c_void = std::mem::transmute(proc);
+            let api = Nvapi { query_interface };
+
+            let initialize: unsafe extern "C" fn() -> NvStatus = api.function(NVAPI_INITIALIZE)?;
+            check(initialize(), "NvAPI_Initialize")?;
+            Ok(api)
+        }
+    }
+
+    /// Look up an NVAPI function; `F` must be the matching `extern "C"` fn pointer type
+    unsafe fn function<F: Copy>(&self, id: u32) -> Result<F> {
+        let pointer = (self.query_interface)(id);
+        if pointer.is_null() {
+            return Err(anyhow!("NVAPI function {:#010x} not available in this driver", id));
+        }
+        Ok(std::mem::transmute_copy(&pointer))
+    }
+
+    /// Run `f` with a loaded DRS session, saving the settings if it succeeds
+    fn with_session<T>(&self, f: impl FnOnce(Session) -> Result<T>) -> Result<T> {
+        unsafe {
+            let create: unsafe extern "C" fn(*mut Session) -> NvStatus = self.function(NVAPI_DRS_CREATE_SESSION)?;
+            let destroy: unsafe extern "C" fn(Session) -> NvStatus = self.function(NVAPI_DRS_DESTROY_SESSION)?;
+            let load: unsafe extern "C" fn(Session) -> NvStatus = self.function(NVAPI_DRS_LOAD_SETTINGS)?;
+            let save: unsafe extern "C" fn(Session) -> NvStatus = self.function(NVAPI_DRS_SAVE_SETTINGS)?;
+
+            let mut session: Session = std::ptr::null_mut();
+            check(create(&mut session), "NvAPI_DRS_CreateSession")?;
+            let result = check(load(session), "NvAPI_DRS_LoadSettings")
+                .and_then(|_| f(session))
+                .and_then(|value| check(save(session), "NvAPI_DRS_SaveSettings").map(|_| value));
+            destroy(session);
+            result
+        }
+    }
+
+    fn base_profile(&self, session: Session) -> Result<ProfileHandle> {
+        unsafe {
+            let get_base: unsafe extern "C" fn(Session, *mut ProfileHandle) -> NvStatus =
+                self.function(NVAPI_DRS_GET_BASE_PROFILE)?;
+            let mut profile: ProfileHandle = std::ptr::null_mut();
+            check(get_base(session, &mut profile), "NvAPI_DRS_GetBaseProfile")?;
+            Ok(profile)
+        }
+    }
+
+    /// User-set value of a DWORD setting on a profile (None = driver default)
+    fn get_dword(&self, session: Session, profile: ProfileHandle, setting_id: u32) -> Result<Option<u32>> {
+        unsafe {
+            let get_setting: unsafe extern "C" fn(Session, ProfileHandle, u32, *mut NvdrsSetting) -> NvStatus =
+                self.function(NVAPI_DRS_GET_SETTING)?;
+            let mut setting: Box<NvdrsSetting> = Box::new(std::mem::zeroed());
+            setting.version = struct_version::<NvdrsSetting>(1);
+            if get_setting(session, profile, setting_id, &mut *setting) != NVAPI_OK {
+                // NVAPI_SETTING_NOT_FOUND - the setting was never changed from its default
+                return Ok(None);
+            }
+            Ok((setting.is_current_predefined == 0).then_some(setting.current_value[0]))
+        }
+    }
+
+    fn set_dword(&self, session: Session, profile: ProfileHandle, setting_id: u32, value: u32) -> Result<()> {
+        unsafe {
+            let set_setting: unsafe extern "C" fn(Session, ProfileHandle, *mut NvdrsSetting) -> NvStatus =
+                self.function(NVAPI_DRS_SET_SETTING)?;
+            let mut setting: Box<NvdrsSetting> = Box::new(std::mem::zeroed());
+            setting.version = struct_version::<NvdrsSetting>(1);
+            setting.setting_id = setting_id;
+            // NVDRS_DWORD_TYPE
+            setting.setting_type = 0;
+            setting.current_value[0] = value;
+            check(set_setting(session, profile, &mut *setting), "NvAPI_DRS_SetSetting")
+        }
+    }
+
+    fn restore_default(&self, session: Session, profile: ProfileHandle, setting_id: u32) -> Result<()> {
+        unsafe {
+            let restore: unsafe extern "C" fn(Session, ProfileHandle, u32) -> NvStatus =
+                self.function(NVAPI_DRS_RESTORE_PROFILE_DEFAULT_SETTING)?;
+            check(restore(session, profile, setting_id), "NvAPI_DRS_RestoreProfileDefaultSetting")
+        }
+    }
+
+    fn current_global_profile(&self, session: Session) -> Result<String> {
+        unsafe {
+            let get_current: unsafe extern "C" fn(Session, *mut ProfileHandle) -> NvStatus =
+                self.function(NVAPI_DRS_GET_CURRENT_GLOBAL_PROFILE)?;
+            let get_info: unsafe extern "C" fn(Session, ProfileHandle, *mut NvdrsProfile) -> NvStatus =
+                self.function(NVAPI_DRS_GET_PROFILE_INFO)?;
+
+            let mut profile: ProfileHandle = std::ptr::null_mut();
+            check(get_current(session, &mut profile), "NvAPI_DRS_GetCurrentGlobalProfile")?;
+            let mut info: Box<NvdrsProfile> = Box::new(std::mem::zeroed());
+            info.version = struct_version::<NvdrsProfile>(1);
+            check(get_info(session, profile, &mut *info), "NvAPI_DRS_GetProfileInfo")?;
+
+            let len = info.profile_name.iter().position(|&c| c == 0).unwrap_or(NVAPI_UNICODE_STRING_MAX);
+            Ok(String::from_utf16_lossy(&info.profile_name[..len]))
+        }
+    }
+
+    fn set_global_profile(&self, session: Session, name: &str) -> Result<()> {
+        unsafe {
+            let set_current: unsafe extern "C" fn(Session, *const u16) -> NvStatus =
+                self.function(NVAPI_DRS_SET_CURRENT_GLOBAL_PROFILE)?;
+            let name_w = to_unicode_string(name);
+            check(set_current(session, name_w.as_ptr()), &format!("Switching to driver profile '{}'", name))
+        }
+    }
+}
+
+/// NVIDIA backend; remembers the settings it replaced
+#[derive(Default)]
+pub struct NvapiBackend {
+    /// Power mode before `apply` (Some(None) = it was at the driver default)
+    previous_power_mode: Option<Option<u32>>,
+    /// Global driver profile before `apply`
+    previous_global_profile: Option<String>,
+}
+
+impl GpuBackend for NvapiBackend {
+    fn apply(&mut self, settings: &GpuSettings) -> Result<Vec<String>> {
+        let api = Nvapi::load()?;
+        let mut applied = Vec::new();
+
+        api.with_session(|session| {
+            if let Some(ref name) = settings.nvidia_profile {
+                let previous = api.current_global_profile(session)?;
+                api.set_global_profile(session, name)?;
+                self.previous_global_profile = Some(previous);
+                applied.push(format!("Driver profile '{}'", name));
+            }
+
+            // The global profile may have changed, so look the base profile up afterwards
+            if settings.prefer_max_performance {
+                let base = api.base_profile(session)?;
+                let previous = api.get_dword(session, base, PREFERRED_PSTATE_ID)?;
+                api.set_dword(session, base, PREFERRED_PSTATE_ID, PREFERRED_PSTATE_PREFER_MAX)?;
+                self.previous_power_mode = Some(previous);
+                applied.push("Prefer maximum performance".to_string());
+            }
+            Ok(())
+        })?;
+
+        Ok(applied)
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        let previous_power_mode = self.previous_power_mode.take();
+        let previous_global_profile = self.previous_global_profile.take();
+        if previous_power_mode.is_none() && previous_global_profile.is_none() {
+            return Ok(());
+        }
+
+        let api = Nvapi::load()?;
+        api.with_session(|session| {
+            if let Some(previous) = previous_power_mode {
+                let base = api.base_profile(session)?;
+                match previous {
+                    Some(value) => api.set_dword(session, base, PREFERRED_PSTATE_ID, value)?,
+                    None => api.restore_default(session, base, PREFERRED_PSTATE_ID)?,
+                }
+            }
+            if let Some(ref name) = previous_global_profile {
+                api.set_global_profile(session, name)?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,90 @@
+//! Global hotkey settings
+//!
+//! The bindings stored in the settings and their parsing. Registering them happens on the app's
+//! hotkey thread (`hotkey_thread` in the `gaming_optimizer` binary).
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_CYCLE_CROSSHAIR: &str = "Ctrl+Alt+C";
+
+/// Hotkey settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct HotkeyConfig {
+    pub enabled: bool,
+    /// e.g. "Ctrl+Alt+C"
+    pub cycle_crosshair: String,
+}
+
+impl Default for HotkeyConfig {
+    fn default() -> Self {
+        HotkeyConfig { enabled: true, cycle_crosshair: DEFAULT_CYCLE_CROSSHAIR.to_string() }
+    }
+}
+
+// RegisterHotKey modifier flags
+pub const MOD_ALT: u32 = 0x0001;
+pub const MOD_CONTROL: u32 = 0x0002;
+pub const MOD_SHIFT: u32 = 0x0004;
+pub const MOD_WIN: u32 = 0x0008;
+
+/// A parsed binding: RegisterHotKey modifier flags plus a virtual key code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    pub modifiers: u32,
+    pub vk: u32,
+}
+
+impl Hotkey {
+    /// Parse "Ctrl+Alt+C", "Shift+F9" etc. At least one modifier is required for letters and
+    /// digits so the binding doesn't eat normal typing.
+    pub fn parse(text: &str) -> Result<Hotkey> {
+        let mut modifiers = 0;
+        let mut vk = None;
+        for part in text.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= MOD_CONTROL,
+                "alt" => modifiers |= MOD_ALT,
+                "shift" => modifiers |= MOD_SHIFT,
+                "win" => modifiers |= MOD_WIN,
+                key if vk.is_none() => vk = Some(virtual_key(key).ok_or_else(|| anyhow!("Unknown key '{}'", part))?),
+                _ => return Err(anyhow!("'{}' has more than one key", text)),
+            }
+        }
+
+        let vk = vk.ok_or_else(|| anyhow!("'{}' has no key", text))?;
+        let is_function_key = (0x70..=0x87).contains(&vk);
+        if modifiers == 0 && !is_function_key {
+            return Err(anyhow!("'{}' needs Ctrl, Alt, Shift or Win", text));
+        }
+        Ok(Hotkey { modifiers, vk })
+    }
+}
+
+/// Virtual key code for a letter, digit or F1-F24
+fn virtual_key(key: &str) -> Option<u32> {
+    let upper = key.to_ascii_uppercase();
+    let mut chars = upper.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphanumeric() => Some(c as u32),
+        (Some('F'), Some(_)) => upper[1..].parse::<u32>().ok().filter(|n| (1..=24).contains(n)).map(|n| 0x6F + n),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hotkey() {
+        assert_eq!(Hotkey::parse("Ctrl+Alt+C").unwrap(), Hotkey { modifiers: MOD_CONTROL | MOD_ALT, vk: 0x43 });
+        assert_eq!(Hotkey::parse("shift + f9").unwrap(), Hotkey { modifiers: MOD_SHIFT, vk: 0x78 });
+        assert_eq!(Hotkey::parse("F12").unwrap(), Hotkey { modifiers: 0, vk: 0x7B });
+        assert!(Hotkey::parse("C").is_err());
+        assert!(Hotkey::parse("Ctrl+C+D").is_err());
+        assert!(Hotkey::parse("Ctrl+Enter").is_err());
+        assert!(Hotkey::parse("Ctrl+F25").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+//! UI translations
+//!
+//! Strings shown in the GUI, tray menu and notifications are looked up by id with `t!`.
+//! English is built in (locales/en.ftl); translations are Fluent (.ftl) files in the
+//! `locales` folder of the data directory, named after the language tag (`de.ftl`,
+//! `pt-BR.ftl`), so the community can add a language without a new build. Missing ids fall
+//! back to English, so a partial translation still works.
+//!
+//! Only the plain Fluent message syntax is read: `id = value`, indented continuation
+//! lines, `{ $name }` placeables and `#` comments. Terms, attributes and selectors are skipped.
+
+use crate::config::get_data_directory;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Built-in English strings
+const ENGLISH: &str = include_str!("../locales/en.ftl");
+/// Id every translation file sets to its own language name, shown in the language picker
+const LANGUAGE_NAME_ID: &str = "language-name";
+
+static ENGLISH_MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| parse_ftl(ENGLISH));
+/// Messages of the selected language (empty for English)
+static TRANSLATION: Lazy<RwLock<HashMap<String, String>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Translated UI string: `t!("id")`, or `t!("id", name = value, ...)` for `{ $name }` placeables
+#[macro_export]
+macro_rules! t {
+    ($id:literal) => {
+        $crate::i18n::tr($id, &[])
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::tr($id, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+// Used as `i18n::t` by the app too
+pub use t;
+
+/// Parse Fluent messages into id -> pattern
+fn parse_ftl(text: &str) -> HashMap<String, String> {
+    let mut messages = HashMap::new();
+    let mut current: Option<(String, String)> = None;
+    // Blank lines only belong to a message if another indented line follows
+    let mut blank_lines = 0;
+
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        // Indented lines continue the previous message
+        if line.starts_with(' ') {
+            if let Some((_, ref mut value)) = current {
+                if !value.is_empty() {
+                    value.push_str(&"\n".repeat(blank_lines + 1));
+                }
+                value.push_str(line.trim_start());
+            }
+            blank_lines = 0;
+            continue;
+        }
+        blank_lines = 0;
+        if let Some((id, value)) = current.take() {
+            messages.insert(id, value);
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((id, value)) if is_message_id(id.trim()) => {
+                current = Some((id.trim().to_string(), value.trim().to_string()));
+            }
+            _ => warn!("[i18n] Skipping unsupported line: {}", line),
+        }
+    }
+    if let Some((id, value)) = current {
+        messages.insert(id, value);
+    }
+    messages
+}
+
+/// Message ids: a letter followed by letters, digits, `-` or `_` (terms start with `-`)
+fn is_message_id(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Replace `{ $name }` placeables with their values; unknown ones are left as `{$name}`
+fn format_pattern(pattern: &str, args: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else { break };
+        let inner = rest[start + 1..start + end].trim();
+        match inner.strip_prefix('$').and_then(|name| args.iter().find(|(arg, _)| *arg == name)) {
+            Some((_, value)) => out.push_str(value),
+            // Quoted literal such as { "{" }
+            None if inner.len() >= 2 && inner.starts_with('"') && inner.ends_with('"') => {
+                out.push_str(&inner[1..inner.len() - 1]);
+            }
+            None => out.push_str(&format!("{{{}}}", inner)),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Look up `id` in the selected language, then English. Use `t!` instead of calling this.
+pub fn tr(id: &str, args: &[(&str, String)]) -> String {
+    if let Some(pattern) = TRANSLATION.read().get(id) {
+        return format_pattern(pattern, args);
+    }
+    match ENGLISH_MESSAGES.get(id) {
+        Some(pattern) => format_pattern(pattern, args),
+        None => {
+            warn!("[i18n] Missing string '{}'", id);
+            id.to_string()
+        }
+    }
+}
+
+/// Folder community translation files are read from
+pub fn locales_dir() -> Option<PathBuf> {
+    get_data_directory().ok().map(|dir| dir.join("locales"))
+}
+
+/// Translation file for `tag`, falling back from a regional tag to the base language
+/// (`pt-BR` -> `pt`)
+fn find_translation(dir: &Path, tag: &str) -> Option<PathBuf> {
+    let base = tag.split(['-', '_']).next().unwrap_or(tag);
+    [tag, base]
+        .into_iter()
+        .map(|candidate| dir.join(format!("{}.ftl", candidate)))
+        .find(|path| path.is_file())
+}
+
+/// Windows display language tag, e.g. "de-DE"
+fn system_language() -> Option<String> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::Globalization::GetUserDefaultLocaleName;
+
+        let mut buffer = [0u16; 85];
+        let len = unsafe { GetUserDefaultLocaleName(&mut buffer) };
+        if len > 1 {
+            return Some(String::from_utf16_lossy(&buffer[..len as usize - 1]));
+        }
+    }
+    None
+}
+
+/// Switch the UI language. `None` follows the Windows language; English or a language
+/// without a translation file uses the built-in strings.
+pub fn set_language(language: Option<&str>) {
+    let tag = language.map(str::to_string).or_else(system_language).unwrap_or_else(|| "en".to_string());
+    let translation = locales_dir()
+        .filter(|_| !tag.eq_ignore_ascii_case("en") && !tag.to_ascii_lowercase().starts_with("en-"))
+        .and_then(|dir| find_translation(&dir, &tag))
+        .and_then(|path| match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                info!("[i18n] Using {}", path.display());
+                Some(parse_ftl(&text))
+            }
+            Err(e) => {
+                warn!("[i18n] Failed to read {}: {}", path.display(), e);
+                None
+            }
+        });
+    *TRANSLATION.write() = translation.unwrap_or_default();
+}
+
+/// Languages that can be picked: English plus every translation file, as (tag, name)
+pub fn available_languages() -> Vec<(String, String)> {
+    let mut languages = vec![("en".to_string(), ENGLISH_MESSAGES[LANGUAGE_NAME_ID].clone())];
+    let entries = locales_dir().and_then(|dir| std::fs::read_dir(dir).ok());
+    for path in entries.into_iter().flatten().flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("ftl") {
+            continue;
+        }
+        let Some(tag) = path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string) else { continue };
+        if tag.eq_ignore_ascii_case("en") {
+            continue;
+        }
+        let name = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| parse_ftl(&text).remove(LANGUAGE_NAME_ID))
+            .unwrap_or_else(|| tag.clone());
+        languages.push((tag, name));
+    }
+    languages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ftl() {
+        let messages = parse_ftl("# Comment\nhello = Hallo { $name }!\n\nmulti =\n    First\n\n    Second\n\n-term = skipped\n");
+        assert_eq!(messages["hello"], "Hallo { $name }!");
+        assert_eq!(messages["multi"], "First\n\nSecond");
+        assert!(!messages.contains_key("-term"));
+    }
+
+    #[test]
+    fn test_format_pattern() {
+        let args = [("name", "FPS".to_string()), ("count", 3.to_string())];
+        assert_eq!(format_pattern("Profile { $name } ({$count})", &args), "Profile FPS (3)");
+        assert_eq!(format_pattern("Missing { $other }", &args), "Missing {$other}");
+        assert_eq!(format_pattern("Brace { \"{\" }", &args), "Brace {");
+    }
+
+    #[test]
+    fn test_english_baseline() {
+        assert_eq!(tr(LANGUAGE_NAME_ID, &[]), "English");
+        assert_eq!(t!("tray-tooltip-active", profile = "FPS"), "Gaming Optimizer - FPS");
+        // Every placeable in the baseline has a name
+        assert!(ENGLISH_MESSAGES.values().all(|pattern| !pattern.contains("{}")));
+    }
+
+    #[test]
+    fn test_find_translation_falls_back_to_base_language() {
+        let dir = std::env::temp_dir().join(format!("gaming_optimizer_locales_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("pt.ftl"), "language-name = Português\n").unwrap();
+        assert_eq!(find_translation(&dir, "pt-BR"), Some(dir.join("pt.ftl")));
+        assert_eq!(find_translation(&dir, "de-DE"), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+//! Discord Rich Presence for the active profile
+//!
+//! Speaks Discord's local RPC protocol over the `discord-ipc-N` named pipe: a handshake with the
+//! application id, then SET_ACTIVITY frames. Presence shows "Optimized: <profile> profile" with
+//! an elapsed timer and disappears when the profile is deactivated or the pipe closes.
+
+use super::ActivationEvent;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::sync::mpsc::{channel, Sender};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+
+/// Discord tries pipes 0-9 (one per running client)
+const PIPE_COUNT: u32 = 10;
+
+/// Discord presence settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiscordConfig {
+    pub enabled: bool,
+    /// Application id from the Discord developer portal (its name is shown as "Playing ...")
+    pub application_id: String,
+}
+
+/// Header + JSON body of an RPC frame
+fn encode_frame(opcode: u32, payload: &Value) -> Vec<u8> {
+    let body = payload.to_string();
+    let mut frame = Vec::with_capacity(8 + body.len());
+    frame.extend_from_slice(&opcode.to_le_bytes());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(body.as_bytes());
+    frame
+}
+
+/// SET_ACTIVITY command; `None` clears the presence
+fn activity_command(activity: Option<(&str, u64)>, nonce: u64) -> Value {
+    let activity = activity.map(|(profile, started)| {
+        json!({
+            "details": format!("Optimized: {} profile", profile),
+            "timestamps": { "start": started },
+        })
+    });
+    json!({
+        "cmd": "SET_ACTIVITY",
+        "args": { "pid": std::process::id(), "activity": activity },
+        "nonce": nonce.to_string(),
+    })
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// An open, handshaken RPC pipe
+struct Connection {
+    pipe: File,
+    nonce: u64,
+}
+
+impl Connection {
+    fn open(application_id: &str) -> Result<Self> {
+        let pipe = (0..PIPE_COUNT)
+            .find_map(|index| {
+                File::options()
+                    .read(true)
+                    .write(true)
+                    .open(format!(r"\\.\pipe\discord-ipc-{}", index))
+                    .ok()
+            })
+            .ok_or_else(|| anyhow!("Discord is not running"))?;
+
+        let mut connection = Connection { pipe, nonce: 0 };
+        connection.write_frame(OP_HANDSHAKE, &json!({ "v": 1, "client_id": application_id }))?;
+        // READY on success, CLOSE with a message for e.g. an unknown application id
+        let (opcode, reply) = connection.read_frame()?;
+        if opcode == OP_CLOSE {
+            return Err(anyhow!("Discord refused the connection: {}", reply["message"].as_str().unwrap_or("unknown error")));
+        }
+        Ok(connection)
+    }
+
+    fn write_frame(&mut self, opcode: u32, payload: &Value) -> Result<()> {
+        self.pipe
+            .write_all(&encode_frame(opcode, payload))
+            .map_err(|e| anyhow!("Failed to write to Discord: {}", e))
+    }
+
+    fn read_frame(&mut self) -> Result<(u32, Value)> {
+        let mut header = [0u8; 8];
+        self.pipe
+            .read_exact(&mut header)
+            .map_err(|e| anyhow!("Failed to read from Discord: {}", e))?;
+        let opcode = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let mut body = vec![0u8; len as usize];
+        self.pipe
+            .read_exact(&mut body)
+            .map_err(|e| anyhow!("Failed to read from Discord: {}", e))?;
+        Ok((opcode, serde_json::from_slice(&body).unwrap_or(Value::Null)))
+    }
+
+    fn set_activity(&mut self, activity: Option<(&str, u64)>) -> Result<()> {
+        self.nonce += 1;
+        self.write_frame(OP_FRAME, &activity_command(activity, self.nonce))?;
+        let (opcode, reply) = self.read_frame()?;
+        if opcode == OP_CLOSE || reply["evt"] == "ERROR" {
+            return Err(anyhow!("Discord rejected the presence: {}", reply["data"]["message"].as_str().unwrap_or("unknown error")));
+        }
+        Ok(())
+    }
+}
+
+/// Publishes the active profile to Discord from a background thread.
+/// Dropping it closes the pipe, which clears the presence.
+pub struct DiscordPresence {
+    events: Sender<ActivationEvent>,
+}
+
+impl DiscordPresence {
+    pub fn spawn(application_id: String) -> Self {
+        let (events, rx) = channel::<ActivationEvent>();
+
+        std::thread::spawn(move || {
+            let mut connection: Option<Connection> = None;
+
+            for event in rx {
+                let activity = match event {
+                    ActivationEvent::Activated { profile } => Some((profile, unix_time())),
+                    ActivationEvent::Deactivated => None,
+                };
+
+                // Retry once with a fresh pipe in case Discord was restarted
+                for _ in 0..2 {
+                    if connection.is_none() {
+                        match Connection::open(&application_id) {
+                            Ok(opened) => connection = Some(opened),
+                            Err(e) => {
+                                warn!("[Discord] {}", e);
+                                break;
+                            }
+                        }
+                    }
+                    let Some(ref mut open) = connection else { break };
+                    match open.set_activity(activity.as_ref().map(|(profile, started)| (profile.as_str(), *started))) {
+                        Ok(()) => break,
+                        Err(e) => {
+                            warn!("[Discord] {}", e);
+                            connection = None;
+                        }
+                    }
+                }
+            }
+        });
+
+        DiscordPresence { events }
+    }
+
+    pub fn publish(&self, event: ActivationEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_frame() {
+        let frame = encode_frame(OP_HANDSHAKE, &json!({ "v": 1 }));
+        assert_eq!(&frame[..8], &[0, 0, 0, 0, 7, 0, 0, 0]);
+        assert_eq!(&frame[8..], br#"{"v":1}"#);
+    }
+
+    #[test]
+    fn test_activity_command() {
+        let command = activity_command(Some(("Valorant", 1700000000)), 3);
+        assert_eq!(command["args"]["activity"]["details"], "Optimized: Valorant profile");
+        assert_eq!(command["args"]["activity"]["timestamps"]["start"], 1700000000);
+        assert_eq!(command["nonce"], "3");
+
+        let cleared = activity_command(None, 4);
+        assert!(cleared["args"]["activity"].is_null());
+    }
+}
@@ -0,0 +1,170 @@
+//! Windows Event Log entries for profile activations
+//!
+//! With the option on, activations, deactivations and failed activations are written to the
+//! Application log under the "GamingOptimizer" source, so they line up with other system events
+//! in Event Viewer and can trigger scheduled tasks. Each kind of entry has its own event id.
+//! Entries are written whether or not the source is registered; registering it (once, as
+//! administrator) only stops Event Viewer from saying the description can't be found.
+
+use crate::activation::ActivationSummary;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Source the entries are written under, in the Application log
+pub const SOURCE: &str = "GamingOptimizer";
+
+/// A profile was activated with every step applied
+pub const EVENT_ACTIVATED: u32 = 1000;
+/// A profile was activated, but some of its steps failed
+pub const EVENT_ACTIVATED_WITH_FAILURES: u32 = 1001;
+/// A step failed and the activation was rolled back
+pub const EVENT_NOT_ACTIVATED: u32 = 1002;
+/// The active profile was deactivated
+pub const EVENT_DEACTIVATED: u32 = 1003;
+/// The active profile was deactivated, but some of its changes couldn't be undone
+pub const EVENT_DEACTIVATED_WITH_FAILURES: u32 = 1004;
+
+/// Event Log settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct EventLogConfig {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Information,
+    Warning,
+    Error,
+}
+
+/// One Event Log entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub id: u32,
+    pub level: Level,
+    pub message: String,
+}
+
+impl Entry {
+    /// Outcome of an activation, followed by its step report
+    pub fn activation(summary: &ActivationSummary) -> Self {
+        let (id, level, headline) = if summary.rolled_back {
+            let step = summary.failed_step().unwrap_or_default();
+            (EVENT_NOT_ACTIVATED, Level::Error, format!("Profile '{}' was not activated: {} failed and the changes were rolled back.", summary.profile, step))
+        } else if summary.failed_count() > 0 {
+            (
+                EVENT_ACTIVATED_WITH_FAILURES,
+                Level::Warning,
+                format!("Profile '{}' activated: {} steps applied, {} failed.", summary.profile, summary.applied_count(), summary.failed_count()),
+            )
+        } else {
+            (EVENT_ACTIVATED, Level::Information, format!("Profile '{}' activated: {} steps applied.", summary.profile, summary.applied_count()))
+        };
+        Entry { id, level, message: format!("{}\n\n{}", headline, summary.report_text()) }
+    }
+
+    /// Deactivation of `profile`, with what couldn't be undone
+    pub fn deactivation(profile: &str, errors: &[String]) -> Self {
+        if errors.is_empty() {
+            return Entry { id: EVENT_DEACTIVATED, level: Level::Information, message: format!("Profile '{}' deactivated.", profile) };
+        }
+        let mut message = format!("Profile '{}' deactivated, but some changes couldn't be undone:\n", profile);
+        message.push_str(&errors.join("\n"));
+        Entry { id: EVENT_DEACTIVATED_WITH_FAILURES, level: Level::Warning, message }
+    }
+}
+
+/// Write `entry` to the Application log
+pub fn write(entry: &Entry) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use windows::core::{HSTRING, PCWSTR};
+        use windows::Win32::Foundation::PSID;
+        use windows::Win32::System::EventLog::{
+            DeregisterEventSource, RegisterEventSourceW, ReportEventW, EVENTLOG_ERROR_TYPE, EVENTLOG_INFORMATION_TYPE,
+            EVENTLOG_WARNING_TYPE,
+        };
+
+        let kind = match entry.level {
+            Level::Information => EVENTLOG_INFORMATION_TYPE,
+            Level::Warning => EVENTLOG_WARNING_TYPE,
+            Level::Error => EVENTLOG_ERROR_TYPE,
+        };
+        let message = HSTRING::from(entry.message.as_str());
+        unsafe {
+            let source = RegisterEventSourceW(PCWSTR::null(), &HSTRING::from(SOURCE))
+                .map_err(|e| anyhow!("Failed to open the Event Log: {}", e))?;
+            let result = ReportEventW(source, kind, 0, entry.id, PSID::default(), 0, Some(&[PCWSTR(message.as_ptr())]), None);
+            let _ = DeregisterEventSource(source);
+            result.map_err(|e| anyhow!("Failed to write to the Event Log: {}", e))
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = entry;
+        Err(anyhow!("The Event Log is only available on Windows"))
+    }
+}
+
+/// Whether the source is registered, so Event Viewer shows the entries' text as their description
+pub fn is_registered() -> bool {
+    crate::shell::run_powershell(&format!(
+        "Test-Path 'HKLM:\\SYSTEM\\CurrentControlSet\\Services\\EventLog\\Application\\{}'",
+        SOURCE
+    ))
+    .is_ok_and(|output| output.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Register the source in the Application log (needs administrator rights, once per PC)
+pub fn register() -> Result<()> {
+    crate::elevation::require_admin()?;
+    crate::shell::run_powershell(&format!("New-EventLog -LogName Application -Source {}", SOURCE))
+        .map(|_| ())
+        .map_err(|e| anyhow!("Failed to register the Event Log source: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::activation::{StepResult, StepStatus};
+
+    fn summary(statuses: Vec<(&'static str, StepStatus)>, rolled_back: bool) -> ActivationSummary {
+        ActivationSummary {
+            profile: "Valorant".to_string(),
+            results: statuses.into_iter().map(|(name, status)| StepResult { name, status }).collect(),
+            rolled_back,
+            revert_errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_activation_entries() {
+        let applied = summary(vec![("Processes", StepStatus::Applied(Vec::new())), ("Power", StepStatus::Applied(Vec::new()))], false);
+        let entry = Entry::activation(&applied);
+        assert_eq!((entry.id, entry.level), (EVENT_ACTIVATED, Level::Information));
+        assert!(entry.message.starts_with("Profile 'Valorant' activated: 2 steps applied.\n\nProfile: Valorant\nProcesses: applied"));
+
+        let partial = summary(vec![("Processes", StepStatus::Applied(Vec::new())), ("Audio", StepStatus::Failed("no device".to_string()))], false);
+        let entry = Entry::activation(&partial);
+        assert_eq!((entry.id, entry.level), (EVENT_ACTIVATED_WITH_FAILURES, Level::Warning));
+        assert!(entry.message.contains("Audio: FAILED: no device"));
+
+        let rolled_back = summary(vec![("Processes", StepStatus::RolledBack), ("Power", StepStatus::Failed("locked".to_string()))], true);
+        let entry = Entry::activation(&rolled_back);
+        assert_eq!((entry.id, entry.level), (EVENT_NOT_ACTIVATED, Level::Error));
+        assert!(entry.message.starts_with("Profile 'Valorant' was not activated: Power failed"));
+    }
+
+    #[test]
+    fn test_deactivation_entries() {
+        assert_eq!(
+            Entry::deactivation("Valorant", &[]),
+            Entry { id: EVENT_DEACTIVATED, level: Level::Information, message: "Profile 'Valorant' deactivated.".to_string() }
+        );
+        let entry = Entry::deactivation("Valorant", &["Power: plan is locked".to_string()]);
+        assert_eq!((entry.id, entry.level), (EVENT_DEACTIVATED_WITH_FAILURES, Level::Warning));
+        assert!(entry.message.ends_with("couldn't be undone:\nPower: plan is locked"));
+    }
+}
@@ -0,0 +1,17 @@
+//! Third-party integrations driven by profile activation
+//!
+//! Integrations run on their own threads and are fed `ActivationEvent`s by the GUI, so a slow
+//! or missing client (Discord not running) never blocks activation.
+
+pub mod discord;
+pub mod event_log;
+pub mod streamdeck;
+
+/// Profile lifecycle events published to integrations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActivationEvent {
+    /// A profile was activated (or re-activated)
+    Activated { profile: String },
+    /// The active profile was deactivated
+    Deactivated,
+}
@@ -0,0 +1,230 @@
+//! Stream Deck plugin protocol
+//!
+//! A small event protocol on the remote API's `/api/streamdeck` WebSocket, shaped after the
+//! Elgato SDK so a plugin can forward it almost 1:1: each key is bound to a profile, shows that
+//! profile's crosshair as its icon, switches to state 1 while the profile is live and toggles it
+//! on press. JSON text frames, tagged by `event`:
+//!
+//!   plugin → app   {"event":"getProfiles"}
+//!                  {"event":"getState"}
+//!                  {"event":"toggle","profile":"FPS"}
+//!   app → plugin   {"event":"profiles","profiles":[{"name","active","icon","activeIcon"}]}
+//!                  {"event":"state","activeProfile":"FPS"}   (on connect and whenever it changes)
+//!                  {"event":"result","ok":true,"message":"..."}   (answer to toggle)
+//!
+//! Icons are 144x144 PNG data URIs, ready for the SDK's setImage.
+
+use crate::cli::{CliCommand, LiveStatus};
+use crate::config::get_data_directory;
+use crate::crosshair_gen::CrosshairParams;
+use crate::ipc::RequestSender;
+use crate::profile::load_profiles;
+use crate::remote_api::{is_timeout, set_poll_interval};
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use image::{imageops, Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tungstenite::{Error as WsError, Message as WsMessage, WebSocket};
+
+/// Key images are 72x72; twice that stays sharp on high-DPI decks
+const ICON_SIZE: u32 = 144;
+/// Crosshair size inside the icon
+const ICON_CROSSHAIR_SIZE: u32 = 100;
+const ICON_BACKGROUND: Rgba<u8> = Rgba([24, 24, 28, 255]);
+/// Border drawn on the active-state icon
+const ICON_ACTIVE_BORDER: Rgba<u8> = Rgba([0, 200, 83, 255]);
+const ICON_BORDER_WIDTH: u32 = 8;
+/// How quickly a profile switched elsewhere (tray, hotkey) shows up on the deck
+const STATE_POLL_SECS: u64 = 1;
+
+/// Events sent by the plugin
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "camelCase")]
+enum DeckRequest {
+    GetProfiles,
+    GetState,
+    Toggle { profile: String },
+}
+
+/// Events sent to the plugin
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "camelCase")]
+enum DeckEvent {
+    Profiles { profiles: Vec<DeckProfile> },
+    #[serde(rename_all = "camelCase")]
+    State { active_profile: Option<String> },
+    Result { ok: bool, message: String },
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DeckProfile {
+    name: String,
+    active: bool,
+    icon: String,
+    active_icon: String,
+}
+
+/// Pressing a profile's key activates it, or deactivates it if it's already live
+fn toggle_command(profile: &str, active: Option<&str>) -> CliCommand {
+    if active == Some(profile) {
+        CliCommand::Deactivate
+    } else {
+        CliCommand::Activate { profile: profile.to_string() }
+    }
+}
+
+/// Key icon: the crosshair centered on a dark key, with a green border for the active state.
+/// Profiles without a crosshair image get the default generated crosshair.
+fn render_icon(crosshair_path: Option<&Path>, active: bool) -> RgbaImage {
+    let crosshair = crosshair_path
+        .and_then(|path| image::open(path).ok())
+        .map(|image| image.to_rgba8())
+        .unwrap_or_else(|| CrosshairParams::default().render());
+    let crosshair = imageops::resize(&crosshair, ICON_CROSSHAIR_SIZE, ICON_CROSSHAIR_SIZE, imageops::FilterType::Triangle);
+
+    let mut icon = RgbaImage::from_pixel(ICON_SIZE, ICON_SIZE, ICON_BACKGROUND);
+    let offset = i64::from((ICON_SIZE - ICON_CROSSHAIR_SIZE) / 2);
+    imageops::overlay(&mut icon, &crosshair, offset, offset);
+
+    if active {
+        for (x, y, pixel) in icon.enumerate_pixels_mut() {
+            let edge = x.min(y).min(ICON_SIZE - 1 - x).min(ICON_SIZE - 1 - y);
+            if edge < ICON_BORDER_WIDTH {
+                *pixel = ICON_ACTIVE_BORDER;
+            }
+        }
+    }
+    icon
+}
+
+fn icon_data_uri(icon: &RgbaImage) -> Result<String> {
+    let mut png = Vec::new();
+    icon.write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .map_err(|e| anyhow!("Failed to encode key icon: {}", e))?;
+    Ok(format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(png)))
+}
+
+/// Profiles as saved by the GUI, with both key icons
+fn deck_profiles(active: Option<&str>) -> Result<Vec<DeckProfile>> {
+    let profiles = load_profiles(&get_data_directory()?)?;
+    profiles
+        .into_iter()
+        .map(|profile| {
+            let crosshair = profile.crosshair_image_path.as_deref().map(Path::new);
+            Ok(DeckProfile {
+                active: active == Some(profile.name.as_str()),
+                icon: icon_data_uri(&render_icon(crosshair, false))?,
+                active_icon: icon_data_uri(&render_icon(crosshair, true))?,
+                name: profile.name,
+            })
+        })
+        .collect()
+}
+
+/// Active profile according to the running GUI
+fn active_profile(requests: &RequestSender) -> Option<String> {
+    requests.request(CliCommand::Status)
+        .data
+        .and_then(|data| serde_json::from_value::<LiveStatus>(data).ok())
+        .and_then(|status| status.active_profile)
+}
+
+fn send(socket: &mut WebSocket<TcpStream>, event: &DeckEvent) -> Result<()> {
+    let text = serde_json::to_string(event).map_err(|e| anyhow!("Failed to encode Stream Deck event: {}", e))?;
+    socket
+        .send(WsMessage::Text(text))
+        .map_err(|e| anyhow!("Failed to send Stream Deck event: {}", e))
+}
+
+/// Run one plugin connection until it closes or the API stops
+pub fn serve(mut socket: WebSocket<TcpStream>, requests: &RequestSender, stop: &AtomicBool) -> Result<()> {
+    set_poll_interval(&socket, Duration::from_secs(STATE_POLL_SECS))?;
+
+    let mut active = active_profile(requests);
+    send(&mut socket, &DeckEvent::State { active_profile: active.clone() })?;
+
+    while !stop.load(Ordering::Relaxed) {
+        let request = match socket.read() {
+            Ok(WsMessage::Text(text)) => serde_json::from_str::<DeckRequest>(&text),
+            Ok(WsMessage::Close(_)) | Err(WsError::ConnectionClosed) => return Ok(()),
+            Ok(_) => continue,
+            Err(ref e) if is_timeout(e) => {
+                let current = active_profile(requests);
+                if current != active {
+                    active = current;
+                    send(&mut socket, &DeckEvent::State { active_profile: active.clone() })?;
+                }
+                continue;
+            }
+            Err(e) => return Err(anyhow!("Stream Deck connection error: {}", e)),
+        };
+
+        match request {
+            Ok(DeckRequest::GetProfiles) => {
+                let event = match deck_profiles(active.as_deref()) {
+                    Ok(profiles) => DeckEvent::Profiles { profiles },
+                    Err(e) => DeckEvent::Result { ok: false, message: e.to_string() },
+                };
+                send(&mut socket, &event)?;
+            }
+            Ok(DeckRequest::GetState) => {
+                send(&mut socket, &DeckEvent::State { active_profile: active.clone() })?;
+            }
+            Ok(DeckRequest::Toggle { profile }) => {
+                let reply = requests.request(toggle_command(&profile, active.as_deref()));
+                send(&mut socket, &DeckEvent::Result { ok: reply.ok, message: reply.message })?;
+                // Answer with the new state right away instead of waiting for the next poll
+                active = active_profile(requests);
+                send(&mut socket, &DeckEvent::State { active_profile: active.clone() })?;
+            }
+            Err(e) => {
+                send(&mut socket, &DeckEvent::Result { ok: false, message: format!("Invalid event: {}", e) })?;
+            }
+        }
+    }
+    let _ = socket.close(None);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_messages() {
+        assert_eq!(
+            serde_json::from_str::<DeckRequest>(r#"{"event":"toggle","profile":"FPS"}"#).unwrap(),
+            DeckRequest::Toggle { profile: "FPS".to_string() }
+        );
+        assert_eq!(serde_json::from_str::<DeckRequest>(r#"{"event":"getProfiles"}"#).unwrap(), DeckRequest::GetProfiles);
+        assert_eq!(
+            serde_json::to_string(&DeckEvent::State { active_profile: Some("FPS".to_string()) }).unwrap(),
+            r#"{"event":"state","activeProfile":"FPS"}"#
+        );
+    }
+
+    #[test]
+    fn test_toggle_command() {
+        assert_eq!(toggle_command("FPS", Some("FPS")), CliCommand::Deactivate);
+        assert_eq!(toggle_command("FPS", Some("MOBA")), CliCommand::Activate { profile: "FPS".to_string() });
+        assert_eq!(toggle_command("FPS", None), CliCommand::Activate { profile: "FPS".to_string() });
+    }
+
+    #[test]
+    fn test_active_icon_has_border() {
+        let idle = render_icon(None, false);
+        let active = render_icon(None, true);
+        assert_eq!(idle.dimensions(), (ICON_SIZE, ICON_SIZE));
+        assert_eq!(*idle.get_pixel(0, 0), ICON_BACKGROUND);
+        assert_eq!(*active.get_pixel(0, 0), ICON_ACTIVE_BORDER);
+        // The crosshair itself is the same in both states
+        assert_eq!(idle.get_pixel(ICON_SIZE / 2, ICON_SIZE / 2), active.get_pixel(ICON_SIZE / 2, ICON_SIZE / 2));
+        assert!(icon_data_uri(&idle).unwrap().starts_with("data:image/png;base64,"));
+    }
+}
@@ -0,0 +1,318 @@
+//! Messages between the running mode and the threads that feed it
+//!
+//! Tray actions (icon clicks, context menu and flyout) come out of `TrayFlyoutManager::poll`,
+//! edits to the data directory's files out of `DataWatcher::poll`.
+//! Commands from the CLI pipe and the remote API go through a bounded queue: each request
+//! carries an id and its own reply channel, a full queue turns new requests away, and the mode
+//! beats a heartbeat every time it checks the queue, so callers get an answer right away
+//! instead of waiting out the reply timeout when it has hung.
+//!
+//! Whatever feeds the mode calls `wake`, so its loop can block in `wait` for up to a second
+//! while idle instead of polling every few milliseconds.
+
+use crate::cli::{CliCommand, CliReply, CliRequest};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Requests waiting for the running mode; more than this are answered with "busy"
+const REQUEST_QUEUE_SIZE: usize = 16;
+/// How long a request waits for the running mode to handle it
+const REPLY_TIMEOUT: Duration = Duration::from_secs(60);
+/// The mode checks the queue every tick; no check for this long means it has hung (an
+/// activation closing stubborn apps can legitimately hold it up for a while)
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Messages from Tray to GUI
+#[derive(Debug, Clone)]
+pub enum TrayToGui {
+    /// User selected a profile from tray
+    ActivateProfile(String),
+    /// User deactivated profile from tray
+    DeactivateProfile,
+    /// User toggled overlay from tray
+    ToggleOverlay,
+    /// User requested the next crosshair preset
+    CycleCrosshair,
+    /// User wants to move the live crosshair with the arrow keys
+    AdjustCrosshair,
+    /// User picked an offset preset of the active profile (None = its own offsets)
+    SelectOffsetPreset(Option<String>),
+    /// User paused or resumed the optimizer
+    TogglePause,
+    /// User ticked or unticked "Start with Windows"
+    SetAutostart(bool),
+    /// User requested to open settings/GUI
+    OpenSettings,
+    /// User requested exit
+    Exit,
+}
+
+/// Loop interval while idle: periodic work (sensor readings, overlay health) runs at this pace
+pub const IDLE_TICK: Duration = Duration::from_secs(1);
+/// Loop interval while a tray click or file change has to be picked up on time
+pub const BUSY_TICK: Duration = Duration::from_millis(50);
+
+/// Set by `wake` until the next `wait` returns
+static WAKE_PENDING: Mutex<bool> = Mutex::new(false);
+static WAKE_SIGNAL: Condvar = Condvar::new();
+/// Thread blocked in `wait_with_messages` (0 = none), woken with a posted message
+static MESSAGE_THREAD: AtomicU32 = AtomicU32::new(0);
+
+/// Wake the running mode's loop; something is waiting for it
+pub fn wake() {
+    if let Ok(mut pending) = WAKE_PENDING.lock() {
+        *pending = true;
+        WAKE_SIGNAL.notify_all();
+    }
+    #[cfg(windows)]
+    {
+        use windows::Win32::Foundation::{LPARAM, WPARAM};
+        use windows::Win32::UI::WindowsAndMessaging::{PostThreadMessageW, WM_NULL};
+
+        let thread = MESSAGE_THREAD.load(Ordering::Relaxed);
+        if thread != 0 {
+            let _ = unsafe { PostThreadMessageW(thread, WM_NULL, WPARAM(0), LPARAM(0)) };
+        }
+    }
+}
+
+/// Block until `wake` is called or `timeout` has passed
+pub fn wait(timeout: Duration) {
+    let Ok(pending) = WAKE_PENDING.lock() else {
+        std::thread::sleep(timeout);
+        return;
+    };
+    if let Ok((mut pending, _)) = WAKE_SIGNAL.wait_timeout_while(pending, timeout, |pending| !*pending) {
+        *pending = false;
+    }
+}
+
+/// Like `wait`, but also returns when a window message arrives for this thread, for loops that
+/// pump the tray icon's messages themselves
+#[cfg(windows)]
+pub fn wait_with_messages(timeout: Duration) {
+    use windows::Win32::System::Threading::GetCurrentThreadId;
+    use windows::Win32::UI::WindowsAndMessaging::{MsgWaitForMultipleObjects, QS_ALLINPUT};
+
+    MESSAGE_THREAD.store(unsafe { GetCurrentThreadId() }, Ordering::Relaxed);
+    if let Ok(mut pending) = WAKE_PENDING.lock() {
+        if std::mem::take(&mut *pending) {
+            return;
+        }
+    }
+    // A `wake` from here on posts a message, which ends the wait as well
+    let _ = unsafe { MsgWaitForMultipleObjects(None, false, timeout.as_millis() as u32, QS_ALLINPUT) };
+    if let Ok(mut pending) = WAKE_PENDING.lock() {
+        *pending = false;
+    }
+}
+
+#[cfg(not(windows))]
+pub fn wait_with_messages(timeout: Duration) {
+    wait(timeout);
+}
+
+/// A file in the data directory that was changed on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataFile {
+    /// The profile file (profiles.json, .toml or .yaml)
+    Profiles,
+    /// config.json
+    Config,
+}
+
+/// Last time the running mode checked for requests
+#[derive(Clone)]
+struct Heartbeat(Arc<Mutex<Instant>>);
+
+impl Heartbeat {
+    fn new() -> Self {
+        Heartbeat(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    fn beat(&self) {
+        if let Ok(mut last) = self.0.lock() {
+            *last = Instant::now();
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        self.0.lock().map(|last| last.elapsed() < HEARTBEAT_TIMEOUT).unwrap_or(false)
+    }
+}
+
+/// Queue for the CLI pipe and the remote API to hand commands to the running mode
+pub fn request_channel() -> (RequestSender, RequestReceiver) {
+    let (tx, rx) = sync_channel(REQUEST_QUEUE_SIZE);
+    let heartbeat = Heartbeat::new();
+    let sender = RequestSender { tx, heartbeat: heartbeat.clone(), next_id: Arc::new(AtomicU64::new(1)) };
+    (sender, RequestReceiver { rx, heartbeat })
+}
+
+/// Sending end, cloned into the pipe server and every API connection
+#[derive(Clone)]
+pub struct RequestSender {
+    tx: SyncSender<CliRequest>,
+    heartbeat: Heartbeat,
+    next_id: Arc<AtomicU64>,
+}
+
+impl RequestSender {
+    /// Hand a command to the running mode and wait for its answer
+    pub fn request(&self, command: CliCommand) -> CliReply {
+        self.request_with_timeout(command, REPLY_TIMEOUT)
+    }
+
+    fn request_with_timeout(&self, command: CliCommand, timeout: Duration) -> CliReply {
+        if !self.heartbeat.is_alive() {
+            return CliReply::error("Gaming Optimizer isn't responding");
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (reply_tx, reply_rx) = channel();
+        match self.tx.try_send(CliRequest { id, command, reply: reply_tx }) {
+            Ok(()) => wake(),
+            Err(TrySendError::Full(request)) => {
+                warn!("[IPC] Queue full, turned away request {} ({:?})", id, request.command);
+                return CliReply::error("Gaming Optimizer is busy, try again in a moment");
+            }
+            Err(TrySendError::Disconnected(_)) => return CliReply::error("Gaming Optimizer is shutting down"),
+        }
+
+        match reply_rx.recv_timeout(timeout) {
+            Ok(reply) => reply,
+            Err(_) => {
+                warn!("[IPC] Request {} got no answer within {:?}", id, timeout);
+                CliReply::error("Gaming Optimizer didn't answer in time")
+            }
+        }
+    }
+}
+
+/// Receiving end, polled by the running mode
+pub struct RequestReceiver {
+    rx: Receiver<CliRequest>,
+    heartbeat: Heartbeat,
+}
+
+impl RequestReceiver {
+    /// Next waiting request. Call every tick even when nothing is expected: that's the heartbeat.
+    pub fn try_recv(&self) -> Option<CliRequest> {
+        self.heartbeat.beat();
+        self.rx.try_recv().ok()
+    }
+}
+
+impl CliRequest {
+    /// Send the answer back to whoever is waiting for it
+    pub fn answer(self, reply: CliReply) {
+        if self.reply.send(reply).is_err() {
+            // The caller timed out; it already told its client so
+            debug!("[IPC] Answer to request {} ({:?}) came too late", self.id, self.command);
+        }
+    }
+}
+
+/// Caps the clients the CLI pipe or the remote API serve at once, so clients that connect and
+/// never finish can't pile up threads
+#[derive(Clone)]
+pub struct ConnectionLimit {
+    active: Arc<AtomicUsize>,
+    max: usize,
+}
+
+impl ConnectionLimit {
+    pub fn new(max: usize) -> Self {
+        ConnectionLimit { active: Arc::new(AtomicUsize::new(0)), max }
+    }
+
+    /// A slot for one more client (None when `max` are being served); freed when dropped
+    pub fn try_acquire(&self) -> Option<ConnectionSlot> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| (active < self.max).then_some(active + 1))
+            .ok()
+            .map(|_| ConnectionSlot(Arc::clone(&self.active)))
+    }
+}
+
+pub struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_limit() {
+        let limit = ConnectionLimit::new(2);
+        let first = limit.try_acquire().unwrap();
+        let _second = limit.clone().try_acquire().unwrap();
+        assert!(limit.try_acquire().is_none());
+        drop(first);
+        assert!(limit.try_acquire().is_some());
+    }
+
+    #[test]
+    fn test_request_gets_its_answer() {
+        let (sender, receiver) = request_channel();
+        let worker = std::thread::spawn(move || loop {
+            if let Some(request) = receiver.try_recv() {
+                let reply = CliReply::ok(format!("{:?}", request.command));
+                request.answer(reply);
+                return;
+            }
+            std::thread::yield_now();
+        });
+
+        let reply = sender.request(CliCommand::Status);
+        assert!(reply.ok);
+        assert_eq!(reply.message, "Status");
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn test_full_queue_turns_requests_away() {
+        let (sender, _receiver) = request_channel();
+        for _ in 0..REQUEST_QUEUE_SIZE {
+            let (reply, _) = channel();
+            sender.tx.try_send(CliRequest { id: 0, command: CliCommand::List, reply }).unwrap();
+        }
+
+        let reply = sender.request_with_timeout(CliCommand::List, Duration::from_millis(10));
+        assert!(!reply.ok);
+        assert!(reply.message.contains("busy"));
+    }
+
+    #[test]
+    fn test_hung_receiver_answers_right_away() {
+        let (sender, _receiver) = request_channel();
+        *sender.heartbeat.0.lock().unwrap() = Instant::now() - HEARTBEAT_TIMEOUT;
+
+        let reply = sender.request(CliCommand::List);
+        assert!(!reply.ok);
+        assert!(reply.message.contains("isn't responding"));
+    }
+
+    #[test]
+    fn test_wake_ends_wait() {
+        let start = Instant::now();
+        wake();
+        wait(Duration::from_secs(5));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_closed_receiver() {
+        let (sender, receiver) = request_channel();
+        drop(receiver);
+        assert!(sender.request(CliCommand::List).message.contains("shutting down"));
+    }
+}
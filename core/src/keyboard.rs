@@ -0,0 +1,395 @@
+//! Keyboard lock while gaming
+//!
+//! Pins the input language of the foreground window to a chosen layout, swallows the
+//! Windows keys with a low-level keyboard hook and turns off the Sticky Keys shortcut
+//! (Shift five times). The hook and layout check run on a dedicated thread with its own
+//! message loop; everything is undone on restore.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use tracing::info;
+
+/// Keyboard behavior while a profile is active
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyboardSettings {
+    /// Input layout to pin, as an 8-digit hex id like "04090409" (None = don't pin)
+    #[serde(default)]
+    pub layout: Option<String>,
+    /// Swallow the left/right Windows keys (also blocks Win+Space layout switching)
+    #[serde(default)]
+    pub block_windows_key: bool,
+    /// Disable the Sticky Keys shortcut so holding Shift can't open its popup
+    #[serde(default)]
+    pub suppress_sticky_keys: bool,
+}
+
+impl KeyboardSettings {
+    /// Whether any setting is configured
+    pub fn is_empty(&self) -> bool {
+        self.layout.is_none() && !self.block_windows_key && !self.suppress_sticky_keys
+    }
+
+    /// The settings that leave the game alone, for anti-cheat safe profiles: no low-level
+    /// keyboard hook (Win key block) and no layout requests posted to the foreground window
+    pub fn without_hooks(&self) -> KeyboardSettings {
+        KeyboardSettings {
+            layout: None,
+            block_windows_key: false,
+            suppress_sticky_keys: self.suppress_sticky_keys,
+        }
+    }
+}
+
+/// An installed keyboard layout
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    /// 8-digit hex layout id (as stored in profiles)
+    pub id: String,
+    pub name: String,
+}
+
+impl fmt::Display for KeyboardLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.name, self.id)
+    }
+}
+
+/// Layout id for a keyboard layout handle (its low 32 bits: device id + language id)
+pub fn format_layout_id(handle: isize) -> String {
+    format!("{:08X}", handle as u32)
+}
+
+/// Parse an 8-digit hex layout id
+pub fn parse_layout_id(id: &str) -> Option<u32> {
+    if id.len() != 8 {
+        return None;
+    }
+    u32::from_str_radix(id, 16).ok()
+}
+
+/// Handles of the layouts installed for this user
+#[cfg(windows)]
+fn layout_handles() -> Vec<windows::Win32::UI::TextServices::HKL> {
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetKeyboardLayoutList;
+    use windows::Win32::UI::TextServices::HKL;
+
+    unsafe {
+        let count = GetKeyboardLayoutList(None);
+        let mut handles = vec![HKL::default(); count.max(0) as usize];
+        let written = GetKeyboardLayoutList(Some(&mut handles));
+        handles.truncate(written.max(0) as usize);
+        handles
+    }
+}
+
+/// List the keyboard layouts installed for this user
+pub fn list_layouts() -> Result<Vec<KeyboardLayout>> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::Globalization::{GetLocaleInfoW, LOCALE_SLOCALIZEDDISPLAYNAME};
+
+        let layouts: Vec<KeyboardLayout> = layout_handles()
+            .into_iter()
+            .map(|handle| {
+                // The low word is the input language
+                let language = (handle.0 as u32) & 0xFFFF;
+                let mut buffer = [0u16; 128];
+                let len = unsafe { GetLocaleInfoW(language, LOCALE_SLOCALIZEDDISPLAYNAME, Some(&mut buffer)) };
+                let name = if len > 1 {
+                    String::from_utf16_lossy(&buffer[..len as usize - 1])
+                } else {
+                    format!("Language {:04X}", language)
+                };
+                KeyboardLayout { id: format_layout_id(handle.0), name }
+            })
+            .collect();
+
+        if layouts.is_empty() {
+            return Err(anyhow!("No keyboard layouts found"));
+        }
+        Ok(layouts)
+    }
+
+    #[cfg(not(windows))]
+    Err(anyhow!("Keyboard layouts are only available on Windows"))
+}
+
+/// How often the foreground window's layout is checked while pinned
+#[cfg(windows)]
+const LAYOUT_CHECK_MS: u32 = 500;
+
+/// Low-level keyboard hook swallowing the Windows keys
+#[cfg(windows)]
+unsafe extern "system" fn block_windows_key_proc(
+    code: i32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+    use windows::Win32::Foundation::LRESULT;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{VK_LWIN, VK_RWIN};
+    use windows::Win32::UI::WindowsAndMessaging::{CallNextHookEx, HC_ACTION, HHOOK, KBDLLHOOKSTRUCT};
+
+    if code == HC_ACTION as i32 {
+        let info = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+        if info.vkCode == VK_LWIN.0 as u32 || info.vkCode == VK_RWIN.0 as u32 {
+            return LRESULT(1);
+        }
+    }
+    CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+}
+
+/// Ask the foreground window to switch to `layout` if it's using another one
+#[cfg(windows)]
+unsafe fn enforce_layout(layout: windows::Win32::UI::TextServices::HKL) {
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetKeyboardLayout;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId, PostMessageW, WM_INPUTLANGCHANGEREQUEST,
+    };
+
+    let window = GetForegroundWindow();
+    if window.0 == 0 {
+        return;
+    }
+    let thread_id = GetWindowThreadProcessId(window, None);
+    if GetKeyboardLayout(thread_id) != layout {
+        // Elevated games ignore this (UIPI) unless the optimizer is elevated too
+        let _ = PostMessageW(window, WM_INPUTLANGCHANGEREQUEST, WPARAM(0), LPARAM(layout.0));
+    }
+}
+
+/// Thread running the keyboard hook and/or layout check
+#[cfg(windows)]
+struct HookThread {
+    thread_id: u32,
+    handle: std::thread::JoinHandle<()>,
+}
+
+#[cfg(windows)]
+impl HookThread {
+    fn spawn(block_windows_key: bool, layout: Option<windows::Win32::UI::TextServices::HKL>) -> Result<Self> {
+        use std::sync::mpsc::channel;
+        use windows::Win32::Foundation::{HINSTANCE, HWND};
+        use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+        use windows::Win32::System::Threading::GetCurrentThreadId;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            GetMessageW, KillTimer, PeekMessageW, SetTimer, SetWindowsHookExW, UnhookWindowsHookEx, MSG,
+            PM_NOREMOVE, WH_KEYBOARD_LL, WM_TIMER, WM_USER,
+        };
+
+        let (ready_tx, ready_rx) = channel();
+        let handle = std::thread::spawn(move || unsafe {
+            // Create the message queue before reporting ready so the quit message can't be lost
+            let mut msg = MSG::default();
+            let _ = PeekMessageW(&mut msg, HWND::default(), WM_USER, WM_USER, PM_NOREMOVE);
+
+            let hook = if block_windows_key {
+                let module = GetModuleHandleW(None).unwrap_or_default();
+                match SetWindowsHookExW(WH_KEYBOARD_LL, Some(block_windows_key_proc), HINSTANCE(module.0), 0) {
+                    Ok(hook) => Some(hook),
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(anyhow!("Failed to install keyboard hook: {}", e)));
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+            let timer = layout.map(|layout| {
+                enforce_layout(layout);
+                SetTimer(HWND::default(), 0, LAYOUT_CHECK_MS, None)
+            });
+            let _ = ready_tx.send(Ok(GetCurrentThreadId()));
+
+            while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+                if msg.message == WM_TIMER {
+                    if let Some(layout) = layout {
+                        enforce_layout(layout);
+                    }
+                }
+            }
+
+            if let Some(timer) = timer {
+                let _ = KillTimer(HWND::default(), timer);
+            }
+            if let Some(hook) = hook {
+                let _ = UnhookWindowsHookEx(hook);
+            }
+        });
+
+        let thread_id = ready_rx
+            .recv()
+            .map_err(|_| anyhow!("Keyboard hook thread exited"))??;
+        Ok(HookThread { thread_id, handle })
+    }
+
+    fn stop(self) {
+        use windows::Win32::Foundation::{LPARAM, WPARAM};
+        use windows::Win32::UI::WindowsAndMessaging::{PostThreadMessageW, WM_QUIT};
+
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        let _ = self.handle.join();
+    }
+}
+
+/// Current Sticky Keys flags
+#[cfg(windows)]
+fn sticky_keys_flags() -> Result<u32> {
+    use windows::Win32::UI::Accessibility::STICKYKEYS;
+    use windows::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPI_GETSTICKYKEYS, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS};
+
+    let mut sticky_keys = STICKYKEYS {
+        cbSize: std::mem::size_of::<STICKYKEYS>() as u32,
+        ..Default::default()
+    };
+    unsafe {
+        SystemParametersInfoW(
+            SPI_GETSTICKYKEYS,
+            sticky_keys.cbSize,
+            Some(&mut sticky_keys as *mut STICKYKEYS as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    }
+    .map_err(|e| anyhow!("Failed to read Sticky Keys settings: {}", e))?;
+    Ok(sticky_keys.dwFlags.0)
+}
+
+/// Set the Sticky Keys flags for this session only (not written to the user profile,
+/// so a crash leaves the shortcut back on after the next sign-in)
+#[cfg(windows)]
+fn set_sticky_keys_flags(flags: u32) -> Result<()> {
+    use windows::Win32::UI::Accessibility::{STICKYKEYS, STICKYKEYS_FLAGS};
+    use windows::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPI_SETSTICKYKEYS, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS};
+
+    let mut sticky_keys = STICKYKEYS {
+        cbSize: std::mem::size_of::<STICKYKEYS>() as u32,
+        dwFlags: STICKYKEYS_FLAGS(flags),
+    };
+    unsafe {
+        SystemParametersInfoW(
+            SPI_SETSTICKYKEYS,
+            sticky_keys.cbSize,
+            Some(&mut sticky_keys as *mut STICKYKEYS as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    }
+    .map_err(|e| anyhow!("Failed to change Sticky Keys settings: {}", e))
+}
+
+/// Applies a profile's keyboard settings and undoes them afterwards
+#[derive(Default)]
+pub struct KeyboardLock {
+    #[cfg(windows)]
+    hook_thread: Option<HookThread>,
+    /// Sticky Keys flags before the shortcut was disabled (None = nothing to restore)
+    previous_sticky_keys: Option<u32>,
+}
+
+impl KeyboardLock {
+    /// Restore any previous profile's settings, then apply the new ones.
+    /// Returns a summary of what was changed.
+    pub fn apply(&mut self, settings: &KeyboardSettings) -> Result<Vec<String>> {
+        self.restore()?;
+        if settings.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        #[cfg(windows)]
+        {
+            use windows::Win32::UI::Accessibility::SKF_HOTKEYACTIVE;
+
+            let mut applied = Vec::new();
+
+            let layout = match settings.layout {
+                Some(ref id) => {
+                    let wanted = parse_layout_id(id).ok_or_else(|| anyhow!("Invalid keyboard layout id: {}", id))?;
+                    let handle = layout_handles()
+                        .into_iter()
+                        .find(|handle| handle.0 as u32 == wanted)
+                        .ok_or_else(|| anyhow!("Keyboard layout {} is not installed", id))?;
+                    Some(handle)
+                }
+                None => None,
+            };
+
+            if settings.block_windows_key || layout.is_some() {
+                self.hook_thread = Some(HookThread::spawn(settings.block_windows_key, layout)?);
+                if let Some(ref id) = settings.layout {
+                    let name = list_layouts()
+                        .ok()
+                        .and_then(|layouts| layouts.into_iter().find(|layout| layout.id.eq_ignore_ascii_case(id)))
+                        .map_or_else(|| id.clone(), |layout| layout.name);
+                    applied.push(format!("{} layout pinned", name));
+                }
+                if settings.block_windows_key {
+                    applied.push("Win key blocked".to_string());
+                }
+            }
+
+            if settings.suppress_sticky_keys {
+                let flags = sticky_keys_flags()?;
+                if flags & SKF_HOTKEYACTIVE.0 != 0 {
+                    set_sticky_keys_flags(flags & !SKF_HOTKEYACTIVE.0)?;
+                    self.previous_sticky_keys = Some(flags);
+                }
+                applied.push("Sticky Keys shortcut off".to_string());
+            }
+
+            info!("[Keyboard] Applied {}", applied.join(", "));
+            Ok(applied)
+        }
+
+        #[cfg(not(windows))]
+        Err(anyhow!("Keyboard lock is only available on Windows"))
+    }
+
+    /// Undo `apply` (no-op if nothing was applied)
+    pub fn restore(&mut self) -> Result<()> {
+        #[cfg(windows)]
+        {
+            if let Some(thread) = self.hook_thread.take() {
+                thread.stop();
+                info!("[Keyboard] Keyboard hook removed");
+            }
+            if let Some(flags) = self.previous_sticky_keys {
+                set_sticky_keys_flags(flags)?;
+                self.previous_sticky_keys = None;
+                info!("[Keyboard] Sticky Keys shortcut restored");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_ids() {
+        assert_eq!(format_layout_id(0x0409_0409), "04090409");
+        // 64-bit handles of layout variants are sign-extended
+        assert_eq!(format_layout_id(0xFFFF_FFFF_F002_0409_u64 as isize), "F0020409");
+        assert_eq!(parse_layout_id("04090409"), Some(0x0409_0409));
+        assert_eq!(parse_layout_id("f0020409"), Some(0xF002_0409));
+        assert_eq!(parse_layout_id("0409"), None);
+        assert_eq!(parse_layout_id("0409040G"), None);
+    }
+
+    #[test]
+    fn test_without_hooks() {
+        let settings = KeyboardSettings {
+            layout: Some("04090409".to_string()),
+            block_windows_key: true,
+            suppress_sticky_keys: true,
+        };
+        let safe = settings.without_hooks();
+        assert_eq!(safe.layout, None);
+        assert!(!safe.block_windows_key);
+        assert!(safe.suppress_sticky_keys);
+        assert!(KeyboardSettings { suppress_sticky_keys: false, ..settings }.without_hooks().is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+//! Kill list import
+//!
+//! Reads lists of apps to close that users already keep for other tools: plain text (one
+//! exe per line, also `taskkill /im` lines of debloat scripts), CSV exports with a process
+//! column, and Razer Cortex boost lists (XML with full exe paths). Protected processes are
+//! filtered out before the names reach a profile.
+
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use crate::process;
+
+/// File types offered in the import dialog
+pub const EXTENSIONS: [&str; 6] = ["txt", "csv", "xml", "bat", "cmd", "ps1"];
+
+/// CSV headers of the column that holds the process names, most specific first
+const CSV_PROCESS_HEADERS: [&str; 7] = ["process name", "processname", "image name", "process", "exe", "executable", "name"];
+
+/// Names read from a kill list
+#[derive(Debug, Default, PartialEq)]
+pub struct KillListImport {
+    /// Exe names to close, in file order without duplicates
+    pub processes: Vec<String>,
+    /// Entries left out because they are on the protected list
+    pub protected: Vec<String>,
+}
+
+/// Characters that end an exe name inside a line (quotes, XML, CSV, paths, switches)
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | ',' | ';' | '=' | '|' | '/' | '\\' | ':' | '*' | '?' | '(' | ')')
+}
+
+/// Every `name.exe` in `text`; folders of full paths are dropped
+fn exe_names(text: &str) -> Vec<String> {
+    text.split(is_separator)
+        .filter(|token| token.len() > 4 && token.to_lowercase().ends_with(".exe"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// A bare list entry as an exe name ("discord" -> "discord.exe")
+fn with_exe(name: &str) -> String {
+    if name.to_lowercase().ends_with(".exe") {
+        name.to_string()
+    } else {
+        format!("{}.exe", name)
+    }
+}
+
+/// One entry per line; lines without an exe name count as a bare name when they are a single word
+fn parse_text(contents: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', ';']) || line.starts_with("//") || line.to_lowercase().starts_with("rem ") {
+            continue;
+        }
+        let exes = exe_names(line);
+        if !exes.is_empty() {
+            names.extend(exes);
+        } else if !line.contains(is_separator) {
+            names.push(with_exe(line));
+        }
+    }
+    names
+}
+
+/// CSV with a header: the process column if there is one, otherwise any cell with an exe name
+fn parse_csv(contents: &str) -> Vec<String> {
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let cells = |line: &str| -> Vec<String> {
+        line.split([',', ';']).map(|cell| cell.trim().trim_matches('"').trim().to_string()).collect()
+    };
+    let headers: Vec<String> = cells(header).iter().map(|cell| cell.to_lowercase()).collect();
+    let column = CSV_PROCESS_HEADERS.iter().find_map(|name| headers.iter().position(|header| header == name));
+
+    let mut names = exe_names(header);
+    for line in lines {
+        match column {
+            Some(column) => {
+                if let Some(cell) = cells(line).get(column).filter(|cell| !cell.is_empty()) {
+                    names.push(exe_names(cell).pop().unwrap_or_else(|| with_exe(cell)));
+                }
+            }
+            None => names.extend(exe_names(line)),
+        }
+    }
+    names
+}
+
+/// Parse a kill list; the format comes from the extension (CSV, XML) with plain text as the fallback
+pub fn parse(path: &Path, contents: &str) -> KillListImport {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+    let names = match extension.as_str() {
+        "csv" => parse_csv(contents),
+        // Razer Cortex and other XML lists: the exe paths are in element text or attributes
+        "xml" => exe_names(contents),
+        _ if contents.trim_start().starts_with('<') => exe_names(contents),
+        _ => parse_text(contents),
+    };
+
+    let mut import = KillListImport::default();
+    let mut seen = HashSet::new();
+    for name in names {
+        if !seen.insert(name.to_lowercase()) {
+            continue;
+        }
+        if process::would_be_protected(&name) {
+            import.protected.push(name);
+        } else {
+            import.processes.push(name);
+        }
+    }
+    import
+}
+
+/// Read and parse a kill list file
+pub fn import_file(path: &Path) -> Result<KillListImport> {
+    let bytes = fs::read(path).map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+    let import = parse(path, &String::from_utf8_lossy(&bytes));
+    if import.processes.is_empty() && import.protected.is_empty() {
+        return Err(anyhow!("No process names found in {}", path.display()));
+    }
+    Ok(import)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text() {
+        let list = "# apps to close\nDiscord.exe\nspotify\n\ntaskkill /F /IM OneDrive.exe\nREM cleanup\ndiscord.exe\nexplorer.exe\n";
+        let import = parse(Path::new("list.txt"), list);
+        assert_eq!(import.processes, ["Discord.exe", "spotify.exe", "OneDrive.exe"]);
+        assert_eq!(import.protected, ["explorer.exe"]);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "Name,Process Name,Memory\nDiscord,Discord.exe,120 MB\n\"Steam\",\"steamwebhelper\",80 MB\nShell,dwm.exe,40 MB\n";
+        let import = parse(Path::new("export.csv"), csv);
+        assert_eq!(import.processes, ["Discord.exe", "steamwebhelper.exe"]);
+        assert_eq!(import.protected, ["dwm.exe"]);
+
+        // No header names a process column: every exe cell counts
+        let import = parse(Path::new("export.csv"), "App;Path\nChrome;C:\\Program Files\\Google\\chrome.exe\n");
+        assert_eq!(import.processes, ["chrome.exe"]);
+    }
+
+    #[test]
+    fn test_parse_xml() {
+        let xml = r#"<?xml version="1.0"?>
+<BoostList>
+  <Process Path="C:\Program Files\Epic Games\EpicGamesLauncher.exe" Enabled="true" />
+  <Process><Path>C:\Windows\System32\svchost.exe</Path></Process>
+</BoostList>"#;
+        let import = parse(Path::new("Cortex.xml"), xml);
+        assert_eq!(import.processes, ["EpicGamesLauncher.exe"]);
+        assert_eq!(import.protected, ["svchost.exe"]);
+    }
+}
@@ -0,0 +1,338 @@
+//! Latency to the game server
+//!
+//! While a profile is active, the game's busiest remote endpoint is looked up in the TCP
+//! connection table and pinged once a second (ICMP echo, falling back to timing a TCP connect
+//! for servers that drop ICMP). Current/average ping and jitter feed the dashboard, the overlay
+//! HUD and the session stats, to tell a slow PC apart from a slow connection.
+//!
+//! UDP has no remote endpoint in the connection tables, so games that talk to their server over
+//! UDP only are measured against their TCP (matchmaking/API) server instead.
+
+use std::collections::VecDeque;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Samples the average and jitter are computed over
+const WINDOW: usize = 30;
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+const PING_TIMEOUT_MS: u32 = 1000;
+/// How often the endpoint is looked up again (the game may have changed servers)
+const DETECT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Latency figures in milliseconds (None = no successful ping in the window)
+#[derive(Clone, Debug, PartialEq)]
+pub struct LatencyStats {
+    pub endpoint: SocketAddr,
+    pub current_ms: Option<f32>,
+    pub average_ms: Option<f32>,
+    /// Mean difference between consecutive successful pings
+    pub jitter_ms: Option<f32>,
+    /// Share of lost pings in the window, 0-100
+    pub loss_percent: f32,
+}
+
+impl LatencyStats {
+    /// Line for the dashboard and the overlay HUD, e.g. "Ping 24ms avg 27 jit 3"
+    pub fn summary_line(&self) -> String {
+        match (self.current_ms, self.average_ms) {
+            (Some(current), Some(average)) => format!(
+                "Ping {:.0}ms avg {:.0} jit {:.0}",
+                current,
+                average,
+                self.jitter_ms.unwrap_or(0.0)
+            ),
+            (None, Some(average)) => format!("Ping timeout (avg {:.0}ms)", average),
+            _ => "Ping timeout".to_string(),
+        }
+    }
+}
+
+/// Last `WINDOW` ping results (None = lost)
+#[derive(Default)]
+struct PingWindow {
+    samples: VecDeque<Option<f32>>,
+}
+
+impl PingWindow {
+    fn push(&mut self, sample: Option<f32>) {
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    fn stats(&self, endpoint: SocketAddr) -> LatencyStats {
+        let replies: Vec<f32> = self.samples.iter().flatten().copied().collect();
+        let average_ms = (!replies.is_empty()).then(|| replies.iter().sum::<f32>() / replies.len() as f32);
+        let jitter_ms = (replies.len() > 1).then(|| {
+            replies.windows(2).map(|pair| (pair[1] - pair[0]).abs()).sum::<f32>() / (replies.len() - 1) as f32
+        });
+        let lost = self.samples.iter().filter(|sample| sample.is_none()).count();
+
+        LatencyStats {
+            endpoint,
+            current_ms: self.samples.back().copied().flatten(),
+            average_ms,
+            jitter_ms,
+            loss_percent: if self.samples.is_empty() { 0.0 } else { lost as f32 * 100.0 / self.samples.len() as f32 },
+        }
+    }
+}
+
+/// Pick the game server among a process's connections: public addresses only, preferring
+/// ports other than web traffic (80/443 are usually launchers, stores and telemetry), then the
+/// endpoint the process has the most connections to.
+fn pick_endpoint(connections: &[SocketAddr]) -> Option<SocketAddr> {
+    let is_public = |addr: &SocketAddr| match addr.ip() {
+        IpAddr::V4(ip) => !(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified()),
+        IpAddr::V6(ip) => !(ip.is_loopback() || ip.is_unspecified()),
+    };
+    let is_web = |addr: &SocketAddr| matches!(addr.port(), 80 | 443);
+
+    let mut candidates: Vec<(SocketAddr, usize)> = Vec::new();
+    for addr in connections.iter().filter(|addr| is_public(addr)) {
+        match candidates.iter_mut().find(|(known, _)| known == addr) {
+            Some((_, count)) => *count += 1,
+            None => candidates.push((*addr, 1)),
+        }
+    }
+    candidates
+        .into_iter()
+        .max_by_key(|(addr, count)| (!is_web(addr), *count))
+        .map(|(addr, _)| addr)
+}
+
+/// Remote ends of the established TCP connections of processes named `process_name`
+#[cfg(windows)]
+fn game_connections(process_name: &str) -> Vec<SocketAddr> {
+    use windows::Win32::Foundation::NO_ERROR;
+    use windows::Win32::NetworkManagement::IpHelper::{
+        GetExtendedTcpTable, MIB_TCPROW_OWNER_PID, MIB_TCP_STATE_ESTAB, TCP_TABLE_OWNER_PID_ALL,
+    };
+    const AF_INET: u32 = 2;
+
+    let mut system = sysinfo::System::new();
+    system.refresh_processes();
+    let pids: Vec<u32> = system
+        .processes()
+        .iter()
+        .filter(|(_, process)| process.name().eq_ignore_ascii_case(process_name))
+        .map(|(pid, _)| pid.as_u32())
+        .collect();
+    if pids.is_empty() {
+        return Vec::new();
+    }
+
+    unsafe {
+        // Ask for the size first; retry in case connections were added in between
+        let mut size = 0u32;
+        let mut buffer: Vec<u32> = Vec::new();
+        for _ in 0..3 {
+            let result = GetExtendedTcpTable(
+                (!buffer.is_empty()).then(|| buffer.as_mut_ptr().cast()),
+                &mut size,
+                false,
+                AF_INET,
+                TCP_TABLE_OWNER_PID_ALL,
+                0,
+            );
+            if result == NO_ERROR.0 && !buffer.is_empty() {
+                break;
+            }
+            buffer = vec![0u32; (size as usize).div_ceil(4)];
+        }
+        if buffer.is_empty() {
+            return Vec::new();
+        }
+
+        // MIB_TCPTABLE_OWNER_PID: entry count followed by the rows
+        let count = buffer[0] as usize;
+        let rows = std::slice::from_raw_parts(buffer.as_ptr().add(1).cast::<MIB_TCPROW_OWNER_PID>(), count);
+        rows.iter()
+            .filter(|row| row.dwState == MIB_TCP_STATE_ESTAB.0 as u32 && pids.contains(&row.dwOwningPid))
+            .map(|row| {
+                // Address and port are in network byte order
+                let ip = Ipv4Addr::from(row.dwRemoteAddr.to_ne_bytes());
+                let port = u16::from_be(row.dwRemotePort as u16);
+                SocketAddr::new(IpAddr::V4(ip), port)
+            })
+            .collect()
+    }
+}
+
+#[cfg(not(windows))]
+fn game_connections(_process_name: &str) -> Vec<SocketAddr> {
+    Vec::new()
+}
+
+/// ICMP echo round trip in milliseconds (None = no reply)
+#[cfg(windows)]
+fn icmp_ping(ip: Ipv4Addr) -> Option<f32> {
+    use windows::Win32::NetworkManagement::IpHelper::{IcmpCloseHandle, IcmpCreateFile, IcmpSendEcho, ICMP_ECHO_REPLY};
+
+    const PAYLOAD: [u8; 32] = [0x61; 32];
+    unsafe {
+        let handle = IcmpCreateFile().ok()?;
+        // Room for one reply, its payload and an ICMP error
+        let mut reply = vec![0u8; std::mem::size_of::<ICMP_ECHO_REPLY>() + PAYLOAD.len() + 8];
+        let replies = IcmpSendEcho(
+            handle,
+            u32::from_ne_bytes(ip.octets()),
+            PAYLOAD.as_ptr().cast(),
+            PAYLOAD.len() as u16,
+            None,
+            reply.as_mut_ptr().cast(),
+            reply.len() as u32,
+            PING_TIMEOUT_MS,
+        );
+        let _ = IcmpCloseHandle(handle);
+
+        let echo = reply.as_ptr().cast::<ICMP_ECHO_REPLY>().read_unaligned();
+        // Status 0 = IP_SUCCESS
+        (replies > 0 && echo.Status == 0).then_some(echo.RoundTripTime as f32)
+    }
+}
+
+#[cfg(not(windows))]
+fn icmp_ping(_ip: Ipv4Addr) -> Option<f32> {
+    None
+}
+
+/// Time a TCP handshake with the endpoint (for servers that drop ICMP)
+fn tcp_ping(endpoint: SocketAddr) -> Option<f32> {
+    let started = Instant::now();
+    TcpStream::connect_timeout(&endpoint, Duration::from_millis(PING_TIMEOUT_MS as u64))
+        .ok()
+        .map(|_| started.elapsed().as_secs_f32() * 1000.0)
+}
+
+struct Shared {
+    latest: Mutex<Option<LatencyStats>>,
+    running: AtomicBool,
+}
+
+/// Pings a game's server on a background thread until dropped
+pub struct LatencyMonitor {
+    process_name: String,
+    shared: Arc<Shared>,
+}
+
+impl LatencyMonitor {
+    pub fn spawn(process_name: &str) -> Self {
+        let shared = Arc::new(Shared {
+            latest: Mutex::new(None),
+            running: AtomicBool::new(true),
+        });
+
+        let thread_shared = shared.clone();
+        let name = process_name.to_string();
+        std::thread::spawn(move || {
+            let mut endpoint: Option<SocketAddr> = None;
+            let mut use_icmp = true;
+            let mut window = PingWindow::default();
+            let mut last_detect: Option<Instant> = None;
+
+            while thread_shared.running.load(Ordering::Relaxed) {
+                if last_detect.is_none_or(|at| at.elapsed() >= DETECT_INTERVAL) {
+                    last_detect = Some(Instant::now());
+                    let detected = pick_endpoint(&game_connections(&name));
+                    if detected != endpoint {
+                        if let Some(addr) = detected {
+                            info!("[Latency] {} server: {}", name, addr);
+                        }
+                        endpoint = detected;
+                        use_icmp = true;
+                        window = PingWindow::default();
+                    }
+                }
+
+                let stats = endpoint.map(|addr| {
+                    let mut sample = match addr.ip() {
+                        IpAddr::V4(ip) if use_icmp => icmp_ping(ip),
+                        _ => None,
+                    };
+                    // Switch to TCP for good once ICMP turns out to be blocked
+                    if sample.is_none() {
+                        sample = tcp_ping(addr);
+                        if sample.is_some() {
+                            use_icmp = false;
+                        }
+                    }
+                    window.push(sample);
+                    window.stats(addr)
+                });
+                if let Ok(mut latest) = thread_shared.latest.lock() {
+                    *latest = stats;
+                }
+                std::thread::sleep(PING_INTERVAL);
+            }
+        });
+
+        LatencyMonitor {
+            process_name: process_name.to_string(),
+            shared,
+        }
+    }
+
+    /// Executable whose server is being pinged
+    pub fn process_name(&self) -> &str {
+        &self.process_name
+    }
+
+    /// Latest figures (None until the game's server has been found)
+    pub fn latest(&self) -> Option<LatencyStats> {
+        self.shared.latest.lock().ok().and_then(|latest| latest.clone())
+    }
+}
+
+impl Drop for LatencyMonitor {
+    fn drop(&mut self) {
+        self.shared.running.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(text: &str) -> SocketAddr {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_pick_endpoint_prefers_public_non_web() {
+        let connections = [
+            addr("127.0.0.1:5000"),
+            addr("192.168.1.10:7000"),
+            addr("104.16.1.1:443"),
+            addr("104.16.1.1:443"),
+            addr("162.249.72.1:7100"),
+        ];
+        assert_eq!(pick_endpoint(&connections), Some(addr("162.249.72.1:7100")));
+        assert_eq!(pick_endpoint(&connections[..4]), Some(addr("104.16.1.1:443")));
+        assert_eq!(pick_endpoint(&connections[..2]), None);
+    }
+
+    #[test]
+    fn test_ping_window_stats() {
+        let endpoint = addr("1.2.3.4:7100");
+        let mut window = PingWindow::default();
+        for sample in [Some(20.0), Some(30.0), None, Some(25.0)] {
+            window.push(sample);
+        }
+        let stats = window.stats(endpoint);
+        assert_eq!(stats.current_ms, Some(25.0));
+        assert_eq!(stats.average_ms, Some(25.0));
+        assert_eq!(stats.jitter_ms, Some(7.5));
+        assert_eq!(stats.loss_percent, 25.0);
+        assert_eq!(stats.summary_line(), "Ping 25ms avg 25 jit 8");
+
+        for _ in 0..WINDOW {
+            window.push(None);
+        }
+        assert_eq!(window.stats(endpoint).summary_line(), "Ping timeout");
+    }
+}
@@ -0,0 +1,74 @@
+//! Gaming Optimizer core
+//!
+//! Everything the app does apart from windows, the tray and the desktop: profiles, process
+//! closing, activation and revert, config and the data folder, the control pipe and remote API.
+//! Nothing in here needs a display or the tray, so it's tested on its own; the GUI, tray flyout,
+//! global hotkeys, toasts and the crosshair overlay launcher live in the `gaming_optimizer`
+//! binary, which hands activation its overlay through `overlay::OverlayBackend`.
+
+pub mod config;
+pub mod preflight;
+pub mod process;
+pub mod profile;
+pub mod profile_lint;
+pub mod tray_clicks;
+pub mod tray_tooltip;
+pub mod theme;
+pub mod ipc;
+pub mod common_apps;
+pub mod game_knowledge;
+pub mod game_watch;
+pub mod kill_list_import;
+pub mod crosshair_library;
+pub mod overlay;
+pub mod crosshair_gen;
+pub mod crosshair_codes;
+pub mod crosshair_position;
+pub mod overlay_text;
+pub mod overlay_render;
+pub mod overlay_conflicts;
+pub mod hotkeys;
+pub mod shell;
+pub mod services;
+pub mod fan;
+pub mod file_watch;
+pub mod power;
+pub mod registry;
+pub mod game_features;
+pub mod ntdll;
+pub mod timer;
+pub mod display;
+pub mod audio;
+pub mod network;
+pub mod storage;
+pub mod obs;
+pub mod captures;
+pub mod gamepad;
+pub mod openrgb;
+pub mod integrations;
+pub mod plugins;
+pub mod notifications;
+pub mod toasts;
+pub mod gpu;
+pub mod tweaks;
+pub mod keyboard;
+pub mod monitor;
+#[cfg(windows)]
+pub mod wmi;
+pub mod sessions;
+pub mod benchmark;
+pub mod alerts;
+pub mod latency;
+pub mod logging;
+pub mod crash;
+pub mod cli;
+pub mod remote_api;
+pub mod single_instance;
+pub mod elevation;
+pub mod autostart;
+pub mod backup;
+pub mod migration;
+pub mod updater;
+pub mod activation;
+pub mod visual_effects;
+pub mod i18n;
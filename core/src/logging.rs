@@ -0,0 +1,190 @@
+//! Log files in the data directory
+//!
+//! The app runs without a console, so log output goes through `tracing` into
+//! logs/gaming_optimizer.log (rotated at 1 MB, the last five files kept) as well as stdout for
+//! runs from a terminal (not for CLI commands, which print their own output there). The level
+//! comes from the config and can be changed while running.
+
+use crate::config::get_data_directory;
+use crate::i18n::t;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::error;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, Registry};
+
+const LOG_FILE: &str = "gaming_optimizer.log";
+const MAX_BYTES: u64 = 1024 * 1024;
+/// Rotated files kept next to the current one
+const KEEP_FILES: usize = 4;
+
+static LEVEL_HANDLE: OnceCell<reload::Handle<LevelFilter, Registry>> = OnceCell::new();
+
+/// Most verbose level written to the log (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace];
+
+    fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogLevel::Error => write!(f, "{}", t!("log-level-error")),
+            LogLevel::Warn => write!(f, "{}", t!("log-level-warn")),
+            LogLevel::Info => write!(f, "{}", t!("log-level-info")),
+            LogLevel::Debug => write!(f, "{}", t!("log-level-debug")),
+            LogLevel::Trace => write!(f, "{}", t!("log-level-trace")),
+        }
+    }
+}
+
+/// Folder the log files are written to
+pub fn log_dir() -> Option<PathBuf> {
+    get_data_directory().ok().map(|dir| dir.join("logs"))
+}
+
+/// Show the log folder in Explorer
+pub fn open_log_dir() {
+    let Some(dir) = log_dir() else {
+        error!("[Logging] No data directory to open logs from");
+        return;
+    };
+    if let Err(e) = open::that(&dir) {
+        error!("[Logging] Failed to open {}: {}", dir.display(), e);
+    }
+}
+
+/// Append-only log file that moves itself to gaming_optimizer.1.log (and .1 to .2, ...)
+/// once it grows past `max_bytes`
+struct RotatingFile {
+    dir: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+}
+
+impl RotatingFile {
+    fn open(dir: &Path, max_bytes: u64) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let file = OpenOptions::new().create(true).append(true).open(dir.join(LOG_FILE))?;
+        let written = file.metadata()?.len();
+        Ok(RotatingFile { dir: dir.to_path_buf(), file, written, max_bytes })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("gaming_optimizer.{}.log", index))
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        // The oldest file is overwritten by the rename
+        for index in (1..KEEP_FILES).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(self.dir.join(LOG_FILE), self.rotated_path(1))?;
+
+        self.file = OpenOptions::new().create(true).append(true).open(self.dir.join(LOG_FILE))?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            // Keep logging into the current file if it can't be moved (e.g. open in an editor)
+            let _ = self.rotate();
+        }
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Install the global subscriber, also logging to stdout if `console`
+pub fn init(level: LogLevel, console: bool) {
+    let (filter, handle) = reload::Layer::new(level.filter());
+
+    let file_layer = log_dir()
+        .and_then(|dir| RotatingFile::open(&dir, MAX_BYTES).ok())
+        .map(|file| tracing_subscriber::fmt::layer().with_ansi(false).with_writer(Mutex::new(file)));
+
+    if tracing_subscriber::registry()
+        .with(filter)
+        .with(file_layer)
+        .with(console.then(tracing_subscriber::fmt::layer))
+        .try_init()
+        .is_ok()
+    {
+        let _ = LEVEL_HANDLE.set(handle);
+    }
+}
+
+/// Change the level of the running subscriber
+pub fn set_level(level: LogLevel) {
+    if let Some(handle) = LEVEL_HANDLE.get() {
+        let _ = handle.modify(|filter| *filter = level.filter());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotating_file_keeps_limited_history() {
+        let dir = std::env::temp_dir().join(format!("gaming_optimizer_log_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut file = RotatingFile::open(&dir, 10).unwrap();
+        for line in 0..8 {
+            file.write_all(format!("line {}\n", line).as_bytes()).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(dir.join(LOG_FILE)).unwrap(), "line 7\n");
+        assert_eq!(fs::read_to_string(dir.join("gaming_optimizer.1.log")).unwrap(), "line 6\n");
+        assert_eq!(fs::read_to_string(dir.join("gaming_optimizer.4.log")).unwrap(), "line 3\n");
+        assert!(!dir.join("gaming_optimizer.5.log").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_log_level_serialization() {
+        assert_eq!(serde_json::to_string(&LogLevel::Debug).unwrap(), "\"debug\"");
+        assert_eq!(serde_json::from_str::<LogLevel>("\"warn\"").unwrap(), LogLevel::Warn);
+    }
+}
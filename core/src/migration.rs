@@ -0,0 +1,307 @@
+//! Data migration from earlier versions
+//!
+//! config.json records the layout version of the data folder (`data_version`). On the first
+//! start after an update from a version without it, profiles get the fields added since then
+//! written out with their defaults, crosshair images referenced by absolute paths outside the
+//! crosshair library are moved into it, and config.json loses settings this version doesn't
+//! know while keeping every one it can still read (instead of `load_config` falling back to
+//! defaults for the whole file). Changed files are kept as `<file>.pre-migration` first, and
+//! what was done is written to the log.
+
+use crate::config::{get_data_directory, has_app_data, AppConfig};
+use crate::crosshair_library::import_to_library;
+use crate::profile::{load_profiles_recovering, profile_file_to_load, save_profiles, Profile, ProfileFormat};
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Layout version written by this version of the app
+pub const CURRENT_DATA_VERSION: u32 = 1;
+
+/// Suffix of the copies of files as they were before migrating
+const ORIGINAL_SUFFIX: &str = ".pre-migration";
+
+/// What a migration changed
+#[derive(Debug, Default)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub changes: Vec<String>,
+    /// Copies of the files as they were before
+    pub originals: Vec<PathBuf>,
+}
+
+impl MigrationReport {
+    fn log(&self) {
+        info!("[Migration] Data folder migrated from version {} to {}", self.from_version, CURRENT_DATA_VERSION);
+        if self.changes.is_empty() {
+            info!("[Migration] Nothing needed changing");
+        }
+        for change in &self.changes {
+            info!("[Migration] {}", change);
+        }
+        for original in &self.originals {
+            info!("[Migration] Original kept as {}", original.display());
+        }
+    }
+}
+
+/// Migrate the data folder if an earlier version wrote it and log what was done; a failure is
+/// logged and leaves the version alone, so it's tried again on the next start
+pub fn run() {
+    let Ok(data_dir) = get_data_directory() else {
+        return;
+    };
+    match migrate(&data_dir) {
+        Ok(Some(report)) => report.log(),
+        Ok(None) => {}
+        Err(e) => warn!("[Migration] Failed, will try again on the next start: {}", e),
+    }
+}
+
+/// Bring `data_dir` up to `CURRENT_DATA_VERSION`; None when it's already there (or empty)
+pub fn migrate(data_dir: &Path) -> Result<Option<MigrationReport>> {
+    let config_path = data_dir.join("config.json");
+    let mut report = MigrationReport::default();
+    let stored_config = match fs::read_to_string(&config_path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            report.changes.push(format!("config.json couldn't be read ({}); settings reset to their defaults", e));
+            Value::Null
+        }),
+        Err(_) => Value::Null,
+    };
+    report.from_version = stored_config.get("data_version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if report.from_version >= CURRENT_DATA_VERSION || !has_app_data(data_dir) {
+        return Ok(None);
+    }
+
+    migrate_profiles(data_dir, &mut report)?;
+
+    let changes_before = report.changes.len();
+    let mut config = migrate_config(&stored_config, &mut report.changes);
+    if config_path.exists() && report.changes.len() > changes_before {
+        report.originals.push(keep_original(&config_path)?);
+    }
+    config.data_version = CURRENT_DATA_VERSION;
+    let json = serde_json::to_string_pretty(&config).map_err(|e| anyhow!("Failed to serialize config: {}", e))?;
+    fs::write(&config_path, json).map_err(|e| anyhow!("Failed to write config.json: {}", e))?;
+    Ok(Some(report))
+}
+
+/// Copy `path` to `<path>.pre-migration`, unless an earlier attempt already did
+fn keep_original(path: &Path) -> Result<PathBuf> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(ORIGINAL_SUFFIX);
+    let original = path.with_file_name(name);
+    if !original.exists() {
+        fs::copy(path, &original).map_err(|e| anyhow!("Failed to copy {}: {}", path.display(), e))?;
+    }
+    Ok(original)
+}
+
+/// Write the profile file out in the current layout; entries that aren't valid profiles are
+/// moved to profiles.invalid.json as on every load
+fn migrate_profiles(data_dir: &Path, report: &mut MigrationReport) -> Result<()> {
+    let Some(path) = profile_file_to_load(data_dir) else {
+        return Ok(());
+    };
+    let name = path.file_name().map_or_else(String::new, |name| name.to_string_lossy().to_string());
+    let format = ProfileFormat::from_path(&path).ok_or_else(|| anyhow!("Unknown profile format: {}", name))?;
+    let contents = fs::read_to_string(&path).map_err(|e| anyhow!("Failed to read {}: {}", name, e))?;
+    let entries = raw_entries(format, &contents).map_err(|e| anyhow!("Failed to parse {}: {}", name, e))?;
+
+    let loaded = load_profiles_recovering(data_dir)?;
+    let mut changed = !loaded.quarantined.is_empty();
+    for invalid in &loaded.quarantined {
+        report.changes.push(format!("Moved to profiles.invalid.json: {}", invalid.error));
+    }
+
+    // Valid entries come back in file order, so they line up with the entries that parse
+    let valid_entries = entries.iter().filter(|entry| Profile::deserialize(*entry).is_ok());
+    let library = data_dir.join("crosshairs");
+    let mut profiles = loaded.profiles;
+    for (profile, entry) in profiles.iter_mut().zip(valid_entries) {
+        let mut added = Vec::new();
+        let current = serde_json::to_value(&*profile).map_err(|e| anyhow!("Failed to serialize profile: {}", e))?;
+        added_keys(entry, &current, "", &mut added);
+        if !added.is_empty() {
+            report.changes.push(format!("Profile {}: added {} with defaults", profile.name, added.join(", ")));
+            changed = true;
+        }
+
+        let profile_name = profile.name.clone();
+        for image_path in profile.image_paths_mut() {
+            let Some(old) = image_path.clone() else {
+                continue;
+            };
+            match library_path(&old, &library) {
+                Ok(Some(new)) => {
+                    let new = new.to_string_lossy().to_string();
+                    report.changes.push(format!("Profile {}: crosshair {} is now {}", profile_name, old, new));
+                    *image_path = Some(new);
+                    changed = true;
+                }
+                Ok(None) => {}
+                Err(e) => report.changes.push(format!("Profile {}: kept crosshair {} ({})", profile_name, old, e)),
+            }
+        }
+    }
+
+    if changed {
+        report.originals.push(keep_original(&path)?);
+        save_profiles(&profiles, data_dir)?;
+    }
+    Ok(())
+}
+
+/// Profile entries of a profile file as plain values, valid profiles or not
+fn raw_entries(format: ProfileFormat, contents: &str) -> Result<Vec<Value>> {
+    #[derive(Deserialize)]
+    struct TomlEntries {
+        #[serde(default)]
+        profiles: Vec<Value>,
+    }
+    match format {
+        ProfileFormat::Json => serde_json::from_str(contents).map_err(|e| anyhow!("{}", e)),
+        ProfileFormat::Toml => toml::from_str::<TomlEntries>(contents)
+            .map(|file| file.profiles)
+            .map_err(|e| anyhow!("{}", e)),
+        ProfileFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| anyhow!("{}", e)),
+    }
+}
+
+/// Add the keys of `new` that `old` doesn't have to `added`, nested ones as `outer.inner`
+fn added_keys(old: &Value, new: &Value, prefix: &str, added: &mut Vec<String>) {
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return;
+    };
+    for (key, value) in new {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match old.get(key) {
+            Some(old_value) => added_keys(old_value, value, &path, added),
+            None => added.push(path),
+        }
+    }
+}
+
+/// Where a crosshair image outside `library` goes: imported into it while the file is still
+/// there, or the library's image of the same name when it's gone (the data folder was moved or
+/// restored). None for images already in the library.
+fn library_path(path: &str, library: &Path) -> Result<Option<PathBuf>> {
+    let path = Path::new(path);
+    if path.parent() == Some(library) {
+        return Ok(None);
+    }
+    if path.is_file() {
+        return import_to_library(path, library).map(Some);
+    }
+    path.file_name()
+        .map(|name| library.join(name))
+        .filter(|candidate| candidate.is_file())
+        .map(Some)
+        .ok_or_else(|| anyhow!("the file wasn't found"))
+}
+
+/// The settings of a config.json value: unknown keys are dropped and keys whose values can't be
+/// read go back to their defaults, one at a time, so the rest of the file is kept
+fn migrate_config(stored: &Value, changes: &mut Vec<String>) -> AppConfig {
+    let Some(stored) = stored.as_object() else {
+        return AppConfig::default();
+    };
+    let Ok(Value::Object(mut settings)) = serde_json::to_value(AppConfig::default()) else {
+        return AppConfig::default();
+    };
+
+    let missing: Vec<String> = settings
+        .keys()
+        .filter(|key| !stored.contains_key(*key) && *key != "data_version")
+        .cloned()
+        .collect();
+    for (key, value) in stored {
+        let Some(default) = settings.insert(key.clone(), value.clone()) else {
+            settings.remove(key);
+            changes.push(format!("Dropped unknown setting `{}`", key));
+            continue;
+        };
+        if let Err(e) = AppConfig::deserialize(&Value::Object(settings.clone())) {
+            settings.insert(key.clone(), default);
+            changes.push(format!("Reset setting `{}` to its default ({})", key, e));
+        }
+    }
+    if !missing.is_empty() {
+        changes.push(format!("Added settings with defaults: {}", missing.join(", ")));
+    }
+    AppConfig::deserialize(&Value::Object(settings)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::{create_profile, load_profiles};
+
+    #[test]
+    fn test_added_keys() {
+        let old = serde_json::json!({ "name": "FPS", "network": { "limit": 1 } });
+        let new = serde_json::json!({ "name": "FPS", "fan_speed_max": null, "network": { "limit": 1, "pause": false } });
+        let mut added = Vec::new();
+        added_keys(&old, &new, "", &mut added);
+        assert_eq!(added, ["fan_speed_max", "network.pause"]);
+    }
+
+    #[test]
+    fn test_migrate_config() {
+        let stored = serde_json::json!({
+            "active_profile": "FPS",
+            "overlay_visible": "yes",
+            "paused": true,
+            "tray_icon_style": "dark",
+        });
+        let mut changes = Vec::new();
+        let config = migrate_config(&stored, &mut changes);
+        assert_eq!(config.active_profile.as_deref(), Some("FPS"));
+        assert!(config.paused);
+        assert!(!config.overlay_visible);
+        assert!(changes.iter().any(|change| change.starts_with("Reset setting `overlay_visible`")), "{:?}", changes);
+        assert!(changes.contains(&"Dropped unknown setting `tray_icon_style`".to_string()));
+        assert!(changes.iter().any(|change| change.starts_with("Added settings with defaults: ")));
+    }
+
+    #[test]
+    fn test_migrate() {
+        let data_dir = std::env::temp_dir().join(format!("gaming_optimizer_migration_{}", std::process::id()));
+        let library = data_dir.join("crosshairs");
+        fs::create_dir_all(&library).unwrap();
+
+        // An image outside the library, and one that's only left in the library
+        let outside = data_dir.join("dot.png");
+        image::RgbaImage::new(100, 100).save(&outside).unwrap();
+        image::RgbaImage::new(100, 100).save(library.join("cross.png")).unwrap();
+
+        let mut fps = create_profile("FPS".to_string());
+        fps.crosshair_image_path = Some(outside.to_string_lossy().to_string());
+        let mut moba = create_profile("MOBA".to_string());
+        moba.crosshair_image_path = Some("C:/Users/You/AppData/Roaming/GamingOptimizer/crosshairs/cross.png".to_string());
+        let mut entries = vec![serde_json::to_value(&fps).unwrap(), serde_json::to_value(&moba).unwrap()];
+        entries[0].as_object_mut().unwrap().remove("fan_speed_max");
+        fs::write(data_dir.join("profiles.json"), serde_json::to_string(&entries).unwrap()).unwrap();
+        fs::write(data_dir.join("config.json"), "{\"active_profile\": null, \"overlay_visible\": false}").unwrap();
+
+        let report = migrate(&data_dir).unwrap().unwrap();
+        assert_eq!(report.from_version, 0);
+        assert!(report.changes.iter().any(|change| change == "Profile FPS: added fan_speed_max with defaults"));
+        let profiles = load_profiles(&data_dir).unwrap();
+        assert_eq!(profiles[0].crosshair_image_path.as_deref(), Some(library.join("dot.png").to_string_lossy().as_ref()));
+        assert_eq!(profiles[1].crosshair_image_path.as_deref(), Some(library.join("cross.png").to_string_lossy().as_ref()));
+        assert!(data_dir.join("profiles.json.pre-migration").exists());
+        assert!(data_dir.join("config.json.pre-migration").exists());
+
+        // Migrated once
+        let config: AppConfig = serde_json::from_str(&fs::read_to_string(data_dir.join("config.json")).unwrap()).unwrap();
+        assert_eq!(config.data_version, CURRENT_DATA_VERSION);
+        assert!(migrate(&data_dir).unwrap().is_none());
+
+        fs::remove_dir_all(&data_dir).unwrap();
+    }
+}
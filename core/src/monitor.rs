@@ -0,0 +1,479 @@
+//! Hardware monitoring: CPU/GPU load, clocks, temperatures and fan speeds
+//!
+//! Full readings come from the LibreHardwareMonitor (or OpenHardwareMonitor) WMI provider,
+//! which is only there while that app runs. Without it, CPU load and clock still come from
+//! sysinfo and the CPU temperature from the ACPI thermal zone (administrator only); whatever
+//! can't be read is simply left empty. Sampling happens on a background thread and the GUI
+//! polls the latest snapshot.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+#[cfg(windows)]
+use crate::wmi;
+
+/// WMI namespaces of the sensor providers, in order of preference
+const PROVIDERS: [(&str, &str); 2] = [
+    ("root\\LibreHardwareMonitor", "LibreHardwareMonitor"),
+    ("root\\OpenHardwareMonitor", "OpenHardwareMonitor"),
+];
+
+/// How long a missing provider is left alone before looking for it again
+const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Busiest processes kept per snapshot for the dashboard
+const TOP_PROCESSES: usize = 5;
+
+pub const MIN_INTERVAL_MS: u64 = 500;
+pub const MAX_INTERVAL_MS: u64 = 60_000;
+
+/// Monitoring settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MonitorConfig {
+    /// Time between samples in milliseconds
+    pub interval_ms: u64,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        MonitorConfig { interval_ms: 2000 }
+    }
+}
+
+impl MonitorConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms.clamp(MIN_INTERVAL_MS, MAX_INTERVAL_MS))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FanReading {
+    pub name: String,
+    pub rpm: f32,
+}
+
+/// A running process and what it uses
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProcessLoad {
+    pub name: String,
+    /// Percent of the whole CPU (not of one core)
+    pub cpu_percent: f32,
+    pub memory_kb: u64,
+}
+
+/// One set of readings. `None` = not available on this machine (or not right now).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HardwareSnapshot {
+    /// Percent
+    pub cpu_load: Option<f32>,
+    pub cpu_clock_mhz: Option<f32>,
+    /// Degrees Celsius
+    pub cpu_temp: Option<f32>,
+    pub gpu_load: Option<f32>,
+    pub gpu_clock_mhz: Option<f32>,
+    pub gpu_temp: Option<f32>,
+    pub fans: Vec<FanReading>,
+    pub memory_used_kb: Option<u64>,
+    pub memory_total_kb: Option<u64>,
+    /// Busiest processes by CPU, then memory
+    pub top_processes: Vec<ProcessLoad>,
+    /// Sensor provider the GPU/fan readings came from (None = basic readings only)
+    pub provider: Option<&'static str>,
+}
+
+impl HardwareSnapshot {
+    /// Percent of physical memory in use
+    pub fn memory_percent(&self) -> Option<f32> {
+        match (self.memory_used_kb, self.memory_total_kb) {
+            (Some(used), Some(total)) if total > 0 => Some(used as f32 * 100.0 / total as f32),
+            _ => None,
+        }
+    }
+
+    /// Short lines for the dashboard and the overlay HUD, e.g. "CPU 45% 4200MHz 62C"
+    /// (ASCII only, the HUD font has no degree sign)
+    pub fn summary_lines(&self) -> Vec<String> {
+        fn line(label: &str, load: Option<f32>, clock: Option<f32>, temp: Option<f32>) -> Option<String> {
+            let parts: Vec<String> = [
+                load.map(|v| format!("{:.0}%", v)),
+                clock.map(|v| format!("{:.0}MHz", v)),
+                temp.map(|v| format!("{:.0}C", v)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            (!parts.is_empty()).then(|| format!("{} {}", label, parts.join(" ")))
+        }
+
+        let mut lines = Vec::new();
+        lines.extend(line("CPU", self.cpu_load, self.cpu_clock_mhz, self.cpu_temp));
+        lines.extend(line("GPU", self.gpu_load, self.gpu_clock_mhz, self.gpu_temp));
+        if !self.fans.is_empty() {
+            let fans: Vec<String> = self.fans.iter().map(|fan| format!("{:.0}", fan.rpm)).collect();
+            lines.push(format!("Fan {} RPM", fans.join("/")));
+        }
+        lines
+    }
+}
+
+/// A sensor row from the provider's `Sensor` WMI class
+#[derive(Clone, Debug, PartialEq)]
+struct Sensor {
+    /// "Load", "Clock", "Temperature", "Fan", ...
+    sensor_type: String,
+    name: String,
+    /// e.g. "/intelcpu/0/temperature/1" or "/gpu-nvidia/0/load/0"
+    identifier: String,
+    value: f32,
+}
+
+impl Sensor {
+    /// Hardware part of the identifier ("/gpu-nvidia/0")
+    fn hardware(&self) -> &str {
+        let mut end = self.identifier.len();
+        for _ in 0..2 {
+            end = self.identifier[..end].rfind('/').unwrap_or(0);
+        }
+        &self.identifier[..end]
+    }
+
+    fn is_cpu(&self) -> bool {
+        self.hardware().contains("cpu/")
+    }
+
+    fn is_gpu(&self) -> bool {
+        self.identifier.starts_with("/gpu")
+    }
+}
+
+/// Fill a snapshot from provider sensors
+fn apply_sensors(snapshot: &mut HardwareSnapshot, sensors: &[Sensor]) {
+    let find = |filter: &dyn Fn(&Sensor) -> bool, sensor_type: &str, names: &[&str]| {
+        names.iter().find_map(|name| {
+            sensors
+                .iter()
+                .find(|s| filter(s) && s.sensor_type == sensor_type && s.name == *name)
+                .map(|s| s.value)
+        })
+    };
+
+    // CPU: package temperature when there is one, else the hottest CPU sensor
+    let is_cpu = |s: &Sensor| s.is_cpu();
+    if let Some(load) = find(&is_cpu, "Load", &["CPU Total"]) {
+        snapshot.cpu_load = Some(load);
+    }
+    let cpu_temp = find(&is_cpu, "Temperature", &["CPU Package", "Core (Tctl/Tdie)", "Core Average"]).or_else(|| {
+        sensors
+            .iter()
+            .filter(|s| s.is_cpu() && s.sensor_type == "Temperature")
+            .map(|s| s.value)
+            .reduce(f32::max)
+    });
+    if cpu_temp.is_some() {
+        snapshot.cpu_temp = cpu_temp;
+    }
+    let core_clocks: Vec<f32> = sensors
+        .iter()
+        .filter(|s| s.is_cpu() && s.sensor_type == "Clock" && s.name.starts_with("Core"))
+        .map(|s| s.value)
+        .collect();
+    if !core_clocks.is_empty() {
+        snapshot.cpu_clock_mhz = Some(core_clocks.iter().sum::<f32>() / core_clocks.len() as f32);
+    }
+
+    // GPU: prefer a dedicated card over integrated graphics
+    let gpu = sensors
+        .iter()
+        .filter(|s| s.is_gpu())
+        .map(|s| s.hardware())
+        .min_by_key(|hardware| hardware.starts_with("/gpu-intel"));
+    if let Some(gpu) = gpu {
+        let on_gpu = |s: &Sensor| s.hardware() == gpu;
+        snapshot.gpu_load = find(&on_gpu, "Load", &["GPU Core"]);
+        snapshot.gpu_clock_mhz = find(&on_gpu, "Clock", &["GPU Core"]);
+        snapshot.gpu_temp = find(&on_gpu, "Temperature", &["GPU Core", "GPU Hot Spot"]);
+    }
+
+    // Fans that are spinning (boards report unconnected headers as 0 RPM)
+    snapshot.fans = sensors
+        .iter()
+        .filter(|s| s.sensor_type == "Fan" && s.value > 0.0)
+        .map(|s| FanReading { name: s.name.clone(), rpm: s.value })
+        .collect();
+}
+
+/// The `count` busiest processes, by CPU and then memory (the idle process is left out)
+fn top_processes(processes: impl Iterator<Item = ProcessLoad>, count: usize) -> Vec<ProcessLoad> {
+    let mut processes: Vec<ProcessLoad> = processes.filter(|process| process.name != "System Idle Process").collect();
+    processes.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent).then(b.memory_kb.cmp(&a.memory_kb)));
+    processes.truncate(count);
+    processes
+}
+
+/// ACPI thermal zone temperature is reported in tenths of a kelvin
+fn decikelvin_to_celsius(value: f64) -> f32 {
+    (value / 10.0 - 273.15) as f32
+}
+
+/// State of the sampler thread
+struct Sampler {
+    system: sysinfo::System,
+    #[cfg(windows)]
+    provider: Option<(wmi::Connection, &'static str)>,
+    #[cfg(windows)]
+    acpi: Option<wmi::Connection>,
+    /// When to look for missing sensor sources again
+    next_probe: Instant,
+}
+
+impl Sampler {
+    fn new() -> Self {
+        Sampler {
+            system: sysinfo::System::new(),
+            #[cfg(windows)]
+            provider: None,
+            #[cfg(windows)]
+            acpi: None,
+            next_probe: Instant::now(),
+        }
+    }
+
+    fn sample(&mut self) -> HardwareSnapshot {
+        self.system.refresh_cpu();
+        self.system.refresh_memory();
+        // Process CPU usage is measured between refreshes, so the first sample reads 0%
+        self.system.refresh_processes();
+        let cpus = self.system.cpus();
+        let core_count = cpus.len().max(1) as f32;
+        let processes = self.system.processes().values().map(|process| ProcessLoad {
+            name: process.name().to_string(),
+            cpu_percent: process.cpu_usage() / core_count,
+            memory_kb: process.memory() / 1024,
+        });
+        let mut snapshot = HardwareSnapshot {
+            cpu_load: Some(self.system.global_cpu_info().cpu_usage()),
+            cpu_clock_mhz: cpus.iter().map(|cpu| cpu.frequency()).max().filter(|&mhz| mhz > 0).map(|mhz| mhz as f32),
+            memory_used_kb: Some(self.system.used_memory() / 1024),
+            memory_total_kb: Some(self.system.total_memory() / 1024),
+            top_processes: top_processes(processes, TOP_PROCESSES),
+            ..HardwareSnapshot::default()
+        };
+
+        #[cfg(windows)]
+        self.sample_wmi(&mut snapshot);
+
+        snapshot
+    }
+
+    #[cfg(windows)]
+    fn sample_wmi(&mut self, snapshot: &mut HardwareSnapshot) {
+        if (self.provider.is_none() || self.acpi.is_none()) && Instant::now() >= self.next_probe {
+            self.next_probe = Instant::now() + PROBE_INTERVAL;
+            if self.provider.is_none() {
+                self.provider = PROVIDERS
+                    .iter()
+                    .find_map(|&(namespace, name)| wmi::Connection::open(namespace).ok().map(|c| (c, name)));
+                if let Some((_, name)) = self.provider {
+                    info!("[Monitor] Using {} sensors", name);
+                }
+            }
+            if self.acpi.is_none() {
+                self.acpi = wmi::Connection::open("root\\WMI").ok();
+            }
+        }
+
+        if let Some((ref connection, name)) = self.provider {
+            match read_sensors(connection) {
+                // The namespace stays registered after the app exits, but has no sensors
+                Ok(sensors) if !sensors.is_empty() => {
+                    apply_sensors(snapshot, &sensors);
+                    snapshot.provider = Some(name);
+                    return;
+                }
+                Ok(_) => self.provider = None,
+                Err(e) => {
+                    info!("[Monitor] {} sensors unavailable: {}", name, e);
+                    self.provider = None;
+                }
+            }
+        }
+
+        if let Some(ref connection) = self.acpi {
+            let mut hottest: Option<f32> = None;
+            let result = connection.query("SELECT CurrentTemperature FROM MSAcpi_ThermalZoneTemperature", |row| {
+                if let Some(value) = wmi::get_number(row, "CurrentTemperature") {
+                    let celsius = decikelvin_to_celsius(value);
+                    hottest = Some(hottest.map_or(celsius, |t| t.max(celsius)));
+                }
+            });
+            match result {
+                Ok(()) => snapshot.cpu_temp = hottest,
+                // Usually "access denied" without administrator rights
+                Err(_) => self.acpi = None,
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+fn read_sensors(connection: &wmi::Connection) -> Result<Vec<Sensor>> {
+    let mut sensors = Vec::new();
+    connection.query("SELECT SensorType, Name, Identifier, Value FROM Sensor", |row| {
+        let (Some(sensor_type), Some(name), Some(identifier), Some(value)) = (
+            wmi::get_string(row, "SensorType"),
+            wmi::get_string(row, "Name"),
+            wmi::get_string(row, "Identifier"),
+            wmi::get_number(row, "Value"),
+        ) else {
+            return;
+        };
+        sensors.push(Sensor { sensor_type, name, identifier, value: value as f32 });
+    })?;
+    Ok(sensors)
+}
+
+struct Shared {
+    latest: Mutex<Option<HardwareSnapshot>>,
+    interval_ms: AtomicU64,
+    running: AtomicBool,
+}
+
+/// Samples hardware sensors on a background thread until dropped
+pub struct HardwareMonitor {
+    shared: Arc<Shared>,
+}
+
+impl HardwareMonitor {
+    pub fn spawn(config: &MonitorConfig) -> Self {
+        let shared = Arc::new(Shared {
+            latest: Mutex::new(None),
+            interval_ms: AtomicU64::new(config.interval().as_millis() as u64),
+            running: AtomicBool::new(true),
+        });
+
+        let thread_shared = shared.clone();
+        std::thread::spawn(move || {
+            #[cfg(windows)]
+            let _com = unsafe {
+                windows::Win32::System::Com::CoInitializeEx(None, windows::Win32::System::Com::COINIT_MULTITHREADED)
+            };
+
+            let mut sampler = Sampler::new();
+            while thread_shared.running.load(Ordering::Relaxed) {
+                let snapshot = sampler.sample();
+                if let Ok(mut latest) = thread_shared.latest.lock() {
+                    *latest = Some(snapshot);
+                }
+                std::thread::sleep(Duration::from_millis(thread_shared.interval_ms.load(Ordering::Relaxed)));
+            }
+        });
+
+        HardwareMonitor { shared }
+    }
+
+    /// Most recent readings (None until the first sample is taken)
+    pub fn latest(&self) -> Option<HardwareSnapshot> {
+        self.shared.latest.lock().ok().and_then(|latest| latest.clone())
+    }
+
+    /// Change the sampling interval (takes effect after the current wait)
+    pub fn set_config(&self, config: &MonitorConfig) {
+        self.shared.interval_ms.store(config.interval().as_millis() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Drop for HardwareMonitor {
+    fn drop(&mut self) {
+        self.shared.running.store(false, Ordering::Relaxed);
+    }
+}
+
+/// File the GUI writes the HUD text to for the crosshair overlay to show
+pub fn hud_file() -> Option<PathBuf> {
+    crate::config::get_data_directory().ok().map(|dir| dir.join("hud.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sensor(sensor_type: &str, name: &str, identifier: &str, value: f32) -> Sensor {
+        Sensor {
+            sensor_type: sensor_type.to_string(),
+            name: name.to_string(),
+            identifier: identifier.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_apply_sensors() {
+        let sensors = [
+            sensor("Load", "CPU Total", "/intelcpu/0/load/0", 37.5),
+            sensor("Clock", "Core #1", "/intelcpu/0/clock/1", 4000.0),
+            sensor("Clock", "Core #2", "/intelcpu/0/clock/2", 4400.0),
+            sensor("Clock", "Bus Speed", "/intelcpu/0/clock/0", 100.0),
+            sensor("Temperature", "Core #1", "/intelcpu/0/temperature/0", 58.0),
+            sensor("Temperature", "CPU Package", "/intelcpu/0/temperature/4", 61.0),
+            sensor("Load", "GPU Core", "/gpu-intel-integrated/0/load/0", 3.0),
+            sensor("Load", "GPU Core", "/gpu-nvidia/0/load/0", 96.0),
+            sensor("Temperature", "GPU Core", "/gpu-nvidia/0/temperature/0", 71.0),
+            sensor("Clock", "GPU Core", "/gpu-nvidia/0/clock/0", 1905.0),
+            sensor("Fan", "Fan #1", "/lpc/nct6798d/0/fan/0", 1250.0),
+            sensor("Fan", "Fan #2", "/lpc/nct6798d/0/fan/1", 0.0),
+            sensor("Fan", "GPU Fan", "/gpu-nvidia/0/fan/0", 1600.0),
+        ];
+
+        let mut snapshot = HardwareSnapshot { cpu_load: Some(10.0), ..HardwareSnapshot::default() };
+        apply_sensors(&mut snapshot, &sensors);
+
+        assert_eq!(snapshot.cpu_load, Some(37.5));
+        assert_eq!(snapshot.cpu_clock_mhz, Some(4200.0));
+        assert_eq!(snapshot.cpu_temp, Some(61.0));
+        assert_eq!(snapshot.gpu_load, Some(96.0));
+        assert_eq!(snapshot.gpu_clock_mhz, Some(1905.0));
+        assert_eq!(snapshot.gpu_temp, Some(71.0));
+        let fans: Vec<&str> = snapshot.fans.iter().map(|fan| fan.name.as_str()).collect();
+        assert_eq!(fans, ["Fan #1", "GPU Fan"]);
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        let snapshot = HardwareSnapshot {
+            cpu_load: Some(45.2),
+            cpu_clock_mhz: Some(4200.0),
+            cpu_temp: Some(62.4),
+            fans: vec![FanReading { name: "Fan #1".to_string(), rpm: 1250.0 }],
+            ..HardwareSnapshot::default()
+        };
+        assert_eq!(snapshot.summary_lines(), ["CPU 45% 4200MHz 62C", "Fan 1250 RPM"]);
+        assert!(HardwareSnapshot::default().summary_lines().is_empty());
+    }
+
+    #[test]
+    fn test_top_processes() {
+        let load = |name: &str, cpu_percent: f32, memory_kb: u64| ProcessLoad { name: name.to_string(), cpu_percent, memory_kb };
+        let processes = vec![
+            load("System Idle Process", 90.0, 8),
+            load("chrome.exe", 2.0, 900_000),
+            load("game.exe", 35.0, 4_000_000),
+            load("explorer.exe", 2.0, 150_000),
+            load("svchost.exe", 0.0, 20_000),
+        ];
+        let names: Vec<String> = top_processes(processes.into_iter(), 3).into_iter().map(|process| process.name).collect();
+        assert_eq!(names, ["game.exe", "chrome.exe", "explorer.exe"]);
+
+        let snapshot = HardwareSnapshot { memory_used_kb: Some(4_000_000), memory_total_kb: Some(16_000_000), ..HardwareSnapshot::default() };
+        assert_eq!(snapshot.memory_percent(), Some(25.0));
+        assert_eq!(HardwareSnapshot::default().memory_percent(), None);
+    }
+
+    #[test]
+    fn test_decikelvin_to_celsius() {
+        assert!((decikelvin_to_celsius(3331.5) - 60.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,168 @@
+//! Network QoS while a profile is active
+//!
+//! Pauses the Delivery Optimization service (Windows Update / Store downloads) and throttles
+//! chosen processes with NetQos policies so launchers and sync clients don't saturate the
+//! uplink mid-match. Policies go into the ActiveStore, so they never outlive a reboot even
+//! if restore doesn't run. Both need administrator rights.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use crate::services::ServiceBackend;
+use crate::shell::{ps_quote, run_powershell};
+use tracing::info;
+
+/// Delivery Optimization service
+const DELIVERY_OPTIMIZATION_SERVICE: &str = "DoSvc";
+
+/// Prefix of the QoS policies we create (used to find and remove them)
+const POLICY_PREFIX: &str = "GamingOptimizer";
+
+/// Bandwidth cap for one process
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct BandwidthLimit {
+    /// Executable name, e.g. "steam.exe"
+    pub process: String,
+    /// Cap in kilobits per second
+    pub limit_kbps: u32,
+}
+
+/// Network rules applied while a profile is active
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetworkSettings {
+    /// Stop the Delivery Optimization service (background Windows Update downloads)
+    #[serde(default)]
+    pub pause_delivery_optimization: bool,
+    #[serde(default)]
+    pub bandwidth_limits: Vec<BandwidthLimit>,
+}
+
+impl NetworkSettings {
+    /// Whether any rule is configured
+    pub fn is_empty(&self) -> bool {
+        !self.pause_delivery_optimization && self.bandwidth_limits.is_empty()
+    }
+}
+
+/// Rules applied by `NetworkSwitcher::apply`
+#[derive(Debug, Default)]
+pub struct NetworkReport {
+    /// e.g. "Delivery Optimization paused", "steam.exe ≤ 2000 Kbps"
+    pub applied: Vec<String>,
+    /// Rules that couldn't be applied, with the reason
+    pub failed: Vec<String>,
+}
+
+/// Name of the QoS policy throttling a process
+fn policy_name(process: &str) -> String {
+    format!("{} {}", POLICY_PREFIX, process.trim())
+}
+
+fn add_policy(limit: &BandwidthLimit) -> Result<()> {
+    run_powershell(&format!(
+        "New-NetQosPolicy -Name {} -AppPathNameMatchCondition {} -ThrottleRateActionBitsPerSecond {} -PolicyStore ActiveStore -ErrorAction Stop | Out-Null",
+        ps_quote(&policy_name(&limit.process)),
+        ps_quote(limit.process.trim()),
+        limit.limit_kbps as u64 * 1000
+    ))
+    .map(|_| ())
+}
+
+fn remove_policy(name: &str) -> Result<()> {
+    run_powershell(&format!(
+        "Remove-NetQosPolicy -Name {} -PolicyStore ActiveStore -Confirm:$false -ErrorAction Stop",
+        ps_quote(name)
+    ))
+    .map(|_| ())
+}
+
+/// Applies a profile's network rules and undoes them afterwards
+#[derive(Default)]
+pub struct NetworkSwitcher {
+    /// Whether we stopped Delivery Optimization (and should start it again)
+    paused_delivery_optimization: bool,
+    /// Names of the QoS policies we created
+    policies: Vec<String>,
+}
+
+impl NetworkSwitcher {
+    /// Undo any previous profile's rules, then apply the new ones
+    pub fn apply(&mut self, settings: &NetworkSettings, services: &mut dyn ServiceBackend) -> Result<NetworkReport> {
+        self.restore(services)?;
+        let mut report = NetworkReport::default();
+        if settings.is_empty() {
+            return Ok(report);
+        }
+
+        if settings.pause_delivery_optimization {
+            match self.pause_delivery_optimization(services) {
+                Ok(()) => report.applied.push("Delivery Optimization paused".to_string()),
+                Err(e) => report.failed.push(format!("Delivery Optimization: {}", e)),
+            }
+        }
+
+        for limit in &settings.bandwidth_limits {
+            let name = policy_name(&limit.process);
+            // A leftover policy from a crash would make New-NetQosPolicy fail
+            let _ = remove_policy(&name);
+            match add_policy(limit) {
+                Ok(()) => {
+                    report.applied.push(format!("{} ≤ {} Kbps", limit.process, limit.limit_kbps));
+                    self.policies.push(name);
+                }
+                Err(e) => report.failed.push(format!("{}: {}", limit.process, e)),
+            }
+        }
+
+        if !report.applied.is_empty() {
+            info!("[Network] Applied {}", report.applied.join(", "));
+        }
+        Ok(report)
+    }
+
+    fn pause_delivery_optimization(&mut self, services: &mut dyn ServiceBackend) -> Result<()> {
+        if !services.is_running(DELIVERY_OPTIMIZATION_SERVICE)? {
+            // Already idle - nothing to pause or restart later
+            return Ok(());
+        }
+        services
+            .stop(DELIVERY_OPTIMIZATION_SERVICE)
+            .map_err(|e| anyhow!("Failed to stop {} (run as administrator?): {}", DELIVERY_OPTIMIZATION_SERVICE, e))?;
+        self.paused_delivery_optimization = true;
+        Ok(())
+    }
+
+    /// Remove our policies and restart Delivery Optimization (no-op if nothing was applied)
+    pub fn restore(&mut self, services: &mut dyn ServiceBackend) -> Result<()> {
+        let mut first_error = None;
+
+        for name in std::mem::take(&mut self.policies) {
+            if let Err(e) = remove_policy(&name) {
+                first_error.get_or_insert(e);
+            }
+        }
+
+        if std::mem::take(&mut self.paused_delivery_optimization) {
+            if let Err(e) = services.start(DELIVERY_OPTIMIZATION_SERVICE) {
+                first_error.get_or_insert(anyhow!("Failed to restart {}: {}", DELIVERY_OPTIMIZATION_SERVICE, e));
+            } else {
+                info!("[Network] Delivery Optimization resumed");
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_name() {
+        assert_eq!(policy_name(" steam.exe "), "GamingOptimizer steam.exe");
+        assert!(NetworkSettings::default().is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+//! Do Not Disturb while gaming
+//!
+//! Turns off toast notifications (the same switch as Settings > Notifications) so popups don't
+//! appear over the game, optionally muting notification sounds too. The user's previous
+//! settings are restored on deactivation.
+
+use anyhow::Result;
+use crate::registry::{Hive, RegistryChanges};
+use tracing::info;
+
+const NOTIFICATION_SETTINGS_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Notifications\\Settings";
+const TOASTS_ENABLED: &str = "NOC_GLOBAL_SETTING_TOASTS_ENABLED";
+const SOUNDS_ENABLED: &str = "NOC_GLOBAL_SETTING_ALLOW_NOTIFICATION_SOUND";
+
+/// Applies Do Not Disturb and remembers the settings it replaced
+#[derive(Default)]
+pub struct DoNotDisturb {
+    changes: RegistryChanges,
+}
+
+impl DoNotDisturb {
+    /// Whether notifications are currently suppressed by us
+    pub fn is_active(&self) -> bool {
+        !self.changes.is_empty()
+    }
+
+    /// Suppress toast notifications (and their sounds if `mute_sounds`)
+    pub fn enable(&mut self, mute_sounds: bool) -> Result<()> {
+        self.restore()?;
+        self.changes.set_dword(Hive::CurrentUser, NOTIFICATION_SETTINGS_KEY, TOASTS_ENABLED, 0)?;
+        if mute_sounds {
+            self.changes.set_dword(Hive::CurrentUser, NOTIFICATION_SETTINGS_KEY, SOUNDS_ENABLED, 0)?;
+        }
+        info!("[Notifications] Do Not Disturb on{}", if mute_sounds { " (sounds muted)" } else { "" });
+        Ok(())
+    }
+
+    /// Restore the previous notification settings (no-op if nothing was changed)
+    pub fn restore(&mut self) -> Result<()> {
+        if self.changes.is_empty() {
+            return Ok(());
+        }
+        self.changes.restore()?;
+        info!("[Notifications] Notification settings restored");
+        Ok(())
+    }
+}
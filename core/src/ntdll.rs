@@ -0,0 +1,15 @@
+//! Lookup of undocumented ntdll exports (not in the import libraries)
+
+/// Address of an ntdll export
+///
+/// # Safety
+/// `name` must be NUL-terminated, and the result may only be called through the export's real
+/// signature
+#[cfg(windows)]
+pub unsafe fn proc_address(name: &[u8]) -> Option<unsafe extern "system" fn() -> isize> {
+    use windows::core::{s, PCSTR};
+    use windows::Win32::System::LibraryLoader::{GetModuleHandleA, GetProcAddress};
+
+    let ntdll = GetModuleHandleA(s!("ntdll.dll")).ok()?;
+    GetProcAddress(ntdll, PCSTR(name.as_ptr()))
+}
@@ -0,0 +1,336 @@
+//! OBS Studio scenes and recording through obs-websocket
+//!
+//! Talks obs-websocket 5 (built into OBS 28 and newer, port 4455 by default) to switch the
+//! program scene and start recording or the replay buffer when a profile is activated. The
+//! scene that was live beforehand is switched back to on restore, and only outputs this app
+//! started are stopped again, so a recording the user started by hand keeps running.
+
+use anyhow::{anyhow, Result};
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use tracing::info;
+use tungstenite::{Message as WsMessage, WebSocket};
+
+pub const DEFAULT_PORT: u16 = 4455;
+
+const RPC_VERSION: u32 = 1;
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+const OP_HELLO: u64 = 0;
+const OP_IDENTIFY: u64 = 1;
+const OP_IDENTIFIED: u64 = 2;
+const OP_REQUEST: u64 = 6;
+const OP_REQUEST_RESPONSE: u64 = 7;
+
+/// obs-websocket server address (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct ObsConfig {
+    pub host: String,
+    pub port: u16,
+    /// Server password from OBS' WebSocket Server Settings (empty = authentication off)
+    pub password: String,
+}
+
+impl Default for ObsConfig {
+    fn default() -> Self {
+        ObsConfig {
+            host: "127.0.0.1".to_string(),
+            port: DEFAULT_PORT,
+            password: String::new(),
+        }
+    }
+}
+
+/// What OBS does while a profile is active
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct ObsSettings {
+    /// Program scene to switch to (None = leave the scene alone)
+    pub scene: Option<String>,
+    /// Start recording (stopped again on deactivation)
+    pub record: bool,
+    /// Start the replay buffer (stopped again on deactivation)
+    pub replay_buffer: bool,
+}
+
+impl ObsSettings {
+    pub fn is_empty(&self) -> bool {
+        self.scene.is_none() && !self.record && !self.replay_buffer
+    }
+}
+
+/// Identify `authentication` for a password: base64(sha256(base64(sha256(password + salt)) + challenge))
+fn authentication(password: &str, salt: &str, challenge: &str) -> String {
+    let base64 = base64::engine::general_purpose::STANDARD;
+    let secret = base64.encode(Sha256::digest(format!("{}{}", password, salt)));
+    base64.encode(Sha256::digest(format!("{}{}", secret, challenge)))
+}
+
+/// Identify message answering the server's Hello
+fn identify_message(hello: &Value, password: &str) -> Result<Value> {
+    let mut identify = json!({ "rpcVersion": RPC_VERSION, "eventSubscriptions": 0 });
+    let auth = &hello["authentication"];
+    if auth.is_object() {
+        if password.is_empty() {
+            return Err(anyhow!("OBS asks for a password (set it in the OBS settings)"));
+        }
+        let salt = auth["salt"].as_str().unwrap_or_default();
+        let challenge = auth["challenge"].as_str().unwrap_or_default();
+        identify["authentication"] = json!(authentication(password, salt, challenge));
+    }
+    Ok(json!({ "op": OP_IDENTIFY, "d": identify }))
+}
+
+/// Scene names from a GetSceneList response, top of OBS' list first
+fn parse_scene_list(data: &Value) -> Vec<String> {
+    let mut scenes: Vec<(i64, String)> = data["scenes"]
+        .as_array()
+        .map(|scenes| {
+            scenes
+                .iter()
+                .filter_map(|scene| Some((scene["sceneIndex"].as_i64().unwrap_or(0), scene["sceneName"].as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    // OBS numbers scenes from the bottom of its list
+    scenes.sort_by_key(|(index, _)| std::cmp::Reverse(*index));
+    scenes.into_iter().map(|(_, name)| name).collect()
+}
+
+/// An identified obs-websocket session
+struct Client {
+    socket: WebSocket<TcpStream>,
+    request_id: u64,
+}
+
+impl Client {
+    fn connect(config: &ObsConfig) -> Result<Self> {
+        let address = (config.host.as_str(), config.port)
+            .to_socket_addrs()
+            .map_err(|e| anyhow!("Invalid OBS address {}:{}: {}", config.host, config.port, e))?
+            .next()
+            .ok_or_else(|| anyhow!("Invalid OBS address {}:{}", config.host, config.port))?;
+        let stream = TcpStream::connect_timeout(&address, TIMEOUT)
+            .map_err(|e| anyhow!("OBS not reachable at {} (is the WebSocket server enabled?): {}", address, e))?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(TIMEOUT)))
+            .map_err(|e| anyhow!("Failed to configure OBS connection: {}", e))?;
+        let (socket, _) = tungstenite::client(format!("ws://{}", address), stream)
+            .map_err(|e| anyhow!("OBS WebSocket handshake failed: {}", e))?;
+
+        let mut client = Client { socket, request_id: 0 };
+        let hello = client.receive(OP_HELLO)?;
+        let identify = identify_message(&hello, &config.password)?;
+        client.send(&identify)?;
+        client.receive(OP_IDENTIFIED)?;
+        Ok(client)
+    }
+
+    fn send(&mut self, message: &Value) -> Result<()> {
+        self.socket
+            .send(WsMessage::Text(message.to_string()))
+            .map_err(|e| anyhow!("Failed to write to OBS: {}", e))
+    }
+
+    /// Data of the next message with opcode `op`
+    fn receive(&mut self, op: u64) -> Result<Value> {
+        loop {
+            let message = self.socket.read().map_err(|e| anyhow!("Failed to read from OBS: {}", e))?;
+            match message {
+                WsMessage::Text(text) => {
+                    let mut message: Value = serde_json::from_str(&text).map_err(|e| anyhow!("Invalid message from OBS: {}", e))?;
+                    if message["op"].as_u64() == Some(op) {
+                        return Ok(message["d"].take());
+                    }
+                }
+                // A wrong password closes the connection with the reason
+                WsMessage::Close(frame) => {
+                    let reason = frame.map(|frame| frame.reason.to_string()).unwrap_or_default();
+                    return Err(anyhow!("OBS closed the connection: {}", reason));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Send a request; its response data
+    fn request(&mut self, request_type: &str, data: Value) -> Result<Value> {
+        self.request_id += 1;
+        let id = self.request_id.to_string();
+        let mut request = json!({ "requestType": request_type, "requestId": id });
+        if !data.is_null() {
+            request["requestData"] = data;
+        }
+        self.send(&json!({ "op": OP_REQUEST, "d": request }))?;
+        loop {
+            let mut response = self.receive(OP_REQUEST_RESPONSE)?;
+            if response["requestId"] != id.as_str() {
+                continue;
+            }
+            let status = &response["requestStatus"];
+            if status["result"] != true {
+                return Err(anyhow!("OBS {} failed: {}", request_type, status["comment"].as_str().unwrap_or("unknown error")));
+            }
+            return Ok(response["responseData"].take());
+        }
+    }
+
+    /// Whether the output a Get*Status request reports on is running
+    fn is_active(&mut self, status_request: &str) -> Result<bool> {
+        Ok(self.request(status_request, Value::Null)?["outputActive"] == true)
+    }
+
+    fn close(mut self) {
+        let _ = self.socket.close(None);
+        let _ = self.socket.flush();
+    }
+}
+
+/// Scenes in OBS (for the scene picker)
+pub fn list_scenes(config: &ObsConfig) -> Result<Vec<String>> {
+    let mut client = Client::connect(config)?;
+    let scenes = parse_scene_list(&client.request("GetSceneList", Value::Null)?);
+    client.close();
+    Ok(scenes)
+}
+
+/// What has to be undone in OBS
+#[derive(Debug, Clone)]
+struct Changes {
+    config: ObsConfig,
+    previous_scene: Option<String>,
+    started_record: bool,
+    started_replay_buffer: bool,
+}
+
+/// Applies a profile's OBS settings and undoes them afterwards
+#[derive(Default)]
+pub struct ObsSwitcher {
+    /// None = nothing to undo
+    changes: Option<Changes>,
+}
+
+impl ObsSwitcher {
+    /// Undo any previous profile's changes, then apply the settings.
+    /// Returns a summary of what was changed.
+    pub fn apply(&mut self, config: &ObsConfig, settings: &ObsSettings) -> Result<String> {
+        self.restore()?;
+
+        let mut client = Client::connect(config)?;
+        let mut changes = Changes { config: config.clone(), previous_scene: None, started_record: false, started_replay_buffer: false };
+        let mut summary = Vec::new();
+
+        // Recorded as soon as each change is made, so a later failure still undoes it
+        let result = (|| -> Result<()> {
+            if let Some(ref scene) = settings.scene {
+                let current = client.request("GetCurrentProgramScene", Value::Null)?;
+                let previous = current["currentProgramSceneName"].as_str().map(str::to_string);
+                if previous.as_deref() != Some(scene.as_str()) {
+                    client.request("SetCurrentProgramScene", json!({ "sceneName": scene }))?;
+                    changes.previous_scene = previous;
+                }
+                summary.push(format!("scene {}", scene));
+            }
+            if settings.record {
+                if !client.is_active("GetRecordStatus")? {
+                    client.request("StartRecord", Value::Null)?;
+                    changes.started_record = true;
+                }
+                summary.push("recording".to_string());
+            }
+            if settings.replay_buffer {
+                if !client.is_active("GetReplayBufferStatus")? {
+                    client.request("StartReplayBuffer", Value::Null)?;
+                    changes.started_replay_buffer = true;
+                }
+                summary.push("replay buffer".to_string());
+            }
+            Ok(())
+        })();
+        client.close();
+        self.changes = Some(changes);
+        result?;
+
+        let summary = summary.join(", ");
+        info!("[OBS] Applied {}", summary);
+        Ok(summary)
+    }
+
+    /// Stop what `apply` started and switch back to the previous scene (no-op if nothing was applied)
+    pub fn restore(&mut self) -> Result<()> {
+        let Some(changes) = self.changes.take() else {
+            return Ok(());
+        };
+        if changes.previous_scene.is_none() && !changes.started_record && !changes.started_replay_buffer {
+            return Ok(());
+        }
+        let mut client = Client::connect(&changes.config)?;
+        let result = (|| -> Result<()> {
+            if changes.started_record {
+                client.request("StopRecord", Value::Null)?;
+            }
+            if changes.started_replay_buffer {
+                client.request("StopReplayBuffer", Value::Null)?;
+            }
+            if let Some(ref scene) = changes.previous_scene {
+                client.request("SetCurrentProgramScene", json!({ "sceneName": scene }))?;
+            }
+            Ok(())
+        })();
+        client.close();
+        result?;
+        info!("[OBS] Previous scene and outputs restored");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authentication() {
+        // Example from the obs-websocket protocol documentation
+        assert_eq!(
+            authentication(
+                "supersecretpassword",
+                "lM1GncleQOaCu9lT1yeUZhFYnqhsLLP1G5lAGo3ixaI=",
+                "+IxH4CnCiqpX1rM9scsNynZzbOe4KhDeYcTNS3PDaeY="
+            ),
+            "1Ct943GAT+6YQUUX47Ia/ncufilbe6+oD6lY+5kaCu4="
+        );
+    }
+
+    #[test]
+    fn test_identify_message() {
+        let open = json!({ "obsWebSocketVersion": "5.4.2", "rpcVersion": 1 });
+        assert_eq!(identify_message(&open, "").unwrap(), json!({ "op": 1, "d": { "rpcVersion": 1, "eventSubscriptions": 0 } }));
+
+        let protected = json!({ "rpcVersion": 1, "authentication": { "salt": "salt", "challenge": "challenge" } });
+        assert!(identify_message(&protected, "").is_err());
+        let identify = identify_message(&protected, "secret").unwrap();
+        assert_eq!(identify["d"]["authentication"], authentication("secret", "salt", "challenge"));
+    }
+
+    #[test]
+    fn test_parse_scene_list() {
+        let data = json!({
+            "currentProgramSceneName": "Desktop",
+            "scenes": [
+                { "sceneIndex": 0, "sceneName": "BRB" },
+                { "sceneIndex": 2, "sceneName": "Desktop" },
+                { "sceneIndex": 1, "sceneName": "Gameplay" },
+            ],
+        });
+        assert_eq!(parse_scene_list(&data), ["Desktop", "Gameplay", "BRB"]);
+        assert!(parse_scene_list(&Value::Null).is_empty());
+        assert!(ObsSettings::default().is_empty());
+        assert!(!ObsSettings { record: true, ..Default::default() }.is_empty());
+    }
+}
@@ -0,0 +1,408 @@
+//! RGB lighting through the OpenRGB SDK server
+//!
+//! Talks OpenRGB's network protocol (the SDK server, TCP port 6742 by default) to switch all
+//! devices to a solid color or to one of the user's saved OpenRGB profiles. The lighting in
+//! place beforehand is saved as a temporary OpenRGB profile and loaded back on restore.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use tracing::info;
+
+pub const DEFAULT_PORT: u16 = 6742;
+
+/// Highest protocol version we understand (controller data layout changes with it)
+const CLIENT_PROTOCOL_VERSION: u32 = 3;
+/// Profiles (needed to restore the previous lighting) arrived in protocol 2
+const MIN_PROTOCOL_VERSION: u32 = 2;
+/// OpenRGB profile holding the lighting from before activation
+const RESTORE_PROFILE: &str = "GamingOptimizer restore";
+const TIMEOUT: Duration = Duration::from_secs(3);
+
+const MAGIC: &[u8; 4] = b"ORGB";
+const REQUEST_CONTROLLER_COUNT: u32 = 0;
+const REQUEST_CONTROLLER_DATA: u32 = 1;
+const REQUEST_PROTOCOL_VERSION: u32 = 40;
+const SET_CLIENT_NAME: u32 = 50;
+const REQUEST_PROFILE_LIST: u32 = 150;
+const REQUEST_SAVE_PROFILE: u32 = 151;
+const REQUEST_LOAD_PROFILE: u32 = 152;
+const REQUEST_DELETE_PROFILE: u32 = 153;
+const RGBCONTROLLER_UPDATELEDS: u32 = 1050;
+const RGBCONTROLLER_SETCUSTOMMODE: u32 = 1100;
+
+/// OpenRGB SDK server address (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OpenRgbConfig {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Default for OpenRgbConfig {
+    fn default() -> Self {
+        OpenRgbConfig {
+            host: "127.0.0.1".to_string(),
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+/// Lighting applied while a profile is active
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum RgbScene {
+    /// Every LED on every device set to one RGB color
+    Color([u8; 3]),
+    /// A profile saved in OpenRGB
+    Profile(String),
+}
+
+impl fmt::Display for RgbScene {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RgbScene::Color([r, g, b]) => write!(f, "#{:02X}{:02X}{:02X}", r, g, b),
+            RgbScene::Profile(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Cursor over a little-endian OpenRGB payload
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// u16 length (including the NUL) followed by the bytes
+    fn string(&mut self) -> Option<String> {
+        let len = self.u16()? as usize;
+        let bytes = self.bytes(len)?;
+        let text = bytes.split(|&b| b == 0).next().unwrap_or_default();
+        Some(String::from_utf8_lossy(text).into_owned())
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        self.string().map(|_| ())
+    }
+}
+
+/// Number of LEDs in a REQUEST_CONTROLLER_DATA reply (the color list at its end)
+fn parse_led_count(data: &[u8], protocol: u32) -> Option<usize> {
+    let mut reader = Reader::new(data);
+    reader.u32()?; // data size
+    reader.u32()?; // device type
+    reader.skip_string()?; // name
+    if protocol >= 1 {
+        reader.skip_string()?; // vendor
+    }
+    for _ in 0..4 {
+        reader.skip_string()?; // description, version, serial, location
+    }
+
+    let num_modes = reader.u16()?;
+    reader.u32()?; // active mode
+    for _ in 0..num_modes {
+        reader.skip_string()?;
+        // value, flags, speed min/max, [brightness min/max], colors min/max, speed,
+        // [brightness], direction, color mode
+        let fields = if protocol >= 3 { 12 } else { 9 };
+        reader.bytes(fields * 4)?;
+        let num_colors = reader.u16()? as usize;
+        reader.bytes(num_colors * 4)?;
+    }
+
+    let num_zones = reader.u16()?;
+    for _ in 0..num_zones {
+        reader.skip_string()?;
+        reader.bytes(4 * 4)?; // type, leds min/max/count
+        let matrix_len = reader.u16()? as usize;
+        reader.bytes(matrix_len)?;
+    }
+
+    let num_leds = reader.u16()?;
+    for _ in 0..num_leds {
+        reader.skip_string()?;
+        reader.u32()?; // value
+    }
+
+    reader.u16().map(usize::from)
+}
+
+/// Profile names in a REQUEST_PROFILE_LIST reply
+fn parse_profile_list(data: &[u8]) -> Option<Vec<String>> {
+    let mut reader = Reader::new(data);
+    reader.u32()?; // data size
+    let count = reader.u16()?;
+    (0..count).map(|_| reader.string()).collect()
+}
+
+/// UPDATELEDS payload setting `count` LEDs to one color
+fn solid_color_payload(count: usize, [r, g, b]: [u8; 3]) -> Result<Vec<u8>> {
+    let count = u16::try_from(count).map_err(|_| anyhow!("Too many LEDs on one device ({})", count))?;
+    let size = 4 + 2 + 4 * count as u32;
+    let mut payload = Vec::with_capacity(size as usize);
+    payload.extend_from_slice(&size.to_le_bytes());
+    payload.extend_from_slice(&count.to_le_bytes());
+    for _ in 0..count {
+        payload.extend_from_slice(&[r, g, b, 0]);
+    }
+    Ok(payload)
+}
+
+fn nul_terminated(text: &str) -> Vec<u8> {
+    let mut bytes = text.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+/// A connection to the OpenRGB SDK server
+struct Client {
+    stream: TcpStream,
+    protocol: u32,
+}
+
+impl Client {
+    fn connect(config: &OpenRgbConfig) -> Result<Self> {
+        let address = (config.host.as_str(), config.port)
+            .to_socket_addrs()
+            .map_err(|e| anyhow!("Invalid OpenRGB address {}:{}: {}", config.host, config.port, e))?
+            .next()
+            .ok_or_else(|| anyhow!("Invalid OpenRGB address {}:{}", config.host, config.port))?;
+        let stream = TcpStream::connect_timeout(&address, TIMEOUT)
+            .map_err(|e| anyhow!("OpenRGB SDK server not reachable at {} (is the server started?): {}", address, e))?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(TIMEOUT)))
+            .map_err(|e| anyhow!("Failed to configure OpenRGB connection: {}", e))?;
+
+        let mut client = Client { stream, protocol: 0 };
+        client.send(0, REQUEST_PROTOCOL_VERSION, &CLIENT_PROTOCOL_VERSION.to_le_bytes())?;
+        let reply = client.receive(REQUEST_PROTOCOL_VERSION)?;
+        let server_protocol = Reader::new(&reply).u32().unwrap_or(0);
+        client.protocol = server_protocol.min(CLIENT_PROTOCOL_VERSION);
+        if client.protocol < MIN_PROTOCOL_VERSION {
+            return Err(anyhow!("OpenRGB server is too old (protocol {}, needs 0.6 or newer)", server_protocol));
+        }
+        client.send(0, SET_CLIENT_NAME, &nul_terminated("Gaming Optimizer"))?;
+        Ok(client)
+    }
+
+    fn send(&mut self, device: u32, packet: u32, data: &[u8]) -> Result<()> {
+        let mut message = Vec::with_capacity(16 + data.len());
+        message.extend_from_slice(MAGIC);
+        message.extend_from_slice(&device.to_le_bytes());
+        message.extend_from_slice(&packet.to_le_bytes());
+        message.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        message.extend_from_slice(data);
+        self.stream
+            .write_all(&message)
+            .map_err(|e| anyhow!("Failed to send to OpenRGB: {}", e))
+    }
+
+    /// Read packets until one with the given id arrives (skipping device-list notifications)
+    fn receive(&mut self, packet: u32) -> Result<Vec<u8>> {
+        loop {
+            let mut header = [0u8; 16];
+            self.stream
+                .read_exact(&mut header)
+                .map_err(|e| anyhow!("No reply from OpenRGB: {}", e))?;
+            if &header[..4] != MAGIC {
+                return Err(anyhow!("Unexpected reply from OpenRGB (not an SDK server?)"));
+            }
+            let mut reader = Reader::new(&header[4..]);
+            let (_device, id, size) = (reader.u32(), reader.u32(), reader.u32());
+            let mut data = vec![0u8; size.unwrap_or(0) as usize];
+            self.stream
+                .read_exact(&mut data)
+                .map_err(|e| anyhow!("Truncated reply from OpenRGB: {}", e))?;
+            if id == Some(packet) {
+                return Ok(data);
+            }
+        }
+    }
+
+    fn controller_count(&mut self) -> Result<u32> {
+        self.send(0, REQUEST_CONTROLLER_COUNT, &[])?;
+        let reply = self.receive(REQUEST_CONTROLLER_COUNT)?;
+        Reader::new(&reply).u32().ok_or_else(|| anyhow!("Invalid controller count from OpenRGB"))
+    }
+
+    fn led_count(&mut self, device: u32) -> Result<usize> {
+        self.send(device, REQUEST_CONTROLLER_DATA, &self.protocol.to_le_bytes())?;
+        let reply = self.receive(REQUEST_CONTROLLER_DATA)?;
+        parse_led_count(&reply, self.protocol)
+            .ok_or_else(|| anyhow!("Invalid data for OpenRGB device {}", device))
+    }
+
+    fn profiles(&mut self) -> Result<Vec<String>> {
+        self.send(0, REQUEST_PROFILE_LIST, &[])?;
+        let reply = self.receive(REQUEST_PROFILE_LIST)?;
+        parse_profile_list(&reply).ok_or_else(|| anyhow!("Invalid profile list from OpenRGB"))
+    }
+
+    fn profile_command(&mut self, packet: u32, name: &str) -> Result<()> {
+        self.send(0, packet, &nul_terminated(name))
+    }
+
+    /// Round trip so the commands sent so far are processed before the connection closes
+    fn flush(&mut self) -> Result<()> {
+        self.controller_count().map(|_| ())
+    }
+
+    /// Switch every device to direct mode with all LEDs set to `color`.
+    /// Returns the number of devices changed.
+    fn set_color(&mut self, color: [u8; 3]) -> Result<u32> {
+        let count = self.controller_count()?;
+        let mut changed = 0;
+        for device in 0..count {
+            let leds = self.led_count(device)?;
+            if leds == 0 {
+                continue;
+            }
+            self.send(device, RGBCONTROLLER_SETCUSTOMMODE, &[])?;
+            self.send(device, RGBCONTROLLER_UPDATELEDS, &solid_color_payload(leds, color)?)?;
+            changed += 1;
+        }
+        Ok(changed)
+    }
+}
+
+/// Profiles saved in OpenRGB (for the scene picker)
+pub fn list_profiles(config: &OpenRgbConfig) -> Result<Vec<String>> {
+    let mut profiles = Client::connect(config)?.profiles()?;
+    profiles.retain(|name| name != RESTORE_PROFILE);
+    Ok(profiles)
+}
+
+/// Applies a profile's lighting and brings the previous lighting back afterwards
+#[derive(Default)]
+pub struct RgbSwitcher {
+    /// Server holding our restore profile (None = nothing to restore)
+    saved_on: Option<OpenRgbConfig>,
+}
+
+impl RgbSwitcher {
+    /// Restore any previous profile's lighting, then apply the scene.
+    /// Returns a summary of what was changed.
+    pub fn apply(&mut self, config: &OpenRgbConfig, scene: &RgbScene) -> Result<String> {
+        self.restore()?;
+
+        let mut client = Client::connect(config)?;
+        if let RgbScene::Profile(ref name) = scene {
+            if !client.profiles()?.contains(name) {
+                return Err(anyhow!("OpenRGB profile '{}' not found", name));
+            }
+        }
+
+        client.profile_command(REQUEST_SAVE_PROFILE, RESTORE_PROFILE)?;
+        self.saved_on = Some(config.clone());
+
+        let summary = match scene {
+            RgbScene::Color(color) => {
+                let devices = client.set_color(*color)?;
+                format!("{} on {} devices", scene, devices)
+            }
+            RgbScene::Profile(name) => {
+                client.profile_command(REQUEST_LOAD_PROFILE, name)?;
+                format!("profile {}", name)
+            }
+        };
+        client.flush()?;
+        info!("[RGB] Applied {}", summary);
+        Ok(summary)
+    }
+
+    /// Load the lighting saved by `apply` (no-op if nothing was applied)
+    pub fn restore(&mut self) -> Result<()> {
+        let Some(config) = self.saved_on.take() else {
+            return Ok(());
+        };
+        let mut client = Client::connect(&config)?;
+        client.profile_command(REQUEST_LOAD_PROFILE, RESTORE_PROFILE)?;
+        client.profile_command(REQUEST_DELETE_PROFILE, RESTORE_PROFILE)?;
+        client.flush()?;
+        info!("[RGB] Previous lighting restored");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_string(data: &mut Vec<u8>, text: &str) {
+        data.extend_from_slice(&(text.len() as u16 + 1).to_le_bytes());
+        data.extend_from_slice(text.as_bytes());
+        data.push(0);
+    }
+
+    #[test]
+    fn test_parse_profile_list() {
+        let mut data = 0u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&2u16.to_le_bytes());
+        push_string(&mut data, "Red");
+        push_string(&mut data, "Off");
+        assert_eq!(parse_profile_list(&data), Some(vec!["Red".to_string(), "Off".to_string()]));
+        assert_eq!(parse_profile_list(&data[..data.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_parse_led_count() {
+        let mut data = 0u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&5i32.to_le_bytes()); // keyboard
+        for text in ["Keyboard", "Vendor", "Description", "1.0", "SN", "HID"] {
+            push_string(&mut data, text);
+        }
+        // One mode with one color
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&0i32.to_le_bytes());
+        push_string(&mut data, "Direct");
+        data.extend_from_slice(&[0u8; 12 * 4]);
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; 4]);
+        // One zone with a 1x2 matrix
+        data.extend_from_slice(&1u16.to_le_bytes());
+        push_string(&mut data, "Keys");
+        data.extend_from_slice(&[0u8; 16]);
+        data.extend_from_slice(&16u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; 16]);
+        // Two LEDs and their colors
+        data.extend_from_slice(&2u16.to_le_bytes());
+        push_string(&mut data, "Key: A");
+        data.extend_from_slice(&0u32.to_le_bytes());
+        push_string(&mut data, "Key: B");
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; 8]);
+
+        assert_eq!(parse_led_count(&data, 3), Some(2));
+        assert_eq!(parse_led_count(&data[..data.len() - 9], 3), None);
+    }
+
+    #[test]
+    fn test_solid_color_payload() {
+        let payload = solid_color_payload(2, [255, 0, 16]).unwrap();
+        assert_eq!(payload, vec![14, 0, 0, 0, 2, 0, 255, 0, 16, 0, 255, 0, 16, 0]);
+        assert_eq!(RgbScene::Color([255, 0, 16]).to_string(), "#FF0010");
+    }
+}
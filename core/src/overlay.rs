@@ -0,0 +1,90 @@
+//! Crosshair overlay interface
+//!
+//! The overlay is crosshair.exe, which the app starts and talks to (`crosshair_overlay` in the
+//! `gaming_optimizer` binary). Activation only reaches it through `OverlayBackend`, so it runs
+//! without a display and against a fake in the tests.
+
+use crate::crosshair_position::CrosshairAnchor;
+use crate::overlay_text::Corner;
+use crate::profile::OverlayNote;
+
+/// Optional overlay behaviour passed to crosshair.exe as trailing flags
+#[derive(Debug, Clone, Default)]
+pub struct OverlayOptions {
+    /// Apply WDA_EXCLUDEFROMCAPTURE so recordings and screenshots don't show the crosshair
+    pub exclude_from_capture: bool,
+    /// RGBA tint multiplied into every crosshair pixel
+    pub tint: Option<[u8; 4]>,
+    /// Text note drawn in a screen corner next to the crosshair
+    pub note: Option<OverlayNote>,
+    /// Screen anchor the pixel offsets are relative to
+    pub anchor: CrosshairAnchor,
+    /// Corner to show live hardware readings in (read from `monitor::hud_file`)
+    pub hud: Option<Corner>,
+}
+
+/// A running crosshair overlay
+pub trait Overlay: Send {
+    /// Whether it gave up because it would have stolen mouse clicks
+    fn click_through_failed(&mut self) -> bool;
+
+    /// Show another crosshair without restarting, so nothing flashes; false if the overlay is
+    /// gone or didn't take it (start a new one then)
+    fn swap(&mut self, image_path: &str, x_offset: i32, y_offset: i32, options: &OverlayOptions) -> bool;
+
+    fn stop(&self);
+}
+
+/// Starting crosshair overlays; activation goes through this so tests can use a fake
+pub trait OverlayBackend: Send {
+    fn start(&mut self, image_path: String, x_offset: i32, y_offset: i32, options: &OverlayOptions) -> Result<Box<dyn Overlay>, String>;
+}
+
+/// No overlay to start (until the app hands in crosshair.exe's)
+#[derive(Default)]
+pub struct NoOverlay;
+
+impl OverlayBackend for NoOverlay {
+    fn start(&mut self, _image_path: String, _x_offset: i32, _y_offset: i32, _options: &OverlayOptions) -> Result<Box<dyn Overlay>, String> {
+        Err("No crosshair overlay available".to_string())
+    }
+}
+
+/// Parse a `#RRGGBB` or `#RRGGBBAA` hex color into RGBA components
+pub fn parse_hex_color(text: &str) -> Option<[u8; 4]> {
+    let hex = text.trim().trim_start_matches('#');
+    if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+
+    Some([channel(0)?, channel(2)?, channel(4)?, alpha])
+}
+
+/// Format RGBA components as `#RRGGBBAA`
+pub fn format_hex_color(rgba: [u8; 4]) -> String {
+    format!("#{:02X}{:02X}{:02X}{:02X}", rgba[0], rgba[1], rgba[2], rgba[3])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#00FF00"), Some([0, 255, 0, 255]));
+        assert_eq!(parse_hex_color("00ffff80"), Some([0, 255, 255, 128]));
+        assert_eq!(parse_hex_color(" #FF00FF "), Some([255, 0, 255, 255]));
+        assert_eq!(parse_hex_color("#FFF"), None);
+        assert_eq!(parse_hex_color("#GG0000"), None);
+        assert_eq!(parse_hex_color("#ééé"), None);
+    }
+
+    #[test]
+    fn test_hex_color_roundtrip() {
+        let color = [12, 200, 34, 255];
+        assert_eq!(parse_hex_color(&format_hex_color(color)), Some(color));
+    }
+}
@@ -0,0 +1,122 @@
+//! Third-party overlay detection
+//!
+//! Overlay injectors hook into the game's rendering. They are a common reason for the
+//! click-through crosshair disappearing or flickering, and some anti-cheats refuse to start
+//! alongside them. After an activation the running processes are checked for the well-known
+//! ones, so the report can warn and offer a fix where one is safe.
+
+use crate::i18n::t;
+use crate::profile::Profile;
+use std::fmt;
+
+/// A known overlay injector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverlayInjector {
+    GeForceExperience,
+    Discord,
+    RivaTuner,
+    XboxGameBar,
+}
+
+/// Safe one-click way to get an injector out of the way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayFix {
+    /// Close the overlay's own processes (the app behind it keeps working)
+    CloseProcesses(&'static [&'static str]),
+    /// Turn Game Bar off in the profile's Windows game features
+    TurnOffGameBar,
+}
+
+impl OverlayInjector {
+    pub const ALL: [OverlayInjector; 4] = [
+        OverlayInjector::GeForceExperience,
+        OverlayInjector::Discord,
+        OverlayInjector::RivaTuner,
+        OverlayInjector::XboxGameBar,
+    ];
+
+    /// Processes that mean the overlay is loaded
+    fn processes(self) -> &'static [&'static str] {
+        match self {
+            OverlayInjector::GeForceExperience => &["NVIDIA Overlay.exe", "NVIDIA Share.exe"],
+            OverlayInjector::Discord => &["Discord.exe", "DiscordPTB.exe", "DiscordCanary.exe"],
+            OverlayInjector::RivaTuner => &["RTSS.exe"],
+            OverlayInjector::XboxGameBar => &["GameBar.exe", "GameBarFTServer.exe"],
+        }
+    }
+
+    /// None when the only fix is in the other app's settings (closing Discord would end calls)
+    pub fn fix(self) -> Option<OverlayFix> {
+        match self {
+            OverlayInjector::GeForceExperience => Some(OverlayFix::CloseProcesses(&["NVIDIA Overlay.exe", "NVIDIA Share.exe"])),
+            OverlayInjector::Discord => None,
+            OverlayInjector::RivaTuner => Some(OverlayFix::CloseProcesses(&["RTSS.exe"])),
+            OverlayInjector::XboxGameBar => Some(OverlayFix::TurnOffGameBar),
+        }
+    }
+
+    /// What to do about it by hand
+    pub fn hint(self) -> String {
+        match self {
+            OverlayInjector::GeForceExperience => t!("overlay-hint-geforce"),
+            OverlayInjector::Discord => t!("overlay-hint-discord"),
+            OverlayInjector::RivaTuner => t!("overlay-hint-rivatuner"),
+            OverlayInjector::XboxGameBar => t!("overlay-hint-game-bar"),
+        }
+    }
+}
+
+impl fmt::Display for OverlayInjector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverlayInjector::GeForceExperience => write!(f, "GeForce Experience"),
+            OverlayInjector::Discord => write!(f, "Discord"),
+            OverlayInjector::RivaTuner => write!(f, "RivaTuner"),
+            OverlayInjector::XboxGameBar => write!(f, "Xbox Game Bar"),
+        }
+    }
+}
+
+/// Injectors among `running` that matter for `profile`: nothing when it shows no crosshair, and
+/// none that the profile already closes or turns off
+pub fn detect<'a>(profile: &Profile, running: impl IntoIterator<Item = &'a str>) -> Vec<OverlayInjector> {
+    if !profile.overlay_enabled {
+        return Vec::new();
+    }
+    let running: Vec<&str> = running.into_iter().collect();
+    let closed = |exe: &str| profile.processes_to_kill.iter().any(|name| name.eq_ignore_ascii_case(exe));
+    OverlayInjector::ALL
+        .into_iter()
+        .filter(|injector| {
+            injector
+                .processes()
+                .iter()
+                .any(|exe| !closed(exe) && running.iter().any(|name| name.eq_ignore_ascii_case(exe)))
+        })
+        .filter(|injector| !(*injector == OverlayInjector::XboxGameBar && profile.game_features.game_bar == Some(false)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::create_profile;
+
+    #[test]
+    fn test_detect() {
+        let mut profile = create_profile("FPS".to_string());
+        let running = ["explorer.exe", "discord.exe", "RTSS.exe", "GameBar.exe"];
+        assert_eq!(
+            detect(&profile, running),
+            [OverlayInjector::Discord, OverlayInjector::RivaTuner, OverlayInjector::XboxGameBar]
+        );
+
+        // Closed or turned off by the profile itself
+        profile.processes_to_kill.push("rtss.exe".to_string());
+        profile.game_features.game_bar = Some(false);
+        assert_eq!(detect(&profile, running), [OverlayInjector::Discord]);
+
+        profile.overlay_enabled = false;
+        assert!(detect(&profile, running).is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+//! Crosshair pixel preparation and blending
+//!
+//! crosshair.exe tints and premultiplies the crosshair before handing it to UpdateLayeredWindow,
+//! which blends it over the screen per pixel. The same code is shared with crosshair.exe and the
+//! overlay benchmark (`cargo bench --bench overlay_render`, criterion); `blend_over` does on the
+//! CPU what the compositor does with the result, so a frame's cost can be measured at any
+//! resolution.
+
+use std::time::{Duration, Instant};
+
+/// Edge length of the synthetic crosshair (same as `crosshair_gen::CROSSHAIR_SIZE`)
+pub const SYNTHETIC_SIZE: u32 = 100;
+
+/// Multiply an RGBA pixel by the tint color (white pixels become exactly the tint)
+pub fn apply_tint(pixel: [u8; 4], tint: Option<[u8; 4]>) -> [u8; 4] {
+    match tint {
+        Some(t) => [
+            ((pixel[0] as u16 * t[0] as u16) / 255) as u8,
+            ((pixel[1] as u16 * t[1] as u16) / 255) as u8,
+            ((pixel[2] as u16 * t[2] as u16) / 255) as u8,
+            ((pixel[3] as u16 * t[3] as u16) / 255) as u8,
+        ],
+        None => pixel,
+    }
+}
+
+/// Convert straight RGBA pixels to premultiplied BGRA bytes
+pub fn to_premultiplied_bgra(pixels: impl Iterator<Item = [u8; 4]>) -> Vec<u8> {
+    let mut bgra_pixels = Vec::with_capacity(pixels.size_hint().0 * 4);
+    bgra_pixels.extend(pixels.flat_map(|pixel| {
+        // Premultiply alpha for proper blending
+        let a = pixel[3] as u32;
+        let premultiply = |c: u8| (c as u32 * a / 255) as u8;
+        [premultiply(pixel[2]), premultiply(pixel[1]), premultiply(pixel[0]), pixel[3]] // BGRA
+    }));
+    bgra_pixels
+}
+
+/// Blend premultiplied BGRA `pixels` (`width` x `height`) over a BGRA frame at (x, y), clipped
+/// to the frame (what AC_SRC_OVER with AC_SRC_ALPHA does)
+pub fn blend_over(frame: &mut [u8], frame_width: u32, pixels: &[u8], width: u32, height: u32, x: i32, y: i32) {
+    let (frame_width, width) = (frame_width as i64, width as i64);
+    let frame_height = frame.len() as i64 / 4 / frame_width.max(1);
+    // Columns of the layer that land on the frame
+    let first = (-(x as i64)).clamp(0, width);
+    let last = (frame_width - x as i64).clamp(0, width);
+    if first >= last {
+        return;
+    }
+    for row in 0..height as i64 {
+        let frame_y = y as i64 + row;
+        if frame_y < 0 || frame_y >= frame_height {
+            continue;
+        }
+        let src = &pixels[((row * width + first) * 4) as usize..((row * width + last) * 4) as usize];
+        let dst_start = ((frame_y * frame_width + x as i64 + first) * 4) as usize;
+        let dst = &mut frame[dst_start..dst_start + src.len()];
+        for (over, under) in src.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+            let inverse = 255 - over[3] as u16;
+            if inverse == 255 {
+                continue;
+            }
+            for (o, u) in over.iter().zip(under.iter_mut()) {
+                *u = (*o as u16 + (*u as u16 * inverse + 127) / 255) as u8;
+            }
+        }
+    }
+}
+
+/// A white plus with a dark outline and a soft-edged center dot, `width` x `height` RGBA (a
+/// screen-sized one stands in for full-screen layers like the HUD)
+pub fn synthetic_crosshair(width: u32, height: u32) -> Vec<[u8; 4]> {
+    let (center_x, center_y) = (width as f32 / 2.0, height as f32 / 2.0);
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = ((x as f32 + 0.5 - center_x).abs(), (y as f32 + 0.5 - center_y).abs());
+            let arm = dx.min(dy);
+            let pixel = if arm < 1.5 {
+                [255, 255, 255, 255]
+            } else if arm < 3.0 {
+                [0, 0, 0, 160]
+            } else {
+                let distance = (dx * dx + dy * dy).sqrt();
+                let alpha = (255.0 * (4.0 - distance).clamp(0.0, 1.0)) as u8;
+                [255, 255, 255, alpha]
+            };
+            pixels.push(pixel);
+        }
+    }
+    pixels
+}
+
+/// Tint and premultiply `image`, then blend it centered over a `screen_width`-wide BGRA frame
+pub fn render_centered(image: &[[u8; 4]], width: u32, height: u32, tint: Option<[u8; 4]>, screen_width: u32, frame: &mut [u8]) {
+    let screen_height = (frame.len() / 4 / screen_width.max(1) as usize) as i32;
+    let x = (screen_width as i32 - width as i32) / 2;
+    let y = (screen_height - height as i32) / 2;
+    let pixels = to_premultiplied_bgra(image.iter().map(|&pixel| apply_tint(pixel, tint)));
+    blend_over(frame, screen_width, &pixels, width, height, x, y);
+}
+
+/// A `screen_width` x `screen_height` frame to render into (reused between calls through `frame`)
+pub fn clear_frame((screen_width, screen_height): (u32, u32), frame: &mut Vec<u8>) {
+    frame.clear();
+    frame.resize((screen_width * screen_height * 4) as usize, 0x20);
+}
+
+/// Time one frame of `render_centered` over a freshly cleared frame
+pub fn time_frame(
+    image: &[[u8; 4]],
+    width: u32,
+    height: u32,
+    tint: Option<[u8; 4]>,
+    screen: (u32, u32),
+    frame: &mut Vec<u8>,
+) -> Duration {
+    clear_frame(screen, frame);
+    let start = Instant::now();
+    render_centered(image, width, height, tint, screen.0, frame);
+    start.elapsed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_premultiply() {
+        assert_eq!(to_premultiplied_bgra([[255, 128, 0, 255]].into_iter()), [0, 128, 255, 255]);
+        assert_eq!(to_premultiplied_bgra([[255, 255, 255, 0]].into_iter()), [0, 0, 0, 0]);
+        assert_eq!(to_premultiplied_bgra([[200, 100, 50, 128]].into_iter()), [25, 50, 100, 128]);
+    }
+
+    #[test]
+    fn test_apply_tint() {
+        assert_eq!(apply_tint([255, 255, 255, 255], Some([0, 255, 0, 128])), [0, 255, 0, 128]);
+        assert_eq!(apply_tint([10, 20, 30, 40], None), [10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn test_blend_over() {
+        // 2x1 frame of mid grey; opaque red on the left, half-transparent black on the right
+        let mut frame = vec![100; 8];
+        let pixels = to_premultiplied_bgra([[255, 0, 0, 255], [0, 0, 0, 128]].into_iter());
+        blend_over(&mut frame, 2, &pixels, 2, 1, 0, 0);
+        assert_eq!(&frame[..4], [0, 0, 255, 255]);
+        assert_eq!(&frame[4..], [50, 50, 50, 178]);
+
+        // A transparent layer leaves the frame alone
+        let mut frame = vec![100; 8];
+        blend_over(&mut frame, 2, &[0; 8], 2, 1, 0, 0);
+        assert_eq!(frame, [100; 8]);
+    }
+
+    #[test]
+    fn test_blend_over_clips() {
+        let mut frame = vec![0; 4 * 4 * 4];
+        let pixels = to_premultiplied_bgra(vec![[255, 255, 255, 255]; 9].into_iter());
+        // Hangs over the top-left corner: only the bottom-right 2x2 of the layer lands
+        blend_over(&mut frame, 4, &pixels, 3, 3, -1, -1);
+        let covered = frame.chunks(4).filter(|pixel| pixel[3] == 255).count();
+        assert_eq!(covered, 4);
+        // Completely off screen
+        blend_over(&mut frame, 4, &pixels, 3, 3, 10, 10);
+        assert_eq!(frame.chunks(4).filter(|pixel| pixel[3] == 255).count(), 4);
+    }
+
+    #[test]
+    fn test_render_centered() {
+        let mut frame = Vec::new();
+        clear_frame((4, 4), &mut frame);
+        render_centered(&[[255, 255, 255, 255]; 4], 2, 2, Some([255, 0, 0, 255]), 4, &mut frame);
+        // The red 2x2 lands in the middle of the 4x4 frame
+        let red: Vec<usize> = frame.chunks(4).enumerate().filter(|(_, pixel)| *pixel == [0, 0, 255, 255]).map(|(i, _)| i).collect();
+        assert_eq!(red, [5, 6, 9, 10]);
+        assert!(frame.chunks(4).filter(|pixel| *pixel != [0, 0, 255, 255]).all(|pixel| pixel == [0x20; 4]));
+    }
+
+    #[test]
+    fn test_time_frame() {
+        let image = synthetic_crosshair(SYNTHETIC_SIZE, SYNTHETIC_SIZE);
+        assert_eq!(image.len(), (SYNTHETIC_SIZE * SYNTHETIC_SIZE) as usize);
+
+        let mut frame = Vec::new();
+        for (width, height) in [(1920, 1080), (2560, 1440), (3840, 2160)] {
+            time_frame(&image, SYNTHETIC_SIZE, SYNTHETIC_SIZE, Some([0, 255, 0, 255]), (width, height), &mut frame);
+            assert_eq!(frame.len(), (width * height * 4) as usize);
+            // The center of the plus is green after tinting
+            let center = (((height / 2) * width + width / 2) * 4) as usize;
+            assert_eq!(&frame[center..center + 4], [0, 255, 0, 255]);
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Simple bitmap text rasterization for overlay notes
+//!
+//! Uses an embedded 5x7 ASCII font scaled by whole pixels, so the overlay can draw
+//! short reminders without depending on a font engine. Shared with crosshair.exe.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Glyph width in font pixels
+const GLYPH_WIDTH: usize = 5;
+/// Glyph height in font pixels
+const GLYPH_HEIGHT: usize = 7;
+/// Base cell height (glyph + line spacing) that `font_size` is measured against
+const CELL_HEIGHT: u32 = 8;
+
+/// 5x7 font for ASCII 0x20..=0x7E, one byte per column, least significant bit on top
+const FONT_5X7: [[u8; GLYPH_WIDTH]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x55, 0x22, 0x50], // &
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // )
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // *
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // 0
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x00, 0x08, 0x14, 0x22, 0x41], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x41, 0x22, 0x14, 0x08, 0x00], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // @
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // D
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7F, 0x09, 0x09, 0x01, 0x01], // F
+    [0x3E, 0x41, 0x41, 0x51, 0x32], // G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // H
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // J
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7F, 0x02, 0x04, 0x02, 0x7F], // M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // N
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // T
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // V
+    [0x7F, 0x20, 0x18, 0x20, 0x7F], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x03, 0x04, 0x78, 0x04, 0x03], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // backslash
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // f
+    [0x08, 0x14, 0x54, 0x54, 0x3C], // g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // i
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // j
+    [0x00, 0x7F, 0x10, 0x28, 0x44], // k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // l
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // p
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // t
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // v
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x08, 0x04, 0x08, 0x10, 0x08], // ~
+];
+
+/// Screen corner an overlay note is anchored to
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Corner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    pub const ALL: [Corner; 4] = [
+        Corner::TopLeft,
+        Corner::TopRight,
+        Corner::BottomLeft,
+        Corner::BottomRight,
+    ];
+
+    /// Command-line name used when passing the corner to crosshair.exe
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Corner::TopLeft => "top-left",
+            Corner::TopRight => "top-right",
+            Corner::BottomLeft => "bottom-left",
+            Corner::BottomRight => "bottom-right",
+        }
+    }
+}
+
+impl FromStr for Corner {
+    type Err = ();
+
+    /// Parse a corner from its command-line name
+    fn from_str(arg: &str) -> Result<Self, Self::Err> {
+        Corner::ALL.into_iter().find(|c| c.as_arg() == arg).ok_or(())
+    }
+}
+
+impl fmt::Display for Corner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Corner::TopLeft => "Top left",
+            Corner::TopRight => "Top right",
+            Corner::BottomLeft => "Bottom left",
+            Corner::BottomRight => "Bottom right",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Rasterized text as straight (non-premultiplied) RGBA pixels, row-major
+pub struct TextBitmap {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+/// Look up the glyph columns for a character (unsupported characters render as '?')
+fn glyph(ch: char) -> &'static [u8; GLYPH_WIDTH] {
+    let code = ch as u32;
+    if (0x20..=0x7E).contains(&code) {
+        &FONT_5X7[(code - 0x20) as usize]
+    } else {
+        &FONT_5X7[('?' as u32 - 0x20) as usize]
+    }
+}
+
+/// Rasterize (possibly multi-line) text at roughly `font_size` pixels per line,
+/// with a dark 1-pixel outline so it stays readable over any game scene
+pub fn rasterize(text: &str, font_size: u32, color: [u8; 4]) -> TextBitmap {
+    let scale = (font_size / CELL_HEIGHT).max(1) as usize;
+    let lines: Vec<&str> = text.lines().collect();
+    let columns = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    // One font pixel of padding on every side leaves room for the outline
+    let cell_w = (GLYPH_WIDTH + 1) * scale;
+    let cell_h = CELL_HEIGHT as usize * scale;
+    let width = columns * cell_w + scale * 2;
+    let height = lines.len() * cell_h + scale * 2;
+
+    let mut mask = vec![false; width * height];
+    for (row, line) in lines.iter().enumerate() {
+        for (col, ch) in line.chars().enumerate() {
+            let origin_x = scale + col * cell_w;
+            let origin_y = scale + row * cell_h;
+            for (gx, bits) in glyph(ch).iter().enumerate() {
+                for gy in 0..GLYPH_HEIGHT {
+                    if bits & (1 << gy) == 0 {
+                        continue;
+                    }
+                    for sy in 0..scale {
+                        for sx in 0..scale {
+                            let x = origin_x + gx * scale + sx;
+                            let y = origin_y + gy * scale + sy;
+                            mask[y * width + x] = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let lit = |x: isize, y: isize| {
+        x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height && mask[y as usize * width + x as usize]
+    };
+
+    let outline = [0, 0, 0, (color[3] as u32 * 200 / 255) as u8];
+    let mut pixels = vec![[0u8; 4]; width * height];
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let index = y as usize * width + x as usize;
+            if mask[index] {
+                pixels[index] = color;
+            } else if (-1..=1).any(|dy| (-1..=1).any(|dx| lit(x + dx, y + dy))) {
+                pixels[index] = outline;
+            }
+        }
+    }
+
+    TextBitmap {
+        width: width as u32,
+        height: height as u32,
+        pixels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rasterize_dimensions() {
+        let bitmap = rasterize("AB", 8, [255, 255, 255, 255]);
+        assert_eq!(bitmap.width, 2 * 6 + 2);
+        assert_eq!(bitmap.height, 8 + 2);
+        assert_eq!(bitmap.pixels.len(), (bitmap.width * bitmap.height) as usize);
+
+        // Two lines at double scale
+        let bitmap = rasterize("A\nBC", 16, [255, 255, 255, 255]);
+        assert_eq!(bitmap.width, 2 * 12 + 4);
+        assert_eq!(bitmap.height, 2 * 16 + 4);
+    }
+
+    #[test]
+    fn test_rasterize_draws_glyph_and_outline() {
+        let color = [0, 255, 0, 255];
+        // 'I' has a full vertical bar in its middle column
+        let bitmap = rasterize("I", 8, color);
+        let at = |x: u32, y: u32| bitmap.pixels[(y * bitmap.width + x) as usize];
+        assert_eq!(at(1 + 2, 1 + 3), color);
+        assert_eq!(at(0, 0)[0..3], [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_corner_args_roundtrip() {
+        for corner in Corner::ALL {
+            assert_eq!(corner.as_arg().parse::<Corner>(), Ok(corner));
+        }
+        assert!("middle".parse::<Corner>().is_err());
+    }
+}
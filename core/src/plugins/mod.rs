@@ -0,0 +1,301 @@
+//! Plugins
+//!
+//! The `plugins` folder of the data directory holds Rhai scripts (see `script`) and programs in
+//! any language (see `program`). Both are called when something happens: a profile is activated
+//! or deactivated, a known game starts, or a temperature alert fires. Each gets the event plus
+//! the app's state (active profile and latest readings), and can answer with the commands the
+//! command line has (`activate`, `deactivate`, `overlay`). Each plugin is off until enabled.
+
+pub mod program;
+pub mod script;
+
+use crate::alerts::TemperatureAlert;
+use crate::cli::CliCommand;
+use crate::ipc::RequestSender;
+use crate::monitor::HardwareSnapshot;
+use anyhow::Result;
+use program::Program;
+use script::Script;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use tracing::{info, warn};
+
+/// Folder in the data directory the plugins are loaded from
+pub const PLUGINS_FOLDER: &str = "plugins";
+
+/// Plugin settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct PluginConfig {
+    /// File names of the plugins that run (a new plugin stays off until it's enabled)
+    pub enabled: Vec<String>,
+}
+
+impl PluginConfig {
+    pub fn is_enabled(&self, file: &str) -> bool {
+        self.enabled.iter().any(|name| name.eq_ignore_ascii_case(file))
+    }
+
+    pub fn set_enabled(&mut self, file: &str, enabled: bool) {
+        self.enabled.retain(|name| !name.eq_ignore_ascii_case(file));
+        if enabled {
+            self.enabled.push(file.to_string());
+            self.enabled.sort();
+        }
+    }
+}
+
+/// Something plugins are called for
+#[derive(Debug, Clone, PartialEq)]
+pub enum PluginEvent {
+    Activated { profile: String, applied: usize, failed: usize, rolled_back: bool },
+    Deactivated { profile: String },
+    /// A known game started (`profile` is the one named after it, if any)
+    GameStarted { game: String, exe: String, profile: Option<String> },
+    TemperatureAlert(TemperatureAlert),
+}
+
+impl PluginEvent {
+    /// Script function called for the event
+    pub fn hook(&self) -> &'static str {
+        match self {
+            PluginEvent::Activated { .. } => "on_activate",
+            PluginEvent::Deactivated { .. } => "on_deactivate",
+            PluginEvent::GameStarted { .. } => "on_game_start",
+            PluginEvent::TemperatureAlert(_) => "on_temp_alert",
+        }
+    }
+
+    /// `event` field of the context
+    fn name(&self) -> &'static str {
+        match self {
+            PluginEvent::Activated { .. } => "activated",
+            PluginEvent::Deactivated { .. } => "deactivated",
+            PluginEvent::GameStarted { .. } => "game_started",
+            PluginEvent::TemperatureAlert(_) => "temperature_alert",
+        }
+    }
+}
+
+/// The app's state when the event happened
+#[derive(Debug, Clone, Default)]
+pub struct SystemContext {
+    pub active_profile: Option<String>,
+    pub hardware: Option<HardwareSnapshot>,
+}
+
+/// What a plugin is given: the event's details plus `system` (readings are null when missing)
+pub fn context(event: &PluginEvent, system: &SystemContext) -> Value {
+    let mut ctx = match event {
+        PluginEvent::Activated { profile, applied, failed, rolled_back } => {
+            json!({ "profile": profile, "applied": applied, "failed": failed, "rolled_back": rolled_back })
+        }
+        PluginEvent::Deactivated { profile } => json!({ "profile": profile }),
+        PluginEvent::GameStarted { game, exe, profile } => json!({ "game": game, "exe": exe, "profile": profile }),
+        PluginEvent::TemperatureAlert(alert) => {
+            json!({ "sensor": alert.sensor.to_string(), "temp": alert.temp_c, "limit": alert.limit_c })
+        }
+    };
+    let hardware = system.hardware.clone().unwrap_or_default();
+    ctx["event"] = json!(event.name());
+    ctx["system"] = json!({
+        "active_profile": system.active_profile,
+        "cpu_load": hardware.cpu_load,
+        "cpu_temp": hardware.cpu_temp,
+        "gpu_load": hardware.gpu_load,
+        "gpu_temp": hardware.gpu_temp,
+        "ram": hardware.memory_percent(),
+    });
+    ctx
+}
+
+/// Whether `file` is a script or a program plugin
+fn is_plugin(file: &Path) -> bool {
+    file.extension().is_some_and(|ext| {
+        ext.eq_ignore_ascii_case(script::EXTENSION) || program::EXTENSIONS.iter().any(|known| ext.eq_ignore_ascii_case(known))
+    })
+}
+
+/// Scripts and programs in `folder`, sorted
+pub fn list_plugins(folder: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_plugin(path))
+        .filter_map(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+        .collect();
+    files.sort_by_key(|file| file.to_lowercase());
+    files
+}
+
+enum Plugin {
+    /// Boxed: a compiled script is far bigger than a program entry
+    Script(Box<Script>),
+    Program(Program),
+}
+
+impl Plugin {
+    fn file(&self) -> &str {
+        match self {
+            Plugin::Script(script) => &script.file,
+            Plugin::Program(program) => &program.file,
+        }
+    }
+
+    fn run(&mut self, event: &PluginEvent, system: &SystemContext) -> Result<Vec<CliCommand>> {
+        match self {
+            Plugin::Script(script) => script.run(event, system),
+            Plugin::Program(program) => program.run(event, system),
+        }
+    }
+}
+
+/// The enabled plugins in `folder` (scripts that don't compile are left out)
+fn load_plugins(folder: &Path, config: &PluginConfig) -> Vec<Plugin> {
+    list_plugins(folder)
+        .into_iter()
+        .filter(|file| config.is_enabled(file))
+        .filter_map(|file| {
+            let is_script = Path::new(&file).extension().is_some_and(|ext| ext.eq_ignore_ascii_case(script::EXTENSION));
+            if !is_script {
+                return Some(Plugin::Program(Program::new(folder, &file)));
+            }
+            let source = fs::read_to_string(folder.join(&file)).map_err(|e| warn!("[Plugins] Failed to read {}: {}", file, e)).ok()?;
+            Script::load(&file, &source).map(|script| Plugin::Script(Box::new(script))).map_err(|e| warn!("[Plugins] {}", e)).ok()
+        })
+        .collect()
+}
+
+/// Calls the enabled plugins on a thread of their own, one event at a time, so a slow plugin
+/// never holds up activation. Dropping it stops the thread.
+pub struct PluginHost {
+    tx: Sender<(PluginEvent, SystemContext)>,
+}
+
+impl PluginHost {
+    /// Host for the plugins enabled in `config`, in `data_dir`'s plugins folder (None when no
+    /// plugin is enabled, or in safe mode)
+    pub fn start(data_dir: &Path, config: &PluginConfig, requests: &RequestSender) -> Option<Self> {
+        if config.enabled.is_empty() || crate::config::is_safe_mode() {
+            return None;
+        }
+        Some(Self::spawn(data_dir.join(PLUGINS_FOLDER), config.clone(), requests.clone()))
+    }
+
+    /// Load the plugins enabled in `config` from `folder`; their commands go to `requests`
+    pub fn spawn(folder: PathBuf, config: PluginConfig, requests: RequestSender) -> Self {
+        let (tx, rx) = channel::<(PluginEvent, SystemContext)>();
+        std::thread::spawn(move || {
+            // The script engines aren't Send, so they're made here
+            let mut plugins = load_plugins(&folder, &config);
+            info!("[Plugins] {} plugin(s) loaded", plugins.len());
+
+            // Events a plugin's own commands caused skip that plugin, so a hook that activates a
+            // profile doesn't run again for that activation
+            let mut pending: VecDeque<(PluginEvent, SystemContext, HashSet<usize>)> = VecDeque::new();
+            loop {
+                let (event, system, skip) = match pending.pop_front() {
+                    Some(queued) => queued,
+                    None => match rx.recv() {
+                        Ok((event, system)) => (event, system, HashSet::new()),
+                        Err(_) => return,
+                    },
+                };
+
+                let mut sent = skip.clone();
+                for (index, plugin) in plugins.iter_mut().enumerate().filter(|(index, _)| !skip.contains(index)) {
+                    let commands = match plugin.run(&event, &system) {
+                        Ok(commands) => commands,
+                        Err(e) => {
+                            warn!("[Plugins] {}", e);
+                            continue;
+                        }
+                    };
+                    for command in commands {
+                        sent.insert(index);
+                        let reply = requests.request(command.clone());
+                        if !reply.ok {
+                            warn!("[Plugins] {}: {:?}: {}", plugin.file(), command, reply.message);
+                        }
+                    }
+                }
+
+                // Commands are answered after the events they cause are published
+                if sent.len() > skip.len() {
+                    pending.extend(rx.try_iter().map(|(event, system)| (event, system, sent.clone())));
+                }
+            }
+        });
+        PluginHost { tx }
+    }
+
+    pub fn publish(&self, event: PluginEvent, system: SystemContext) {
+        let _ = self.tx.send((event, system));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::AlertSensor;
+
+    #[test]
+    fn test_context() {
+        let system = SystemContext {
+            active_profile: Some("Valorant".to_string()),
+            hardware: Some(HardwareSnapshot { gpu_temp: Some(91.0), ..Default::default() }),
+        };
+        let alert = PluginEvent::TemperatureAlert(TemperatureAlert { sensor: AlertSensor::Gpu, temp_c: 91.0, limit_c: 87.0 });
+        assert_eq!(
+            context(&alert, &system),
+            json!({
+                "event": "temperature_alert",
+                "sensor": "GPU",
+                "temp": 91.0,
+                "limit": 87.0,
+                "system": {
+                    "active_profile": "Valorant",
+                    "cpu_load": null,
+                    "cpu_temp": null,
+                    "gpu_load": null,
+                    "gpu_temp": 91.0,
+                    "ram": null,
+                },
+            })
+        );
+        let started = PluginEvent::GameStarted { game: "Valorant".to_string(), exe: "VALORANT.exe".to_string(), profile: None };
+        assert_eq!(context(&started, &SystemContext::default())["profile"], Value::Null);
+    }
+
+    #[test]
+    fn test_plugin_config() {
+        let mut config = PluginConfig::default();
+        config.set_enabled("Cooling.rhai", true);
+        config.set_enabled("obs.exe", true);
+        assert!(config.is_enabled("cooling.rhai"));
+        assert_eq!(config.enabled, ["Cooling.rhai", "obs.exe"]);
+        config.set_enabled("COOLING.rhai", false);
+        assert_eq!(config.enabled, ["obs.exe"]);
+
+        let dir = std::env::temp_dir().join(format!("go_plugins_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("b.rhai"), "fn on_activate(ctx) { activate(ctx.profile) }").unwrap();
+        fs::write(dir.join("broken.rhai"), "fn on_activate(ctx) {").unwrap();
+        fs::write(dir.join("A.RHAI"), "").unwrap();
+        fs::write(dir.join("home_assistant.cmd"), "").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        assert_eq!(list_plugins(&dir), ["A.RHAI", "b.rhai", "broken.rhai", "home_assistant.cmd"]);
+
+        let enabled = vec!["b.rhai".to_string(), "broken.rhai".to_string(), "home_assistant.cmd".to_string()];
+        let loaded = load_plugins(&dir, &PluginConfig { enabled });
+        assert_eq!(loaded.iter().map(Plugin::file).collect::<Vec<_>>(), ["b.rhai", "home_assistant.cmd"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
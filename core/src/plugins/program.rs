@@ -0,0 +1,204 @@
+//! Program plugins
+//!
+//! Programs in the plugins folder (`.exe`, `.bat`, `.cmd`) are started once per event with the
+//! event as one JSON line on stdin, e.g. `{"event":"activated","profile":"Valorant",...}`, so an
+//! integration can be written in any language. A program may answer with one JSON line on
+//! stdout, `{"ok":true,"message":"...","commands":[...]}`, with commands in the WebSocket API's
+//! format (`{"Activate":{"profile":"Quiet"}}`, `"Deactivate"`). A program that doesn't exit in
+//! time is killed, and one that fails several times in a row is left out until the plugins are
+//! reloaded, so a broken integration can't hold up every event.
+
+use super::{context, PluginEvent, SystemContext};
+use crate::cli::CliCommand;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Extensions of program plugins
+pub const EXTENSIONS: [&str; 3] = ["exe", "bat", "cmd"];
+
+/// How long a program gets per event
+const TIMEOUT: Duration = Duration::from_secs(5);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Failures in a row after which a program is left out
+const MAX_FAILURES: u32 = 3;
+/// Commands one answer can send
+const MAX_COMMANDS: usize = 8;
+
+/// What a program answered
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct Reply {
+    pub ok: bool,
+    /// Logged, or the error when `ok` is false
+    pub message: String,
+    pub commands: Vec<CliCommand>,
+}
+
+impl Default for Reply {
+    fn default() -> Self {
+        Reply { ok: true, message: String::new(), commands: Vec::new() }
+    }
+}
+
+/// The last JSON line of a program's output (no JSON line = done, nothing to say)
+pub fn parse_reply(output: &str) -> Result<Reply> {
+    match output.lines().map(str::trim).rfind(|line| line.starts_with('{')) {
+        Some(line) => serde_json::from_str(line).map_err(|e| anyhow!("Invalid answer {}: {}", line, e)),
+        None => Ok(Reply::default()),
+    }
+}
+
+/// Commands plugins may send (the same ones scripts have)
+fn allowed(command: &CliCommand) -> bool {
+    matches!(command, CliCommand::Activate { .. } | CliCommand::Deactivate | CliCommand::Overlay { .. })
+}
+
+/// A program in the plugins folder
+pub struct Program {
+    pub file: String,
+    path: PathBuf,
+    /// Failures in a row
+    failures: u32,
+}
+
+impl Program {
+    pub fn new(folder: &Path, file: &str) -> Self {
+        Program { file: file.to_string(), path: folder.join(file), failures: 0 }
+    }
+
+    /// Whether it failed too often and is left out until the plugins are reloaded
+    pub fn is_left_out(&self) -> bool {
+        self.failures >= MAX_FAILURES
+    }
+
+    /// Start the program for `event`; the commands it answered with
+    pub fn run(&mut self, event: &PluginEvent, system: &SystemContext) -> Result<Vec<CliCommand>> {
+        if self.is_left_out() {
+            return Ok(Vec::new());
+        }
+        let input = format!("{}\n", context(event, system));
+        let reply = run_program(&self.path, &input, TIMEOUT)
+            .and_then(|output| parse_reply(&output))
+            .and_then(|reply| if reply.ok { Ok(reply) } else { Err(anyhow!("{}", reply.message)) });
+
+        match reply {
+            Ok(reply) => {
+                self.failures = 0;
+                if !reply.message.is_empty() {
+                    info!("[Plugins] {}: {}", self.file, reply.message);
+                }
+                let (commands, refused): (Vec<_>, Vec<_>) = reply.commands.into_iter().partition(allowed);
+                for command in refused {
+                    warn!("[Plugins] {}: {:?} isn't available to plugins", self.file, command);
+                }
+                Ok(commands.into_iter().take(MAX_COMMANDS).collect())
+            }
+            Err(e) => {
+                self.failures += 1;
+                if self.is_left_out() {
+                    warn!("[Plugins] {} failed {} times in a row and is left out until the plugins are reloaded", self.file, MAX_FAILURES);
+                }
+                Err(anyhow!("{} failed on {}: {}", self.file, event.hook(), e))
+            }
+        }
+    }
+}
+
+/// Run `path` with `input` on stdin; its stdout, or why it failed
+fn run_program(path: &Path, input: &str, timeout: Duration) -> Result<String> {
+    let mut command = Command::new(path);
+    command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    if let Some(folder) = path.parent() {
+        command.current_dir(folder);
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = command.spawn().map_err(|e| anyhow!("Failed to start: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A program that doesn't read its input closes the pipe early, which is fine
+        let _ = stdin.write_all(input.as_bytes());
+    }
+
+    // Read while it runs, so a chatty program can't fill the pipe and stall
+    let read = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut text = String::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_string(&mut text);
+            }
+            text
+        })
+    };
+    let stdout = read(child.stdout.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>));
+    let stderr = read(child.stderr.take().map(|pipe| Box::new(pipe) as Box<dyn Read + Send>));
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| anyhow!("Failed to wait for it: {}", e))? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!("Didn't finish within {} s", timeout.as_secs()));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let output = stdout.join().unwrap_or_default();
+    if !status.success() {
+        let errors = stderr.join().unwrap_or_default();
+        return Err(anyhow!("Exited with {}: {}", status, errors.trim()));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::OverlayState;
+
+    #[test]
+    fn test_parse_reply() {
+        assert_eq!(parse_reply("").unwrap(), Reply::default());
+        assert_eq!(parse_reply("connecting to Home Assistant...\r\ndone\r\n").unwrap(), Reply::default());
+
+        let output = "starting\n{\"message\":\"scene set\",\"commands\":[{\"Overlay\":{\"state\":\"Off\"}},\"Deactivate\"]}\n";
+        assert_eq!(
+            parse_reply(output).unwrap(),
+            Reply {
+                ok: true,
+                message: "scene set".to_string(),
+                commands: vec![CliCommand::Overlay { state: OverlayState::Off }, CliCommand::Deactivate],
+            }
+        );
+        assert!(!parse_reply("{\"ok\":false,\"message\":\"OBS isn't running\"}").unwrap().ok);
+        assert!(parse_reply("{\"commands\":[\"Reboot\"]}").is_err());
+    }
+
+    #[test]
+    fn test_failing_program_is_left_out() {
+        let folder = std::env::temp_dir().join(format!("go_program_plugins_{}", std::process::id()));
+        let mut program = Program::new(&folder, "missing.exe");
+        let event = PluginEvent::Deactivated { profile: "Valorant".to_string() };
+        for _ in 0..MAX_FAILURES {
+            assert!(program.run(&event, &SystemContext::default()).is_err());
+        }
+        assert!(program.is_left_out());
+        assert!(program.run(&event, &SystemContext::default()).unwrap().is_empty());
+        assert!(allowed(&CliCommand::Activate { profile: "Quiet".to_string() }));
+        assert!(!allowed(&CliCommand::Quit));
+    }
+}
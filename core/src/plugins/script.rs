@@ -0,0 +1,159 @@
+//! Rhai plugin scripts
+//!
+//! Each script gets an engine of its own with no module loading and no `eval`, and limits on
+//! operations, call depth and value sizes, so a runaway hook is stopped instead of hanging the
+//! plugin thread. Hooks queue commands rather than sending them; the host sends them once the
+//! hook returns.
+
+use super::{context, PluginEvent, SystemContext};
+use crate::cli::{CliCommand, OverlayState};
+use anyhow::{anyhow, Result};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{CallFnOptions, Dynamic, Engine, Scope, AST};
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::info;
+
+/// Extension of script plugins
+pub const EXTENSION: &str = "rhai";
+
+// Sandbox limits, per hook call
+const MAX_OPERATIONS: u64 = 1_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_STRING_SIZE: usize = 64 * 1024;
+const MAX_COLLECTION_SIZE: usize = 10_000;
+/// Commands one hook call can send
+const MAX_COMMANDS: usize = 8;
+
+/// A compiled script with its own sandboxed engine
+pub struct Script {
+    pub file: String,
+    engine: Engine,
+    ast: AST,
+    /// Commands the running hook asked for
+    commands: Rc<RefCell<Vec<CliCommand>>>,
+}
+
+impl Script {
+    /// Compile `source`; `file` names the script in the log
+    pub fn load(file: &str, source: &str) -> Result<Self> {
+        let commands: Rc<RefCell<Vec<CliCommand>>> = Rc::default();
+        let mut engine = Engine::new();
+        engine
+            .set_module_resolver(DummyModuleResolver::new())
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_string_size(MAX_STRING_SIZE)
+            .set_max_array_size(MAX_COLLECTION_SIZE)
+            .set_max_map_size(MAX_COLLECTION_SIZE)
+            .disable_symbol("eval");
+
+        let name = file.to_string();
+        engine.on_print(move |message| info!("[Plugins] {}: {}", name, message));
+        let name = file.to_string();
+        engine.on_debug(move |message, _, position| info!("[Plugins] {} ({}): {}", name, position, message));
+        let name = file.to_string();
+        engine.register_fn("log", move |message: &str| info!("[Plugins] {}: {}", name, message));
+
+        let queue = |commands: &Rc<RefCell<Vec<CliCommand>>>| {
+            let commands = Rc::clone(commands);
+            move |command: CliCommand| {
+                let mut commands = commands.borrow_mut();
+                if commands.len() < MAX_COMMANDS {
+                    commands.push(command);
+                }
+            }
+        };
+        let push = queue(&commands);
+        engine.register_fn("activate", move |profile: &str| push(CliCommand::Activate { profile: profile.to_string() }));
+        let push = queue(&commands);
+        engine.register_fn("deactivate", move || push(CliCommand::Deactivate));
+        let push = queue(&commands);
+        engine.register_fn("overlay", move |on: bool| {
+            push(CliCommand::Overlay { state: if on { OverlayState::On } else { OverlayState::Off } })
+        });
+
+        let ast = engine.compile(source).map_err(|e| anyhow!("Failed to compile {}: {}", file, e))?;
+        Ok(Script { file: file.to_string(), engine, ast, commands })
+    }
+
+    /// Whether the script defines `hook` (taking `ctx`)
+    pub fn has_hook(&self, hook: &str) -> bool {
+        self.ast.iter_functions().any(|function| function.name == hook && function.params.len() == 1)
+    }
+
+    /// Run the event's hook if the script has one; the commands it asked for
+    pub fn run(&self, event: &PluginEvent, system: &SystemContext) -> Result<Vec<CliCommand>> {
+        let hook = event.hook();
+        if !self.has_hook(hook) {
+            return Ok(Vec::new());
+        }
+        let ctx = rhai::serde::to_dynamic(context(event, system)).map_err(|e| anyhow!("Failed to pass {} its context: {}", self.file, e))?;
+        self.commands.borrow_mut().clear();
+        // Only the hook runs, not the script's top-level statements; what it returns is ignored
+        let options = CallFnOptions::new().eval_ast(false);
+        let _ = self
+            .engine
+            .call_fn_with_options::<Dynamic>(options, &mut Scope::new(), &self.ast, hook, (ctx,))
+            .map_err(|e| anyhow!("{} failed in {}: {}", hook, self.file, e))?;
+        Ok(self.commands.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::{AlertSensor, TemperatureAlert};
+    use crate::monitor::HardwareSnapshot;
+
+    fn activated() -> PluginEvent {
+        PluginEvent::Activated { profile: "Valorant".to_string(), applied: 5, failed: 1, rolled_back: false }
+    }
+
+    #[test]
+    fn test_hooks_queue_commands() {
+        let script = r#"
+            fn on_activate(ctx) {
+                if ctx.failed > 0 && ctx.system.cpu_temp > 80.0 {
+                    overlay(false);
+                    activate("Quiet");
+                }
+            }
+            fn on_temp_alert(ctx) { log(`${ctx.sensor} at ${ctx.temp}`); deactivate(); }
+        "#;
+        let script = Script::load("cooling.rhai", script).unwrap();
+        assert!(script.has_hook("on_activate"));
+        assert!(!script.has_hook("on_game_start"));
+
+        let hot = SystemContext {
+            active_profile: Some("Valorant".to_string()),
+            hardware: Some(HardwareSnapshot { cpu_temp: Some(85.0), ..Default::default() }),
+        };
+        assert_eq!(
+            script.run(&activated(), &hot).unwrap(),
+            [CliCommand::Overlay { state: OverlayState::Off }, CliCommand::Activate { profile: "Quiet".to_string() }]
+        );
+        // Missing readings are (), which never compares above a limit
+        assert!(script.run(&activated(), &SystemContext::default()).unwrap().is_empty());
+
+        let alert = PluginEvent::TemperatureAlert(TemperatureAlert { sensor: AlertSensor::Gpu, temp_c: 91.0, limit_c: 87.0 });
+        assert_eq!(script.run(&alert, &SystemContext::default()).unwrap(), [CliCommand::Deactivate]);
+        // No hook for this one
+        let started = PluginEvent::GameStarted { game: "Valorant".to_string(), exe: "VALORANT.exe".to_string(), profile: None };
+        assert!(script.run(&started, &SystemContext::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sandbox() {
+        let runaway = Script::load("loop.rhai", "fn on_deactivate(ctx) { loop { activate(ctx.profile); } }").unwrap();
+        let event = PluginEvent::Deactivated { profile: "Valorant".to_string() };
+        assert!(runaway.run(&event, &SystemContext::default()).is_err());
+
+        assert!(Script::load("import.rhai", r#"import "other" as other; fn on_deactivate(ctx) { other::run(); }"#)
+            .unwrap()
+            .run(&event, &SystemContext::default())
+            .is_err());
+        assert!(Script::load("eval.rhai", r#"fn on_deactivate(ctx) { eval("1") }"#).is_err());
+        assert!(Script::load("broken.rhai", "fn on_activate(ctx) {").is_err());
+    }
+}
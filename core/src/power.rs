@@ -0,0 +1,247 @@
+//! Windows power plan switching
+//!
+//! Lists the installed power schemes and switches the active one while a profile is
+//! active, restoring the user's previous plan afterwards.
+
+use anyhow::{anyhow, Result};
+use std::fmt;
+use tracing::info;
+
+/// An installed power scheme
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PowerPlan {
+    /// Lowercase hyphenated scheme GUID (as stored in profiles)
+    pub guid: String,
+    pub name: String,
+}
+
+impl fmt::Display for PowerPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Parse a `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` GUID (braces optional) into its 128-bit value
+pub fn parse_guid(text: &str) -> Option<u128> {
+    let text = text.trim().trim_start_matches('{').trim_end_matches('}');
+    let groups: Vec<&str> = text.split('-').collect();
+    let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+    if lengths != [8, 4, 4, 4, 12] || !text.chars().all(|c| c == '-' || c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u128::from_str_radix(&groups.concat(), 16).ok()
+}
+
+/// Format a 128-bit GUID value as lowercase hyphenated text
+pub fn format_guid(value: u128) -> String {
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        value >> 96,
+        (value >> 80) & 0xFFFF,
+        (value >> 64) & 0xFFFF,
+        (value >> 48) & 0xFFFF,
+        value & 0xFFFF_FFFF_FFFF
+    )
+}
+
+/// Friendly name of a scheme (falls back to its GUID)
+#[cfg(windows)]
+unsafe fn friendly_name(guid: &windows::core::GUID) -> String {
+    use windows::Win32::System::Power::PowerReadFriendlyName;
+    use windows::Win32::System::Registry::HKEY;
+
+    let mut size: u32 = 0;
+    if PowerReadFriendlyName(HKEY::default(), Some(guid), None, None, None, &mut size).is_err() || size == 0 {
+        return format_guid(guid.to_u128());
+    }
+
+    // Size is in bytes of a NUL-terminated UTF-16 string
+    let mut buffer = vec![0u16; (size as usize).div_ceil(2)];
+    if PowerReadFriendlyName(
+        HKEY::default(),
+        Some(guid),
+        None,
+        None,
+        Some(buffer.as_mut_ptr() as *mut u8),
+        &mut size,
+    )
+    .is_err()
+    {
+        return format_guid(guid.to_u128());
+    }
+
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
+
+/// List installed power plans
+pub fn list_power_plans() -> Result<Vec<PowerPlan>> {
+    #[cfg(windows)]
+    {
+        use windows::core::GUID;
+        use windows::Win32::System::Power::{PowerEnumerate, ACCESS_SCHEME};
+        use windows::Win32::System::Registry::HKEY;
+
+        let mut plans = Vec::new();
+        unsafe {
+            for index in 0.. {
+                let mut guid = GUID::zeroed();
+                let mut size = std::mem::size_of::<GUID>() as u32;
+                let result = PowerEnumerate(
+                    HKEY::default(),
+                    None,
+                    None,
+                    ACCESS_SCHEME,
+                    index,
+                    Some(&mut guid as *mut GUID as *mut u8),
+                    &mut size,
+                );
+                // Enumeration ends with ERROR_NO_MORE_ITEMS
+                if result.is_err() {
+                    break;
+                }
+                plans.push(PowerPlan {
+                    guid: format_guid(guid.to_u128()),
+                    name: friendly_name(&guid),
+                });
+            }
+        }
+
+        if plans.is_empty() {
+            return Err(anyhow!("No power plans found"));
+        }
+        Ok(plans)
+    }
+
+    #[cfg(not(windows))]
+    Err(anyhow!("Power plans are only available on Windows"))
+}
+
+/// GUID of the currently active power plan
+pub fn active_power_plan() -> Result<String> {
+    #[cfg(windows)]
+    unsafe {
+        use windows::core::GUID;
+        use windows::Win32::Foundation::{LocalFree, HLOCAL};
+        use windows::Win32::System::Power::PowerGetActiveScheme;
+        use windows::Win32::System::Registry::HKEY;
+
+        let mut active: *mut GUID = std::ptr::null_mut();
+        PowerGetActiveScheme(HKEY::default(), &mut active)
+            .map_err(|e| anyhow!("Failed to read active power plan: {}", e))?;
+        if active.is_null() {
+            return Err(anyhow!("Failed to read active power plan"));
+        }
+
+        // The scheme GUID is allocated by powrprof and must be released with LocalFree
+        let guid = format_guid((*active).to_u128());
+        let _ = LocalFree(HLOCAL(active as *mut _));
+        Ok(guid)
+    }
+
+    #[cfg(not(windows))]
+    Err(anyhow!("Power plans are only available on Windows"))
+}
+
+/// Make the plan with the given GUID active (PowerSetActiveScheme)
+pub fn set_active_power_plan(guid: &str) -> Result<()> {
+    let value = parse_guid(guid).ok_or_else(|| anyhow!("Invalid power plan GUID: {}", guid))?;
+
+    #[cfg(windows)]
+    {
+        use windows::core::GUID;
+        use windows::Win32::System::Power::PowerSetActiveScheme;
+        use windows::Win32::System::Registry::HKEY;
+
+        let scheme = GUID::from_u128(value);
+        unsafe { PowerSetActiveScheme(HKEY::default(), Some(&scheme)) }
+            .map_err(|e| anyhow!("Failed to switch power plan: {}", e))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = value;
+        Err(anyhow!("Power plans are only available on Windows"))
+    }
+}
+
+/// Name of an installed plan, falling back to the GUID for plans that are gone
+pub fn plan_name(plans: &[PowerPlan], guid: &str) -> String {
+    plans
+        .iter()
+        .find(|plan| plan.guid.eq_ignore_ascii_case(guid))
+        .map(|plan| plan.name.clone())
+        .unwrap_or_else(|| guid.to_string())
+}
+
+/// Reading and setting the active plan; activation goes through this so tests can use a fake
+pub trait PowerBackend: Send {
+    fn active_plan(&mut self) -> Result<String>;
+    fn set_active_plan(&mut self, guid: &str) -> Result<()>;
+}
+
+/// This machine's power schemes
+#[derive(Default)]
+pub struct LivePowerPlans;
+
+impl PowerBackend for LivePowerPlans {
+    fn active_plan(&mut self) -> Result<String> {
+        active_power_plan()
+    }
+
+    fn set_active_plan(&mut self, guid: &str) -> Result<()> {
+        set_active_power_plan(guid)
+    }
+}
+
+/// Switches to a profile's power plan and remembers the one it replaced
+#[derive(Default)]
+pub struct PowerPlanSwitcher {
+    /// Plan that was active before the first switch (None = nothing to restore)
+    previous: Option<String>,
+}
+
+impl PowerPlanSwitcher {
+    /// Activate the given plan, keeping the original plan for `restore`
+    pub fn apply(&mut self, backend: &mut dyn PowerBackend, guid: &str) -> Result<()> {
+        if self.previous.is_none() {
+            self.previous = Some(backend.active_plan()?);
+        }
+        backend.set_active_plan(guid)?;
+        info!("[Power] Switched to power plan {}", guid);
+        Ok(())
+    }
+
+    /// Switch back to the plan that was active before `apply` (no-op if nothing was applied)
+    pub fn restore(&mut self, backend: &mut dyn PowerBackend) -> Result<()> {
+        let Some(previous) = self.previous.take() else {
+            return Ok(());
+        };
+        backend.set_active_plan(&previous)?;
+        info!("[Power] Restored power plan {}", previous);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guid_roundtrip() {
+        // High performance plan
+        let guid = "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c";
+        let value = parse_guid(guid).unwrap();
+        assert_eq!(value, 0x8c5e7fda_e8bf_4a96_9a85_a6e23a8c635c);
+        assert_eq!(format_guid(value), guid);
+        assert_eq!(parse_guid("{8C5E7FDA-E8BF-4A96-9A85-A6E23A8C635C}"), Some(value));
+    }
+
+    #[test]
+    fn test_parse_guid_rejects_invalid() {
+        assert_eq!(parse_guid(""), None);
+        assert_eq!(parse_guid("8c5e7fda-e8bf-4a96-9a85"), None);
+        assert_eq!(parse_guid("8c5e7fda-e8bf-4a96-9a85-a6e23a8c635z"), None);
+        assert_eq!(parse_guid("8c5e7fdae8bf4a969a85a6e23a8c635c"), None);
+    }
+}
@@ -0,0 +1,289 @@
+//! Activation preflight
+//!
+//! Checks that a profile still fits this machine before it is applied: its crosshair image
+//! exists, the monitor its display mode is for is connected, the app has the rights its
+//! admin-only features need, and the game it's named after is installed (so launching it can
+//! trigger the profile). A profile saved before a hardware or install change then asks first
+//! instead of being half applied.
+
+use crate::elevation::{self, AdminFeature};
+use crate::game_knowledge::GameEntry;
+use crate::i18n::t;
+use crate::profile::Profile;
+use anyhow::Result;
+use once_cell::sync::OnceCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Folder levels searched below an install folder for a game executable
+/// (e.g. `game\bin\win64\cs2.exe`)
+const EXE_SEARCH_DEPTH: usize = 4;
+
+/// Something about this machine that gets in the way of a profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Crosshair image that doesn't exist (the crosshair step would fail)
+    MissingCrosshair(String),
+    /// Display mode for a monitor that isn't connected (the display step would fail)
+    MonitorDisconnected(String),
+    /// Admin-only features skipped because the app isn't running as administrator
+    NeedsAdmin(Vec<AdminFeature>),
+    /// The game the profile is named after isn't installed, so its launch never triggers it
+    GameNotInstalled(String),
+}
+
+impl Problem {
+    /// Whether activating would fail a step, so the user is asked first
+    pub fn blocks_activation(&self) -> bool {
+        matches!(self, Problem::MissingCrosshair(_) | Problem::MonitorDisconnected(_))
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Problem::MissingCrosshair(path) => t!("preflight-missing-crosshair", path = path),
+            Problem::MonitorDisconnected(device) => t!("preflight-monitor-disconnected", monitor = device),
+            Problem::NeedsAdmin(features) => {
+                let features: Vec<String> = features.iter().map(ToString::to_string).collect();
+                t!("preflight-needs-admin", features = features.join(", "))
+            }
+            Problem::GameNotInstalled(game) => t!("preflight-game-not-installed", game = game),
+        }
+    }
+}
+
+/// Outcome of `check`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadinessReport {
+    pub problems: Vec<Problem>,
+}
+
+impl ReadinessReport {
+    pub fn is_ready(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Problems that would fail a step
+    pub fn blocking(&self) -> impl Iterator<Item = &Problem> {
+        self.problems.iter().filter(|problem| problem.blocks_activation())
+    }
+}
+
+/// A program from Apps & features
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledProgram {
+    pub name: String,
+    pub folder: PathBuf,
+}
+
+/// What `check` compares a profile against
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+    /// Device names of the connected monitors (None when they couldn't be listed)
+    pub monitors: Option<Vec<String>>,
+    pub elevated: bool,
+    /// Installed programs (None when they couldn't be listed)
+    pub programs: Option<Vec<InstalledProgram>>,
+}
+
+impl Environment {
+    /// This machine, as far as `profile` needs it (monitors and programs are only listed when
+    /// the profile has a display mode or is named after a game in `games`)
+    pub fn detect(profile: &Profile, games: &[GameEntry]) -> Self {
+        let monitors = profile.display_mode.as_ref().and_then(|_| match crate::display::list_monitors() {
+            Ok(monitors) => Some(monitors.into_iter().map(|monitor| monitor.device).collect()),
+            Err(e) => {
+                warn!("[Preflight] {}", e);
+                None
+            }
+        });
+        let programs = game_for(profile, games).and_then(|_| installed_programs().cloned());
+        Environment { monitors, elevated: elevation::capabilities().elevated, programs }
+    }
+}
+
+/// The game `profile` is named after, if it has executables that could trigger it
+fn game_for<'a>(profile: &Profile, games: &'a [GameEntry]) -> Option<&'a GameEntry> {
+    games.iter().find(|game| !game.executables.is_empty() && game.matches_name(&profile.name))
+}
+
+/// What in `environment` gets in the way of `profile`; `games` are the known games
+pub fn check(profile: &Profile, games: &[GameEntry], environment: &Environment) -> ReadinessReport {
+    let mut report = ReadinessReport::default();
+
+    // Same conditions as the crosshair step
+    if profile.overlay_enabled && !crate::config::is_safe_mode() {
+        if let Some(ref path) = profile.crosshair_image_path {
+            if !Path::new(path).exists() {
+                report.problems.push(Problem::MissingCrosshair(path.clone()));
+            }
+        }
+    }
+
+    if let (Some(setting), Some(monitors)) = (&profile.display_mode, &environment.monitors) {
+        if !monitors.iter().any(|device| device.eq_ignore_ascii_case(&setting.device)) {
+            report.problems.push(Problem::MonitorDisconnected(setting.device.clone()));
+        }
+    }
+
+    if !environment.elevated {
+        let features = AdminFeature::required_by(profile);
+        if !features.is_empty() {
+            report.problems.push(Problem::NeedsAdmin(features));
+        }
+    }
+
+    if let (Some(game), Some(programs)) = (game_for(profile, games), &environment.programs) {
+        if !game_installed(game, programs) {
+            report.problems.push(Problem::GameNotInstalled(game.name.clone()));
+        }
+    }
+
+    report
+}
+
+/// Whether one of `game`'s executables is in the folder of an installed program named after it
+fn game_installed(game: &GameEntry, programs: &[InstalledProgram]) -> bool {
+    programs
+        .iter()
+        .filter(|program| game.matches_name(&program.name))
+        .any(|program| game.executables.iter().any(|exe| contains_file(&program.folder, exe, EXE_SEARCH_DEPTH)))
+}
+
+/// Whether `name` (any case) is in `folder` or up to `depth` levels below it
+fn contains_file(folder: &Path, name: &str, depth: usize) -> bool {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return false;
+    };
+    let mut subfolders = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            subfolders.push(path);
+        } else if entry.file_name().to_string_lossy().eq_ignore_ascii_case(name) {
+            return true;
+        }
+    }
+    depth > 0 && subfolders.iter().any(|subfolder| contains_file(subfolder, name, depth - 1))
+}
+
+/// Installed programs, listed once per run (Steam, Epic and most other launchers register
+/// each game in Apps & features with its install folder)
+fn installed_programs() -> Option<&'static Vec<InstalledProgram>> {
+    static PROGRAMS: OnceCell<Vec<InstalledProgram>> = OnceCell::new();
+    PROGRAMS
+        .get_or_try_init(list_programs)
+        .map_err(|e| warn!("[Preflight] Failed to list installed programs: {}", e))
+        .ok()
+}
+
+fn list_programs() -> Result<Vec<InstalledProgram>> {
+    let output = crate::shell::run_powershell(
+        "Get-ItemProperty 'HKLM:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\*', \
+         'HKLM:\\SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\*', \
+         'HKCU:\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\*' -ErrorAction SilentlyContinue \
+         | Where-Object { $_.DisplayName -and $_.InstallLocation } \
+         | ForEach-Object { $_.DisplayName + \"`t\" + $_.InstallLocation }",
+    )?;
+    Ok(parse_programs(&output))
+}
+
+/// "Name<TAB>folder" lines
+fn parse_programs(output: &str) -> Vec<InstalledProgram> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(name, folder)| (name.trim(), folder.trim().trim_matches('"')))
+        .filter(|(name, folder)| !name.is_empty() && !folder.is_empty())
+        .map(|(name, folder)| InstalledProgram { name: name.to_string(), folder: PathBuf::from(folder) })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::{DisplayMode, DisplaySetting};
+    use crate::profile::create_profile;
+    use crate::storage::StorageSettings;
+
+    fn game(name: &str, exe: &str) -> GameEntry {
+        GameEntry {
+            name: name.to_string(),
+            aliases: Vec::new(),
+            executables: vec![exe.to_string()],
+            kill: Vec::new(),
+            power_plan: None,
+            high_precision_timer: false,
+            conflicting_overlays: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_profile() {
+        let mut profile = create_profile("Valorant ranked".to_string());
+        profile.crosshair_image_path = Some("C:\\no\\such\\crosshair.png".to_string());
+        profile.display_mode = Some(DisplaySetting {
+            device: "\\\\.\\DISPLAY2".to_string(),
+            mode: DisplayMode { width: 1920, height: 1080, refresh_hz: 240 },
+        });
+        profile.storage = StorageSettings { pause_search_indexing: true, pause_maintenance_tasks: false };
+        let games = [game("Valorant", "VALORANT.exe")];
+        let environment = Environment {
+            monitors: Some(vec!["\\\\.\\DISPLAY1".to_string()]),
+            elevated: false,
+            programs: Some(Vec::new()),
+        };
+
+        let report = check(&profile, &games, &environment);
+        assert_eq!(
+            report.problems,
+            [
+                Problem::MissingCrosshair("C:\\no\\such\\crosshair.png".to_string()),
+                Problem::MonitorDisconnected("\\\\.\\DISPLAY2".to_string()),
+                Problem::NeedsAdmin(vec![AdminFeature::ServiceControl]),
+                Problem::GameNotInstalled("Valorant".to_string()),
+            ]
+        );
+        assert_eq!(report.blocking().count(), 2);
+
+        // Nothing is checked that couldn't be looked up, or that the profile doesn't use
+        profile.overlay_enabled = false;
+        let environment = Environment { monitors: None, elevated: true, programs: None };
+        assert!(check(&profile, &games, &environment).is_ready());
+    }
+
+    #[test]
+    fn test_game_installed() {
+        let dir = std::env::temp_dir().join(format!("go_preflight_{}", std::process::id()));
+        let bin = dir.join("Counter-Strike Global Offensive").join("game").join("bin").join("win64");
+        fs::create_dir_all(&bin).unwrap();
+        fs::write(bin.join("cs2.exe"), b"").unwrap();
+        let cs2 = game("Counter-Strike 2", "cs2.exe");
+        let program = |name: &str| InstalledProgram { name: name.to_string(), folder: dir.join("Counter-Strike Global Offensive") };
+
+        assert!(game_installed(&cs2, &[program("Counter-Strike 2")]));
+        // Only folders of programs named after the game are searched
+        assert!(!game_installed(&cs2, &[program("Notepad++")]));
+        assert!(!game_installed(&game("Counter-Strike 2", "csgo.exe"), &[program("Counter-Strike 2")]));
+        assert!(!contains_file(&dir, "cs2.exe", 3));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_programs() {
+        let output = "Counter-Strike 2\tC:\\Steam\\steamapps\\common\\Counter-Strike Global Offensive\r\n\
+                      Broken entry\n\
+                      Notepad++\t\"C:\\Program Files\\Notepad++\"\n";
+        assert_eq!(
+            parse_programs(output),
+            [
+                InstalledProgram {
+                    name: "Counter-Strike 2".to_string(),
+                    folder: PathBuf::from("C:\\Steam\\steamapps\\common\\Counter-Strike Global Offensive"),
+                },
+                InstalledProgram { name: "Notepad++".to_string(), folder: PathBuf::from("C:\\Program Files\\Notepad++") },
+            ]
+        );
+    }
+}
@@ -0,0 +1,1016 @@
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, System, UpdateKind};
+use tracing::warn;
+
+/// Information about a running process
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    pub memory_kb: u64,
+    pub cpu_percent: f32,
+    /// Executable path (None if access is denied, e.g. for system processes)
+    pub exe: Option<PathBuf>,
+}
+
+/// A running app that activating a profile would close
+#[derive(Debug, Clone)]
+pub struct KillCandidate {
+    /// Name as listed in the profile
+    pub name: String,
+    /// Number of running instances
+    pub instances: usize,
+    /// Memory used by all instances
+    pub memory_kb: u64,
+    /// Executable of the first instance, for its icon
+    pub exe: Option<PathBuf>,
+}
+
+/// Icon pixels of an executable
+pub struct ProcessIcon {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// Report of process killing operation
+#[derive(Debug, Clone)]
+pub struct KillReport {
+    pub killed: Vec<String>,
+    pub failed: Vec<String>,
+    pub not_found: Vec<String>,
+    pub blocklist_skipped: Vec<String>,
+    /// Terminated but still running when the wait ran out (hung in shutdown)
+    pub timed_out: Vec<String>,
+    /// Not tried: the pass ran out of time before their turn
+    pub not_started: Vec<String>,
+    /// Executables of the closed processes, started again when the profile is deactivated
+    pub closed_exes: Vec<PathBuf>,
+}
+
+impl KillReport {
+    fn new() -> Self {
+        KillReport {
+            killed: Vec::new(),
+            failed: Vec::new(),
+            not_found: Vec::new(),
+            blocklist_skipped: Vec::new(),
+            timed_out: Vec::new(),
+            not_started: Vec::new(),
+            closed_exes: Vec::new(),
+        }
+    }
+}
+
+/// Threads terminating processes at the same time
+const KILL_THREADS: usize = 8;
+
+/// How long one entry's processes get to exit after being terminated
+const KILL_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Poll interval while waiting for terminated processes to exit
+const EXIT_POLL: Duration = Duration::from_millis(50);
+
+/// Running instances of a kill list, kept between kill passes
+///
+/// Each pass only takes pids and names (no CPU, memory or exe details) and matches the names
+/// of processes that started since the last one, so repeated passes over the same targets stay
+/// cheap. A different target list starts the index over.
+pub struct ProcessIndex {
+    sys: System,
+    targets: Vec<String>,
+    /// Every process seen in the last pass: its name and the targets it matches
+    known: HashMap<Pid, (String, Vec<usize>)>,
+}
+
+impl ProcessIndex {
+    pub fn new() -> Self {
+        ProcessIndex { sys: System::new(), targets: Vec::new(), known: HashMap::new() }
+    }
+
+    /// Pids of the running instances of each target, in target order
+    pub fn matches(&mut self, targets: &[String]) -> Vec<Vec<Pid>> {
+        if self.targets != targets {
+            self.targets = targets.to_vec();
+            self.known.clear();
+        }
+        let processes = snapshot(&mut self.sys);
+        // A reused pid has a different name and gets matched again
+        self.known.retain(|pid, (name, _)| processes.get(pid) == Some(name));
+        for (pid, name) in processes {
+            self.known.entry(pid).or_insert_with(|| {
+                let matched = (0..targets.len()).filter(|&i| matches_target(&name, &targets[i])).collect();
+                (name, matched)
+            });
+        }
+
+        let mut matches = vec![Vec::new(); targets.len()];
+        for (pid, (_, matched)) in &self.known {
+            for &i in matched {
+                matches[i].push(*pid);
+            }
+        }
+        matches
+    }
+}
+
+impl Default for ProcessIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shared by every kill pass, so passes over the same list reuse it
+static KILL_INDEX: Lazy<Mutex<ProcessIndex>> = Lazy::new(|| Mutex::new(ProcessIndex::new()));
+
+/// How closing the instances of one kill list entry went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillOutcome {
+    /// Instances that exited (or were already gone), refused to be terminated, and were
+    /// terminated but still running when the wait ran out
+    Finished { closed: usize, failed: usize, timed_out: usize },
+    /// The pass ran out of time before the entry's turn came; nothing was terminated
+    NotStarted,
+}
+
+impl KillOutcome {
+    /// Every instance closed
+    pub fn closed(count: usize) -> Self {
+        KillOutcome::Finished { closed: count, failed: 0, timed_out: 0 }
+    }
+
+    fn record(self, report: &mut KillReport, name: &str) {
+        let KillOutcome::Finished { closed, failed, timed_out } = self else {
+            report.not_started.push(name.to_string());
+            return;
+        };
+        let name = if closed > 0 && failed + timed_out > 0 { format!("{} (partial)", name) } else { name.to_string() };
+        if closed > 0 || failed + timed_out == 0 {
+            report.killed.push(name.clone());
+        }
+        if failed > 0 {
+            report.failed.push(name.clone());
+        }
+        if timed_out > 0 {
+            report.timed_out.push(name);
+        }
+    }
+}
+
+/// Take process lists from Toolhelp snapshots; turned off if one fails
+static TOOLHELP: AtomicBool = AtomicBool::new(true);
+
+/// `fast` while `fast_enabled` is set; the first time it fails the flag is cleared and
+/// `fallback` is used from then on
+fn with_fallback<T>(fast_enabled: &AtomicBool, fast: impl FnOnce() -> Result<T>, fallback: impl FnOnce() -> T) -> T {
+    if fast_enabled.load(Ordering::Relaxed) {
+        match fast() {
+            Ok(value) => return value,
+            Err(e) => {
+                warn!("[Process] {}; using sysinfo", e);
+                fast_enabled.store(false, Ordering::Relaxed);
+            }
+        }
+    }
+    fallback()
+}
+
+/// Pid and name of every running process. A Toolhelp snapshot only copies those out of the
+/// kernel, where a full sysinfo refresh can take a few hundred ms on busy machines; sysinfo
+/// (names only) is the fallback.
+fn snapshot(sys: &mut System) -> HashMap<Pid, String> {
+    with_fallback(&TOOLHELP, toolhelp_snapshot, || {
+        sys.refresh_processes_specifics(ProcessRefreshKind::new());
+        sys.processes().iter().map(|(pid, process)| (*pid, process.name().to_string())).collect()
+    })
+}
+
+/// Name in a PROCESSENTRY32W's szExeFile (NUL-terminated unless it fills the array)
+fn exe_name(exe_file: &[u16]) -> String {
+    let len = exe_file.iter().position(|&c| c == 0).unwrap_or(exe_file.len());
+    String::from_utf16_lossy(&exe_file[..len])
+}
+
+/// CPU use since the last listing, in percent of one core (like sysinfo's)
+fn cpu_percent(cpu_time_100ns: u64, elapsed: Duration) -> f32 {
+    if elapsed.is_zero() {
+        return 0.0;
+    }
+    (cpu_time_100ns as f64 / 100_000.0 / elapsed.as_secs_f64()) as f32
+}
+
+/// Pids and names of the running processes from a Toolhelp snapshot
+#[cfg(windows)]
+fn toolhelp_snapshot() -> Result<HashMap<Pid, String>> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+    };
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)
+            .map_err(|e| anyhow!("Failed to snapshot processes: {}", e))?;
+        let mut entry = PROCESSENTRY32W { dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32, ..Default::default() };
+        let mut processes = HashMap::new();
+        let mut next = Process32FirstW(snapshot, &mut entry);
+        while next.is_ok() {
+            processes.insert(Pid::from_u32(entry.th32ProcessID), exe_name(&entry.szExeFile));
+            next = Process32NextW(snapshot, &mut entry);
+        }
+        let _ = CloseHandle(snapshot);
+        if processes.is_empty() {
+            return Err(anyhow!("Failed to read process snapshot: {:?}", next.err()));
+        }
+        Ok(processes)
+    }
+}
+
+#[cfg(not(windows))]
+fn toolhelp_snapshot() -> Result<HashMap<Pid, String>> {
+    let _ = exe_name;
+    Err(anyhow!("Process snapshots are only available on Windows"))
+}
+
+/// CPU time (100 ns units) of each process at the last Toolhelp listing
+#[cfg(windows)]
+static CPU_TIMES: Lazy<Mutex<HashMap<u32, (u64, Instant)>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Every running process from a Toolhelp snapshot, with memory, CPU and exe read from each one
+/// that lets us open it (system processes list with just their name)
+#[cfg(windows)]
+fn toolhelp_list() -> Result<Vec<ProcessInfo>> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::{CloseHandle, FILETIME};
+    use windows::Win32::System::ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use windows::Win32::System::Threading::{
+        GetProcessTimes, OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let processes = toolhelp_snapshot()?;
+    let now = Instant::now();
+    let mut cpu_times = CPU_TIMES.lock().map_err(|_| anyhow!("CPU times are poisoned"))?;
+    let mut seen = HashMap::new();
+    let list = processes
+        .into_iter()
+        .map(|(pid, name)| {
+            let pid = pid.as_u32();
+            let mut info = ProcessInfo { pid, name, memory_kb: 0, cpu_percent: 0.0, exe: None };
+            let Ok(process) = (unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }) else {
+                return info;
+            };
+            unsafe {
+                let mut buffer = [0u16; 1024];
+                let mut len = buffer.len() as u32;
+                if QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut len).is_ok() {
+                    info.exe = Some(PathBuf::from(String::from_utf16_lossy(&buffer[..len as usize])));
+                }
+
+                let mut counters = PROCESS_MEMORY_COUNTERS::default();
+                if K32GetProcessMemoryInfo(process, &mut counters, std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32).as_bool() {
+                    info.memory_kb = counters.WorkingSetSize as u64 / 1024;
+                }
+
+                let (mut created, mut exited, mut kernel, mut user) = Default::default();
+                if GetProcessTimes(process, &mut created, &mut exited, &mut kernel, &mut user).is_ok() {
+                    let ticks = |time: FILETIME| (u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime);
+                    let cpu_time = ticks(kernel) + ticks(user);
+                    if let Some(&(last_time, last_seen)) = cpu_times.get(&pid) {
+                        info.cpu_percent = cpu_percent(cpu_time.saturating_sub(last_time), now - last_seen);
+                    }
+                    seen.insert(pid, (cpu_time, now));
+                }
+                let _ = CloseHandle(process);
+            }
+            info
+        })
+        .collect();
+    *cpu_times = seen;
+    Ok(list)
+}
+
+#[cfg(not(windows))]
+fn toolhelp_list() -> Result<Vec<ProcessInfo>> {
+    let _ = cpu_percent;
+    toolhelp_snapshot().map(|_| Vec::new())
+}
+
+/// Every running process through sysinfo (the fallback when snapshots fail)
+fn sysinfo_list() -> Vec<ProcessInfo> {
+    let mut sys = System::new_all();
+    sys.refresh_all();
+    sys.processes()
+        .iter()
+        .map(|(pid, process)| ProcessInfo {
+            pid: pid.as_u32(),
+            name: process.name().to_string(),
+            memory_kb: process.memory() / 1024,
+            cpu_percent: process.cpu_usage(),
+            exe: process.exe().map(Path::to_path_buf),
+        })
+        .collect()
+}
+
+/// Critical Windows processes that cannot be killed
+/// Killing these could crash the system or cause serious instability
+const PROTECTED_PROCESSES: &[&str] = &[
+    "csrss.exe",      // Client Server Runtime
+    "dwm.exe",        // Desktop Window Manager
+    "explorer.exe",   // Windows Explorer (shell)
+    "lsass.exe",      // Local Security Authority
+    "services.exe",   // Services Control Manager
+    "smss.exe",       // Session Manager
+    "system",         // System process
+    "wininit.exe",    // Windows Init
+    "winlogon.exe",   // Windows Logon
+    "svchost.exe",    // Service Host (critical services)
+];
+
+/// Safety settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct SafetyConfig {
+    /// Ask before a profile activation closes processes
+    pub confirm_kill: bool,
+    /// Extra processes that are never closed, on top of the built-in list
+    pub protected_processes: Vec<String>,
+}
+
+/// User additions to the protected list (normalized), set from the safety settings
+static USER_PROTECTED: Lazy<RwLock<Vec<String>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Replace the user's protected processes
+pub fn set_user_protected(process_names: &[String]) {
+    if let Ok(mut guard) = USER_PROTECTED.write() {
+        *guard = process_names.iter().map(|name| normalize_process_name(name.trim())).collect();
+    }
+}
+
+/// Check if a process name is in the protected list (case-insensitive)
+fn is_protected(process_name: &str) -> bool {
+    let name_lower = process_name.to_lowercase();
+    PROTECTED_PROCESSES
+        .iter()
+        .any(|protected| protected.to_lowercase() == name_lower)
+        || USER_PROTECTED
+            .read()
+            .is_ok_and(|names| names.contains(&normalize_process_name(process_name)))
+}
+
+/// Normalize process name for matching (case-insensitive, strips .exe if present)
+fn normalize_process_name(name: &str) -> String {
+    let lower = name.to_lowercase();
+    if lower.ends_with(".exe") {
+        lower[..lower.len() - 4].to_string()
+    } else {
+        lower
+    }
+}
+
+/// List all running processes
+pub fn list_processes() -> Vec<ProcessInfo> {
+    LiveProcesses.list()
+}
+
+/// Listing, finding and closing processes. Activation, the kill confirmation and dry runs go
+/// through this, so they can run against `FakeProcesses` in tests (or another source such as
+/// WMI or Toolhelp).
+pub trait ProcessBackend: Send {
+    /// Every running process, sorted by name
+    fn list(&mut self) -> Vec<ProcessInfo>;
+
+    /// Pids of the running instances of each target, in target order
+    fn find(&mut self, targets: &[String]) -> Vec<Vec<Pid>> {
+        let running = self.list();
+        targets
+            .iter()
+            .map(|target| {
+                running
+                    .iter()
+                    .filter(|process| matches_target(&process.name, target))
+                    .map(|process| Pid::from_u32(process.pid))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Executable paths of `pids` that are still running and readable
+    fn exe_paths(&mut self, pids: &[Pid]) -> Vec<PathBuf> {
+        self.list()
+            .into_iter()
+            .filter(|process| pids.contains(&Pid::from_u32(process.pid)))
+            .filter_map(|process| process.exe)
+            .collect()
+    }
+
+    /// Terminate each job's processes and wait for them to exit; outcomes are in job order
+    fn terminate(&mut self, jobs: Vec<Vec<Pid>>) -> Vec<KillOutcome>;
+
+    /// Start an app again (one that a profile closed)
+    fn launch(&mut self, exe: &Path) -> Result<()>;
+}
+
+/// The processes running on this machine, from Toolhelp snapshots (sysinfo if those fail)
+#[derive(Default)]
+pub struct LiveProcesses;
+
+impl ProcessBackend for LiveProcesses {
+    fn list(&mut self) -> Vec<ProcessInfo> {
+        let mut processes = with_fallback(&TOOLHELP, toolhelp_list, sysinfo_list);
+
+        // Sort by name for easier viewing
+        processes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        processes
+    }
+
+    // Only refreshes names, reusing the matches of earlier passes
+    fn find(&mut self, targets: &[String]) -> Vec<Vec<Pid>> {
+        match KILL_INDEX.lock() {
+            Ok(mut index) => index.matches(targets),
+            Err(_) => ProcessIndex::new().matches(targets),
+        }
+    }
+
+    // Only the exe of each pid, not a full refresh
+    fn exe_paths(&mut self, pids: &[Pid]) -> Vec<PathBuf> {
+        let mut sys = System::new();
+        let mut exes = Vec::new();
+        for &pid in pids {
+            sys.refresh_process_specifics(pid, ProcessRefreshKind::new().with_exe(UpdateKind::OnlyIfNotSet));
+            exes.extend(sys.process(pid).and_then(|process| process.exe()).map(Path::to_path_buf));
+        }
+        exes
+    }
+
+    fn terminate(&mut self, jobs: Vec<Vec<Pid>>) -> Vec<KillOutcome> {
+        terminate_all(jobs)
+    }
+
+    fn launch(&mut self, exe: &Path) -> Result<()> {
+        let mut command = std::process::Command::new(exe);
+        // Some apps look for their files next to the exe
+        if let Some(dir) = exe.parent() {
+            command.current_dir(dir);
+        }
+        command.spawn().map_err(|e| anyhow!("Failed to start {}: {}", exe.display(), e))?;
+        Ok(())
+    }
+}
+
+/// What terminating one `FakeProcesses` process does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FakeExit {
+    Exits,
+    /// Termination fails; it keeps running
+    Refuses,
+    /// Terminated but never exits
+    Hangs,
+}
+
+/// In-memory process table for tests. Closing a process removes it unless `set_exit` gave its
+/// pid another behaviour. Clones share the table, so a test can keep one and hand the other to
+/// the code under test.
+#[derive(Clone, Default)]
+pub struct FakeProcesses {
+    table: Arc<parking_lot::Mutex<FakeTable>>,
+}
+
+#[derive(Default)]
+struct FakeTable {
+    running: Vec<ProcessInfo>,
+    exits: HashMap<u32, FakeExit>,
+    terminated: Vec<u32>,
+    launched: Vec<PathBuf>,
+}
+
+impl FakeProcesses {
+    /// Processes as (name, pid)
+    pub fn new(running: &[(&str, u32)]) -> Self {
+        let fake = FakeProcesses::default();
+        for &(name, pid) in running {
+            fake.start(name, pid, 0);
+        }
+        fake
+    }
+
+    /// Add a process using `memory_kb`
+    pub fn start(&self, name: &str, pid: u32, memory_kb: u64) {
+        let process = ProcessInfo { pid, name: name.to_string(), memory_kb, cpu_percent: 0.0, exe: None };
+        self.table.lock().running.push(process);
+    }
+
+    /// How terminating `pid` goes: `Refuses` and `Hangs` leave it running
+    pub fn set_exit(&self, pid: u32, exit: FakeExit) {
+        self.table.lock().exits.insert(pid, exit);
+    }
+
+    /// Names of the processes still running
+    pub fn running(&self) -> Vec<String> {
+        self.table.lock().running.iter().map(|process| process.name.clone()).collect()
+    }
+
+    /// Give `pid` an executable path
+    pub fn set_exe(&self, pid: u32, exe: &str) {
+        if let Some(process) = self.table.lock().running.iter_mut().find(|process| process.pid == pid) {
+            process.exe = Some(PathBuf::from(exe));
+        }
+    }
+
+    /// Every pid a terminate call was made for, in call order
+    pub fn terminated(&self) -> Vec<u32> {
+        self.table.lock().terminated.clone()
+    }
+
+    /// Every exe a launch call was made for, in call order
+    pub fn launched(&self) -> Vec<PathBuf> {
+        self.table.lock().launched.clone()
+    }
+}
+
+impl ProcessBackend for FakeProcesses {
+    fn list(&mut self) -> Vec<ProcessInfo> {
+        let mut processes = self.table.lock().running.clone();
+        processes.sort_by(|a, b| a.name.cmp(&b.name));
+        processes
+    }
+
+    fn terminate(&mut self, jobs: Vec<Vec<Pid>>) -> Vec<KillOutcome> {
+        let mut table = self.table.lock();
+        jobs.iter()
+            .map(|pids| {
+                let (mut closed, mut failed, mut timed_out) = (0, 0, 0);
+                for pid in pids.iter().map(|pid| pid.as_u32()) {
+                    table.terminated.push(pid);
+                    match table.exits.get(&pid).copied().unwrap_or(FakeExit::Exits) {
+                        FakeExit::Exits => {
+                            table.running.retain(|process| process.pid != pid);
+                            closed += 1;
+                        }
+                        FakeExit::Refuses => failed += 1,
+                        FakeExit::Hangs => timed_out += 1,
+                    }
+                }
+                KillOutcome::Finished { closed, failed, timed_out }
+            })
+            .collect()
+    }
+
+    fn launch(&mut self, exe: &Path) -> Result<()> {
+        let mut table = self.table.lock();
+        table.launched.push(exe.to_path_buf());
+        // Running from now on, under a pid no earlier process had
+        let pid = table.running.iter().map(|process| process.pid).chain(table.terminated.iter().copied()).max().unwrap_or(0) + 1;
+        let name = exe.file_name().map_or_else(|| exe.to_string_lossy(), |name| name.to_string_lossy()).into_owned();
+        table.running.push(ProcessInfo { pid, name, memory_kb: 0, cpu_percent: 0.0, exe: Some(exe.to_path_buf()) });
+        Ok(())
+    }
+}
+
+/// Kill processes by name
+/// Returns a detailed report of what happened
+pub fn kill_processes(process_names: &[String]) -> KillReport {
+    kill_matching(&mut LiveProcesses, process_names, false)
+}
+
+/// `kill_processes` through `backend`
+pub fn kill_processes_with(backend: &mut dyn ProcessBackend, process_names: &[String]) -> KillReport {
+    kill_matching(backend, process_names, false)
+}
+
+/// Report what `kill_processes` would do without closing anything
+/// (`killed` lists the names that are running and would be closed)
+pub fn preview_kill(process_names: &[String]) -> KillReport {
+    preview_kill_with(&mut LiveProcesses, process_names)
+}
+
+/// `preview_kill` through `backend`
+pub fn preview_kill_with(backend: &mut dyn ProcessBackend, process_names: &[String]) -> KillReport {
+    kill_matching(backend, process_names, true)
+}
+
+fn kill_matching(backend: &mut dyn ProcessBackend, process_names: &[String], dry_run: bool) -> KillReport {
+    let mut report = KillReport::new();
+    let matches = backend.find(process_names);
+
+    // Entries with running instances, in list order
+    let mut targets: Vec<(&String, Vec<Pid>)> = Vec::new();
+    for (target_name, pids) in process_names.iter().zip(matches) {
+        let target_normalized = normalize_process_name(target_name);
+
+        // Check if process is protected
+        if is_protected(&target_normalized) || is_protected(target_name) {
+            report.blocklist_skipped.push(target_name.clone());
+            continue;
+        }
+
+        if pids.is_empty() {
+            report.not_found.push(target_name.clone());
+        } else if dry_run {
+            report.killed.push(target_name.clone());
+        } else {
+            targets.push((target_name, pids));
+        }
+    }
+
+    let (names, jobs): (Vec<&String>, Vec<Vec<Pid>>) = targets.into_iter().unzip();
+    // Read before terminating; a closed process has no path left to read
+    let exes: Vec<Vec<PathBuf>> = jobs.iter().map(|pids| backend.exe_paths(pids)).collect();
+    for ((name, outcome), exes) in names.into_iter().zip(backend.terminate(jobs)).zip(exes) {
+        if matches!(outcome, KillOutcome::Finished { closed, .. } if closed > 0) {
+            for exe in exes {
+                if !report.closed_exes.contains(&exe) {
+                    report.closed_exes.push(exe);
+                }
+            }
+        }
+        outcome.record(&mut report, name);
+    }
+
+    report
+}
+
+/// Terminate each job's processes on a few worker threads, so one app that hangs on its way
+/// out doesn't hold up the rest; outcomes are in job order
+fn terminate_all(jobs: Vec<Vec<Pid>>) -> Vec<KillOutcome> {
+    run_jobs(jobs, KILL_TIMEOUT * 2, terminate)
+}
+
+/// Run `terminate` over `jobs` on up to KILL_THREADS threads, giving the whole pass `limit`.
+/// Jobs no worker got to by then are `NotStarted`; started ones that haven't reported back are
+/// stuck in the terminate call itself, count as timed out and are left to finish in the
+/// background.
+fn run_jobs<F>(jobs: Vec<Vec<Pid>>, limit: Duration, terminate: F) -> Vec<KillOutcome>
+where
+    F: Fn(&[Pid]) -> KillOutcome + Send + Sync + 'static,
+{
+    let deadline = Instant::now() + limit;
+    let sizes: Vec<usize> = jobs.iter().map(Vec::len).collect();
+    let count = jobs.len();
+    let queue = Arc::new(Mutex::new(jobs.into_iter().enumerate()));
+    let terminate = Arc::new(terminate);
+    let (tx, rx) = mpsc::channel();
+    for _ in 0..count.min(KILL_THREADS) {
+        let (queue, terminate, tx) = (Arc::clone(&queue), Arc::clone(&terminate), tx.clone());
+        std::thread::spawn(move || {
+            // Takes the lock only for the pop, not while terminating; nothing new is started
+            // once the pass is out of time
+            let next_job = || queue.lock().ok().filter(|_| Instant::now() < deadline).and_then(|mut queue| queue.next());
+            while let Some((index, pids)) = next_job() {
+                // None: started
+                if tx.send((index, None)).is_err() || tx.send((index, Some(terminate(&pids)))).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    let mut outcomes = vec![KillOutcome::NotStarted; count];
+    while let Ok((index, outcome)) = rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        outcomes[index] = outcome.unwrap_or(KillOutcome::Finished { closed: 0, failed: 0, timed_out: sizes[index] });
+    }
+    outcomes
+}
+
+/// Terminate `pids` and wait up to KILL_TIMEOUT for them to exit
+fn terminate(pids: &[Pid]) -> KillOutcome {
+    let mut sys = System::new();
+    let mut exiting = Vec::new();
+    let mut failed = 0;
+    for &pid in pids {
+        if !sys.refresh_process(pid) {
+            // Exited on its own since the scan
+            continue;
+        }
+        match sys.process(pid) {
+            Some(process) if !process.kill() => failed += 1,
+            _ => exiting.push(pid),
+        }
+    }
+
+    let deadline = Instant::now() + KILL_TIMEOUT;
+    loop {
+        exiting.retain(|&pid| sys.refresh_process(pid));
+        if exiting.is_empty() || Instant::now() >= deadline {
+            break;
+        }
+        std::thread::sleep(EXIT_POLL);
+    }
+
+    KillOutcome::Finished { closed: pids.len() - failed - exiting.len(), failed, timed_out: exiting.len() }
+}
+
+/// Match either with or without .exe extension
+fn matches_target(process_name: &str, target_name: &str) -> bool {
+    normalize_process_name(process_name) == normalize_process_name(target_name)
+        || process_name.to_lowercase() == target_name.to_lowercase()
+}
+
+/// Running, unprotected apps that `kill_processes` would close, with their memory use
+pub fn kill_candidates(process_names: &[String]) -> Vec<KillCandidate> {
+    kill_candidates_with(&mut LiveProcesses, process_names)
+}
+
+/// `kill_candidates` through `backend`
+pub fn kill_candidates_with(backend: &mut dyn ProcessBackend, process_names: &[String]) -> Vec<KillCandidate> {
+    let running = backend.list();
+
+    let mut candidates = Vec::new();
+    for target_name in process_names {
+        if is_protected(&normalize_process_name(target_name)) || is_protected(target_name) {
+            continue;
+        }
+        let mut candidate = KillCandidate { name: target_name.clone(), instances: 0, memory_kb: 0, exe: None };
+        for process in running.iter().filter(|process| matches_target(&process.name, target_name)) {
+            candidate.instances += 1;
+            candidate.memory_kb += process.memory_kb;
+            if candidate.exe.is_none() {
+                candidate.exe = process.exe.clone();
+            }
+        }
+        if candidate.instances > 0 {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+/// Small (16x16) shell icon of an executable (the generic program icon if it has none).
+/// The calling thread needs COM initialized.
+pub fn exe_icon(exe: &Path) -> Option<ProcessIcon> {
+    use windows::core::HSTRING;
+    use windows::Win32::Graphics::Gdi::{
+        DeleteObject, GetDC, GetDIBits, ReleaseDC, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+    };
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::Storage::FileSystem::FILE_FLAGS_AND_ATTRIBUTES;
+    use windows::Win32::UI::Shell::{SHGetFileInfoW, SHFILEINFOW, SHGFI_ICON, SHGFI_SMALLICON};
+    use windows::Win32::UI::WindowsAndMessaging::{DestroyIcon, GetIconInfo, ICONINFO};
+
+    unsafe {
+        let mut file_info = SHFILEINFOW::default();
+        let found = SHGetFileInfoW(
+            &HSTRING::from(exe.as_os_str()),
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            Some(&mut file_info),
+            std::mem::size_of::<SHFILEINFOW>() as u32,
+            SHGFI_ICON | SHGFI_SMALLICON,
+        );
+        let icon = file_info.hIcon;
+        if found == 0 || icon.is_invalid() {
+            return None;
+        }
+        let mut info = ICONINFO::default();
+        let result = GetIconInfo(icon, &mut info);
+        let _ = DestroyIcon(icon);
+        result.ok()?;
+
+        let dc = GetDC(HWND::default());
+        let mut bitmap_info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER { biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32, ..Default::default() },
+            ..Default::default()
+        };
+        // The first call only fills in the size
+        let mut pixels = None;
+        if GetDIBits(dc, info.hbmColor, 0, 0, None, &mut bitmap_info, DIB_RGB_COLORS) != 0 {
+            let width = bitmap_info.bmiHeader.biWidth.unsigned_abs();
+            let height = bitmap_info.bmiHeader.biHeight.unsigned_abs();
+            bitmap_info.bmiHeader.biHeight = -(height as i32); // top-down rows
+            bitmap_info.bmiHeader.biBitCount = 32;
+            bitmap_info.bmiHeader.biCompression = BI_RGB.0;
+            let mut bgra = vec![0u8; (width * height * 4) as usize];
+            let lines = GetDIBits(
+                dc,
+                info.hbmColor,
+                0,
+                height,
+                Some(bgra.as_mut_ptr().cast()),
+                &mut bitmap_info,
+                DIB_RGB_COLORS,
+            );
+            if lines == height as i32 {
+                pixels = Some(ProcessIcon { width, height, rgba: bgra_to_rgba(bgra) });
+            }
+        }
+        ReleaseDC(HWND::default(), dc);
+        DeleteObject(info.hbmColor);
+        DeleteObject(info.hbmMask);
+        pixels
+    }
+}
+
+/// Convert GDI pixels to RGBA; icons without an alpha channel are treated as opaque
+fn bgra_to_rgba(mut pixels: Vec<u8>) -> Vec<u8> {
+    let has_alpha = pixels.chunks_exact(4).any(|pixel| pixel[3] != 0);
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+        if !has_alpha {
+            pixel[3] = 0xFF;
+        }
+    }
+    pixels
+}
+
+/// Check if a process name would be blocked by the safety blocklist
+pub fn would_be_protected(process_name: &str) -> bool {
+    is_protected(process_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_process_name() {
+        assert_eq!(normalize_process_name("notepad.exe"), "notepad");
+        assert_eq!(normalize_process_name("Notepad.exe"), "notepad");
+        assert_eq!(normalize_process_name("NOTEPAD.EXE"), "notepad");
+        assert_eq!(normalize_process_name("notepad"), "notepad");
+    }
+
+    #[test]
+    fn test_is_protected() {
+        assert!(is_protected("csrss.exe"));
+        assert!(is_protected("CSRSS.EXE"));
+        assert!(is_protected("explorer.exe"));
+        assert!(is_protected("Explorer.exe"));
+        assert!(!is_protected("notepad.exe"));
+        assert!(!is_protected("chrome.exe"));
+    }
+
+    #[test]
+    fn test_user_protected() {
+        assert!(!is_protected("obs64.exe"));
+        set_user_protected(&["OBS64.exe ".to_string()]);
+        assert!(is_protected("obs64"));
+        assert!(is_protected("obs64.exe"));
+        set_user_protected(&[]);
+        assert!(!is_protected("obs64.exe"));
+    }
+
+    #[test]
+    fn test_would_be_protected() {
+        assert!(would_be_protected("dwm.exe"));
+        assert!(would_be_protected("DWM.exe"));
+        assert!(!would_be_protected("discord.exe"));
+    }
+
+    #[test]
+    fn test_matches_target() {
+        assert!(matches_target("Discord.exe", "discord"));
+        assert!(matches_target("discord.exe", "Discord.exe"));
+        assert!(!matches_target("discordptb.exe", "discord.exe"));
+    }
+
+    #[test]
+    fn test_bgra_to_rgba() {
+        assert_eq!(bgra_to_rgba(vec![1, 2, 3, 0x80]), vec![3, 2, 1, 0x80]);
+        // No alpha channel at all: opaque
+        assert_eq!(bgra_to_rgba(vec![1, 2, 3, 0, 4, 5, 6, 0]), vec![3, 2, 1, 0xFF, 6, 5, 4, 0xFF]);
+    }
+
+    #[test]
+    fn test_list_processes() {
+        let processes = list_processes();
+        // Should return at least some processes on any system
+        assert!(!processes.is_empty());
+    }
+
+    #[test]
+    fn test_exe_name() {
+        let mut exe_file = [0u16; 260];
+        for (slot, c) in exe_file.iter_mut().zip("Discord.exe".encode_utf16()) {
+            *slot = c;
+        }
+        assert_eq!(exe_name(&exe_file), "Discord.exe");
+        // Garbage after the terminator is ignored
+        exe_file[20] = u16::from(b'x');
+        assert_eq!(exe_name(&exe_file), "Discord.exe");
+        // A name filling the whole array has no terminator
+        assert_eq!(exe_name(&[u16::from(b'a'); 4]), "aaaa");
+        assert_eq!(exe_name(&[0; 260]), "");
+    }
+
+    #[test]
+    fn test_with_fallback() {
+        let fast_enabled = AtomicBool::new(true);
+        assert_eq!(with_fallback(&fast_enabled, || Ok("fast"), || "fallback"), "fast");
+        assert!(fast_enabled.load(Ordering::Relaxed));
+
+        assert_eq!(with_fallback(&fast_enabled, || Err(anyhow!("no snapshot")), || "fallback"), "fallback");
+        assert!(!fast_enabled.load(Ordering::Relaxed));
+        // Not tried again once it failed
+        assert_eq!(with_fallback(&fast_enabled, || -> Result<&str> { panic!("fast path retried") }, || "fallback"), "fallback");
+    }
+
+    #[test]
+    fn test_cpu_percent() {
+        // 0.5 s of CPU time in 1 s: half a core
+        assert_eq!(cpu_percent(5_000_000, Duration::from_secs(1)), 50.0);
+        assert_eq!(cpu_percent(5_000_000, Duration::ZERO), 0.0);
+    }
+
+    #[test]
+    fn test_kill_report_new() {
+        let report = KillReport::new();
+        assert!(report.killed.is_empty());
+        assert!(report.failed.is_empty());
+        assert!(report.not_found.is_empty());
+        assert!(report.blocklist_skipped.is_empty());
+        assert!(report.timed_out.is_empty());
+    }
+
+    #[test]
+    fn test_process_index() {
+        let process = list_processes().into_iter().next().unwrap();
+        let targets = vec![process.name.clone(), "no-such-app.exe".to_string()];
+        let mut index = ProcessIndex::new();
+        let matches = index.matches(&targets);
+        assert!(matches[0].contains(&Pid::from_u32(process.pid)));
+        assert!(matches[1].is_empty());
+
+        // The next pass over the same list reuses the index
+        assert!(index.matches(&targets)[0].contains(&Pid::from_u32(process.pid)));
+        assert!(index.matches(&targets[1..])[0].is_empty());
+    }
+
+    #[test]
+    fn test_kill_outcomes() {
+        let mut report = KillReport::new();
+        KillOutcome::closed(2).record(&mut report, "discord.exe");
+        KillOutcome::Finished { closed: 1, failed: 1, timed_out: 0 }.record(&mut report, "chrome.exe");
+        KillOutcome::Finished { closed: 0, failed: 0, timed_out: 1 }.record(&mut report, "steam.exe");
+        // Refused and hung instances of one entry are both reported
+        KillOutcome::Finished { closed: 0, failed: 1, timed_out: 1 }.record(&mut report, "teams.exe");
+        KillOutcome::NotStarted.record(&mut report, "slack.exe");
+        assert_eq!(report.killed, ["discord.exe", "chrome.exe (partial)"]);
+        assert_eq!(report.failed, ["chrome.exe (partial)", "teams.exe"]);
+        assert_eq!(report.timed_out, ["steam.exe", "teams.exe"]);
+        assert_eq!(report.not_started, ["slack.exe"]);
+
+        // Processes that are already gone count as closed
+        assert_eq!(terminate_all(vec![vec![Pid::from_u32(u32::MAX - 1)]; 3]), [KillOutcome::closed(1); 3]);
+    }
+
+    #[test]
+    fn test_run_jobs_with_more_jobs_than_threads() {
+        let jobs = |count: u32| (0..count).map(|i| vec![Pid::from_u32(i), Pid::from_u32(i + 100)]).collect::<Vec<_>>();
+        let quick = |pids: &[Pid]| {
+            std::thread::sleep(Duration::from_millis(20));
+            KillOutcome::closed(pids.len())
+        };
+        let count = KILL_THREADS as u32 * 2 + 3;
+        assert_eq!(run_jobs(jobs(count), Duration::from_secs(10), quick), vec![KillOutcome::closed(2); count as usize]);
+
+        // The first round hangs past the pass's deadline: those jobs time out, the rest never start
+        let hung = |_: &[Pid]| {
+            std::thread::sleep(Duration::from_secs(2));
+            KillOutcome::closed(2)
+        };
+        let outcomes = run_jobs(jobs(count), Duration::from_millis(300), hung);
+        assert_eq!(outcomes[..KILL_THREADS], vec![KillOutcome::Finished { closed: 0, failed: 0, timed_out: 2 }; KILL_THREADS]);
+        assert!(outcomes[KILL_THREADS..].iter().all(|outcome| *outcome == KillOutcome::NotStarted));
+    }
+
+    #[test]
+    fn test_kill_with_fake_backend() {
+        let mut fake = FakeProcesses::new(&[("Discord.exe", 10), ("Discord.exe", 11), ("steam.exe", 20), ("chrome.exe", 30), ("dwm.exe", 40)]);
+        fake.set_exit(11, FakeExit::Refuses);
+        fake.set_exit(20, FakeExit::Hangs);
+        let names: Vec<String> = ["discord", "steam.exe", "chrome.exe", "spotify.exe", "dwm.exe"].map(String::from).to_vec();
+
+        // A dry run closes nothing
+        let preview = preview_kill_with(&mut fake, &names);
+        assert_eq!(preview.killed, ["discord", "steam.exe", "chrome.exe"]);
+        assert_eq!(preview.not_found, ["spotify.exe"]);
+        assert_eq!(preview.blocklist_skipped, ["dwm.exe"]);
+        assert!(fake.terminated().is_empty());
+
+        let report = kill_processes_with(&mut fake, &names);
+        assert_eq!(report.killed, ["discord (partial)", "chrome.exe"]);
+        assert_eq!(report.failed, ["discord (partial)"]);
+        assert_eq!(report.timed_out, ["steam.exe"]);
+        assert_eq!(fake.terminated(), [10, 11, 20, 30]);
+        assert_eq!(fake.running(), ["Discord.exe", "steam.exe", "dwm.exe"]);
+    }
+
+    #[test]
+    fn test_kill_candidates_with_fake_backend() {
+        let fake = FakeProcesses::default();
+        fake.start("Discord.exe", 10, 150_000);
+        fake.start("discord.exe", 11, 50_000);
+        fake.start("explorer.exe", 20, 80_000);
+        let names: Vec<String> = ["Discord.exe", "explorer.exe", "steam.exe"].map(String::from).to_vec();
+
+        let candidates = kill_candidates_with(&mut fake.clone(), &names);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!((candidates[0].name.as_str(), candidates[0].instances, candidates[0].memory_kb), ("Discord.exe", 2, 200_000));
+    }
+}
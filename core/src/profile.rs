@@ -0,0 +1,1242 @@
+use anyhow::{anyhow, Result};
+use crate::crosshair_gen::CrosshairParams;
+use crate::crosshair_position::CrosshairAnchor;
+use crate::display::DisplaySetting;
+use crate::audio::AudioSettings;
+use crate::network::NetworkSettings;
+use crate::storage::StorageSettings;
+use crate::openrgb::RgbScene;
+use crate::obs::ObsSettings;
+use crate::captures::CaptureSettings;
+use crate::gpu::GpuSettings;
+use crate::tweaks::SystemTweak;
+use crate::keyboard::{self, KeyboardSettings};
+use crate::game_features::GameFeatureToggles;
+use crate::overlay_text::Corner;
+use crate::alerts::AlertThresholds;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table};
+use tracing::warn;
+
+/// Gaming profile containing optimization settings and crosshair configuration
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub processes_to_kill: Vec<String>,
+    pub crosshair_image_path: Option<String>,
+    pub crosshair_x_offset: i32,
+    pub crosshair_y_offset: i32,
+    /// Screen anchor the offsets are relative to (defaults to the screen center)
+    #[serde(default)]
+    pub crosshair_anchor: CrosshairAnchor,
+    pub overlay_enabled: bool,
+    #[serde(default)]
+    pub fan_speed_max: bool,
+    /// Power plan GUID to switch to while active (None = leave the current plan)
+    #[serde(default)]
+    pub power_plan: Option<String>,
+    /// Windows Game Mode / Game Bar / background recording switches while active
+    #[serde(default)]
+    pub game_features: GameFeatureToggles,
+    /// Request the finest system timer resolution while active
+    #[serde(default)]
+    pub high_precision_timer: bool,
+    /// Turn off animations, shadows and transparency while active
+    #[serde(default)]
+    pub best_performance_visuals: bool,
+    /// Resolution/refresh rate forced on a monitor while active (None = leave the desktop mode)
+    #[serde(default)]
+    pub display_mode: Option<DisplaySetting>,
+    /// Master and per-app volume levels while active
+    #[serde(default)]
+    pub audio: AudioSettings,
+    /// Delivery Optimization pause and per-process bandwidth caps while active
+    #[serde(default)]
+    pub network: NetworkSettings,
+    /// Search indexing and defrag/maintenance tasks paused while active
+    #[serde(default)]
+    pub storage: StorageSettings,
+    /// OpenRGB lighting while active (None = leave the lighting alone)
+    #[serde(default)]
+    pub rgb_scene: Option<RgbScene>,
+    /// OBS scene and recording/replay buffer while active
+    #[serde(default)]
+    pub obs: ObsSettings,
+    /// New screenshots and clips moved into a folder named after the profile while active
+    #[serde(default)]
+    pub captures: CaptureSettings,
+    /// Suppress toast notifications while active
+    #[serde(default)]
+    pub do_not_disturb: bool,
+    /// Also mute notification sounds (only with do_not_disturb)
+    #[serde(default)]
+    pub mute_notification_sounds: bool,
+    /// NVIDIA/AMD driver settings while active
+    #[serde(default)]
+    pub gpu: GpuSettings,
+    /// One-shot actions run on activation (purge standby list, flush DNS, ...)
+    #[serde(default)]
+    pub system_tweaks: Vec<SystemTweak>,
+    /// Input layout pinning, Win key block and Sticky Keys shortcut while active
+    #[serde(default)]
+    pub keyboard: KeyboardSettings,
+    /// Undo everything already applied when an activation step fails
+    #[serde(default)]
+    pub rollback_on_failure: bool,
+    /// Hide the crosshair from screen capture (OBS, screenshots) while keeping it visible
+    #[serde(default)]
+    pub exclude_from_capture: bool,
+    /// RGBA color multiplied into the crosshair image (None = original colors)
+    #[serde(default)]
+    pub crosshair_tint: Option<[u8; 4]>,
+    /// Extra crosshair configurations (e.g. per weapon) that can be cycled while active
+    #[serde(default)]
+    pub crosshair_presets: Vec<CrosshairPreset>,
+    /// Named pixel offsets for the profile's own crosshair (e.g. "1080p", "1440p ultrawide"),
+    /// picked from the tray when a game runs at another display mode
+    #[serde(default)]
+    pub offset_presets: Vec<OffsetPreset>,
+    /// Offset preset in use instead of `crosshair_x_offset`/`crosshair_y_offset` (None = those)
+    #[serde(default)]
+    pub selected_offset_preset: Option<String>,
+    /// Generator settings the crosshair image was rendered from (e.g. an imported share code)
+    #[serde(default)]
+    pub crosshair_params: Option<CrosshairParams>,
+    /// Optional text note drawn in a screen corner while the overlay is shown
+    #[serde(default)]
+    pub overlay_note: Option<OverlayNote>,
+    /// Screen corner for live CPU/GPU readings on the overlay (None = off)
+    #[serde(default)]
+    pub hardware_hud: Option<Corner>,
+    /// Temperature limits that replace the global alert limits while active (unset = global)
+    #[serde(default)]
+    pub alert_thresholds: AlertThresholds,
+    /// Activate without asking which running apps get closed ("don't ask again")
+    #[serde(default)]
+    pub skip_kill_confirmation: bool,
+    /// Listed with a quick-activate button on the dashboard
+    #[serde(default)]
+    pub favorite: bool,
+    /// For Vanguard/EAC/BattlEye titles: only the external crosshair window, no input hooks or
+    /// messages to the game (the Win key block and layout pinning are skipped)
+    #[serde(default)]
+    pub anti_cheat_safe: bool,
+    /// Kept but out of the way: hidden from the tray, the flyout, the quick switcher and the
+    /// dashboard until restored (e.g. a seasonal game)
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// Short reminder or cheat sheet drawn by the overlay
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OverlayNote {
+    pub text: String,
+    /// Approximate line height in pixels (8-64)
+    pub font_size: u32,
+    pub corner: Corner,
+    /// RGBA text color
+    pub color: [u8; 4],
+}
+
+/// Named crosshair configuration that can be cycled to while a profile is active
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CrosshairPreset {
+    pub name: String,
+    pub image_path: Option<String>,
+    pub x_offset: i32,
+    pub y_offset: i32,
+    #[serde(default)]
+    pub tint: Option<[u8; 4]>,
+}
+
+/// Pixel offsets of the profile's own crosshair saved under a name
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OffsetPreset {
+    pub name: String,
+    pub x_offset: i32,
+    pub y_offset: i32,
+}
+
+impl Profile {
+    /// Validate profile data
+    pub fn validate(&self) -> Result<()> {
+        // Validate name length (1-50 characters)
+        if self.name.is_empty() || self.name.len() > 50 {
+            return Err(anyhow!(
+                "Profile name must be between 1 and 50 characters"
+            ));
+        }
+
+        // Validate crosshair image path if provided
+        if let Some(ref path) = self.crosshair_image_path {
+            let path_obj = Path::new(path);
+
+            // Check if file exists
+            if !path_obj.exists() {
+                return Err(anyhow!(
+                    "Crosshair image file does not exist: {}",
+                    path
+                ));
+            }
+
+            // Check if file has .png extension
+            if path_obj.extension().and_then(|s| s.to_str()) != Some("png") {
+                return Err(anyhow!(
+                    "Crosshair image must be a PNG file: {}",
+                    path
+                ));
+            }
+        }
+
+        // Validate X/Y offsets (-500 to +500 pixels)
+        if self.crosshair_x_offset < -500 || self.crosshair_x_offset > 500 {
+            return Err(anyhow!(
+                "X offset must be between -500 and 500 pixels"
+            ));
+        }
+        if self.crosshair_y_offset < -500 || self.crosshair_y_offset > 500 {
+            return Err(anyhow!(
+                "Y offset must be between -500 and 500 pixels"
+            ));
+        }
+
+        for preset in &self.offset_presets {
+            if !(-500..=500).contains(&preset.x_offset) || !(-500..=500).contains(&preset.y_offset) {
+                return Err(anyhow!(
+                    "Offsets of preset '{}' must be between -500 and 500 pixels",
+                    preset.name
+                ));
+            }
+        }
+
+        // Validate anchor percentages (0-100%)
+        self.crosshair_anchor.validate().map_err(|e| anyhow!(e))?;
+
+        // Validate power plan GUID
+        if let Some(ref guid) = self.power_plan {
+            if crate::power::parse_guid(guid).is_none() {
+                return Err(anyhow!("Invalid power plan GUID: {}", guid));
+            }
+        }
+
+        // Validate display mode
+        if let Some(ref display) = self.display_mode {
+            let mode = display.mode;
+            if display.device.trim().is_empty() {
+                return Err(anyhow!("Display mode needs a monitor"));
+            }
+            if mode.width == 0 || mode.height == 0 || mode.refresh_hz == 0 {
+                return Err(anyhow!("Invalid display mode: {}", mode));
+            }
+        }
+
+        // Validate volume levels (0-100%)
+        if self.audio.master_percent.is_some_and(|percent| percent > 100) {
+            return Err(anyhow!("Master volume must be between 0 and 100%"));
+        }
+        for app in &self.audio.app_volumes {
+            if app.process.trim().is_empty() {
+                return Err(anyhow!("App volume needs a process name"));
+            }
+            if app.percent > 100 {
+                return Err(anyhow!("Volume for {} must be between 0 and 100%", app.process));
+            }
+        }
+
+        // Validate NVIDIA driver profile name
+        if self.gpu.nvidia_profile.as_ref().is_some_and(|name| name.trim().is_empty()) {
+            return Err(anyhow!("NVIDIA driver profile name cannot be empty"));
+        }
+
+        // Validate OpenRGB profile name
+        if let Some(RgbScene::Profile(ref name)) = self.rgb_scene {
+            if name.trim().is_empty() {
+                return Err(anyhow!("OpenRGB profile name cannot be empty"));
+            }
+        }
+
+        // Validate OBS scene name
+        if self.obs.scene.as_ref().is_some_and(|scene| scene.trim().is_empty()) {
+            return Err(anyhow!("OBS scene name cannot be empty"));
+        }
+
+        // Validate capture target folder
+        if self.captures.target_folder.as_ref().is_some_and(|folder| folder.trim().is_empty()) {
+            return Err(anyhow!("Capture folder cannot be empty"));
+        }
+
+        // Validate pinned keyboard layout
+        if let Some(ref layout) = self.keyboard.layout {
+            if keyboard::parse_layout_id(layout).is_none() {
+                return Err(anyhow!("Invalid keyboard layout id: {}", layout));
+            }
+        }
+
+        // Validate bandwidth limits
+        for limit in &self.network.bandwidth_limits {
+            if limit.process.trim().is_empty() {
+                return Err(anyhow!("Bandwidth limit needs a process name"));
+            }
+            if limit.limit_kbps == 0 {
+                return Err(anyhow!("Bandwidth limit for {} must be above 0 Kbps", limit.process));
+            }
+        }
+
+        // Validate crosshair presets
+        for preset in &self.crosshair_presets {
+            if preset.name.trim().is_empty() || preset.name.len() > 50 {
+                return Err(anyhow!(
+                    "Crosshair preset names must be between 1 and 50 characters"
+                ));
+            }
+        }
+
+        // Validate overlay note
+        if let Some(ref note) = self.overlay_note {
+            if note.text.trim().is_empty() || note.text.len() > 200 {
+                return Err(anyhow!(
+                    "Overlay note must be between 1 and 200 characters"
+                ));
+            }
+            if note.font_size < 8 || note.font_size > 64 {
+                return Err(anyhow!(
+                    "Overlay note font size must be between 8 and 64 pixels"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of crosshair slots to cycle through (base crosshair + presets)
+    pub fn crosshair_slot_count(&self) -> usize {
+        1 + self.crosshair_presets.len()
+    }
+
+    /// Crosshair configuration for a slot; slot 0 is the profile's own crosshair
+    pub fn crosshair_for_slot(&self, slot: usize) -> CrosshairPreset {
+        match slot.checked_sub(1).and_then(|i| self.crosshair_presets.get(i)) {
+            Some(preset) => preset.clone(),
+            None => {
+                let (x_offset, y_offset) = self.base_offset();
+                CrosshairPreset {
+                    name: "Default".to_string(),
+                    image_path: self.crosshair_image_path.clone(),
+                    x_offset,
+                    y_offset,
+                    tint: self.crosshair_tint,
+                }
+            }
+        }
+    }
+
+    /// The selected offset preset, if it still exists
+    pub fn selected_offsets(&self) -> Option<&OffsetPreset> {
+        let name = self.selected_offset_preset.as_deref()?;
+        self.offset_presets.iter().find(|preset| preset.name == name)
+    }
+
+    /// Pixel offsets of the profile's own crosshair: the selected offset preset's, or its own
+    pub fn base_offset(&self) -> (i32, i32) {
+        match self.selected_offsets() {
+            Some(preset) => (preset.x_offset, preset.y_offset),
+            None => (self.crosshair_x_offset, self.crosshair_y_offset),
+        }
+    }
+
+    /// Use the offset preset called `name` (None = the profile's own offsets); false if there
+    /// is no such preset
+    pub fn select_offset_preset(&mut self, name: Option<&str>) -> bool {
+        if name.is_some_and(|name| !self.offset_presets.iter().any(|preset| preset.name == name)) {
+            return false;
+        }
+        self.selected_offset_preset = name.map(str::to_string);
+        true
+    }
+
+    /// Store new pixel offsets for a slot (out-of-range slots change the base crosshair, as
+    /// `crosshair_for_slot` shows them; the base crosshair's go to the selected offset preset)
+    pub fn set_slot_offset(&mut self, slot: usize, x_offset: i32, y_offset: i32) {
+        match slot.checked_sub(1).and_then(|i| self.crosshair_presets.get_mut(i)) {
+            Some(preset) => {
+                preset.x_offset = x_offset;
+                preset.y_offset = y_offset;
+            }
+            None => {
+                let selected = self.selected_offset_preset.clone();
+                match self.offset_presets.iter_mut().find(|preset| Some(&preset.name) == selected.as_ref()) {
+                    Some(preset) => {
+                        preset.x_offset = x_offset;
+                        preset.y_offset = y_offset;
+                    }
+                    None => {
+                        self.crosshair_x_offset = x_offset;
+                        self.crosshair_y_offset = y_offset;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every crosshair image path of the profile (main image and presets)
+    pub fn image_paths_mut(&mut self) -> impl Iterator<Item = &mut Option<String>> {
+        std::iter::once(&mut self.crosshair_image_path)
+            .chain(self.crosshair_presets.iter_mut().map(|preset| &mut preset.image_path))
+    }
+}
+
+/// Profile file names in the order they're looked for. A hand-written profiles.toml or
+/// profiles.yaml wins over the profiles.json the app creates; an empty one is filled in from
+/// profiles.json on the next save, which is how to switch formats.
+pub const PROFILE_FILES: [&str; 4] = ["profiles.toml", "profiles.yaml", "profiles.yml", "profiles.json"];
+
+/// Where entries of profiles.json that aren't valid profiles are moved, so loading the rest and
+/// saving over the file doesn't lose them
+pub const INVALID_PROFILES_FILE: &str = "profiles.invalid.json";
+
+/// Largest profile file that's read (a real one is a few hundred KB at most)
+const MAX_PROFILE_FILE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// An entry of a JSON profile file that couldn't be read as a profile
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidEntry {
+    /// "Profile 3 (FPS): missing field `name`"
+    pub error: String,
+    pub entry: serde_json::Value,
+}
+
+/// Profiles read from the profile file, and the entries moved to `INVALID_PROFILES_FILE`
+#[derive(Debug, Default)]
+pub struct LoadedProfiles {
+    pub profiles: Vec<Profile>,
+    pub quarantined: Vec<InvalidEntry>,
+}
+
+/// Formats the profile file can be in, picked by its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileFormat {
+    Json,
+    /// Comments survive saves made by the app
+    Toml,
+    /// Read and written, but comments are lost when the app saves
+    Yaml,
+}
+
+/// Top level of a TOML profile file, which has to be a table (`[[profiles]]` entries)
+#[derive(Serialize, Deserialize)]
+struct TomlProfiles {
+    #[serde(default)]
+    profiles: Vec<Profile>,
+}
+
+impl ProfileFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(ProfileFormat::Json),
+            "toml" => Some(ProfileFormat::Toml),
+            "yaml" | "yml" => Some(ProfileFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn parse(self, contents: &str) -> Result<Vec<Profile>> {
+        match self {
+            ProfileFormat::Json => serde_json::from_str(contents).map_err(|e| anyhow!("{}", e)),
+            ProfileFormat::Toml => toml::from_str::<TomlProfiles>(contents)
+                .map(|file| file.profiles)
+                .map_err(|e| anyhow!("{}", e)),
+            ProfileFormat::Yaml => serde_yaml::from_str(contents).map_err(|e| anyhow!("{}", e)),
+        }
+    }
+
+    /// Like `parse`, but JSON entries that aren't valid profiles are returned on their own
+    /// instead of failing the whole file (which then only fails when it isn't an array)
+    pub fn parse_entries(self, contents: &str) -> Result<(Vec<Profile>, Vec<InvalidEntry>)> {
+        if self != ProfileFormat::Json {
+            return self.parse(contents).map(|profiles| (profiles, Vec::new()));
+        }
+        let entries: Vec<serde_json::Value> = serde_json::from_str(contents).map_err(|e| anyhow!("{}", e))?;
+        let mut profiles = Vec::with_capacity(entries.len());
+        let mut invalid = Vec::new();
+        for (index, entry) in entries.into_iter().enumerate() {
+            match Profile::deserialize(&entry) {
+                Ok(profile) => profiles.push(profile),
+                Err(e) => {
+                    let name = entry.get("name").and_then(|name| name.as_str()).unwrap_or("no name");
+                    let error = format!("Profile {} ({}): {}", index + 1, name, e);
+                    invalid.push(InvalidEntry { error, entry });
+                }
+            }
+        }
+        Ok((profiles, invalid))
+    }
+
+    /// Text of a profile file; a TOML file keeps the comments of `previous` (the file's current
+    /// contents) wherever the same setting is still there
+    pub fn serialize(self, profiles: &[Profile], previous: Option<&str>) -> Result<String> {
+        match self {
+            ProfileFormat::Json => serde_json::to_string_pretty(profiles).map_err(|e| anyhow!("{}", e)),
+            ProfileFormat::Toml => {
+                let file = TomlProfiles { profiles: profiles.to_vec() };
+                let text = toml::to_string_pretty(&file).map_err(|e| anyhow!("{}", e))?;
+                let Some(previous) = previous.and_then(|previous| previous.parse::<DocumentMut>().ok()) else {
+                    return Ok(text);
+                };
+                let mut document = text.parse::<DocumentMut>().map_err(|e| anyhow!("{}", e))?;
+                keep_comments(previous.as_table(), document.as_table_mut());
+                document.set_trailing(previous.trailing().as_str().unwrap_or_default());
+                Ok(document.to_string())
+            }
+            ProfileFormat::Yaml => serde_yaml::to_string(profiles).map_err(|e| anyhow!("{}", e)),
+        }
+    }
+}
+
+/// Copy the comments around keys, values and tables in `old` to the same ones in `new`.
+/// Entries of an array of tables are matched by their `name` (profiles, presets), so comments
+/// follow a profile when others are added or removed before it.
+fn keep_comments(old: &Table, new: &mut Table) {
+    *new.decor_mut() = old.decor().clone();
+    let keys: Vec<String> = new.iter().map(|(key, _)| key.to_string()).collect();
+    for key in keys {
+        let (Some(old_key), Some(old_item)) = (old.key(&key), old.get(&key)) else {
+            continue;
+        };
+        if let Some(mut new_key) = new.key_mut(&key) {
+            *new_key.leaf_decor_mut() = old_key.leaf_decor().clone();
+        }
+        match (old_item, &mut new[key.as_str()]) {
+            (Item::Table(old_table), Item::Table(new_table)) => keep_comments(old_table, new_table),
+            (Item::Value(old_value), Item::Value(new_value)) => *new_value.decor_mut() = old_value.decor().clone(),
+            (Item::ArrayOfTables(old_array), Item::ArrayOfTables(new_array)) => {
+                let name = |table: &Table| table.get("name").and_then(Item::as_str).map(str::to_string);
+                for (index, new_table) in new_array.iter_mut().enumerate() {
+                    let old_table = match name(new_table) {
+                        Some(ref wanted) => old_array.iter().find(|table| name(table).as_ref() == Some(wanted)),
+                        None => old_array.get(index),
+                    };
+                    if let Some(old_table) = old_table {
+                        keep_comments(old_table, new_table);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The data directory's profile file: the first of `PROFILE_FILES` that exists, or profiles.json
+pub fn profiles_path(data_dir: &Path) -> PathBuf {
+    PROFILE_FILES
+        .iter()
+        .map(|name| data_dir.join(name))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| data_dir.join("profiles.json"))
+}
+
+/// The profile file profiles are loaded from: the first of `PROFILE_FILES` with something in it
+/// (an empty profiles.toml/yaml is waiting to be filled in from profiles.json)
+pub fn profile_file_to_load(data_dir: &Path) -> Option<PathBuf> {
+    PROFILE_FILES
+        .iter()
+        .map(|name| data_dir.join(name))
+        .find(|path| fs::read_to_string(path).is_ok_and(|contents| !contents.trim().is_empty()))
+}
+
+/// Read and parse a profile file in whatever format its extension says
+fn read_profile_file(path: &Path) -> Result<(Vec<Profile>, Vec<InvalidEntry>)> {
+    let name = path.file_name().map_or_else(String::new, |name| name.to_string_lossy().to_string());
+    let format = ProfileFormat::from_path(path).ok_or_else(|| anyhow!("Unknown profile format: {}", name))?;
+    let size = fs::metadata(path)
+        .map_err(|e| anyhow!("Failed to read {}: {}", name, e))?
+        .len();
+    if size > MAX_PROFILE_FILE_BYTES {
+        return Err(anyhow!("{} is too large ({} MB)", name, size / (1024 * 1024)));
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read {}: {}", name, e))?;
+    format.parse_entries(&contents).map_err(|e| anyhow!("Failed to parse {}: {}", name, e))
+}
+
+/// Add entries to `INVALID_PROFILES_FILE`, leaving out ones that are already in it
+fn quarantine_entries(data_dir: &Path, entries: &[InvalidEntry]) -> Result<()> {
+    let path = data_dir.join(INVALID_PROFILES_FILE);
+    let mut quarantined: Vec<serde_json::Value> = match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", INVALID_PROFILES_FILE, e))?,
+        Err(_) => Vec::new(),
+    };
+    for invalid in entries {
+        if !quarantined.iter().any(|existing| existing.get("profile") == Some(&invalid.entry)) {
+            quarantined.push(serde_json::json!({ "error": invalid.error, "profile": invalid.entry }));
+        }
+    }
+    let contents = serde_json::to_string_pretty(&quarantined)
+        .map_err(|e| anyhow!("Failed to serialize {}: {}", INVALID_PROFILES_FILE, e))?;
+    fs::write(&path, contents).map_err(|e| anyhow!("Failed to write {}: {}", INVALID_PROFILES_FILE, e))
+}
+
+/// Load profiles from the profile file in user data directory (see `profiles_path`)
+/// Returns empty vector if file doesn't exist (not an error)
+pub fn load_profiles(data_dir: &Path) -> Result<Vec<Profile>> {
+    load_profiles_recovering(data_dir).map(|loaded| loaded.profiles)
+}
+
+/// Load profiles like `load_profiles`, also reporting the entries of profiles.json that weren't
+/// valid profiles. Those are moved to `INVALID_PROFILES_FILE` first; if that fails, loading
+/// fails, so a later save can't drop them.
+pub fn load_profiles_recovering(data_dir: &Path) -> Result<LoadedProfiles> {
+    // If file doesn't exist, return empty vector
+    let Some(path) = profile_file_to_load(data_dir) else {
+        return Ok(LoadedProfiles::default());
+    };
+    let (profiles, quarantined) = read_profile_file(&path)?;
+    if !quarantined.is_empty() {
+        quarantine_entries(data_dir, &quarantined)?;
+        for invalid in &quarantined {
+            warn!("[Profile] Moved to {}: {}", INVALID_PROFILES_FILE, invalid.error);
+        }
+    }
+    Ok(LoadedProfiles { profiles, quarantined })
+}
+
+/// Save profiles to the profile file in user data directory, in its format (JSON for a new file)
+/// Creates directory if it doesn't exist
+pub fn save_profiles(profiles: &[Profile], data_dir: &Path) -> Result<()> {
+    // Create directory if it doesn't exist
+    fs::create_dir_all(data_dir)
+        .map_err(|e| anyhow!("Failed to create data directory: {}", e))?;
+
+    let profiles_path = profiles_path(data_dir);
+    let name = profiles_path.file_name().map_or_else(String::new, |name| name.to_string_lossy().to_string());
+    let format = ProfileFormat::from_path(&profiles_path).unwrap_or(ProfileFormat::Json);
+
+    let previous = fs::read_to_string(&profiles_path).ok();
+    let contents = format.serialize(profiles, previous.as_deref())
+        .map_err(|e| anyhow!("Failed to serialize profiles: {}", e))?;
+
+    // Write to file
+    fs::write(&profiles_path, contents)
+        .map_err(|e| anyhow!("Failed to write {}: {}", name, e))?;
+
+    Ok(())
+}
+
+/// Which way `sync_profiles` copied the profile file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncResult {
+    UpToDate,
+    /// The sync folder's copy was newer and replaced ours
+    Pulled,
+    /// Ours was newer and was copied to the sync folder
+    Pushed,
+}
+
+/// Two-way sync of the profile file with a shared folder (OneDrive, Dropbox, a network share);
+/// whichever copy was written last wins. The shared copy has the same name, so the same format.
+pub fn sync_profiles(data_dir: &Path, sync_dir: &Path) -> Result<SyncResult> {
+    let local = profiles_path(data_dir);
+    let shared = sync_dir.join(local.file_name().unwrap_or_default());
+    let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+
+    // Same content needs no copy, whatever the timestamps say
+    if let (Ok(a), Ok(b)) = (fs::read(&local), fs::read(&shared)) {
+        if a == b {
+            return Ok(SyncResult::UpToDate);
+        }
+    }
+
+    let (from, to, result) = match (modified(&local), modified(&shared)) {
+        (None, None) => return Ok(SyncResult::UpToDate),
+        (Some(_), None) => (&local, &shared, SyncResult::Pushed),
+        (None, Some(_)) => (&shared, &local, SyncResult::Pulled),
+        (Some(ours), Some(theirs)) if theirs > ours => (&shared, &local, SyncResult::Pulled),
+        (Some(_), Some(_)) => (&local, &shared, SyncResult::Pushed),
+    };
+
+    // Don't spread a broken file to the other side
+    let contents = fs::read_to_string(from).map_err(|e| anyhow!("Failed to read {}: {}", from.display(), e))?;
+    ProfileFormat::from_path(from)
+        .unwrap_or(ProfileFormat::Json)
+        .parse(&contents)
+        .map_err(|e| anyhow!("Not syncing {}: {}", from.display(), e))?;
+    fs::create_dir_all(sync_dir).map_err(|e| anyhow!("Failed to create sync folder: {}", e))?;
+    fs::write(to, contents).map_err(|e| anyhow!("Failed to write {}: {}", to.display(), e))?;
+    Ok(result)
+}
+
+/// Machine-local tweaks to shared profiles in the data directory (never synced), keyed by
+/// computer name and then profile name:
+/// `{ "GAMING-PC": { "*": { "overlay_enabled": false }, "FPS": { "crosshair_x_offset": 3 } } }`
+pub const OVERRIDES_FILE: &str = "overrides.json";
+/// Profile key of overrides that apply to every profile on a machine
+pub const ALL_PROFILES: &str = "*";
+
+/// Contents of overrides.json
+#[derive(Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub struct MachineOverrides(HashMap<String, HashMap<String, serde_json::Value>>);
+
+/// Name this machine's overrides are kept under
+pub fn machine_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_default()
+}
+
+/// Merge `patch` into `base`: objects key by key, anything else replaced, and null removes the
+/// key so the setting falls back to its default
+fn merge_json(base: &mut serde_json::Value, patch: &serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                if value.is_null() {
+                    base.remove(key);
+                } else {
+                    merge_json(base.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+                }
+            }
+        }
+        (base, patch) => *base = patch.clone(),
+    }
+}
+
+impl MachineOverrides {
+    /// Read overrides.json; no file means no overrides
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(OVERRIDES_FILE);
+        if !path.exists() {
+            return Ok(MachineOverrides::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", OVERRIDES_FILE, e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", OVERRIDES_FILE, e))
+    }
+
+    /// The overrides `machine` has for `profile_name`, in the order they apply
+    fn entries(&self, profile_name: &str, machine: &str) -> Vec<&serde_json::Value> {
+        let Some(profiles) = self.0.iter().find(|(name, _)| name.eq_ignore_ascii_case(machine)).map(|(_, p)| p) else {
+            return Vec::new();
+        };
+        [ALL_PROFILES, profile_name].iter().filter_map(|key| profiles.get(*key)).collect()
+    }
+
+    /// Settings of `profile` that `machine` overrides
+    pub fn overridden_fields(&self, profile_name: &str, machine: &str) -> Vec<String> {
+        let mut fields: Vec<String> = self
+            .entries(profile_name, machine)
+            .into_iter()
+            .filter_map(serde_json::Value::as_object)
+            .flat_map(|entry| entry.keys().cloned())
+            .filter(|field| field != "name")
+            .collect();
+        fields.sort();
+        fields.dedup();
+        fields
+    }
+
+    /// `profile` as `machine` uses it. Precedence, last wins: the shared profile, the machine's
+    /// "*" entry, then its entry for the profile's name. The name itself can't be overridden, and
+    /// the result has to pass `Profile::validate`.
+    pub fn apply(&self, profile: &Profile, machine: &str) -> Result<Profile> {
+        let entries = self.entries(&profile.name, machine);
+        if entries.is_empty() {
+            return Ok(profile.clone());
+        }
+
+        let mut merged = serde_json::to_value(profile).map_err(|e| anyhow!("Failed to serialize profile: {}", e))?;
+        for entry in entries {
+            if !entry.is_object() {
+                return Err(anyhow!("Overrides for {} on {} must be an object", profile.name, machine));
+            }
+            merge_json(&mut merged, entry);
+        }
+        let mut result: Profile = serde_json::from_value(merged)
+            .map_err(|e| anyhow!("Overrides for {} on {}: {}", profile.name, machine, e))?;
+        result.name = profile.name.clone();
+        result.validate()
+            .map_err(|e| anyhow!("Overrides for {} on {}: {}", profile.name, machine, e))?;
+        Ok(result)
+    }
+}
+
+/// How often and when a profile was activated (kept in the global config by profile name)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProfileUsage {
+    pub activations: u32,
+    /// Unix time of the last activation
+    pub last_activated: u64,
+}
+
+/// Up to `count` profiles for one-click access in the tray menu: favorites first, then the
+/// most recently activated (more activations break ties). Never-used profiles are left out.
+pub fn quick_profiles(profiles: &[Profile], usage: &HashMap<String, ProfileUsage>, count: usize) -> Vec<String> {
+    let mut candidates: Vec<(&Profile, ProfileUsage)> = profiles
+        .iter()
+        .map(|profile| (profile, usage.get(&profile.name).cloned().unwrap_or_default()))
+        .filter(|(profile, usage)| !profile.archived && (profile.favorite || usage.activations > 0))
+        .collect();
+    candidates.sort_by(|(a, a_usage), (b, b_usage)| {
+        b.favorite
+            .cmp(&a.favorite)
+            .then(b_usage.last_activated.cmp(&a_usage.last_activated))
+            .then(b_usage.activations.cmp(&a_usage.activations))
+    });
+    candidates.into_iter().take(count).map(|(profile, _)| profile.name.clone()).collect()
+}
+
+/// Create a new profile with default values
+pub fn create_profile(name: String) -> Profile {
+    Profile {
+        name,
+        processes_to_kill: Vec::new(),
+        crosshair_image_path: None,
+        crosshair_x_offset: 0,
+        crosshair_y_offset: 0,
+        crosshair_anchor: CrosshairAnchor::default(),
+        overlay_enabled: true,
+        fan_speed_max: false,
+        power_plan: None,
+        game_features: GameFeatureToggles::default(),
+        high_precision_timer: false,
+        best_performance_visuals: false,
+        display_mode: None,
+        audio: AudioSettings::default(),
+        network: NetworkSettings::default(),
+        storage: StorageSettings::default(),
+        rgb_scene: None,
+        obs: ObsSettings::default(),
+        captures: CaptureSettings::default(),
+        do_not_disturb: false,
+        mute_notification_sounds: false,
+        gpu: GpuSettings::default(),
+        system_tweaks: Vec::new(),
+        keyboard: KeyboardSettings::default(),
+        rollback_on_failure: false,
+        exclude_from_capture: false,
+        crosshair_tint: None,
+        crosshair_presets: Vec::new(),
+        offset_presets: Vec::new(),
+        selected_offset_preset: None,
+        crosshair_params: None,
+        overlay_note: None,
+        hardware_hud: None,
+        alert_thresholds: AlertThresholds::default(),
+        skip_kill_confirmation: false,
+        favorite: false,
+        anti_cheat_safe: false,
+        archived: false,
+    }
+}
+
+/// Delete profile at the specified index
+pub fn delete_profile(profiles: &mut Vec<Profile>, index: usize) {
+    if index < profiles.len() {
+        profiles.remove(index);
+    }
+}
+
+/// Check if profile name is unique in the list (case-insensitive)
+pub fn is_profile_name_unique(profiles: &[Profile], name: &str, exclude_index: Option<usize>) -> bool {
+    let name_lower = name.to_lowercase();
+
+    for (i, profile) in profiles.iter().enumerate() {
+        // Skip the profile at exclude_index (for updates)
+        if let Some(exclude) = exclude_index {
+            if i == exclude {
+                continue;
+            }
+        }
+
+        if profile.name.to_lowercase() == name_lower {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::AppVolume;
+    use crate::network::BandwidthLimit;
+    use crate::display::DisplayMode;
+
+    #[test]
+    fn test_quick_profiles() {
+        let profile = |name: &str, favorite: bool| Profile { favorite, ..create_profile(name.to_string()) };
+        let profiles = [profile("Work", false), profile("CS2", false), profile("Valorant", true), profile("Apex", false), profile("Unused", false)];
+        let used = |activations: u32, last_activated: u64| ProfileUsage { activations, last_activated };
+        let usage = HashMap::from([
+            ("Work".to_string(), used(1, 300)),
+            ("CS2".to_string(), used(40, 200)),
+            ("Apex".to_string(), used(2, 300)),
+            ("Deleted".to_string(), used(99, 999)),
+        ]);
+
+        assert_eq!(quick_profiles(&profiles, &usage, 3), ["Valorant", "Apex", "Work"]);
+        assert_eq!(quick_profiles(&profiles, &usage, 10), ["Valorant", "Apex", "Work", "CS2"]);
+        assert!(quick_profiles(&profiles[4..], &usage, 3).is_empty());
+
+        // Archived profiles stay out, even favorites
+        let mut profiles = profiles;
+        profiles[2].archived = true;
+        profiles[3].archived = true;
+        assert_eq!(quick_profiles(&profiles, &usage, 3), ["Work", "CS2"]);
+    }
+
+    #[test]
+    fn test_create_profile() {
+        let profile = create_profile("Test Profile".to_string());
+        assert_eq!(profile.name, "Test Profile");
+        assert!(profile.processes_to_kill.is_empty());
+        assert_eq!(profile.crosshair_image_path, None);
+        assert_eq!(profile.crosshair_x_offset, 0);
+        assert_eq!(profile.crosshair_y_offset, 0);
+        assert_eq!(profile.overlay_enabled, true);
+        assert!(!profile.exclude_from_capture);
+    }
+
+    #[test]
+    fn test_validate_name_length() {
+        let mut profile = create_profile("Valid".to_string());
+        assert!(profile.validate().is_ok());
+
+        profile.name = "".to_string();
+        assert!(profile.validate().is_err());
+
+        profile.name = "a".repeat(51);
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_offsets() {
+        let mut profile = create_profile("Test".to_string());
+
+        profile.crosshair_x_offset = -500;
+        assert!(profile.validate().is_ok());
+
+        profile.crosshair_x_offset = 500;
+        assert!(profile.validate().is_ok());
+
+        profile.crosshair_x_offset = -501;
+        assert!(profile.validate().is_err());
+
+        profile.crosshair_x_offset = 0;
+        profile.crosshair_y_offset = 501;
+        assert!(profile.validate().is_err());
+
+        profile.crosshair_y_offset = 0;
+        profile.crosshair_anchor = CrosshairAnchor::Percent { x: 50.0, y: 45.0 };
+        assert!(profile.validate().is_ok());
+        profile.crosshair_anchor = CrosshairAnchor::Percent { x: 50.0, y: 150.0 };
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_power_plan() {
+        let mut profile = create_profile("Test".to_string());
+        profile.power_plan = Some("8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c".to_string());
+        assert!(profile.validate().is_ok());
+
+        profile.power_plan = Some("high performance".to_string());
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_audio() {
+        let mut profile = create_profile("Test".to_string());
+        profile.audio.master_percent = Some(80);
+        profile.audio.app_volumes.push(AppVolume { process: "Discord.exe".to_string(), percent: 40 });
+        assert!(profile.validate().is_ok());
+
+        profile.audio.app_volumes[0].percent = 140;
+        assert!(profile.validate().is_err());
+
+        profile.audio.app_volumes[0] = AppVolume { process: " ".to_string(), percent: 40 };
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_bandwidth_limits() {
+        let mut profile = create_profile("Test".to_string());
+        profile.network.bandwidth_limits.push(BandwidthLimit { process: "steam.exe".to_string(), limit_kbps: 2000 });
+        assert!(profile.validate().is_ok());
+
+        profile.network.bandwidth_limits[0].limit_kbps = 0;
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_display_mode() {
+        let mut profile = create_profile("Test".to_string());
+        let mode = DisplayMode { width: 1280, height: 960, refresh_hz: 240 };
+        profile.display_mode = Some(DisplaySetting { device: "\\\\.\\DISPLAY1".to_string(), mode });
+        assert!(profile.validate().is_ok());
+
+        profile.display_mode = Some(DisplaySetting {
+            device: "\\\\.\\DISPLAY1".to_string(),
+            mode: DisplayMode { refresh_hz: 0, ..mode },
+        });
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_keyboard_layout() {
+        let mut profile = create_profile("Test".to_string());
+        profile.keyboard.layout = Some("04090409".to_string());
+        assert!(profile.validate().is_ok());
+
+        profile.keyboard.layout = Some("en-US".to_string());
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_crosshair_slots() {
+        let mut profile = create_profile("Test".to_string());
+        profile.crosshair_x_offset = 3;
+        assert_eq!(profile.crosshair_slot_count(), 1);
+        assert_eq!(profile.crosshair_for_slot(0).x_offset, 3);
+
+        profile.crosshair_presets.push(CrosshairPreset {
+            name: "Sniper".to_string(),
+            image_path: None,
+            x_offset: 0,
+            y_offset: 10,
+            tint: None,
+        });
+        assert_eq!(profile.crosshair_slot_count(), 2);
+        assert_eq!(profile.crosshair_for_slot(1).name, "Sniper");
+        // Out-of-range slots fall back to the base crosshair
+        assert_eq!(profile.crosshair_for_slot(5).name, "Default");
+
+        profile.set_slot_offset(1, -4, 12);
+        assert_eq!((profile.crosshair_for_slot(1).x_offset, profile.crosshair_for_slot(1).y_offset), (-4, 12));
+        assert_eq!(profile.crosshair_x_offset, 3);
+        profile.set_slot_offset(0, 5, 6);
+        assert_eq!((profile.crosshair_x_offset, profile.crosshair_y_offset), (5, 6));
+
+        profile.crosshair_presets[0].name = "".to_string();
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_offset_presets() {
+        let mut profile = create_profile("Test".to_string());
+        profile.crosshair_x_offset = 3;
+        profile.offset_presets.push(OffsetPreset { name: "1440p ultrawide".to_string(), x_offset: -2, y_offset: 7 });
+
+        assert!(!profile.select_offset_preset(Some("4K")));
+        assert_eq!(profile.base_offset(), (3, 0));
+        assert!(profile.select_offset_preset(Some("1440p ultrawide")));
+        assert_eq!(profile.base_offset(), (-2, 7));
+        assert_eq!(profile.crosshair_for_slot(0).x_offset, -2);
+
+        // Adjusting the base crosshair changes the preset in use, not the profile's own offsets
+        profile.set_slot_offset(0, 1, 8);
+        assert_eq!(profile.offset_presets[0], OffsetPreset { name: "1440p ultrawide".to_string(), x_offset: 1, y_offset: 8 });
+        assert_eq!(profile.crosshair_x_offset, 3);
+
+        // A deleted preset falls back to the profile's own offsets
+        profile.offset_presets.clear();
+        assert_eq!(profile.base_offset(), (3, 0));
+        assert!(profile.select_offset_preset(None));
+        assert_eq!(profile.selected_offset_preset, None);
+
+        profile.offset_presets.push(OffsetPreset { name: "Far".to_string(), x_offset: 900, y_offset: 0 });
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_overlay_note() {
+        let mut profile = create_profile("Test".to_string());
+        profile.overlay_note = Some(OverlayNote {
+            text: "DON'T TILT".to_string(),
+            font_size: 16,
+            corner: Corner::TopRight,
+            color: [255, 255, 255, 255],
+        });
+        assert!(profile.validate().is_ok());
+
+        profile.overlay_note.as_mut().unwrap().font_size = 100;
+        assert!(profile.validate().is_err());
+
+        let note = profile.overlay_note.as_mut().unwrap();
+        note.font_size = 16;
+        note.text = "   ".to_string();
+        assert!(profile.validate().is_err());
+    }
+
+    #[test]
+    fn test_sync_profiles() {
+        let root = std::env::temp_dir().join(format!("gaming_optimizer_sync_{}", std::process::id()));
+        let (data_dir, sync_dir) = (root.join("data"), root.join("sync"));
+        fs::create_dir_all(&data_dir).unwrap();
+
+        assert_eq!(sync_profiles(&data_dir, &sync_dir).unwrap(), SyncResult::UpToDate);
+        save_profiles(&[create_profile("FPS".to_string())], &data_dir).unwrap();
+        assert_eq!(sync_profiles(&data_dir, &sync_dir).unwrap(), SyncResult::Pushed);
+        assert_eq!(sync_profiles(&data_dir, &sync_dir).unwrap(), SyncResult::UpToDate);
+
+        // A newer copy from another machine replaces ours
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        save_profiles(&[create_profile("MOBA".to_string())], &sync_dir).unwrap();
+        assert_eq!(sync_profiles(&data_dir, &sync_dir).unwrap(), SyncResult::Pulled);
+        assert_eq!(load_profiles(&data_dir).unwrap()[0].name, "MOBA");
+
+        // A corrupt copy is never spread
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(sync_dir.join("profiles.json"), "not json").unwrap();
+        assert!(sync_profiles(&data_dir, &sync_dir).is_err());
+        assert_eq!(load_profiles(&data_dir).unwrap()[0].name, "MOBA");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_profile_formats_round_trip() {
+        let mut fps = create_profile("FPS".to_string());
+        fps.processes_to_kill = vec!["Discord.exe".to_string()];
+        fps.crosshair_presets.push(CrosshairPreset {
+            name: "Sniper".to_string(),
+            image_path: None,
+            x_offset: 2,
+            y_offset: -3,
+            tint: Some([255, 0, 0, 255]),
+        });
+        let profiles = vec![fps, create_profile("MOBA".to_string())];
+
+        for format in [ProfileFormat::Json, ProfileFormat::Toml, ProfileFormat::Yaml] {
+            let text = format.serialize(&profiles, None).unwrap();
+            assert_eq!(format.parse(&text).unwrap(), profiles, "{:?}", format);
+        }
+        assert_eq!(ProfileFormat::from_path(Path::new("profiles.YML")), Some(ProfileFormat::Yaml));
+        assert_eq!(ProfileFormat::from_path(Path::new("profiles")), None);
+    }
+
+    #[test]
+    fn test_toml_keeps_comments() {
+        let mut profiles = vec![create_profile("FPS".to_string())];
+        profiles[0].processes_to_kill = vec!["Discord.exe".to_string()];
+        let text = ProfileFormat::Toml.serialize(&profiles, None).unwrap();
+        let edited = text
+            .replace("[[profiles]]\nname = \"FPS\"", "# Shooters\n[[profiles]]\nname = \"FPS\"")
+            .replace("processes_to_kill = [\"Discord.exe\"]", "# Chat apps\nprocesses_to_kill = [\"Discord.exe\"] # voice lag");
+        assert_ne!(edited, text);
+
+        // A new profile before it and a changed setting keep the comments where they were
+        profiles.insert(0, create_profile("Arcade".to_string()));
+        profiles[1].fan_speed_max = true;
+        let saved = ProfileFormat::Toml.serialize(&profiles, Some(&edited)).unwrap();
+        assert!(saved.contains("# Shooters\n[[profiles]]\nname = \"FPS\""), "{}", saved);
+        assert!(saved.contains("# Chat apps\nprocesses_to_kill = [\"Discord.exe\"] # voice lag"), "{}", saved);
+        assert_eq!(saved.matches("# Shooters").count(), 1);
+        assert_eq!(ProfileFormat::Toml.parse(&saved).unwrap(), profiles);
+    }
+
+    #[test]
+    fn test_partial_recovery() {
+        let data_dir = std::env::temp_dir().join(format!("gaming_optimizer_recovery_{}", std::process::id()));
+        fs::create_dir_all(&data_dir).unwrap();
+
+        let mut entries = vec![serde_json::to_value(create_profile("FPS".to_string())).unwrap()];
+        entries.push(serde_json::json!({ "name": "Broken", "crosshair_x_offset": "left" }));
+        entries.push(serde_json::to_value(create_profile("MOBA".to_string())).unwrap());
+        fs::write(data_dir.join("profiles.json"), serde_json::to_string(&entries).unwrap()).unwrap();
+
+        let loaded = load_profiles_recovering(&data_dir).unwrap();
+        let names: Vec<&str> = loaded.profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["FPS", "MOBA"]);
+        assert_eq!(loaded.quarantined.len(), 1);
+        assert!(loaded.quarantined[0].error.starts_with("Profile 2 (Broken)"), "{}", loaded.quarantined[0].error);
+
+        // Loading again doesn't quarantine the same entry twice
+        load_profiles(&data_dir).unwrap();
+        let invalid: Vec<serde_json::Value> =
+            serde_json::from_str(&fs::read_to_string(data_dir.join(INVALID_PROFILES_FILE)).unwrap()).unwrap();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0]["profile"], entries[1]);
+
+        // A file that isn't an array still fails as a whole
+        fs::write(data_dir.join("profiles.json"), "{\"name\": \"FPS\"}").unwrap();
+        assert!(load_profiles(&data_dir).is_err());
+
+        fs::remove_dir_all(&data_dir).unwrap();
+    }
+
+    #[test]
+    fn test_profile_file_selection() {
+        let data_dir = std::env::temp_dir().join(format!("gaming_optimizer_formats_{}", std::process::id()));
+        fs::create_dir_all(&data_dir).unwrap();
+
+        save_profiles(&[create_profile("FPS".to_string())], &data_dir).unwrap();
+        assert_eq!(profiles_path(&data_dir), data_dir.join("profiles.json"));
+
+        // An empty profiles.toml is filled in from profiles.json on the next save
+        fs::write(data_dir.join("profiles.toml"), "").unwrap();
+        let mut profiles = load_profiles(&data_dir).unwrap();
+        assert_eq!(profiles[0].name, "FPS");
+        profiles.push(create_profile("MOBA".to_string()));
+        save_profiles(&profiles, &data_dir).unwrap();
+        assert!(fs::read_to_string(data_dir.join("profiles.toml")).unwrap().contains("[[profiles]]"));
+        assert_eq!(load_profiles(&data_dir).unwrap(), profiles);
+
+        fs::remove_dir_all(&data_dir).unwrap();
+    }
+
+    #[test]
+    fn test_machine_overrides() {
+        let mut profile = create_profile("FPS".to_string());
+        profile.crosshair_x_offset = 5;
+        profile.power_plan = Some("381b4222-f694-41f0-9685-ff5bb260df2e".to_string());
+        profile.audio.master_percent = Some(80);
+        let overrides: MachineOverrides = serde_json::from_str(r#"{
+            "gaming-pc": {
+                "*": { "overlay_enabled": false, "crosshair_x_offset": 1 },
+                "FPS": { "name": "Renamed", "crosshair_x_offset": 3, "power_plan": null, "audio": { "app_volumes": [] } }
+            },
+            "LAPTOP": { "FPS": { "crosshair_x_offset": 9 } }
+        }"#).unwrap();
+
+        // The profile's own entry wins over "*", which wins over the shared profile
+        let applied = overrides.apply(&profile, "GAMING-PC").unwrap();
+        assert_eq!(applied.name, "FPS");
+        assert_eq!(applied.crosshair_x_offset, 3);
+        assert!(!applied.overlay_enabled);
+        assert_eq!(applied.power_plan, None);
+        // Objects are merged, so the rest of the audio settings stay
+        assert_eq!(applied.audio.master_percent, Some(80));
+        assert_eq!(
+            overrides.overridden_fields("FPS", "Gaming-PC"),
+            vec!["audio", "crosshair_x_offset", "overlay_enabled", "power_plan"]
+        );
+
+        assert_eq!(overrides.apply(&profile, "LAPTOP").unwrap().crosshair_x_offset, 9);
+        assert_eq!(overrides.apply(&profile, "OFFICE").unwrap(), profile);
+        assert!(overrides.overridden_fields("MOBA", "LAPTOP").is_empty());
+
+        // Overrides that make the profile invalid are refused
+        let broken: MachineOverrides = serde_json::from_str(r#"{ "LAPTOP": { "FPS": { "crosshair_x_offset": "left" } } }"#).unwrap();
+        assert!(broken.apply(&profile, "LAPTOP").is_err());
+        let out_of_range: MachineOverrides = serde_json::from_str(r#"{ "LAPTOP": { "*": { "crosshair_x_offset": 100000 } } }"#).unwrap();
+        assert!(out_of_range.apply(&profile, "LAPTOP").is_err());
+    }
+
+    #[test]
+    fn test_is_profile_name_unique() {
+        let profiles = vec![
+            create_profile("Profile 1".to_string()),
+            create_profile("Profile 2".to_string()),
+        ];
+
+        assert!(is_profile_name_unique(&profiles, "Profile 3", None));
+        assert!(!is_profile_name_unique(&profiles, "Profile 1", None));
+        assert!(!is_profile_name_unique(&profiles, "profile 1", None)); // Case-insensitive
+        assert!(is_profile_name_unique(&profiles, "Profile 1", Some(0))); // Exclude self
+    }
+}
@@ -0,0 +1,163 @@
+//! Profile lint
+//!
+//! Finds settings that save fine but are risky or can't work: kill entries on the protected
+//! list, crosshair images that are gone, offsets outside the overlay's range and names that
+//! only differ by case. Run when a profile is saved and on demand from the editor.
+
+use crate::i18n::t;
+use crate::process;
+use crate::profile::Profile;
+use std::path::Path;
+
+/// Largest crosshair offset, in pixels either way (as `Profile::validate`)
+const MAX_OFFSET: i32 = 500;
+
+/// Something to fix in a profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lint {
+    /// Kill entry for a process that is never closed
+    ProtectedKill(String),
+    /// Crosshair image (of the base crosshair or a preset) that doesn't exist
+    MissingCrosshair { preset: Option<String>, path: String },
+    /// Crosshair offset beyond ±500 pixels
+    OffsetOutOfRange { preset: Option<String>, x: i32, y: i32 },
+    /// Screen anchor outside 0-100%
+    AnchorOutOfRange,
+    /// Another profile with the same name in different case
+    DuplicateName(String),
+}
+
+impl Lint {
+    /// What's wrong and what to do about it
+    pub fn message(&self) -> String {
+        match self {
+            Lint::ProtectedKill(name) => t!("lint-protected-kill", process = name),
+            Lint::MissingCrosshair { preset: None, path } => t!("lint-missing-crosshair", path = path),
+            Lint::MissingCrosshair { preset: Some(preset), path } => {
+                t!("lint-missing-preset-crosshair", preset = preset, path = path)
+            }
+            Lint::OffsetOutOfRange { preset: None, x, y } => t!("lint-offset-out-of-range", x = x, y = y),
+            Lint::OffsetOutOfRange { preset: Some(preset), x, y } => {
+                t!("lint-preset-offset-out-of-range", preset = preset, x = x, y = y)
+            }
+            Lint::AnchorOutOfRange => t!("lint-anchor-out-of-range"),
+            Lint::DuplicateName(other) => t!("lint-duplicate-name", profile = other),
+        }
+    }
+}
+
+/// Kill entries without ".exe" are matched like the running process names that have it
+fn is_protected_entry(name: &str) -> bool {
+    let name = name.trim();
+    process::would_be_protected(name) || process::would_be_protected(&format!("{}.exe", name))
+}
+
+fn offset_in_range(x: i32, y: i32) -> bool {
+    (-MAX_OFFSET..=MAX_OFFSET).contains(&x) && (-MAX_OFFSET..=MAX_OFFSET).contains(&y)
+}
+
+/// Problems of `profile`, checked against the other profiles in `profiles` (which may
+/// include `profile` itself)
+pub fn lint_profile(profile: &Profile, profiles: &[Profile]) -> Vec<Lint> {
+    let mut lints = Vec::new();
+
+    for name in &profile.processes_to_kill {
+        if is_protected_entry(name) {
+            lints.push(Lint::ProtectedKill(name.clone()));
+        }
+    }
+
+    if let Some(ref path) = profile.crosshair_image_path {
+        if !Path::new(path).exists() {
+            lints.push(Lint::MissingCrosshair { preset: None, path: path.clone() });
+        }
+    }
+    for preset in &profile.crosshair_presets {
+        if let Some(ref path) = preset.image_path {
+            if !Path::new(path).exists() {
+                lints.push(Lint::MissingCrosshair { preset: Some(preset.name.clone()), path: path.clone() });
+            }
+        }
+    }
+
+    if !offset_in_range(profile.crosshair_x_offset, profile.crosshair_y_offset) {
+        lints.push(Lint::OffsetOutOfRange {
+            preset: None,
+            x: profile.crosshair_x_offset,
+            y: profile.crosshair_y_offset,
+        });
+    }
+    // Weapon presets, then the offset presets for other resolutions
+    let presets = profile
+        .crosshair_presets
+        .iter()
+        .map(|preset| (&preset.name, preset.x_offset, preset.y_offset))
+        .chain(profile.offset_presets.iter().map(|preset| (&preset.name, preset.x_offset, preset.y_offset)));
+    for (name, x, y) in presets {
+        if !offset_in_range(x, y) {
+            lints.push(Lint::OffsetOutOfRange { preset: Some(name.clone()), x, y });
+        }
+    }
+    if profile.crosshair_anchor.validate().is_err() {
+        lints.push(Lint::AnchorOutOfRange);
+    }
+
+    let name_lower = profile.name.to_lowercase();
+    for other in profiles {
+        if other.name != profile.name && other.name.to_lowercase() == name_lower {
+            lints.push(Lint::DuplicateName(other.name.clone()));
+        }
+    }
+
+    lints
+}
+
+/// Problems of every profile, with the name of the profile they belong to
+pub fn lint_all(profiles: &[Profile]) -> Vec<(String, Lint)> {
+    profiles
+        .iter()
+        .flat_map(|profile| {
+            lint_profile(profile, profiles).into_iter().map(|lint| (profile.name.clone(), lint))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::{create_profile, CrosshairPreset};
+
+    #[test]
+    fn test_lint_profile() {
+        let mut profile = create_profile("CS2".to_string());
+        profile.processes_to_kill = vec!["discord.exe".to_string(), "explorer.exe".to_string(), "DWM".to_string()];
+        profile.crosshair_image_path = Some("does/not/exist.png".to_string());
+        profile.crosshair_x_offset = 600;
+        profile.crosshair_presets.push(CrosshairPreset {
+            name: "AWP".to_string(),
+            image_path: None,
+            x_offset: 0,
+            y_offset: -501,
+            tint: None,
+        });
+        let profiles = [profile.clone(), create_profile("cs2".to_string()), create_profile("Apex".to_string())];
+
+        assert_eq!(
+            lint_profile(&profile, &profiles),
+            [
+                Lint::ProtectedKill("explorer.exe".to_string()),
+                Lint::ProtectedKill("DWM".to_string()),
+                Lint::MissingCrosshair { preset: None, path: "does/not/exist.png".to_string() },
+                Lint::OffsetOutOfRange { preset: None, x: 600, y: 0 },
+                Lint::OffsetOutOfRange { preset: Some("AWP".to_string()), x: 0, y: -501 },
+                Lint::DuplicateName("cs2".to_string()),
+            ]
+        );
+        assert!(lint_profile(&profiles[2], &profiles).is_empty());
+
+        // Both halves of a case-only duplicate are reported
+        let all = lint_all(&profiles);
+        assert!(all.contains(&("cs2".to_string(), Lint::DuplicateName("CS2".to_string()))));
+        assert_eq!(all.iter().filter(|(name, _)| name == "Apex").count(), 0);
+    }
+}
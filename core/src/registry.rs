@@ -0,0 +1,202 @@
+//! Small registry helpers for DWORD (and string) settings
+//!
+//! Values are read before being changed so callers can restore them exactly,
+//! including deleting values that didn't exist before.
+
+use anyhow::{anyhow, Result};
+
+/// Registry root a setting lives under
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hive {
+    CurrentUser,
+}
+
+#[cfg(windows)]
+fn hkey(hive: Hive) -> windows::Win32::System::Registry::HKEY {
+    use windows::Win32::System::Registry::HKEY_CURRENT_USER;
+    match hive {
+        Hive::CurrentUser => HKEY_CURRENT_USER,
+    }
+}
+
+#[cfg(windows)]
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Read a DWORD value (Ok(None) if the value or key doesn't exist)
+pub fn read_dword(hive: Hive, subkey: &str, name: &str) -> Result<Option<u32>> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::ERROR_FILE_NOT_FOUND;
+        use windows::Win32::System::Registry::{RegGetValueW, RRF_RT_REG_DWORD};
+
+        let (subkey_w, name_w) = (wide(subkey), wide(name));
+        let mut value: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let result = unsafe {
+            RegGetValueW(
+                hkey(hive),
+                PCWSTR(subkey_w.as_ptr()),
+                PCWSTR(name_w.as_ptr()),
+                RRF_RT_REG_DWORD,
+                None,
+                Some(&mut value as *mut u32 as *mut _),
+                Some(&mut size),
+            )
+        };
+
+        match result {
+            Ok(()) => Ok(Some(value)),
+            Err(e) if e.code() == ERROR_FILE_NOT_FOUND.to_hresult() => Ok(None),
+            Err(e) => Err(anyhow!("Failed to read {}\\{}: {}", subkey, name, e)),
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (hive, subkey, name);
+        Err(anyhow!("The registry is only available on Windows"))
+    }
+}
+
+/// Write a DWORD value, creating the key if needed
+pub fn write_dword(hive: Hive, subkey: &str, name: &str, value: u32) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::System::Registry::{RegSetKeyValueW, REG_DWORD};
+
+        let (subkey_w, name_w) = (wide(subkey), wide(name));
+        unsafe {
+            RegSetKeyValueW(
+                hkey(hive),
+                PCWSTR(subkey_w.as_ptr()),
+                PCWSTR(name_w.as_ptr()),
+                REG_DWORD.0,
+                Some(&value as *const u32 as *const _),
+                std::mem::size_of::<u32>() as u32,
+            )
+        }
+        .map_err(|e| anyhow!("Failed to write {}\\{}: {}", subkey, name, e))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (hive, subkey, name, value);
+        Err(anyhow!("The registry is only available on Windows"))
+    }
+}
+
+/// Write a string (REG_SZ) value, creating the key if needed
+pub fn write_string(hive: Hive, subkey: &str, name: &str, value: &str) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::System::Registry::{RegSetKeyValueW, REG_SZ};
+
+        let (subkey_w, name_w, value_w) = (wide(subkey), wide(name), wide(value));
+        unsafe {
+            RegSetKeyValueW(
+                hkey(hive),
+                PCWSTR(subkey_w.as_ptr()),
+                PCWSTR(name_w.as_ptr()),
+                REG_SZ.0,
+                Some(value_w.as_ptr() as *const _),
+                (value_w.len() * std::mem::size_of::<u16>()) as u32,
+            )
+        }
+        .map_err(|e| anyhow!("Failed to write {}\\{}: {}", subkey, name, e))
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (hive, subkey, name, value);
+        Err(anyhow!("The registry is only available on Windows"))
+    }
+}
+
+/// Delete a value (missing values are not an error)
+pub fn delete_value(hive: Hive, subkey: &str, name: &str) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use windows::core::PCWSTR;
+        use windows::Win32::Foundation::ERROR_FILE_NOT_FOUND;
+        use windows::Win32::System::Registry::RegDeleteKeyValueW;
+
+        let (subkey_w, name_w) = (wide(subkey), wide(name));
+        match unsafe { RegDeleteKeyValueW(hkey(hive), PCWSTR(subkey_w.as_ptr()), PCWSTR(name_w.as_ptr())) } {
+            Ok(()) => Ok(()),
+            Err(e) if e.code() == ERROR_FILE_NOT_FOUND.to_hresult() => Ok(()),
+            Err(e) => Err(anyhow!("Failed to delete {}\\{}: {}", subkey, name, e)),
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = (hive, subkey, name);
+        Err(anyhow!("The registry is only available on Windows"))
+    }
+}
+
+/// Restore a value captured with `read_dword` (None deletes it again)
+fn restore_dword(hive: Hive, subkey: &str, name: &str, previous: Option<u32>) -> Result<()> {
+    match previous {
+        Some(value) => write_dword(hive, subkey, name, value),
+        None => delete_value(hive, subkey, name),
+    }
+}
+
+/// A DWORD value changed by `RegistryChanges` and what it held before
+struct SavedValue {
+    hive: Hive,
+    subkey: &'static str,
+    name: &'static str,
+    previous: Option<u32>,
+}
+
+/// Writes DWORD values while remembering the originals so they can be put back
+#[derive(Default)]
+pub struct RegistryChanges {
+    /// Original values in the order they were changed
+    saved: Vec<SavedValue>,
+}
+
+impl RegistryChanges {
+    /// Whether any value is currently changed
+    pub fn is_empty(&self) -> bool {
+        self.saved.is_empty()
+    }
+
+    /// Write a value, keeping the original for `restore`
+    pub fn set_dword(&mut self, hive: Hive, subkey: &'static str, name: &'static str, value: u32) -> Result<()> {
+        let previous = read_dword(hive, subkey, name)?;
+        write_dword(hive, subkey, name, value)?;
+        // Changing the same value twice must still restore the first original
+        let already_saved = self
+            .saved
+            .iter()
+            .any(|saved| saved.hive == hive && saved.subkey == subkey && saved.name == name);
+        if !already_saved {
+            self.saved.push(SavedValue { hive, subkey, name, previous });
+        }
+        Ok(())
+    }
+
+    /// Put back every changed value, newest first (no-op if nothing was changed)
+    pub fn restore(&mut self) -> Result<()> {
+        // Keep going so one failure doesn't strand the rest
+        let mut first_error = None;
+        while let Some(saved) = self.saved.pop() {
+            if let Err(e) = restore_dword(saved.hive, saved.subkey, saved.name, saved.previous) {
+                first_error.get_or_insert(e);
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,421 @@
+//! Remote control API
+//!
+//! Opt-in HTTP + WebSocket server on 127.0.0.1 for companion apps (Stream Deck plugins, home
+//! automation, remote apps). Every request needs the token from the settings, either as
+//! `Authorization: Bearer <token>` or `?token=<token>` (browsers can't set WebSocket headers).
+//! Commands are handed to the GUI exactly like CLI commands, so both drive the same activation.
+//!
+//!   GET  /api/profiles                  profile list
+//!   POST /api/profiles/<name>/activate  activate a profile
+//!   POST /api/deactivate                deactivate the active profile
+//!   POST /api/overlay/on | /off         show or hide the crosshair
+//!   GET  /api/status                    active profile and live stats
+//!   GET  /api/ws                        WebSocket: status pushed every 2 s, CLI commands as
+//!                                       JSON text frames (e.g. {"Activate":{"profile":"FPS"}})
+//!   GET  /api/streamdeck                WebSocket for the Stream Deck plugin (see `streamdeck`)
+
+use crate::cli::{CliCommand, CliReply, OverlayState};
+use crate::integrations::streamdeck;
+use crate::ipc::{ConnectionLimit, RequestSender};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tracing::{info, warn};
+use tungstenite::protocol::Role;
+use tungstenite::{Error as WsError, Message as WsMessage, WebSocket};
+
+pub const DEFAULT_PORT: u16 = 8765;
+/// How often the WebSocket pushes the live status
+const STATUS_PUSH_SECS: u64 = 2;
+/// Connections served at once; further ones are closed right away
+const MAX_API_CONNECTIONS: usize = 16;
+/// How long a stalled client may hold a connection (request head, replies, WebSocket sends)
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+/// Longest request or header line read before the token is checked
+const MAX_LINE_LEN: u64 = 8 * 1024;
+/// Most header lines read before the token is checked
+const MAX_HEADERS: usize = 64;
+
+/// Remote API settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// Shared secret clients have to send (generated when the API is first enabled)
+    pub token: String,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        ApiConfig { enabled: false, port: DEFAULT_PORT, token: String::new() }
+    }
+}
+
+/// 64-character hex token from 32 bytes of the OS RNG
+pub fn generate_token() -> Result<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| anyhow!("Failed to generate API token: {}", e))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Compare without bailing out at the first difference
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given.bytes().zip(expected.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// What a request asks for
+#[derive(Debug, PartialEq)]
+enum Route {
+    Command(CliCommand),
+    WebSocket,
+    StreamDeck,
+}
+
+/// Map method + path (without the query) to a route, or an HTTP error
+fn route(method: &str, path: &str) -> Result<Route, (u16, &'static str)> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let route = match (method, segments.as_slice()) {
+        ("GET", ["api", "profiles"]) => Route::Command(CliCommand::List),
+        ("POST", ["api", "profiles", name, "activate"]) => {
+            Route::Command(CliCommand::Activate { profile: percent_decode(name) })
+        }
+        ("POST", ["api", "deactivate"]) => Route::Command(CliCommand::Deactivate),
+        ("POST", ["api", "overlay", "on"]) => Route::Command(CliCommand::Overlay { state: OverlayState::On }),
+        ("POST", ["api", "overlay", "off"]) => Route::Command(CliCommand::Overlay { state: OverlayState::Off }),
+        ("GET", ["api", "status"]) => Route::Command(CliCommand::Status),
+        ("GET", ["api", "ws"]) => Route::WebSocket,
+        ("GET", ["api", "streamdeck"]) => Route::StreamDeck,
+        _ => return Err((404, "Not Found")),
+    };
+    Ok(route)
+}
+
+/// Decode a query value: '+' is a space there, then %XX escapes as in paths
+fn query_decode(text: &str) -> String {
+    percent_decode(&text.replace('+', " "))
+}
+
+/// Decode %XX escapes (profile names with spaces etc.); invalid escapes are kept as they are
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Request line and the headers we look at
+#[derive(Debug, Default)]
+struct RequestHead {
+    method: String,
+    path: String,
+    query_token: Option<String>,
+    bearer_token: Option<String>,
+    websocket_key: Option<String>,
+}
+
+impl RequestHead {
+    fn token(&self) -> Option<&str> {
+        self.bearer_token.as_deref().or(self.query_token.as_deref())
+    }
+}
+
+/// Read one line of at most `MAX_LINE_LEN` bytes (0 = end of stream)
+fn read_limited_line(reader: &mut impl BufRead, line: &mut String) -> Result<usize> {
+    let read = reader
+        .take(MAX_LINE_LEN)
+        .read_line(line)
+        .map_err(|e| anyhow!("Failed to read request: {}", e))?;
+    if read as u64 == MAX_LINE_LEN && !line.ends_with('\n') {
+        return Err(anyhow!("Request line too long"));
+    }
+    Ok(read)
+}
+
+fn read_head(reader: &mut impl BufRead) -> Result<RequestHead> {
+    let mut line = String::new();
+    read_limited_line(reader, &mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(anyhow!("Malformed request line"));
+    };
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut head = RequestHead {
+        method: method.to_string(),
+        path: path.to_string(),
+        query_token: query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .map(query_decode),
+        ..Default::default()
+    };
+
+    for _ in 0..=MAX_HEADERS {
+        line.clear();
+        if read_limited_line(reader, &mut line)? == 0 {
+            return Ok(head);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Ok(head);
+        }
+        let Some((name, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "authorization" => head.bearer_token = value.strip_prefix("Bearer ").map(str::to_string),
+            "sec-websocket-key" => head.websocket_key = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    Err(anyhow!("Too many request headers"))
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, reason: &str, body: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )
+}
+
+fn reply_json(reply: &CliReply) -> String {
+    serde_json::to_string(reply).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Running API server; stops when dropped
+pub struct RemoteApi {
+    stop: Arc<AtomicBool>,
+    listener_thread: Option<JoinHandle<()>>,
+}
+
+impl RemoteApi {
+    /// Start listening on 127.0.0.1:`config.port`
+    pub fn spawn(config: &ApiConfig, requests: RequestSender) -> Result<Self> {
+        if crate::config::is_safe_mode() {
+            return Err(anyhow!("The remote API is off in safe mode"));
+        }
+        if config.token.is_empty() {
+            return Err(anyhow!("No API token set"));
+        }
+        let listener = TcpListener::bind(("127.0.0.1", config.port))
+            .map_err(|e| anyhow!("Failed to listen on port {}: {}", config.port, e))?;
+        // Non-blocking accept so the thread notices `stop`
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| anyhow!("Failed to configure listener: {}", e))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let token = config.token.clone();
+        let limit = ConnectionLimit::new(MAX_API_CONNECTIONS);
+        info!("[API] Listening on 127.0.0.1:{}", config.port);
+
+        let listener_thread = std::thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        let Some(slot) = limit.try_acquire() else {
+                            warn!("[API] Too many connections, closing a new one");
+                            continue;
+                        };
+                        let (token, requests, stop) = (token.clone(), requests.clone(), thread_stop.clone());
+                        std::thread::spawn(move || {
+                            let _slot = slot;
+                            if let Err(e) = handle_connection(stream, &token, &requests, &stop) {
+                                warn!("[API] {}", e);
+                            }
+                        });
+                    }
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(Duration::from_millis(100));
+                    }
+                    Err(e) => {
+                        warn!("[API] Failed to accept connection: {}", e);
+                        std::thread::sleep(Duration::from_millis(100));
+                    }
+                }
+            }
+            info!("[API] Stopped");
+        });
+
+        Ok(RemoteApi { stop, listener_thread: Some(listener_thread) })
+    }
+}
+
+impl Drop for RemoteApi {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wait for the listener to close so a restarted server can bind the same port
+        if let Some(thread) = self.listener_thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, token: &str, requests: &RequestSender, stop: &AtomicBool) -> Result<()> {
+    stream.set_nonblocking(false).map_err(|e| anyhow!("Failed to configure connection: {}", e))?;
+    stream
+        .set_read_timeout(Some(IO_TIMEOUT))
+        .and_then(|()| stream.set_write_timeout(Some(IO_TIMEOUT)))
+        .map_err(|e| anyhow!("Failed to configure connection: {}", e))?;
+
+    let head = read_head(&mut BufReader::new(&stream))?;
+    if !head.token().is_some_and(|given| tokens_match(given, token)) {
+        let body = reply_json(&CliReply::error("Missing or wrong token"));
+        return write_response(&mut stream, 401, "Unauthorized", &body).map_err(|e| anyhow!("Failed to respond: {}", e));
+    }
+
+    match route(&head.method, &head.path) {
+        Ok(Route::Command(command)) => {
+            let reply = requests.request(command);
+            let (status, reason) = if reply.ok { (200, "OK") } else { (400, "Bad Request") };
+            write_response(&mut stream, status, reason, &reply_json(&reply))
+                .map_err(|e| anyhow!("Failed to respond: {}", e))
+        }
+        Ok(route @ (Route::WebSocket | Route::StreamDeck)) => match head.websocket_key {
+            Some(ref key) => {
+                let socket = accept_websocket(stream, key)?;
+                if route == Route::StreamDeck {
+                    streamdeck::serve(socket, requests, stop)
+                } else {
+                    serve_websocket(socket, requests, stop)
+                }
+            }
+            None => write_response(&mut stream, 400, "Bad Request", &reply_json(&CliReply::error("Expected a WebSocket upgrade")))
+                .map_err(|e| anyhow!("Failed to respond: {}", e)),
+        },
+        Err((status, reason)) => write_response(&mut stream, status, reason, &reply_json(&CliReply::error(reason)))
+            .map_err(|e| anyhow!("Failed to respond: {}", e)),
+    }
+}
+
+/// Finish the WebSocket upgrade for a request that carried `Sec-WebSocket-Key`
+fn accept_websocket(mut stream: TcpStream, key: &str) -> Result<WebSocket<TcpStream>> {
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        tungstenite::handshake::derive_accept_key(key.as_bytes())
+    )
+    .map_err(|e| anyhow!("Failed to complete WebSocket handshake: {}", e))?;
+    Ok(WebSocket::from_raw_socket(stream, Role::Server, None))
+}
+
+/// Set how long a read waits before giving the session a chance to push updates
+pub(crate) fn set_poll_interval(socket: &WebSocket<TcpStream>, interval: Duration) -> Result<()> {
+    socket
+        .get_ref()
+        .set_read_timeout(Some(interval))
+        .map_err(|e| anyhow!("Failed to configure connection: {}", e))
+}
+
+/// A read that ran into the poll interval rather than a real error
+pub(crate) fn is_timeout(error: &WsError) -> bool {
+    matches!(error, WsError::Io(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut))
+}
+
+fn serve_websocket(mut socket: WebSocket<TcpStream>, requests: &RequestSender, stop: &AtomicBool) -> Result<()> {
+    // The read timeout doubles as the status push interval
+    set_poll_interval(&socket, Duration::from_secs(STATUS_PUSH_SECS))?;
+
+    while !stop.load(Ordering::Relaxed) {
+        let reply = match socket.read() {
+            Ok(WsMessage::Text(text)) => match serde_json::from_str::<CliCommand>(&text) {
+                Ok(command) => requests.request(command),
+                Err(e) => CliReply::error(format!("Invalid command: {}", e)),
+            },
+            Ok(WsMessage::Close(_)) | Err(WsError::ConnectionClosed) => return Ok(()),
+            Ok(_) => continue,
+            Err(ref e) if is_timeout(e) => requests.request(CliCommand::Status),
+            Err(e) => return Err(anyhow!("WebSocket error: {}", e)),
+        };
+        socket
+            .send(WsMessage::Text(reply_json(&reply)))
+            .map_err(|e| anyhow!("Failed to send WebSocket message: {}", e))?;
+    }
+    let _ = socket.close(None);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes() {
+        assert_eq!(route("GET", "/api/profiles"), Ok(Route::Command(CliCommand::List)));
+        assert_eq!(
+            route("POST", "/api/profiles/Apex%20Legends/activate"),
+            Ok(Route::Command(CliCommand::Activate { profile: "Apex Legends".to_string() }))
+        );
+        assert_eq!(
+            route("POST", "/api/profiles/C++/activate"),
+            Ok(Route::Command(CliCommand::Activate { profile: "C++".to_string() }))
+        );
+        assert_eq!(route("POST", "/api/overlay/off"), Ok(Route::Command(CliCommand::Overlay { state: OverlayState::Off })));
+        assert_eq!(route("GET", "/api/ws"), Ok(Route::WebSocket));
+        assert_eq!(route("GET", "/api/streamdeck"), Ok(Route::StreamDeck));
+        assert_eq!(route("GET", "/api/deactivate"), Err((404, "Not Found")));
+    }
+
+    #[test]
+    fn test_read_head_extracts_tokens() {
+        let request = "GET /api/status?x=1&token=abc%2B1 HTTP/1.1\r\nHost: localhost\r\nauthorization: Bearer secret\r\n\r\n";
+        let head = read_head(&mut BufReader::new(request.as_bytes())).unwrap();
+        assert_eq!(head.method, "GET");
+        assert_eq!(head.path, "/api/status");
+        assert_eq!(head.query_token.as_deref(), Some("abc+1"));
+        // The header wins over the query string
+        assert_eq!(head.token(), Some("secret"));
+
+        let head = read_head(&mut BufReader::new("GET /api/status?token=a+b HTTP/1.1\r\n\r\n".as_bytes())).unwrap();
+        assert_eq!(head.query_token.as_deref(), Some("a b"));
+    }
+
+    #[test]
+    fn test_read_head_limits() {
+        let long_line = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE_LEN as usize));
+        assert!(read_head(&mut BufReader::new(long_line.as_bytes())).is_err());
+
+        let many_headers = format!("GET / HTTP/1.1\r\n{}\r\n", "X-A: b\r\n".repeat(MAX_HEADERS + 1));
+        assert!(read_head(&mut BufReader::new(many_headers.as_bytes())).is_err());
+
+        let enough_headers = format!("GET / HTTP/1.1\r\n{}\r\n", "X-A: b\r\n".repeat(MAX_HEADERS));
+        assert!(read_head(&mut BufReader::new(enough_headers.as_bytes())).is_ok());
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        let token = generate_token().unwrap();
+        assert_eq!(token.len(), 64);
+        assert!(token.bytes().all(|byte| byte.is_ascii_hexdigit() && !byte.is_ascii_uppercase()));
+        assert!(tokens_match(&token, &token));
+        assert!(!tokens_match(&token, &generate_token().unwrap()));
+        assert!(!tokens_match("", &token));
+    }
+}
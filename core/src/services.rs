@@ -0,0 +1,40 @@
+//! Windows services paused while a profile is active
+//!
+//! Network and storage settings stop a service (Delivery Optimization, Windows Search) and
+//! start it again on deactivation. They go through `ServiceBackend`, which is sc.exe on this
+//! machine and a fake in tests.
+
+use crate::elevation;
+use crate::shell::{run_hidden, service_running};
+use anyhow::Result;
+
+/// Querying, stopping and starting services
+pub trait ServiceBackend: Send {
+    /// Err when this run can't stop or start services (not elevated)
+    fn check_access(&self) -> Result<()>;
+    fn is_running(&mut self, name: &str) -> Result<bool>;
+    fn stop(&mut self, name: &str) -> Result<()>;
+    fn start(&mut self, name: &str) -> Result<()>;
+}
+
+/// This machine's services, through sc.exe
+#[derive(Default)]
+pub struct ScServices;
+
+impl ServiceBackend for ScServices {
+    fn check_access(&self) -> Result<()> {
+        elevation::require_admin()
+    }
+
+    fn is_running(&mut self, name: &str) -> Result<bool> {
+        run_hidden("sc.exe", &["query", name]).map(|status| service_running(&status))
+    }
+
+    fn stop(&mut self, name: &str) -> Result<()> {
+        run_hidden("sc.exe", &["stop", name]).map(|_| ())
+    }
+
+    fn start(&mut self, name: &str) -> Result<()> {
+        run_hidden("sc.exe", &["start", name]).map(|_| ())
+    }
+}
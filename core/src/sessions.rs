@@ -0,0 +1,382 @@
+//! Gaming session statistics
+//!
+//! A session runs from a profile's activation to its deactivation. While it runs, the GUI feeds
+//! it hardware samples and the foreground process; the most-seen foreground process is taken
+//! as the game. Finished sessions are appended to sessions.json for the Stats view.
+
+use crate::latency::LatencyStats;
+use crate::monitor::HardwareSnapshot;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Foreground processes that are never the game
+const NOT_GAMES: &[&str] = &["explorer.exe", "crosshair.exe", "searchhost.exe", "shellexperiencehost.exe"];
+
+/// A finished session as stored in sessions.json
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Session {
+    pub profile: String,
+    /// Executable that was in the foreground most of the time (None = nothing but the desktop)
+    pub game: Option<String>,
+    /// Unix time (seconds) the profile was activated
+    pub started: u64,
+    pub duration_secs: u64,
+    pub avg_cpu_load: Option<f32>,
+    pub avg_gpu_load: Option<f32>,
+    pub avg_cpu_temp: Option<f32>,
+    pub avg_gpu_temp: Option<f32>,
+    pub processes_killed: usize,
+    /// Ping to the game's server in milliseconds (None = no server found)
+    #[serde(default)]
+    pub avg_ping_ms: Option<f32>,
+    #[serde(default)]
+    pub avg_jitter_ms: Option<f32>,
+}
+
+impl Session {
+    /// Game, or the profile name when no game was seen
+    pub fn title(&self) -> &str {
+        self.game.as_deref().unwrap_or(&self.profile)
+    }
+}
+
+/// Running mean of readings that may be missing
+#[derive(Default)]
+struct Average {
+    sum: f64,
+    count: u32,
+}
+
+impl Average {
+    fn add(&mut self, value: Option<f32>) {
+        if let Some(value) = value {
+            self.sum += value as f64;
+            self.count += 1;
+        }
+    }
+
+    fn mean(&self) -> Option<f32> {
+        (self.count > 0).then(|| (self.sum / self.count as f64) as f32)
+    }
+}
+
+/// Session of the active profile, collecting samples until it is finished
+pub struct SessionRecorder {
+    profile: String,
+    started: u64,
+    started_at: Instant,
+    processes_killed: usize,
+    cpu_load: Average,
+    gpu_load: Average,
+    cpu_temp: Average,
+    gpu_temp: Average,
+    ping: Average,
+    jitter: Average,
+    /// Lower-cased executable name -> samples it was in the foreground
+    foreground: HashMap<String, u32>,
+}
+
+impl SessionRecorder {
+    pub fn start(profile: &str, processes_killed: usize) -> Self {
+        SessionRecorder {
+            profile: profile.to_string(),
+            started: unix_time(),
+            started_at: Instant::now(),
+            processes_killed,
+            cpu_load: Average::default(),
+            gpu_load: Average::default(),
+            cpu_temp: Average::default(),
+            gpu_temp: Average::default(),
+            ping: Average::default(),
+            jitter: Average::default(),
+            foreground: HashMap::new(),
+        }
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// Add one hardware sample and the process that was in the foreground at the time
+    pub fn sample(&mut self, snapshot: &HardwareSnapshot, foreground: Option<&str>) {
+        self.cpu_load.add(snapshot.cpu_load);
+        self.gpu_load.add(snapshot.gpu_load);
+        self.cpu_temp.add(snapshot.cpu_temp);
+        self.gpu_temp.add(snapshot.gpu_temp);
+
+        let own_exe = std::env::current_exe()
+            .ok()
+            .and_then(|path| path.file_name().map(|name| name.to_string_lossy().to_lowercase()));
+        if let Some(name) = foreground.map(str::to_lowercase) {
+            if !NOT_GAMES.contains(&name.as_str()) && Some(&name) != own_exe.as_ref() {
+                *self.foreground.entry(name).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Add one ping reading for the game's server
+    pub fn sample_latency(&mut self, latency: &LatencyStats) {
+        self.ping.add(latency.current_ms);
+        self.jitter.add(latency.jitter_ms);
+    }
+
+    /// Process seen in the foreground most so far (the game, once one is running)
+    pub fn current_game(&self) -> Option<&str> {
+        // Ties go to the alphabetically first name so the result doesn't depend on hash order
+        self.foreground
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn finish(self) -> Session {
+        Session {
+            game: self.current_game().map(str::to_string),
+            profile: self.profile,
+            started: self.started,
+            duration_secs: self.started_at.elapsed().as_secs(),
+            avg_cpu_load: self.cpu_load.mean(),
+            avg_gpu_load: self.gpu_load.mean(),
+            avg_cpu_temp: self.cpu_temp.mean(),
+            avg_gpu_temp: self.gpu_temp.mean(),
+            processes_killed: self.processes_killed,
+            avg_ping_ms: self.ping.mean(),
+            avg_jitter_ms: self.jitter.mean(),
+        }
+    }
+}
+
+/// Totals for one game (or profile, when no game was detected)
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameTotals {
+    pub title: String,
+    pub sessions: usize,
+    pub total_secs: u64,
+    pub processes_killed: usize,
+    pub avg_cpu_temp: Option<f32>,
+    pub avg_gpu_temp: Option<f32>,
+    pub avg_ping_ms: Option<f32>,
+}
+
+/// Per-game totals, most played first
+pub fn per_game_totals(sessions: &[Session]) -> Vec<GameTotals> {
+    let mut totals: Vec<GameTotals> = Vec::new();
+    let mut averages: Vec<[Average; 3]> = Vec::new();
+
+    for session in sessions {
+        let index = match totals.iter().position(|t| t.title == session.title()) {
+            Some(index) => index,
+            None => {
+                totals.push(GameTotals {
+                    title: session.title().to_string(),
+                    sessions: 0,
+                    total_secs: 0,
+                    processes_killed: 0,
+                    avg_cpu_temp: None,
+                    avg_gpu_temp: None,
+                    avg_ping_ms: None,
+                });
+                averages.push(Default::default());
+                totals.len() - 1
+            }
+        };
+        totals[index].sessions += 1;
+        totals[index].total_secs += session.duration_secs;
+        totals[index].processes_killed += session.processes_killed;
+        averages[index][0].add(session.avg_cpu_temp);
+        averages[index][1].add(session.avg_gpu_temp);
+        averages[index][2].add(session.avg_ping_ms);
+    }
+
+    for (total, [cpu, gpu, ping]) in totals.iter_mut().zip(&averages) {
+        total.avg_cpu_temp = cpu.mean();
+        total.avg_gpu_temp = gpu.mean();
+        total.avg_ping_ms = ping.mean();
+    }
+    totals.sort_by_key(|t| std::cmp::Reverse(t.total_secs));
+    totals
+}
+
+/// Sessions started in the 7 days before `now` (Unix seconds)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WeeklySummary {
+    pub sessions: usize,
+    pub total_secs: u64,
+    pub longest_secs: u64,
+    pub most_played: Option<String>,
+}
+
+pub fn weekly_summary(sessions: &[Session], now: u64) -> WeeklySummary {
+    let week: Vec<Session> = sessions
+        .iter()
+        .filter(|s| s.started <= now && now - s.started < WEEK_SECS)
+        .cloned()
+        .collect();
+
+    WeeklySummary {
+        sessions: week.len(),
+        total_secs: week.iter().map(|s| s.duration_secs).sum(),
+        longest_secs: week.iter().map(|s| s.duration_secs).max().unwrap_or(0),
+        most_played: per_game_totals(&week).into_iter().next().map(|t| t.title),
+    }
+}
+
+/// "2h 05m", "14m", "40s"
+pub fn format_duration(secs: u64) -> String {
+    match (secs / 3600, secs % 3600 / 60) {
+        (0, 0) => format!("{}s", secs),
+        (0, minutes) => format!("{}m", minutes),
+        (hours, minutes) => format!("{}h {:02}m", hours, minutes),
+    }
+}
+
+pub fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Load finished sessions from sessions.json (empty if there are none yet)
+pub fn load_sessions(data_dir: &Path) -> Result<Vec<Session>> {
+    let sessions_path = data_dir.join("sessions.json");
+    if !sessions_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&sessions_path)
+        .map_err(|e| anyhow!("Failed to read sessions.json: {}", e))?;
+
+    serde_json::from_str(&contents)
+        .map_err(|e| anyhow!("Failed to parse sessions.json: {}", e))
+}
+
+/// Save all sessions to sessions.json
+pub fn save_sessions(sessions: &[Session], data_dir: &Path) -> Result<()> {
+    fs::create_dir_all(data_dir)
+        .map_err(|e| anyhow!("Failed to create data directory: {}", e))?;
+
+    let json = serde_json::to_string_pretty(sessions)
+        .map_err(|e| anyhow!("Failed to serialize sessions: {}", e))?;
+
+    fs::write(data_dir.join("sessions.json"), json)
+        .map_err(|e| anyhow!("Failed to write sessions.json: {}", e))
+}
+
+/// Executable name of the process owning the foreground window
+#[cfg(windows)]
+pub fn foreground_process() -> Option<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    unsafe {
+        let window = GetForegroundWindow();
+        if window.0 == 0 {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(window, Some(&mut pid));
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+
+        let mut buffer = [0u16; 260];
+        let mut len = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut len);
+        let _ = CloseHandle(process);
+        result.ok()?;
+
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        path.rsplit('\\').next().map(str::to_string)
+    }
+}
+
+#[cfg(not(windows))]
+pub fn foreground_process() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(profile: &str, game: Option<&str>, started: u64, duration_secs: u64) -> Session {
+        Session {
+            profile: profile.to_string(),
+            game: game.map(str::to_string),
+            started,
+            duration_secs,
+            avg_cpu_load: None,
+            avg_gpu_load: None,
+            avg_cpu_temp: Some(60.0),
+            avg_gpu_temp: None,
+            processes_killed: 2,
+            avg_ping_ms: None,
+            avg_jitter_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_recorder_picks_most_seen_game() {
+        let mut recorder = SessionRecorder::start("FPS", 3);
+        let snapshot = HardwareSnapshot { cpu_load: Some(40.0), cpu_temp: Some(70.0), ..Default::default() };
+        recorder.sample(&snapshot, Some("VALORANT-Win64-Shipping.exe"));
+        recorder.sample(&HardwareSnapshot { cpu_load: Some(60.0), ..Default::default() }, Some("explorer.exe"));
+        recorder.sample(&snapshot, Some("discord.exe"));
+        recorder.sample(&snapshot, Some("valorant-win64-shipping.exe"));
+
+        let session = recorder.finish();
+        assert_eq!(session.game.as_deref(), Some("valorant-win64-shipping.exe"));
+        assert_eq!(session.avg_cpu_load, Some(45.0));
+        assert_eq!(session.avg_cpu_temp, Some(70.0));
+        assert_eq!(session.avg_gpu_temp, None);
+        assert_eq!(session.processes_killed, 3);
+    }
+
+    #[test]
+    fn test_per_game_totals() {
+        let sessions = [
+            session("FPS", Some("cs2.exe"), 0, 600),
+            session("FPS", None, 0, 300),
+            session("FPS", Some("cs2.exe"), 0, 1200),
+        ];
+        let totals = per_game_totals(&sessions);
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].title, "cs2.exe");
+        assert_eq!(totals[0].sessions, 2);
+        assert_eq!(totals[0].total_secs, 1800);
+        assert_eq!(totals[0].processes_killed, 4);
+        assert_eq!(totals[0].avg_cpu_temp, Some(60.0));
+        assert_eq!(totals[1].title, "FPS");
+    }
+
+    #[test]
+    fn test_weekly_summary_ignores_older_sessions() {
+        let now = 10 * WEEK_SECS;
+        let sessions = [
+            session("FPS", Some("cs2.exe"), now - WEEK_SECS - 1, 5000),
+            session("FPS", Some("cs2.exe"), now - 3600, 1800),
+            session("MOBA", Some("league.exe"), now - 60, 2400),
+        ];
+        let summary = weekly_summary(&sessions, now);
+        assert_eq!(summary.sessions, 2);
+        assert_eq!(summary.total_secs, 4200);
+        assert_eq!(summary.longest_secs, 2400);
+        assert_eq!(summary.most_played.as_deref(), Some("league.exe"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(40), "40s");
+        assert_eq!(format_duration(14 * 60 + 5), "14m");
+        assert_eq!(format_duration(2 * 3600 + 5 * 60), "2h 05m");
+    }
+}
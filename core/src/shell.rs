@@ -0,0 +1,68 @@
+//! Helpers for running hidden helper processes (sc, PowerShell, vendor tools)
+
+use anyhow::{anyhow, Result};
+use std::process::{Command, Stdio};
+
+/// Run a command without flashing a console window, returning stdout on success
+pub fn run_hidden(program: &str, args: &[&str]) -> Result<String> {
+    let mut command = Command::new(program);
+    command.args(args).stdin(Stdio::null());
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| anyhow!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("{} exited with {}: {}", program, output.status, stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Run a PowerShell snippet (vendor WMI/ACPI methods, NetQos cmdlets)
+pub fn run_powershell(script: &str) -> Result<String> {
+    run_hidden(
+        "powershell.exe",
+        &["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script],
+    )
+}
+
+/// Quote a value as a PowerShell single-quoted string literal
+pub fn ps_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Whether `sc query` output reports a running service
+pub fn service_running(sc_output: &str) -> bool {
+    sc_output
+        .lines()
+        .any(|line| line.trim_start().starts_with("STATE") && line.contains("RUNNING"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ps_quote() {
+        assert_eq!(ps_quote("steam.exe"), "'steam.exe'");
+        assert_eq!(ps_quote("it's.exe"), "'it''s.exe'");
+    }
+
+    #[test]
+    fn test_service_running() {
+        let running = "SERVICE_NAME: DoSvc\r\n        TYPE               : 20  WIN32_SHARE_PROCESS\r\n        STATE              : 4  RUNNING\r\n";
+        let stopped = "SERVICE_NAME: DoSvc\r\n        STATE              : 1  STOPPED\r\n";
+        assert!(service_running(running));
+        assert!(!service_running(stopped));
+        assert!(!service_running(""));
+    }
+}
@@ -0,0 +1,64 @@
+//! Single instance guard
+//!
+//! A named mutex marks the running copy. A second launch forwards what it was started for
+//! (show the window, or activate a profile after a crash restart) over the CLI pipe and exits,
+//! so there is never a second tray icon or overlay.
+
+#[cfg(windows)]
+const MUTEX_NAME: &str = "Local\\GamingOptimizer.Instance";
+
+/// Mutex handle held by the running instance (as an isize so it can live in a static)
+#[cfg(windows)]
+static INSTANCE_MUTEX: std::sync::Mutex<Option<isize>> = std::sync::Mutex::new(None);
+
+/// Claim the instance for the lifetime of the process (false if another copy is running)
+#[cfg(windows)]
+pub fn acquire() -> bool {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::{CloseHandle, GetLastError, ERROR_ALREADY_EXISTS};
+    use windows::Win32::System::Threading::CreateMutexW;
+
+    let handle = match unsafe { CreateMutexW(None, false, &HSTRING::from(MUTEX_NAME)) } {
+        Ok(handle) => handle,
+        // Without the mutex we can't tell; running is better than refusing to start
+        Err(e) => {
+            tracing::warn!("[Instance] Failed to create instance mutex: {}", e);
+            return true;
+        }
+    };
+
+    // CreateMutexW opens the existing mutex and reports ERROR_ALREADY_EXISTS through GetLastError
+    let already_running = unsafe { GetLastError() }
+        .err()
+        .is_some_and(|e| e.code() == ERROR_ALREADY_EXISTS.to_hresult());
+    if already_running {
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+        return false;
+    }
+
+    if let Ok(mut guard) = INSTANCE_MUTEX.lock() {
+        *guard = Some(handle.0);
+    }
+    true
+}
+
+/// Give up the instance early so a restarted copy can start while this one shuts down
+#[cfg(windows)]
+pub fn release() {
+    let handle = INSTANCE_MUTEX.lock().ok().and_then(|mut guard| guard.take());
+    if let Some(handle) = handle {
+        unsafe {
+            let _ = windows::Win32::Foundation::CloseHandle(windows::Win32::Foundation::HANDLE(handle));
+        }
+    }
+}
+
+#[cfg(not(windows))]
+pub fn acquire() -> bool {
+    true
+}
+
+#[cfg(not(windows))]
+pub fn release() {}
@@ -0,0 +1,184 @@
+//! Storage-friendly mode while a profile is active
+//!
+//! Stops Windows Search indexing and disables the defrag and automatic maintenance
+//! scheduled tasks so background disk work doesn't hitch games (mostly on HDDs). Only things
+//! that were running/enabled are touched, and they're resumed on deactivation. Both need
+//! administrator rights.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use crate::services::ServiceBackend;
+use crate::shell::{ps_quote, run_powershell};
+use tracing::info;
+
+/// Windows Search indexer service
+const SEARCH_SERVICE: &str = "WSearch";
+
+/// Scheduled tasks that start disk-heavy background work
+const MAINTENANCE_TASKS: [&str; 3] = [
+    "\\Microsoft\\Windows\\Defrag\\ScheduledDefrag",
+    "\\Microsoft\\Windows\\TaskScheduler\\Regular Maintenance",
+    "\\Microsoft\\Windows\\TaskScheduler\\Idle Maintenance",
+];
+
+/// Background disk activity paused while a profile is active
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StorageSettings {
+    /// Stop the Windows Search indexer
+    #[serde(default)]
+    pub pause_search_indexing: bool,
+    /// Disable the defrag/optimize and automatic maintenance tasks
+    #[serde(default)]
+    pub pause_maintenance_tasks: bool,
+}
+
+impl StorageSettings {
+    /// Whether anything is paused
+    pub fn is_empty(&self) -> bool {
+        !self.pause_search_indexing && !self.pause_maintenance_tasks
+    }
+}
+
+/// What `StorageSwitcher::apply` paused
+#[derive(Debug, Default)]
+pub struct StorageReport {
+    /// e.g. "Search indexing paused"
+    pub applied: Vec<String>,
+    /// Things that couldn't be paused, with the reason
+    pub failed: Vec<String>,
+}
+
+/// Split a full task path into the `-TaskPath` folder and `-TaskName`
+fn split_task_path(task: &str) -> (&str, &str) {
+    match task.rfind('\\') {
+        Some(index) => (&task[..=index], &task[index + 1..]),
+        None => ("\\", task),
+    }
+}
+
+/// Stop (if running) and disable a scheduled task. Returns false if it was already disabled.
+fn disable_task(task: &str) -> Result<bool> {
+    let (path, name) = split_task_path(task);
+    let output = run_powershell(&format!(
+        "$task = Get-ScheduledTask -TaskPath {} -TaskName {} -ErrorAction Stop; \
+         if ($task.State -eq 'Disabled') {{ 'Disabled' }} else {{ \
+         if ($task.State -eq 'Running') {{ $task | Stop-ScheduledTask }}; \
+         $task | Disable-ScheduledTask -ErrorAction Stop | Out-Null; 'Paused' }}",
+        ps_quote(path),
+        ps_quote(name)
+    ))?;
+    Ok(output.trim() == "Paused")
+}
+
+fn enable_task(task: &str) -> Result<()> {
+    let (path, name) = split_task_path(task);
+    run_powershell(&format!(
+        "Enable-ScheduledTask -TaskPath {} -TaskName {} -ErrorAction Stop | Out-Null",
+        ps_quote(path),
+        ps_quote(name)
+    ))
+    .map(|_| ())
+}
+
+/// Pauses a profile's background disk activity and resumes it afterwards
+#[derive(Default)]
+pub struct StorageSwitcher {
+    /// Whether we stopped the indexer (and should start it again)
+    stopped_search: bool,
+    /// Tasks we disabled
+    disabled_tasks: Vec<&'static str>,
+}
+
+impl StorageSwitcher {
+    /// Resume anything a previous profile paused, then pause what the new one asks for
+    pub fn apply(&mut self, settings: &StorageSettings, services: &mut dyn ServiceBackend) -> Result<StorageReport> {
+        self.restore(services)?;
+
+        let mut report = StorageReport::default();
+        if settings.is_empty() {
+            return Ok(report);
+        }
+
+        if settings.pause_search_indexing {
+            match self.stop_search(services) {
+                Ok(()) => report.applied.push("Search indexing paused".to_string()),
+                Err(e) => report.failed.push(format!("Search indexing: {}", e)),
+            }
+        }
+
+        if settings.pause_maintenance_tasks {
+            let mut failed = Vec::new();
+            for task in MAINTENANCE_TASKS {
+                match disable_task(task) {
+                    Ok(true) => self.disabled_tasks.push(task),
+                    Ok(false) => {}
+                    Err(e) => failed.push(format!("{}: {}", split_task_path(task).1, e)),
+                }
+            }
+            if failed.is_empty() {
+                report.applied.push("Defrag/maintenance paused".to_string());
+            } else {
+                report.failed.extend(failed);
+            }
+        }
+
+        if !report.applied.is_empty() {
+            info!("[Storage] {}", report.applied.join(", "));
+        }
+        Ok(report)
+    }
+
+    fn stop_search(&mut self, services: &mut dyn ServiceBackend) -> Result<()> {
+        if !services.is_running(SEARCH_SERVICE)? {
+            // Indexing is disabled on this machine - nothing to resume later
+            return Ok(());
+        }
+        services
+            .stop(SEARCH_SERVICE)
+            .map_err(|e| anyhow!("Failed to stop {} (run as administrator?): {}", SEARCH_SERVICE, e))?;
+        self.stopped_search = true;
+        Ok(())
+    }
+
+    /// Restart the indexer and re-enable our tasks (no-op if nothing was paused)
+    pub fn restore(&mut self, services: &mut dyn ServiceBackend) -> Result<()> {
+        let mut first_error = None;
+
+        for task in std::mem::take(&mut self.disabled_tasks) {
+            if let Err(e) = enable_task(task) {
+                first_error.get_or_insert(anyhow!("Failed to re-enable {}: {}", task, e));
+            }
+        }
+
+        if std::mem::take(&mut self.stopped_search) {
+            if let Err(e) = services.start(SEARCH_SERVICE) {
+                first_error.get_or_insert(anyhow!("Failed to restart {}: {}", SEARCH_SERVICE, e));
+            } else {
+                info!("[Storage] Search indexing resumed");
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_task_path() {
+        assert_eq!(
+            split_task_path("\\Microsoft\\Windows\\Defrag\\ScheduledDefrag"),
+            ("\\Microsoft\\Windows\\Defrag\\", "ScheduledDefrag")
+        );
+        assert_eq!(
+            split_task_path("\\Microsoft\\Windows\\TaskScheduler\\Regular Maintenance"),
+            ("\\Microsoft\\Windows\\TaskScheduler\\", "Regular Maintenance")
+        );
+        assert_eq!(split_task_path("MyTask"), ("\\", "MyTask"));
+    }
+}
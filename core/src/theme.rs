@@ -0,0 +1,95 @@
+//! Appearance settings
+//!
+//! Dark/light mode and accent color as stored in the config; the GUI turns them into its theme
+//! (gui/styles.rs).
+
+use crate::i18n::t;
+use crate::registry::{self, Hive};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const PERSONALIZE_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize";
+const APPS_USE_LIGHT_THEME: &str = "AppsUseLightTheme";
+
+/// Dark or light (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    /// Follow the Windows app mode
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 3] = [ThemeMode::System, ThemeMode::Dark, ThemeMode::Light];
+}
+
+impl fmt::Display for ThemeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeMode::System => write!(f, "{}", t!("theme-mode-system")),
+            ThemeMode::Dark => write!(f, "{}", t!("theme-mode-dark")),
+            ThemeMode::Light => write!(f, "{}", t!("theme-mode-light")),
+        }
+    }
+}
+
+/// Accent color for interactive widgets (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Accent {
+    #[default]
+    Blue,
+    Purple,
+    Green,
+    Orange,
+    Red,
+    Pink,
+}
+
+impl Accent {
+    pub const ALL: [Accent; 6] = [Accent::Blue, Accent::Purple, Accent::Green, Accent::Orange, Accent::Red, Accent::Pink];
+
+    /// Red, green and blue of the accent
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Accent::Blue => [0x3B, 0x82, 0xF6],
+            Accent::Purple => [0x8B, 0x5C, 0xF6],
+            Accent::Green => [0x10, 0xB9, 0x81],
+            Accent::Orange => [0xF5, 0x9E, 0x0B],
+            Accent::Red => [0xEF, 0x44, 0x44],
+            Accent::Pink => [0xEC, 0x48, 0x99],
+        }
+    }
+}
+
+impl fmt::Display for Accent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Accent::Blue => write!(f, "{}", t!("accent-blue")),
+            Accent::Purple => write!(f, "{}", t!("accent-purple")),
+            Accent::Green => write!(f, "{}", t!("accent-green")),
+            Accent::Orange => write!(f, "{}", t!("accent-orange")),
+            Accent::Red => write!(f, "{}", t!("accent-red")),
+            Accent::Pink => write!(f, "{}", t!("accent-pink")),
+        }
+    }
+}
+
+/// Appearance settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub mode: ThemeMode,
+    pub accent: Accent,
+}
+
+/// Whether Windows is set to dark mode for apps (dark if it can't be read, matching games)
+pub fn system_prefers_dark() -> bool {
+    registry::read_dword(Hive::CurrentUser, PERSONALIZE_KEY, APPS_USE_LIGHT_THEME)
+        .ok()
+        .flatten()
+        .is_none_or(|light| light == 0)
+}
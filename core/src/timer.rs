@@ -0,0 +1,156 @@
+//! System timer resolution control
+//!
+//! Windows ticks at 15.625 ms by default. Old games and some frame limiters sleep far more
+//! accurately at the finest resolution, which is requested through NtSetTimerResolution
+//! (falling back to timeBeginPeriod(1)) while a profile is active. The request is tied to
+//! this process and released on restore.
+
+use anyhow::{anyhow, Result};
+use tracing::info;
+#[cfg(windows)]
+use crate::ntdll;
+
+/// Timer resolutions in 100 ns units (smaller = finer)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimerResolution {
+    /// Coarsest supported resolution (largest interval)
+    pub coarsest: u32,
+    /// Finest supported resolution (smallest interval)
+    pub finest: u32,
+    pub current: u32,
+}
+
+/// Format a 100 ns interval as milliseconds, e.g. 5000 -> "0.500 ms"
+pub fn format_100ns(value: u32) -> String {
+    format!("{:.3} ms", value as f64 / 10_000.0)
+}
+
+#[cfg(windows)]
+type NtQueryTimerResolutionFn = unsafe extern "system" fn(*mut u32, *mut u32, *mut u32) -> i32;
+#[cfg(windows)]
+type NtSetTimerResolutionFn = unsafe extern "system" fn(u32, u8, *mut u32) -> i32;
+
+/// Query the supported and current system timer resolution
+pub fn query_timer_resolution() -> Result<TimerResolution> {
+    #[cfg(windows)]
+    unsafe {
+        let proc = ntdll::proc_address(b"NtQueryTimerResolution\0")
+            .ok_or_else(|| anyhow!("NtQueryTimerResolution is not available"))?;
+        let query: NtQueryTimerResolutionFn = std::mem::transmute(proc);
+
+        // NtQueryTimerResolution reports (maximum interval, minimum interval, current)
+        let (mut coarsest, mut finest, mut current) = (0u32, 0u32, 0u32);
+        let status = query(&mut coarsest, &mut finest, &mut current);
+        if status < 0 {
+            return Err(anyhow!("Failed to query timer resolution: NTSTATUS {:#010x}", status));
+        }
+        Ok(TimerResolution { coarsest, finest, current })
+    }
+
+    #[cfg(not(windows))]
+    Err(anyhow!("Timer resolution is only available on Windows"))
+}
+
+/// How the high-precision timer was requested (determines how it is released)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TimerRequest {
+    /// NtSetTimerResolution with the given interval
+    Native(u32),
+    /// timeBeginPeriod(1)
+    Multimedia,
+}
+
+/// Holds a high-precision timer request while a profile is active
+#[derive(Default)]
+pub struct TimerResolutionSwitcher {
+    request: Option<TimerRequest>,
+}
+
+impl TimerResolutionSwitcher {
+    /// Request the finest timer resolution; returns the resolution now in effect
+    pub fn apply(&mut self) -> Result<TimerResolution> {
+        if self.request.is_none() {
+            self.request = Some(request_finest()?);
+        }
+        let resolution = query_timer_resolution()?;
+        info!("[Timer] High-precision timer active ({})", format_100ns(resolution.current));
+        Ok(resolution)
+    }
+
+    /// Release the request made by `apply` (no-op if nothing was requested)
+    pub fn restore(&mut self) -> Result<()> {
+        let Some(request) = self.request.take() else {
+            return Ok(());
+        };
+        release(request)?;
+        info!("[Timer] Timer resolution request released");
+        Ok(())
+    }
+}
+
+fn request_finest() -> Result<TimerRequest> {
+    #[cfg(windows)]
+    unsafe {
+        use windows::Win32::Media::{timeBeginPeriod, TIMERR_NOERROR};
+
+        if let (Ok(resolution), Some(proc)) = (query_timer_resolution(), ntdll::proc_address(b"NtSetTimerResolution\0")) {
+            let set: NtSetTimerResolutionFn = std::mem::transmute(proc);
+            let mut actual = 0u32;
+            if set(resolution.finest, 1, &mut actual) >= 0 {
+                return Ok(TimerRequest::Native(resolution.finest));
+            }
+        }
+
+        if timeBeginPeriod(1) != TIMERR_NOERROR {
+            return Err(anyhow!("Failed to raise timer resolution"));
+        }
+        Ok(TimerRequest::Multimedia)
+    }
+
+    #[cfg(not(windows))]
+    Err(anyhow!("Timer resolution is only available on Windows"))
+}
+
+fn release(request: TimerRequest) -> Result<()> {
+    #[cfg(windows)]
+    unsafe {
+        use windows::Win32::Media::{timeEndPeriod, TIMERR_NOERROR};
+
+        match request {
+            TimerRequest::Native(interval) => {
+                let proc = ntdll::proc_address(b"NtSetTimerResolution\0")
+                    .ok_or_else(|| anyhow!("NtSetTimerResolution is not available"))?;
+                let set: NtSetTimerResolutionFn = std::mem::transmute(proc);
+                let mut actual = 0u32;
+                let status = set(interval, 0, &mut actual);
+                if status < 0 {
+                    return Err(anyhow!("Failed to release timer resolution: NTSTATUS {:#010x}", status));
+                }
+            }
+            TimerRequest::Multimedia => {
+                if timeEndPeriod(1) != TIMERR_NOERROR {
+                    return Err(anyhow!("Failed to release timer resolution"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    {
+        let _ = request;
+        Err(anyhow!("Timer resolution is only available on Windows"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_100ns() {
+        assert_eq!(format_100ns(156_250), "15.625 ms");
+        assert_eq!(format_100ns(5_000), "0.500 ms");
+        assert_eq!(format_100ns(10_000), "1.000 ms");
+    }
+}
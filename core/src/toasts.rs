@@ -0,0 +1,95 @@
+//! Toast notification settings
+//!
+//! The kinds of events that can raise a Windows toast and the on/off switch per kind. The toasts
+//! themselves are shown by the app (`toast_notifier` in the `gaming_optimizer` binary).
+
+use crate::i18n::t;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Kinds of events that can raise a toast
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastCategory {
+    ProfileActivated,
+    ProcessesKilled,
+    OverlayError,
+    UpdateAvailable,
+}
+
+impl ToastCategory {
+    pub const ALL: [ToastCategory; 4] = [
+        ToastCategory::ProfileActivated,
+        ToastCategory::ProcessesKilled,
+        ToastCategory::OverlayError,
+        ToastCategory::UpdateAvailable,
+    ];
+}
+
+impl fmt::Display for ToastCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToastCategory::ProfileActivated => write!(f, "{}", t!("toast-category-profile-activated")),
+            ToastCategory::ProcessesKilled => write!(f, "{}", t!("toast-category-processes-killed")),
+            ToastCategory::OverlayError => write!(f, "{}", t!("toast-category-overlay-error")),
+            ToastCategory::UpdateAvailable => write!(f, "{}", t!("toast-category-update-available")),
+        }
+    }
+}
+
+/// Which categories are shown (stored in the global config; everything is on by default)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct ToastConfig {
+    pub profile_activated: bool,
+    pub processes_killed: bool,
+    pub overlay_error: bool,
+    pub update_available: bool,
+}
+
+impl Default for ToastConfig {
+    fn default() -> Self {
+        ToastConfig {
+            profile_activated: true,
+            processes_killed: true,
+            overlay_error: true,
+            update_available: true,
+        }
+    }
+}
+
+impl ToastConfig {
+    pub fn is_enabled(&self, category: ToastCategory) -> bool {
+        match category {
+            ToastCategory::ProfileActivated => self.profile_activated,
+            ToastCategory::ProcessesKilled => self.processes_killed,
+            ToastCategory::OverlayError => self.overlay_error,
+            ToastCategory::UpdateAvailable => self.update_available,
+        }
+    }
+
+    pub fn set_enabled(&mut self, category: ToastCategory, enabled: bool) {
+        let flag = match category {
+            ToastCategory::ProfileActivated => &mut self.profile_activated,
+            ToastCategory::ProcessesKilled => &mut self.processes_killed,
+            ToastCategory::OverlayError => &mut self.overlay_error,
+            ToastCategory::UpdateAvailable => &mut self.update_available,
+        };
+        *flag = enabled;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categories_toggle_independently() {
+        let mut config: ToastConfig = serde_json::from_str(r#"{"processes_killed":false}"#).unwrap();
+        assert!(config.is_enabled(ToastCategory::ProfileActivated));
+        assert!(!config.is_enabled(ToastCategory::ProcessesKilled));
+
+        config.set_enabled(ToastCategory::OverlayError, false);
+        assert!(!config.overlay_error);
+        assert!(config.update_available);
+    }
+}
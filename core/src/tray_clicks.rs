@@ -0,0 +1,178 @@
+//! Remappable tray icon clicks
+//!
+//! Single, double and middle clicks each run an action picked in the settings. A single click
+//! waits out the Windows double-click time before running, so a double click doesn't also
+//! trigger it; when double-click does nothing, single clicks run right away.
+
+use crate::i18n::t;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// What a tray click does
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrayClickAction {
+    Nothing,
+    ShowFlyout,
+    ShowWindow,
+    ToggleOverlay,
+    CycleCrosshair,
+    AdjustCrosshair,
+    TogglePause,
+    Deactivate,
+}
+
+impl TrayClickAction {
+    pub const ALL: [TrayClickAction; 8] = [
+        TrayClickAction::Nothing,
+        TrayClickAction::ShowFlyout,
+        TrayClickAction::ShowWindow,
+        TrayClickAction::ToggleOverlay,
+        TrayClickAction::CycleCrosshair,
+        TrayClickAction::AdjustCrosshair,
+        TrayClickAction::TogglePause,
+        TrayClickAction::Deactivate,
+    ];
+}
+
+impl fmt::Display for TrayClickAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrayClickAction::Nothing => write!(f, "{}", t!("tray-click-nothing")),
+            TrayClickAction::ShowFlyout => write!(f, "{}", t!("tray-click-flyout")),
+            TrayClickAction::ShowWindow => write!(f, "{}", t!("tray-click-window")),
+            TrayClickAction::ToggleOverlay => write!(f, "{}", t!("tray-click-overlay")),
+            TrayClickAction::CycleCrosshair => write!(f, "{}", t!("tray-click-cycle-crosshair")),
+            TrayClickAction::AdjustCrosshair => write!(f, "{}", t!("tray-click-adjust-crosshair")),
+            TrayClickAction::TogglePause => write!(f, "{}", t!("tray-click-pause")),
+            TrayClickAction::Deactivate => write!(f, "{}", t!("tray-click-deactivate")),
+        }
+    }
+}
+
+/// Tray click bindings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct TrayClickConfig {
+    pub single_click: TrayClickAction,
+    pub double_click: TrayClickAction,
+    pub middle_click: TrayClickAction,
+}
+
+impl Default for TrayClickConfig {
+    fn default() -> Self {
+        TrayClickConfig {
+            single_click: TrayClickAction::ShowFlyout,
+            double_click: TrayClickAction::ShowWindow,
+            middle_click: TrayClickAction::ToggleOverlay,
+        }
+    }
+}
+
+/// Mouse button released on the tray icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayClick {
+    Left,
+    Middle,
+}
+
+/// Tells single clicks from double clicks and maps them to actions
+pub struct ClickTracker {
+    pub config: TrayClickConfig,
+    double_click_time: Duration,
+    /// Time of a left click that may still become a double click
+    pending: Option<Instant>,
+}
+
+impl ClickTracker {
+    pub fn new(config: TrayClickConfig) -> Self {
+        ClickTracker { config, double_click_time: double_click_time(), pending: None }
+    }
+
+    /// Action to run for a click now (None while a single click waits for a possible second one)
+    pub fn click(&mut self, click: TrayClick, now: Instant) -> Option<TrayClickAction> {
+        let action = match click {
+            TrayClick::Middle => self.config.middle_click,
+            TrayClick::Left if self.config.double_click == TrayClickAction::Nothing => self.config.single_click,
+            TrayClick::Left => match self.pending.take() {
+                Some(first) if now.duration_since(first) < self.double_click_time => self.config.double_click,
+                _ => {
+                    self.pending = Some(now);
+                    return None;
+                }
+            },
+        };
+        (action != TrayClickAction::Nothing).then_some(action)
+    }
+
+    /// Whether a single click is waiting for a possible second one
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Single-click action once the double-click time passed without a second click
+    pub fn poll(&mut self, now: Instant) -> Option<TrayClickAction> {
+        let first = self.pending?;
+        if now.duration_since(first) < self.double_click_time {
+            return None;
+        }
+        self.pending = None;
+        (self.config.single_click != TrayClickAction::Nothing).then_some(self.config.single_click)
+    }
+}
+
+/// The double-click time set in Windows
+fn double_click_time() -> Duration {
+    #[cfg(windows)]
+    {
+        let ms = unsafe { windows::Win32::UI::Input::KeyboardAndMouse::GetDoubleClickTime() };
+        if ms > 0 {
+            return Duration::from_millis(ms as u64);
+        }
+    }
+    Duration::from_millis(500)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(config: TrayClickConfig) -> ClickTracker {
+        ClickTracker { config, double_click_time: Duration::from_millis(500), pending: None }
+    }
+
+    #[test]
+    fn test_single_and_double_click() {
+        let mut clicks = tracker(TrayClickConfig::default());
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        // Single click runs once the double-click time is over
+        assert_eq!(clicks.click(TrayClick::Left, at(0)), None);
+        assert_eq!(clicks.poll(at(300)), None);
+        assert_eq!(clicks.poll(at(500)), Some(TrayClickAction::ShowFlyout));
+        assert_eq!(clicks.poll(at(600)), None);
+
+        // Double click runs only the double-click action
+        assert_eq!(clicks.click(TrayClick::Left, at(1000)), None);
+        assert_eq!(clicks.click(TrayClick::Left, at(1200)), Some(TrayClickAction::ShowWindow));
+        assert_eq!(clicks.poll(at(2000)), None);
+
+        assert_eq!(clicks.click(TrayClick::Middle, at(3000)), Some(TrayClickAction::ToggleOverlay));
+    }
+
+    #[test]
+    fn test_unbound_clicks() {
+        let mut clicks = tracker(TrayClickConfig {
+            single_click: TrayClickAction::CycleCrosshair,
+            double_click: TrayClickAction::Nothing,
+            middle_click: TrayClickAction::Nothing,
+        });
+        let now = Instant::now();
+
+        // Nothing to wait for without a double-click action
+        assert_eq!(clicks.click(TrayClick::Left, now), Some(TrayClickAction::CycleCrosshair));
+        assert_eq!(clicks.click(TrayClick::Left, now), Some(TrayClickAction::CycleCrosshair));
+        assert_eq!(clicks.click(TrayClick::Middle, now), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Tray tooltip with live readings
+//!
+//! The tooltip can follow a template such as "{profile} | CPU {cpu}% | GPU {gpu_temp}°C",
+//! filled in from the hardware monitor's latest readings every time it samples, so hovering
+//! the icon is a health check without opening anything. Without a template the tooltip names
+//! the active profile and the result of its activation.
+
+use crate::i18n::t;
+use crate::monitor::HardwareSnapshot;
+use serde::{Deserialize, Serialize};
+
+/// Placeholders a template can use
+pub const PLACEHOLDERS: [&str; 9] = ["profile", "status", "cpu", "cpu_temp", "cpu_clock", "gpu", "gpu_temp", "gpu_clock", "ram"];
+
+/// Placeholders filled from the hardware monitor
+const READINGS: [&str; 7] = ["cpu", "cpu_temp", "cpu_clock", "gpu", "gpu_temp", "gpu_clock", "ram"];
+
+/// Longest tooltip Windows shows (NOTIFYICONDATA holds 128 UTF-16 units with the terminator)
+const MAX_TOOLTIP_UNITS: usize = 127;
+
+/// Shown for readings this machine doesn't have (or doesn't have yet)
+const NO_READING: &str = "--";
+
+/// Tray tooltip settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct TrayTooltipConfig {
+    /// Text with `{placeholder}`s from `PLACEHOLDERS` (empty = the built-in tooltip)
+    pub template: String,
+}
+
+impl TrayTooltipConfig {
+    pub fn is_custom(&self) -> bool {
+        !self.template.trim().is_empty()
+    }
+
+    /// Whether the template shows hardware readings (so the monitor has to run for it)
+    pub fn uses_readings(&self) -> bool {
+        placeholders(&self.template).any(|name| READINGS.contains(&name))
+    }
+}
+
+/// What the placeholders are filled with
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TooltipValues<'a> {
+    pub profile: Option<&'a str>,
+    /// Result of the last activation, e.g. "12 applied, 1 failed"
+    pub status: Option<&'a str>,
+    pub hardware: Option<&'a HardwareSnapshot>,
+}
+
+/// Names between braces in `template`, in order
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template.split('{').skip(1).filter_map(|part| part.split_once('}').map(|(name, _)| name.trim()))
+}
+
+/// Placeholders in `template` that aren't in `PLACEHOLDERS` (they are shown as typed)
+pub fn unknown_placeholders(template: &str) -> Vec<String> {
+    placeholders(template).filter(|name| !PLACEHOLDERS.contains(name)).map(str::to_string).collect()
+}
+
+/// `template` with its placeholders filled in, cut to what the tooltip can show
+pub fn render(template: &str, values: &TooltipValues) -> String {
+    let hardware = values.hardware.cloned().unwrap_or_default();
+    let number = |value: Option<f32>| value.map_or_else(|| NO_READING.to_string(), |value| format!("{:.0}", value));
+    let value_of = |name: &str| -> Option<String> {
+        Some(match name {
+            "profile" => values.profile.map_or_else(|| t!("tray-tooltip-no-profile"), str::to_string),
+            "status" => values.status.unwrap_or_default().to_string(),
+            "cpu" => number(hardware.cpu_load),
+            "cpu_temp" => number(hardware.cpu_temp),
+            "cpu_clock" => number(hardware.cpu_clock_mhz),
+            "gpu" => number(hardware.gpu_load),
+            "gpu_temp" => number(hardware.gpu_temp),
+            "gpu_clock" => number(hardware.gpu_clock_mhz),
+            "ram" => number(hardware.memory_percent()),
+            _ => return None,
+        })
+    };
+
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}').and_then(|end| value_of(after[..end].trim()).map(|value| (end, value))) {
+            Some((end, value)) => {
+                text.push_str(&value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    truncate(text.trim())
+}
+
+/// `text` cut to `MAX_TOOLTIP_UNITS`, ending in "…" when something was cut
+fn truncate(text: &str) -> String {
+    if text.encode_utf16().count() <= MAX_TOOLTIP_UNITS {
+        return text.to_string();
+    }
+    let mut units = 0;
+    let mut cut: String = text
+        .chars()
+        .take_while(|c| {
+            units += c.len_utf16();
+            units < MAX_TOOLTIP_UNITS
+        })
+        .collect();
+    cut.push('…');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let snapshot = HardwareSnapshot {
+            cpu_load: Some(37.6),
+            gpu_temp: Some(64.2),
+            memory_used_kb: Some(4 * 1024 * 1024),
+            memory_total_kb: Some(16 * 1024 * 1024),
+            ..Default::default()
+        };
+        let values = TooltipValues { profile: Some("Valorant"), status: None, hardware: Some(&snapshot) };
+        assert_eq!(
+            render("{profile} | CPU {cpu}% | GPU {gpu_temp}°C | RAM {ram}%", &values),
+            "Valorant | CPU 38% | GPU 64°C | RAM 25%"
+        );
+
+        // Missing readings, unknown placeholders and stray braces
+        assert_eq!(render("{ profile } {gpu}% {fps} {cpu", &TooltipValues::default()), "Inactive --% {fps} {cpu");
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let config = TrayTooltipConfig { template: "{profile} ({status})".to_string() };
+        assert!(config.is_custom());
+        assert!(!config.uses_readings());
+        assert!(TrayTooltipConfig { template: "CPU {cpu_temp}°C".to_string() }.uses_readings());
+        assert!(!TrayTooltipConfig::default().is_custom());
+        assert_eq!(unknown_placeholders("{fps} {cpu} {Profile}"), ["fps", "Profile"]);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let long = "é".repeat(200);
+        let cut = render(&long, &TooltipValues::default());
+        assert_eq!(cut.encode_utf16().count(), MAX_TOOLTIP_UNITS);
+        assert!(cut.ends_with('…'));
+        assert_eq!(truncate("short"), "short");
+    }
+}
@@ -0,0 +1,190 @@
+//! One-shot system tweaks run on profile activation
+//!
+//! Each tweak is an `Action` run once when the profile activates; nothing is restored
+//! afterwards. Failures are reported per action so one missing privilege doesn't hide
+//! the others' results.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::elevation::{self, AdminFeature};
+use crate::shell::{run_hidden, run_powershell};
+use tracing::{info, warn};
+
+/// A one-shot system action
+pub trait Action {
+    /// Past-tense summary shown in the activation status, e.g. "DNS flushed"
+    fn done_label(&self) -> &'static str;
+
+    fn run(&self) -> Result<()>;
+}
+
+/// Tweaks a profile can enable (stored in profiles)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SystemTweak {
+    PurgeStandbyList,
+    FlushDns,
+    RestartAudioService,
+}
+
+impl SystemTweak {
+    pub const ALL: [SystemTweak; 3] = [
+        SystemTweak::PurgeStandbyList,
+        SystemTweak::FlushDns,
+        SystemTweak::RestartAudioService,
+    ];
+
+    pub fn action(self) -> Box<dyn Action> {
+        match self {
+            SystemTweak::PurgeStandbyList => Box::new(PurgeStandbyList),
+            SystemTweak::FlushDns => Box::new(FlushDns),
+            SystemTweak::RestartAudioService => Box::new(RestartAudioService),
+        }
+    }
+}
+
+impl fmt::Display for SystemTweak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SystemTweak::PurgeStandbyList => write!(f, "Clear RAM standby list"),
+            SystemTweak::FlushDns => write!(f, "Flush DNS cache"),
+            SystemTweak::RestartAudioService => write!(f, "Restart audio service"),
+        }
+    }
+}
+
+/// Outcome of running a profile's tweaks
+#[derive(Debug, Default)]
+pub struct TweakReport {
+    pub done: Vec<&'static str>,
+    /// (tweak, error) for each failed action
+    pub failed: Vec<(SystemTweak, anyhow::Error)>,
+}
+
+/// Run the given tweaks in order, collecting each one's result
+pub fn run_tweaks(tweaks: &[SystemTweak]) -> TweakReport {
+    let mut report = TweakReport::default();
+    for &tweak in tweaks {
+        let action = tweak.action();
+        let result = match AdminFeature::for_tweak(tweak) {
+            Some(_) => elevation::require_admin().and_then(|_| action.run()),
+            None => action.run(),
+        };
+        match result {
+            Ok(()) => {
+                info!("[Tweaks] {}", action.done_label());
+                report.done.push(action.done_label());
+            }
+            Err(e) => {
+                warn!("[Tweaks] {} failed: {}", tweak, e);
+                report.failed.push((tweak, e));
+            }
+        }
+    }
+    report
+}
+
+/// Empty the standby page list so cached file data doesn't crowd out the game
+struct PurgeStandbyList;
+
+impl Action for PurgeStandbyList {
+    fn done_label(&self) -> &'static str {
+        "Standby list cleared"
+    }
+
+    fn run(&self) -> Result<()> {
+        #[cfg(windows)]
+        unsafe {
+            use windows::Win32::Foundation::{CloseHandle, GetLastError, ERROR_NOT_ALL_ASSIGNED, HANDLE, LUID};
+            use windows::Win32::Security::{
+                AdjustTokenPrivileges, LookupPrivilegeValueW, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_ENABLED,
+                SE_PROF_SINGLE_PROCESS_NAME, TOKEN_ADJUST_PRIVILEGES, TOKEN_PRIVILEGES,
+            };
+            use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+            /// SYSTEM_INFORMATION_CLASS::SystemMemoryListInformation
+            const SYSTEM_MEMORY_LIST_INFORMATION: u32 = 80;
+            /// SYSTEM_MEMORY_LIST_COMMAND::MemoryPurgeStandbyList
+            const MEMORY_PURGE_STANDBY_LIST: u32 = 4;
+
+            // The purge requires SeProfileSingleProcessPrivilege (administrators only)
+            let mut token = HANDLE::default();
+            OpenProcessToken(GetCurrentProcess(), TOKEN_ADJUST_PRIVILEGES, &mut token)
+                .map_err(|e| anyhow!("Failed to open process token: {}", e))?;
+            let mut luid = LUID::default();
+            let privilege = LookupPrivilegeValueW(None, SE_PROF_SINGLE_PROCESS_NAME, &mut luid).and_then(|_| {
+                let privileges = TOKEN_PRIVILEGES {
+                    PrivilegeCount: 1,
+                    Privileges: [LUID_AND_ATTRIBUTES { Luid: luid, Attributes: SE_PRIVILEGE_ENABLED }],
+                };
+                AdjustTokenPrivileges(token, false, Some(&privileges), 0, None, None)
+            });
+            let not_assigned = GetLastError().is_err_and(|e| e.code() == ERROR_NOT_ALL_ASSIGNED.to_hresult());
+            let _ = CloseHandle(token);
+            privilege.map_err(|e| anyhow!("Failed to enable memory privilege: {}", e))?;
+            if not_assigned {
+                return Err(anyhow!("Requires running as administrator"));
+            }
+
+            let proc = crate::ntdll::proc_address(b"NtSetSystemInformation\0")
+                .ok_or_else(|| anyhow!("NtSetSystemInformation is not available"))?;
+            let set_information: unsafe extern "system" fn(u32, *const std::ffi::c_void, u32) -> i32 =
+                std::mem::transmute(proc);
+            let command = MEMORY_PURGE_STANDBY_LIST;
+            let status = set_information(
+                SYSTEM_MEMORY_LIST_INFORMATION,
+                &command as *const u32 as *const _,
+                std::mem::size_of::<u32>() as u32,
+            );
+            if status < 0 {
+                return Err(anyhow!("Standby list purge failed: NTSTATUS {:#010x}", status));
+            }
+            Ok(())
+        }
+
+        #[cfg(not(windows))]
+        Err(anyhow!("Only available on Windows"))
+    }
+}
+
+/// Drop cached DNS lookups (stale matchmaking/server addresses)
+struct FlushDns;
+
+impl Action for FlushDns {
+    fn done_label(&self) -> &'static str {
+        "DNS flushed"
+    }
+
+    fn run(&self) -> Result<()> {
+        run_hidden("ipconfig.exe", &["/flushdns"]).map(|_| ())
+    }
+}
+
+/// Restart Windows Audio to clear crackling or a stuck device after driver hiccups
+struct RestartAudioService;
+
+impl Action for RestartAudioService {
+    fn done_label(&self) -> &'static str {
+        "Audio service restarted"
+    }
+
+    fn run(&self) -> Result<()> {
+        // Restart-Service waits for the stop to finish, unlike `sc stop`
+        run_powershell("Restart-Service -Name Audiosrv -Force -ErrorAction Stop")
+            .map(|_| ())
+            .map_err(|e| anyhow!("Requires running as administrator ({})", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tweak_serialization() {
+        let tweaks = vec![SystemTweak::FlushDns, SystemTweak::PurgeStandbyList];
+        let json = serde_json::to_string(&tweaks).unwrap();
+        assert_eq!(json, r#"["FlushDns","PurgeStandbyList"]"#);
+        assert_eq!(serde_json::from_str::<Vec<SystemTweak>>(&json).unwrap(), tweaks);
+    }
+}
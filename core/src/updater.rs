@@ -0,0 +1,383 @@
+//! Update checker and installer
+//!
+//! Looks for a newer GitHub release on the chosen channel (stable skips pre-releases) and, once
+//! the user confirms, downloads its installer, checks it against the SHA-256 published with the
+//! release and runs it. The installer replaces both exes, so the app never swaps itself. GitHub is
+//! reached through PowerShell so the app doesn't have to bundle a TLS stack.
+
+use crate::i18n::t;
+use crate::shell::{ps_quote, run_powershell};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver};
+use tracing::{info, warn};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/tharun-extinct/Gaming_optimizer/releases";
+pub const DEFAULT_INTERVAL_HOURS: u32 = 24;
+
+/// Which releases count as updates
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    /// Pre-releases too
+    Beta,
+}
+
+impl UpdateChannel {
+    pub const ALL: [UpdateChannel; 2] = [UpdateChannel::Stable, UpdateChannel::Beta];
+}
+
+impl fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateChannel::Stable => write!(f, "{}", t!("update-channel-stable")),
+            UpdateChannel::Beta => write!(f, "{}", t!("update-channel-beta")),
+        }
+    }
+}
+
+/// Update settings (stored in the global config)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+pub struct UpdateConfig {
+    pub enabled: bool,
+    pub channel: UpdateChannel,
+    pub interval_hours: u32,
+    /// Unix time of the last check, so restarts don't check again right away
+    pub last_check: u64,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        UpdateConfig {
+            // Checking GitHub is opt-in
+            enabled: false,
+            channel: UpdateChannel::default(),
+            interval_hours: DEFAULT_INTERVAL_HOURS,
+            last_check: 0,
+        }
+    }
+}
+
+impl UpdateConfig {
+    pub fn check_due(&self, now: u64) -> bool {
+        self.enabled && now.saturating_sub(self.last_check) >= u64::from(self.interval_hours.max(1)) * 3600
+    }
+}
+
+/// Dotted version with an optional pre-release part ("v1.4.0-beta.2")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    numbers: [u64; 3],
+    pre: Option<String>,
+}
+
+impl Version {
+    pub fn parse(text: &str) -> Option<Version> {
+        let text = text.trim().trim_start_matches(['v', 'V']);
+        let text = text.split('+').next().unwrap_or(text);
+        let (core, pre) = match text.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (text, None),
+        };
+
+        let mut numbers = [0; 3];
+        for (i, part) in core.split('.').enumerate() {
+            *numbers.get_mut(i)? = part.parse().ok()?;
+        }
+        Some(Version { numbers, pre })
+    }
+
+    pub fn current() -> Version {
+        Version::parse(env!("CARGO_PKG_VERSION")).expect("package version is valid")
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numbers.cmp(&other.numbers).then_with(|| match (&self.pre, &other.pre) {
+            (None, None) => Ordering::Equal,
+            // 1.0.0-beta < 1.0.0
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(a), Some(b)) => compare_pre_release(a, b),
+        })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// "beta.10" > "beta.2": numeric identifiers compare as numbers
+fn compare_pre_release(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => {
+                let order = match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    _ => a.cmp(b),
+                };
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+        }
+    }
+}
+
+/// Setup program attached to each release; it installs both the app and crosshair.exe
+const INSTALLER_ASSET: &str = "GamingOptimizer-Setup.exe";
+/// `sha256sum` output for the installer, published next to it
+const CHECKSUM_ASSET: &str = "GamingOptimizer-Setup.exe.sha256";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub url: String,
+    /// Where the installer's published SHA-256 lives
+    pub checksum_url: String,
+}
+
+/// A release newer than the running version
+#[derive(Debug, Clone, PartialEq)]
+pub struct Release {
+    pub tag: String,
+    pub version: Version,
+    /// Release notes page
+    pub page_url: String,
+    /// None when the release has no installer with a checksum (the page is opened instead)
+    pub asset: Option<ReleaseAsset>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+    html_url: String,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// The installer and its checksum, matched by their exact names
+fn pick_asset(assets: &[GithubAsset]) -> Option<ReleaseAsset> {
+    let url_of = |name: &str| {
+        assets.iter().find(|asset| asset.name.eq_ignore_ascii_case(name)).map(|asset| asset.browser_download_url.clone())
+    };
+    Some(ReleaseAsset {
+        name: INSTALLER_ASSET.to_string(),
+        url: url_of(INSTALLER_ASSET)?,
+        checksum_url: url_of(CHECKSUM_ASSET)?,
+    })
+}
+
+/// Newest release on `channel` that is newer than `current`
+fn newest_release(releases: Vec<GithubRelease>, current: &Version, channel: UpdateChannel) -> Option<Release> {
+    releases
+        .into_iter()
+        .filter(|release| !release.draft && (channel == UpdateChannel::Beta || !release.prerelease))
+        .filter_map(|release| {
+            let version = Version::parse(&release.tag_name)?;
+            (version > *current).then(|| Release {
+                asset: pick_asset(&release.assets),
+                tag: release.tag_name,
+                version,
+                page_url: release.html_url,
+            })
+        })
+        .max_by(|a, b| a.version.cmp(&b.version))
+}
+
+fn fetch_releases() -> Result<Vec<GithubRelease>> {
+    let script = format!(
+        "[Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+         (Invoke-WebRequest -UseBasicParsing -Headers @{{'User-Agent'='GamingOptimizer'; 'Accept'='application/vnd.github+json'}} -Uri {}).Content",
+        ps_quote(RELEASES_URL)
+    );
+    let json = run_powershell(&script).map_err(|e| anyhow!("Failed to reach GitHub: {}", e))?;
+    serde_json::from_str(&json).map_err(|e| anyhow!("Failed to read the release list: {}", e))
+}
+
+/// Check for an update in the background; the receiver gets one result
+pub fn spawn_check(channel_choice: UpdateChannel) -> Receiver<Result<Option<Release>>> {
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        let result = fetch_releases().map(|releases| newest_release(releases, &Version::current(), channel_choice));
+        match result {
+            Ok(Some(ref release)) => info!("[Updater] {} is available", release.tag),
+            Ok(None) => info!("[Updater] Up to date"),
+            Err(ref e) => warn!("[Updater] {}", e),
+        }
+        let _ = tx.send(result);
+    });
+    rx
+}
+
+/// Hash from a `sha256sum` line ("<64 hex digits>  <file name>"), lowercased
+fn parse_checksum(text: &str) -> Option<String> {
+    let hash = text.split_whitespace().next()?;
+    (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())).then(|| hash.to_ascii_lowercase())
+}
+
+/// SHA-256 of a file as lowercase hex
+fn file_sha256(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path).map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+    Ok(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Fail (and delete the file) unless it hashes to `expected`
+fn verify_checksum(path: &Path, expected: &str) -> Result<()> {
+    let actual = file_sha256(path)?;
+    if actual != expected {
+        let _ = fs::remove_file(path);
+        return Err(anyhow!("{} doesn't match its published checksum", path.display()));
+    }
+    Ok(())
+}
+
+fn download(url: &str, path: &Path) -> Result<()> {
+    let script = format!(
+        "$ProgressPreference = 'SilentlyContinue'; Invoke-WebRequest -UseBasicParsing -Uri {} -OutFile {}",
+        ps_quote(url),
+        ps_quote(&path.to_string_lossy())
+    );
+    run_powershell(&script).map(|_| ())
+}
+
+/// Download the installer and its checksum to the temp folder and verify it, in the background
+pub fn spawn_download(asset: ReleaseAsset) -> Receiver<Result<PathBuf>> {
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        let path = std::env::temp_dir().join(INSTALLER_ASSET);
+        let checksum_path = std::env::temp_dir().join(CHECKSUM_ASSET);
+        let result = download(&asset.checksum_url, &checksum_path)
+            .and_then(|()| fs::read_to_string(&checksum_path).map_err(|e| anyhow!("{}", e)))
+            .map_err(|e| anyhow!("Failed to download {}: {}", CHECKSUM_ASSET, e))
+            .and_then(|text| parse_checksum(&text).ok_or_else(|| anyhow!("{} isn't a SHA-256 checksum", CHECKSUM_ASSET)))
+            .and_then(|expected| {
+                download(&asset.url, &path).map_err(|e| anyhow!("Failed to download {}: {}", asset.name, e))?;
+                verify_checksum(&path, &expected)
+            })
+            .map(|()| path);
+        let _ = fs::remove_file(&checksum_path);
+        let _ = tx.send(result);
+    });
+    rx
+}
+
+/// Start the downloaded, verified installer; the caller exits right after
+pub fn install(path: &Path) -> Result<()> {
+    Command::new(path).spawn().map_err(|e| anyhow!("Failed to start the installer: {}", e))?;
+    info!("[Updater] Installing {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    fn release(tag: &str, prerelease: bool, assets: &[&str]) -> GithubRelease {
+        GithubRelease {
+            tag_name: tag.to_string(),
+            prerelease,
+            draft: false,
+            html_url: format!("https://example.com/{}", tag),
+            assets: assets
+                .iter()
+                .map(|name| GithubAsset { name: name.to_string(), browser_download_url: format!("https://example.com/{}", name) })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_version_order() {
+        assert!(version("v1.2.0") > version("1.1.9"));
+        assert!(version("1.2.0") > version("1.2.0-beta.3"));
+        assert!(version("1.2.0-beta.10") > version("1.2.0-beta.2"));
+        assert_eq!(version("v2"), version("2.0.0"));
+        assert_eq!(Version::parse("latest"), None);
+        assert_eq!(Version::parse("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn test_newest_release_respects_channel() {
+        let releases = || {
+            vec![
+                release("v0.3.0-beta.1", true, &["crosshair.exe", "gaming_optimizer.exe"]),
+                release("v0.2.0", false, &["crosshair.exe", "GamingOptimizer-Setup.exe", "GamingOptimizer-Setup.exe.sha256"]),
+                release("v0.0.9", false, &[]),
+            ]
+        };
+        let current = version("0.1.0");
+
+        let stable = newest_release(releases(), &current, UpdateChannel::Stable).unwrap();
+        assert_eq!(stable.tag, "v0.2.0");
+        let asset = stable.asset.unwrap();
+        assert_eq!(asset.url, "https://example.com/GamingOptimizer-Setup.exe");
+        assert_eq!(asset.checksum_url, "https://example.com/GamingOptimizer-Setup.exe.sha256");
+
+        let beta = newest_release(releases(), &current, UpdateChannel::Beta).unwrap();
+        assert_eq!(beta.tag, "v0.3.0-beta.1");
+        // Bare exes are never picked, only the installer with its checksum
+        assert_eq!(beta.asset, None);
+
+        assert_eq!(newest_release(releases(), &version("0.2.0"), UpdateChannel::Stable), None);
+    }
+
+    #[test]
+    fn test_check_due() {
+        assert!(!UpdateConfig::default().check_due(u64::MAX));
+        let config = UpdateConfig { enabled: true, last_check: 1_000, ..UpdateConfig::default() };
+        assert!(!config.check_due(1_000 + 3600));
+        assert!(config.check_due(1_000 + 24 * 3600));
+        assert!(!UpdateConfig { enabled: false, ..config }.check_due(u64::MAX));
+    }
+
+    #[test]
+    fn test_pick_asset_needs_checksum() {
+        assert_eq!(pick_asset(&release("v1.0.0", false, &["GamingOptimizer-Setup.exe"]).assets), None);
+    }
+
+    #[test]
+    fn test_checksum() {
+        let hash = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+        assert_eq!(parse_checksum(&format!("{}  GamingOptimizer-Setup.exe\n", hash)), Some(hash.to_ascii_lowercase()));
+        assert_eq!(parse_checksum("not a hash"), None);
+
+        let path = std::env::temp_dir().join(format!("updater_checksum_test_{}", std::process::id()));
+        fs::write(&path, "abc").unwrap();
+        assert!(verify_checksum(&path, &hash.to_ascii_lowercase()).is_ok());
+        assert!(verify_checksum(&path, &"0".repeat(64)).is_err());
+        // A mismatching download is deleted
+        assert!(!path.exists());
+    }
+}
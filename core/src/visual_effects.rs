@@ -0,0 +1,208 @@
+//! "Best performance" visual effects while gaming
+//!
+//! Turns off window/menu animations, fades, shadows and transparency (what the "Adjust for
+//! best performance" button in Performance Options does). The animation switches are changed
+//! for this session only and the user's previous values are put back on restore.
+
+use anyhow::{anyhow, Result};
+use crate::registry::{Hive, RegistryChanges};
+use tracing::info;
+
+const PERSONALIZE_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize";
+const ENABLE_TRANSPARENCY: &str = "EnableTransparency";
+
+/// An on/off SystemParametersInfo setting
+#[cfg(windows)]
+struct UiEffect {
+    name: &'static str,
+    get: windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_ACTION,
+    set: windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_ACTION,
+    /// The SET action takes the value in uiParam instead of pvParam
+    value_in_ui_param: bool,
+}
+
+#[cfg(windows)]
+const UI_EFFECTS: [UiEffect; 10] = {
+    use windows::Win32::UI::WindowsAndMessaging::*;
+
+    [
+        UiEffect { name: "client area animation", get: SPI_GETCLIENTAREAANIMATION, set: SPI_SETCLIENTAREAANIMATION, value_in_ui_param: false },
+        UiEffect { name: "menu animation", get: SPI_GETMENUANIMATION, set: SPI_SETMENUANIMATION, value_in_ui_param: false },
+        UiEffect { name: "menu fade", get: SPI_GETMENUFADE, set: SPI_SETMENUFADE, value_in_ui_param: false },
+        UiEffect { name: "combo box animation", get: SPI_GETCOMBOBOXANIMATION, set: SPI_SETCOMBOBOXANIMATION, value_in_ui_param: false },
+        UiEffect { name: "smooth scrolling", get: SPI_GETLISTBOXSMOOTHSCROLLING, set: SPI_SETLISTBOXSMOOTHSCROLLING, value_in_ui_param: false },
+        UiEffect { name: "tooltip animation", get: SPI_GETTOOLTIPANIMATION, set: SPI_SETTOOLTIPANIMATION, value_in_ui_param: false },
+        UiEffect { name: "selection fade", get: SPI_GETSELECTIONFADE, set: SPI_SETSELECTIONFADE, value_in_ui_param: false },
+        UiEffect { name: "cursor shadow", get: SPI_GETCURSORSHADOW, set: SPI_SETCURSORSHADOW, value_in_ui_param: false },
+        UiEffect { name: "window shadows", get: SPI_GETDROPSHADOW, set: SPI_SETDROPSHADOW, value_in_ui_param: false },
+        UiEffect { name: "window contents while dragging", get: SPI_GETDRAGFULLWINDOWS, set: SPI_SETDRAGFULLWINDOWS, value_in_ui_param: true },
+    ]
+};
+
+#[cfg(windows)]
+fn read_effect(effect: &UiEffect) -> Result<bool> {
+    use windows::Win32::Foundation::BOOL;
+    use windows::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS};
+
+    let mut value = BOOL(0);
+    unsafe {
+        SystemParametersInfoW(
+            effect.get,
+            0,
+            Some(&mut value as *mut BOOL as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    }
+    .map_err(|e| anyhow!("Failed to read {}: {}", effect.name, e))?;
+    Ok(value.as_bool())
+}
+
+/// Change a setting for this session (not written to the user profile, so a crash leaves
+/// the user's own value back in place after the next sign-in)
+#[cfg(windows)]
+fn write_effect(effect: &UiEffect, enabled: bool) -> Result<()> {
+    use windows::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, SPIF_SENDCHANGE};
+
+    let (ui_param, pv_param) = if effect.value_in_ui_param {
+        (enabled as u32, None)
+    } else {
+        (0, Some(enabled as usize as *mut _))
+    };
+    unsafe { SystemParametersInfoW(effect.set, ui_param, pv_param, SPIF_SENDCHANGE) }
+        .map_err(|e| anyhow!("Failed to change {}: {}", effect.name, e))
+}
+
+/// Minimize/maximize animation (the only setting that takes a struct)
+#[cfg(windows)]
+fn read_minimize_animation() -> Result<bool> {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, ANIMATIONINFO, SPI_GETANIMATION, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+    };
+
+    let mut info = ANIMATIONINFO { cbSize: std::mem::size_of::<ANIMATIONINFO>() as u32, iMinAnimate: 0 };
+    unsafe {
+        SystemParametersInfoW(
+            SPI_GETANIMATION,
+            info.cbSize,
+            Some(&mut info as *mut ANIMATIONINFO as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    }
+    .map_err(|e| anyhow!("Failed to read window animation: {}", e))?;
+    Ok(info.iMinAnimate != 0)
+}
+
+#[cfg(windows)]
+fn write_minimize_animation(enabled: bool) -> Result<()> {
+    use windows::Win32::UI::WindowsAndMessaging::{SystemParametersInfoW, ANIMATIONINFO, SPI_SETANIMATION, SPIF_SENDCHANGE};
+
+    let mut info = ANIMATIONINFO { cbSize: std::mem::size_of::<ANIMATIONINFO>() as u32, iMinAnimate: enabled as i32 };
+    unsafe { SystemParametersInfoW(SPI_SETANIMATION, info.cbSize, Some(&mut info as *mut ANIMATIONINFO as *mut _), SPIF_SENDCHANGE) }
+        .map_err(|e| anyhow!("Failed to change window animation: {}", e))
+}
+
+/// Tell Explorer and apps that the transparency setting changed
+#[cfg(windows)]
+fn broadcast_theme_change() {
+    use windows::core::w;
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{SendMessageTimeoutW, HWND_BROADCAST, SMTO_ABORTIFHUNG, WM_SETTINGCHANGE};
+
+    unsafe {
+        SendMessageTimeoutW(
+            HWND_BROADCAST,
+            WM_SETTINGCHANGE,
+            WPARAM(0),
+            LPARAM(w!("ImmersiveColorSet").as_ptr() as isize),
+            SMTO_ABORTIFHUNG,
+            1000,
+            None,
+        );
+    }
+}
+
+/// Switches to best-performance visual effects and puts the user's settings back afterwards
+#[derive(Default)]
+pub struct VisualEffectsSwitcher {
+    /// Indices into UI_EFFECTS that were turned off
+    disabled_effects: Vec<usize>,
+    /// Minimize/maximize animation was turned off
+    disabled_minimize_animation: bool,
+    transparency: RegistryChanges,
+}
+
+impl VisualEffectsSwitcher {
+    /// Whether any effect is currently turned off by us
+    pub fn is_active(&self) -> bool {
+        !self.disabled_effects.is_empty() || self.disabled_minimize_animation || !self.transparency.is_empty()
+    }
+
+    /// Turn off animations, shadows and transparency. Returns how many settings were changed
+    /// (settings that were already off are left alone).
+    pub fn apply(&mut self) -> Result<usize> {
+        self.restore()?;
+
+        #[cfg(windows)]
+        {
+            for (index, effect) in UI_EFFECTS.iter().enumerate() {
+                if read_effect(effect)? {
+                    write_effect(effect, false)?;
+                    self.disabled_effects.push(index);
+                }
+            }
+            if read_minimize_animation()? {
+                write_minimize_animation(false)?;
+                self.disabled_minimize_animation = true;
+            }
+            self.transparency.set_dword(Hive::CurrentUser, PERSONALIZE_KEY, ENABLE_TRANSPARENCY, 0)?;
+            broadcast_theme_change();
+
+            let changed = self.disabled_effects.len() + self.disabled_minimize_animation as usize + 1;
+            info!("[Visual Effects] Best performance ({} settings changed)", changed);
+            Ok(changed)
+        }
+
+        #[cfg(not(windows))]
+        Err(anyhow!("Visual effects are only available on Windows"))
+    }
+
+    /// Turn the effects we disabled back on (no-op if nothing was changed).
+    /// Keeps going after a failure and returns the first error.
+    pub fn restore(&mut self) -> Result<()> {
+        if !self.is_active() {
+            return Ok(());
+        }
+
+        let mut first_error = None;
+
+        #[cfg(windows)]
+        {
+            for index in self.disabled_effects.drain(..) {
+                if let Err(e) = write_effect(&UI_EFFECTS[index], true) {
+                    first_error.get_or_insert(e);
+                }
+            }
+            if std::mem::take(&mut self.disabled_minimize_animation) {
+                if let Err(e) = write_minimize_animation(true) {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if !self.transparency.is_empty() {
+            if let Err(e) = self.transparency.restore() {
+                first_error.get_or_insert(e);
+            }
+            #[cfg(windows)]
+            broadcast_theme_change();
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => {
+                info!("[Visual Effects] Previous visual effects restored");
+                Ok(())
+            }
+        }
+    }
+}
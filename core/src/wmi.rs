@@ -0,0 +1,151 @@
+//! Minimal WMI client (IWbemServices)
+//!
+//! Queries for the hardware monitor's sensors and event subscriptions for game launch
+//! detection. COM must be initialized on the thread that uses a connection.
+
+use anyhow::{anyhow, Result};
+use std::time::Duration;
+use windows::core::{ComInterface, IUnknown, BSTR, PCWSTR};
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoSetProxyBlanket, CoTaskMemFree, CLSCTX_INPROC_SERVER, EOAC_NONE,
+    RPC_C_AUTHN_LEVEL_CALL, RPC_C_IMP_LEVEL_IMPERSONATE,
+};
+use windows::Win32::System::Variant::{VariantClear, VariantToDouble, VariantToStringAlloc, VARIANT, VT_UNKNOWN};
+use windows::Win32::System::Wmi::{
+    IEnumWbemClassObject, IWbemClassObject, IWbemLocator, IWbemServices, WbemLocator, WBEM_FLAG_FORWARD_ONLY,
+    WBEM_FLAG_RETURN_IMMEDIATELY, WBEM_INFINITE,
+};
+
+/// RPC_C_AUTHN_WINNT / RPC_C_AUTHZ_NONE (from Win32_System_Rpc)
+const AUTHN_WINNT: u32 = 10;
+const AUTHZ_NONE: u32 = 0;
+
+pub struct Connection {
+    services: IWbemServices,
+}
+
+impl Connection {
+    /// Connect to a namespace such as `root\WMI` (COM must be initialized on this thread)
+    pub fn open(namespace: &str) -> Result<Self> {
+        unsafe {
+            let locator: IWbemLocator = CoCreateInstance(&WbemLocator, None, CLSCTX_INPROC_SERVER)
+                .map_err(|e| anyhow!("Failed to start WMI: {}", e))?;
+            let services = locator
+                .ConnectServer(&BSTR::from(namespace), &BSTR::new(), &BSTR::new(), &BSTR::new(), 0, &BSTR::new(), None)
+                .map_err(|e| anyhow!("Failed to open {}: {}", namespace, e))?;
+            CoSetProxyBlanket(
+                &services,
+                AUTHN_WINNT,
+                AUTHZ_NONE,
+                PCWSTR::null(),
+                RPC_C_AUTHN_LEVEL_CALL,
+                RPC_C_IMP_LEVEL_IMPERSONATE,
+                None,
+                EOAC_NONE,
+            )
+            .map_err(|e| anyhow!("Failed to set WMI security: {}", e))?;
+            Ok(Connection { services })
+        }
+    }
+
+    /// Run a WQL query and hand every result object to `row`
+    pub fn query(&self, wql: &str, mut row: impl FnMut(&IWbemClassObject)) -> Result<()> {
+        unsafe {
+            let results = self
+                .services
+                .ExecQuery(
+                    &BSTR::from("WQL"),
+                    &BSTR::from(wql),
+                    WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY,
+                    None,
+                )
+                .map_err(|e| anyhow!("WMI query failed: {}", e))?;
+
+            loop {
+                let mut objects = [None];
+                let mut returned = 0;
+                results
+                    .Next(WBEM_INFINITE, &mut objects, &mut returned)
+                    .ok()
+                    .map_err(|e| anyhow!("WMI query failed: {}", e))?;
+                match objects[0].take() {
+                    Some(object) if returned > 0 => row(&object),
+                    _ => return Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Standing event query, read one event at a time with `next`
+pub struct EventQuery {
+    events: IEnumWbemClassObject,
+}
+
+impl Connection {
+    /// Subscribe to a WQL event query such as `SELECT * FROM Win32_ProcessStartTrace`
+    pub fn subscribe(&self, wql: &str) -> Result<EventQuery> {
+        unsafe {
+            let events = self
+                .services
+                .ExecNotificationQuery(
+                    &BSTR::from("WQL"),
+                    &BSTR::from(wql),
+                    WBEM_FLAG_FORWARD_ONLY | WBEM_FLAG_RETURN_IMMEDIATELY,
+                    None,
+                )
+                .map_err(|e| anyhow!("WMI subscription failed: {}", e))?;
+            Ok(EventQuery { events })
+        }
+    }
+}
+
+impl EventQuery {
+    /// The next event, or None if none arrived within `timeout`
+    pub fn next(&self, timeout: Duration) -> Result<Option<IWbemClassObject>> {
+        unsafe {
+            let mut objects = [None];
+            let mut returned = 0;
+            self.events
+                .Next(timeout.as_millis() as i32, &mut objects, &mut returned)
+                .ok()
+                .map_err(|e| anyhow!("WMI event wait failed: {}", e))?;
+            Ok(objects[0].take().filter(|_| returned > 0))
+        }
+    }
+}
+
+pub fn get_string(object: &IWbemClassObject, name: &str) -> Option<String> {
+    with_property(object, name, |value| unsafe {
+        let text = VariantToStringAlloc(value).ok()?;
+        let result = text.to_string().ok();
+        CoTaskMemFree(Some(text.0 as *const _));
+        result
+    })
+}
+
+pub fn get_number(object: &IWbemClassObject, name: &str) -> Option<f64> {
+    with_property(object, name, |value| unsafe { VariantToDouble(value).ok() })
+}
+
+/// Embedded object such as the `TargetInstance` of an instance event
+pub fn get_object(object: &IWbemClassObject, name: &str) -> Option<IWbemClassObject> {
+    with_property(object, name, |value| unsafe {
+        if value.Anonymous.Anonymous.vt != VT_UNKNOWN {
+            return None;
+        }
+        let unknown: &Option<IUnknown> = &value.Anonymous.Anonymous.Anonymous.punkVal;
+        unknown.as_ref()?.cast().ok()
+    })
+}
+
+fn with_property<T>(object: &IWbemClassObject, name: &str, read: impl FnOnce(&VARIANT) -> Option<T>) -> Option<T> {
+    let name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+    let mut value = VARIANT::default();
+    unsafe {
+        object.Get(PCWSTR(name.as_ptr()), 0, &mut value, None, None).ok()?;
+        let result = read(&value);
+        let _ = VariantClear(&mut value);
+        result
+    }
+}
@@ -0,0 +1,254 @@
+//! Activation plans run against fake process, service and power backends: `cargo test -p
+//! gaming_optimizer_core --test activation`
+//! Checks which steps a profile gets, what each step asks the machine to do, what is put back
+//! on deactivation or rollback, and the report the GUI shows, without touching this machine
+
+use anyhow::{anyhow, Result};
+use gaming_optimizer_core::activation::steps::{plan_for_profile, Backends, SystemState};
+use gaming_optimizer_core::activation::StepStatus;
+use gaming_optimizer_core::power::PowerBackend;
+use gaming_optimizer_core::process::{FakeExit, FakeProcesses};
+use gaming_optimizer_core::profile::{create_profile, Profile};
+use gaming_optimizer_core::obs::ObsConfig;
+use gaming_optimizer_core::openrgb::OpenRgbConfig;
+use gaming_optimizer_core::overlay::NoOverlay;
+use gaming_optimizer_core::services::ServiceBackend;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+const BALANCED: &str = "381b4222-f694-41f0-9685-ff5bb260df2e";
+const HIGH_PERFORMANCE: &str = "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c";
+
+/// Calls the service and power fakes received, in order ("stop DoSvc", "power HIGH", ...)
+type CallLog = Arc<Mutex<Vec<String>>>;
+
+/// Services that are running; stopping and starting only records the call
+struct FakeServices {
+    elevated: bool,
+    running: Vec<&'static str>,
+    log: CallLog,
+}
+
+impl ServiceBackend for FakeServices {
+    fn check_access(&self) -> Result<()> {
+        if self.elevated {
+            Ok(())
+        } else {
+            Err(anyhow!("needs administrator"))
+        }
+    }
+
+    fn is_running(&mut self, name: &str) -> Result<bool> {
+        Ok(self.running.contains(&name))
+    }
+
+    fn stop(&mut self, name: &str) -> Result<()> {
+        self.log.lock().unwrap().push(format!("stop {}", name));
+        Ok(())
+    }
+
+    fn start(&mut self, name: &str) -> Result<()> {
+        self.log.lock().unwrap().push(format!("start {}", name));
+        Ok(())
+    }
+}
+
+/// Starts on the balanced plan; switching to `refuse` fails
+struct FakePower {
+    active: String,
+    refuse: Option<&'static str>,
+    log: CallLog,
+}
+
+impl PowerBackend for FakePower {
+    fn active_plan(&mut self) -> Result<String> {
+        Ok(self.active.clone())
+    }
+
+    fn set_active_plan(&mut self, guid: &str) -> Result<()> {
+        self.log.lock().unwrap().push(format!("power {}", guid));
+        if self.refuse == Some(guid) {
+            return Err(anyhow!("plan {} is locked by policy", guid));
+        }
+        self.active = guid.to_string();
+        Ok(())
+    }
+}
+
+/// Discord (two instances), OneDrive that hangs on its way out, and Explorer
+fn fake_processes() -> FakeProcesses {
+    let processes = FakeProcesses::new(&[("Discord.exe", 100), ("Discord.exe", 101), ("OneDrive.exe", 200), ("explorer.exe", 300)]);
+    processes.set_exit(200, FakeExit::Hangs);
+    processes
+}
+
+fn fake_state(processes: &FakeProcesses, log: &CallLog, elevated: bool, refuse_power: Option<&'static str>) -> SystemState {
+    SystemState {
+        backends: Backends {
+            processes: Box::new(processes.clone()),
+            services: Box::new(FakeServices { elevated, running: vec!["DoSvc", "WSearch"], log: Arc::clone(log) }),
+            power: Box::new(FakePower { active: BALANCED.to_string(), refuse: refuse_power, log: Arc::clone(log) }),
+            overlay: Box::new(NoOverlay),
+        },
+        ..Default::default()
+    }
+}
+
+/// Closes apps, switches the power plan and pauses background services
+fn game_profile() -> Profile {
+    let mut profile = create_profile("Competitive".to_string());
+    profile.overlay_enabled = false;
+    profile.processes_to_kill =
+        vec!["discord.exe".to_string(), "OneDrive.exe".to_string(), "Spotify.exe".to_string(), "explorer.exe".to_string()];
+    profile.power_plan = Some(HIGH_PERFORMANCE.to_string());
+    profile.network.pause_delivery_optimization = true;
+    profile.storage.pause_search_indexing = true;
+    profile
+}
+
+fn calls(log: &CallLog) -> Vec<String> {
+    std::mem::take(&mut *log.lock().unwrap())
+}
+
+#[test]
+fn test_plan_has_a_step_per_used_feature() {
+    let plan = plan_for_profile(&game_profile(), &[], &OpenRgbConfig::default(), &ObsConfig::default());
+    assert_eq!(plan.step_names(), vec!["Processes", "Power", "Network", "Storage"]);
+
+    let mut profile = create_profile("Empty".to_string());
+    profile.overlay_enabled = false;
+    assert!(plan_for_profile(&profile, &[], &OpenRgbConfig::default(), &ObsConfig::default()).step_names().is_empty());
+
+    profile.obs.scene = Some("Gameplay".to_string());
+    assert_eq!(plan_for_profile(&profile, &[], &OpenRgbConfig::default(), &ObsConfig::default()).step_names(), vec!["OBS"]);
+}
+
+#[test]
+fn test_activation_applies_and_reverts_through_backends() {
+    let processes = fake_processes();
+    let log = CallLog::default();
+    let mut state = fake_state(&processes, &log, true, None);
+    let plan = plan_for_profile(&game_profile(), &[], &OpenRgbConfig::default(), &ObsConfig::default());
+
+    let (applied, summary) = plan.execute(&mut state);
+    assert!(!summary.rolled_back);
+    assert_eq!(summary.failed_count(), 0);
+    assert_eq!(summary.applied_count(), 4);
+    assert_eq!(
+        calls(&log),
+        vec![
+            format!("power {}", HIGH_PERFORMANCE),
+            "stop DoSvc".to_string(),
+            "stop WSearch".to_string(),
+        ]
+    );
+
+    assert_eq!(processes.terminated(), [100, 101, 200]);
+    assert_eq!(processes.running(), ["OneDrive.exe", "explorer.exe"]);
+    let report = state.kill_report.as_ref().unwrap();
+    assert_eq!(report.killed, vec!["discord.exe"]);
+    assert_eq!(report.timed_out, vec!["OneDrive.exe"]);
+    assert_eq!(report.not_found, vec!["Spotify.exe"]);
+    assert_eq!(report.blocklist_skipped, vec!["explorer.exe"]);
+    assert_eq!(state.processes_killed, 1);
+
+    let text = summary.report_text();
+    assert!(text.starts_with("Profile: Competitive\n"));
+    assert!(text.contains("Processes: Killed: discord.exe; Timed out: OneDrive.exe; Not running: Spotify.exe; Protected: explorer.exe"));
+    assert!(text.contains("Power: ⚡ Power: "));
+    assert!(text.contains("Network: 🌐 Delivery Optimization paused"));
+    assert!(text.contains("Storage: 💽 Search indexing paused"));
+
+    // Last step first, and only what the profile changed
+    assert!(applied.revert(&mut state).is_empty());
+    assert_eq!(calls(&log), vec!["start WSearch".to_string(), "start DoSvc".to_string(), format!("power {}", BALANCED)]);
+}
+
+#[test]
+fn test_services_left_alone_without_admin() {
+    let log = CallLog::default();
+    let mut state = fake_state(&FakeProcesses::default(), &log, false, None);
+    let mut profile = game_profile();
+    profile.processes_to_kill.clear();
+    profile.power_plan = None;
+
+    let (applied, summary) = plan_for_profile(&profile, &[], &OpenRgbConfig::default(), &ObsConfig::default()).execute(&mut state);
+    assert_eq!(summary.failed_count(), 0);
+    assert_eq!(
+        summary.results[0].status,
+        StepStatus::Applied(vec!["Network: needs administrator".to_string()])
+    );
+    assert!(calls(&log).is_empty());
+
+    assert!(applied.revert(&mut state).is_empty());
+    assert!(calls(&log).is_empty());
+}
+
+#[test]
+fn test_failed_power_switch_rolls_back() {
+    let processes = fake_processes();
+    processes.set_exe(100, "C:/Apps/Discord/Discord.exe");
+    processes.set_exe(101, "C:/Apps/Discord/Discord.exe");
+    let log = CallLog::default();
+    let mut state = fake_state(&processes, &log, true, Some(HIGH_PERFORMANCE));
+
+    // The power plan is essential, so this rolls back without the profile's rollback_on_failure
+    let profile = game_profile();
+    assert!(!profile.rollback_on_failure);
+    let (applied, summary) = plan_for_profile(&profile, &[], &OpenRgbConfig::default(), &ObsConfig::default()).execute(&mut state);
+    assert!(summary.rolled_back);
+    assert_eq!(summary.headline(), "❌ Profile 'Competitive' not activated: Power failed, system restored.");
+    let statuses: Vec<_> = summary.results.iter().map(|r| (r.name, r.status.clone())).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("Processes", StepStatus::RolledBack),
+            ("Power", StepStatus::Failed(format!("plan {} is locked by policy", HIGH_PERFORMANCE))),
+            ("Network", StepStatus::Skipped),
+            ("Storage", StepStatus::Skipped),
+        ]
+    );
+    assert!(summary.revert_errors.is_empty());
+
+    // Discord was started again, the half-applied power step put the original plan back and
+    // services were never touched
+    assert_eq!(processes.launched(), [PathBuf::from("C:/Apps/Discord/Discord.exe")]);
+    assert_eq!(processes.running(), ["OneDrive.exe", "explorer.exe", "Discord.exe"]);
+    assert_eq!(calls(&log), [format!("power {}", HIGH_PERFORMANCE), format!("power {}", BALANCED)]);
+
+    let text = summary.report_text();
+    assert!(text.starts_with("Profile: Competitive (rolled back)\n"));
+    assert!(text.contains("Processes: rolled back"));
+    assert!(text.contains(&format!("Power: FAILED: plan {} is locked by policy", HIGH_PERFORMANCE)));
+    assert!(text.contains("Storage: skipped"));
+
+    // Nothing left to undo on deactivation
+    assert!(applied.revert(&mut state).is_empty());
+    assert!(calls(&log).is_empty());
+}
+
+#[test]
+fn test_deactivation_relaunches_closed_apps() {
+    let processes = fake_processes();
+    processes.set_exe(100, "C:/Apps/Discord/Discord.exe");
+    processes.set_exe(101, "C:/Apps/Discord/Discord.exe");
+    processes.set_exe(200, "C:/Apps/OneDrive/OneDrive.exe");
+    let log = CallLog::default();
+    let mut state = fake_state(&processes, &log, true, None);
+
+    let plan = || plan_for_profile(&game_profile(), &[], &OpenRgbConfig::default(), &ObsConfig::default());
+    let (applied, _) = plan().execute(&mut state);
+    // Both Discord instances share an exe; OneDrive never closed
+    assert_eq!(state.kill_report.as_ref().unwrap().closed_exes, [PathBuf::from("C:/Apps/Discord/Discord.exe")]);
+    assert!(!processes.running().contains(&"Discord.exe".to_string()));
+
+    assert!(applied.revert(&mut state).is_empty());
+    assert_eq!(processes.launched(), [PathBuf::from("C:/Apps/Discord/Discord.exe")]);
+    assert!(processes.running().contains(&"Discord.exe".to_string()));
+
+    // An app the user already started again is left alone
+    let (applied, _) = plan().execute(&mut state);
+    processes.start("Discord.exe", 102, 0);
+    assert!(applied.revert(&mut state).is_empty());
+    assert_eq!(processes.launched().len(), 1);
+}
@@ -1,62 +1,169 @@
 //! Standalone crosshair overlay - works over fullscreen games
 //! Uses DWM composition like Xbox Game Bar, Discord, and NVIDIA overlays
-//! Usage: crosshair.exe <image_path> <x_offset> <y_offset>
+//! The image is uploaded once; afterwards the process blocks on its message queue and only
+//! wakes to re-place the windows on display mode changes or re-assert topmost
+//! Usage: crosshair.exe <image_path> <x_offset> <y_offset> [--exclude-from-capture] [--tint RRGGBBAA]
+//!        [--note <text> --note-size <px> --note-corner <corner> --note-color RRGGBBAA] [--anchor X%,Y%]
+//!        [--hud <file> --hud-corner <corner>]
+//! The app moves the running crosshair by posting `WM_SET_OFFSET` to the window titled `WINDOW_TITLE`,
+//! and hides or shows it with `WM_SET_VISIBLE` while the optimizer is paused. Switching profiles
+//! sends the new arguments with `WM_COPYDATA`, which repaints the existing windows in place.
 
 #![windows_subsystem = "windows"]
 
+use gaming_optimizer_core::crosshair_position::CrosshairAnchor;
+use gaming_optimizer_core::overlay_render::{apply_tint, to_premultiplied_bgra};
+use gaming_optimizer_core::overlay_text::{self, Corner, TextBitmap};
 use std::env;
 use std::path::Path;
 
+/// Exit code reported when the overlay can't be made click-through
+/// (must match `EXIT_CLICK_THROUGH_FAILED` in src/crosshair_overlay.rs)
+#[cfg(windows)]
+const EXIT_CLICK_THROUGH_FAILED: i32 = 3;
+
+/// Title of the crosshair window, so the app can find it (must match `WINDOW_TITLE` in
+/// crosshair_overlay.rs)
+#[cfg(windows)]
+const WINDOW_TITLE: &str = "Gaming Optimizer Crosshair";
+
+/// Move the crosshair to new offsets: wParam is x, lParam is y (must match `WM_SET_OFFSET` in
+/// crosshair_overlay.rs)
+#[cfg(windows)]
+const WM_SET_OFFSET: u32 = 0x8001; // WM_APP + 1
+
+/// Hide (wParam 0) or show (wParam 1) the crosshair, note and HUD (must match `WM_SET_VISIBLE`
+/// in src/crosshair_overlay.rs)
+#[cfg(windows)]
+const WM_SET_VISIBLE: u32 = 0x8002; // WM_APP + 2
+
+/// `dwData` of the `WM_COPYDATA` that swaps a new crosshair into the running overlay; the data is
+/// the command-line arguments joined with NULs (must match `SWAP_OVERLAY_DATA` in
+/// crosshair_overlay.rs)
+#[cfg(windows)]
+const SWAP_OVERLAY_DATA: usize = 0x4348_5253; // "CHRS"
+
+/// Window class shared by the crosshair, note and HUD windows
+#[cfg(windows)]
+const CLASS_NAME: &str = "CrosshairDWMOverlay";
+
 fn main() {
     let args: Vec<String> = env::args().collect();
     
-    if args.len() < 4 {
+    let Some(request) = parse_request(&args[1..]) else {
         return;
+    };
+    
+    #[cfg(windows)]
+    unsafe {
+        run_overlay(request);
+    }
+    #[cfg(not(windows))]
+    let _ = request;
+}
+
+/// Crosshair pixels and placement, from the command line or a later `WM_COPYDATA` swap
+struct OverlayRequest {
+    /// Premultiplied BGRA
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    x_offset: i32,
+    y_offset: i32,
+    flags: OverlayFlags,
+}
+
+/// Parse `<image_path> <x_offset> <y_offset> [flags]` and load the image; None if it can't be
+/// loaded
+fn parse_request(args: &[String]) -> Option<OverlayRequest> {
+    if args.len() < 3 {
+        return None;
     }
     
-    let image_path = &args[1];
-    let x_offset: i32 = args[2].parse().unwrap_or(0);
-    let y_offset: i32 = args[3].parse().unwrap_or(0);
+    let image_path = &args[0];
+    let x_offset: i32 = args[1].parse().unwrap_or(0);
+    let y_offset: i32 = args[2].parse().unwrap_or(0);
+    let flags = &args[3..];
+    let exclude_from_capture = flags.iter().any(|a| a == "--exclude-from-capture");
+    let tint = flag_value(flags, "--tint").and_then(parse_hex_color);
+    let anchor = flag_value(flags, "--anchor")
+        .and_then(|s| s.parse::<CrosshairAnchor>().ok())
+        .unwrap_or_default();
+    let note = flag_value(flags, "--note").map(|text| {
+        let font_size = flag_value(flags, "--note-size")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(16);
+        let color = flag_value(flags, "--note-color")
+            .and_then(parse_hex_color)
+            .unwrap_or([255, 255, 255, 255]);
+        let corner = flag_value(flags, "--note-corner")
+            .and_then(|s| s.parse::<Corner>().ok())
+            .unwrap_or_default();
+        (overlay_text::rasterize(text, font_size, color), corner)
+    });
+    let hud = flag_value(flags, "--hud").map(|path| {
+        let corner = flag_value(flags, "--hud-corner")
+            .and_then(|s| s.parse::<Corner>().ok())
+            .unwrap_or_default();
+        (path.to_string(), corner)
+    });
     
     if !Path::new(image_path).exists() {
-        return;
+        return None;
     }
     
     // Load image
-    let img = match image::open(image_path) {
-        Ok(img) => img,
-        Err(_) => return,
-    };
-    
-    let rgba = img.to_rgba8();
-    let width = rgba.width();
-    let height = rgba.height();
+    let rgba = image::open(image_path).ok()?.to_rgba8();
     
     // Convert to BGRA (premultiplied alpha for UpdateLayeredWindow)
-    let mut bgra_pixels: Vec<u8> = Vec::with_capacity((width * height * 4) as usize);
-    for pixel in rgba.pixels() {
-        let a = pixel[3] as f32 / 255.0;
-        // Premultiply alpha for proper blending
-        bgra_pixels.push((pixel[2] as f32 * a) as u8); // B
-        bgra_pixels.push((pixel[1] as f32 * a) as u8); // G
-        bgra_pixels.push((pixel[0] as f32 * a) as u8); // R
-        bgra_pixels.push(pixel[3]);                     // A
+    Some(OverlayRequest {
+        pixels: to_premultiplied_bgra(rgba.pixels().map(|p| apply_tint(p.0, tint))),
+        width: rgba.width(),
+        height: rgba.height(),
+        x_offset,
+        y_offset,
+        flags: OverlayFlags {
+            exclude_from_capture,
+            note,
+            hud,
+            anchor,
+        },
+    })
+}
+
+/// Optional window behaviour parsed from the trailing command-line flags
+struct OverlayFlags {
+    exclude_from_capture: bool,
+    note: Option<(TextBitmap, Corner)>,
+    /// Text file with the hardware readings (rewritten by the app) and where to show them
+    hud: Option<(String, Corner)>,
+    anchor: CrosshairAnchor,
+}
+
+/// Get the value following a `--flag` argument
+fn flag_value<'a>(flags: &'a [String], name: &str) -> Option<&'a str> {
+    flags
+        .iter()
+        .position(|a| a == name)
+        .and_then(|i| flags.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+/// Parse a `RRGGBB` or `RRGGBBAA` hex color (leading `#` optional)
+fn parse_hex_color(text: &str) -> Option<[u8; 4]> {
+    let hex = text.trim().trim_start_matches('#');
+    if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+        return None;
     }
     
-    #[cfg(windows)]
-    unsafe {
-        run_overlay(bgra_pixels, width, height, x_offset, y_offset);
-    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+    
+    Some([channel(0)?, channel(2)?, channel(4)?, alpha])
 }
 
 #[cfg(windows)]
-unsafe fn run_overlay(
-    pixels: Vec<u8>,
-    img_width: u32,
-    img_height: u32,
-    x_offset: i32,
-    y_offset: i32,
-) {
+unsafe fn run_overlay(request: OverlayRequest) {
     use std::mem::zeroed;
     use std::ptr::null_mut;
     
@@ -69,14 +176,15 @@ unsafe fn run_overlay(
     use windows::Win32::Graphics::Dwm::DwmExtendFrameIntoClientArea;
     use windows::Win32::UI::Controls::MARGINS;
     use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::UI::Accessibility::{SetWinEventHook, UnhookWinEvent};
     use windows::Win32::UI::WindowsAndMessaging::{
-        CreateWindowExW, DispatchMessageW, PeekMessageW,
-        GetSystemMetrics, RegisterClassExW, SetWindowPos, ShowWindow,
-        UpdateLayeredWindow, CS_HREDRAW, CS_VREDRAW, HWND_TOPMOST,
-        MSG, PM_REMOVE, SM_CXSCREEN, SM_CYSCREEN, SWP_NOMOVE, SWP_NOSIZE,
-        SWP_NOACTIVATE, SW_SHOWNA, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_TOOLWINDOW,
+        CreateWindowExW, DispatchMessageW, GetMessageW, KillTimer, SetTimer,
+        GetSystemMetrics, RegisterClassExW, SetWindowDisplayAffinity, SetWindowPos, ShowWindow,
+        UpdateLayeredWindow, CS_HREDRAW, CS_VREDRAW, EVENT_SYSTEM_FOREGROUND, HWND_TOPMOST,
+        MSG, SM_CXSCREEN, SM_CYSCREEN, SWP_NOMOVE, SWP_NOSIZE,
+        SWP_NOACTIVATE, SW_SHOWNA, WINEVENT_OUTOFCONTEXT, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_TOOLWINDOW,
         WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_EX_NOACTIVATE, WS_POPUP,
-        ULW_ALPHA,
+        ULW_ALPHA, WDA_EXCLUDEFROMCAPTURE,
     };
     use windows::core::PCWSTR;
     
@@ -84,12 +192,22 @@ unsafe fn run_overlay(
     let screen_w = GetSystemMetrics(SM_CXSCREEN);
     let screen_h = GetSystemMetrics(SM_CYSCREEN);
     
-    // Calculate centered position
-    let win_x = (screen_w / 2) - (img_width as i32 / 2) + x_offset;
-    let win_y = (screen_h / 2) - (img_height as i32 / 2) + y_offset;
+    let OverlayRequest { pixels, width: img_width, height: img_height, x_offset, y_offset, flags } = request;
+    let OverlayFlags { exclude_from_capture, note, hud, anchor } = flags;
+    
+    // Calculate anchored position (screen center unless --anchor was given)
+    let (win_x, win_y) = anchor.window_position(
+        screen_w,
+        screen_h,
+        img_width as i32,
+        img_height as i32,
+        x_offset,
+        y_offset,
+    );
     
     // Unique class name
-    let class_name: Vec<u16> = "CrosshairDWMOverlay\0".encode_utf16().collect();
+    let class_name: Vec<u16> = CLASS_NAME.encode_utf16().chain(Some(0)).collect();
+    let title: Vec<u16> = WINDOW_TITLE.encode_utf16().chain(Some(0)).collect();
     
     let hinstance = match GetModuleHandleW(PCWSTR::null()) {
         Ok(h) => HINSTANCE(h.0),
@@ -158,7 +276,7 @@ unsafe fn run_overlay(
     let hwnd = CreateWindowExW(
         WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOPMOST | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE,
         PCWSTR(class_name.as_ptr()),
-        PCWSTR::null(),
+        PCWSTR(title.as_ptr()),
         WS_POPUP,
         win_x,
         win_y,
@@ -189,6 +307,12 @@ unsafe fn run_overlay(
     };
     let _ = DwmExtendFrameIntoClientArea(hwnd, &margins);
     
+    // Hide from OBS/screenshots but keep it on the physical display
+    // (requires Windows 10 2004+, older builds just ignore the request)
+    if exclude_from_capture {
+        let _ = SetWindowDisplayAffinity(hwnd, WDA_EXCLUDEFROMCAPTURE);
+    }
+    
     // Use UpdateLayeredWindow with per-pixel alpha for proper transparency
     let blend = BLENDFUNCTION {
         BlendOp: AC_SRC_OVER as u8,
@@ -206,6 +330,7 @@ unsafe fn run_overlay(
     let win_point = POINT { x: win_x, y: win_y };
     
     // Update the layered window with our bitmap
+    count_redraw();
     let _ = UpdateLayeredWindow(
         hwnd,
         screen_dc,
@@ -226,35 +351,652 @@ unsafe fn run_overlay(
     // Show window without activating
     let _ = ShowWindow(hwnd, SW_SHOWNA);
     
-    // Store for cleanup
+    // Never leave a window on top of the game that swallows clicks
+    let center = POINT {
+        x: win_x + img_width as i32 / 2,
+        y: win_y + img_height as i32 / 2,
+    };
+    if !ensure_click_through(hwnd, center) {
+        let _ = windows::Win32::UI::WindowsAndMessaging::DestroyWindow(hwnd);
+        SelectObject(mem_dc, old_obj);
+        let _ = DeleteObject(hbitmap);
+        let _ = DeleteDC(mem_dc);
+        std::process::exit(EXIT_CLICK_THROUGH_FAILED);
+    }
+    
+    // Note text lives in its own small window in a screen corner
+    let note = note.and_then(|(bitmap, corner)| {
+        let (width, height) = (bitmap.width as i32, bitmap.height as i32);
+        let (x, y) = note_position(corner, screen_w, screen_h, width, height);
+        create_note_window(&class_name, hinstance, &bitmap, x, y, exclude_from_capture)
+            .map(|note_hwnd| (note_hwnd, width, height, corner))
+    });
+    
+    // The HUD window starts empty and is filled by the first refresh
+    let hud = hud.and_then(|(path, corner)| {
+        let hud_hwnd = create_text_window(&class_name, hinstance, 0, 0, exclude_from_capture)?;
+        HUD_SOURCE.with(|source| *source.borrow_mut() = Some(HudSource { path, text: String::new(), shown: false }));
+        Some((hud_hwnd, 0, 0, corner))
+    });
+    
+    // Store for cleanup and for re-placing after display changes
     GLOBAL_HWND = Some(hwnd);
+    PLACEMENT = Some(Placement {
+        hwnd,
+        width: img_width as i32,
+        height: img_height as i32,
+        x_offset,
+        y_offset,
+        anchor,
+        note,
+        hud,
+    });
+    
+    // Fullscreen games mostly push the overlay down when they take the foreground,
+    // so re-assert topmost on that event instead of polling
+    let foreground_hook = SetWinEventHook(
+        EVENT_SYSTEM_FOREGROUND,
+        EVENT_SYSTEM_FOREGROUND,
+        None,
+        Some(on_foreground_changed),
+        0,
+        0,
+        WINEVENT_OUTOFCONTEXT,
+    );
+    // Slow safety net for games that cover the overlay without a foreground change
+    SetTimer(hwnd, TOPMOST_TIMER_ID, TOPMOST_FALLBACK_MS, None);
+    if hud.is_some() {
+        SetTimer(hwnd, HUD_TIMER_ID, HUD_REFRESH_MS, None);
+        refresh_hud();
+    }
     
-    // Message loop with periodic topmost refresh
+    // Blocking message loop - the thread sleeps until there is something to do
     let mut msg: MSG = zeroed();
-    let mut counter: u32 = 0;
-    
-    loop {
-        // Process messages (non-blocking)
-        while PeekMessageW(&mut msg, HWND::default(), 0, 0, PM_REMOVE).as_bool() {
-            if msg.message == 0x0012 { // WM_QUIT
-                // Cleanup
-                SelectObject(mem_dc, old_obj);
-                let _ = DeleteObject(hbitmap);
-                let _ = DeleteDC(mem_dc);
-                GLOBAL_HWND = None;
-                return;
-            }
-            let _ = DispatchMessageW(&msg);
+    while GetMessageW(&mut msg, HWND::default(), 0, 0).0 > 0 {
+        count_wakeup();
+        let _ = DispatchMessageW(&msg);
+    }
+    #[cfg(debug_assertions)]
+    {
+        use std::sync::atomic::Ordering;
+        use windows::core::HSTRING;
+        use windows::Win32::System::Diagnostics::Debug::OutputDebugStringW;
+        
+        let summary = format!(
+            "[Crosshair] {} wake-ups, {} redraws\n",
+            WAKEUPS.load(Ordering::Relaxed),
+            REDRAWS.load(Ordering::Relaxed)
+        );
+        OutputDebugStringW(&HSTRING::from(summary));
+    }
+    
+    // Cleanup
+    let _ = KillTimer(hwnd, TOPMOST_TIMER_ID);
+    if hud.is_some() {
+        let _ = KillTimer(hwnd, HUD_TIMER_ID);
+    }
+    if !foreground_hook.is_invalid() {
+        let _ = UnhookWinEvent(foreground_hook);
+    }
+    SelectObject(mem_dc, old_obj);
+    let _ = DeleteObject(hbitmap);
+    let _ = DeleteDC(mem_dc);
+    GLOBAL_HWND = None;
+    PLACEMENT = None;
+}
+
+/// Timer used as a fallback to re-assert topmost
+#[cfg(windows)]
+const TOPMOST_TIMER_ID: usize = 1;
+
+/// Interval of the topmost fallback timer (foreground changes are handled immediately)
+#[cfg(windows)]
+const TOPMOST_FALLBACK_MS: u32 = 1000;
+
+/// Timer that re-reads the HUD file
+#[cfg(windows)]
+const HUD_TIMER_ID: usize = 2;
+
+/// How often the HUD file is re-read (the app rewrites it on every hardware sample)
+#[cfg(windows)]
+const HUD_REFRESH_MS: u32 = 1000;
+
+#[cfg(windows)]
+const HUD_FONT_SIZE: u32 = 14;
+
+#[cfg(windows)]
+const HUD_COLOR: [u8; 4] = [255, 255, 255, 230];
+
+/// Where the HUD text comes from and what is currently shown
+struct HudSource {
+    path: String,
+    text: String,
+    /// The window has been shown (and checked for click-through) once
+    shown: bool,
+}
+
+impl HudSource {
+    /// Take the HUD file's latest text (None if it couldn't be read, which keeps the last
+    /// readings); whether the HUD has to be repainted. A timer tick with unchanged readings
+    /// never redraws.
+    fn take_text(&mut self, file_text: Option<&str>) -> bool {
+        let Some(text) = file_text.map(str::trim_end) else {
+            return false;
+        };
+        if text == self.text {
+            return false;
         }
+        self.text = text.to_string();
+        true
+    }
+}
+
+/// Wake-ups of the overlay thread (posted messages, timers and foreground changes) and layered
+/// window redraws, counted in debug builds and written to the debugger output on exit
+#[cfg(all(windows, debug_assertions))]
+static WAKEUPS: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+#[cfg(all(windows, debug_assertions))]
+static REDRAWS: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+#[cfg(windows)]
+fn count_wakeup() {
+    #[cfg(debug_assertions)]
+    WAKEUPS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(windows)]
+fn count_redraw() {
+    #[cfg(debug_assertions)]
+    REDRAWS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(windows)]
+thread_local! {
+    static HUD_SOURCE: std::cell::RefCell<Option<HudSource>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Overlay windows and the parameters needed to position them on the current display
+#[cfg(windows)]
+#[derive(Clone, Copy)]
+struct Placement {
+    hwnd: windows::Win32::Foundation::HWND,
+    width: i32,
+    height: i32,
+    x_offset: i32,
+    y_offset: i32,
+    anchor: CrosshairAnchor,
+    /// Note window with its size and corner
+    note: Option<(windows::Win32::Foundation::HWND, i32, i32, Corner)>,
+    /// Hardware HUD window with its current size (0 x 0 until the first text) and corner
+    hud: Option<(windows::Win32::Foundation::HWND, i32, i32, Corner)>,
+}
+
+#[cfg(windows)]
+static mut PLACEMENT: Option<Placement> = None;
+
+/// Windows hidden by the app (the HUD stops refreshing until they're shown again)
+#[cfg(windows)]
+static mut HIDDEN: bool = false;
+
+/// Put the overlay windows back on top, optionally moving them for the current screen size
+#[cfg(windows)]
+unsafe fn reassert_overlay(reposition: bool) {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetSystemMetrics, SetWindowPos, HWND_TOPMOST, SM_CXSCREEN, SM_CYSCREEN,
+        SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+    };
+    
+    let Some(placement) = PLACEMENT else {
+        return;
+    };
+    
+    let screen_w = GetSystemMetrics(SM_CXSCREEN);
+    let screen_h = GetSystemMetrics(SM_CYSCREEN);
+    let flags = if reposition {
+        SWP_NOSIZE | SWP_NOACTIVATE
+    } else {
+        SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE
+    };
+    
+    let (x, y) = placement.anchor.window_position(
+        screen_w,
+        screen_h,
+        placement.width,
+        placement.height,
+        placement.x_offset,
+        placement.y_offset,
+    );
+    let _ = SetWindowPos(placement.hwnd, HWND_TOPMOST, x, y, 0, 0, flags);
+    
+    for (text_hwnd, width, height, corner) in placement.note.into_iter().chain(placement.hud) {
+        let (x, y) = note_position(corner, screen_w, screen_h, width, height);
+        let _ = SetWindowPos(text_hwnd, HWND_TOPMOST, x, y, 0, 0, flags);
+    }
+}
+
+/// Re-read the HUD file and repaint the HUD window if the text changed
+#[cfg(windows)]
+unsafe fn refresh_hud() {
+    use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, ShowWindow, SM_CXSCREEN, SM_CYSCREEN, SW_HIDE};
+    
+    let Some(mut placement) = PLACEMENT else {
+        return;
+    };
+    if HIDDEN {
+        return;
+    }
+    let Some((hud_hwnd, _, _, corner)) = placement.hud else {
+        return;
+    };
+    
+    HUD_SOURCE.with(|cell| {
+        let mut cell = cell.borrow_mut();
+        let Some(source) = cell.as_mut() else {
+            return;
+        };
         
-        // Every ~100ms, re-assert topmost (fights fullscreen games)
-        counter = counter.wrapping_add(1);
-        if counter % 6 == 0 {
-            let _ = SetWindowPos(hwnd, HWND_TOPMOST, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE);
+        // A missing or unreadable file keeps the last readings on screen
+        let file_text = std::fs::read_to_string(&source.path).ok();
+        if !source.take_text(file_text.as_deref()) {
+            return;
         }
         
-        std::thread::sleep(std::time::Duration::from_millis(16));
+        let bitmap = overlay_text::rasterize(&source.text, HUD_FONT_SIZE, HUD_COLOR);
+        let (width, height) = (bitmap.width as i32, bitmap.height as i32);
+        let (x, y) = note_position(
+            corner,
+            GetSystemMetrics(SM_CXSCREEN),
+            GetSystemMetrics(SM_CYSCREEN),
+            width,
+            height,
+        );
+        if source.text.is_empty() || !paint_text_window(hud_hwnd, &bitmap, x, y) {
+            let _ = ShowWindow(hud_hwnd, SW_HIDE);
+            source.shown = false;
+            return;
+        }
+        
+        if !source.shown {
+            if !show_text_window(hud_hwnd, &bitmap, x, y) {
+                // Never keep a HUD that takes clicks; stop refreshing it
+                let _ = ShowWindow(hud_hwnd, SW_HIDE);
+                *cell = None;
+                placement.hud = None;
+                PLACEMENT = Some(placement);
+                return;
+            }
+            source.shown = true;
+        }
+        placement.hud = Some((hud_hwnd, width, height, corner));
+        PLACEMENT = Some(placement);
+    });
+}
+
+/// Hide or show every overlay window. A shown HUD is repainted (and checked for
+/// click-through again) on its next refresh.
+#[cfg(windows)]
+unsafe fn set_overlay_visible(visible: bool) {
+    use windows::Win32::UI::WindowsAndMessaging::{ShowWindow, SW_HIDE, SW_SHOWNA};
+    
+    let Some(placement) = PLACEMENT else {
+        return;
+    };
+    HIDDEN = !visible;
+    let command = if visible { SW_SHOWNA } else { SW_HIDE };
+    let _ = ShowWindow(placement.hwnd, command);
+    if let Some((note_hwnd, ..)) = placement.note {
+        let _ = ShowWindow(note_hwnd, command);
+    }
+    if let Some((hud_hwnd, ..)) = placement.hud {
+        let _ = ShowWindow(hud_hwnd, SW_HIDE);
+        HUD_SOURCE.with(|cell| {
+            if let Some(source) = cell.borrow_mut().as_mut() {
+                source.text.clear();
+                source.shown = false;
+            }
+        });
+    }
+    if visible {
+        reassert_overlay(true);
+        refresh_hud();
+    }
+}
+
+/// Swap a new crosshair, note, HUD and capture setting into the running windows, so switching
+/// profiles doesn't tear the overlay down and flash a new one up
+#[cfg(windows)]
+unsafe fn swap_overlay(request: OverlayRequest) -> bool {
+    use windows::Win32::Foundation::{HINSTANCE, POINT};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        DestroyWindow, GetSystemMetrics, KillTimer, SetTimer, SetWindowDisplayAffinity, ShowWindow,
+        SM_CXSCREEN, SM_CYSCREEN, SW_HIDE, WDA_EXCLUDEFROMCAPTURE, WDA_NONE,
+    };
+    use windows::core::PCWSTR;
+    
+    let Some(mut placement) = PLACEMENT else {
+        return false;
+    };
+    let Ok(module) = GetModuleHandleW(PCWSTR::null()) else {
+        return false;
+    };
+    let hinstance = HINSTANCE(module.0);
+    let class_name: Vec<u16> = CLASS_NAME.encode_utf16().chain(Some(0)).collect();
+    
+    let OverlayRequest { pixels, width, height, x_offset, y_offset, flags } = request;
+    let OverlayFlags { exclude_from_capture, note, hud, anchor } = flags;
+    let (width, height) = (width as i32, height as i32);
+    let screen_w = GetSystemMetrics(SM_CXSCREEN);
+    let screen_h = GetSystemMetrics(SM_CYSCREEN);
+    
+    // Same window, new pixels: UpdateLayeredWindow resizes and moves it in one go
+    let (x, y) = anchor.window_position(screen_w, screen_h, width, height, x_offset, y_offset);
+    if !paint_layered_window(placement.hwnd, &pixels, width, height, x, y) {
+        return false;
+    }
+    let center = POINT {
+        x: x + width / 2,
+        y: y + height / 2,
+    };
+    if !ensure_click_through(placement.hwnd, center) {
+        let _ = DestroyWindow(placement.hwnd);
+        std::process::exit(EXIT_CLICK_THROUGH_FAILED);
+    }
+    let affinity = if exclude_from_capture { WDA_EXCLUDEFROMCAPTURE } else { WDA_NONE };
+    let _ = SetWindowDisplayAffinity(placement.hwnd, affinity);
+    
+    // Notes are small and rarely change, so a new note simply gets a new window
+    if let Some((note_hwnd, ..)) = placement.note.take() {
+        let _ = DestroyWindow(note_hwnd);
+    }
+    placement.note = note.and_then(|(bitmap, corner)| {
+        let (width, height) = (bitmap.width as i32, bitmap.height as i32);
+        let (x, y) = note_position(corner, screen_w, screen_h, width, height);
+        let note_hwnd = create_note_window(&class_name, hinstance, &bitmap, x, y, exclude_from_capture)?;
+        if HIDDEN {
+            let _ = ShowWindow(note_hwnd, SW_HIDE);
+        }
+        Some((note_hwnd, width, height, corner))
+    });
+    
+    // The HUD window is kept and repainted from the (possibly new) file on the next refresh
+    let hud_hwnd = match (placement.hud.take(), &hud) {
+        (Some((hud_hwnd, ..)), Some(_)) => Some(hud_hwnd),
+        (Some((hud_hwnd, ..)), None) => {
+            let _ = KillTimer(placement.hwnd, HUD_TIMER_ID);
+            let _ = DestroyWindow(hud_hwnd);
+            None
+        }
+        (None, Some(_)) => {
+            let created = create_text_window(&class_name, hinstance, 0, 0, exclude_from_capture);
+            if created.is_some() {
+                SetTimer(placement.hwnd, HUD_TIMER_ID, HUD_REFRESH_MS, None);
+            }
+            created
+        }
+        (None, None) => None,
+    };
+    HUD_SOURCE.with(|source| *source.borrow_mut() = None);
+    if let (Some(hud_hwnd), Some((path, corner))) = (hud_hwnd, hud) {
+        let _ = SetWindowDisplayAffinity(hud_hwnd, affinity);
+        HUD_SOURCE.with(|source| *source.borrow_mut() = Some(HudSource { path, text: String::new(), shown: false }));
+        placement.hud = Some((hud_hwnd, 0, 0, corner));
+    }
+    
+    placement.width = width;
+    placement.height = height;
+    placement.x_offset = x_offset;
+    placement.y_offset = y_offset;
+    placement.anchor = anchor;
+    PLACEMENT = Some(placement);
+    refresh_hud();
+    true
+}
+
+/// WinEvent callback for EVENT_SYSTEM_FOREGROUND
+#[cfg(windows)]
+unsafe extern "system" fn on_foreground_changed(
+    _hook: windows::Win32::UI::Accessibility::HWINEVENTHOOK,
+    _event: u32,
+    _hwnd: windows::Win32::Foundation::HWND,
+    _id_object: i32,
+    _id_child: i32,
+    _event_thread: u32,
+    _event_time: u32,
+) {
+    count_wakeup();
+    reassert_overlay(false);
+}
+
+/// Distance in pixels between an overlay note and the screen edges
+#[cfg(windows)]
+const NOTE_MARGIN: i32 = 24;
+
+/// Top-left position of a `width` x `height` note in the given screen corner
+#[cfg(windows)]
+fn note_position(corner: Corner, screen_w: i32, screen_h: i32, width: i32, height: i32) -> (i32, i32) {
+    let left = NOTE_MARGIN;
+    let right = screen_w - width - NOTE_MARGIN;
+    let top = NOTE_MARGIN;
+    let bottom = screen_h - height - NOTE_MARGIN;
+    match corner {
+        Corner::TopLeft => (left, top),
+        Corner::TopRight => (right, top),
+        Corner::BottomLeft => (left, bottom),
+        Corner::BottomRight => (right, bottom),
+    }
+}
+
+/// Create a click-through layered window showing a rasterized note at (x, y)
+/// Returns None (and shows nothing) if the window can't be created or would take clicks
+#[cfg(windows)]
+unsafe fn create_note_window(
+    class_name: &[u16],
+    hinstance: windows::Win32::Foundation::HINSTANCE,
+    bitmap: &TextBitmap,
+    x: i32,
+    y: i32,
+    exclude_from_capture: bool,
+) -> Option<windows::Win32::Foundation::HWND> {
+    use windows::Win32::UI::WindowsAndMessaging::DestroyWindow;
+    
+    if bitmap.width == 0 || bitmap.height == 0 {
+        return None;
+    }
+    
+    let hwnd = create_text_window(class_name, hinstance, x, y, exclude_from_capture)?;
+    if !paint_text_window(hwnd, bitmap, x, y) || !show_text_window(hwnd, bitmap, x, y) {
+        let _ = DestroyWindow(hwnd);
+        return None;
+    }
+    
+    Some(hwnd)
+}
+
+/// Create a hidden click-through layered window for text (note or HUD)
+#[cfg(windows)]
+unsafe fn create_text_window(
+    class_name: &[u16],
+    hinstance: windows::Win32::Foundation::HINSTANCE,
+    x: i32,
+    y: i32,
+    exclude_from_capture: bool,
+) -> Option<windows::Win32::Foundation::HWND> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, SetWindowDisplayAffinity, WDA_EXCLUDEFROMCAPTURE, WS_EX_LAYERED,
+        WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_POPUP,
+    };
+    use windows::core::PCWSTR;
+    
+    let hwnd = CreateWindowExW(
+        WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOPMOST | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE,
+        PCWSTR(class_name.as_ptr()),
+        PCWSTR::null(),
+        WS_POPUP,
+        x,
+        y,
+        1,
+        1,
+        HWND::default(),
+        None,
+        hinstance,
+        None,
+    );
+    if hwnd.0 == 0 {
+        return None;
+    }
+    
+    if exclude_from_capture {
+        let _ = SetWindowDisplayAffinity(hwnd, WDA_EXCLUDEFROMCAPTURE);
     }
+    Some(hwnd)
+}
+
+/// Upload rasterized text into a layered window, resizing and moving it to (x, y)
+#[cfg(windows)]
+unsafe fn paint_text_window(
+    hwnd: windows::Win32::Foundation::HWND,
+    bitmap: &TextBitmap,
+    x: i32,
+    y: i32,
+) -> bool {
+    let pixels = to_premultiplied_bgra(bitmap.pixels.iter().copied());
+    paint_layered_window(hwnd, &pixels, bitmap.width as i32, bitmap.height as i32, x, y)
+}
+
+/// Upload premultiplied BGRA pixels into a layered window, resizing and moving it to (x, y)
+#[cfg(windows)]
+unsafe fn paint_layered_window(
+    hwnd: windows::Win32::Foundation::HWND,
+    pixels: &[u8],
+    width: i32,
+    height: i32,
+    x: i32,
+    y: i32,
+) -> bool {
+    use std::mem::zeroed;
+    use std::ptr::null_mut;
+    
+    use windows::Win32::Foundation::{COLORREF, HWND, POINT, SIZE};
+    use windows::Win32::Graphics::Gdi::{
+        CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject,
+        GetDC, ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER,
+        BI_RGB, DIB_RGB_COLORS, AC_SRC_ALPHA, AC_SRC_OVER, BLENDFUNCTION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{UpdateLayeredWindow, ULW_ALPHA};
+    
+    if width == 0 || height == 0 || pixels.len() != (width * height * 4) as usize {
+        return false;
+    }
+    
+    let screen_dc = GetDC(HWND::default());
+    let mem_dc = CreateCompatibleDC(screen_dc);
+    let bmi = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            biHeight: -height, // Top-down
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..zeroed()
+        },
+        bmiColors: [zeroed(); 1],
+    };
+    
+    let mut bits_ptr: *mut std::ffi::c_void = null_mut();
+    let updated = match CreateDIBSection(mem_dc, &bmi, DIB_RGB_COLORS, &mut bits_ptr, None, 0) {
+        Ok(hbitmap) if !bits_ptr.is_null() => {
+            let dst = std::slice::from_raw_parts_mut(bits_ptr as *mut u8, pixels.len());
+            dst.copy_from_slice(pixels);
+            let old_obj = SelectObject(mem_dc, hbitmap);
+            
+            let blend = BLENDFUNCTION {
+                BlendOp: AC_SRC_OVER as u8,
+                BlendFlags: 0,
+                SourceConstantAlpha: 255,
+                AlphaFormat: AC_SRC_ALPHA as u8,
+            };
+            let size = SIZE { cx: width, cy: height };
+            let src_point = POINT { x: 0, y: 0 };
+            let win_point = POINT { x, y };
+            
+            // The layered window keeps its own copy, so the bitmap can be freed right away
+            count_redraw();
+            let result = UpdateLayeredWindow(
+                hwnd,
+                screen_dc,
+                Some(&win_point),
+                Some(&size),
+                mem_dc,
+                Some(&src_point),
+                COLORREF(0),
+                Some(&blend),
+                ULW_ALPHA,
+            );
+            SelectObject(mem_dc, old_obj);
+            let _ = DeleteObject(hbitmap);
+            result.is_ok()
+        }
+        Ok(hbitmap) => {
+            let _ = DeleteObject(hbitmap);
+            false
+        }
+        Err(_) => false,
+    };
+    ReleaseDC(HWND::default(), screen_dc);
+    let _ = DeleteDC(mem_dc);
+    updated
+}
+
+/// Show a painted text window, making sure it doesn't swallow clicks
+#[cfg(windows)]
+unsafe fn show_text_window(
+    hwnd: windows::Win32::Foundation::HWND,
+    bitmap: &TextBitmap,
+    x: i32,
+    y: i32,
+) -> bool {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::UI::WindowsAndMessaging::{ShowWindow, SW_SHOWNA};
+    
+    let _ = ShowWindow(hwnd, SW_SHOWNA);
+    
+    // Text that swallows clicks is worse than no text at all
+    let center = POINT {
+        x: x + bitmap.width as i32 / 2,
+        y: y + bitmap.height as i32 / 2,
+    };
+    ensure_click_through(hwnd, center)
+}
+
+/// Verify the overlay ignores mouse input, re-applying the extended styles once if needed
+#[cfg(windows)]
+unsafe fn ensure_click_through(
+    hwnd: windows::Win32::Foundation::HWND,
+    probe: windows::Win32::Foundation::POINT,
+) -> bool {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetWindowLongPtrW, WindowFromPoint, GWL_EXSTYLE,
+        WS_EX_LAYERED, WS_EX_TRANSPARENT,
+    };
+    
+    let required = (WS_EX_TRANSPARENT.0 | WS_EX_LAYERED.0) as isize;
+    let is_click_through = || {
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        // A transparent window is skipped by hit-testing, so the probe must land elsewhere
+        ex_style & required == required && WindowFromPoint(probe) != hwnd
+    };
+    
+    if is_click_through() {
+        return true;
+    }
+    
+    // Fallback: apply the styles directly to the HWND and check again
+    let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+    SetWindowLongPtrW(hwnd, GWL_EXSTYLE, ex_style | required);
+    is_click_through()
 }
 
 #[cfg(windows)]
@@ -268,21 +1010,83 @@ unsafe extern "system" fn wnd_proc(
     lparam: windows::Win32::Foundation::LPARAM,
 ) -> windows::Win32::Foundation::LRESULT {
     use windows::Win32::Foundation::LRESULT;
-    use windows::Win32::UI::WindowsAndMessaging::{DefWindowProcW, PostQuitMessage};
+    use windows::Win32::System::DataExchange::COPYDATASTRUCT;
+    use windows::Win32::UI::WindowsAndMessaging::{DefWindowProcW, PostQuitMessage, WM_COPYDATA};
     
     const WM_DESTROY: u32 = 0x0002;
     const WM_NCHITTEST: u32 = 0x0084;
+    const WM_DISPLAYCHANGE: u32 = 0x007E;
+    const WM_TIMER: u32 = 0x0113;
     const HTTRANSPARENT: i32 = -1;
     
     match msg {
+        WM_DISPLAYCHANGE if Some(hwnd) == GLOBAL_HWND => {
+            // Resolution changed - keep the crosshair centered on the new mode
+            reassert_overlay(true);
+            LRESULT(0)
+        }
+        WM_SET_OFFSET if Some(hwnd) == GLOBAL_HWND => {
+            // Nudged from the app while the user adjusts the position
+            if let Some(mut placement) = PLACEMENT {
+                placement.x_offset = wparam.0 as isize as i32;
+                placement.y_offset = lparam.0 as i32;
+                PLACEMENT = Some(placement);
+                reassert_overlay(true);
+            }
+            LRESULT(0)
+        }
+        WM_SET_VISIBLE if Some(hwnd) == GLOBAL_HWND => {
+            // The optimizer was paused or resumed
+            set_overlay_visible(wparam.0 != 0);
+            LRESULT(0)
+        }
+        WM_COPYDATA if Some(hwnd) == GLOBAL_HWND => {
+            // Another profile's crosshair: returns 1 once it's on screen, 0 to have the app
+            // restart the overlay instead
+            let data = &*(lparam.0 as *const COPYDATASTRUCT);
+            if data.dwData != SWAP_OVERLAY_DATA || data.lpData.is_null() {
+                return LRESULT(0);
+            }
+            let bytes = std::slice::from_raw_parts(data.lpData as *const u8, data.cbData as usize);
+            let args: Vec<String> = String::from_utf8_lossy(bytes).split('\0').map(str::to_string).collect();
+            let swapped = parse_request(&args).is_some_and(|request| swap_overlay(request));
+            LRESULT(swapped as isize)
+        }
+        WM_TIMER if wparam.0 == TOPMOST_TIMER_ID => {
+            reassert_overlay(false);
+            LRESULT(0)
+        }
+        WM_TIMER if wparam.0 == HUD_TIMER_ID => {
+            refresh_hud();
+            LRESULT(0)
+        }
         WM_NCHITTEST => {
             // Make window completely click-through
             LRESULT(HTTRANSPARENT as isize)
         }
-        WM_DESTROY => {
+        WM_DESTROY if Some(hwnd) == GLOBAL_HWND => {
+            // Note and HUD windows can be destroyed by a swap; only the crosshair ends the overlay
             PostQuitMessage(0);
             LRESULT(0)
         }
         _ => DefWindowProcW(hwnd, msg, wparam, lparam),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_hud_tick_does_not_redraw() {
+        let mut hud = HudSource { path: String::new(), text: String::new(), shown: false };
+        assert!(hud.take_text(Some("CPU 45°C\n")));
+        assert_eq!(hud.text, "CPU 45°C");
+        // Ticks while the readings stay the same or the file is being rewritten
+        for file_text in [Some("CPU 45°C"), Some("CPU 45°C\r\n"), None, Some("CPU 45°C")] {
+            assert!(!hud.take_text(file_text));
+        }
+        assert!(hud.take_text(Some("CPU 46°C")));
+        assert!(hud.take_text(Some("")));
+    }
+}
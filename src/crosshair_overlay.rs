@@ -1,17 +1,79 @@
 //! Crosshair overlay launcher - spawns crosshair as a separate detached process
 //! The crosshair process runs independently and survives even if main app closes
 
+use crate::activation::manager::ActivationManager;
+use crate::activation::steps::Backends;
+use crate::crosshair_position::CrosshairAnchor;
+use crate::overlay::{format_hex_color, Overlay, OverlayBackend, OverlayOptions};
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, Stdio};
+use tracing::info;
+
+/// Exit code crosshair.exe uses when click-through can't be guaranteed
+/// (must match `EXIT_CLICK_THROUGH_FAILED` in src/bin/crosshair.rs)
+const EXIT_CLICK_THROUGH_FAILED: i32 = 3;
+
+/// Title of crosshair.exe's crosshair window (must match `WINDOW_TITLE` in src/bin/crosshair.rs)
+#[cfg(windows)]
+const WINDOW_TITLE: &str = "Gaming Optimizer Crosshair";
+
+/// Message that moves the running crosshair: wParam is x, lParam is y
+/// (must match `WM_SET_OFFSET` in src/bin/crosshair.rs)
+#[cfg(windows)]
+const WM_SET_OFFSET: u32 = 0x8001; // WM_APP + 1
+
+/// Message that hides (wParam 0) or shows (wParam 1) the running crosshair
+/// (must match `WM_SET_VISIBLE` in src/bin/crosshair.rs)
+#[cfg(windows)]
+const WM_SET_VISIBLE: u32 = 0x8002; // WM_APP + 2
+
+/// `dwData` of the `WM_COPYDATA` that swaps a new crosshair into the running overlay
+/// (must match `SWAP_OVERLAY_DATA` in src/bin/crosshair.rs)
+#[cfg(windows)]
+const SWAP_OVERLAY_DATA: usize = 0x4348_5253; // "CHRS"
+
+/// How long to wait for the running crosshair to load and show a swapped-in image
+#[cfg(windows)]
+const SWAP_TIMEOUT_MS: u32 = 2000;
 
 /// Handle to track the crosshair process
 pub struct OverlayHandle {
     process_name: String,
+    child: Child,
 }
 
-impl OverlayHandle {
+impl Overlay for OverlayHandle {
+    /// Check whether the crosshair process gave up because it would have stolen mouse clicks
+    fn click_through_failed(&mut self) -> bool {
+        matches!(
+            self.child.try_wait(),
+            Ok(Some(status)) if status.code() == Some(EXIT_CLICK_THROUGH_FAILED)
+        )
+    }
+
+    /// Show another crosshair in the running overlay, keeping its windows so nothing flashes;
+    /// false if the process is gone or didn't take it (restart with `start_overlay` then)
+    fn swap(&mut self, image_path: &str, x_offset: i32, y_offset: i32, options: &OverlayOptions) -> bool {
+        if !matches!(self.child.try_wait(), Ok(None)) || !Path::new(image_path).exists() {
+            return false;
+        }
+        #[cfg(windows)]
+        {
+            let swapped = send_to_overlay(&overlay_args(image_path, x_offset, y_offset, options));
+            if swapped {
+                info!("[Crosshair] Swapped in {}, Offset: ({}, {})", image_path, x_offset, y_offset);
+            }
+            swapped
+        }
+        #[cfg(not(windows))]
+        {
+            let _ = (x_offset, y_offset, options);
+            false
+        }
+    }
+
     /// Kill all crosshair processes
-    pub fn stop(&self) {
+    fn stop(&self) {
         #[cfg(windows)]
         {
             // Kill the crosshair process by name
@@ -31,12 +93,71 @@ impl Drop for OverlayHandle {
     }
 }
 
+/// Crosshair starts for activation: crosshair.exe from next to the app
+#[derive(Default)]
+pub struct CrosshairExe;
+
+impl OverlayBackend for CrosshairExe {
+    fn start(&mut self, image_path: String, x_offset: i32, y_offset: i32, options: &OverlayOptions) -> Result<Box<dyn Overlay>, String> {
+        start_overlay(image_path, x_offset, y_offset, options).map(|handle| Box::new(handle) as Box<dyn Overlay>)
+    }
+}
+
+/// Activation that shows its crosshair with crosshair.exe
+pub fn activation_manager() -> ActivationManager {
+    ActivationManager::new(Backends { overlay: Box::new(CrosshairExe), ..Default::default() })
+}
+
+/// Convert options into command-line flags understood by crosshair.exe
+fn option_args(options: &OverlayOptions) -> Vec<String> {
+    let mut args = Vec::new();
+    if options.anchor != CrosshairAnchor::default() {
+        let (x, y) = options.anchor.percent();
+        args.push("--anchor".to_string());
+        args.push(format!("{},{}", x, y));
+    }
+    if options.exclude_from_capture {
+        args.push("--exclude-from-capture".to_string());
+    }
+    if let Some(tint) = options.tint {
+        args.push("--tint".to_string());
+        args.push(format_hex_color(tint));
+    }
+    if let Some(ref note) = options.note {
+        args.push("--note".to_string());
+        args.push(note.text.clone());
+        args.push("--note-size".to_string());
+        args.push(note.font_size.to_string());
+        args.push("--note-corner".to_string());
+        args.push(note.corner.as_arg().to_string());
+        args.push("--note-color".to_string());
+        args.push(format_hex_color(note.color));
+    }
+    if let Some(corner) = options.hud {
+        if let Some(path) = crate::monitor::hud_file() {
+            args.push("--hud".to_string());
+            args.push(path.to_string_lossy().into_owned());
+            args.push("--hud-corner".to_string());
+            args.push(corner.as_arg().to_string());
+        }
+    }
+    args
+}
+
+/// Command-line arguments of crosshair.exe (also sent as they are when swapping)
+fn overlay_args(image_path: &str, x_offset: i32, y_offset: i32, options: &OverlayOptions) -> Vec<String> {
+    let mut args = vec![image_path.to_string(), x_offset.to_string(), y_offset.to_string()];
+    args.extend(option_args(options));
+    args
+}
+
 /// Start crosshair as a completely separate process
 /// The crosshair will continue running even if the main app closes
 pub fn start_overlay(
     image_path: String,
     x_offset: i32,
     y_offset: i32,
+    options: &OverlayOptions,
 ) -> Result<OverlayHandle, String> {
     // Validate image exists
     if !Path::new(&image_path).exists() {
@@ -46,8 +167,8 @@ pub fn start_overlay(
     // Find the crosshair executable (should be next to the main exe)
     let crosshair_exe = get_crosshair_exe_path()?;
     
-    println!("[Crosshair] Starting separate process: {}", crosshair_exe.display());
-    println!("[Crosshair] Image: {}, Offset: ({}, {})", image_path, x_offset, y_offset);
+    info!("[Crosshair] Starting separate process: {}", crosshair_exe.display());
+    info!("[Crosshair] Image: {}, Offset: ({}, {})", image_path, x_offset, y_offset);
     
     // Kill any existing crosshair process first
     #[cfg(windows)]
@@ -61,50 +182,114 @@ pub fn start_overlay(
     
     // Spawn crosshair as detached process
     #[cfg(windows)]
-    {
+    let child = {
         use std::os::windows::process::CommandExt;
         const DETACHED_PROCESS: u32 = 0x00000008;
         const CREATE_NO_WINDOW: u32 = 0x08000000;
         
         Command::new(&crosshair_exe)
-            .arg(&image_path)
-            .arg(x_offset.to_string())
-            .arg(y_offset.to_string())
+            .args(overlay_args(&image_path, x_offset, y_offset, options))
             .creation_flags(DETACHED_PROCESS | CREATE_NO_WINDOW)
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .spawn()
-            .map_err(|e| format!("Failed to spawn crosshair process: {}", e))?;
-    }
+            .map_err(|e| format!("Failed to spawn crosshair process: {}", e))?
+    };
     
     #[cfg(not(windows))]
-    {
+    let child = {
         Command::new(&crosshair_exe)
-            .arg(&image_path)
-            .arg(x_offset.to_string())
-            .arg(y_offset.to_string())
+            .args(overlay_args(&image_path, x_offset, y_offset, options))
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .spawn()
-            .map_err(|e| format!("Failed to spawn crosshair process: {}", e))?;
-    }
+            .map_err(|e| format!("Failed to spawn crosshair process: {}", e))?
+    };
     
-    println!("[Crosshair] Process started successfully!");
+    info!("[Crosshair] Process started successfully!");
     
     Ok(OverlayHandle {
         process_name: "crosshair.exe".to_string(),
+        child,
     })
 }
 
-/// Kill all running crosshair processes (can be called without a handle)
-pub fn kill_all_crosshairs() {
+/// Move the running crosshair to new pixel offsets without restarting it; false if no
+/// crosshair window was found
+pub fn move_overlay(x_offset: i32, y_offset: i32) -> bool {
     #[cfg(windows)]
     {
-        let _ = Command::new("taskkill")
-            .args(["/F", "/IM", "crosshair.exe"])
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status();
+        post_to_overlay(WM_SET_OFFSET, x_offset as isize as usize, y_offset as isize)
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = (x_offset, y_offset);
+        false
+    }
+}
+
+/// Hide or show the running crosshair without stopping it; false if no crosshair window was
+/// found
+pub fn set_overlay_visible(visible: bool) -> bool {
+    #[cfg(windows)]
+    {
+        post_to_overlay(WM_SET_VISIBLE, visible as usize, 0)
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = visible;
+        false
+    }
+}
+
+/// Post a message to crosshair.exe's crosshair window
+#[cfg(windows)]
+fn post_to_overlay(msg: u32, wparam: usize, lparam: isize) -> bool {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{FindWindowW, PostMessageW};
+
+    let title: Vec<u16> = WINDOW_TITLE.encode_utf16().chain(Some(0)).collect();
+    unsafe {
+        let hwnd = FindWindowW(PCWSTR::null(), PCWSTR(title.as_ptr()));
+        if hwnd.0 == 0 {
+            return false;
+        }
+        PostMessageW(hwnd, msg, WPARAM(wparam), LPARAM(lparam)).is_ok()
+    }
+}
+
+/// Send crosshair.exe's arguments to its crosshair window; true once the new image is shown
+#[cfg(windows)]
+fn send_to_overlay(args: &[String]) -> bool {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::System::DataExchange::COPYDATASTRUCT;
+    use windows::Win32::UI::WindowsAndMessaging::{FindWindowW, SendMessageTimeoutW, SMTO_ABORTIFHUNG, WM_COPYDATA};
+
+    let title: Vec<u16> = WINDOW_TITLE.encode_utf16().chain(Some(0)).collect();
+    let payload = args.join("\0");
+    let data = COPYDATASTRUCT {
+        dwData: SWAP_OVERLAY_DATA,
+        cbData: payload.len() as u32,
+        lpData: payload.as_ptr() as *mut _,
+    };
+    unsafe {
+        let hwnd = FindWindowW(PCWSTR::null(), PCWSTR(title.as_ptr()));
+        if hwnd.0 == 0 {
+            return false;
+        }
+        let mut result = 0usize;
+        let sent = SendMessageTimeoutW(
+            hwnd,
+            WM_COPYDATA,
+            WPARAM(0),
+            LPARAM(&data as *const COPYDATASTRUCT as isize),
+            SMTO_ABORTIFHUNG,
+            SWAP_TIMEOUT_MS,
+            Some(&mut result),
+        );
+        sent.0 != 0 && result == 1
     }
 }
 
@@ -142,3 +327,17 @@ fn get_crosshair_exe_path() -> Result<std::path::PathBuf, String> {
     
     Err("crosshair.exe not found. Make sure it's in the same directory as the main app.".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_args() {
+        let options = OverlayOptions { exclude_from_capture: true, ..Default::default() };
+        assert_eq!(
+            overlay_args("C:\\dot.png", -3, 12, &options),
+            ["C:\\dot.png", "-3", "12", "--exclude-from-capture"]
+        );
+    }
+}
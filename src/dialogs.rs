@@ -0,0 +1,51 @@
+//! Native dialogs
+//!
+//! The file picker and message boxes (rfd) the GUI and the tray show; the core library stays
+//! free of UI so it runs without a display.
+
+use crate::activation::ExitBehavior;
+use crate::i18n::t;
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+
+/// Open Windows file dialog to select a PNG file
+#[cfg(windows)]
+pub fn open_image_picker() -> Result<PathBuf> {
+    use rfd::FileDialog;
+    
+    let file = FileDialog::new()
+        .add_filter("PNG Image", &["png"])
+        .add_filter("All Files", &["*"])
+        .pick_file();
+
+    file.ok_or_else(|| anyhow!("No file selected"))
+}
+
+#[cfg(not(windows))]
+pub fn open_image_picker() -> Result<PathBuf> {
+    Err(anyhow!("File picker only supported on Windows"))
+}
+
+/// Whether to undo the active profile on exit, asking first if the setting says so (None when
+/// the user cancelled the exit)
+pub fn confirm_exit(behavior: ExitBehavior, active_profile: Option<&str>) -> Option<bool> {
+    let Some(profile) = active_profile else {
+        return Some(true);
+    };
+    match behavior {
+        ExitBehavior::Deactivate => Some(true),
+        ExitBehavior::LeaveActive => Some(false),
+        ExitBehavior::Ask => {
+            let choice = rfd::MessageDialog::new()
+                .set_title(t!("exit-confirm-title"))
+                .set_description(t!("exit-confirm-body", profile = profile))
+                .set_buttons(rfd::MessageButtons::YesNoCancel)
+                .show();
+            match choice {
+                rfd::MessageDialogResult::Yes => Some(true),
+                rfd::MessageDialogResult::No => Some(false),
+                _ => None,
+            }
+        }
+    }
+}
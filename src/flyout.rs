@@ -2,7 +2,10 @@
 /// 
 /// This module implements a modern-looking flyout menu that spawns from the system tray
 /// using Win32 layered windows with GDI+ for anti-aliased rendering and DWM for shadows.
+/// Each profile row has an activate/stop button, a crosshair toggle and the time it was last
+/// used; the footer opens the GUI, starts crosshair adjustment or exits.
 
+use std::collections::HashMap;
 use std::mem;
 use std::ptr::null_mut;
 use std::sync::mpsc::Sender;
@@ -15,43 +18,311 @@ use windows::Win32::{
         GdiPlus::*,
     },
     System::LibraryLoader::GetModuleHandleW,
+    UI::Input::KeyboardAndMouse::*,
     UI::WindowsAndMessaging::*,
 };
 
-use crate::ipc::TrayToGui;
-use crate::profile::Profile;
+use crate::i18n::t;
+use crate::ipc::{self, TrayToGui};
+use crate::profile::{Profile, ProfileUsage};
+use crate::sessions;
+use tracing::{info, warn};
 
 const WINDOW_CLASS: &str = "TrayFlyoutWindowClass";
 const FLYOUT_WIDTH: i32 = 386;  // Match PowerToys
 const FLYOUT_HEIGHT: i32 = 486;  // Match PowerToys
 const ITEM_HEIGHT: i32 = 60;     // Taller items
 const PADDING: i32 = 16;
+/// Top of the first profile row (below the title and subtitle)
+const ITEMS_START_Y: i32 = 90;
+/// Height of the "Open GUI" / "Adjust crosshair" / "Exit" footer
+const FOOTER_HEIGHT: i32 = 56;
+/// Profile rows that fit above the footer (leaving a line for the "more" hint)
+const MAX_ROWS: usize = ((FLYOUT_HEIGHT - ITEMS_START_Y - FOOTER_HEIGHT - 20) / ITEM_HEIGHT) as usize;
+/// Crosshair icon color while the overlay is shown (same blue as the tray badge)
+const CROSSHAIR_COLOR: u32 = 0xFF_3D_A5_FF;
 
 /// Flyout window state
 pub struct FlyoutWindow {
     hwnd: HWND,
-    profiles: Vec<Profile>,
+    rows: Vec<FlyoutRow>,
+    usage: HashMap<String, ProfileUsage>,
     active_profile: Option<String>,
-    hover_index: Option<usize>,
+    overlay_visible: bool,
+    hover: Option<FlyoutTarget>,
     to_gui_tx: Sender<TrayToGui>,
     gdiplus_token: usize,
 }
 
-/// Menu item for rendering
-#[derive(Clone)]
-struct MenuItem {
+/// A profile row
+#[derive(Debug, Clone, PartialEq)]
+struct FlyoutRow {
     name: String,
-    is_active: bool,
+    processes: usize,
+    has_crosshair: bool,
+    /// Unix time of the last activation (None = never used)
+    last_activated: Option<u64>,
+}
+
+/// Clickable part of the flyout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlyoutTarget {
+    /// Anywhere else on a profile row (activates it)
+    Row(usize),
+    /// Activate button (stop button on the active profile)
+    Activate(usize),
+    /// Crosshair toggle (only acts on the active profile)
+    Overlay(usize),
+    OpenGui,
+    AdjustCrosshair,
+    Exit,
+}
+
+/// Rectangle in window coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Bounds {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Bounds {
+    fn from_rect(rect: &RECT) -> Self {
+        Bounds { x: rect.left, y: rect.top, width: rect.right - rect.left, height: rect.bottom - rect.top }
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    fn rect(&self) -> RectF {
+        RectF { X: self.x as f32, Y: self.y as f32, Width: self.width as f32, Height: self.height as f32 }
+    }
+}
+
+fn row_bounds(row: usize) -> Bounds {
+    Bounds { x: PADDING, y: ITEMS_START_Y + row as i32 * ITEM_HEIGHT, width: FLYOUT_WIDTH - PADDING * 2, height: ITEM_HEIGHT - 4 }
+}
+
+fn activate_bounds(row: usize) -> Bounds {
+    let row = row_bounds(row);
+    Bounds { x: row.x + row.width - 84, y: row.y + (row.height - 28) / 2, width: 76, height: 28 }
+}
+
+fn overlay_bounds(row: usize) -> Bounds {
+    let activate = activate_bounds(row);
+    Bounds { x: activate.x - 36, width: 28, ..activate }
+}
+
+/// Footer button `index` of three (Open GUI, Adjust crosshair, Exit)
+fn footer_bounds(index: i32) -> Bounds {
+    let width = (FLYOUT_WIDTH - PADDING * 2 - 8 * 2) / 3;
+    Bounds { x: PADDING + index * (width + 8), y: FLYOUT_HEIGHT - FOOTER_HEIGHT + 8, width, height: 32 }
+}
+
+fn open_gui_bounds() -> Bounds {
+    footer_bounds(0)
+}
+
+fn adjust_bounds() -> Bounds {
+    footer_bounds(1)
+}
+
+fn exit_bounds() -> Bounds {
+    footer_bounds(2)
+}
+
+/// What is under the mouse at (`x`, `y`) with `rows` profile rows shown
+fn hit_test(x: i32, y: i32, rows: usize) -> Option<FlyoutTarget> {
+    if open_gui_bounds().contains(x, y) {
+        return Some(FlyoutTarget::OpenGui);
+    }
+    if adjust_bounds().contains(x, y) {
+        return Some(FlyoutTarget::AdjustCrosshair);
+    }
+    if exit_bounds().contains(x, y) {
+        return Some(FlyoutTarget::Exit);
+    }
+    let row = (0..rows.min(MAX_ROWS)).find(|&row| row_bounds(row).contains(x, y))?;
+    Some(if activate_bounds(row).contains(x, y) {
+        FlyoutTarget::Activate(row)
+    } else if overlay_bounds(row).contains(x, y) {
+        FlyoutTarget::Overlay(row)
+    } else {
+        FlyoutTarget::Row(row)
+    })
+}
+
+/// Keyboard navigation step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NavKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Tab (Shift+Tab is `Back`)
+    Next,
+    Back,
+}
+
+/// Focusable targets line by line, left to right: a line per shown row (the crosshair toggle
+/// only on the active profile, as it does nothing elsewhere), then the footer
+fn focus_lines(rows: &[FlyoutRow], active_profile: Option<&str>) -> Vec<Vec<FlyoutTarget>> {
+    let mut lines: Vec<Vec<FlyoutTarget>> = rows
+        .iter()
+        .take(MAX_ROWS)
+        .enumerate()
+        .map(|(i, row)| {
+            if row.has_crosshair && active_profile == Some(row.name.as_str()) {
+                vec![FlyoutTarget::Overlay(i), FlyoutTarget::Activate(i)]
+            } else {
+                vec![FlyoutTarget::Activate(i)]
+            }
+        })
+        .collect();
+    lines.push(vec![FlyoutTarget::OpenGui, FlyoutTarget::AdjustCrosshair, FlyoutTarget::Exit]);
+    lines
+}
+
+/// Where `key` moves the keyboard focus from `current`. Up/Down go to the neighbouring line
+/// (a row's activate button, or the first footer button) and wrap around; Left/Right stay on
+/// the line; Tab/Shift+Tab walk every target.
+fn navigate(current: Option<FlyoutTarget>, key: NavKey, lines: &[Vec<FlyoutTarget>]) -> Option<FlyoutTarget> {
+    // A row's default target is its activate button (the last one), the footer's is "Open GUI"
+    let default_of = |line: &Vec<FlyoutTarget>| match line.last() {
+        Some(FlyoutTarget::Exit) => line.first().copied(),
+        last => last.copied(),
+    };
+    // A hovered row counts as its activate button
+    let current = match current {
+        Some(FlyoutTarget::Row(i)) => Some(FlyoutTarget::Activate(i)),
+        other => other,
+    };
+    let position = current.and_then(|target| {
+        lines.iter().enumerate().find_map(|(l, line)| line.iter().position(|&t| t == target).map(|c| (l, c)))
+    });
+    let count = lines.len();
+
+    match (key, position) {
+        (NavKey::Up, Some((l, _))) => default_of(&lines[(l + count - 1) % count]),
+        (NavKey::Up, None) => default_of(lines.last()?),
+        (NavKey::Down, Some((l, _))) => default_of(&lines[(l + 1) % count]),
+        (NavKey::Down | NavKey::Left | NavKey::Right, None) => default_of(lines.first()?),
+        (NavKey::Left, Some((l, c))) => Some(lines[l][c.saturating_sub(1)]),
+        (NavKey::Right, Some((l, c))) => Some(lines[l][(c + 1).min(lines[l].len() - 1)]),
+        (NavKey::Next | NavKey::Back, _) => {
+            let flat: Vec<FlyoutTarget> = lines.iter().flatten().copied().collect();
+            let index = flat.iter().position(|&t| Some(t) == current);
+            let next = match (key, index) {
+                (NavKey::Next, Some(i)) => (i + 1) % flat.len(),
+                (NavKey::Next, None) => 0,
+                (_, Some(i)) => (i + flat.len() - 1) % flat.len(),
+                (_, None) => flat.len().checked_sub(1)?,
+            };
+            flat.get(next).copied()
+        }
+    }
+}
+
+/// Rows for `profiles`: favorites first, then the most recently used, otherwise in list order
+fn build_rows(profiles: &[Profile], usage: &HashMap<String, ProfileUsage>) -> Vec<FlyoutRow> {
+    let mut rows: Vec<(bool, FlyoutRow)> = profiles
+        .iter()
+        .map(|profile| {
+            let last_activated = usage.get(&profile.name).map(|usage| usage.last_activated).filter(|&time| time > 0);
+            let row = FlyoutRow {
+                name: profile.name.clone(),
+                processes: profile.processes_to_kill.len(),
+                has_crosshair: profile.overlay_enabled,
+                last_activated,
+            };
+            (profile.favorite, row)
+        })
+        .collect();
+    rows.sort_by(|(a_favorite, a), (b_favorite, b)| b_favorite.cmp(a_favorite).then(b.last_activated.cmp(&a.last_activated)));
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
+/// "Used 2h 05m ago", "Used 3d ago" or "Never used"
+fn last_used_label(last_activated: Option<u64>, now: u64) -> String {
+    match last_activated {
+        Some(time) => {
+            let secs = now.saturating_sub(time);
+            let age = if secs >= 86400 { format!("{}d", secs / 86400) } else { sessions::format_duration(secs) };
+            t!("flyout-last-used", age = age)
+        }
+        None => t!("flyout-never-used"),
+    }
+}
+
+/// Screen edge the taskbar is docked to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TaskbarEdge {
+    Bottom,
+    Top,
+    Left,
+    Right,
+}
+
+/// Gap between the flyout and the taskbar or the screen edge (PowerToys uses 12px)
+const FLYOUT_MARGIN: i32 = 12;
+
+/// The edge the taskbar takes space from on `monitor` (the work area is the rest). An
+/// auto-hidden taskbar takes none, so the edge the tray icon is closest to is used instead.
+fn taskbar_edge(tray: Bounds, monitor: Bounds, work_area: Bounds) -> TaskbarEdge {
+    if work_area.y + work_area.height < monitor.y + monitor.height {
+        return TaskbarEdge::Bottom;
+    }
+    if work_area.y > monitor.y {
+        return TaskbarEdge::Top;
+    }
+    if work_area.x > monitor.x {
+        return TaskbarEdge::Left;
+    }
+    if work_area.x + work_area.width < monitor.x + monitor.width {
+        return TaskbarEdge::Right;
+    }
+    let (cx, cy) = (tray.x + tray.width / 2, tray.y + tray.height / 2);
+    [
+        (monitor.y + monitor.height - cy, TaskbarEdge::Bottom),
+        (cy - monitor.y, TaskbarEdge::Top),
+        (cx - monitor.x, TaskbarEdge::Left),
+        (monitor.x + monitor.width - cx, TaskbarEdge::Right),
+    ]
+    .into_iter()
+    .min_by_key(|&(distance, _)| distance)
+    .map_or(TaskbarEdge::Bottom, |(_, edge)| edge)
+}
+
+/// Top-left corner of a flyout of `size` next to the tray icon: against the taskbar, centered
+/// on the icon along it, and kept inside the work area
+fn flyout_position(tray: Bounds, monitor: Bounds, work_area: Bounds, size: (i32, i32)) -> (i32, i32) {
+    let (width, height) = size;
+    let (cx, cy) = (tray.x + tray.width / 2, tray.y + tray.height / 2);
+    let (x, y) = match taskbar_edge(tray, monitor, work_area) {
+        TaskbarEdge::Bottom => (cx - width / 2, work_area.y + work_area.height - height - FLYOUT_MARGIN),
+        TaskbarEdge::Top => (cx - width / 2, work_area.y + FLYOUT_MARGIN),
+        TaskbarEdge::Left => (work_area.x + FLYOUT_MARGIN, cy - height / 2),
+        TaskbarEdge::Right => (work_area.x + work_area.width - width - FLYOUT_MARGIN, cy - height / 2),
+    };
+    let clamp = |value: i32, start: i32, length: i32, size: i32| {
+        value.min(start + length - size - FLYOUT_MARGIN).max(start + FLYOUT_MARGIN)
+    };
+    (clamp(x, work_area.x, work_area.width, width), clamp(y, work_area.y, work_area.height, height))
 }
 
 impl FlyoutWindow {
-    /// Create and show the flyout window near the tray icon
+    /// Create and show the flyout window near the tray icon (boxed, as the window keeps a
+    /// pointer to it)
     pub fn new(
-        _tray_rect: RECT,
-        profiles: Vec<Profile>,
+        tray_rect: RECT,
+        profiles: &[Profile],
+        usage: HashMap<String, ProfileUsage>,
         active_profile: Option<String>,
+        overlay_visible: bool,
         to_gui_tx: Sender<TrayToGui>,
-    ) -> anyhow::Result<Self> {
+    ) -> anyhow::Result<Box<Self>> {
         unsafe {
             // Initialize GDI+
             let startup_input = GdiplusStartupInput {
@@ -91,17 +362,23 @@ impl FlyoutWindow {
             // Use fixed dimensions like PowerToys
             let window_height = FLYOUT_HEIGHT;
 
-            // Calculate position - appear above the tray icon in bottom-right
-            let screen_width = GetSystemMetrics(SM_CXSCREEN);
-            let screen_height = GetSystemMetrics(SM_CYSCREEN);
-            
-            // Position: right side of screen, above taskbar (like PowerToys)
-            let margin = 12; // PowerToys uses 12px margin
-            let final_x = screen_width - FLYOUT_WIDTH - margin;
-            let final_y = screen_height - window_height - 60; // 60px above bottom (for taskbar)
-            
-            println!("[FLYOUT] Screen: {}x{}, Position: ({}, {}), Size: {}x{}", 
-                screen_width, screen_height, final_x, final_y, FLYOUT_WIDTH, window_height);
+            // Against the taskbar next to the tray icon, on the monitor it is on
+            let monitor = MonitorFromRect(&tray_rect, MONITOR_DEFAULTTONEAREST);
+            let mut monitor_info = MONITORINFO { cbSize: mem::size_of::<MONITORINFO>() as u32, ..Default::default() };
+            if !GetMonitorInfoW(monitor, &mut monitor_info).as_bool() {
+                let (width, height) = (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN));
+                monitor_info.rcMonitor = RECT { left: 0, top: 0, right: width, bottom: height };
+                monitor_info.rcWork = monitor_info.rcMonitor;
+            }
+            let (final_x, final_y) = flyout_position(
+                Bounds::from_rect(&tray_rect),
+                Bounds::from_rect(&monitor_info.rcMonitor),
+                Bounds::from_rect(&monitor_info.rcWork),
+                (FLYOUT_WIDTH, window_height),
+            );
+
+            info!("[FLYOUT] Tray: {:?}, work area: {:?}, position: ({}, {}), size: {}x{}",
+                tray_rect, monitor_info.rcWork, final_x, final_y, FLYOUT_WIDTH, window_height);
 
             // Create layered window at the correct position
             let hwnd = CreateWindowExW(
@@ -132,25 +409,29 @@ impl FlyoutWindow {
                 mem::size_of::<DWMNCRENDERINGPOLICY>() as u32,
             )?;
 
-            let flyout = Self {
+            let flyout = Box::new(Self {
                 hwnd,
-                profiles,
+                rows: build_rows(profiles, &usage),
+                usage,
                 active_profile,
-                hover_index: None,
+                overlay_visible,
+                hover: None,
                 to_gui_tx,
                 gdiplus_token,
-            };
+            });
 
             // Store pointer to flyout in window data
-            SetWindowLongPtrW(hwnd, GWLP_USERDATA, &flyout as *const _ as isize);
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, &*flyout as *const _ as isize);
 
             // Initial render
             flyout.render()?;
 
-            // Show and activate window so user can interact
+            // Show and activate window so it takes the keyboard and closes when focus moves on
             ShowWindow(hwnd, SW_SHOW);
-            use windows::Win32::UI::WindowsAndMessaging::SetForegroundWindow;
-            SetForegroundWindow(hwnd);
+            if !SetForegroundWindow(hwnd).as_bool() {
+                warn!("[FLYOUT] Could not take the foreground; the flyout won't see the keyboard");
+            }
+            SetFocus(hwnd);
             
             anyhow::Ok(flyout)
         }
@@ -244,7 +525,7 @@ impl FlyoutWindow {
         let mut brush_title: *mut GpSolidFill = null_mut();
         GdipCreateSolidFill(0xFF_FF_FF_FF, &mut brush_title);
         
-        let title = "Gaming Profiles\0".encode_utf16().collect::<Vec<u16>>();
+        let title = t!("flyout-title").encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
         let title_rect = RectF {
             X: PADDING as f32,
             Y: PADDING as f32,
@@ -278,7 +559,7 @@ impl FlyoutWindow {
         let mut brush_subtitle: *mut GpSolidFill = null_mut();
         GdipCreateSolidFill(0x80_FF_FF_FF, &mut brush_subtitle);
         
-        let subtitle = "Click to activate a profile\0".encode_utf16().collect::<Vec<u16>>();
+        let subtitle = t!("flyout-subtitle").encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
         let subtitle_rect = RectF {
             X: PADDING as f32,
             Y: 56.0,
@@ -300,142 +581,99 @@ impl FlyoutWindow {
         );
         GdipDeleteBrush(brush_subtitle as *mut GpBrush);
         
-        // Profile items start below subtitle
-        let items_start_y = 90;
-
-        // Draw profile items
-        for (i, profile) in self.profiles.iter().enumerate() {
-            let y = items_start_y + i as i32 * ITEM_HEIGHT;
-            let is_hover = self.hover_index == Some(i);
-            let is_active = self.active_profile.as_ref() == Some(&profile.name);
-
-            // Item background (rounded rectangle for hover)
-            if is_hover {
-                let mut brush_hover: *mut GpSolidFill = null_mut();
-                GdipCreateSolidFill(0x40_FF_FF_FF, &mut brush_hover);
-                
-                let mut hover_path: *mut GpPath = null_mut();
-                GdipCreatePath(FillModeWinding, &mut hover_path);
-                Self::add_rounded_rectangle(
-                    hover_path,
-                    PADDING as f32,
-                    y as f32,
-                    (FLYOUT_WIDTH - PADDING * 2) as f32,
-                    (ITEM_HEIGHT - 4) as f32,
-                    6.0,
-                );
-                GdipFillPath(graphics, brush_hover as *mut GpBrush, hover_path);
-                GdipDeletePath(hover_path);
-                GdipDeleteBrush(brush_hover as *mut GpBrush);
+        let now = sessions::unix_time();
+        let mut center_format: *mut GpStringFormat = null_mut();
+        GdipCreateStringFormat(0, 0, &mut center_format);
+        GdipSetStringFormatAlign(center_format, StringAlignmentCenter);
+        GdipSetStringFormatLineAlign(center_format, StringAlignmentCenter);
+
+        // Draw profile rows
+        for (i, row) in self.rows.iter().take(MAX_ROWS).enumerate() {
+            let bounds = row_bounds(i);
+            let is_active = self.active_profile.as_ref() == Some(&row.name);
+            let row_hovered = matches!(self.hover, Some(FlyoutTarget::Row(r) | FlyoutTarget::Activate(r) | FlyoutTarget::Overlay(r)) if r == i);
+
+            if row_hovered {
+                Self::fill_rounded(graphics, bounds, 6.0, 0x40_FF_FF_FF);
             }
 
-            // Profile name text
-            let mut brush_text: *mut GpSolidFill = null_mut();
-            GdipCreateSolidFill(0xFF_FF_FF_FF, &mut brush_text);
-            
-            let text = profile.name.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
-            let rect = RectF {
-                X: (PADDING + 12) as f32,
-                Y: (y + 8) as f32,
-                Width: (FLYOUT_WIDTH - PADDING * 2 - 50) as f32,
-                Height: 24.0,
-            };
+            // Name, then "Active" / last used and the number of processes to close
+            let text_width = overlay_bounds(i).x - bounds.x - 16;
+            let name_rect = RectF { X: (bounds.x + 12) as f32, Y: (bounds.y + 8) as f32, Width: text_width as f32, Height: 24.0 };
+            Self::draw_text(graphics, &row.name, font, &name_rect, string_format, 0xFF_FF_FF_FF);
 
-            GdipDrawString(
-                graphics,
-                PCWSTR(text.as_ptr()),
-                text.len() as i32 - 1,
-                font,
-                &rect,
-                string_format,
-                brush_text as *mut GpBrush,
-            );
-            
-            // Profile description (processes to kill count)
-            let desc = format!("{} processes to manage\0", profile.processes_to_kill.len());
-            let desc_utf16: Vec<u16> = desc.encode_utf16().collect();
-            let desc_rect = RectF {
-                X: (PADDING + 12) as f32,
-                Y: (y + 30) as f32,
-                Width: (FLYOUT_WIDTH - PADDING * 2 - 50) as f32,
-                Height: 20.0,
-            };
-            
-            let mut brush_desc: *mut GpSolidFill = null_mut();
-            GdipCreateSolidFill(0x80_FF_FF_FF, &mut brush_desc);
-            
-            GdipDrawString(
-                graphics,
-                PCWSTR(desc_utf16.as_ptr()),
-                desc_utf16.len() as i32 - 1,
-                small_font,
-                &desc_rect,
-                string_format,
-                brush_desc as *mut GpBrush,
-            );
-            GdipDeleteBrush(brush_desc as *mut GpBrush);
-
-            // Active indicator (checkmark or "Active" badge)
-            if is_active {
-                let mut brush_active: *mut GpSolidFill = null_mut();
-                GdipCreateSolidFill(0xFF_4C_AF_50, &mut brush_active); // Green
-                
-                let badge_x = FLYOUT_WIDTH - PADDING - 60;
-                let badge_y = y + ITEM_HEIGHT / 2 - 10;
-                
-                // Draw "Active" text
-                let active_text = "Active\0".encode_utf16().collect::<Vec<u16>>();
-                let active_rect = RectF {
-                    X: badge_x as f32,
-                    Y: badge_y as f32,
-                    Width: 50.0,
-                    Height: 20.0,
+            let status = if is_active { t!("flyout-active") } else { last_used_label(row.last_activated, now) };
+            let desc = format!("{} · {}", status, t!("flyout-processes", count = row.processes));
+            let desc_rect = RectF { X: (bounds.x + 12) as f32, Y: (bounds.y + 30) as f32, Width: text_width as f32, Height: 20.0 };
+            let desc_color = if is_active { 0xFF_4C_AF_50 } else { 0x80_FF_FF_FF };
+            Self::draw_text(graphics, &desc, small_font, &desc_rect, string_format, desc_color);
+
+            // Crosshair toggle: lit while the active profile's crosshair is shown
+            if row.has_crosshair {
+                let toggle = overlay_bounds(i);
+                let color = match (is_active, self.overlay_visible) {
+                    (true, true) => CROSSHAIR_COLOR,
+                    (true, false) => 0xC0_FF_FF_FF,
+                    (false, _) => 0x40_FF_FF_FF,
                 };
-                GdipDrawString(
-                    graphics,
-                    PCWSTR(active_text.as_ptr()),
-                    active_text.len() as i32 - 1,
-                    small_font,
-                    &active_rect,
-                    string_format,
-                    brush_active as *mut GpBrush,
-                );
-                GdipDeleteBrush(brush_active as *mut GpBrush);
+                if is_active && self.hover == Some(FlyoutTarget::Overlay(i)) {
+                    Self::fill_rounded(graphics, toggle, 6.0, 0x30_FF_FF_FF);
+                }
+                Self::draw_crosshair_icon(graphics, toggle, color);
             }
 
-            GdipDeleteBrush(brush_text as *mut GpBrush);
+            // Activate button (stop on the active profile)
+            let button = activate_bounds(i);
+            let hovered = self.hover == Some(FlyoutTarget::Activate(i));
+            let (label, fill) = match (is_active, hovered) {
+                (true, false) => (t!("flyout-stop"), 0xFF_38_8E_3C),
+                (true, true) => (t!("flyout-stop"), 0xFF_43_A0_47),
+                (false, false) => (t!("flyout-activate"), 0x30_FF_FF_FF),
+                (false, true) => (t!("flyout-activate"), 0x60_FF_FF_FF),
+            };
+            Self::fill_rounded(graphics, button, 6.0, fill);
+            Self::draw_text(graphics, &label, small_font, &button.rect(), center_format, 0xFF_FF_FF_FF);
         }
-        
+
+        // Hint for the rows that don't fit
+        if self.rows.len() > MAX_ROWS {
+            let more_rect = RectF {
+                X: PADDING as f32,
+                Y: (ITEMS_START_Y + MAX_ROWS as i32 * ITEM_HEIGHT) as f32,
+                Width: (FLYOUT_WIDTH - PADDING * 2) as f32,
+                Height: 20.0,
+            };
+            let more = t!("flyout-more", count = self.rows.len() - MAX_ROWS);
+            Self::draw_text(graphics, &more, small_font, &more_rect, center_format, 0x80_FF_FF_FF);
+        }
+
         // Draw "No profiles" message if empty
-        if self.profiles.is_empty() {
-            let mut brush_empty: *mut GpSolidFill = null_mut();
-            GdipCreateSolidFill(0x80_FF_FF_FF, &mut brush_empty);
-            
-            let empty_text = "No gaming profiles configured\0".encode_utf16().collect::<Vec<u16>>();
+        if self.rows.is_empty() {
             let empty_rect = RectF {
                 X: PADDING as f32,
                 Y: (window_height / 2 - 20) as f32,
                 Width: (FLYOUT_WIDTH - PADDING * 2) as f32,
                 Height: 40.0,
             };
-            
-            let mut center_format: *mut GpStringFormat = null_mut();
-            GdipCreateStringFormat(0, 0, &mut center_format);
-            GdipSetStringFormatAlign(center_format, StringAlignmentCenter);
-            GdipSetStringFormatLineAlign(center_format, StringAlignmentCenter);
-            
-            GdipDrawString(
-                graphics,
-                PCWSTR(empty_text.as_ptr()),
-                empty_text.len() as i32 - 1,
-                font,
-                &empty_rect,
-                center_format,
-                brush_empty as *mut GpBrush,
-            );
-            GdipDeleteBrush(brush_empty as *mut GpBrush);
-            GdipDeleteStringFormat(center_format);
+            Self::draw_text(graphics, &t!("flyout-no-profiles"), font, &empty_rect, center_format, 0x80_FF_FF_FF);
+        }
+
+        // Footer with "Open GUI", "Adjust crosshair" and "Exit"
+        let mut pen_footer: *mut GpPen = null_mut();
+        GdipCreatePen1(0x40_FF_FF_FF, 1.0, UnitPixel, &mut pen_footer);
+        let footer_y = FLYOUT_HEIGHT - FOOTER_HEIGHT;
+        GdipDrawLineI(graphics, pen_footer, PADDING, footer_y, FLYOUT_WIDTH - PADDING, footer_y);
+        GdipDeletePen(pen_footer);
+        for (target, bounds, label) in [
+            (FlyoutTarget::OpenGui, open_gui_bounds(), t!("flyout-open-gui")),
+            (FlyoutTarget::AdjustCrosshair, adjust_bounds(), t!("flyout-adjust")),
+            (FlyoutTarget::Exit, exit_bounds(), t!("flyout-exit")),
+        ] {
+            let fill = if self.hover == Some(target) { 0x50_FF_FF_FF } else { 0x20_FF_FF_FF };
+            Self::fill_rounded(graphics, bounds, 6.0, fill);
+            Self::draw_text(graphics, &label, small_font, &bounds.rect(), center_format, 0xFF_FF_FF_FF);
         }
+        GdipDeleteStringFormat(center_format);
 
         // Cleanup GDI+ resources
         GdipDeleteFont(font);
@@ -512,6 +750,47 @@ impl FlyoutWindow {
         GdipClosePathFigure(path);
     }
 
+    /// Draw `text` in `rect` with a solid `color` (ARGB)
+    unsafe fn draw_text(
+        graphics: *mut GpGraphics,
+        text: &str,
+        font: *mut GpFont,
+        rect: &RectF,
+        format: *mut GpStringFormat,
+        color: u32,
+    ) {
+        let mut brush: *mut GpSolidFill = null_mut();
+        GdipCreateSolidFill(color, &mut brush);
+        let text: Vec<u16> = text.encode_utf16().collect();
+        GdipDrawString(graphics, PCWSTR(text.as_ptr()), text.len() as i32, font, rect, format, brush as *mut GpBrush);
+        GdipDeleteBrush(brush as *mut GpBrush);
+    }
+
+    /// Fill `bounds` as a rounded rectangle with a solid `color` (ARGB)
+    unsafe fn fill_rounded(graphics: *mut GpGraphics, bounds: Bounds, radius: f32, color: u32) {
+        let mut brush: *mut GpSolidFill = null_mut();
+        GdipCreateSolidFill(color, &mut brush);
+        let mut path: *mut GpPath = null_mut();
+        GdipCreatePath(FillModeWinding, &mut path);
+        Self::add_rounded_rectangle(path, bounds.x as f32, bounds.y as f32, bounds.width as f32, bounds.height as f32, radius);
+        GdipFillPath(graphics, brush as *mut GpBrush, path);
+        GdipDeletePath(path);
+        GdipDeleteBrush(brush as *mut GpBrush);
+    }
+
+    /// Draw a small crosshair (circle with four ticks) centered in `bounds`
+    unsafe fn draw_crosshair_icon(graphics: *mut GpGraphics, bounds: Bounds, color: u32) {
+        let mut pen: *mut GpPen = null_mut();
+        GdipCreatePen1(color, 1.5, UnitPixel, &mut pen);
+        let (cx, cy) = (bounds.x + bounds.width / 2, bounds.y + bounds.height / 2);
+        GdipDrawEllipseI(graphics, pen, cx - 7, cy - 7, 14, 14);
+        GdipDrawLineI(graphics, pen, cx - 11, cy, cx - 3, cy);
+        GdipDrawLineI(graphics, pen, cx + 3, cy, cx + 11, cy);
+        GdipDrawLineI(graphics, pen, cx, cy - 11, cx, cy - 3);
+        GdipDrawLineI(graphics, pen, cx, cy + 3, cx, cy + 11);
+        GdipDeletePen(pen);
+    }
+
     /// Draw checkmark symbol
     unsafe fn draw_checkmark(graphics: *mut GpGraphics, x: i32, y: i32) {
         let mut pen: *mut GpPen = null_mut();
@@ -560,20 +839,9 @@ impl FlyoutWindow {
                     let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as i32;
                     let x = (lparam.0 & 0xFFFF) as i16 as i32;
                     
-                    // Items start at y=90 (below title and subtitle)
-                    let items_start_y = 90;
-                    let item_index = (y - items_start_y) / ITEM_HEIGHT;
-                    
-                    // Check if mouse is in the item area
-                    if y >= items_start_y && x >= PADDING && x < (FLYOUT_WIDTH - PADDING) 
-                        && item_index >= 0 && (item_index as usize) < flyout.profiles.len() 
-                    {
-                        if flyout.hover_index != Some(item_index as usize) {
-                            flyout.hover_index = Some(item_index as usize);
-                            let _ = flyout.render();
-                        }
-                    } else if flyout.hover_index.is_some() {
-                        flyout.hover_index = None;
+                    let hover = hit_test(x, y, flyout.rows.len());
+                    if flyout.hover != hover {
+                        flyout.hover = hover;
                         let _ = flyout.render();
                     }
                 }
@@ -582,25 +850,25 @@ impl FlyoutWindow {
             WM_LBUTTONDOWN => {
                 let flyout = Self::get_flyout(hwnd);
                 if let Some(flyout) = flyout {
-                    if let Some(index) = flyout.hover_index {
-                        if let Some(profile) = flyout.profiles.get(index) {
-                            println!("[FLYOUT] Activating profile: {}", profile.name);
-                            // Send activation request to main app
-                            let _ = flyout.to_gui_tx.send(TrayToGui::ActivateProfile(profile.name.clone()));
-                            // Close flyout
-                            let _ = PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
-                        }
+                    let y = ((lparam.0 >> 16) & 0xFFFF) as i16 as i32;
+                    let x = (lparam.0 & 0xFFFF) as i16 as i32;
+                    
+                    if let Some(target) = hit_test(x, y, flyout.rows.len()) {
+                        flyout.click(target);
                     }
                 }
                 LRESULT(0)
             }
-            WM_KILLFOCUS => {
-                // Don't auto-close on focus loss - let user interact
+            WM_KEYDOWN => {
+                if let Some(flyout) = Self::get_flyout(hwnd) {
+                    flyout.key_down(VIRTUAL_KEY(wparam.0 as u16));
+                }
                 LRESULT(0)
             }
-            WM_ACTIVATE => {
-                // Close flyout when deactivated (clicked outside)
-                if wparam.0 == 0 {
+            WM_ACTIVATE | WM_KILLFOCUS => {
+                // Close once another window (or a click outside) takes over
+                let lost = msg == WM_KILLFOCUS || (wparam.0 & 0xFFFF) as u32 == WA_INACTIVE;
+                if lost {
                     let _ = PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
                 }
                 LRESULT(0)
@@ -644,12 +912,99 @@ impl FlyoutWindow {
         }
     }
 
+    /// Run the action under a click; everything but the crosshair toggle closes the flyout
+    fn click(&mut self, target: FlyoutTarget) {
+        let row_index = match target {
+            FlyoutTarget::Row(i) | FlyoutTarget::Activate(i) | FlyoutTarget::Overlay(i) => Some(i),
+            FlyoutTarget::OpenGui | FlyoutTarget::AdjustCrosshair | FlyoutTarget::Exit => None,
+        };
+        let row = row_index.and_then(|i| self.rows.get(i));
+        let is_active = row.is_some_and(|row| self.active_profile.as_ref() == Some(&row.name));
+
+        let message = match (target, row) {
+            (FlyoutTarget::Overlay(_), Some(row)) => {
+                if is_active && row.has_crosshair {
+                    info!("[FLYOUT] Toggling crosshair of {}", row.name);
+                    let _ = self.to_gui_tx.send(TrayToGui::ToggleOverlay);
+                    ipc::wake();
+                    self.overlay_visible = !self.overlay_visible;
+                    let _ = unsafe { self.render() };
+                }
+                return;
+            }
+            (FlyoutTarget::Activate(_), Some(row)) if is_active => {
+                info!("[FLYOUT] Deactivating profile: {}", row.name);
+                TrayToGui::DeactivateProfile
+            }
+            (FlyoutTarget::Row(_) | FlyoutTarget::Activate(_), Some(row)) => {
+                info!("[FLYOUT] Activating profile: {}", row.name);
+                TrayToGui::ActivateProfile(row.name.clone())
+            }
+            (FlyoutTarget::OpenGui, _) => TrayToGui::OpenSettings,
+            (FlyoutTarget::AdjustCrosshair, _) => TrayToGui::AdjustCrosshair,
+            (FlyoutTarget::Exit, _) => TrayToGui::Exit,
+            _ => return,
+        };
+        let _ = self.to_gui_tx.send(message);
+        ipc::wake();
+        unsafe {
+            let _ = PostMessageW(self.hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+        }
+    }
+
+    /// Escape closes, arrows and Tab move the highlight, Enter or Space run what is highlighted
+    fn key_down(&mut self, key: VIRTUAL_KEY) {
+        let shift = unsafe { GetKeyState(VK_SHIFT.0 as i32) } < 0;
+        let step = match key {
+            VK_ESCAPE => {
+                info!("[FLYOUT] Dismissed with Escape");
+                unsafe {
+                    let _ = PostMessageW(self.hwnd, WM_CLOSE, WPARAM(0), LPARAM(0));
+                }
+                return;
+            }
+            VK_RETURN | VK_SPACE => {
+                if let Some(target) = self.hover {
+                    self.click(target);
+                }
+                return;
+            }
+            VK_UP => NavKey::Up,
+            VK_DOWN => NavKey::Down,
+            VK_LEFT => NavKey::Left,
+            VK_RIGHT => NavKey::Right,
+            VK_TAB if shift => NavKey::Back,
+            VK_TAB => NavKey::Next,
+            _ => return,
+        };
+        let lines = focus_lines(&self.rows, self.active_profile.as_deref());
+        let target = navigate(self.hover, step, &lines);
+        if target != self.hover {
+            self.hover = target;
+            let _ = unsafe { self.render() };
+        }
+    }
+
+    /// Whether the window is still open (it closes itself after most clicks)
+    pub fn is_open(&self) -> bool {
+        unsafe { IsWindow(self.hwnd).as_bool() }
+    }
+
     /// Update profiles list
-    pub fn update_profiles(&mut self, profiles: Vec<Profile>, active: Option<String>) -> anyhow::Result<()> {
-        self.profiles = profiles;
+    pub fn update_profiles(&mut self, profiles: &[Profile], active: Option<String>) -> anyhow::Result<()> {
+        self.rows = build_rows(profiles, &self.usage);
         self.active_profile = active;
         unsafe { self.render() }
     }
+
+    /// Light or dim the active profile's crosshair toggle
+    pub fn set_overlay_visible(&mut self, visible: bool) -> anyhow::Result<()> {
+        if self.overlay_visible == visible {
+            return anyhow::Ok(());
+        }
+        self.overlay_visible = visible;
+        unsafe { self.render() }
+    }
 }
 
 impl Drop for FlyoutWindow {
@@ -678,3 +1033,106 @@ pub fn get_tray_rect() -> RECT {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::create_profile;
+
+    #[test]
+    fn test_hit_test() {
+        let activate = activate_bounds(1);
+        assert_eq!(hit_test(activate.x + 1, activate.y + 1, 3), Some(FlyoutTarget::Activate(1)));
+        let toggle = overlay_bounds(2);
+        assert_eq!(hit_test(toggle.x + 1, toggle.y + 1, 3), Some(FlyoutTarget::Overlay(2)));
+        assert_eq!(hit_test(PADDING + 4, ITEMS_START_Y + 4, 3), Some(FlyoutTarget::Row(0)));
+        // Rows beyond the profiles (or beyond what fits) aren't clickable
+        assert_eq!(hit_test(PADDING + 4, row_bounds(3).y + 4, 3), None);
+        assert_eq!(hit_test(PADDING + 4, row_bounds(MAX_ROWS).y + 4, 100), None);
+        assert_eq!(hit_test(exit_bounds().x + 1, exit_bounds().y + 1, 0), Some(FlyoutTarget::Exit));
+        assert_eq!(hit_test(open_gui_bounds().x + 1, open_gui_bounds().y + 1, 0), Some(FlyoutTarget::OpenGui));
+        assert_eq!(hit_test(adjust_bounds().x + 1, adjust_bounds().y + 1, 0), Some(FlyoutTarget::AdjustCrosshair));
+        // Gaps between the footer buttons aren't clickable
+        assert_eq!(hit_test(adjust_bounds().x - 4, adjust_bounds().y + 1, 0), None);
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        let row = |name: &str, has_crosshair: bool| FlyoutRow { name: name.to_string(), processes: 0, has_crosshair, last_activated: None };
+        let rows = [row("A", true), row("B", true)];
+        let lines = focus_lines(&rows, Some("B"));
+        assert_eq!(lines[1], [FlyoutTarget::Overlay(1), FlyoutTarget::Activate(1)]);
+
+        assert_eq!(navigate(None, NavKey::Down, &lines), Some(FlyoutTarget::Activate(0)));
+        assert_eq!(navigate(None, NavKey::Up, &lines), Some(FlyoutTarget::OpenGui));
+        // A hovered row moves like its activate button
+        assert_eq!(navigate(Some(FlyoutTarget::Row(0)), NavKey::Down, &lines), Some(FlyoutTarget::Activate(1)));
+        assert_eq!(navigate(Some(FlyoutTarget::Activate(1)), NavKey::Left, &lines), Some(FlyoutTarget::Overlay(1)));
+        assert_eq!(navigate(Some(FlyoutTarget::Overlay(1)), NavKey::Left, &lines), Some(FlyoutTarget::Overlay(1)));
+        assert_eq!(navigate(Some(FlyoutTarget::Exit), NavKey::Right, &lines), Some(FlyoutTarget::Exit));
+        // Up and Down wrap between the first row and the footer
+        assert_eq!(navigate(Some(FlyoutTarget::Exit), NavKey::Down, &lines), Some(FlyoutTarget::Activate(0)));
+        assert_eq!(navigate(Some(FlyoutTarget::Activate(0)), NavKey::Up, &lines), Some(FlyoutTarget::OpenGui));
+        // Tab walks every target
+        assert_eq!(navigate(Some(FlyoutTarget::Activate(0)), NavKey::Next, &lines), Some(FlyoutTarget::Overlay(1)));
+        assert_eq!(navigate(Some(FlyoutTarget::Activate(0)), NavKey::Back, &lines), Some(FlyoutTarget::Exit));
+        assert_eq!(navigate(None, NavKey::Back, &lines), Some(FlyoutTarget::Exit));
+
+        // Without profiles only the footer is left
+        let lines = focus_lines(&[], None);
+        assert_eq!(navigate(Some(FlyoutTarget::OpenGui), NavKey::Down, &lines), Some(FlyoutTarget::OpenGui));
+    }
+
+    #[test]
+    fn test_flyout_position() {
+        let monitor = Bounds { x: 0, y: 0, width: 1920, height: 1080 };
+        let size = (FLYOUT_WIDTH, FLYOUT_HEIGHT);
+
+        // Bottom taskbar, tray in the corner: right-aligned above the taskbar
+        let work = Bounds { height: 1032, ..monitor };
+        let tray = Bounds { x: 1800, y: 1040, width: 24, height: 24 };
+        assert_eq!(flyout_position(tray, monitor, work, size), (1920 - FLYOUT_WIDTH - 12, 1032 - FLYOUT_HEIGHT - 12));
+
+        // Top taskbar
+        let work = Bounds { y: 48, height: 1032, ..monitor };
+        let tray = Bounds { x: 1000, y: 12, width: 24, height: 24 };
+        assert_eq!(flyout_position(tray, monitor, work, size), (1012 - FLYOUT_WIDTH / 2, 60));
+
+        // Left and right taskbars: beside it, centered on the icon as far as the screen allows
+        let work = Bounds { x: 62, width: 1858, ..monitor };
+        let tray = Bounds { x: 19, y: 1000, width: 24, height: 24 };
+        assert_eq!(flyout_position(tray, monitor, work, size), (74, 1080 - FLYOUT_HEIGHT - 12));
+        let work = Bounds { width: 1858, ..monitor };
+        let tray = Bounds { x: 1877, y: 400, width: 24, height: 24 };
+        assert_eq!(flyout_position(tray, monitor, work, size), (1858 - FLYOUT_WIDTH - 12, 412 - FLYOUT_HEIGHT / 2));
+
+        // Auto-hidden taskbar on a second monitor: the closest edge to the icon
+        let monitor = Bounds { x: 1920, y: 0, width: 2560, height: 1440 };
+        let tray = Bounds { x: 1930, y: 700, width: 24, height: 24 };
+        assert_eq!(taskbar_edge(tray, monitor, monitor), TaskbarEdge::Left);
+    }
+
+    #[test]
+    fn test_build_rows() {
+        let mut favorite = create_profile("Favorite".to_string());
+        favorite.favorite = true;
+        let profiles = [create_profile("Old".to_string()), create_profile("Unused".to_string()), favorite, create_profile("Recent".to_string())];
+        let usage = HashMap::from([
+            ("Old".to_string(), ProfileUsage { activations: 9, last_activated: 100 }),
+            ("Recent".to_string(), ProfileUsage { activations: 1, last_activated: 200 }),
+        ]);
+
+        let rows = build_rows(&profiles, &usage);
+        let names: Vec<&str> = rows.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, ["Favorite", "Recent", "Old", "Unused"]);
+        assert_eq!(rows[1].last_activated, Some(200));
+        assert_eq!(rows[3].last_activated, None);
+    }
+
+    #[test]
+    fn test_last_used_label() {
+        assert_eq!(last_used_label(None, 1000), "Never used");
+        assert_eq!(last_used_label(Some(1000 - 300), 1000), "Used 5m ago");
+        assert_eq!(last_used_label(Some(0), 3 * 86400 + 5), "Used 3d ago");
+    }
+}
@@ -0,0 +1,68 @@
+//! Executable icons for the process selector and the close-apps confirmation
+//!
+//! Icons are read with SHGetFileInfo on a background thread, so a process list refresh
+//! doesn't stall the window, and kept by executable path for the rest of the session.
+//! Rows show no icon until theirs has arrived.
+
+use crate::process::{self, ProcessIcon};
+use iced::widget::image;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use tracing::debug;
+
+pub struct IconCache {
+    /// None while loading or when the executable has no readable icon
+    icons: HashMap<PathBuf, Option<image::Handle>>,
+    tx: Sender<(PathBuf, Option<ProcessIcon>)>,
+    rx: Receiver<(PathBuf, Option<ProcessIcon>)>,
+}
+
+impl Default for IconCache {
+    fn default() -> Self {
+        let (tx, rx) = channel();
+        IconCache { icons: HashMap::new(), tx, rx }
+    }
+}
+
+impl IconCache {
+    pub fn get(&self, exe: &Path) -> Option<&image::Handle> {
+        self.icons.get(exe).and_then(Option::as_ref)
+    }
+
+    /// Start loading the icons of `exes` that aren't cached or on their way
+    pub fn request(&mut self, exes: impl IntoIterator<Item = PathBuf>) {
+        let missing: Vec<PathBuf> = exes.into_iter().filter(|exe| !self.icons.contains_key(exe)).collect();
+        if missing.is_empty() {
+            return;
+        }
+        for exe in &missing {
+            self.icons.insert(exe.clone(), None);
+        }
+        debug!("[Icons] Loading {} icons", missing.len());
+
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED};
+
+            let initialized = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }.is_ok();
+            for exe in missing {
+                let icon = process::exe_icon(&exe);
+                if tx.send((exe, icon)).is_err() {
+                    break;
+                }
+            }
+            if initialized {
+                unsafe { CoUninitialize() };
+            }
+        });
+    }
+
+    /// Store icons that finished loading
+    pub fn poll(&mut self) {
+        while let Ok((exe, icon)) = self.rx.try_recv() {
+            let handle = icon.map(|icon| image::Handle::from_pixels(icon.width, icon.height, icon.rgba));
+            self.icons.insert(exe, handle);
+        }
+    }
+}
@@ -1,59 +1,144 @@
 /// ICED GUI Application Module with System Tray Integration
+mod icons;
+mod process_list;
 mod profile_editor;
+mod quick_switch;
 pub mod styles;
 
 use iced::{
     executor, Application, Command, Element, Settings, Length, Alignment, Theme, Subscription,
-    widget::{Container, Column, Row, Text, Button, Scrollable, Checkbox, TextInput, Space, Toggler},
+    widget::{Container, Column, Row, Text, Button, Scrollable, Checkbox, TextInput, Space, Toggler, PickList, Image, image, ProgressBar, scrollable, text_editor, text_input},
 };
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
-use crate::profile::Profile;
-use crate::common_apps::COMMON_APPS;
-use crate::config::get_data_directory;
-use crate::profile::{load_profiles, save_profiles};
-use crate::image_picker::{open_image_picker, validate_crosshair_image};
-use crate::process::{list_processes, kill_processes, ProcessInfo};
-use crate::crosshair_overlay::{self, OverlayHandle};
-use crate::tray_flyout::TrayFlyoutManager;
-use std::sync::Mutex;
+use crate::profile::{CrosshairPreset, OffsetPreset, OverlayNote, Profile};
+use crate::profile_lint::{self, Lint};
+use crate::common_apps::{self, CommonApp};
+use crate::game_knowledge::{self, GameEntry, Suggestion};
+use crate::game_watch::GameWatcher;
+use crate::overlay_conflicts::{self, OverlayFix, OverlayInjector};
+use crate::preflight::{self, Environment, Problem};
+use crate::elevation::{self, AdminFeature, Capabilities};
+use crate::config::{self, get_data_directory, load_config, read_config, save_config, SessionState, WindowLayout};
+use crate::file_watch::DataWatcher;
+use crate::profile::{
+    self, load_profiles_recovering, save_profiles, sync_profiles, InvalidEntry, ProfileUsage, SyncResult,
+    INVALID_PROFILES_FILE,
+};
+use crate::dialogs::{self, open_image_picker};
+use crate::crosshair_library::{self, validate_crosshair_image};
+use crate::process::{self, KillCandidate, KillReport, ProcessInfo, SafetyConfig};
+use crate::crosshair_overlay;
+use crate::overlay::{format_hex_color, parse_hex_color, OverlayOptions};
+use crate::ipc::{self, DataFile, RequestReceiver, RequestSender, TrayToGui};
+use crate::tray_flyout::{self, TrayFlyoutManager};
+use crate::tray_badge::TrayState;
+use crate::tray_clicks::{TrayClickAction, TrayClickConfig};
+use crate::tray_tooltip::{self, TrayTooltipConfig};
+use crate::activation::{manager::{Activation, ActivationManager}, ActivationSummary, ExitBehavior, StepStatus};
+use crate::hotkeys::{self, HotkeyConfig};
+use crate::hotkey_thread::{self, HotkeyAction, HotkeyThread};
+use crate::gamepad::{self, GamepadAction, GamepadConfig, GamepadThread};
+use crate::power::{self, PowerPlan};
+use crate::game_features::GameFeatureToggles;
+use crate::timer::{self, TimerResolution};
+use crate::display::{self, DisplayMode, DisplaySetting, Monitor};
+use crate::audio::{AppVolume, AudioSettings};
+use crate::network::{BandwidthLimit, NetworkSettings};
+use crate::storage::StorageSettings;
+use crate::obs::{self, ObsConfig, ObsSettings};
+use crate::captures::{self, CaptureSettings};
+use crate::openrgb::{self, OpenRgbConfig, RgbScene};
+use crate::integrations::ActivationEvent;
+use crate::integrations::discord::{DiscordConfig, DiscordPresence};
+use crate::integrations::event_log::{self, EventLogConfig};
+use crate::plugins::{self, PluginConfig, PluginEvent, PluginHost, SystemContext};
+use crate::gpu::{self, GpuSettings, GpuVendor};
+use crate::tweaks::SystemTweak;
+use crate::keyboard::{self, KeyboardLayout, KeyboardSettings};
+use crate::crosshair_codes::{imported_file_name, parse_crosshair_code};
+use crate::crosshair_gen::{self, CrosshairParams};
+use crate::crosshair_position::{CrosshairAnchor, NamedAnchor};
+use crate::overlay_render;
+use crate::overlay_text::{self, Corner};
+use crate::monitor::{self, HardwareMonitor, HardwareSnapshot, MonitorConfig};
+use crate::sessions::{self, Session, SessionRecorder};
+use crate::benchmark::{self, BenchmarkResult, BenchmarkRun, BenchmarkStage};
+use crate::latency::{LatencyMonitor, LatencyStats};
+use crate::logging::{self, LogLevel};
+use crate::crash;
+use crate::i18n::{self, t};
+use crate::cli::{self, CliCommand, CliReply, LiveStatus, OverlayState};
+use crate::toast_notifier;
+use crate::toasts::{ToastCategory, ToastConfig};
+use crate::remote_api::{self, ApiConfig, RemoteApi};
+use crate::autostart::{self, AutostartConfig};
+use crate::backup::{self, Backup};
+use crate::kill_list_import;
+use styles::{Accent, ThemeConfig, ThemeMode};
+use quick_switch::QuickSwitch;
+use process_list::{ListItem, ProcessListState, ProcessSort};
+use icons::IconCache;
+use crate::updater::{self, Release, UpdateChannel, UpdateConfig};
+use crate::alerts::{AlertBalloon, AlertConfig, AlertThresholds, AlertTracker, TemperatureAlert};
 use std::sync::mpsc::Receiver;
 use std::time::Instant;
-use once_cell::sync::Lazy;
-use tray_icon::{TrayIconEvent, MouseButton, MouseButtonState};
-use tray_icon::menu::MenuEvent;
-use windows::Win32::UI::WindowsAndMessaging::{MSG, PeekMessageW, TranslateMessage, DispatchMessageW, PM_REMOVE};
-
-/// Global channel for tray icon events
-static TRAY_EVENT_RX: Lazy<Mutex<Option<Receiver<TrayIconEvent>>>> = Lazy::new(|| Mutex::new(None));
-
-/// Global channel for menu events
-static MENU_EVENT_RX: Lazy<Mutex<Option<Receiver<MenuEvent>>>> = Lazy::new(|| Mutex::new(None));
-
-/// Global sender for profile activations from flyout
-static FLYOUT_PROFILE_RX: Lazy<Mutex<Option<Receiver<String>>>> = Lazy::new(|| Mutex::new(None));
-
-/// Track click timing for double-click detection
-static LAST_CLICK_TIME: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
-static PENDING_SINGLE_CLICK: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
-
-/// Store menu item IDs for checking exit
-static MENU_EXIT_ID: Lazy<Mutex<Option<tray_icon::menu::MenuId>>> = Lazy::new(|| Mutex::new(None));
+use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
+use tracing::{error, info, warn};
 
 #[derive(Debug, Clone)]
 pub enum Message {
     // Profile management
     ProfileNameChanged(String),
     ProfileSelected(usize),
+    ProfileFilterChanged(String),
+    ToggleQuickSwitch,
+    CloseQuickSwitch,
+    QuickSwitchQueryChanged(String),
+    QuickSwitchMove(i32),
+    /// Act on the highlighted match: activate it, or open it in the editor
+    QuickSwitchSubmit { edit: bool },
+    QuickSwitchPick { index: usize, edit: bool },
     NewProfile,
+    ShowDashboard,
+    EditorTabSelected(EditorTab),
+    ShowStats,
+    ShowSettings,
+    /// Quick-activate button on the dashboard
+    ActivateFavorite(String),
+    FavoriteToggled(bool),
+    ArchivedToggled(bool),
+    AntiCheatSafeToggled(bool),
+    ToggleArchivedList,
+    RestoreProfile(usize),
+    ThemeModeSelected(ThemeMode),
+    LanguageSelected(LanguageChoice),
+    OpenTranslations,
+    AccentSelected(Accent),
+    RunBenchmark,
     SaveProfile,
     DeleteProfile,
     ActivateProfile,
+    ConfirmKill,
+    CancelKill,
+    KillDontAskAgainToggled(bool),
+    ToggleKillCategory(KillCategory),
+    CopyActivationReport,
+    DismissActivationReport,
+    FixOverlay(OverlayInjector),
+    CheckProfiles,
+    DismissLint,
     
     // Process selection
     ProcessToggled(String, bool),
     RefreshProcesses,
+    ImportKillList,
+    ApplySuggestion(Suggestion),
+    DismissGameSuggestions,
     ProcessFilterChanged(String),
+    ProcessSortSelected(ProcessSort),
+    ProcessSelectedOnlyToggled(bool),
+    ProcessListScrolled(scrollable::Viewport),
     
     // Crosshair settings
     CrosshairOffsetXChanged(String),
@@ -63,18 +148,151 @@ pub enum Message {
     CrosshairMoveLeft,
     CrosshairMoveRight,
     CrosshairCenter,
+    AnchorSelected(CrosshairAnchor),
+    AnchorPercentXChanged(String),
+    AnchorPercentYChanged(String),
     OverlayEnabledToggled(bool),
+    ExcludeFromCaptureToggled(bool),
+    TintSelected(Option<[u8; 4]>),
+    TintHexChanged(String),
+    PresetNameChanged(String),
+    AddPreset,
+    LoadPreset(usize),
+    DeletePreset(usize),
+    OffsetPresetNameChanged(String),
+    AddOffsetPreset,
+    LoadOffsetPreset(usize),
+    UseOffsetPreset(usize),
+    DeleteOffsetPreset(usize),
+    CycleCrosshair,
     SelectImage,
+    /// A file was dropped onto the window
+    ImageDropped(std::path::PathBuf),
     ClearImage,
+    CrosshairCodeChanged(String),
+    ImportCrosshairCode,
+    NoteTextEdited(text_editor::Action),
+    NoteSizeChanged(String),
+    NoteCornerSelected(Corner),
+    NoteColorChanged(String),
+    HardwareHudSelected(HudCornerChoice),
     
     // Fan control
     FanSpeedMaxToggled(bool),
+    PowerPlanSelected(PowerPlanChoice),
+    GameFeatureSelected(GameFeature, FeatureChoice),
+    HighPrecisionTimerToggled(bool),
+    BestPerformanceVisualsToggled(bool),
+    DisplayMonitorSelected(Monitor),
+    DisplayModeSelected(DisplayModeChoice),
+    MasterVolumeChanged(String),
+    AppVolumeProcessChanged(String),
+    AppVolumePercentChanged(String),
+    AddAppVolume,
+    RemoveAppVolume(usize),
+    PauseDeliveryOptimizationToggled(bool),
+    LimitProcessChanged(String),
+    LimitKbpsChanged(String),
+    AddBandwidthLimit,
+    RemoveBandwidthLimit(usize),
+    PauseSearchIndexingToggled(bool),
+    PauseMaintenanceTasksToggled(bool),
+    RgbSceneSelected(RgbSceneChoice),
+    RgbColorChanged(String),
+    OpenRgbHostChanged(String),
+    OpenRgbPortChanged(String),
+    ConnectOpenRgb,
+    ObsSceneSelected(ObsSceneChoice),
+    ObsRecordToggled(bool),
+    ObsReplayBufferToggled(bool),
+    ObsHostChanged(String),
+    ObsPortChanged(String),
+    ObsPasswordChanged(String),
+    ConnectObs,
+    SortCapturesToggled(bool),
+    ChooseCaptureFolder,
+    ClearCaptureFolder,
+    DiscordPresenceToggled(bool),
+    DiscordApplicationIdChanged(String),
+    SaveDiscordSettings,
+    EventLogToggled(bool),
+    PluginToggled(String, bool),
+    OpenPluginsFolder,
+    ReloadPlugins,
+    MonitorIntervalChanged(String),
+    SaveMonitorSettings,
+    AlertsToggled(bool),
+    AlertCpuLimitChanged(String),
+    AlertGpuLimitChanged(String),
+    AlertMaxFansToggled(bool),
+    AlertSwitchProfileSelected(SwitchProfileChoice),
+    SaveAlertSettings,
+    DoNotDisturbToggled(bool),
+    MuteNotificationSoundsToggled(bool),
+    GpuPreferMaxToggled(bool),
+    NvidiaProfileChanged(String),
+    SystemTweakToggled(SystemTweak, bool),
+    KeyboardLayoutSelected(KeyboardLayoutChoice),
+    BlockWindowsKeyToggled(bool),
+    SuppressStickyKeysToggled(bool),
+    RollbackOnFailureToggled(bool),
+    SkipKillConfirmationToggled(bool),
+    ProfileCpuLimitChanged(String),
+    ProfileGpuLimitChanged(String),
+    RefreshDiagnostics,
+    LogLevelSelected(LogLevel),
+    ToastCategoryToggled(ToastCategory, bool),
+    UpdateChecksToggled(bool),
+    UpdateChannelSelected(UpdateChannel),
+    UpdateIntervalChanged(String),
+    SaveUpdateSettings,
+    CheckForUpdates,
+    InstallUpdate,
+    OpenReleaseNotes,
+    HotkeysToggled(bool),
+    TraySingleClickSelected(TrayClickAction),
+    TrayDoubleClickSelected(TrayClickAction),
+    TrayMiddleClickSelected(TrayClickAction),
+    TrayTooltipChanged(String),
+    SaveTrayTooltip,
+    HotkeyBindingChanged(String),
+    SaveHotkeySettings,
+    GamepadToggled(bool),
+    GamepadToggleOverlayChanged(String),
+    GamepadCycleCrosshairChanged(String),
+    SaveGamepadSettings,
+    ChooseSyncFolder,
+    ClearSyncFolder,
+    SyncProfilesNow,
+    ExportBackup,
+    ImportBackup,
+    ChooseDataFolder,
+    ResetDataFolder,
+    ConfirmKillToggled(bool),
+    ProtectedProcessInputChanged(String),
+    AddProtectedProcess,
+    RemoveProtectedProcess(usize),
+    AutostartToggled(bool),
+    AutostartElevatedToggled(bool),
+    ExitBehaviorSelected(ExitBehavior),
+    ResumeLastSessionToggled(bool),
+    AutoActivateGamesToggled(bool),
+    RemoteApiToggled(bool),
+    RemoteApiPortChanged(String),
+    NewRemoteApiToken,
+    SaveRemoteApiSettings,
+    OpenLogs,
     
     // Tray events
     TrayTick,
     TrayProfileSelected(String),
     TrayDeactivate,
     TrayExit,
+    WindowCloseRequested,
+    RestartNormally,
+    RestartAsAdmin,
+    WindowMoved(i32, i32),
+    WindowResized(u32, u32),
 }
 
 pub struct GameOptimizer {
@@ -85,233 +303,605 @@ pub struct GameOptimizer {
     edit_name: String,
     edit_x_offset: String,
     edit_y_offset: String,
+    edit_anchor: CrosshairAnchor,
+    edit_anchor_x: String,
+    edit_anchor_y: String,
     edit_image_path: Option<String>,
+    edit_crosshair_params: Option<CrosshairParams>,
+    edit_crosshair_code: String,
     edit_overlay_enabled: bool,
+    edit_exclude_from_capture: bool,
+    edit_tint: Option<[u8; 4]>,
+    edit_tint_hex: String,
+    edit_presets: Vec<CrosshairPreset>,
+    edit_preset_name: String,
+    edit_offset_presets: Vec<OffsetPreset>,
+    edit_selected_offset_preset: Option<String>,
+    edit_offset_preset_name: String,
+    edit_note_text: text_editor::Content,
+    edit_note_size: String,
+    edit_note_corner: Corner,
+    edit_note_color_hex: String,
+    edit_hardware_hud: Option<Corner>,
     edit_fan_speed_max: bool,
+    edit_power_plan: Option<String>,
+    edit_game_features: GameFeatureToggles,
+    edit_high_precision_timer: bool,
+    edit_best_performance_visuals: bool,
+    edit_display_device: Option<String>,
+    edit_display_mode: Option<DisplayMode>,
+    edit_master_volume: String,
+    edit_app_volumes: Vec<AppVolume>,
+    edit_app_volume_process: String,
+    edit_app_volume_percent: String,
+    edit_pause_delivery_optimization: bool,
+    edit_bandwidth_limits: Vec<BandwidthLimit>,
+    edit_limit_process: String,
+    edit_limit_kbps: String,
+    edit_storage: StorageSettings,
+    edit_rgb_scene: Option<RgbScene>,
+    edit_rgb_hex: String,
+    edit_obs: ObsSettings,
+    edit_captures: CaptureSettings,
+    /// Screenshots/captures folders sorting watches (looked up at startup)
+    capture_folders: Vec<std::path::PathBuf>,
+    edit_do_not_disturb: bool,
+    edit_mute_notification_sounds: bool,
+    edit_gpu_prefer_max: bool,
+    edit_nvidia_profile: String,
+    edit_system_tweaks: Vec<SystemTweak>,
+    edit_keyboard_layout: Option<String>,
+    edit_block_windows_key: bool,
+    edit_suppress_sticky_keys: bool,
+    edit_rollback_on_failure: bool,
+    edit_skip_kill_confirmation: bool,
+    edit_favorite: bool,
+    edit_archived: bool,
+    edit_anti_cheat_safe: bool,
+    edit_cpu_limit: String,
+    edit_gpu_limit: String,
+    // The editor's profile as last loaded or saved, to detect unsaved changes
+    edit_baseline: Profile,
+    
+    // GPU vendors detected at startup
+    gpu_vendors: Vec<GpuVendor>,
+    
+    // Monitors on this machine and the modes supported by the one being edited
+    monitors: Vec<Monitor>,
+    display_modes: Vec<DisplayMode>,
+    
+    // Last queried system timer resolution (diagnostics panel)
+    timer_resolution: Option<TimerResolution>,
+    // Time to render the edited crosshair over a frame of this screen's size, once measured
+    overlay_render_time: Option<(Duration, u32, u32)>,
+    
+    // Power plans installed on this machine (for the editor dropdown)
+    power_plans: Vec<PowerPlan>,
+    
+    // OpenRGB server (global config), its settings form and the profiles it offers
+    openrgb: OpenRgbConfig,
+    openrgb_host: String,
+    openrgb_port: String,
+    openrgb_profiles: Vec<String>,
+    
+    // obs-websocket server (global config), its settings form and the scenes it offers
+    obs: ObsConfig,
+    obs_host: String,
+    obs_port: String,
+    obs_password: String,
+    obs_scenes: Vec<String>,
+    
+    // Discord Rich Presence settings (global config) and the running integration
+    discord_config: DiscordConfig,
+    discord_presence: Option<DiscordPresence>,
+    // Activation entries in the Windows Event Log (global config)
+    event_log: EventLogConfig,
+    // Plugin scripts and programs in the data directory, which of them run (global config) and
+    // their thread
+    plugin_files: Vec<String>,
+    plugin_config: PluginConfig,
+    plugins: Option<PluginHost>,
+    
+    // Log file verbosity and which events raise Windows notifications (global config)
+    log_level: LogLevel,
+    toast_config: ToastConfig,
+    // Set while a tray pick is being activated, so its result is summarized in a notification
+    tray_activation: bool,
+    
+    // Hardware sensor sampling (global config), its interval field (seconds) and the latest readings
+    monitor_config: MonitorConfig,
+    monitor_interval: String,
+    hardware_monitor: HardwareMonitor,
+    hardware: Option<HardwareSnapshot>,
+    
+    // Pings the server of the game detected in the current session
+    latency: Option<LatencyMonitor>,
+    latency_stats: Option<LatencyStats>,
+    
+    // Temperature alerts (global config), their limit fields, which have fired and the tray balloon
+    alert_config: AlertConfig,
+    alert_cpu_limit: String,
+    alert_gpu_limit: String,
+    alert_tracker: AlertTracker,
+    alert_balloon: AlertBalloon,
+    
+    // Keyboard layouts installed for this user (for the editor dropdown)
+    keyboard_layouts: Vec<KeyboardLayout>,
     
     // Process selection (executable name -> selected)
     process_selection: HashMap<String, bool>,
     
+    // Whether admin-only features work in this run (detected at startup)
+    capabilities: Capabilities,
+    
     // Live system processes
     running_processes: Vec<ProcessInfo>,
+    // Bundled common apps plus the user's common_apps.json, re-read on refresh
+    common_apps: Vec<CommonApp>,
+    // Per-game recommendations, and whether they're hidden for the profile being edited
+    games: Vec<GameEntry>,
+    hide_game_suggestions: bool,
     process_filter: String,
+    // Sorting, "selected only" and scroll position of the process selector
+    process_list: ProcessListState,
+    // Executable icons shown next to process names
+    process_icons: IconCache,
+    
+    // Search box above the profile list and the Ctrl+K quick switcher (None = closed)
+    profile_filter: String,
+    /// Whether the archived profiles are listed below the others
+    show_archived: bool,
+    quick_switch: Option<QuickSwitch>,
     
     // Status message
     status_message: String,
     
     // Data directory
     data_dir: Option<std::path::PathBuf>,
+    /// Whether the data directory was moved away from the default location in the settings
+    data_dir_moved: bool,
+    /// Running with `--portable` or portable.txt, where the data directory can't be moved
+    portable: bool,
+    /// Reports the profile file and config.json edited outside the app
+    data_watcher: Option<DataWatcher>,
+    
+    // Session of the active profile and finished sessions from sessions.json
+    session: Option<SessionRecorder>,
+    sessions: Vec<Session>,
+    
+    // What the right panel shows (dashboard, profile editor, stats or app settings)
+    view: View,
+    editor_tab: EditorTab,
+    
+    // UI language (global config) and the languages there are translations for
+    language: Option<String>,
+    languages: Vec<(String, String)>,
+    
+    // Appearance (global config), the Windows app mode and when it was last read
+    theme_config: ThemeConfig,
+    system_dark: bool,
+    system_theme_checked: Instant,
+    
+    // Before/after benchmark in progress and finished results from benchmarks.json
+    benchmark: Option<BenchmarkRun>,
+    benchmarks: Vec<BenchmarkResult>,
     
     // Active profile
     active_profile_name: Option<String>,
     
-    // Crosshair overlay handle
-    overlay_handle: Option<OverlayHandle>,
+    // What the active profile changed (with its crosshair overlay) and the steps to undo it,
+    // plus the result of its activation
+    activation: ActivationManager,
+    last_activation: Option<ActivationSummary>,
+    
+    // Index into the active profile's crosshair slots (0 = base crosshair)
+    active_crosshair_slot: usize,
+    
+    // Set when the overlay had to be shut down (e.g. click-through not guaranteed)
+    overlay_error: Option<String>,
     
     // Tray manager (kept in app state since TrayIcon is !Send)
     tray_manager: Option<TrayFlyoutManager>,
-}
-
-/// Tray action to be processed by the app
-#[derive(Debug, Clone)]
-enum TrayAction {
-    ShowFlyout,
-    HideFlyout,
-    ProfileSelected(String),
-    Exit,
-    None,
-}
-
-/// Process tray events - returns action for the app to handle
-fn process_tray_events() -> TrayAction {
-    // IMPORTANT: Pump Windows messages for tray icon to work
-    // iced's winit doesn't process these by default
-    unsafe {
-        use windows::Win32::UI::WindowsAndMessaging::*;
-        let mut msg = MSG::default();
-        while PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE).as_bool() {
-            // Don't process WM_QUIT here - let iced handle shutdown
-            if msg.message == WM_QUIT {
-                println!("[GUI] WM_QUIT received in message pump - ignoring");
-                continue;
-            }
-            TranslateMessage(&msg);
-            DispatchMessageW(&msg);
-        }
-    }
     
-    // Check for profile activation from flyout
-    if let Ok(guard) = FLYOUT_PROFILE_RX.lock() {
-        if let Some(ref rx) = *guard {
-            if let Ok(profile_name) = rx.try_recv() {
-                println!("[GUI] Profile activated from flyout: {}", profile_name);
-                return TrayAction::ProfileSelected(profile_name);
-            }
-        }
-    }
+    // Global hotkey settings (global config), the binding being edited and the thread
+    // delivering hotkey actions
+    hotkey_config: HotkeyConfig,
+    hotkey_binding: String,
+    hotkeys: HotkeyThread,
     
-    // Check for menu events (right-click context menu)
-    if let Ok(guard) = MENU_EVENT_RX.lock() {
-        if let Some(ref rx) = *guard {
-            if let Ok(event) = rx.try_recv() {
-                println!("[GUI] Menu event received: {:?}", event);
-                // Check if it's the exit item
-                if let Ok(exit_guard) = MENU_EXIT_ID.lock() {
-                    if let Some(ref exit_id) = *exit_guard {
-                        if event.id == *exit_id {
-                            return TrayAction::Exit;
-                        }
-                    }
-                }
-            }
-        }
-    }
+    // Controller combos (global config), the combos being edited and the polling thread
+    gamepad_config: GamepadConfig,
+    gamepad_toggle_overlay: String,
+    gamepad_cycle_crosshair: String,
+    gamepad: GamepadThread,
     
-    // Check for tray icon click events
-    if let Ok(guard) = TRAY_EVENT_RX.lock() {
-        if let Some(ref rx) = *guard {
-            if let Ok(event) = rx.try_recv() {
-                match event {
-                    TrayIconEvent::Click { button, button_state, .. } => {
-                        if button == MouseButton::Left && button_state == MouseButtonState::Up {
-                            let now = Instant::now();
-                            
-                            // Check for double-click
-                            let is_double_click = if let Ok(guard) = LAST_CLICK_TIME.lock() {
-                                if let Some(last_time) = *guard {
-                                    now.duration_since(last_time).as_millis() < 500
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            };
-                            
-                            if is_double_click {
-                                // Double-click - clear state
-                                if let Ok(mut guard) = LAST_CLICK_TIME.lock() {
-                                    *guard = None;
-                                }
-                                if let Ok(mut guard) = PENDING_SINGLE_CLICK.lock() {
-                                    *guard = false;
-                                }
-                                println!("[GUI] Double-click detected - GUI already open");
-                                // GUI is already open, nothing to do
-                            } else {
-                                // First click - start timer
-                                if let Ok(mut guard) = LAST_CLICK_TIME.lock() {
-                                    *guard = Some(now);
-                                }
-                                if let Ok(mut guard) = PENDING_SINGLE_CLICK.lock() {
-                                    *guard = true;
-                                }
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-    }
+    // Arrow-key adjustment of the live crosshair started from the tray
+    crosshair_adjust: Option<CrosshairAdjust>,
     
-    // Check if single-click timer expired (show flyout)
-    let should_toggle_flyout = if let Ok(guard) = PENDING_SINGLE_CLICK.lock() {
-        if *guard {
-            if let Ok(time_guard) = LAST_CLICK_TIME.lock() {
-                if let Some(last_time) = *time_guard {
-                    Instant::now().duration_since(last_time).as_millis() >= 500
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        } else {
-            false
-        }
-    } else {
-        false
-    };
+    // Paused from the tray: hotkeys, hardware/game watching and the crosshair are suspended
+    // while the active profile stays applied
+    paused: bool,
     
-    if should_toggle_flyout {
-        // Clear pending state
-        if let Ok(mut guard) = PENDING_SINGLE_CLICK.lock() {
-            *guard = false;
-        }
-        return TrayAction::ShowFlyout;
-    }
+    // Actions of single, double and middle clicks on the tray icon (global config)
+    tray_clicks: TrayClickConfig,
+    
+    // Tray tooltip template (global config)
+    tray_tooltip: TrayTooltipConfig,
+    
+    // Folder the profile file is synced with (global config)
+    sync_folder: Option<std::path::PathBuf>,
+    
+    // Process closing safeguards (global config) and the protected process being typed
+    safety_config: SafetyConfig,
+    protected_process_input: String,
+    
+    // Activation waiting for the user to confirm the apps it closes
+    kill_confirmation: Option<KillConfirmation>,
+    
+    // The last activation had failed steps (warning badge on the tray icon)
+    activation_failed: bool,
     
-    TrayAction::None
+    // Activation counts behind the favorite/recent profiles in the tray (global config)
+    profile_usage: HashMap<String, ProfileUsage>,
+    
+    // Outcome of the last activation, shown under the editor until dismissed
+    activation_report: Option<ActivationReport>,
+    
+    // Lint findings (profile name, problem) from the last save or check, until dismissed
+    lint_results: Vec<(String, Lint)>,
+    
+    // Commands from the command line and the remote API; both share the sender
+    cli_tx: RequestSender,
+    cli_rx: RequestReceiver,
+    
+    // Start with Windows, what exiting does with the active profile and whether the next start
+    // picks up where this run left off (global config)
+    autostart: AutostartConfig,
+    exit_behavior: ExitBehavior,
+    resume_last_session: bool,
+    /// Activate a game's profile when it starts, watched by `game_watcher` while on
+    auto_activate_games: bool,
+    game_watcher: Option<GameWatcher>,
+    /// Last active profile, overlay and pause state written to config.json
+    saved_session: SessionState,
+    /// Main window size and position, saved on exit
+    window_layout: WindowLayout,
+    
+    // Update checks (global config), the interval field (hours), a check or download in flight
+    // and the release it found
+    update_config: UpdateConfig,
+    update_interval: String,
+    update_check: Option<Receiver<Result<Option<Release>, anyhow::Error>>>,
+    update_download: Option<Receiver<Result<std::path::PathBuf, anyhow::Error>>>,
+    available_update: Option<Release>,
+    
+    // Remote control API settings (global config), its port field and the running server
+    api_config: ApiConfig,
+    api_port: String,
+    remote_api: Option<RemoteApi>,
 }
 
 impl GameOptimizer {
     fn load_profiles_from_disk(&mut self) {
+        // Pick up profiles edited on another machine first
+        if let (Some(data_dir), Some(sync_dir)) = (&self.data_dir, &self.sync_folder) {
+            if let Err(e) = sync_profiles(data_dir, sync_dir) {
+                warn!("[Sync] {}", e);
+            }
+        }
         if let Some(ref data_dir) = self.data_dir {
-            match load_profiles(data_dir) {
-                Ok(profiles) => {
-                    self.profiles = profiles;
-                    self.status_message = format!("Loaded {} profiles", self.profiles.len());
+            match load_profiles_recovering(data_dir) {
+                Ok(loaded) => {
+                    self.profiles = loaded.profiles;
+                    self.status_message = quarantine_status(&loaded.quarantined)
+                        .unwrap_or_else(|| t!("status-profiles-loaded", count = self.profiles.len()));
                 }
                 Err(e) => {
-                    self.status_message = format!("Failed to load profiles: {}", e);
+                    self.status_message = t!("status-profiles-load-failed", error = e);
                 }
             }
         }
     }
     
-    fn save_profiles_to_disk(&mut self) {
+    /// Write the profile file (and the sync folder); false if it couldn't be written
+    fn save_profiles_to_disk(&mut self) -> bool {
         if let Some(ref data_dir) = self.data_dir {
             match save_profiles(&self.profiles, data_dir) {
                 Ok(_) => {
-                    self.status_message = "Profiles saved successfully".to_string();
+                    self.status_message = t!("status-profiles-saved");
+                    if let Some(ref sync_dir) = self.sync_folder {
+                        if let Err(e) = sync_profiles(data_dir, sync_dir) {
+                            self.status_message = t!("status-profiles-saved-sync-failed", error = e);
+                        }
+                    }
+                    return true;
                 }
                 Err(e) => {
-                    self.status_message = format!("Failed to save profiles: {}", e);
+                    self.status_message = t!("status-profiles-save-failed", error = e);
                 }
             }
         }
+        false
     }
     
     fn refresh_running_processes(&mut self) {
-        self.running_processes = list_processes();
+        self.running_processes = self.activation.processes().list();
         self.running_processes.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        self.process_icons.request(self.running_processes.iter().filter_map(|process| process.exe.clone()));
     }
     
     fn clear_edit_form(&mut self) {
+        self.hide_game_suggestions = false;
         self.edit_name = String::new();
         self.edit_x_offset = "0".to_string();
         self.edit_y_offset = "0".to_string();
+        self.edit_anchor = CrosshairAnchor::default();
+        self.edit_anchor_x = "50".to_string();
+        self.edit_anchor_y = "50".to_string();
         self.edit_image_path = None;
+        self.edit_crosshair_params = None;
+        self.edit_crosshair_code = String::new();
         self.edit_overlay_enabled = false;
+        self.edit_exclude_from_capture = false;
+        self.edit_tint = None;
+        self.edit_tint_hex = String::new();
+        self.edit_presets.clear();
+        self.edit_preset_name = String::new();
+        self.edit_offset_presets.clear();
+        self.edit_selected_offset_preset = None;
+        self.edit_offset_preset_name = String::new();
+        self.edit_note_text = text_editor::Content::new();
+        self.edit_note_size = DEFAULT_NOTE_SIZE.to_string();
+        self.edit_note_corner = Corner::default();
+        self.edit_note_color_hex = format_hex_color(DEFAULT_NOTE_COLOR);
+        self.edit_hardware_hud = None;
         self.edit_fan_speed_max = false;
+        self.edit_power_plan = None;
+        self.edit_game_features = GameFeatureToggles::default();
+        self.edit_high_precision_timer = false;
+        self.edit_best_performance_visuals = false;
+        self.edit_display_mode = None;
+        self.select_display_device(None);
+        self.edit_master_volume = String::new();
+        self.edit_app_volumes.clear();
+        self.edit_app_volume_process = String::new();
+        self.edit_app_volume_percent = String::new();
+        self.edit_pause_delivery_optimization = false;
+        self.edit_bandwidth_limits.clear();
+        self.edit_limit_process = String::new();
+        self.edit_limit_kbps = String::new();
+        self.edit_storage = StorageSettings::default();
+        self.edit_rgb_scene = None;
+        self.edit_rgb_hex = String::new();
+        self.edit_obs = ObsSettings::default();
+        self.edit_captures = CaptureSettings::default();
+        self.edit_do_not_disturb = false;
+        self.edit_mute_notification_sounds = false;
+        self.edit_gpu_prefer_max = false;
+        self.edit_nvidia_profile = String::new();
+        self.edit_system_tweaks.clear();
+        self.edit_keyboard_layout = None;
+        self.edit_block_windows_key = false;
+        self.edit_suppress_sticky_keys = false;
+        self.edit_rollback_on_failure = false;
+        self.edit_skip_kill_confirmation = false;
+        self.edit_favorite = false;
+        self.edit_archived = false;
+        self.edit_anti_cheat_safe = false;
+        self.edit_cpu_limit = String::new();
+        self.edit_gpu_limit = String::new();
         self.process_selection.clear();
         self.selected_profile_index = None;
+        self.edit_baseline = self.edited_profile();
     }
     
     fn load_profile_to_edit(&mut self, index: usize) {
         if let Some(profile) = self.profiles.get(index) {
+            self.hide_game_suggestions = false;
             self.edit_name = profile.name.clone();
             self.edit_x_offset = profile.crosshair_x_offset.to_string();
             self.edit_y_offset = profile.crosshair_y_offset.to_string();
+            self.edit_anchor = profile.crosshair_anchor;
+            let (anchor_x, anchor_y) = profile.crosshair_anchor.percent();
+            self.edit_anchor_x = anchor_x.to_string();
+            self.edit_anchor_y = anchor_y.to_string();
             self.edit_image_path = profile.crosshair_image_path.clone();
+            self.edit_crosshair_params = profile.crosshair_params.clone();
+            self.edit_crosshair_code = String::new();
             self.edit_overlay_enabled = profile.overlay_enabled;
+            self.edit_exclude_from_capture = profile.exclude_from_capture;
+            self.edit_tint = profile.crosshair_tint;
+            self.edit_tint_hex = profile.crosshair_tint.map(format_hex_color).unwrap_or_default();
+            self.edit_presets = profile.crosshair_presets.clone();
+            self.edit_preset_name = String::new();
+            self.edit_offset_presets = profile.offset_presets.clone();
+            self.edit_selected_offset_preset = profile.selected_offset_preset.clone();
+            self.edit_offset_preset_name = String::new();
+            let note = profile.overlay_note.as_ref();
+            self.edit_note_text = text_editor::Content::with_text(note.map(|n| n.text.as_str()).unwrap_or(""));
+            self.edit_note_size = note.map_or(DEFAULT_NOTE_SIZE, |n| n.font_size).to_string();
+            self.edit_note_corner = note.map(|n| n.corner).unwrap_or_default();
+            self.edit_note_color_hex = format_hex_color(note.map_or(DEFAULT_NOTE_COLOR, |n| n.color));
+            self.edit_hardware_hud = profile.hardware_hud;
             self.edit_fan_speed_max = profile.fan_speed_max;
+            self.edit_power_plan = profile.power_plan.clone();
+            self.edit_game_features = profile.game_features;
+            self.edit_high_precision_timer = profile.high_precision_timer;
+            self.edit_best_performance_visuals = profile.best_performance_visuals;
+            let display_mode = profile.display_mode.clone();
+            self.edit_master_volume = profile.audio.master_percent.map(|p| p.to_string()).unwrap_or_default();
+            self.edit_app_volumes = profile.audio.app_volumes.clone();
+            self.edit_app_volume_process = String::new();
+            self.edit_app_volume_percent = String::new();
+            self.edit_pause_delivery_optimization = profile.network.pause_delivery_optimization;
+            self.edit_bandwidth_limits = profile.network.bandwidth_limits.clone();
+            self.edit_limit_process = String::new();
+            self.edit_limit_kbps = String::new();
+            self.edit_storage = profile.storage;
+            self.edit_rgb_scene = profile.rgb_scene.clone();
+            self.edit_rgb_hex = match profile.rgb_scene {
+                Some(RgbScene::Color([r, g, b])) => format!("#{:02X}{:02X}{:02X}", r, g, b),
+                _ => String::new(),
+            };
+            self.edit_obs = profile.obs.clone();
+            self.edit_captures = profile.captures.clone();
+            self.edit_do_not_disturb = profile.do_not_disturb;
+            self.edit_mute_notification_sounds = profile.mute_notification_sounds;
+            self.edit_gpu_prefer_max = profile.gpu.prefer_max_performance;
+            self.edit_nvidia_profile = profile.gpu.nvidia_profile.clone().unwrap_or_default();
+            self.edit_system_tweaks = profile.system_tweaks.clone();
+            self.edit_keyboard_layout = profile.keyboard.layout.clone();
+            self.edit_block_windows_key = profile.keyboard.block_windows_key;
+            self.edit_suppress_sticky_keys = profile.keyboard.suppress_sticky_keys;
+            self.edit_rollback_on_failure = profile.rollback_on_failure;
+            self.edit_skip_kill_confirmation = profile.skip_kill_confirmation;
+            self.edit_favorite = profile.favorite;
+            self.edit_archived = profile.archived;
+            self.edit_anti_cheat_safe = profile.anti_cheat_safe;
+            self.edit_cpu_limit = format_limit(profile.alert_thresholds.cpu_temp_c);
+            self.edit_gpu_limit = format_limit(profile.alert_thresholds.gpu_temp_c);
             
             self.process_selection.clear();
             for proc in &profile.processes_to_kill {
                 self.process_selection.insert(proc.clone(), true);
             }
             
+            self.edit_display_mode = display_mode.as_ref().map(|display| display.mode);
+            self.select_display_device(display_mode.map(|display| display.device));
+            
             self.selected_profile_index = Some(index);
+            self.edit_baseline = self.edited_profile();
+        }
+    }
+    
+    /// Profile as currently entered in the editor
+    fn edited_profile(&self) -> Profile {
+        let x_offset = self.edit_x_offset.parse().unwrap_or(0);
+        let y_offset = self.edit_y_offset.parse().unwrap_or(0);
+        
+        Profile {
+            name: self.edit_name.clone(),
+            processes_to_kill: self.get_selected_processes(),
+            crosshair_image_path: self.edit_image_path.clone(),
+            crosshair_x_offset: x_offset,
+            crosshair_y_offset: y_offset,
+            crosshair_anchor: self.edit_anchor,
+            overlay_enabled: self.edit_overlay_enabled,
+            fan_speed_max: self.edit_fan_speed_max,
+            power_plan: self.edit_power_plan.clone(),
+            game_features: self.edit_game_features,
+            high_precision_timer: self.edit_high_precision_timer,
+            best_performance_visuals: self.edit_best_performance_visuals,
+            display_mode: self.edit_display_setting(),
+            audio: AudioSettings {
+                master_percent: self.edit_master_volume.trim().parse().ok(),
+                app_volumes: self.edit_app_volumes.clone(),
+            },
+            network: NetworkSettings {
+                pause_delivery_optimization: self.edit_pause_delivery_optimization,
+                bandwidth_limits: self.edit_bandwidth_limits.clone(),
+            },
+            storage: self.edit_storage,
+            rgb_scene: self.edit_rgb_scene.clone(),
+            obs: self.edit_obs.clone(),
+            captures: self.edit_captures.clone(),
+            do_not_disturb: self.edit_do_not_disturb,
+            mute_notification_sounds: self.edit_do_not_disturb && self.edit_mute_notification_sounds,
+            gpu: GpuSettings {
+                prefer_max_performance: self.edit_gpu_prefer_max,
+                nvidia_profile: Some(self.edit_nvidia_profile.trim().to_string()).filter(|name| !name.is_empty()),
+            },
+            system_tweaks: self.edit_system_tweaks.clone(),
+            keyboard: KeyboardSettings {
+                layout: self.edit_keyboard_layout.clone(),
+                block_windows_key: self.edit_block_windows_key,
+                suppress_sticky_keys: self.edit_suppress_sticky_keys,
+            },
+            rollback_on_failure: self.edit_rollback_on_failure,
+            alert_thresholds: AlertThresholds {
+                cpu_temp_c: self.edit_cpu_limit.trim().parse().ok(),
+                gpu_temp_c: self.edit_gpu_limit.trim().parse().ok(),
+            },
+            exclude_from_capture: self.edit_exclude_from_capture,
+            crosshair_tint: self.edit_tint,
+            crosshair_presets: self.edit_presets.clone(),
+            offset_presets: self.edit_offset_presets.clone(),
+            selected_offset_preset: self.edit_selected_offset_preset.clone(),
+            crosshair_params: self.edit_crosshair_params.clone(),
+            overlay_note: self.edit_overlay_note(),
+            hardware_hud: self.edit_hardware_hud,
+            skip_kill_confirmation: self.edit_skip_kill_confirmation,
+            favorite: self.edit_favorite,
+            anti_cheat_safe: self.edit_anti_cheat_safe,
+            archived: self.edit_archived,
+        }
+    }
+    
+    /// Whether the editor holds changes that haven't been saved
+    fn has_unsaved_edits(&self) -> bool {
+        self.edited_profile() != self.edit_baseline
+    }
+    
+    /// Store the editor's profile; false if it can't be saved (the status message says why)
+    fn save_edited_profile(&mut self) -> bool {
+        if self.edit_name.trim().is_empty() {
+            self.status_message = t!("status-empty-profile-name");
+            return false;
+        }
+        
+        let profile = self.edited_profile();
+        self.edit_baseline = profile.clone();
+        self.lint_results = profile_lint::lint_profile(&profile, &self.profiles)
+            .into_iter()
+            .map(|lint| (profile.name.clone(), lint))
+            .collect();
+        if let Some(index) = self.selected_profile_index {
+            self.profiles[index] = profile;
+            self.status_message = t!("status-profile-updated", profile = self.edit_name);
+        } else {
+            self.profiles.push(profile);
+            self.selected_profile_index = Some(self.profiles.len() - 1);
+            self.status_message = t!("status-profile-created", profile = self.edit_name);
+        }
+        
+        self.save_profiles_to_disk();
+        true
+    }
+    
+    /// Before the editor is replaced or the app exits: offer to save unsaved edits.
+    /// False if the user cancelled or saving failed, so the caller should stay put.
+    fn resolve_unsaved_edits(&mut self) -> bool {
+        if !self.has_unsaved_edits() {
+            return true;
+        }
+        let profile = if self.edit_name.trim().is_empty() { t!("unsaved-new-profile") } else { self.edit_name.clone() };
+        let choice = rfd::MessageDialog::new()
+            .set_title(t!("unsaved-changes-title"))
+            .set_description(t!("unsaved-changes-body", profile = profile))
+            .set_buttons(rfd::MessageButtons::YesNoCancel)
+            .show();
+        match choice {
+            rfd::MessageDialogResult::Yes => {
+                let saved = self.save_edited_profile();
+                if saved {
+                    self.update_tray();
+                }
+                saved
+            }
+            rfd::MessageDialogResult::No => true,
+            _ => false,
         }
     }
     
     fn get_selected_processes(&self) -> Vec<String> {
-        self.process_selection
+        let mut processes = self.process_selection
             .iter()
             .filter(|(_, &selected)| selected)
             .map(|(name, _)| name.clone())
-            .collect()
+            .collect::<Vec<_>>();
+        // Stable order, so saved files and unsaved-change checks don't depend on hashing
+        processes.sort();
+        processes
     }
     
     fn activate_profile_by_name(&mut self, name: &str) {
@@ -323,779 +913,6513 @@ impl GameOptimizer {
     }
     
     fn activate_current_profile(&mut self) {
+        self.set_paused(false);
         if let Some(index) = self.selected_profile_index {
             if let Some(profile) = self.profiles.get(index) {
                 let profile_name = profile.name.clone();
-                let processes = profile.processes_to_kill.clone();
-                let fan_max = profile.fan_speed_max;
-                let overlay_enabled = profile.overlay_enabled;
-                let image_path = profile.crosshair_image_path.clone();
-                let x_offset = profile.crosshair_x_offset;
-                let y_offset = profile.crosshair_y_offset;
-                
-                let report = kill_processes(&processes);
-                
-                let mut status_parts = Vec::new();
-                
-                if !report.killed.is_empty() {
-                    status_parts.push(format!("Killed: {}", report.killed.join(", ")));
-                }
-                if !report.not_found.is_empty() {
-                    status_parts.push(format!("Not running: {}", report.not_found.join(", ")));
-                }
-                if !report.blocklist_skipped.is_empty() {
-                    status_parts.push(format!("Protected: {}", report.blocklist_skipped.join(", ")));
+                let mut needs_admin = self.capabilities.blocked(profile);
+                // Missing admin rights are listed with needs_admin
+                let mut readiness = preflight::check(profile, &self.games, &Environment::detect(profile, &self.games)).problems;
+                readiness.retain(|problem| !matches!(problem, Problem::NeedsAdmin(_)));
+                let Activation { summary, kills, revert_errors } = self.activation.activate(profile, &self.power_plans, &self.openrgb, &self.obs);
+                // Closing usually only fails for apps that run as administrator
+                if !self.capabilities.elevated && kills.as_ref().is_some_and(|kills| !kills.failed.is_empty()) {
+                    needs_admin.push(AdminFeature::ElevatedProcesses);
                 }
                 
                 self.active_profile_name = Some(profile_name.clone());
+                self.active_crosshair_slot = 0;
+                self.activation_failed = summary.failed_count() > 0;
+                self.activation_report = Some(ActivationReport {
+                    summary: summary.clone(),
+                    kills,
+                    expanded: HashSet::new(),
+                    overlays: Vec::new(),
+                    needs_admin,
+                    readiness,
+                });
                 
-                if fan_max {
-                    status_parts.push("Fan: MAX".to_string());
+                self.timer_resolution = timer::query_timer_resolution().ok();
+                if self.activation.system.overlay.is_some() {
+                    self.overlay_error = None;
                 }
                 
-                // Handle crosshair overlay
-                // First, stop any existing overlay
-                if let Some(ref mut handle) = self.overlay_handle {
-                    handle.stop();
+                // The report panel lists the details
+                self.status_message = summary.headline();
+                self.notify_activation(&summary);
+                self.log_event(event_log::Entry::activation(&summary));
+                self.publish_plugin_event(PluginEvent::Activated {
+                    profile: profile_name.clone(),
+                    applied: summary.applied_count(),
+                    failed: summary.failed_count(),
+                    rolled_back: summary.rolled_back,
+                });
+                for error in revert_errors {
+                    self.status_message.push_str(&format!(" | {}", t!("status-previous-profile-error", error = error)));
                 }
-                self.overlay_handle = None;
                 
-                // Start new overlay if enabled and image path exists
-                if overlay_enabled {
-                    if let Some(ref path) = image_path {
-                        match crosshair_overlay::start_overlay(path.clone(), x_offset, y_offset) {
-                            Ok(handle) => {
-                                self.overlay_handle = Some(handle);
-                                status_parts.push("🎯 Crosshair ON".to_string());
-                            }
-                            Err(e) => {
-                                status_parts.push(format!("Crosshair error: {}", e));
-                            }
-                        }
-                    } else {
-                        status_parts.push("Crosshair: No image".to_string());
-                    }
-                }
-                
-                if status_parts.is_empty() {
-                    self.status_message = format!("✅ Profile '{}' activated!", profile_name);
+                let rolled_back = summary.rolled_back;
+                if rolled_back {
+                    self.active_profile_name = None;
+                    self.last_activation = None;
+                    self.publish_activation(ActivationEvent::Deactivated);
                 } else {
-                    self.status_message = format!("✅ Profile '{}' activated! {}", profile_name, status_parts.join(" | "));
+                    self.record_profile_use(&profile_name);
+                    self.publish_activation(ActivationEvent::Activated { profile: profile_name });
+                    self.last_activation = Some(summary);
                 }
                 
                 self.refresh_running_processes();
+                if !rolled_back {
+                    let profile = &self.profiles[index];
+                    let overlays = overlay_conflicts::detect(profile, self.running_processes.iter().map(|p| p.name.as_str()));
+                    if let Some(ref mut report) = self.activation_report {
+                        report.overlays = overlays;
+                    }
+                }
                 
                 // Update tray with new active profile
                 self.update_tray();
             }
         } else {
-            self.status_message = "⚠️ No profile selected to activate".to_string();
+            self.status_message = t!("status-no-profile-selected");
         }
     }
     
-    fn deactivate_profile(&mut self) {
-        self.active_profile_name = None;
-        
-        // Stop overlay when deactivating
-        if let Some(ref mut handle) = self.overlay_handle {
-            handle.stop();
+    /// Run the one-click fix for an overlay from the activation report
+    fn fix_overlay(&mut self, injector: OverlayInjector) {
+        let Some(fix) = injector.fix() else { return };
+        match fix {
+            OverlayFix::CloseProcesses(names) => {
+                let names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+                let report = process::kill_processes_with(self.activation.processes(), &names);
+                let still_running: Vec<&str> = report.failed.iter().chain(&report.timed_out).chain(&report.not_started).map(String::as_str).collect();
+                if !still_running.is_empty() {
+                    self.status_message = t!("status-overlay-close-failed", overlay = injector, processes = still_running.join(", "));
+                    return;
+                }
+                self.status_message = t!("status-overlay-closed", overlay = injector);
+                self.refresh_running_processes();
+            }
+            OverlayFix::TurnOffGameBar => {
+                let Some(name) = self.active_profile_name.clone() else { return };
+                let Some(profile) = self.profiles.iter_mut().find(|p| p.name == name) else { return };
+                profile.game_features.game_bar = Some(false);
+                if self.selected_profile_index.and_then(|index| self.profiles.get(index)).is_some_and(|p| p.name == name) {
+                    self.edit_game_features.game_bar = Some(false);
+                    self.edit_baseline.game_features.game_bar = Some(false);
+                }
+                if !self.save_profiles_to_disk() {
+                    return;
+                }
+                self.status_message = t!("status-overlay-game-bar-off", profile = name);
+            }
+        }
+        if let Some(ref mut report) = self.activation_report {
+            report.overlays.retain(|&other| other != injector);
         }
-        self.overlay_handle = None;
-        
-        self.status_message = "Profile deactivated".to_string();
-        self.update_tray();
     }
     
-    /// Update the live crosshair overlay with new offsets (restarts if running)
-    fn update_live_overlay(&mut self) {
-        // Only update if we have an active overlay
-        if self.overlay_handle.is_some() {
-            // Stop existing overlay
-            if let Some(ref handle) = self.overlay_handle {
-                handle.stop();
-            }
-            self.overlay_handle = None;
-            
-            // Restart with new offsets if we have an image
-            if self.edit_overlay_enabled {
-                if let Some(ref path) = self.edit_image_path {
-                    let x_offset: i32 = self.edit_x_offset.parse().unwrap_or(0);
-                    let y_offset: i32 = self.edit_y_offset.parse().unwrap_or(0);
-                    
-                    match crosshair_overlay::start_overlay(path.clone(), x_offset, y_offset) {
-                        Ok(handle) => {
-                            self.overlay_handle = Some(handle);
-                        }
-                        Err(e) => {
-                            self.status_message = format!("Crosshair error: {}", e);
-                        }
-                    }
-                }
+    /// Raise the notifications for an activation result
+    fn notify_activation(&self, summary: &ActivationSummary) {
+        if summary.rolled_back {
+            toast_notifier::notify(
+                &self.toast_config,
+                ToastCategory::ProfileActivated,
+                &t!("toast-not-activated-title", profile = summary.profile),
+                &t!("toast-not-activated-body", step = summary.failed_step().unwrap_or_default()),
+            );
+        } else {
+            let body = if self.tray_activation {
+                // The tray has no status bar, so this one notification is all the feedback
+                summary.tray_notification(self.activation_report.as_ref().and_then(|report| report.kills.as_ref()))
+            } else {
+                summary.tray_status()
+            };
+            toast_notifier::notify(
+                &self.toast_config,
+                ToastCategory::ProfileActivated,
+                &t!("toast-activated-title", profile = summary.profile),
+                &body,
+            );
+        }
+        
+        if self.activation.system.processes_killed > 0 && !self.tray_activation {
+            toast_notifier::notify(
+                &self.toast_config,
+                ToastCategory::ProcessesKilled,
+                &t!("toast-processes-closed-title"),
+                &t!("toast-processes-closed-body", count = self.activation.system.processes_killed, profile = summary.profile),
+            );
+        }
+        
+        for result in &summary.results {
+            if let ("Crosshair", StepStatus::Failed(ref error)) = (result.name, &result.status) {
+                toast_notifier::notify(&self.toast_config, ToastCategory::OverlayError, &t!("toast-crosshair-failed-title"), error);
             }
         }
     }
     
-    fn update_tray(&mut self) {
-        // Update tray with current profiles
-        if let Some(ref mut tray) = self.tray_manager {
-            tray.update_profiles(self.profiles.clone());
-            tray.set_active_profile(self.active_profile_name.clone());
+    /// Record the session and forward a profile lifecycle event to the enabled integrations
+    fn publish_activation(&mut self, event: ActivationEvent) {
+        self.finish_session();
+        if let ActivationEvent::Activated { ref profile } = event {
+            self.session = Some(SessionRecorder::start(profile, self.activation.system.processes_killed));
+        }
+        
+        if let Some(ref presence) = self.discord_presence {
+            presence.publish(event);
         }
     }
     
-    fn toggle_flyout(&mut self) {
-        if let Some(ref mut tray) = self.tray_manager {
-            if tray.is_flyout_visible() {
-                tray.hide_flyout();
-            } else {
-                if let Err(e) = tray.show_flyout() {
-                    eprintln!("[GUI] Failed to show flyout: {}", e);
-                }
+    /// End the running session (if any) and append it to sessions.json
+    fn finish_session(&mut self) {
+        let Some(recorder) = self.session.take() else {
+            return;
+        };
+        
+        let session = recorder.finish();
+        info!(
+            "[Sessions] {} ({}): {}",
+            session.profile,
+            session.title(),
+            sessions::format_duration(session.duration_secs)
+        );
+        self.sessions.push(session);
+        if let Some(ref data_dir) = self.data_dir {
+            if let Err(e) = sessions::save_sessions(&self.sessions, data_dir) {
+                warn!("[Sessions] {}", e);
             }
         }
     }
-}
-
-impl Application for GameOptimizer {
-    type Executor = executor::Default;
-    type Message = Message;
-    type Theme = Theme;
-    type Flags = ();
-
-    fn new(_flags: ()) -> (Self, Command<Message>) {
-        let data_dir = get_data_directory().ok();
-        let mut app = GameOptimizer {
-            profiles: Vec::new(),
-            selected_profile_index: None,
-            edit_name: String::new(),
-            edit_x_offset: "0".to_string(),
-            edit_y_offset: "0".to_string(),
-            edit_image_path: None,
-            edit_overlay_enabled: false,
-            edit_fan_speed_max: false,
-            process_selection: HashMap::new(),
-            running_processes: Vec::new(),
-            process_filter: String::new(),
-            status_message: "Welcome to Gaming Optimizer".to_string(),
-            data_dir,
-            active_profile_name: None,
-            overlay_handle: None,
-            tray_manager: None,  // Will be set by run() via Flags if we change approach
+    
+    /// Measure the baseline for the selected profile; it is activated once that is done
+    fn start_benchmark(&mut self) {
+        if self.benchmark.is_some() {
+            self.status_message = t!("status-benchmark-running");
+            return;
+        }
+        let Some(profile) = self.selected_profile_index.and_then(|i| self.profiles.get(i)) else {
+            self.status_message = t!("status-benchmark-no-profile");
+            return;
         };
-        app.load_profiles_from_disk();
-        app.refresh_running_processes();
+        let name = profile.name.clone();
         
-        // Create tray manager on main thread (inside iced's new)
-        let app_config = crate::config::load_config();
-        match TrayFlyoutManager::new_with_channels(app.profiles.clone(), app_config.active_profile) {
-            Ok((tray, event_rx, menu_rx, profile_rx)) => {
-                // Store the exit menu ID
-                if let Ok(mut guard) = MENU_EXIT_ID.lock() {
-                    *guard = Some(tray.menu_item_exit.clone());
-                }
-                // Store channels in globals
-                if let Ok(mut guard) = TRAY_EVENT_RX.lock() {
-                    *guard = Some(event_rx);
-                }
-                if let Ok(mut guard) = MENU_EVENT_RX.lock() {
-                    *guard = Some(menu_rx);
+        // The baseline has to be taken without any profile's changes
+        if self.active_profile_name.is_some() {
+            self.deactivate_profile();
+        }
+        self.benchmark = Some(BenchmarkRun::start(&name));
+        self.status_message = t!("status-benchmark-baseline", profile = name);
+    }
+    
+    /// Advance the running benchmark: activate after the baseline, store the result at the end
+    fn poll_benchmark(&mut self) {
+        let Some(ref mut run) = self.benchmark else {
+            return;
+        };
+        
+        // Deactivating or switching profiles while settling would skew the second measurement
+        if run.is_settling() && self.active_profile_name.as_deref() != Some(run.profile()) {
+            self.benchmark = None;
+            self.status_message = t!("status-benchmark-cancelled");
+            return;
+        }
+        
+        match run.poll() {
+            Ok(None) => {}
+            Ok(Some(BenchmarkStage::BaselineTaken)) => {
+                let name = run.profile().to_string();
+                self.activate_profile_by_name(&name);
+                if self.active_profile_name.as_deref() == Some(name.as_str()) {
+                    self.status_message = t!(
+                        "status-benchmark-settling",
+                        profile = name,
+                        minutes = benchmark::SETTLE_SECS / 60
+                    );
                 }
-                if let Ok(mut guard) = FLYOUT_PROFILE_RX.lock() {
-                    *guard = Some(profile_rx);
+            }
+            Ok(Some(BenchmarkStage::Finished(result))) => {
+                self.benchmark = None;
+                self.status_message = t!("status-benchmark-finished", profile = result.profile, result = result.comparison_lines().join(" | "));
+                self.benchmarks.push(result);
+                if let Some(ref data_dir) = self.data_dir {
+                    if let Err(e) = benchmark::save_benchmarks(&self.benchmarks, data_dir) {
+                        self.status_message = t!("status-benchmark-save-failed", error = e);
+                    }
                 }
-                app.tray_manager = Some(tray);
-                println!("[GUI] Tray manager created successfully");
             }
             Err(e) => {
-                eprintln!("[GUI] Failed to create tray: {}", e);
+                self.benchmark = None;
+                self.status_message = t!("status-benchmark-failed", error = e);
             }
         }
-        
-        (app, Command::none())
-    }
-
-    fn title(&self) -> String {
-        String::from("Gaming Optimizer - Profile Manager")
     }
-
-    fn subscription(&self) -> Subscription<Message> {
-        // Poll for tray events (faster polling for responsive click detection)
-        struct TrayPoller;
+    
+    /// Save the Discord settings and restart (or stop) the presence with them
+    fn save_discord_settings(&mut self) {
+        self.discord_config.application_id = self.discord_config.application_id.trim().to_string();
+        let mut app_config = load_config();
+        app_config.discord = self.discord_config.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
         
-        iced::subscription::unfold(
-            std::any::TypeId::of::<TrayPoller>(),
-            (),
-            |_| async move {
-                std::thread::sleep(Duration::from_millis(50)); // 50ms for responsive clicks
-                (Message::TrayTick, ())
+        // Dropping the old presence closes its pipe, which clears it in Discord
+        self.discord_presence = None;
+        if self.discord_config.enabled && !self.discord_config.application_id.is_empty() {
+            let presence = DiscordPresence::spawn(self.discord_config.application_id.clone());
+            if let Some(ref profile) = self.active_profile_name {
+                presence.publish(ActivationEvent::Activated { profile: profile.clone() });
             }
-        )
+            self.discord_presence = Some(presence);
+        }
     }
-
-    fn update(&mut self, message: Message) -> Command<Message> {
-        match message {
-            Message::TrayTick => {
-                // Process tray events (clicks, menu, flyout profile selection)
-                match process_tray_events() {
-                    TrayAction::ShowFlyout => {
-                        self.toggle_flyout();
-                    }
-                    TrayAction::ProfileSelected(name) => {
-                        return self.update(Message::TrayProfileSelected(name));
-                    }
-                    TrayAction::Exit => {
-                        return self.update(Message::TrayExit);
-                    }
-                    _ => {}
+    
+    /// Save the Event Log setting; turning it on registers the source when running as
+    /// administrator, so Event Viewer shows the entries' text
+    fn save_event_log_settings(&mut self) {
+        let mut app_config = load_config();
+        app_config.event_log = self.event_log.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+            return;
+        }
+        
+        if self.event_log.enabled && !event_log::is_registered() {
+            self.status_message = if self.capabilities.elevated {
+                match event_log::register() {
+                    Ok(()) => t!("status-event-log-registered"),
+                    Err(e) => t!("status-event-log-register-failed", error = e),
                 }
+            } else {
+                t!("status-event-log-not-registered")
+            };
+        }
+    }
+    
+    /// Rescan the plugins folder and reload the enabled plugins (script edits take effect this
+    /// way, and programs that failed too often get another chance)
+    fn restart_plugins(&mut self) {
+        let Some(ref data_dir) = self.data_dir else {
+            return;
+        };
+        self.plugin_files = plugins::list_plugins(&data_dir.join(plugins::PLUGINS_FOLDER));
+        self.plugins = PluginHost::start(data_dir, &self.plugin_config, &self.cli_tx);
+    }
+    
+    /// Hand an event to the enabled plugins
+    fn publish_plugin_event(&self, event: PluginEvent) {
+        if let Some(ref plugins) = self.plugins {
+            let context = SystemContext { active_profile: self.active_profile_name.clone(), hardware: self.hardware.clone() };
+            plugins.publish(event, context);
+        }
+    }
+    
+    /// Write an activation entry to the Windows Event Log, if that's turned on
+    fn log_event(&self, entry: event_log::Entry) {
+        if self.event_log.enabled {
+            if let Err(e) = event_log::write(&entry) {
+                warn!("[EventLog] {}", e);
             }
-            
-            Message::TrayProfileSelected(name) => {
-                self.activate_profile_by_name(&name);
-            }
-            
-            Message::TrayDeactivate => {
-                self.deactivate_profile();
-            }
-            
-            Message::TrayExit => {
-                // Clean exit
-                std::process::exit(0);
-            }
-            
-            Message::ProfileNameChanged(name) => {
-                self.edit_name = name;
-            }
-            
-            Message::ProfileSelected(index) => {
-                self.load_profile_to_edit(index);
-                self.status_message = format!("Editing profile: {}", self.edit_name);
-            }
-            
-            Message::NewProfile => {
-                self.clear_edit_form();
-                self.status_message = "Creating new profile".to_string();
-            }
-            
-            Message::SaveProfile => {
-                if self.edit_name.trim().is_empty() {
-                    self.status_message = "❌ Error: Profile name cannot be empty".to_string();
-                    return Command::none();
-                }
-                
-                let x_offset = self.edit_x_offset.parse().unwrap_or(0);
-                let y_offset = self.edit_y_offset.parse().unwrap_or(0);
-                
-                let profile = Profile {
-                    name: self.edit_name.clone(),
-                    processes_to_kill: self.get_selected_processes(),
-                    crosshair_image_path: self.edit_image_path.clone(),
-                    crosshair_x_offset: x_offset,
-                    crosshair_y_offset: y_offset,
-                    overlay_enabled: self.edit_overlay_enabled,
-                    fan_speed_max: self.edit_fan_speed_max,
-                };
-                
-                if let Some(index) = self.selected_profile_index {
-                    self.profiles[index] = profile;
-                    self.status_message = format!("✅ Updated profile: {}", self.edit_name);
-                } else {
-                    self.profiles.push(profile);
-                    self.selected_profile_index = Some(self.profiles.len() - 1);
-                    self.status_message = format!("✅ Created profile: {}", self.edit_name);
+        }
+    }
+    
+    /// Save the update channel and interval
+    fn save_update_settings(&mut self) {
+        match self.update_interval.trim().parse::<u32>().ok().filter(|hours| *hours > 0) {
+            Some(hours) => self.update_config.interval_hours = hours,
+            None => self.update_interval = self.update_config.interval_hours.to_string(),
+        }
+        let mut app_config = load_config();
+        app_config.updates = self.update_config.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
+    }
+    
+    fn start_update_check(&mut self) {
+        if self.update_check.is_some() {
+            return;
+        }
+        self.update_config.last_check = sessions::unix_time();
+        let mut app_config = load_config();
+        app_config.updates.last_check = self.update_config.last_check;
+        let _ = save_config(&app_config);
+        self.update_check = Some(updater::spawn_check(self.update_config.channel));
+    }
+    
+    /// Run due checks and pick up check/download results
+    fn poll_updates(&mut self) {
+        if self.update_config.check_due(sessions::unix_time()) {
+            self.start_update_check();
+        }
+        
+        if let Some(result) = self.update_check.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.update_check = None;
+            match result {
+                Ok(Some(release)) => {
+                    // Only tell the user once per version
+                    if self.available_update.as_ref().map(|known| &known.version) != Some(&release.version) {
+                        toast_notifier::notify(
+                            &self.toast_config,
+                            ToastCategory::UpdateAvailable,
+                            &t!("toast-update-title"),
+                            &t!("toast-update-body", version = release.tag),
+                        );
+                        self.status_message = t!("status-update-available", version = release.tag);
+                    }
+                    self.available_update = Some(release);
                 }
-                
-                self.save_profiles_to_disk();
-                self.update_tray();
+                Ok(None) => self.available_update = None,
+                Err(e) => self.status_message = t!("status-update-check-failed", error = e),
             }
-            
-            Message::DeleteProfile => {
-                if let Some(index) = self.selected_profile_index {
-                    let name = self.profiles[index].name.clone();
-                    self.profiles.remove(index);
-                    self.clear_edit_form();
-                    self.save_profiles_to_disk();
-                    self.update_tray();
-                    self.status_message = format!("🗑️ Deleted profile: {}", name);
+        }
+        
+        if let Some(result) = self.update_download.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.update_download = None;
+            match result.and_then(|path| updater::install(&path)) {
+                // The installer takes over; exit like the tray's Exit does
+                Ok(()) => {
+                    let _ = self.update(Message::TrayExit);
                 }
+                Err(e) => self.status_message = t!("status-update-failed", error = e),
             }
-            
-            Message::ActivateProfile => {
-                self.activate_current_profile();
+        }
+    }
+    
+    /// Whether exiting should undo the active profile (None = the user cancelled the exit)
+    fn confirm_exit(&self) -> Option<bool> {
+        dialogs::confirm_exit(self.exit_behavior, self.active_profile_name.as_deref())
+    }
+    
+    /// Clean exit - don't leave the fans pinned at max
+    fn exit(&mut self, deactivate: bool) -> ! {
+        // The session is saved as it was before shutting down, so it can be resumed
+        self.remember_session();
+        let mut app_config = load_config();
+        app_config.window = self.window_layout;
+        if let Err(e) = save_config(&app_config) {
+            warn!("[GUI] Failed to save the window layout: {}", e);
+        }
+        self.activation.shutdown(deactivate);
+        self.alert_balloon.clear();
+        self.finish_session();
+        std::process::exit(0);
+    }
+    
+    /// Activate a profile picked in the window or the tray. When a step would fail on this PC
+    /// (preflight) or, with the safety setting on, running apps would be closed, they are listed
+    /// first and nothing happens until the user confirms.
+    fn request_activation(&mut self, name: &str) -> Command<Message> {
+        let selected = self.selected_profile_index.and_then(|index| self.profiles.get(index));
+        // Activating another profile loads it into the editor
+        if selected.is_none_or(|profile| profile.name != name) && !self.resolve_unsaved_edits() {
+            return Command::none();
+        }
+        let Some(profile) = self.profiles.iter().find(|p| p.name == name) else { return Command::none() };
+        let problems: Vec<Problem> = preflight::check(profile, &self.games, &Environment::detect(profile, &self.games))
+            .blocking()
+            .cloned()
+            .collect();
+        let apps = if self.safety_config.confirm_kill && !profile.skip_kill_confirmation {
+            process::kill_candidates_with(self.activation.processes(), &profile.processes_to_kill)
+        } else {
+            Vec::new()
+        };
+        if !apps.is_empty() || !problems.is_empty() {
+            self.process_icons.request(apps.iter().filter_map(|app| app.exe.clone()));
+            self.kill_confirmation = Some(KillConfirmation { profile: name.to_string(), apps, problems, dont_ask_again: false });
+            // A tray click may come while the window is hidden
+            return show_main_window();
+        }
+        self.activate_selected_or_named(name);
+        Command::none()
+    }
+    
+    /// Activate `name`, keeping unsaved edits if it's the profile open in the editor
+    fn activate_selected_or_named(&mut self, name: &str) {
+        let selected = self.selected_profile_index.and_then(|index| self.profiles.get(index));
+        if selected.is_some_and(|profile| profile.name == name) {
+            self.activate_current_profile();
+        } else {
+            self.activate_profile_by_name(name);
+        }
+    }
+    
+    /// Ask, then download the update (installed by `poll_updates` once it's there)
+    fn install_update(&mut self) {
+        let Some(release) = self.available_update.clone() else { return };
+        let Some(asset) = release.asset else {
+            // Nothing installable attached; let the user grab it from the release page
+            let _ = open::that(&release.page_url);
+            return;
+        };
+        
+        let description = match self.active_profile_name {
+            Some(ref profile) => t!("install-update-body-active", version = release.tag, profile = profile),
+            None => t!("install-update-body", version = release.tag),
+        };
+        let confirmed = rfd::MessageDialog::new()
+            .set_title(t!("install-update-title"))
+            .set_description(description)
+            .set_buttons(rfd::MessageButtons::YesNo)
+            .show();
+        if confirmed != rfd::MessageDialogResult::Yes {
+            return;
+        }
+        
+        self.status_message = t!("status-update-downloading", file = asset.name);
+        self.update_download = Some(updater::spawn_download(asset));
+    }
+    
+    /// Re-register the hotkeys with the edited binding; the old binding stays if the new one fails
+    fn save_hotkey_settings(&mut self) {
+        let config = HotkeyConfig { cycle_crosshair: self.hotkey_binding.trim().to_string(), ..self.hotkey_config.clone() };
+        // The running thread has to let go of its keys before they can be registered again
+        self.hotkeys = HotkeyThread::disabled();
+        match hotkey_thread::spawn_hotkey_thread(&config) {
+            Ok(thread) => {
+                // Registering checks the binding; a paused optimizer still keeps its keys free
+                self.hotkeys = if self.paused { HotkeyThread::disabled() } else { thread };
+                self.hotkey_config = config;
+                self.hotkey_binding = self.hotkey_config.cycle_crosshair.clone();
+                let mut app_config = load_config();
+                app_config.hotkeys = self.hotkey_config.clone();
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
             }
-            
-            Message::ProcessToggled(process, enabled) => {
-                self.process_selection.insert(process, enabled);
+            Err(e) => {
+                self.status_message = t!("status-hotkey-failed", error = e);
+                self.hotkeys = hotkey_thread::spawn_hotkey_thread(&self.hotkey_config).unwrap_or_else(|_| HotkeyThread::disabled());
             }
-            
-            Message::RefreshProcesses => {
-                self.refresh_running_processes();
-                self.status_message = format!("🔄 Refreshed: {} processes found", self.running_processes.len());
+        }
+    }
+    
+    /// Restart controller polling with the edited combos; the old combos stay if one is invalid
+    fn save_gamepad_settings(&mut self, enabled: bool) {
+        let config = GamepadConfig {
+            enabled,
+            toggle_overlay: self.gamepad_toggle_overlay.trim().to_string(),
+            cycle_crosshair: self.gamepad_cycle_crosshair.trim().to_string(),
+        };
+        match gamepad::spawn_gamepad_thread(&config) {
+            Ok(thread) => {
+                self.gamepad = if self.paused { GamepadThread::disabled() } else { thread };
+                self.gamepad_toggle_overlay = config.toggle_overlay.clone();
+                self.gamepad_cycle_crosshair = config.cycle_crosshair.clone();
+                self.gamepad_config = config;
+                let mut app_config = load_config();
+                app_config.gamepad = self.gamepad_config.clone();
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
             }
-            
-            Message::ProcessFilterChanged(filter) => {
-                self.process_filter = filter;
+            Err(e) => self.status_message = t!("status-gamepad-failed", error = e),
+        }
+    }
+    
+    /// Store the tray click bindings and apply them to the running tray
+    fn save_tray_click_settings(&mut self) {
+        if let Some(ref mut tray) = self.tray_manager {
+            tray.set_click_config(self.tray_clicks);
+        }
+        let mut app_config = load_config();
+        app_config.tray_clicks = self.tray_clicks;
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
+    }
+    
+    /// Store the tooltip template and show it on the running tray
+    fn save_tray_tooltip_settings(&mut self) {
+        if let Some(ref mut tray) = self.tray_manager {
+            tray.set_tooltip_config(self.tray_tooltip.clone());
+            tray.set_hardware(self.hardware.as_ref());
+        }
+        let mut app_config = load_config();
+        app_config.tray_tooltip = self.tray_tooltip.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
+    }
+    
+    fn save_theme_settings(&mut self) {
+        let mut app_config = load_config();
+        app_config.theme = self.theme_config;
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
+    }
+    
+    /// Pick up a dark/light switch in Windows while following the system setting
+    fn refresh_system_theme(&mut self) {
+        if self.theme_config.mode != ThemeMode::System || self.system_theme_checked.elapsed() < SYSTEM_THEME_POLL {
+            return;
+        }
+        self.system_theme_checked = Instant::now();
+        self.system_dark = styles::system_prefers_dark();
+    }
+    
+    fn save_sync_folder(&mut self) {
+        let mut app_config = load_config();
+        app_config.sync_folder = self.sync_folder.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
+    }
+    
+    /// Pick up the profile file after it was changed outside the app; the editor stays on its profile
+    fn reload_profiles_from_disk(&mut self) {
+        let Some(ref data_dir) = self.data_dir else {
+            return;
+        };
+        let loaded = match load_profiles_recovering(data_dir) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.status_message = t!("status-profiles-load-failed", error = e);
+                return;
             }
-            
-            Message::CrosshairOffsetXChanged(value) => {
-                self.edit_x_offset = value;
+        };
+        // Our own saves come back as change events too
+        if loaded.profiles == self.profiles && loaded.quarantined.is_empty() {
+            return;
+        }
+        
+        let selected = self.selected_profile_index.and_then(|index| self.profiles.get(index)).map(|p| p.name.clone());
+        self.profiles = loaded.profiles;
+        self.selected_profile_index = selected.and_then(|name| self.profiles.iter().position(|p| p.name == name));
+        self.status_message = quarantine_status(&loaded.quarantined)
+            .unwrap_or_else(|| t!("status-profiles-reloaded", count = self.profiles.len()));
+        self.update_tray();
+    }
+    
+    /// Apply config.json after it was changed outside the app. Settings backed by a running
+    /// thread or server (hotkeys, monitoring, alerts, remote API, Discord, updates) wait for the
+    /// next start.
+    fn reload_config_from_disk(&mut self) {
+        let app_config = match read_config() {
+            Ok(config) => config,
+            Err(e) => {
+                self.status_message = t!("status-config-reload-failed", error = e);
+                return;
             }
-            
-            Message::CrosshairOffsetYChanged(value) => {
-                self.edit_y_offset = value;
+        };
+        
+        let changed = app_config.log_level != self.log_level
+            || app_config.language != self.language
+            || app_config.toasts != self.toast_config
+            || app_config.theme != self.theme_config
+            || app_config.exit_behavior != self.exit_behavior
+            || app_config.resume_last_session != self.resume_last_session
+            || app_config.auto_activate_games != self.auto_activate_games
+            || app_config.safety != self.safety_config
+            || app_config.tray_clicks != self.tray_clicks
+            || app_config.tray_tooltip != self.tray_tooltip
+            || app_config.sync_folder != self.sync_folder
+            || app_config.event_log != self.event_log
+            || app_config.plugins != self.plugin_config
+            || app_config.openrgb != self.openrgb
+            || app_config.obs != self.obs
+            || app_config.gamepad != self.gamepad_config;
+        
+        logging::set_level(app_config.log_level);
+        self.log_level = app_config.log_level;
+        i18n::set_language(app_config.language.as_deref());
+        self.language = app_config.language;
+        self.toast_config = app_config.toasts;
+        self.theme_config = app_config.theme;
+        self.exit_behavior = app_config.exit_behavior;
+        self.resume_last_session = app_config.resume_last_session;
+        self.set_auto_activate_games(app_config.auto_activate_games);
+        process::set_user_protected(&app_config.safety.protected_processes);
+        self.safety_config = app_config.safety;
+        self.tray_clicks = app_config.tray_clicks;
+        self.tray_tooltip = app_config.tray_tooltip;
+        if let Some(ref mut tray) = self.tray_manager {
+            tray.set_click_config(self.tray_clicks);
+            tray.set_tooltip_config(self.tray_tooltip.clone());
+            tray.set_hardware(self.hardware.as_ref());
+        }
+        self.sync_folder = app_config.sync_folder;
+        self.event_log = app_config.event_log;
+        if app_config.plugins != self.plugin_config {
+            self.plugin_config = app_config.plugins;
+            self.restart_plugins();
+        }
+        if app_config.openrgb != self.openrgb {
+            self.openrgb_host = app_config.openrgb.host.clone();
+            self.openrgb_port = app_config.openrgb.port.to_string();
+            self.openrgb = app_config.openrgb;
+        }
+        if app_config.gamepad != self.gamepad_config {
+            self.gamepad_toggle_overlay = app_config.gamepad.toggle_overlay.clone();
+            self.gamepad_cycle_crosshair = app_config.gamepad.cycle_crosshair.clone();
+            self.gamepad_config = app_config.gamepad;
+            self.gamepad = if self.paused {
+                GamepadThread::disabled()
+            } else {
+                gamepad::spawn_gamepad_thread(&self.gamepad_config).unwrap_or_else(|e| {
+                    warn!("[Gamepad] {}", e);
+                    GamepadThread::disabled()
+                })
+            };
+        }
+        if app_config.obs != self.obs {
+            self.obs_host = app_config.obs.host.clone();
+            self.obs_port = app_config.obs.port.to_string();
+            self.obs_password = app_config.obs.password.clone();
+            self.obs = app_config.obs;
+        }
+        self.profile_usage = app_config.profile_usage;
+        self.update_tray();
+        
+        // Our own saves come back as change events too
+        if changed {
+            self.status_message = t!("status-config-reloaded");
+        }
+    }
+    
+    /// Sync the profile file with the sync folder, reloading the list if the other side was newer
+    fn sync_profiles_now(&mut self) {
+        let (Some(data_dir), Some(sync_dir)) = (self.data_dir.clone(), self.sync_folder.clone()) else {
+            return;
+        };
+        match sync_profiles(&data_dir, &sync_dir) {
+            Ok(SyncResult::Pulled) => {
+                self.load_profiles_from_disk();
+                self.status_message = t!("status-sync-pulled", count = self.profiles.len(), folder = sync_dir.display());
+                if let Some(ref mut tray) = self.tray_manager {
+                    tray.update_profiles(self.profiles.clone());
+                }
             }
-            
-            Message::CrosshairMoveUp => {
-                let current: i32 = self.edit_y_offset.parse().unwrap_or(0);
-                self.edit_y_offset = (current - 1).to_string();
-                self.update_live_overlay();
+            Ok(SyncResult::Pushed) => self.status_message = t!("status-sync-pushed", folder = sync_dir.display()),
+            Ok(SyncResult::UpToDate) => {}
+            Err(e) => self.status_message = t!("status-sync-failed", error = e),
+        }
+    }
+    
+    /// Save profiles, settings and crosshair images to a zip picked by the user
+    fn export_backup(&mut self) {
+        let Some(data_dir) = self.data_dir.clone() else {
+            return;
+        };
+        let Some(target) = rfd::FileDialog::new()
+            .set_title(t!("backup-export-dialog-title"))
+            .add_filter("Zip", &["zip"])
+            .set_file_name("GamingOptimizer-backup.zip")
+            .save_file()
+        else {
+            return;
+        };
+        self.status_message = match backup::export(&data_dir, &target) {
+            Ok(backup) => t!(
+                "status-backup-exported",
+                path = target.display(),
+                profiles = backup.profiles.len(),
+                images = backup.image_count()
+            ),
+            Err(e) => t!("status-backup-export-failed", error = e),
+        };
+    }
+    
+    /// Tick `name` in the process selector, using the spelling of a running or already listed
+    /// process so its row gets ticked
+    fn select_process(&mut self, name: &str) {
+        let known = self.running_processes.iter().map(|p| &p.name).chain(self.process_selection.keys())
+            .find(|known| known.eq_ignore_ascii_case(name))
+            .cloned();
+        self.process_selection.insert(known.unwrap_or_else(|| name.to_string()), true);
+    }
+    
+    /// Tick the processes of a kill list kept for another tool (text, CSV, Razer Cortex XML)
+    fn import_kill_list(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(t!("kill-list-import-dialog-title"))
+            .add_filter("Kill Lists", &kill_list_import::EXTENSIONS)
+            .add_filter("All Files", &["*"])
+            .pick_file()
+        else {
+            return;
+        };
+        let import = match kill_list_import::import_file(&path) {
+            Ok(import) => import,
+            Err(e) => {
+                self.status_message = t!("status-kill-list-import-failed", error = e);
+                return;
             }
-            
-            Message::CrosshairMoveDown => {
-                let current: i32 = self.edit_y_offset.parse().unwrap_or(0);
-                self.edit_y_offset = (current + 1).to_string();
-                self.update_live_overlay();
+        };
+        
+        for name in &import.processes {
+            self.select_process(name);
+        }
+        self.status_message = if import.protected.is_empty() {
+            t!("status-kill-list-imported", count = import.processes.len())
+        } else {
+            t!(
+                "status-kill-list-imported-protected",
+                count = import.processes.len(),
+                protected = import.protected.join(", ")
+            )
+        };
+    }
+    
+    /// Restore a backup zip, asking first what to do with profiles that already exist
+    fn import_backup(&mut self) {
+        let Some(data_dir) = self.data_dir.clone() else {
+            return;
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(t!("backup-import-dialog-title"))
+            .add_filter("Zip", &["zip"])
+            .pick_file()
+        else {
+            return;
+        };
+        let backup = match Backup::read(&path) {
+            Ok(backup) => backup,
+            Err(e) => {
+                self.status_message = t!("status-backup-restore-failed", error = e);
+                return;
             }
-            
-            Message::CrosshairMoveLeft => {
-                let current: i32 = self.edit_x_offset.parse().unwrap_or(0);
-                self.edit_x_offset = (current - 1).to_string();
-                self.update_live_overlay();
+        };
+        
+        let conflicts = backup.conflicts(&self.profiles);
+        let dialog = rfd::MessageDialog::new().set_title(t!("backup-import-title"));
+        let replace_existing = if conflicts.is_empty() {
+            let confirmed = dialog
+                .set_description(t!("backup-import-body", count = backup.profiles.len()))
+                .set_buttons(rfd::MessageButtons::YesNo)
+                .show();
+            if confirmed != rfd::MessageDialogResult::Yes {
+                return;
             }
-            
-            Message::CrosshairMoveRight => {
-                let current: i32 = self.edit_x_offset.parse().unwrap_or(0);
-                self.edit_x_offset = (current + 1).to_string();
-                self.update_live_overlay();
+            false
+        } else {
+            let choice = dialog
+                .set_description(t!("backup-import-conflicts", count = conflicts.len(), names = conflicts.join(", ")))
+                .set_buttons(rfd::MessageButtons::YesNoCancel)
+                .show();
+            match choice {
+                rfd::MessageDialogResult::Yes => true,
+                rfd::MessageDialogResult::No => false,
+                _ => return,
             }
-            
-            Message::CrosshairCenter => {
-                self.edit_x_offset = "0".to_string();
-                self.edit_y_offset = "0".to_string();
-                self.status_message = "Crosshair centered".to_string();
-                self.update_live_overlay();
+        };
+        
+        match backup.restore(&data_dir, &self.profiles, replace_existing) {
+            Ok(summary) => {
+                self.reload_profiles_from_disk();
+                self.reload_config_from_disk();
+                // The hotkeys have to be registered again with the restored bindings
+                self.hotkey_config = load_config().hotkeys;
+                self.hotkey_binding = self.hotkey_config.cycle_crosshair.clone();
+                self.save_hotkey_settings();
+                self.status_message = t!(
+                    "status-backup-restored",
+                    added = summary.added,
+                    replaced = summary.replaced,
+                    kept = summary.kept,
+                    images = summary.images
+                );
             }
-            
-            Message::OverlayEnabledToggled(enabled) => {
-                self.edit_overlay_enabled = enabled;
+            Err(e) => self.status_message = t!("status-backup-restore-failed", error = e),
+        }
+    }
+    
+    /// Keep the data in `target` from now on (None = default location), copying it over unless
+    /// the user picks the data that's already there
+    fn move_data_directory(&mut self, target: Option<std::path::PathBuf>) {
+        let copy_files = match target {
+            Some(ref folder) if self.data_dir.as_ref() != Some(folder) && config::has_app_data(folder) => {
+                let choice = rfd::MessageDialog::new()
+                    .set_title(t!("data-folder-title"))
+                    .set_description(t!("data-folder-has-data", folder = folder.display()))
+                    .set_buttons(rfd::MessageButtons::YesNoCancel)
+                    .show();
+                match choice {
+                    rfd::MessageDialogResult::Yes => false,
+                    rfd::MessageDialogResult::No => true,
+                    _ => return,
+                }
             }
-            
-            Message::FanSpeedMaxToggled(enabled) => {
-                self.edit_fan_speed_max = enabled;
+            _ => true,
+        };
+        
+        // Everything in memory is written to the new folder from here on, so save it first
+        self.save_profiles_to_disk();
+        let (data_dir, copied) = match config::set_data_directory(target.as_deref(), copy_files) {
+            Ok(moved) => moved,
+            Err(e) => {
+                self.status_message = t!("status-data-folder-failed", error = e);
+                return;
             }
-            
-            Message::SelectImage => {
-                match open_image_picker() {
-                    Ok(path) => {
-                        match validate_crosshair_image(&path) {
-                            Ok(_) => {
-                                let path_str = path.to_string_lossy().to_string();
-                                self.edit_image_path = Some(path_str.clone());
-                                self.status_message = format!("📁 Selected image: {}", path_str);
-                            }
-                            Err(e) => {
-                                self.status_message = format!("❌ Invalid image: {}", e);
-                            }
-                        }
-                    }
-                    Err(_) => {}
+        };
+        
+        self.data_watcher = DataWatcher::spawn(&data_dir).map_err(|e| warn!("[Watch] {}", e)).ok();
+        self.sessions = sessions::load_sessions(&data_dir).unwrap_or_default();
+        self.benchmarks = benchmark::load_benchmarks(&data_dir).unwrap_or_default();
+        self.common_apps = common_apps::load_common_apps(Some(&data_dir));
+        self.games = game_knowledge::load_games(Some(&data_dir));
+        self.data_dir = Some(data_dir.clone());
+        self.data_dir_moved = target.is_some();
+        if !copy_files {
+            self.reload_profiles_from_disk();
+            self.reload_config_from_disk();
+            self.hotkey_config = load_config().hotkeys;
+            self.hotkey_binding = self.hotkey_config.cycle_crosshair.clone();
+            self.save_hotkey_settings();
+        }
+        self.status_message = t!("status-data-folder-moved", folder = data_dir.display(), files = copied);
+    }
+    
+    fn save_safety_settings(&mut self) {
+        process::set_user_protected(&self.safety_config.protected_processes);
+        let mut app_config = load_config();
+        app_config.safety = self.safety_config.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
+    }
+    
+    /// Register the new auto-start settings; on failure the switches (and tray check) stay as they were
+    fn save_autostart_settings(&mut self, config: AutostartConfig) {
+        match autostart::save(&config) {
+            Ok(()) => self.autostart = config,
+            Err(e) => self.status_message = t!("status-autostart-failed", error = e),
+        }
+        if let Some(ref tray) = self.tray_manager {
+            tray.set_autostart_checked(self.autostart.enabled);
+        }
+    }
+    
+    /// Save the remote API settings and restart (or stop) the server with them
+    fn save_remote_api_settings(&mut self) {
+        let Some(port) = self.api_port.trim().parse::<u16>().ok().filter(|port| *port >= 1024) else {
+            self.status_message = t!("status-invalid-api-port");
+            return;
+        };
+        self.api_config.port = port;
+        if self.api_config.token.is_empty() {
+            match remote_api::generate_token() {
+                Ok(token) => self.api_config.token = token,
+                Err(e) => {
+                    self.status_message = t!("status-remote-api-failed", error = e);
+                    return;
                 }
             }
-            
-            Message::ClearImage => {
-                self.edit_image_path = None;
-                self.status_message = "Cleared crosshair image".to_string();
-            }
+        }
+        let mut app_config = load_config();
+        app_config.api = self.api_config.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
         }
         
-        Command::none()
-    }
+        // The old server has to let go of the port before the new one binds it
+        self.remote_api = None;
+        if self.api_config.enabled {
+            match RemoteApi::spawn(&self.api_config, self.cli_tx.clone()) {
+                Ok(api) => {
+                    self.remote_api = Some(api);
+                    self.status_message = t!("status-remote-api-listening", port = port);
+                }
+                Err(e) => self.status_message = t!("status-remote-api-failed", error = e),
+            }
+        }
+    }
+    
+    /// Save the sampling interval and hand it to the running monitor
+    fn save_monitor_settings(&mut self) {
+        let Some(seconds) = self.monitor_interval.trim().parse::<f64>().ok().filter(|s| s.is_finite() && *s > 0.0) else {
+            self.status_message = t!("status-invalid-monitor-interval");
+            return;
+        };
+        
+        self.monitor_config.interval_ms = ((seconds * 1000.0) as u64).clamp(monitor::MIN_INTERVAL_MS, monitor::MAX_INTERVAL_MS);
+        self.monitor_interval = format_interval_secs(self.monitor_config.interval_ms);
+        let mut app_config = load_config();
+        app_config.monitor = self.monitor_config.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
+        self.hardware_monitor.set_config(&self.monitor_config);
+    }
+    
+    /// Save the alert settings; alerts that already fired may fire again under the new limits
+    fn save_alert_settings(&mut self) {
+        self.alert_config.thresholds = AlertThresholds {
+            cpu_temp_c: self.alert_cpu_limit.trim().parse().ok(),
+            gpu_temp_c: self.alert_gpu_limit.trim().parse().ok(),
+        };
+        let mut app_config = load_config();
+        app_config.alerts = self.alert_config.clone();
+        if let Err(e) = save_config(&app_config) {
+            self.status_message = t!("status-settings-save-failed", error = e);
+        }
+        self.alert_tracker.reset();
+        self.alert_balloon.clear();
+    }
+    
+    /// Compare the latest readings with the active limits and react to new alerts
+    fn check_alerts(&mut self) {
+        if !self.alert_config.enabled {
+            return;
+        }
+        let Some(ref snapshot) = self.hardware else {
+            return;
+        };
+        
+        let global = self.alert_config.thresholds;
+        let thresholds = self
+            .active_profile_name
+            .as_ref()
+            .and_then(|name| self.profiles.iter().find(|p| &p.name == name))
+            .map_or(global, |profile| profile.alert_thresholds.or(global));
+        
+        let alerts = self.alert_tracker.check(snapshot, &thresholds);
+        if !alerts.is_empty() {
+            self.handle_alerts(alerts);
+        } else if !self.alert_tracker.any_fired() {
+            self.alert_balloon.clear();
+        }
+    }
+    
+    fn handle_alerts(&mut self, alerts: Vec<TemperatureAlert>) {
+        for alert in &alerts {
+            self.publish_plugin_event(PluginEvent::TemperatureAlert(alert.clone()));
+        }
+        let messages: Vec<String> = alerts.iter().map(|alert| alert.message()).collect();
+        warn!("[Alerts] {}", messages.join(", "));
+        self.alert_balloon.show(&t!("alert-balloon-title"), &messages.join("\n"));
+        
+        let mut status_parts = vec![format!("🔥 {}", messages.join(", "))];
+        if self.alert_config.max_fans && !self.activation.system.fan.is_applied() {
+            match self.activation.system.fan.apply_max() {
+                Ok(backend) => status_parts.push(t!("status-alert-fan-max", backend = backend)),
+                Err(e) => status_parts.push(t!("status-alert-fan-failed", error = e)),
+            }
+        }
+        
+        // Only step down while gaming, and only to a profile that still exists
+        if let Some(target) = self.alert_config.switch_to_profile.clone() {
+            let gaming = self.active_profile_name.is_some();
+            if gaming && self.active_profile_name.as_deref() != Some(target.as_str()) {
+                if self.profiles.iter().any(|p| p.name == target) {
+                    self.activate_profile_by_name(&target);
+                    status_parts.push(t!("status-alert-switched", profile = target));
+                } else {
+                    status_parts.push(t!("status-alert-profile-missing", profile = target));
+                }
+            }
+        }
+        
+        self.status_message = status_parts.join(" | ");
+    }
+    
+    /// Follow the session's game with the latency monitor and record its ping
+    fn refresh_latency(&mut self) {
+        let game = self.session.as_ref().and_then(|session| session.current_game()).map(str::to_string);
+        if game.as_deref() != self.latency.as_ref().map(|monitor| monitor.process_name()) {
+            self.latency = game.as_deref().map(LatencyMonitor::spawn);
+        }
+        
+        self.latency_stats = self.latency.as_ref().and_then(|monitor| monitor.latest());
+        if let (Some(ref mut session), Some(ref latency)) = (&mut self.session, &self.latency_stats) {
+            session.sample_latency(latency);
+        }
+    }
+    
+    /// Pick up the monitor's latest readings and pass them on to the overlay HUD
+    fn refresh_hardware(&mut self) {
+        let latest = self.hardware_monitor.latest();
+        if latest == self.hardware {
+            return;
+        }
+        self.hardware = latest;
+        if let Some(ref mut tray) = self.tray_manager {
+            tray.set_hardware(self.hardware.as_ref());
+        }
+        
+        if let (Some(ref mut session), Some(ref snapshot)) = (&mut self.session, &self.hardware) {
+            session.sample(snapshot, sessions::foreground_process().as_deref());
+        }
+        self.refresh_latency();
+        self.check_alerts();
+        
+        // crosshair.exe polls this file while the HUD is enabled
+        if self.activation.system.overlay.is_some() {
+            if let (Some(path), Some(ref snapshot)) = (monitor::hud_file(), &self.hardware) {
+                let mut lines = snapshot.summary_lines();
+                lines.extend(self.latency_stats.as_ref().map(|latency| latency.summary_line()));
+                if let Err(e) = std::fs::write(&path, lines.join("\n")) {
+                    warn!("[Monitor] Failed to write HUD file: {}", e);
+                }
+            }
+        }
+    }
+    
+    fn deactivate_profile(&mut self) {
+        let profile = self.active_profile_name.take();
+        self.active_crosshair_slot = 0;
+        self.last_activation = None;
+        self.activation_failed = false;
+        
+        self.publish_activation(ActivationEvent::Deactivated);
+        
+        // Steps are undone last first, so the overlay goes before anything else
+        let mut status_parts = vec![t!("status-profile-deactivated")];
+        let errors = self.activation.deactivate();
+        if let Some(ref profile) = profile {
+            self.log_event(event_log::Entry::deactivation(profile, &errors));
+            self.publish_plugin_event(PluginEvent::Deactivated { profile: profile.clone() });
+        }
+        status_parts.extend(errors);
+        self.timer_resolution = timer::query_timer_resolution().ok();
+        self.status_message = status_parts.join(" | ");
+        self.update_tray();
+    }
+    
+    /// Update the live crosshair overlay with the editor's settings (swapped in if running)
+    fn update_live_overlay(&mut self) {
+        // Only update if we have an active overlay
+        if self.activation.system.overlay.is_some() {
+            self.set_paused(false);
+            let mut running = self.activation.system.overlay.take();
+            
+            // Show the new settings if we have an image, in the running overlay when it takes them
+            if self.edit_overlay_enabled {
+                if let Some(ref path) = self.edit_image_path {
+                    let x_offset: i32 = self.edit_x_offset.parse().unwrap_or(0);
+                    let y_offset: i32 = self.edit_y_offset.parse().unwrap_or(0);
+                    let options = OverlayOptions {
+                        exclude_from_capture: self.edit_exclude_from_capture,
+                        tint: self.edit_tint,
+                        note: self.edit_overlay_note(),
+                        anchor: self.edit_anchor,
+                        hud: self.edit_hardware_hud,
+                    };
+                    
+                    if let Some(mut handle) = running.take() {
+                        if handle.swap(path, x_offset, y_offset, &options) {
+                            self.activation.system.overlay = Some(handle);
+                            return;
+                        }
+                        handle.stop();
+                    }
+                    match self.activation.system.backends.overlay.start(path.clone(), x_offset, y_offset, &options) {
+                        Ok(handle) => {
+                            self.activation.system.overlay = Some(handle);
+                            self.overlay_error = None;
+                        }
+                        Err(e) => {
+                            self.status_message = t!("status-crosshair-error", error = e);
+                        }
+                    }
+                }
+            }
+            
+            if let Some(handle) = running {
+                handle.stop();
+            }
+        }
+    }
+    
+    /// Switch to a custom percentage anchor once both fields hold valid percentages
+    fn apply_anchor_percent(&mut self) {
+        let (Ok(x), Ok(y)) = (self.edit_anchor_x.trim().parse(), self.edit_anchor_y.trim().parse()) else {
+            return;
+        };
+        
+        let anchor = CrosshairAnchor::Percent { x, y };
+        if anchor.validate().is_ok() && anchor != self.edit_anchor {
+            self.edit_anchor = anchor;
+            self.update_live_overlay();
+        }
+    }
+    
+    /// Pick the monitor being edited (None = primary) and load its supported modes
+    fn select_display_device(&mut self, device: Option<String>) {
+        self.edit_display_device = device.or_else(|| self.monitors.first().map(|m| m.device.clone()));
+        self.display_modes = match self.edit_display_device {
+            Some(ref device) => display::list_modes(device).unwrap_or_default(),
+            None => Vec::new(),
+        };
+    }
+    
+    /// Display mode from the editor fields (None = leave the desktop mode)
+    fn edit_display_setting(&self) -> Option<DisplaySetting> {
+        Some(DisplaySetting {
+            device: self.edit_display_device.clone()?,
+            mode: self.edit_display_mode?,
+        })
+    }
+    
+    /// Display name of an installed power plan (falls back to the GUID)
+    fn power_plan_name(&self, guid: &str) -> String {
+        power::plan_name(&self.power_plans, guid)
+    }
+    
+    /// Overlay note from the editor fields (None when the note text is empty)
+    fn edit_overlay_note(&self) -> Option<OverlayNote> {
+        let text = self.edit_note_text.text().trim_end().to_string();
+        if text.trim().is_empty() {
+            return None;
+        }
+        
+        Some(OverlayNote {
+            text,
+            font_size: self.edit_note_size.parse::<u32>().unwrap_or(DEFAULT_NOTE_SIZE).clamp(8, 64),
+            corner: self.edit_note_corner,
+            color: parse_hex_color(&self.edit_note_color_hex).unwrap_or(DEFAULT_NOTE_COLOR),
+        })
+    }
+    
+    /// The active profile, if any
+    fn active_profile(&self) -> Option<Profile> {
+        self.active_profile_name
+            .as_ref()
+            .and_then(|name| self.profiles.iter().find(|p| &p.name == name))
+            .cloned()
+    }
+    
+    /// Switch the live overlay to the active profile's next crosshair preset
+    /// (only restarts the crosshair process, processes are not killed again)
+    fn cycle_crosshair(&mut self) {
+        let Some(profile) = self.active_profile() else {
+            self.status_message = t!("status-cycle-no-profile");
+            return;
+        };
+        
+        if !profile.overlay_enabled || profile.crosshair_slot_count() < 2 {
+            self.status_message = t!("status-cycle-no-presets", profile = profile.name);
+            return;
+        }
+        
+        self.active_crosshair_slot = (self.active_crosshair_slot + 1) % profile.crosshair_slot_count();
+        self.status_message = match self.show_crosshair(&profile) {
+            Ok(name) => t!("status-crosshair-shown", crosshair = name),
+            Err(e) => e,
+        };
+    }
+    
+    /// Switch the active profile to another offset preset (None = its own offsets), keep the
+    /// choice and move a crosshair that is showing the profile's own image
+    fn select_offset_preset(&mut self, preset: Option<String>) {
+        // The tray only offers presets of the active profile
+        let Some(index) = self.active_profile_name.as_ref().and_then(|name| self.profiles.iter().position(|p| &p.name == name)) else {
+            return;
+        };
+        if !self.profiles[index].select_offset_preset(preset.as_deref()) {
+            return;
+        }
+        
+        // Keep the editor in step unless it holds other unsaved changes
+        let reload_editor = self.selected_profile_index == Some(index) && !self.has_unsaved_edits();
+        self.save_profiles_to_disk();
+        if reload_editor {
+            self.load_profile_to_edit(index);
+        }
+        
+        let profile = self.profiles[index].clone();
+        let (x, y) = profile.base_offset();
+        let name = preset.unwrap_or_else(|| t!("offset-preset-default"));
+        self.status_message = t!("status-offset-preset-selected", preset = name.as_str(), x = x, y = y);
+        if self.activation.system.overlay.is_some() && self.active_crosshair_slot == 0 {
+            if let Err(e) = self.show_crosshair(&profile) {
+                self.status_message = e;
+            }
+        }
+        self.update_tray();
+    }
+    
+    /// (Re)start the overlay with the profile's current crosshair slot; returns the crosshair name
+    fn show_crosshair(&mut self, profile: &Profile) -> Result<String, String> {
+        self.set_paused(false);
+        let name = self.activation.show_crosshair(profile, self.active_crosshair_slot)?;
+        self.overlay_error = None;
+        Ok(name)
+    }
+    
+    /// Active profile and the latest readings, for `status` and the remote API
+    fn live_status(&self) -> LiveStatus {
+        let hardware = self.hardware.clone().unwrap_or_default();
+        LiveStatus {
+            active_profile: self.active_profile_name.clone(),
+            overlay_visible: self.activation.system.overlay.is_some(),
+            game: self.session.as_ref().and_then(|session| session.current_game()).map(str::to_string),
+            cpu_load: hardware.cpu_load,
+            gpu_load: hardware.gpu_load,
+            cpu_temp: hardware.cpu_temp,
+            gpu_temp: hardware.gpu_temp,
+            ping_ms: self.latency_stats.as_ref().and_then(|stats| stats.current_ms),
+        }
+    }
+    
+    /// Answer a command from `gaming_optimizer <command>` or the remote API
+    fn handle_cli_command(&mut self, command: CliCommand) -> CliReply {
+        match command {
+            CliCommand::Activate { profile } => {
+                if !self.profiles.iter().any(|p| p.name == profile) {
+                    return CliReply::error(format!("No profile named '{}'", profile));
+                }
+                self.activate_profile_by_name(&profile);
+                if self.active_profile_name.as_deref() == Some(profile.as_str()) {
+                    CliReply::ok(self.status_message.clone())
+                } else {
+                    CliReply::error(self.status_message.clone())
+                }
+            }
+            CliCommand::Deactivate => {
+                if self.active_profile_name.is_none() {
+                    return CliReply::ok("No profile is active");
+                }
+                self.deactivate_profile();
+                CliReply::ok(self.status_message.clone())
+            }
+            // The window itself is raised by the caller, which can return window commands
+            CliCommand::Show => CliReply::ok("Gaming Optimizer window shown"),
+            CliCommand::List => {
+                let active = self.active_profile_name.as_deref();
+                CliReply::ok(cli::format_profile_list(&self.profiles, active))
+                    .with_data(cli::profile_entries(&self.profiles, active))
+            }
+            CliCommand::Status => {
+                let status = self.live_status();
+                CliReply::ok(status.summary()).with_data(status)
+            }
+            CliCommand::Kill { profile, dry_run } => {
+                let active = self.active_profile_name.as_deref();
+                cli::kill_for_profile(self.activation.processes(), &self.profiles, profile.as_deref(), active, dry_run)
+            }
+            CliCommand::Overlay { state: OverlayState::Off } => {
+                self.activation.hide_crosshair();
+                CliReply::ok("Crosshair hidden")
+            }
+            CliCommand::Overlay { state: OverlayState::On } => {
+                let Some(profile) = self.active_profile() else {
+                    return CliReply::error("No profile is active");
+                };
+                if !profile.overlay_enabled {
+                    return CliReply::error(format!("Profile '{}' has no crosshair", profile.name));
+                }
+                match self.show_crosshair(&profile) {
+                    Ok(name) => CliReply::ok(format!("Crosshair shown: {}", name)),
+                    Err(e) => CliReply::error(e),
+                }
+            }
+            // The caller exits once the reply is sent
+            CliCommand::Quit => CliReply::ok("Gaming Optimizer closed"),
+        }
+    }
+    
+    /// Parse the pasted Valorant/CS2 code and render it into the crosshair image
+    fn import_crosshair_code(&mut self) -> anyhow::Result<String> {
+        let params = parse_crosshair_code(&self.edit_crosshair_code)?;
+        let data_dir = self
+            .data_dir
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Data directory unavailable"))?;
+        
+        // Same code always maps to the same generated file
+        let path = data_dir
+            .join("crosshairs")
+            .join(imported_file_name(&self.edit_crosshair_code));
+        
+        params.save_png(&path)?;
+        
+        let path_str = path.to_string_lossy().to_string();
+        self.edit_image_path = Some(path_str.clone());
+        self.edit_crosshair_params = Some(params);
+        Ok(path_str)
+    }
+    
+    /// Take the arrow keys to move the live crosshair (tray menu, flyout or a tray click)
+    fn start_crosshair_adjust(&mut self) {
+        if self.crosshair_adjust.is_some() {
+            return;
+        }
+        self.set_paused(false);
+        let Some(profile) = self.active_profile().filter(|_| self.activation.system.overlay.is_some()) else {
+            self.status_message = t!("status-adjust-no-crosshair");
+            return;
+        };
+        
+        let crosshair = profile.crosshair_for_slot(self.active_crosshair_slot);
+        match hotkey_thread::spawn_adjust_hotkeys() {
+            Ok(hotkeys) => {
+                info!("[GUI] Adjusting crosshair '{}' of {}", crosshair.name, profile.name);
+                self.crosshair_adjust = Some(CrosshairAdjust {
+                    hotkeys,
+                    profile: profile.name,
+                    slot: self.active_crosshair_slot,
+                    x_offset: crosshair.x_offset,
+                    y_offset: crosshair.y_offset,
+                });
+                self.status_message = t!("status-adjust-started");
+            }
+            Err(e) => self.status_message = t!("status-adjust-failed", error = e),
+        }
+    }
+    
+    /// Move the crosshair for arrow keys and finish on Enter/Escape
+    fn poll_crosshair_adjust(&mut self) {
+        let Some(adjust) = self.crosshair_adjust.as_mut() else {
+            return;
+        };
+        
+        // The crosshair was hidden, deactivated or switched to another preset meanwhile
+        let same_crosshair = self.active_profile_name.as_ref() == Some(&adjust.profile) && self.active_crosshair_slot == adjust.slot;
+        if self.activation.system.overlay.is_none() || !same_crosshair {
+            self.crosshair_adjust = None;
+            self.status_message = t!("status-adjust-cancelled");
+            return;
+        }
+        
+        let mut finished = false;
+        while let Ok(action) = adjust.hotkeys.actions.try_recv() {
+            match action {
+                HotkeyAction::NudgeCrosshair(dx, dy) => {
+                    adjust.x_offset += dx;
+                    adjust.y_offset += dy;
+                    if !crosshair_overlay::move_overlay(adjust.x_offset, adjust.y_offset) {
+                        warn!("[GUI] Crosshair window not found while adjusting");
+                    }
+                }
+                HotkeyAction::FinishAdjust => finished = true,
+                HotkeyAction::CycleCrosshair => {}
+            }
+        }
+        if finished {
+            self.finish_crosshair_adjust();
+        }
+    }
+    
+    /// Store the adjusted offsets in the profile and give the keys back
+    fn finish_crosshair_adjust(&mut self) {
+        let Some(adjust) = self.crosshair_adjust.take() else {
+            return;
+        };
+        let Some(index) = self.profiles.iter().position(|p| p.name == adjust.profile) else {
+            return;
+        };
+        
+        // Show the new offsets in the editor unless it holds other unsaved changes
+        let reload_editor = self.selected_profile_index == Some(index) && !self.has_unsaved_edits();
+        self.profiles[index].set_slot_offset(adjust.slot, adjust.x_offset, adjust.y_offset);
+        if self.save_profiles_to_disk() {
+            self.status_message = t!("status-adjust-saved", x = adjust.x_offset, y = adjust.y_offset);
+        }
+        if reload_editor {
+            self.load_profile_to_edit(index);
+        }
+        self.update_tray();
+    }
+    
+    /// Suspend or resume everything that watches in the background, leaving the active profile
+    /// applied. Showing a crosshair or activating a profile resumes.
+    fn set_paused(&mut self, paused: bool) {
+        if paused == self.paused {
+            return;
+        }
+        self.paused = paused;
+        info!("[GUI] Optimizer {}", if paused { "paused" } else { "resumed" });
+        
+        if paused {
+            // Give the keys back to other apps while screen-sharing or troubleshooting
+            self.crosshair_adjust = None;
+            self.hotkeys = HotkeyThread::disabled();
+            self.gamepad = GamepadThread::disabled();
+            self.latency = None;
+            self.latency_stats = None;
+            self.alert_balloon.clear();
+            self.status_message = t!("status-paused");
+        } else {
+            self.hotkeys = hotkey_thread::spawn_hotkey_thread(&self.hotkey_config).unwrap_or_else(|e| {
+                warn!("[HOTKEY] {}", e);
+                HotkeyThread::disabled()
+            });
+            self.gamepad = gamepad::spawn_gamepad_thread(&self.gamepad_config).unwrap_or_else(|e| {
+                warn!("[Gamepad] {}", e);
+                GamepadThread::disabled()
+            });
+            self.status_message = t!("status-resumed");
+        }
+        
+        if self.activation.system.overlay.is_some() && !crosshair_overlay::set_overlay_visible(!paused) {
+            warn!("[GUI] Crosshair window not found while {}", if paused { "pausing" } else { "resuming" });
+        }
+        if let Some(ref mut tray) = self.tray_manager {
+            tray.set_paused(paused);
+        }
+    }
+    
+    /// Show or hide the active profile's crosshair (tray click binding)
+    fn toggle_overlay(&mut self) {
+        let showing = self.activation.system.overlay.is_none();
+        let state = if showing { OverlayState::On } else { OverlayState::Off };
+        let reply = self.handle_cli_command(CliCommand::Overlay { state });
+        self.status_message = match (reply.ok, showing) {
+            (true, true) => t!("status-overlay-shown"),
+            (true, false) => t!("status-overlay-hidden"),
+            (false, _) => format!("⚠️ {}", reply.message),
+        };
+    }
+    
+    /// Detect an overlay that exited because it couldn't be made click-through
+    fn check_overlay_health(&mut self) {
+        let failed = self
+            .activation
+            .system
+            .overlay
+            .as_mut()
+            .map(|handle| handle.click_through_failed())
+            .unwrap_or(false);
+        
+        if failed {
+            self.activation.system.overlay = None;
+            let error = t!("overlay-click-through-failed");
+            self.status_message = format!("⚠️ {}", error);
+            toast_notifier::notify(&self.toast_config, ToastCategory::OverlayError, &t!("toast-crosshair-disabled-title"), &error);
+            self.overlay_error = Some(error);
+        }
+    }
+    
+    /// Write the active profile, overlay and pause state to config.json when they've changed
+    fn remember_session(&mut self) {
+        // Safe mode keeps the state of the last normal run for the next one
+        if config::is_safe_mode() {
+            return;
+        }
+        let session = SessionState {
+            active_profile: self.active_profile_name.clone(),
+            overlay_visible: self.activation.system.overlay.is_some(),
+            paused: self.paused,
+        };
+        if session == self.saved_session {
+            return;
+        }
+        match config::save_session_state(&session) {
+            Ok(()) => self.saved_session = session,
+            Err(e) => warn!("[GUI] Failed to save the session state: {}", e),
+        }
+    }
+    
+    /// Bring back what was running when the app last closed
+    fn resume_session(&mut self, session: &SessionState) {
+        if let Some(ref name) = session.active_profile {
+            if !self.profiles.iter().any(|profile| &profile.name == name) {
+                warn!("[GUI] Can't resume {}: the profile no longer exists", name);
+                return;
+            }
+            info!("[GUI] Resuming {}", name);
+            self.activate_profile_by_name(name);
+            if !session.overlay_visible {
+                self.activation.hide_crosshair();
+                self.refresh_tray_badges();
+            }
+        }
+        if session.paused {
+            self.set_paused(true);
+        }
+    }
+    
+    fn update_tray(&mut self) {
+        crash::set_active_profile(self.active_profile_name.as_deref());
+        
+        // Update tray with current profiles
+        if let Some(ref mut tray) = self.tray_manager {
+            tray.update_profiles(self.profiles.clone());
+            tray.set_active_profile(self.active_profile_name.clone());
+            tray.set_activation_status(self.last_activation.as_ref().map(|summary| summary.tray_status()));
+            tray.set_quick_profiles(&profile::quick_profiles(&self.profiles, &self.profile_usage, tray_flyout::QUICK_PROFILES));
+        }
+    }
+    
+    /// Start or stop watching for game launches
+    fn set_auto_activate_games(&mut self, enabled: bool) {
+        if enabled != self.game_watcher.is_some() {
+            self.game_watcher = if enabled { start_game_watcher() } else { None };
+        }
+        self.auto_activate_games = enabled;
+    }
+    
+    /// Profile to activate for a game that just started; launches while paused are dropped
+    /// (plugins hear about every known game that starts)
+    fn poll_game_launches(&self) -> Option<String> {
+        let launched = self.game_watcher.as_ref().map(GameWatcher::poll).unwrap_or_default();
+        for exe in &launched {
+            if let Some(game) = game_knowledge::game_for_exe(&self.games, exe) {
+                let profile = game_knowledge::profile_for_launch(&self.games, &self.profiles, exe).map(|p| p.name.clone());
+                self.publish_plugin_event(PluginEvent::GameStarted { game: game.name.clone(), exe: exe.clone(), profile });
+            }
+        }
+        if self.paused {
+            return None;
+        }
+        let (exe, profile) = launched
+            .iter()
+            .find_map(|exe| game_knowledge::profile_for_launch(&self.games, &self.profiles, exe).map(|profile| (exe, profile)))?;
+        if self.active_profile_name.as_deref() == Some(profile.name.as_str()) {
+            return None;
+        }
+        info!("[Games] {} started, activating {}", exe, profile.name);
+        Some(profile.name.clone())
+    }
+    
+    /// Time until the next TrayTick unless something wakes it earlier
+    fn tick_interval(&self) -> Duration {
+        let tray_busy = self.tray_manager.as_ref().is_some_and(TrayFlyoutManager::is_busy);
+        let settling = self.data_watcher.as_ref().is_some_and(DataWatcher::is_settling);
+        if tray_busy || settling {
+            ipc::BUSY_TICK
+        } else {
+            ipc::IDLE_TICK
+        }
+    }
+    
+    /// Match the tray icon badges to the active profile, the overlay and the last activation
+    fn refresh_tray_badges(&mut self) {
+        if let Some(ref mut tray) = self.tray_manager {
+            tray.set_badges(TrayState {
+                profile_active: self.active_profile_name.is_some(),
+                overlay_visible: self.activation.system.overlay.is_some(),
+                activation_failed: self.activation_failed,
+            });
+        }
+    }
+    
+    /// Count an activation for the favorites and recent profiles in the tray
+    fn record_profile_use(&mut self, name: &str) {
+        let usage = self.profile_usage.entry(name.to_string()).or_default();
+        usage.activations += 1;
+        usage.last_activated = sessions::unix_time();
+        
+        let mut app_config = load_config();
+        app_config.profile_usage = self.profile_usage.clone();
+        if let Err(e) = save_config(&app_config) {
+            warn!("[GUI] Failed to save profile usage: {}", e);
+        }
+    }
+}
 
-    fn view(&self) -> Element<'_, Message> {
-        // Left panel - Profile list
-        let mut profile_list = Column::new()
+impl Application for GameOptimizer {
+    type Executor = executor::Default;
+    type Message = Message;
+    type Theme = Theme;
+    type Flags = Option<String>;
+
+    fn new(restore_profile: Option<String>) -> (Self, Command<Message>) {
+        let data_dir = get_data_directory().ok();
+        let app_config = load_config();
+        let (cli_tx, cli_rx) = ipc::request_channel();
+        cli::spawn_pipe_server(cli_tx.clone());
+        let mut app = GameOptimizer {
+            profiles: Vec::new(),
+            selected_profile_index: None,
+            edit_name: String::new(),
+            edit_x_offset: "0".to_string(),
+            edit_y_offset: "0".to_string(),
+            edit_anchor: CrosshairAnchor::default(),
+            edit_anchor_x: "50".to_string(),
+            edit_anchor_y: "50".to_string(),
+            edit_image_path: None,
+            edit_crosshair_params: None,
+            edit_crosshair_code: String::new(),
+            edit_overlay_enabled: false,
+            edit_exclude_from_capture: false,
+            edit_tint: None,
+            edit_tint_hex: String::new(),
+            edit_presets: Vec::new(),
+            edit_offset_presets: Vec::new(),
+            edit_selected_offset_preset: None,
+            edit_offset_preset_name: String::new(),
+            edit_preset_name: String::new(),
+            edit_note_text: text_editor::Content::new(),
+            edit_note_size: DEFAULT_NOTE_SIZE.to_string(),
+            edit_note_corner: Corner::default(),
+            edit_note_color_hex: format_hex_color(DEFAULT_NOTE_COLOR),
+            edit_hardware_hud: None,
+            edit_fan_speed_max: false,
+            edit_power_plan: None,
+            edit_game_features: GameFeatureToggles::default(),
+            edit_high_precision_timer: false,
+            edit_best_performance_visuals: false,
+            edit_display_device: None,
+            edit_display_mode: None,
+            edit_master_volume: String::new(),
+            edit_app_volumes: Vec::new(),
+            edit_app_volume_process: String::new(),
+            edit_app_volume_percent: String::new(),
+            edit_pause_delivery_optimization: false,
+            edit_bandwidth_limits: Vec::new(),
+            edit_limit_process: String::new(),
+            edit_limit_kbps: String::new(),
+            edit_storage: StorageSettings::default(),
+            edit_rgb_scene: None,
+            edit_rgb_hex: String::new(),
+            edit_obs: ObsSettings::default(),
+            edit_captures: CaptureSettings::default(),
+            capture_folders: captures::capture_folders(),
+            edit_do_not_disturb: false,
+            edit_mute_notification_sounds: false,
+            edit_gpu_prefer_max: false,
+            edit_nvidia_profile: String::new(),
+            edit_system_tweaks: Vec::new(),
+            edit_keyboard_layout: None,
+            edit_block_windows_key: false,
+            edit_suppress_sticky_keys: false,
+            edit_rollback_on_failure: false,
+            edit_skip_kill_confirmation: false,
+            edit_favorite: false,
+            edit_archived: false,
+            edit_anti_cheat_safe: false,
+            edit_baseline: crate::profile::create_profile(String::new()),
+            edit_cpu_limit: String::new(),
+            edit_gpu_limit: String::new(),
+            gpu_vendors: gpu::detect_vendors(),
+            monitors: display::list_monitors().unwrap_or_default(),
+            display_modes: Vec::new(),
+            timer_resolution: timer::query_timer_resolution().ok(),
+            overlay_render_time: None,
+            power_plans: power::list_power_plans().unwrap_or_default(),
+            openrgb_host: app_config.openrgb.host.clone(),
+            openrgb_port: app_config.openrgb.port.to_string(),
+            openrgb: app_config.openrgb.clone(),
+            openrgb_profiles: Vec::new(),
+            obs: app_config.obs.clone(),
+            obs_host: app_config.obs.host.clone(),
+            obs_port: app_config.obs.port.to_string(),
+            obs_password: app_config.obs.password.clone(),
+            obs_scenes: Vec::new(),
+            discord_presence: app_config.discord.enabled.then(|| DiscordPresence::spawn(app_config.discord.application_id.clone())),
+            discord_config: app_config.discord.clone(),
+            event_log: app_config.event_log.clone(),
+            plugin_files: data_dir.as_ref().map(|dir| plugins::list_plugins(&dir.join(plugins::PLUGINS_FOLDER))).unwrap_or_default(),
+            plugins: data_dir.as_ref().and_then(|dir| PluginHost::start(dir, &app_config.plugins, &cli_tx)),
+            plugin_config: app_config.plugins.clone(),
+            log_level: app_config.log_level,
+            toast_config: app_config.toasts.clone(),
+            tray_activation: false,
+            monitor_interval: format_interval_secs(app_config.monitor.interval_ms),
+            hardware_monitor: HardwareMonitor::spawn(&app_config.monitor),
+            monitor_config: app_config.monitor.clone(),
+            hardware: None,
+            latency: None,
+            latency_stats: None,
+            alert_cpu_limit: format_limit(app_config.alerts.thresholds.cpu_temp_c),
+            alert_gpu_limit: format_limit(app_config.alerts.thresholds.gpu_temp_c),
+            alert_config: app_config.alerts.clone(),
+            alert_tracker: AlertTracker::default(),
+            alert_balloon: AlertBalloon::new(),
+            keyboard_layouts: keyboard::list_layouts().unwrap_or_default(),
+            process_selection: HashMap::new(),
+            running_processes: Vec::new(),
+            process_filter: String::new(),
+            process_list: ProcessListState::default(),
+            process_icons: IconCache::default(),
+            profile_filter: String::new(),
+            show_archived: false,
+            quick_switch: None,
+            status_message: t!("status-welcome"),
+            session: None,
+            sessions: data_dir.as_deref().and_then(|dir| sessions::load_sessions(dir).ok()).unwrap_or_default(),
+            view: View::Dashboard,
+            editor_tab: EditorTab::default(),
+            language: app_config.language.clone(),
+            languages: i18n::available_languages(),
+            theme_config: app_config.theme,
+            system_dark: styles::system_prefers_dark(),
+            system_theme_checked: Instant::now(),
+            benchmark: None,
+            benchmarks: data_dir.as_deref().and_then(|dir| benchmark::load_benchmarks(dir).ok()).unwrap_or_default(),
+            common_apps: common_apps::load_common_apps(data_dir.as_deref()),
+            games: game_knowledge::load_games(data_dir.as_deref()),
+            hide_game_suggestions: false,
+            capabilities: elevation::capabilities(),
+            data_watcher: data_dir.as_deref().and_then(|dir| DataWatcher::spawn(dir).map_err(|e| warn!("[Watch] {}", e)).ok()),
+            data_dir,
+            data_dir_moved: config::data_directory_override().is_some(),
+            portable: config::is_portable(),
+            active_profile_name: None,
+            activation: crosshair_overlay::activation_manager(),
+            last_activation: None,
+            active_crosshair_slot: 0,
+            overlay_error: None,
+            tray_manager: None,  // Will be set by run() via Flags if we change approach
+            hotkeys: hotkey_thread::spawn_hotkey_thread(&app_config.hotkeys).unwrap_or_else(|e| {
+                warn!("[HOTKEY] {}", e);
+                HotkeyThread::disabled()
+            }),
+            hotkey_binding: app_config.hotkeys.cycle_crosshair.clone(),
+            crosshair_adjust: None,
+            paused: false,
+            hotkey_config: app_config.hotkeys.clone(),
+            gamepad_config: app_config.gamepad.clone(),
+            gamepad_toggle_overlay: app_config.gamepad.toggle_overlay.clone(),
+            gamepad_cycle_crosshair: app_config.gamepad.cycle_crosshair.clone(),
+            gamepad: gamepad::spawn_gamepad_thread(&app_config.gamepad).unwrap_or_else(|e| {
+                warn!("[Gamepad] {}", e);
+                GamepadThread::disabled()
+            }),
+            tray_clicks: app_config.tray_clicks,
+            tray_tooltip: app_config.tray_tooltip.clone(),
+            sync_folder: app_config.sync_folder.clone(),
+            safety_config: app_config.safety.clone(),
+            protected_process_input: String::new(),
+            kill_confirmation: None,
+            activation_report: None,
+            lint_results: Vec::new(),
+            activation_failed: false,
+            profile_usage: app_config.profile_usage.clone(),
+            remote_api: app_config.api.enabled.then(|| RemoteApi::spawn(&app_config.api, cli_tx.clone()))
+                .and_then(|started| started.map_err(|e| warn!("[API] {}", e)).ok()),
+            autostart: app_config.autostart.clone(),
+            exit_behavior: app_config.exit_behavior,
+            resume_last_session: app_config.resume_last_session,
+            auto_activate_games: app_config.auto_activate_games,
+            game_watcher: app_config.auto_activate_games.then(start_game_watcher).flatten(),
+            saved_session: app_config.session_state(),
+            window_layout: app_config.window,
+            update_interval: app_config.updates.interval_hours.to_string(),
+            update_config: app_config.updates.clone(),
+            update_check: None,
+            update_download: None,
+            available_update: None,
+            api_port: app_config.api.port.to_string(),
+            api_config: app_config.api.clone(),
+            cli_tx,
+            cli_rx,
+        };
+        app.select_display_device(None);
+        process::set_user_protected(&app.safety_config.protected_processes);
+        app.load_profiles_from_disk();
+        app.refresh_running_processes();
+        app.edit_baseline = app.edited_profile();
+        
+        // Create tray manager on main thread (inside iced's new)
+        match TrayFlyoutManager::new(app.profiles.clone(), app_config.active_profile.clone()) {
+            Ok(tray) => {
+                app.tray_manager = Some(tray);
+                app.update_tray();
+                info!("[GUI] Tray manager created successfully");
+            }
+            Err(e) => {
+                error!("[GUI] Failed to create tray: {}", e);
+            }
+        }
+        
+        // Restarted after a crash with the profile that was active
+        if config::is_safe_mode() {
+            app.status_message = t!("status-safe-mode");
+        } else if let Some(name) = restore_profile {
+            info!("[GUI] Reactivating {} after a crash", name);
+            app.activate_profile_by_name(&name);
+        } else if app_config.resume_last_session {
+            app.resume_session(&app_config.session_state());
+        }
+        
+        (app, Command::none())
+    }
+
+    fn title(&self) -> String {
+        t!("window-title")
+    }
+    
+    fn theme(&self) -> Theme {
+        styles::theme(&self.theme_config, self.system_dark)
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        // Ticks right away when the tray, a command or a hotkey wakes it, otherwise once per
+        // interval; a new interval restarts the subscription
+        struct TrayPoller;
+        let interval = self.tick_interval();
+        
+        Subscription::batch([
+            iced::subscription::unfold(
+                (std::any::TypeId::of::<TrayPoller>(), interval),
+                (),
+                move |_| async move {
+                    ipc::wait(interval);
+                    (Message::TrayTick, ())
+                }
+            ),
+            // Also while a text box has focus, so the quick switcher works from its search box
+            iced::event::listen_with(|event, _status| match event {
+                iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { key, modifiers, .. }) => quick_switch_key(key, modifiers),
+                iced::Event::Window(_, iced::window::Event::FileDropped(path)) => Some(Message::ImageDropped(path)),
+                iced::Event::Window(_, iced::window::Event::CloseRequested) => Some(Message::WindowCloseRequested),
+                iced::Event::Window(_, iced::window::Event::Moved { x, y }) => Some(Message::WindowMoved(x, y)),
+                iced::Event::Window(_, iced::window::Event::Resized { width, height }) => Some(Message::WindowResized(width, height)),
+                _ => None,
+            }),
+        ])
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::TrayTick => {
+                self.remember_session();
+                self.check_overlay_health();
+                if !self.paused {
+                    self.refresh_hardware();
+                }
+                self.poll_benchmark();
+                self.poll_updates();
+                self.refresh_system_theme();
+                self.process_icons.poll();
+                self.refresh_tray_badges();
+                
+                if let Ok(HotkeyAction::CycleCrosshair) = self.hotkeys.actions.try_recv() {
+                    self.cycle_crosshair();
+                }
+                while let Ok(action) = self.gamepad.actions.try_recv() {
+                    match action {
+                        GamepadAction::ToggleOverlay => self.toggle_overlay(),
+                        GamepadAction::CycleCrosshair => self.cycle_crosshair(),
+                    }
+                }
+                self.poll_crosshair_adjust();
+                
+                for file in self.data_watcher.as_mut().map(DataWatcher::poll).unwrap_or_default() {
+                    match file {
+                        DataFile::Profiles => self.reload_profiles_from_disk(),
+                        DataFile::Config => self.reload_config_from_disk(),
+                    }
+                }
+                
+                let mut focus_window = false;
+                let mut quit = false;
+                while let Some(request) = self.cli_rx.try_recv() {
+                    focus_window |= request.command == CliCommand::Show;
+                    quit |= request.command == CliCommand::Quit;
+                    let reply = self.handle_cli_command(request.command.clone());
+                    request.answer(reply);
+                }
+                if quit {
+                    // Nobody may be at the screen to answer, so "Ask" undoes the profile
+                    self.exit(self.exit_behavior != ExitBehavior::LeaveActive);
+                }
+                
+                // Tray clicks, menu picks and flyout actions. iced's winit doesn't pump the
+                // tray icon's window messages, so that happens here too.
+                if tray_flyout::pump_messages() {
+                    info!("[GUI] WM_QUIT received in message pump - ignoring");
+                }
+                match self.tray_manager.as_mut().and_then(|tray| tray.poll()) {
+                    Some(TrayToGui::ActivateProfile(name)) => {
+                        return self.update(Message::TrayProfileSelected(name));
+                    }
+                    Some(TrayToGui::CycleCrosshair) => {
+                        self.cycle_crosshair();
+                    }
+                    Some(TrayToGui::AdjustCrosshair) => {
+                        self.start_crosshair_adjust();
+                    }
+                    Some(TrayToGui::SelectOffsetPreset(preset)) => {
+                        self.select_offset_preset(preset);
+                    }
+                    Some(TrayToGui::ToggleOverlay) => {
+                        self.toggle_overlay();
+                    }
+                    Some(TrayToGui::TogglePause) => {
+                        self.set_paused(!self.paused);
+                    }
+                    Some(TrayToGui::DeactivateProfile) => {
+                        self.deactivate_profile();
+                    }
+                    Some(TrayToGui::OpenSettings) => {
+                        focus_window = true;
+                    }
+                    Some(TrayToGui::SetAutostart(enabled)) => {
+                        self.save_autostart_settings(AutostartConfig { enabled, ..self.autostart.clone() });
+                    }
+                    Some(TrayToGui::Exit) if self.resolve_unsaved_edits() => {
+                        return self.update(Message::TrayExit);
+                    }
+                    _ => {}
+                }
+                
+                if focus_window {
+                    return show_main_window();
+                }
+                // Notified like a tray activation, since the window is usually hidden behind the game
+                if let Some(name) = self.poll_game_launches() {
+                    return self.update(Message::TrayProfileSelected(name));
+                }
+            }
+            
+            Message::TrayProfileSelected(name) => {
+                self.tray_activation = true;
+                let command = self.request_activation(&name);
+                self.tray_activation = false;
+                return command;
+            }
+            
+            Message::TrayDeactivate => {
+                self.deactivate_profile();
+            }
+            
+            Message::WindowCloseRequested => {
+                if self.resolve_unsaved_edits() {
+                    return self.update(Message::TrayExit);
+                }
+            }
+            
+            Message::TrayExit => {
+                if let Some(deactivate) = self.confirm_exit() {
+                    self.exit(deactivate);
+                }
+            }
+            
+            Message::RestartNormally => {
+                if let Some(deactivate) = self.confirm_exit() {
+                    // Otherwise the new copy would find this one still running and hand over to it
+                    crate::single_instance::release();
+                    let started = std::env::current_exe()
+                        .and_then(|exe| std::process::Command::new(exe).args(config::relaunch_args()).spawn());
+                    match started {
+                        Ok(_) => self.exit(deactivate),
+                        Err(e) => self.status_message = t!("status-restart-failed", error = e),
+                    }
+                }
+            }
+            
+            Message::RestartAsAdmin => {
+                if let Some(deactivate) = self.confirm_exit() {
+                    crate::single_instance::release();
+                    match elevation::restart_as_admin(&config::relaunch_args()) {
+                        Ok(()) => self.exit(deactivate),
+                        Err(e) => {
+                            // Declined UAC prompt: keep running as the only copy
+                            crate::single_instance::acquire();
+                            self.status_message = t!("status-restart-failed", error = e);
+                        }
+                    }
+                }
+            }
+            
+            Message::WindowMoved(x, y) => {
+                // Minimizing moves the window far off screen
+                if x > MINIMIZED_POSITION && y > MINIMIZED_POSITION {
+                    self.window_layout.position = Some((x, y));
+                }
+            }
+            
+            Message::WindowResized(width, height) => {
+                if width > 0 && height > 0 {
+                    self.window_layout.width = width as f32;
+                    self.window_layout.height = height as f32;
+                }
+            }
+            
+            Message::ProfileNameChanged(name) => {
+                self.edit_name = name;
+            }
+            
+            Message::ProfileSelected(index) => {
+                if !self.resolve_unsaved_edits() {
+                    return Command::none();
+                }
+                self.view = View::Editor;
+                self.load_profile_to_edit(index);
+                self.status_message = t!("status-editing-profile", profile = self.edit_name);
+            }
+            
+            Message::ProfileFilterChanged(filter) => {
+                self.profile_filter = filter;
+            }
+            
+            Message::ToggleQuickSwitch => {
+                if self.quick_switch.take().is_none() {
+                    self.quick_switch = Some(QuickSwitch::default());
+                    return text_input::focus(text_input::Id::new(QUICK_SWITCH_INPUT));
+                }
+            }
+            
+            Message::CloseQuickSwitch => {
+                self.quick_switch = None;
+            }
+            
+            Message::QuickSwitchQueryChanged(query) => {
+                if let Some(ref mut quick_switch) = self.quick_switch {
+                    quick_switch.query = query;
+                    quick_switch.selected = 0;
+                }
+            }
+            
+            Message::QuickSwitchMove(step) => {
+                let count = self.quick_switch.as_ref().map_or(0, |quick_switch| self.profile_matches(&quick_switch.query, false).len());
+                if let Some(ref mut quick_switch) = self.quick_switch {
+                    if count > 0 {
+                        quick_switch.selected = (quick_switch.selected as i32 + step).rem_euclid(count as i32) as usize;
+                    }
+                }
+            }
+            
+            Message::QuickSwitchSubmit { edit } => {
+                let Some(ref quick_switch) = self.quick_switch else { return Command::none() };
+                if let Some(&index) = self.profile_matches(&quick_switch.query, false).get(quick_switch.selected) {
+                    return self.update(Message::QuickSwitchPick { index, edit });
+                }
+            }
+            
+            Message::QuickSwitchPick { index, edit } => {
+                self.quick_switch = None;
+                if edit {
+                    return self.update(Message::ProfileSelected(index));
+                }
+                if let Some(profile) = self.profiles.get(index) {
+                    return self.request_activation(&profile.name.clone());
+                }
+            }
+            
+            Message::NewProfile => {
+                if !self.resolve_unsaved_edits() {
+                    return Command::none();
+                }
+                self.view = View::Editor;
+                self.clear_edit_form();
+                self.status_message = t!("status-creating-profile");
+            }
+            
+            Message::ShowDashboard => {
+                self.view = View::Dashboard;
+            }
+            
+            Message::EditorTabSelected(tab) => {
+                self.editor_tab = tab;
+            }
+            
+            Message::ShowStats => {
+                self.view = View::Stats;
+            }
+            
+            Message::ShowSettings => {
+                self.view = View::Settings;
+            }
+            
+            Message::LanguageSelected(choice) => {
+                self.language = match choice {
+                    LanguageChoice::System => None,
+                    LanguageChoice::Language { tag, .. } => Some(tag),
+                };
+                i18n::set_language(self.language.as_deref());
+                let mut app_config = load_config();
+                app_config.language = self.language.clone();
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
+            }
+            
+            Message::OpenTranslations => {
+                // Created on demand so there's somewhere to drop a translation file
+                if let Some(dir) = i18n::locales_dir() {
+                    if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| open::that(&dir)) {
+                        self.status_message = t!("status-translations-open-failed", error = e);
+                    }
+                }
+                self.languages = i18n::available_languages();
+            }
+            
+            Message::ThemeModeSelected(mode) => {
+                self.theme_config.mode = mode;
+                self.save_theme_settings();
+            }
+            
+            Message::AccentSelected(accent) => {
+                self.theme_config.accent = accent;
+                self.save_theme_settings();
+            }
+            
+            Message::TraySingleClickSelected(action) => {
+                self.tray_clicks.single_click = action;
+                self.save_tray_click_settings();
+            }
+            
+            Message::TrayDoubleClickSelected(action) => {
+                self.tray_clicks.double_click = action;
+                self.save_tray_click_settings();
+            }
+            
+            Message::TrayMiddleClickSelected(action) => {
+                self.tray_clicks.middle_click = action;
+                self.save_tray_click_settings();
+            }
+            
+            Message::TrayTooltipChanged(template) => {
+                self.tray_tooltip.template = template;
+            }
+            
+            Message::SaveTrayTooltip => {
+                self.save_tray_tooltip_settings();
+            }
+            
+            Message::RunBenchmark => {
+                self.start_benchmark();
+            }
+            
+            Message::SaveProfile => {
+                if self.save_edited_profile() {
+                    self.update_tray();
+                }
+            }
+            
+            Message::DeleteProfile => {
+                if let Some(index) = self.selected_profile_index {
+                    let name = self.profiles[index].name.clone();
+                    self.profiles.remove(index);
+                    self.clear_edit_form();
+                    self.save_profiles_to_disk();
+                    self.update_tray();
+                    self.status_message = t!("status-profile-deleted", profile = name);
+                }
+            }
+            
+            Message::ActivateProfile => {
+                match self.selected_profile_index.and_then(|index| self.profiles.get(index)) {
+                    Some(profile) => return self.request_activation(&profile.name.clone()),
+                    None => self.status_message = t!("status-no-profile-selected"),
+                }
+            }
+            
+            Message::ConfirmKill => {
+                let Some(confirmation) = self.kill_confirmation.take() else { return Command::none() };
+                if confirmation.dont_ask_again {
+                    if let Some(profile) = self.profiles.iter_mut().find(|p| p.name == confirmation.profile) {
+                        profile.skip_kill_confirmation = true;
+                        if self.selected_profile_index.and_then(|index| self.profiles.get(index)).is_some_and(|p| p.name == confirmation.profile) {
+                            self.edit_skip_kill_confirmation = true;
+                            self.edit_baseline.skip_kill_confirmation = true;
+                        }
+                        self.save_profiles_to_disk();
+                    }
+                }
+                self.activate_selected_or_named(&confirmation.profile);
+            }
+            
+            Message::CancelKill => {
+                if let Some(confirmation) = self.kill_confirmation.take() {
+                    self.status_message = t!("status-activation-cancelled", profile = confirmation.profile);
+                }
+            }
+            
+            Message::KillDontAskAgainToggled(enabled) => {
+                if let Some(ref mut confirmation) = self.kill_confirmation {
+                    confirmation.dont_ask_again = enabled;
+                }
+            }
+            
+            Message::ToggleKillCategory(category) => {
+                if let Some(ref mut report) = self.activation_report {
+                    if !report.expanded.remove(&category) {
+                        report.expanded.insert(category);
+                    }
+                }
+            }
+            
+            Message::CopyActivationReport => {
+                if let Some(ref report) = self.activation_report {
+                    self.status_message = t!("status-report-copied");
+                    return iced::clipboard::write(report.text());
+                }
+            }
+            
+            Message::DismissActivationReport => {
+                self.activation_report = None;
+            }
+            
+            Message::FixOverlay(injector) => {
+                self.fix_overlay(injector);
+            }
+            
+            Message::CheckProfiles => {
+                self.lint_results = profile_lint::lint_all(&self.profiles);
+                self.status_message = if self.lint_results.is_empty() {
+                    t!("status-lint-clean", count = self.profiles.len())
+                } else {
+                    t!("status-lint-found", count = self.lint_results.len())
+                };
+            }
+            
+            Message::DismissLint => {
+                self.lint_results.clear();
+            }
+            
+            Message::ProcessToggled(process, enabled) => {
+                self.process_selection.insert(process, enabled);
+            }
+            
+            Message::ImportKillList => {
+                self.import_kill_list();
+            }
+            
+            Message::ApplySuggestion(suggestion) => {
+                match suggestion {
+                    Suggestion::Kill(ref exe) => self.select_process(exe),
+                    Suggestion::CloseOverlay(ref overlay) => self.select_process(&overlay.executable),
+                    Suggestion::PowerPlan(ref guid) => self.edit_power_plan = Some(guid.clone()),
+                    Suggestion::HighPrecisionTimer => self.edit_high_precision_timer = true,
+                }
+                self.status_message = t!("status-suggestion-applied", suggestion = self.suggestion_label(&suggestion));
+            }
+            
+            Message::DismissGameSuggestions => {
+                self.hide_game_suggestions = true;
+            }
+            
+            Message::RefreshProcesses => {
+                self.common_apps = common_apps::load_common_apps(self.data_dir.as_deref());
+                self.refresh_running_processes();
+                self.status_message = t!("status-processes-refreshed", count = self.running_processes.len());
+            }
+            
+            Message::ProcessSortSelected(column) => {
+                self.process_list.sort_by(column);
+                return self.scroll_process_list_to_top();
+            }
+            
+            Message::ProcessSelectedOnlyToggled(selected_only) => {
+                self.process_list.selected_only = selected_only;
+                return self.scroll_process_list_to_top();
+            }
+            
+            Message::ProcessListScrolled(viewport) => {
+                self.process_list.scroll_offset = viewport.absolute_offset().y;
+            }
+            
+            Message::ProcessFilterChanged(filter) => {
+                self.process_filter = filter;
+                return self.scroll_process_list_to_top();
+            }
+            
+            Message::CrosshairOffsetXChanged(value) => {
+                self.edit_x_offset = value;
+            }
+            
+            Message::CrosshairOffsetYChanged(value) => {
+                self.edit_y_offset = value;
+            }
+            
+            Message::CrosshairMoveUp => {
+                let current: i32 = self.edit_y_offset.parse().unwrap_or(0);
+                self.edit_y_offset = (current - 1).to_string();
+                self.update_live_overlay();
+            }
+            
+            Message::CrosshairMoveDown => {
+                let current: i32 = self.edit_y_offset.parse().unwrap_or(0);
+                self.edit_y_offset = (current + 1).to_string();
+                self.update_live_overlay();
+            }
+            
+            Message::CrosshairMoveLeft => {
+                let current: i32 = self.edit_x_offset.parse().unwrap_or(0);
+                self.edit_x_offset = (current - 1).to_string();
+                self.update_live_overlay();
+            }
+            
+            Message::CrosshairMoveRight => {
+                let current: i32 = self.edit_x_offset.parse().unwrap_or(0);
+                self.edit_x_offset = (current + 1).to_string();
+                self.update_live_overlay();
+            }
+            
+            Message::CrosshairCenter => {
+                self.edit_x_offset = "0".to_string();
+                self.edit_y_offset = "0".to_string();
+                self.status_message = t!("status-crosshair-centered");
+                self.update_live_overlay();
+            }
+            
+            Message::AnchorSelected(anchor) => {
+                self.edit_anchor = anchor;
+                let (anchor_x, anchor_y) = anchor.percent();
+                self.edit_anchor_x = anchor_x.to_string();
+                self.edit_anchor_y = anchor_y.to_string();
+                self.update_live_overlay();
+            }
+            
+            Message::AnchorPercentXChanged(value) => {
+                self.edit_anchor_x = value;
+                self.apply_anchor_percent();
+            }
+            
+            Message::AnchorPercentYChanged(value) => {
+                self.edit_anchor_y = value;
+                self.apply_anchor_percent();
+            }
+            
+            Message::OverlayEnabledToggled(enabled) => {
+                self.edit_overlay_enabled = enabled;
+            }
+            
+            Message::ExcludeFromCaptureToggled(enabled) => {
+                self.edit_exclude_from_capture = enabled;
+                self.update_live_overlay();
+            }
+            
+            Message::TintSelected(tint) => {
+                self.edit_tint = tint;
+                self.edit_tint_hex = tint.map(format_hex_color).unwrap_or_default();
+                self.update_live_overlay();
+            }
+            
+            Message::PresetNameChanged(name) => {
+                self.edit_preset_name = name;
+            }
+            
+            Message::AddPreset => {
+                let name = self.edit_preset_name.trim().to_string();
+                if name.is_empty() {
+                    self.status_message = t!("status-empty-preset-name");
+                    return Command::none();
+                }
+                
+                let preset = CrosshairPreset {
+                    name: name.clone(),
+                    image_path: self.edit_image_path.clone(),
+                    x_offset: self.edit_x_offset.parse().unwrap_or(0),
+                    y_offset: self.edit_y_offset.parse().unwrap_or(0),
+                    tint: self.edit_tint,
+                };
+                
+                // Saving under an existing name overwrites that preset
+                match self.edit_presets.iter().position(|p| p.name.eq_ignore_ascii_case(&name)) {
+                    Some(index) => self.edit_presets[index] = preset,
+                    None => self.edit_presets.push(preset),
+                }
+                self.edit_preset_name = String::new();
+                self.status_message = t!("status-preset-added", preset = name);
+            }
+            
+            Message::LoadPreset(index) => {
+                if let Some(preset) = self.edit_presets.get(index).cloned() {
+                    self.edit_image_path = preset.image_path;
+                    self.edit_x_offset = preset.x_offset.to_string();
+                    self.edit_y_offset = preset.y_offset.to_string();
+                    self.edit_tint = preset.tint;
+                    self.edit_tint_hex = preset.tint.map(format_hex_color).unwrap_or_default();
+                    self.edit_preset_name = preset.name.clone();
+                    self.status_message = t!("status-preset-loaded", preset = preset.name);
+                    self.update_live_overlay();
+                }
+            }
+            
+            Message::DeletePreset(index) => {
+                if index < self.edit_presets.len() {
+                    let preset = self.edit_presets.remove(index);
+                    self.status_message = t!("status-preset-removed", preset = preset.name);
+                }
+            }
+            
+            Message::OffsetPresetNameChanged(name) => {
+                self.edit_offset_preset_name = name;
+            }
+            
+            Message::AddOffsetPreset => {
+                let name = self.edit_offset_preset_name.trim().to_string();
+                if name.is_empty() {
+                    self.status_message = t!("status-empty-offset-preset-name");
+                    return Command::none();
+                }
+                
+                let x_offset = self.edit_x_offset.parse().unwrap_or(0);
+                let y_offset = self.edit_y_offset.parse().unwrap_or(0);
+                
+                // Saving under an existing name overwrites that preset (keeping its spelling, which
+                // the selection refers to)
+                match self.edit_offset_presets.iter_mut().find(|p| p.name.eq_ignore_ascii_case(&name)) {
+                    Some(preset) => {
+                        preset.x_offset = x_offset;
+                        preset.y_offset = y_offset;
+                    }
+                    None => self.edit_offset_presets.push(OffsetPreset { name: name.clone(), x_offset, y_offset }),
+                }
+                self.edit_offset_preset_name = String::new();
+                self.status_message = t!("status-preset-added", preset = name);
+            }
+            
+            Message::LoadOffsetPreset(index) => {
+                if let Some(preset) = self.edit_offset_presets.get(index).cloned() {
+                    self.edit_x_offset = preset.x_offset.to_string();
+                    self.edit_y_offset = preset.y_offset.to_string();
+                    self.edit_offset_preset_name = preset.name.clone();
+                    self.status_message = t!("status-preset-loaded", preset = preset.name);
+                    self.update_live_overlay();
+                }
+            }
+            
+            Message::UseOffsetPreset(index) => {
+                if let Some(preset) = self.edit_offset_presets.get(index) {
+                    let selected = self.edit_selected_offset_preset.as_ref() == Some(&preset.name);
+                    self.edit_selected_offset_preset = (!selected).then(|| preset.name.clone());
+                }
+            }
+            
+            Message::DeleteOffsetPreset(index) => {
+                if index < self.edit_offset_presets.len() {
+                    let preset = self.edit_offset_presets.remove(index);
+                    if self.edit_selected_offset_preset.as_ref() == Some(&preset.name) {
+                        self.edit_selected_offset_preset = None;
+                    }
+                    self.status_message = t!("status-preset-removed", preset = preset.name);
+                }
+            }
+            
+            Message::CycleCrosshair => {
+                self.cycle_crosshair();
+            }
+            
+            Message::TintHexChanged(value) => {
+                // Only apply once the text is a complete color; empty clears the tint
+                if value.trim().is_empty() {
+                    self.edit_tint = None;
+                    self.update_live_overlay();
+                } else if let Some(tint) = parse_hex_color(&value) {
+                    self.edit_tint = Some(tint);
+                    self.update_live_overlay();
+                }
+                self.edit_tint_hex = value;
+            }
+            
+            Message::NoteTextEdited(action) => {
+                // Text changes are applied on save/activate rather than restarting the overlay per keystroke
+                self.edit_note_text.perform(action);
+            }
+            
+            Message::NoteSizeChanged(value) => {
+                if value.chars().all(|c| c.is_ascii_digit()) && value.len() <= 2 {
+                    self.edit_note_size = value;
+                }
+            }
+            
+            Message::NoteCornerSelected(corner) => {
+                self.edit_note_corner = corner;
+                self.update_live_overlay();
+            }
+            
+            Message::HardwareHudSelected(choice) => {
+                self.edit_hardware_hud = match choice {
+                    HudCornerChoice::Off => None,
+                    HudCornerChoice::Corner(corner) => Some(corner),
+                };
+                self.update_live_overlay();
+            }
+            
+            Message::NoteColorChanged(value) => {
+                let complete = parse_hex_color(&value).is_some();
+                self.edit_note_color_hex = value;
+                if complete {
+                    self.update_live_overlay();
+                }
+            }
+            
+            Message::FanSpeedMaxToggled(enabled) => {
+                self.edit_fan_speed_max = enabled;
+            }
+            
+            Message::PowerPlanSelected(choice) => {
+                self.edit_power_plan = match choice {
+                    PowerPlanChoice::Unchanged => None,
+                    PowerPlanChoice::Plan(plan) => Some(plan.guid),
+                };
+            }
+            
+            Message::GameFeatureSelected(feature, choice) => {
+                let value = choice.as_option();
+                match feature {
+                    GameFeature::GameMode => self.edit_game_features.game_mode = value,
+                    GameFeature::GameBar => self.edit_game_features.game_bar = value,
+                    GameFeature::BackgroundRecording => self.edit_game_features.background_recording = value,
+                }
+            }
+            
+            Message::HighPrecisionTimerToggled(enabled) => {
+                self.edit_high_precision_timer = enabled;
+            }
+            
+            Message::BestPerformanceVisualsToggled(enabled) => {
+                self.edit_best_performance_visuals = enabled;
+            }
+            
+            Message::DisplayMonitorSelected(monitor) => {
+                self.select_display_device(Some(monitor.device));
+            }
+            
+            Message::DisplayModeSelected(choice) => {
+                self.edit_display_mode = match choice {
+                    DisplayModeChoice::Unchanged => None,
+                    DisplayModeChoice::Mode(mode) => Some(mode),
+                };
+            }
+            
+            Message::MasterVolumeChanged(value) => {
+                self.edit_master_volume = value;
+            }
+            
+            Message::AppVolumeProcessChanged(value) => {
+                self.edit_app_volume_process = value;
+            }
+            
+            Message::AppVolumePercentChanged(value) => {
+                self.edit_app_volume_percent = value;
+            }
+            
+            Message::AddAppVolume => {
+                let process = self.edit_app_volume_process.trim().to_string();
+                let percent = match self.edit_app_volume_percent.trim().parse::<u8>() {
+                    Ok(percent) if percent <= 100 => percent,
+                    _ => {
+                        self.status_message = t!("status-invalid-app-volume");
+                        return Command::none();
+                    }
+                };
+                if process.is_empty() {
+                    self.status_message = t!("status-empty-app-volume-process");
+                    return Command::none();
+                }
+                
+                // Adding an app that's already listed updates its level
+                let volume = AppVolume { process: process.clone(), percent };
+                match self.edit_app_volumes.iter().position(|v| v.process.eq_ignore_ascii_case(&process)) {
+                    Some(index) => self.edit_app_volumes[index] = volume,
+                    None => self.edit_app_volumes.push(volume),
+                }
+                self.edit_app_volume_process = String::new();
+                self.edit_app_volume_percent = String::new();
+            }
+            
+            Message::RemoveAppVolume(index) => {
+                if index < self.edit_app_volumes.len() {
+                    self.edit_app_volumes.remove(index);
+                }
+            }
+            
+            Message::PauseDeliveryOptimizationToggled(enabled) => {
+                self.edit_pause_delivery_optimization = enabled;
+            }
+            
+            Message::LimitProcessChanged(value) => {
+                self.edit_limit_process = value;
+            }
+            
+            Message::LimitKbpsChanged(value) => {
+                self.edit_limit_kbps = value;
+            }
+            
+            Message::AddBandwidthLimit => {
+                let process = self.edit_limit_process.trim().to_string();
+                let limit_kbps = match self.edit_limit_kbps.trim().parse::<u32>() {
+                    Ok(kbps) if kbps > 0 => kbps,
+                    _ => {
+                        self.status_message = t!("status-invalid-bandwidth-limit");
+                        return Command::none();
+                    }
+                };
+                if process.is_empty() {
+                    self.status_message = t!("status-empty-bandwidth-limit-process");
+                    return Command::none();
+                }
+                
+                // Adding a process that's already listed updates its cap
+                let limit = BandwidthLimit { process: process.clone(), limit_kbps };
+                match self.edit_bandwidth_limits.iter().position(|l| l.process.eq_ignore_ascii_case(&process)) {
+                    Some(index) => self.edit_bandwidth_limits[index] = limit,
+                    None => self.edit_bandwidth_limits.push(limit),
+                }
+                self.edit_limit_process = String::new();
+                self.edit_limit_kbps = String::new();
+            }
+            
+            Message::RemoveBandwidthLimit(index) => {
+                if index < self.edit_bandwidth_limits.len() {
+                    self.edit_bandwidth_limits.remove(index);
+                }
+            }
+            
+            Message::PauseSearchIndexingToggled(enabled) => {
+                self.edit_storage.pause_search_indexing = enabled;
+            }
+            
+            Message::PauseMaintenanceTasksToggled(enabled) => {
+                self.edit_storage.pause_maintenance_tasks = enabled;
+            }
+            
+            Message::RgbSceneSelected(choice) => {
+                self.edit_rgb_scene = match choice {
+                    RgbSceneChoice::Unchanged => None,
+                    RgbSceneChoice::SolidColor => {
+                        let [r, g, b, _] = parse_hex_color(&self.edit_rgb_hex).unwrap_or([255, 0, 0, 255]);
+                        self.edit_rgb_hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
+                        Some(RgbScene::Color([r, g, b]))
+                    }
+                    RgbSceneChoice::Profile(name) => Some(RgbScene::Profile(name)),
+                };
+            }
+            
+            Message::RgbColorChanged(hex) => {
+                if let Some([r, g, b, _]) = parse_hex_color(&hex) {
+                    self.edit_rgb_scene = Some(RgbScene::Color([r, g, b]));
+                }
+                self.edit_rgb_hex = hex;
+            }
+            
+            Message::OpenRgbHostChanged(host) => {
+                self.openrgb_host = host;
+            }
+            
+            Message::OpenRgbPortChanged(port) => {
+                self.openrgb_port = port;
+            }
+            
+            Message::ConnectOpenRgb => {
+                let Ok(port) = self.openrgb_port.trim().parse() else {
+                    self.status_message = t!("status-invalid-openrgb-port");
+                    return Command::none();
+                };
+                self.openrgb = OpenRgbConfig {
+                    host: self.openrgb_host.trim().to_string(),
+                    port,
+                };
+                
+                let mut app_config = load_config();
+                app_config.openrgb = self.openrgb.clone();
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                    return Command::none();
+                }
+                
+                match openrgb::list_profiles(&self.openrgb) {
+                    Ok(profiles) => {
+                        self.status_message = t!("status-openrgb-connected", count = profiles.len());
+                        self.openrgb_profiles = profiles;
+                    }
+                    Err(e) => self.status_message = t!("status-openrgb-failed", error = e),
+                }
+            }
+            
+            Message::ObsSceneSelected(choice) => {
+                self.edit_obs.scene = match choice {
+                    ObsSceneChoice::Unchanged => None,
+                    ObsSceneChoice::Scene(name) => Some(name),
+                };
+            }
+            
+            Message::ObsRecordToggled(enabled) => {
+                self.edit_obs.record = enabled;
+            }
+            
+            Message::ObsReplayBufferToggled(enabled) => {
+                self.edit_obs.replay_buffer = enabled;
+            }
+            
+            Message::ObsHostChanged(host) => {
+                self.obs_host = host;
+            }
+            
+            Message::ObsPortChanged(port) => {
+                self.obs_port = port;
+            }
+            
+            Message::ObsPasswordChanged(password) => {
+                self.obs_password = password;
+            }
+            
+            Message::ConnectObs => {
+                let Ok(port) = self.obs_port.trim().parse() else {
+                    self.status_message = t!("status-invalid-obs-port");
+                    return Command::none();
+                };
+                self.obs = ObsConfig {
+                    host: self.obs_host.trim().to_string(),
+                    port,
+                    password: self.obs_password.clone(),
+                };
+                
+                let mut app_config = load_config();
+                app_config.obs = self.obs.clone();
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                    return Command::none();
+                }
+                
+                match obs::list_scenes(&self.obs) {
+                    Ok(scenes) => {
+                        self.status_message = t!("status-obs-connected", count = scenes.len());
+                        self.obs_scenes = scenes;
+                    }
+                    Err(e) => self.status_message = t!("status-obs-failed", error = e),
+                }
+            }
+            
+            Message::SortCapturesToggled(enabled) => {
+                self.edit_captures.sort = enabled;
+            }
+            
+            Message::ChooseCaptureFolder => {
+                if let Some(folder) = rfd::FileDialog::new().set_title(t!("captures-folder-dialog-title")).pick_folder() {
+                    self.edit_captures.target_folder = Some(folder.display().to_string());
+                }
+            }
+            
+            Message::ClearCaptureFolder => {
+                self.edit_captures.target_folder = None;
+            }
+            
+            Message::DiscordPresenceToggled(enabled) => {
+                self.discord_config.enabled = enabled;
+                self.save_discord_settings();
+            }
+            
+            Message::DiscordApplicationIdChanged(id) => {
+                self.discord_config.application_id = id;
+            }
+            
+            Message::SaveDiscordSettings => {
+                self.save_discord_settings();
+            }
+            
+            Message::EventLogToggled(enabled) => {
+                self.event_log.enabled = enabled;
+                self.save_event_log_settings();
+            }
+            
+            Message::PluginToggled(file, enabled) => {
+                self.plugin_config.set_enabled(&file, enabled);
+                let mut app_config = load_config();
+                app_config.plugins = self.plugin_config.clone();
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
+                self.restart_plugins();
+            }
+            
+            Message::OpenPluginsFolder => {
+                // Created on demand so there's somewhere to put a script
+                if let Some(ref data_dir) = self.data_dir {
+                    let dir = data_dir.join(plugins::PLUGINS_FOLDER);
+                    if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| open::that(&dir)) {
+                        self.status_message = t!("status-plugins-open-failed", error = e);
+                    }
+                }
+            }
+            
+            Message::ReloadPlugins => {
+                self.restart_plugins();
+                self.status_message = t!("status-plugins-reloaded", count = self.plugin_config.enabled.len());
+            }
+            
+            Message::MonitorIntervalChanged(value) => {
+                if value.chars().all(|c| c.is_ascii_digit() || c == '.') && value.len() <= 5 {
+                    self.monitor_interval = value;
+                }
+            }
+            
+            Message::SaveMonitorSettings => {
+                self.save_monitor_settings();
+            }
+            
+            Message::AlertsToggled(enabled) => {
+                self.alert_config.enabled = enabled;
+                self.save_alert_settings();
+            }
+            
+            Message::AlertCpuLimitChanged(value) => {
+                if is_limit_input(&value) {
+                    self.alert_cpu_limit = value;
+                }
+            }
+            
+            Message::AlertGpuLimitChanged(value) => {
+                if is_limit_input(&value) {
+                    self.alert_gpu_limit = value;
+                }
+            }
+            
+            Message::AlertMaxFansToggled(enabled) => {
+                self.alert_config.max_fans = enabled;
+                self.save_alert_settings();
+            }
+            
+            Message::AlertSwitchProfileSelected(choice) => {
+                self.alert_config.switch_to_profile = match choice {
+                    SwitchProfileChoice::None => None,
+                    SwitchProfileChoice::Profile(name) => Some(name),
+                };
+                self.save_alert_settings();
+            }
+            
+            Message::SaveAlertSettings => {
+                self.save_alert_settings();
+            }
+            
+            Message::DoNotDisturbToggled(enabled) => {
+                self.edit_do_not_disturb = enabled;
+            }
+            
+            Message::MuteNotificationSoundsToggled(enabled) => {
+                self.edit_mute_notification_sounds = enabled;
+            }
+            
+            Message::GpuPreferMaxToggled(enabled) => {
+                self.edit_gpu_prefer_max = enabled;
+            }
+            
+            Message::NvidiaProfileChanged(name) => {
+                self.edit_nvidia_profile = name;
+            }
+            
+            Message::KeyboardLayoutSelected(choice) => {
+                self.edit_keyboard_layout = match choice {
+                    KeyboardLayoutChoice::Unchanged => None,
+                    KeyboardLayoutChoice::Layout(layout) => Some(layout.id),
+                };
+            }
+            
+            Message::BlockWindowsKeyToggled(enabled) => {
+                self.edit_block_windows_key = enabled;
+            }
+            
+            Message::SuppressStickyKeysToggled(enabled) => {
+                self.edit_suppress_sticky_keys = enabled;
+            }
+            
+            Message::RollbackOnFailureToggled(enabled) => {
+                self.edit_rollback_on_failure = enabled;
+            }
+            
+            Message::SkipKillConfirmationToggled(skip) => {
+                self.edit_skip_kill_confirmation = skip;
+            }
+            
+            Message::FavoriteToggled(favorite) => {
+                self.edit_favorite = favorite;
+            }
+            
+            Message::ArchivedToggled(archived) => {
+                self.edit_archived = archived;
+            }
+            
+            Message::AntiCheatSafeToggled(enabled) => {
+                self.edit_anti_cheat_safe = enabled;
+            }
+            
+            Message::ToggleArchivedList => {
+                self.show_archived = !self.show_archived;
+            }
+            
+            Message::RestoreProfile(index) => {
+                let Some(profile) = self.profiles.get_mut(index) else { return Command::none() };
+                profile.archived = false;
+                let name = profile.name.clone();
+                // The editor shows the restored profile without counting it as an edit
+                if self.selected_profile_index == Some(index) {
+                    self.edit_archived = false;
+                    self.edit_baseline.archived = false;
+                }
+                if self.save_profiles_to_disk() {
+                    self.status_message = t!("status-profile-restored", profile = name);
+                }
+                self.update_tray();
+            }
+            
+            Message::ActivateFavorite(name) => {
+                return self.request_activation(&name);
+            }
+            
+            Message::ProfileCpuLimitChanged(value) => {
+                if is_limit_input(&value) {
+                    self.edit_cpu_limit = value;
+                }
+            }
+            
+            Message::ProfileGpuLimitChanged(value) => {
+                if is_limit_input(&value) {
+                    self.edit_gpu_limit = value;
+                }
+            }
+            
+            Message::SystemTweakToggled(tweak, enabled) => {
+                self.edit_system_tweaks.retain(|t| *t != tweak);
+                if enabled {
+                    self.edit_system_tweaks.push(tweak);
+                }
+            }
+            
+            Message::RefreshDiagnostics => {
+                self.timer_resolution = timer::query_timer_resolution().ok();
+                self.overlay_render_time = Some(self.measure_overlay_render());
+            }
+            
+            Message::LogLevelSelected(level) => {
+                self.log_level = level;
+                logging::set_level(level);
+                let mut app_config = load_config();
+                app_config.log_level = level;
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
+            }
+            
+            Message::OpenLogs => {
+                logging::open_log_dir();
+            }
+            
+            Message::ToastCategoryToggled(category, enabled) => {
+                self.toast_config.set_enabled(category, enabled);
+                let mut app_config = load_config();
+                app_config.toasts = self.toast_config.clone();
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
+            }
+            
+            Message::UpdateChecksToggled(enabled) => {
+                self.update_config.enabled = enabled;
+                self.save_update_settings();
+            }
+            
+            Message::UpdateChannelSelected(channel) => {
+                self.update_config.channel = channel;
+                // A different channel can have a different answer
+                self.available_update = None;
+                self.save_update_settings();
+            }
+            
+            Message::UpdateIntervalChanged(hours) => {
+                if hours.chars().all(|c| c.is_ascii_digit()) && hours.len() <= 4 {
+                    self.update_interval = hours;
+                }
+            }
+            
+            Message::SaveUpdateSettings => {
+                self.save_update_settings();
+            }
+            
+            Message::CheckForUpdates => {
+                self.start_update_check();
+            }
+            
+            Message::InstallUpdate => {
+                self.install_update();
+            }
+            
+            Message::OpenReleaseNotes => {
+                if let Some(ref release) = self.available_update {
+                    if let Err(e) = open::that(&release.page_url) {
+                        self.status_message = t!("status-release-notes-failed", error = e);
+                    }
+                }
+            }
+            
+            Message::HotkeysToggled(enabled) => {
+                self.hotkey_config.enabled = enabled;
+                self.save_hotkey_settings();
+            }
+            
+            Message::HotkeyBindingChanged(binding) => {
+                self.hotkey_binding = binding;
+            }
+            
+            Message::SaveHotkeySettings => {
+                self.save_hotkey_settings();
+            }
+            
+            Message::GamepadToggled(enabled) => {
+                self.save_gamepad_settings(enabled);
+            }
+            
+            Message::GamepadToggleOverlayChanged(combo) => {
+                self.gamepad_toggle_overlay = combo;
+            }
+            
+            Message::GamepadCycleCrosshairChanged(combo) => {
+                self.gamepad_cycle_crosshair = combo;
+            }
+            
+            Message::SaveGamepadSettings => {
+                self.save_gamepad_settings(self.gamepad_config.enabled);
+            }
+            
+            Message::ChooseSyncFolder => {
+                if let Some(folder) = rfd::FileDialog::new().set_title(t!("sync-folder-dialog-title")).pick_folder() {
+                    self.sync_folder = Some(folder);
+                    self.save_sync_folder();
+                    self.sync_profiles_now();
+                }
+            }
+            
+            Message::ClearSyncFolder => {
+                self.sync_folder = None;
+                self.save_sync_folder();
+            }
+            
+            Message::SyncProfilesNow => {
+                self.sync_profiles_now();
+            }
+            
+            Message::ExportBackup => {
+                self.export_backup();
+            }
+            
+            Message::ImportBackup => {
+                self.import_backup();
+            }
+            
+            Message::ChooseDataFolder => {
+                if let Some(folder) = rfd::FileDialog::new().set_title(t!("data-folder-dialog-title")).pick_folder() {
+                    self.move_data_directory(Some(folder));
+                }
+            }
+            
+            Message::ResetDataFolder => {
+                self.move_data_directory(None);
+            }
+            
+            Message::ConfirmKillToggled(enabled) => {
+                self.safety_config.confirm_kill = enabled;
+                self.save_safety_settings();
+            }
+            
+            Message::ProtectedProcessInputChanged(name) => {
+                self.protected_process_input = name;
+            }
+            
+            Message::AddProtectedProcess => {
+                let name = self.protected_process_input.trim().to_string();
+                let exists = self.safety_config.protected_processes.iter().any(|p| p.eq_ignore_ascii_case(&name));
+                if !name.is_empty() && !exists {
+                    self.safety_config.protected_processes.push(name);
+                    self.save_safety_settings();
+                }
+                self.protected_process_input.clear();
+            }
+            
+            Message::RemoveProtectedProcess(index) => {
+                if index < self.safety_config.protected_processes.len() {
+                    self.safety_config.protected_processes.remove(index);
+                    self.save_safety_settings();
+                }
+            }
+            
+            Message::AutostartToggled(enabled) => {
+                self.save_autostart_settings(AutostartConfig { enabled, ..self.autostart.clone() });
+            }
+            
+            Message::AutostartElevatedToggled(elevated) => {
+                self.save_autostart_settings(AutostartConfig { elevated, ..self.autostart.clone() });
+            }
+            
+            Message::ExitBehaviorSelected(behavior) => {
+                self.exit_behavior = behavior;
+                let mut app_config = load_config();
+                app_config.exit_behavior = behavior;
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
+            }
+            
+            Message::ResumeLastSessionToggled(enabled) => {
+                self.resume_last_session = enabled;
+                let mut app_config = load_config();
+                app_config.resume_last_session = enabled;
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
+            }
+            
+            Message::AutoActivateGamesToggled(enabled) => {
+                self.set_auto_activate_games(enabled);
+                let mut app_config = load_config();
+                app_config.auto_activate_games = enabled;
+                if let Err(e) = save_config(&app_config) {
+                    self.status_message = t!("status-settings-save-failed", error = e);
+                }
+            }
+            
+            Message::RemoteApiToggled(enabled) => {
+                self.api_config.enabled = enabled;
+                self.save_remote_api_settings();
+            }
+            
+            Message::RemoteApiPortChanged(port) => {
+                if port.chars().all(|c| c.is_ascii_digit()) && port.len() <= 5 {
+                    self.api_port = port;
+                }
+            }
+            
+            Message::NewRemoteApiToken => {
+                // Clients using the old token are locked out once the server restarts
+                match remote_api::generate_token() {
+                    Ok(token) => {
+                        self.api_config.token = token;
+                        self.save_remote_api_settings();
+                    }
+                    Err(e) => self.status_message = t!("status-remote-api-failed", error = e),
+                }
+            }
+            
+            Message::SaveRemoteApiSettings => {
+                self.save_remote_api_settings();
+            }
+            
+            Message::SelectImage => {
+                match open_image_picker() {
+                    Ok(path) => {
+                        match validate_crosshair_image(&path) {
+                            Ok(_) => {
+                                let path_str = path.to_string_lossy().to_string();
+                                self.edit_image_path = Some(path_str.clone());
+                                self.edit_crosshair_params = None;
+                                self.status_message = t!("status-image-selected", path = path_str);
+                            }
+                            Err(e) => {
+                                self.status_message = t!("status-image-invalid", error = e);
+                            }
+                        }
+                    }
+                    Err(_) => {}
+                }
+            }
+            
+            Message::ImageDropped(path) => {
+                let library = self.data_dir.as_ref().map(|dir| dir.join("crosshairs"));
+                let imported = library
+                    .ok_or_else(|| anyhow::anyhow!("Data directory unavailable"))
+                    .and_then(|library| crosshair_library::import_to_library(&path, &library));
+                match imported {
+                    Ok(path) => {
+                        let path_str = path.to_string_lossy().to_string();
+                        self.view = View::Editor;
+                        self.editor_tab = EditorTab::Crosshair;
+                        self.edit_image_path = Some(path_str.clone());
+                        self.edit_crosshair_params = None;
+                        self.status_message = t!("status-image-dropped", path = path_str);
+                    }
+                    Err(e) => {
+                        self.status_message = t!("status-image-invalid", error = e);
+                    }
+                }
+            }
+            
+            Message::ClearImage => {
+                self.edit_image_path = None;
+                self.edit_crosshair_params = None;
+                self.status_message = t!("status-crosshair-image-cleared");
+            }
+            
+            Message::CrosshairCodeChanged(code) => {
+                self.edit_crosshair_code = code;
+            }
+            
+            Message::ImportCrosshairCode => {
+                match self.import_crosshair_code() {
+                    Ok(path) => {
+                        self.status_message = t!("status-crosshair-code-imported", path = path);
+                        self.edit_crosshair_code = String::new();
+                        self.update_live_overlay();
+                    }
+                    Err(e) => {
+                        self.status_message = t!("status-crosshair-code-invalid", error = e);
+                    }
+                }
+            }
+        }
+        
+        Command::none()
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        if let Some(ref confirmation) = self.kill_confirmation {
+            return self.render_kill_confirmation(confirmation);
+        }
+        if let Some(ref quick_switch) = self.quick_switch {
+            return self.render_quick_switch(quick_switch);
+        }
+        
+        // Left panel - Profile list
+        let mut profile_list = Column::new()
+            .spacing(5)
+            .padding(10)
+            .push(Text::new(t!("profiles")).size(20))
+            .push(
+                TextInput::new(&t!("profile-search-placeholder"), &self.profile_filter)
+                    .on_input(Message::ProfileFilterChanged)
+                    .padding(6)
+            )
+            .push(Space::new(Length::Fill, Length::Fixed(5.0)));
+        
+        let matches = self.profile_matches(&self.profile_filter, false);
+        let archived = self.profile_matches(&self.profile_filter, true);
+        if matches.is_empty() && archived.is_empty() && !self.profiles.is_empty() {
+            profile_list = profile_list.push(Text::new(t!("no-matching-profiles")).size(12));
+        }
+        for i in matches {
+            let profile = &self.profiles[i];
+            let is_selected = self.selected_profile_index == Some(i);
+            let is_active = self.active_profile_name.as_ref() == Some(&profile.name);
+            
+            let label = if is_active {
+                format!("🟢 {}", profile.name)
+            } else if is_selected {
+                format!("▶ {}", profile.name)
+            } else {
+                profile.name.clone()
+            };
+            
+            profile_list = profile_list.push(
+                Button::new(Text::new(label))
+                    .on_press(Message::ProfileSelected(i))
+                    .width(Length::Fill)
+                    .padding(8)
+            );
+        }
+        
+        // Archived profiles, folded away unless asked for
+        if !archived.is_empty() {
+            let arrow = if self.show_archived { "▾" } else { "▸" };
+            profile_list = profile_list.push(
+                Button::new(Text::new(format!("{} {}", arrow, t!("archived-profiles", count = archived.len()))).size(12))
+                    .on_press(Message::ToggleArchivedList)
+                    .style(iced::theme::Button::Text)
+                    .width(Length::Fill)
+                    .padding(4)
+            );
+        }
+        if self.show_archived {
+            for i in archived {
+                let profile = &self.profiles[i];
+                let label = if self.selected_profile_index == Some(i) { format!("▶ {}", profile.name) } else { profile.name.clone() };
+                profile_list = profile_list.push(
+                    Row::new()
+                        .spacing(4)
+                        .push(
+                            Button::new(Text::new(label).size(13))
+                                .on_press(Message::ProfileSelected(i))
+                                .style(iced::theme::Button::Secondary)
+                                .width(Length::Fill)
+                                .padding(6)
+                        )
+                        .push(
+                            Button::new(Text::new(t!("restore-profile")).size(12))
+                                .on_press(Message::RestoreProfile(i))
+                                .padding(6)
+                        )
+                );
+            }
+        }
+        
+        profile_list = profile_list
+            .push(Space::new(Length::Fill, Length::Fixed(10.0)))
+            .push(
+                Button::new(Text::new(t!("dashboard")))
+                    .on_press(Message::ShowDashboard)
+                    .width(Length::Fill)
+                    .padding(10)
+            )
+            .push(
+                Button::new(Text::new(t!("new-profile")))
+                    .on_press(Message::NewProfile)
+                    .width(Length::Fill)
+                    .padding(10)
+            )
+            .push(
+                Button::new(Text::new(t!("stats")))
+                    .on_press(Message::ShowStats)
+                    .width(Length::Fill)
+                    .padding(10)
+            )
+            .push(
+                Button::new(Text::new(t!("settings")))
+                    .on_press(Message::ShowSettings)
+                    .width(Length::Fill)
+                    .padding(10)
+            )
+            .push(Space::new(Length::Fill, Length::Fixed(20.0)))
+            .push(self.render_hardware_monitor());
+        
+        let left_panel = Container::new(
+            Scrollable::new(profile_list)
+        )
+        .width(Length::Fixed(200.0))
+        .height(Length::Fill)
+        .padding(10);
+        
+        // Right panel - Edit form, one tab at a time; the actions stay below the tabs
+        let mut tabs = Row::new().spacing(5);
+        for tab in EditorTab::ALL {
+            let style = if tab == self.editor_tab {
+                iced::theme::Button::Primary
+            } else {
+                iced::theme::Button::Secondary
+            };
+            tabs = tabs.push(Button::new(Text::new(tab.to_string())).on_press(Message::EditorTabSelected(tab)).style(style).padding(8));
+        }
+        
+        let edit_section = Column::new()
+            .spacing(15)
+            .padding(20)
+            .push(
+                Row::new()
+                    .spacing(15)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("edit-profile")).size(24))
+                    .push(if self.has_unsaved_edits() { Text::new(t!("unsaved-changes")).size(14) } else { Text::new("") })
+            )
+            .push_maybe(self.render_game_suggestions())
+            .push(tabs)
+            .push(
+                Scrollable::new(
+                    match self.editor_tab {
+                        EditorTab::General => self.render_general_tab(),
+                        EditorTab::Processes => self.render_processes_tab(),
+                        EditorTab::Crosshair => self.render_crosshair_tab(),
+                        EditorTab::SystemTweaks => self.render_tweaks_tab(),
+                    }
+                )
+                .height(Length::Fill)
+            )
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(
+                        Button::new(Text::new(t!("save-profile")))
+                            .on_press(Message::SaveProfile)
+                            .padding(12)
+                    )
+                    .push(
+                        if self.selected_profile_index.is_some() {
+                            Button::new(Text::new(t!("delete")))
+                                .on_press(Message::DeleteProfile)
+                                .padding(12)
+                        } else {
+                            Button::new(Text::new(t!("delete"))).padding(12)
+                        }
+                    )
+                    .push(
+                        if self.selected_profile_index.is_some() {
+                            Button::new(Text::new(t!("activate")))
+                                .on_press(Message::ActivateProfile)
+                                .padding(12)
+                        } else {
+                            Button::new(Text::new(t!("activate"))).padding(12)
+                        }
+                    )
+                    .push(
+                        if self.selected_profile_index.is_some() && self.benchmark.is_none() {
+                            Button::new(Text::new(t!("benchmark")))
+                                .on_press(Message::RunBenchmark)
+                                .padding(12)
+                        } else {
+                            Button::new(Text::new(t!("benchmark"))).padding(12)
+                        }
+                    )
+                    .push(
+                        Button::new(Text::new(t!("check-profiles")))
+                            .on_press(Message::CheckProfiles)
+                            .padding(12)
+                    )
+            )
+            .push_maybe((!self.lint_results.is_empty()).then(|| self.render_lint_results()))
+            .push_maybe(self.activation_report.as_ref().map(render_activation_report));
+        
+        let right_panel = Container::new(
+            match self.view {
+                View::Dashboard => Scrollable::new(self.render_dashboard()).into(),
+                View::Editor => Element::from(edit_section),
+                View::Stats => Scrollable::new(self.render_stats()).into(),
+                View::Settings => Scrollable::new(self.render_settings()).into(),
+            }
+        )
+        .width(Length::Fill)
+        .height(Length::Fill);
+        
+        let mut content = Column::new();
+        if config::is_safe_mode() {
+            content = content.push(self.render_safe_mode_banner());
+        }
+        let content = content
+            .push(
+                Row::new()
+                    .push(left_panel)
+                    .push(right_panel)
+                    .height(Length::FillPortion(9))
+            )
+            .push(
+                Container::new(
+                    Row::new()
+                        .spacing(20)
+                        .push(Text::new(&self.status_message).size(14))
+                        .push(Space::new(Length::Fill, Length::Shrink))
+                        .push(
+                            if let Some(ref name) = self.active_profile_name {
+                                let fan = if self.activation.system.fan.is_applied() { format!(" | {}", t!("status-bar-fan-max")) } else { String::new() };
+                                let dnd = if self.activation.system.do_not_disturb.is_active() { format!(" | {}", t!("status-bar-dnd")) } else { String::new() };
+                                let anti_cheat = if self.profiles.iter().any(|p| &p.name == name && p.anti_cheat_safe) {
+                                    format!(" | {}", t!("status-bar-anti-cheat-safe"))
+                                } else {
+                                    String::new()
+                                };
+                                Text::new(t!("status-bar-active", profile = name, fan = fan, dnd = dnd, anti_cheat = anti_cheat)).size(14)
+                            } else {
+                                Text::new(t!("status-bar-inactive")).size(14)
+                            }
+                        )
+                )
+                .width(Length::Fill)
+                .padding(10)
+                .height(Length::FillPortion(1))
+            );
+
+        Container::new(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
+
+/// Crosshair being moved with the arrow keys, and where it is now
+struct CrosshairAdjust {
+    /// Arrow keys, Enter and Escape, registered until this is dropped
+    hotkeys: HotkeyThread,
+    profile: String,
+    slot: usize,
+    x_offset: i32,
+    y_offset: i32,
+}
+
+/// Apps a profile activation would close and the preflight problems that would fail a step,
+/// shown for confirmation before it runs
+struct KillConfirmation {
+    profile: String,
+    apps: Vec<KillCandidate>,
+    problems: Vec<Problem>,
+    /// Set the profile's "don't ask again" flag on confirm
+    dont_ask_again: bool,
+}
+
+/// Outcome of the last activation for the report panel
+struct ActivationReport {
+    summary: ActivationSummary,
+    /// None when the profile closes no processes
+    kills: Option<KillReport>,
+    /// Process categories whose names are listed
+    expanded: HashSet<KillCategory>,
+    /// Third-party overlays found running that can fight with the crosshair or anti-cheat
+    overlays: Vec<OverlayInjector>,
+    /// Features of the profile skipped because the app isn't running as administrator
+    needs_admin: Vec<AdminFeature>,
+    /// Other preflight problems found before it ran (missing crosshair, game not installed, ...)
+    readiness: Vec<Problem>,
+}
+
+impl ActivationReport {
+    /// Step results as plain text, for pasting into bug reports (the process step's lines
+    /// already list the processes)
+    fn text(&self) -> String {
+        let mut text = format!("Gaming Optimizer {}\n{}", env!("CARGO_PKG_VERSION"), self.summary.report_text());
+        for problem in &self.readiness {
+            text.push_str(&format!("\nPreflight: {}", problem.message()));
+        }
+        text
+    }
+}
+
+/// Group of processes in the activation report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KillCategory {
+    Closed,
+    Failed,
+    TimedOut,
+    NotStarted,
+    NotRunning,
+    Protected,
+}
+
+impl KillCategory {
+    const ALL: [KillCategory; 6] = [
+        KillCategory::Closed,
+        KillCategory::Failed,
+        KillCategory::TimedOut,
+        KillCategory::NotStarted,
+        KillCategory::NotRunning,
+        KillCategory::Protected,
+    ];
+    
+    fn names(self, report: &KillReport) -> &[String] {
+        match self {
+            KillCategory::Closed => &report.killed,
+            KillCategory::Failed => &report.failed,
+            KillCategory::TimedOut => &report.timed_out,
+            KillCategory::NotStarted => &report.not_started,
+            KillCategory::NotRunning => &report.not_found,
+            KillCategory::Protected => &report.blocklist_skipped,
+        }
+    }
+    
+    fn label(self) -> String {
+        match self {
+            KillCategory::Closed => t!("report-closed"),
+            KillCategory::Failed => t!("report-failed"),
+            KillCategory::TimedOut => t!("report-timed-out"),
+            KillCategory::NotStarted => t!("report-not-started"),
+            KillCategory::NotRunning => t!("report-not-running"),
+            KillCategory::Protected => t!("report-protected"),
+        }
+    }
+}
+
+/// Headline, failed steps and expandable process lists of the last activation
+fn render_activation_report(report: &ActivationReport) -> Element<'_, Message> {
+    let summary = &report.summary;
+    let mut content = Column::new()
+        .spacing(8)
+        .push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(summary.headline()).size(16).width(Length::Fill))
+                .push(Button::new(Text::new(t!("copy-report")).size(12)).on_press(Message::CopyActivationReport).padding(6))
+                .push(Button::new(Text::new("✕").size(12)).on_press(Message::DismissActivationReport).padding(6))
+        )
+        .push(Text::new(summary.tray_status()).size(12));
+    
+    for result in &summary.results {
+        if let StepStatus::Failed(ref error) = result.status {
+            content = content.push(Text::new(format!("{}: {}", result.name, error)).size(12).style(styles::ERROR_TEXT));
+        }
+    }
+    for error in &summary.revert_errors {
+        content = content.push(Text::new(error.clone()).size(12).style(styles::ERROR_TEXT));
+    }
+    if !report.needs_admin.is_empty() {
+        let features: Vec<String> = report.needs_admin.iter().map(ToString::to_string).collect();
+        content = content.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(t!("activation-needs-admin", features = features.join(", ")))
+                        .size(12)
+                        .style(styles::WARNING_TEXT)
+                        .width(Length::Fill)
+                )
+                .push(Button::new(Text::new(t!("restart-as-admin")).size(12)).on_press(Message::RestartAsAdmin).padding(4))
+        );
+    }
+    for problem in &report.readiness {
+        content = content.push(Text::new(format!("⚠ {}", problem.message())).size(12).style(styles::WARNING_TEXT));
+    }
+    for &injector in &report.overlays {
+        let fix = injector.fix().map(|fix| {
+            let label = match fix {
+                OverlayFix::CloseProcesses(_) => t!("overlay-fix-close"),
+                OverlayFix::TurnOffGameBar => t!("overlay-fix-game-bar"),
+            };
+            Button::new(Text::new(label).size(12)).on_press(Message::FixOverlay(injector)).padding(4)
+        });
+        content = content.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(format!("⚠ {}", t!("overlay-conflict", overlay = injector, hint = injector.hint())))
+                        .size(12)
+                        .style(styles::WARNING_TEXT)
+                        .width(Length::Fill)
+                )
+                .push_maybe(fix)
+        );
+    }
+    
+    if let Some(ref kills) = report.kills {
+        for category in KillCategory::ALL {
+            let names = category.names(kills);
+            if names.is_empty() {
+                continue;
+            }
+            let expanded = report.expanded.contains(&category);
+            let arrow = if expanded { "▼" } else { "▶" };
+            let label = Text::new(format!("{} {} ({})", arrow, category.label(), names.len())).size(14);
+            let label = if matches!(category, KillCategory::Failed | KillCategory::TimedOut | KillCategory::NotStarted) { label.style(styles::ERROR_TEXT) } else { label };
+            content = content.push(
+                Button::new(label)
+                    .on_press(Message::ToggleKillCategory(category))
+                    .style(iced::theme::Button::Text)
+                    .padding(2)
+            );
+            if expanded {
+                content = content.push(Text::new(names.join(", ")).size(12));
+            }
+        }
+    }
+    
+    Container::new(content)
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+}
+
+/// Scrollable id of the process selector, scrolled back up when its rows change
+const PROCESS_LIST_ID: &str = "process-list";
+
+/// Text box id of the quick switcher, focused when it opens
+const QUICK_SWITCH_INPUT: &str = "quick-switch";
+
+/// Keys of the quick switcher: Ctrl+K toggles it, the rest only act while it's open
+fn quick_switch_key(key: iced::keyboard::Key, modifiers: iced::keyboard::Modifiers) -> Option<Message> {
+    use iced::keyboard::key::Named;
+    use iced::keyboard::Key;
+    
+    match key {
+        Key::Character(c) if modifiers.control() && c.as_str().eq_ignore_ascii_case("k") => Some(Message::ToggleQuickSwitch),
+        Key::Named(Named::Escape) => Some(Message::CloseQuickSwitch),
+        Key::Named(Named::ArrowUp) => Some(Message::QuickSwitchMove(-1)),
+        Key::Named(Named::ArrowDown) => Some(Message::QuickSwitchMove(1)),
+        Key::Named(Named::Enter) => Some(Message::QuickSwitchSubmit { edit: modifiers.control() }),
+        _ => None,
+    }
+}
+
+/// Watcher for "activate when a game starts" (None, logged, when it can't start)
+fn start_game_watcher() -> Option<GameWatcher> {
+    GameWatcher::spawn().map_err(|e| warn!("[Games] {}", e)).ok()
+}
+
+/// Status line for profile entries that were moved to profiles.invalid.json (None without any)
+fn quarantine_status(quarantined: &[InvalidEntry]) -> Option<String> {
+    if quarantined.is_empty() {
+        return None;
+    }
+    let errors: Vec<&str> = quarantined.iter().map(|invalid| invalid.error.as_str()).collect();
+    Some(t!(
+        "status-profiles-quarantined",
+        count = quarantined.len(),
+        file = INVALID_PROFILES_FILE,
+        errors = errors.join("; ")
+    ))
+}
+
+/// Bring the main window up (it's hidden after a --minimized start)
+fn show_main_window() -> Command<Message> {
+    Command::batch([
+        iced::window::change_mode(iced::window::Id::MAIN, iced::window::Mode::Windowed),
+        iced::window::minimize(iced::window::Id::MAIN, false),
+        iced::window::gain_focus(iced::window::Id::MAIN),
+    ])
+}
+
+/// What the right panel shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum View {
+    #[default]
+    Dashboard,
+    Editor,
+    Stats,
+    Settings,
+}
+
+/// Section of the profile editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EditorTab {
+    #[default]
+    General,
+    Processes,
+    Crosshair,
+    SystemTweaks,
+}
+
+impl EditorTab {
+    const ALL: [EditorTab; 4] = [EditorTab::General, EditorTab::Processes, EditorTab::Crosshair, EditorTab::SystemTweaks];
+}
+
+impl std::fmt::Display for EditorTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditorTab::General => write!(f, "{}", t!("tab-general")),
+            EditorTab::Processes => write!(f, "{}", t!("tab-processes")),
+            EditorTab::Crosshair => write!(f, "{}", t!("tab-crosshair")),
+            EditorTab::SystemTweaks => write!(f, "{}", t!("tab-system-tweaks")),
+        }
+    }
+}
+
+/// Power plan dropdown entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum PowerPlanChoice {
+    /// Leave the current plan alone
+    Unchanged,
+    Plan(PowerPlan),
+}
+
+impl std::fmt::Display for PowerPlanChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerPlanChoice::Unchanged => write!(f, "{}", t!("choice-dont-change")),
+            PowerPlanChoice::Plan(plan) => write!(f, "{}", plan),
+        }
+    }
+}
+
+/// RGB lighting dropdown entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RgbSceneChoice {
+    /// Leave the lighting alone
+    Unchanged,
+    SolidColor,
+    /// A profile saved in OpenRGB
+    Profile(String),
+}
+
+impl std::fmt::Display for RgbSceneChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RgbSceneChoice::Unchanged => write!(f, "{}", t!("choice-dont-change")),
+            RgbSceneChoice::SolidColor => write!(f, "{}", t!("choice-solid-color")),
+            RgbSceneChoice::Profile(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// OBS scene dropdown entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObsSceneChoice {
+    /// Leave the scene alone
+    Unchanged,
+    Scene(String),
+}
+
+impl std::fmt::Display for ObsSceneChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObsSceneChoice::Unchanged => write!(f, "{}", t!("choice-dont-change")),
+            ObsSceneChoice::Scene(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Keyboard layout dropdown entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyboardLayoutChoice {
+    /// Don't pin a layout
+    Unchanged,
+    Layout(KeyboardLayout),
+}
+
+impl std::fmt::Display for KeyboardLayoutChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyboardLayoutChoice::Unchanged => write!(f, "{}", t!("choice-dont-pin")),
+            KeyboardLayoutChoice::Layout(layout) => write!(f, "{}", layout),
+        }
+    }
+}
+
+/// Display mode dropdown entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayModeChoice {
+    /// Keep the desktop mode
+    Unchanged,
+    Mode(DisplayMode),
+}
+
+impl std::fmt::Display for DisplayModeChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisplayModeChoice::Unchanged => write!(f, "{}", t!("choice-desktop-mode")),
+            DisplayModeChoice::Mode(mode) => write!(f, "{}", mode),
+        }
+    }
+}
+
+/// Alert "switch to profile" dropdown entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwitchProfileChoice {
+    None,
+    Profile(String),
+}
+
+impl std::fmt::Display for SwitchProfileChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwitchProfileChoice::None => write!(f, "{}", t!("choice-dont-switch")),
+            SwitchProfileChoice::Profile(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Language dropdown entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LanguageChoice {
+    /// Follow the Windows display language
+    System,
+    Language { tag: String, name: String },
+}
+
+impl std::fmt::Display for LanguageChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LanguageChoice::System => write!(f, "{}", t!("language-system")),
+            LanguageChoice::Language { name, .. } => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Overlay hardware HUD dropdown entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudCornerChoice {
+    Off,
+    Corner(Corner),
+}
+
+impl HudCornerChoice {
+    fn all() -> Vec<HudCornerChoice> {
+        std::iter::once(HudCornerChoice::Off)
+            .chain(Corner::ALL.iter().copied().map(HudCornerChoice::Corner))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for HudCornerChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HudCornerChoice::Off => write!(f, "{}", t!("choice-off")),
+            HudCornerChoice::Corner(corner) => write!(f, "{}", corner),
+        }
+    }
+}
+
+/// Windows gaming feature shown in the editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameFeature {
+    GameMode,
+    GameBar,
+    BackgroundRecording,
+}
+
+/// Per-feature dropdown entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureChoice {
+    /// Leave the user's setting alone
+    Unchanged,
+    On,
+    Off,
+}
+
+impl FeatureChoice {
+    const ALL: [FeatureChoice; 3] = [FeatureChoice::Unchanged, FeatureChoice::On, FeatureChoice::Off];
+    
+    fn from_option(value: Option<bool>) -> Self {
+        match value {
+            None => FeatureChoice::Unchanged,
+            Some(true) => FeatureChoice::On,
+            Some(false) => FeatureChoice::Off,
+        }
+    }
+    
+    fn as_option(self) -> Option<bool> {
+        match self {
+            FeatureChoice::Unchanged => None,
+            FeatureChoice::On => Some(true),
+            FeatureChoice::Off => Some(false),
+        }
+    }
+}
+
+impl std::fmt::Display for FeatureChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeatureChoice::Unchanged => write!(f, "{}", t!("choice-dont-change")),
+            FeatureChoice::On => write!(f, "{}", t!("choice-on")),
+            FeatureChoice::Off => write!(f, "{}", t!("choice-off")),
+        }
+    }
+}
+
+/// Named anchors offered in the position picker
+const ANCHOR_CHOICES: &[NamedAnchor] = &[
+    NamedAnchor::Center,
+    NamedAnchor::TopCenter,
+    NamedAnchor::BottomCenter,
+    NamedAnchor::LeftCenter,
+    NamedAnchor::RightCenter,
+];
+
+/// Overlay note defaults used by the editor
+const DEFAULT_NOTE_SIZE: u32 = 16;
+const DEFAULT_NOTE_COLOR: [u8; 4] = [255, 255, 255, 255];
+
+/// How often the Windows dark/light setting is re-read while the theme follows it
+const SYSTEM_THEME_POLL: Duration = Duration::from_secs(5);
+
+/// Smallest main window that fits the settings columns
+const MIN_WINDOW_WIDTH: f32 = 900.0;
+const MIN_WINDOW_HEIGHT: f32 = 650.0;
+/// Windows moves a minimized window to (-32000, -32000)
+const MINIMIZED_POSITION: i32 = -32000;
+
+/// Temperature limit as shown in a settings field (empty = no limit)
+fn format_limit(limit: Option<f32>) -> String {
+    limit.map(|c| format!("{}", c)).unwrap_or_default()
+}
+
+/// Accept only what can become a whole-degree limit
+fn is_limit_input(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_digit()) && value.len() <= 3
+}
+
+/// Sampling interval as shown in the settings field ("2", "0.5")
+fn format_interval_secs(interval_ms: u64) -> String {
+    format!("{}", interval_ms as f64 / 1000.0)
+}
+
+/// Color swatches offered in the crosshair tint picker (labels are string ids)
+const TINT_PRESETS: &[(&str, Option<[u8; 4]>)] = &[
+    ("tint-original", None),
+    ("tint-green", Some([0, 255, 0, 255])),
+    ("tint-cyan", Some([0, 255, 255, 255])),
+    ("tint-magenta", Some([255, 0, 255, 255])),
+    ("tint-red", Some([255, 0, 0, 255])),
+    ("tint-yellow", Some([255, 255, 0, 255])),
+];
+
+impl GameOptimizer {
+    fn render_tint_picker(&self) -> Element<'_, Message> {
+        let mut swatches = Row::new()
+            .spacing(5)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("tint-color")).size(12));
+        
+        for (label, tint) in TINT_PRESETS {
+            let swatch = match tint {
+                Some([r, g, b, _]) => Text::new("■").size(18).style(iced::Color::from_rgb8(*r, *g, *b)),
+                None => Text::new(i18n::tr(label, &[])).size(12),
+            };
+            let content = if self.edit_tint == *tint {
+                Row::new().spacing(3).align_items(Alignment::Center).push(Text::new("✓").size(12)).push(swatch)
+            } else {
+                Row::new().push(swatch)
+            };
+            
+            swatches = swatches.push(
+                Button::new(content)
+                    .on_press(Message::TintSelected(*tint))
+                    .padding(4)
+            );
+        }
+        
+        swatches
+            .push(
+                TextInput::new("#RRGGBBAA", &self.edit_tint_hex)
+                    .on_input(Message::TintHexChanged)
+                    .width(Length::Fixed(100.0))
+                    .padding(5)
+            )
+            .into()
+    }
+    
+    /// Median time to tint, premultiply and blend the edited crosshair (a synthetic one without
+    /// an image) over a frame the size of the primary screen
+    fn measure_overlay_render(&self) -> (Duration, u32, u32) {
+        const RUNS: usize = 5;
+        let (width, height) = unsafe {
+            (GetSystemMetrics(SM_CXSCREEN).max(1) as u32, GetSystemMetrics(SM_CYSCREEN).max(1) as u32)
+        };
+        let image = self.edit_image_path.as_ref().and_then(|path| ::image::open(path).ok()).map(|img| img.to_rgba8());
+        let (pixels, image_width, image_height) = match image {
+            Some(rgba) => (rgba.pixels().map(|p| p.0).collect(), rgba.width(), rgba.height()),
+            None => {
+                let size = overlay_render::SYNTHETIC_SIZE;
+                (overlay_render::synthetic_crosshair(size, size), size, size)
+            }
+        };
+        
+        let mut frame = Vec::new();
+        let mut times: Vec<Duration> = (0..RUNS)
+            .map(|_| overlay_render::time_frame(&pixels, image_width, image_height, self.edit_tint, (width, height), &mut frame))
+            .collect();
+        times.sort();
+        (times[RUNS / 2], width, height)
+    }
+    
+    fn render_diagnostics(&self) -> Element<'_, Message> {
+        let timer_lines = match self.timer_resolution {
+            Some(resolution) => Column::new()
+                .spacing(2)
+                .push(Text::new(t!("timer-current", value = timer::format_100ns(resolution.current))).size(12))
+                .push(
+                    Text::new(t!(
+                        "timer-range",
+                        finest = timer::format_100ns(resolution.finest),
+                        coarsest = timer::format_100ns(resolution.coarsest)
+                    ))
+                    .size(11)
+                ),
+            None => Column::new().push(Text::new(t!("timer-unavailable")).size(12)),
+        };
+        
+        let render_line = self.overlay_render_time.map(|(time, width, height)| {
+            let ms = format!("{:.2}", time.as_secs_f64() * 1000.0);
+            Text::new(t!("overlay-render-time", ms = ms, width = width, height = height)).size(12)
+        });
+        
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("diagnostics")).size(16))
+                .push(timer_lines)
+                .push_maybe(render_line)
+                .push(
+                    Button::new(Text::new(t!("diagnostics-refresh")).size(12))
+                        .on_press(Message::RefreshDiagnostics)
+                        .padding(4)
+                )
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(t!("log-level")).size(12))
+                        .push(
+                            PickList::new(&LogLevel::ALL[..], Some(self.log_level), Message::LogLevelSelected)
+                                .text_size(12)
+                                .width(Length::Fixed(100.0))
+                        )
+                        .push(
+                            Button::new(Text::new(t!("open-logs")).size(12))
+                                .on_press(Message::OpenLogs)
+                                .padding(4)
+                        )
+                )
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    /// App-wide settings, stored in config.json rather than in a profile
+    fn render_settings(&self) -> Element<'_, Message> {
+        let gap = || Space::new(Length::Fill, Length::Fixed(10.0));
+        let left = Column::new()
+            .width(Length::FillPortion(1))
+            .push(self.render_appearance_settings())
+            .push(gap())
+            .push(self.render_language_settings())
+            .push(gap())
+            .push(self.render_startup_settings())
+            .push(gap())
+            .push(self.render_update_settings())
+            .push(gap())
+            .push(self.render_toast_settings())
+            .push(gap())
+            .push(self.render_hotkey_settings())
+            .push(gap())
+            .push(self.render_gamepad_settings())
+            .push(gap())
+            .push(self.render_tray_click_settings())
+            .push(gap())
+            .push(self.render_tray_tooltip_settings())
+            .push(gap())
+            .push(self.render_safety_settings())
+            .push(gap())
+            .push(self.render_sync_settings())
+            .push(self.render_backup_settings())
+            .push(gap())
+            .push(self.render_data_folder_settings());
+        let right = Column::new()
+            .width(Length::FillPortion(1))
+            .push(self.render_diagnostics())
+            .push(gap())
+            .push(self.render_alert_settings())
+            .push(gap())
+            .push(self.render_openrgb_settings())
+            .push(gap())
+            .push(self.render_obs_settings())
+            .push(gap())
+            .push(self.render_discord_settings())
+            .push(gap())
+            .push(self.render_event_log_settings())
+            .push(gap())
+            .push(self.render_plugin_settings())
+            .push(gap())
+            .push(self.render_remote_api_settings());
+        
+        Column::new()
+            .spacing(15)
+            .padding(20)
+            .push(Text::new(t!("settings")).size(24))
+            .push(Row::new().spacing(15).push(left).push(right))
+            .into()
+    }
+    
+    fn render_appearance_settings(&self) -> Element<'_, Message> {
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("appearance")).size(16))
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(t!("theme-mode")).size(12).width(Length::Fixed(50.0)))
+                        .push(
+                            PickList::new(&ThemeMode::ALL[..], Some(self.theme_config.mode), Message::ThemeModeSelected)
+                                .text_size(12)
+                                .width(Length::Fixed(140.0))
+                        )
+                )
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(t!("accent")).size(12).width(Length::Fixed(50.0)))
+                        .push(
+                            PickList::new(&Accent::ALL[..], Some(self.theme_config.accent), Message::AccentSelected)
+                                .text_size(12)
+                                .width(Length::Fixed(140.0))
+                        )
+                        .push(Text::new("■").size(18).style(styles::accent_color(self.theme_config.accent)))
+                )
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_language_settings(&self) -> Element<'_, Message> {
+        let mut choices = vec![LanguageChoice::System];
+        choices.extend(
+            self.languages
+                .iter()
+                .map(|(tag, name)| LanguageChoice::Language { tag: tag.clone(), name: name.clone() })
+        );
+        let selected = match self.language {
+            Some(ref tag) => choices.iter().find(|choice| matches!(choice, LanguageChoice::Language { tag: t, .. } if t == tag)).cloned(),
+            None => Some(LanguageChoice::System),
+        };
+        
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("language")).size(16))
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(PickList::new(choices, selected, Message::LanguageSelected).text_size(12).width(Length::Fixed(160.0)))
+                        .push(
+                            Button::new(Text::new(t!("language-open-folder")).size(12))
+                                .on_press(Message::OpenTranslations)
+                                .padding(4)
+                        )
+                )
+                .push(Text::new(t!("language-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_hotkey_settings(&self) -> Element<'_, Message> {
+        let mut content = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("hotkeys")).size(16))
+            .push(
+                Checkbox::new(t!("enable-global-hotkeys"), self.hotkey_config.enabled)
+                    .on_toggle(Message::HotkeysToggled)
+                    .text_size(12)
+            );
+        if self.hotkey_config.enabled {
+            content = content
+                .push(Text::new(t!("next-crosshair")).size(12))
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .push(
+                            TextInput::new(hotkeys::DEFAULT_CYCLE_CROSSHAIR, &self.hotkey_binding)
+                                .on_input(Message::HotkeyBindingChanged)
+                                .on_submit(Message::SaveHotkeySettings)
+                                .padding(4)
+                                .size(12)
+                        )
+                        .push(
+                            Button::new(Text::new(t!("save")).size(12))
+                                .on_press(Message::SaveHotkeySettings)
+                                .padding(4)
+                        )
+                )
+                .push(Text::new(t!("hotkey-example")).size(11));
+        }
+        Container::new(content)
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+    
+    fn render_gamepad_settings(&self) -> Element<'_, Message> {
+        let mut content = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("gamepad")).size(16))
+            .push(
+                Checkbox::new(t!("gamepad-enabled"), self.gamepad_config.enabled)
+                    .on_toggle(Message::GamepadToggled)
+                    .text_size(12)
+            );
+        if self.gamepad_config.enabled {
+            content = content
+                .push(Text::new(t!("gamepad-toggle-overlay")).size(12))
+                .push(
+                    TextInput::new(gamepad::DEFAULT_TOGGLE_OVERLAY, &self.gamepad_toggle_overlay)
+                        .on_input(Message::GamepadToggleOverlayChanged)
+                        .on_submit(Message::SaveGamepadSettings)
+                        .padding(4)
+                        .size(12)
+                )
+                .push(Text::new(t!("next-crosshair")).size(12))
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .push(
+                            TextInput::new(gamepad::DEFAULT_CYCLE_CROSSHAIR, &self.gamepad_cycle_crosshair)
+                                .on_input(Message::GamepadCycleCrosshairChanged)
+                                .on_submit(Message::SaveGamepadSettings)
+                                .padding(4)
+                                .size(12)
+                        )
+                        .push(
+                            Button::new(Text::new(t!("save")).size(12))
+                                .on_press(Message::SaveGamepadSettings)
+                                .padding(4)
+                        )
+                )
+                .push(Text::new(t!("gamepad-hint")).size(11));
+        }
+        Container::new(content)
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+    
+    fn render_tray_click_settings(&self) -> Element<'_, Message> {
+        let binding = |label: String, action: TrayClickAction, on_select: fn(TrayClickAction) -> Message| {
+            Row::new()
+                .spacing(5)
+                .align_items(Alignment::Center)
+                .push(Text::new(label).size(12).width(Length::Fixed(90.0)))
+                .push(
+                    PickList::new(&TrayClickAction::ALL[..], Some(action), on_select)
+                        .text_size(12)
+                        .width(Length::Fixed(160.0))
+                )
+        };
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("tray-clicks")).size(16))
+                .push(binding(t!("tray-single-click"), self.tray_clicks.single_click, Message::TraySingleClickSelected))
+                .push(binding(t!("tray-double-click"), self.tray_clicks.double_click, Message::TrayDoubleClickSelected))
+                .push(binding(t!("tray-middle-click"), self.tray_clicks.middle_click, Message::TrayMiddleClickSelected))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_tray_tooltip_settings(&self) -> Element<'_, Message> {
+        let mut content = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("tray-tooltip")).size(16))
+            .push(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                    .push(
+                        TextInput::new("{profile} | CPU {cpu}% | GPU {gpu_temp}°C", &self.tray_tooltip.template)
+                            .on_input(Message::TrayTooltipChanged)
+                            .on_submit(Message::SaveTrayTooltip)
+                            .size(12)
+                            .padding(4)
+                    )
+                    .push(
+                        Button::new(Text::new(t!("save")).size(12))
+                            .on_press(Message::SaveTrayTooltip)
+                            .padding(4)
+                    )
+            )
+            .push(Text::new(t!("tray-tooltip-hint", placeholders = tray_tooltip::PLACEHOLDERS.map(|name| format!("{{{}}}", name)).join(" "))).size(11));
+        let unknown = tray_tooltip::unknown_placeholders(&self.tray_tooltip.template);
+        if !unknown.is_empty() {
+            content = content.push(
+                Text::new(t!("tray-tooltip-unknown", placeholders = unknown.join(", ")))
+                    .size(11)
+                    .style(styles::WARNING_TEXT)
+            );
+        }
+        Container::new(content)
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+    
+    fn render_safety_settings(&self) -> Element<'_, Message> {
+        let mut content = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("safety")).size(16))
+            .push(
+                Checkbox::new(t!("ask-before-closing-running-apps"), self.safety_config.confirm_kill)
+                    .on_toggle(Message::ConfirmKillToggled)
+                    .text_size(12)
+            )
+            .push(Text::new(t!("safety-protected-processes")).size(12));
+        for (i, name) in self.safety_config.protected_processes.iter().enumerate() {
+            content = content.push(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(name.as_str()).size(12).width(Length::Fill))
+                    .push(
+                        Button::new(Text::new("✕").size(11))
+                            .on_press(Message::RemoveProtectedProcess(i))
+                            .padding(2)
+                    )
+            );
+        }
+        content = content.push(
+            Row::new()
+                .spacing(5)
+                .push(
+                    TextInput::new("process.exe", &self.protected_process_input)
+                        .on_input(Message::ProtectedProcessInputChanged)
+                        .on_submit(Message::AddProtectedProcess)
+                        .padding(4)
+                        .size(12)
+                )
+                .push(
+                    Button::new(Text::new(t!("add")).size(12))
+                        .on_press(Message::AddProtectedProcess)
+                        .padding(4)
+                )
+        );
+        Container::new(content)
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+    
+    fn render_sync_settings(&self) -> Element<'_, Message> {
+        let folder = self.sync_folder.as_ref().map_or_else(|| t!("profile-sync-off"), |path| path.display().to_string());
+        let mut buttons = Row::new()
+            .spacing(5)
+            .push(
+                Button::new(Text::new(t!("choose-folder")).size(12))
+                    .on_press(Message::ChooseSyncFolder)
+                    .padding(4)
+            );
+        if self.sync_folder.is_some() {
+            buttons = buttons
+                .push(
+                    Button::new(Text::new(t!("sync-now")).size(12))
+                        .on_press(Message::SyncProfilesNow)
+                        .padding(4)
+                )
+                .push(
+                    Button::new(Text::new(t!("stop-syncing")).size(12))
+                        .on_press(Message::ClearSyncFolder)
+                        .padding(4)
+                );
+        }
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("profile-sync")).size(16))
+                .push(Text::new(folder).size(12))
+                .push(buttons)
+                .push(Text::new(t!("profile-sync-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_backup_settings(&self) -> Element<'_, Message> {
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("backup")).size(16))
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .push(
+                            Button::new(Text::new(t!("backup-export")).size(12))
+                                .on_press(Message::ExportBackup)
+                                .padding(4)
+                        )
+                        .push(
+                            Button::new(Text::new(t!("backup-import")).size(12))
+                                .on_press(Message::ImportBackup)
+                                .padding(4)
+                        )
+                )
+                .push(Text::new(t!("backup-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    /// What a game suggestion does, for its row and the status line
+    fn suggestion_label(&self, suggestion: &Suggestion) -> String {
+        match suggestion {
+            Suggestion::Kill(exe) => t!("suggest-kill", process = exe),
+            Suggestion::CloseOverlay(overlay) => t!("suggest-close-overlay", overlay = overlay.name, process = overlay.executable),
+            Suggestion::PowerPlan(guid) => t!("suggest-power-plan", plan = self.power_plan_name(guid)),
+            Suggestion::HighPrecisionTimer => t!("suggest-high-precision-timer"),
+        }
+    }
+    
+    /// Recommendations for the game the edited profile is for, with an Apply button each
+    fn render_game_suggestions(&self) -> Option<Element<'_, Message>> {
+        if self.hide_game_suggestions {
+            return None;
+        }
+        let running: Vec<&str> = self.running_processes.iter().map(|p| p.name.as_str()).collect();
+        let game = game_knowledge::find_game(&self.games, &self.edit_name, &running)?;
+        let plans: Vec<String> = self.power_plans.iter().map(|plan| plan.guid.clone()).collect();
+        let suggestions = game.suggestions(&self.edited_profile(), &plans);
+        if suggestions.is_empty() {
+            return None;
+        }
+        
+        let mut content = Column::new()
+            .spacing(4)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("game-suggestions-title", game = game.name)).size(16).width(Length::Fill))
+                    .push(Button::new(Text::new("✕").size(12)).on_press(Message::DismissGameSuggestions).padding(6))
+            );
+        for suggestion in suggestions {
+            let reason = match suggestion {
+                Suggestion::CloseOverlay(ref overlay) if !overlay.reason.is_empty() => Some(overlay.reason.clone()),
+                _ => None,
+            };
+            content = content
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(self.suggestion_label(&suggestion)).size(13).width(Length::Fill))
+                        .push(
+                            Button::new(Text::new(t!("apply")).size(12))
+                                .on_press(Message::ApplySuggestion(suggestion))
+                                .padding(4)
+                        )
+                )
+                .push_maybe(reason.map(|reason| Text::new(reason).size(11)));
+        }
+        
+        Some(
+            Container::new(content)
+                .width(Length::Fill)
+                .padding(8)
+                .style(iced::theme::Container::Box)
+                .into()
+        )
+    }
+    
+    /// Lint warnings under the editor; the profile names open that profile
+    fn render_lint_results(&self) -> Element<'_, Message> {
+        let mut content = Column::new()
+            .spacing(6)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("lint-title", count = self.lint_results.len())).size(16).width(Length::Fill))
+                    .push(Button::new(Text::new("✕").size(12)).on_press(Message::DismissLint).padding(6))
+            );
+        
+        for (name, lint) in &self.lint_results {
+            let open = self.profiles.iter().position(|p| &p.name == name).map(Message::ProfileSelected);
+            content = content.push(
+                Row::new()
+                    .spacing(6)
+                    .align_items(Alignment::Center)
+                    .push(
+                        Button::new(Text::new(name.as_str()).size(12))
+                            .on_press_maybe(open)
+                            .style(iced::theme::Button::Text)
+                            .padding(2)
+                    )
+                    .push(Text::new(format!("⚠ {}", lint.message())).size(12).style(styles::WARNING_TEXT))
+            );
+        }
+        
+        Container::new(content)
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+    
+    /// "Needs administrator" note with a restart button, for editors of admin-only features;
+    /// nothing when the app already runs elevated
+    fn render_admin_badge(&self) -> Option<Element<'_, Message>> {
+        if self.capabilities.elevated {
+            return None;
+        }
+        Some(
+            Row::new()
+                .spacing(8)
+                .align_items(Alignment::Center)
+                .push(Text::new(t!("admin-badge")).size(12).style(styles::WARNING_TEXT))
+                .push(
+                    Button::new(Text::new(t!("restart-as-admin")).size(11))
+                        .on_press(Message::RestartAsAdmin)
+                        .padding(3)
+                )
+                .into()
+        )
+    }
+    
+    /// Strip across the top of the window while running with --safe-mode
+    fn render_safe_mode_banner(&self) -> Element<'_, Message> {
+        Container::new(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(t!("safe-mode-banner")).size(14).width(Length::Fill))
+                .push(
+                    Button::new(Text::new(t!("safe-mode-restart")).size(12))
+                        .on_press(Message::RestartNormally)
+                        .padding(6)
+                )
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(styles::banner)
+        .into()
+    }
+    
+    fn render_data_folder_settings(&self) -> Element<'_, Message> {
+        let folder = self.data_dir.as_ref().map_or_else(String::new, |path| path.display().to_string());
+        let mut column = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("data-folder")).size(16))
+            .push(Text::new(folder).size(12));
+        if self.portable {
+            column = column.push(Text::new(t!("data-folder-portable")).size(11));
+        } else {
+            let mut buttons = Row::new()
+                .spacing(5)
+                .push(
+                    Button::new(Text::new(t!("choose-folder")).size(12))
+                        .on_press(Message::ChooseDataFolder)
+                        .padding(4)
+                );
+            if self.data_dir_moved {
+                buttons = buttons.push(
+                    Button::new(Text::new(t!("data-folder-default")).size(12))
+                        .on_press(Message::ResetDataFolder)
+                        .padding(4)
+                );
+            }
+            column = column.push(buttons).push(Text::new(t!("data-folder-hint")).size(11));
+        }
+        Container::new(column)
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+    
+    fn render_startup_settings(&self) -> Element<'_, Message> {
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("startup")).size(16))
+                .push(
+                    Checkbox::new(t!("start-with-windows"), self.autostart.enabled)
+                        .on_toggle(Message::AutostartToggled)
+                        .text_size(12)
+                )
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(Alignment::Center)
+                        .push(
+                            // Creating or removing the highest-privileges task needs elevation
+                            Checkbox::new(t!("start-as-administrator"), self.autostart.elevated)
+                                .on_toggle_maybe(self.capabilities.elevated.then_some(Message::AutostartElevatedToggled))
+                                .text_size(12)
+                        )
+                        .push_maybe(self.render_admin_badge())
+                )
+                .push(Text::new(t!("startup-hint")).size(11))
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(t!("exit-behavior")).size(12))
+                        .push(
+                            PickList::new(&ExitBehavior::ALL[..], Some(self.exit_behavior), Message::ExitBehaviorSelected)
+                                .text_size(12)
+                        )
+                )
+                .push(
+                    Checkbox::new(t!("resume-last-session"), self.resume_last_session)
+                        .on_toggle(Message::ResumeLastSessionToggled)
+                        .text_size(12)
+                )
+                .push(
+                    Checkbox::new(t!("auto-activate-games"), self.auto_activate_games)
+                        .on_toggle(Message::AutoActivateGamesToggled)
+                        .text_size(12)
+                )
+                .push(Text::new(t!("auto-activate-games-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    /// Name and the system settings the profile switches
+    fn render_general_tab(&self) -> Element<'_, Message> {
+        Column::new()
+            .spacing(15)
+            .push(Text::new(t!("profile-name")))
+            .push(
+                TextInput::new(&t!("profile-name-placeholder"), &self.edit_name)
+                    .on_input(Message::ProfileNameChanged)
+                    .padding(10)
+                    .width(Length::Fill)
+            )
+            .push(
+                Checkbox::new(t!("favorite-profile"), self.edit_favorite)
+                    .on_toggle(Message::FavoriteToggled)
+            )
+            .push(
+                Checkbox::new(t!("archived-profile"), self.edit_archived)
+                    .on_toggle(Message::ArchivedToggled)
+            )
+            .push(
+                Column::new()
+                    .spacing(4)
+                    .push(
+                        Checkbox::new(t!("anti-cheat-safe"), self.edit_anti_cheat_safe)
+                            .on_toggle(Message::AntiCheatSafeToggled)
+                    )
+                    .push(Text::new(t!("anti-cheat-safe-hint")).size(12))
+            )
+            
+            .push(Space::new(Length::Fill, Length::Fixed(10.0)))
+            
+            .push(
+                Row::new()
+                    .spacing(20)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("fan-speed")).size(18))
+                    .push(
+                        Toggler::new(
+                            Some(t!("set-to-max-when-active")),
+                            self.edit_fan_speed_max,
+                            Message::FanSpeedMaxToggled
+                        )
+                        .width(Length::Shrink)
+                    )
+            )
+            .push(self.render_power_plan_picker())
+            .push(self.render_display_mode_picker())
+            .push(self.render_audio_editor())
+            .push(self.render_network_editor())
+            .push(self.render_storage_settings())
+            .push(self.render_rgb_picker())
+            .push(self.render_obs_picker())
+            .push(self.render_capture_settings())
+            .push(self.render_gpu_settings())
+            .push(self.render_keyboard_settings())
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("alert-limits")).size(18))
+                    .push(Text::new(t!("cpu-c")).size(12))
+                    .push(
+                        TextInput::new(&self.alert_cpu_limit, &self.edit_cpu_limit)
+                            .on_input(Message::ProfileCpuLimitChanged)
+                            .width(Length::Fixed(60.0))
+                            .padding(5)
+                    )
+                    .push(Text::new(t!("gpu-c")).size(12))
+                    .push(
+                        TextInput::new(&self.alert_gpu_limit, &self.edit_gpu_limit)
+                            .on_input(Message::ProfileGpuLimitChanged)
+                            .width(Length::Fixed(60.0))
+                            .padding(5)
+                    )
+                    .push(Text::new(t!("alert-limits-hint")).size(12))
+            )
+            .push(
+                Checkbox::new(t!("rollback-on-failure"), self.edit_rollback_on_failure)
+                    .on_toggle(Message::RollbackOnFailureToggled)
+            )
+            .into()
+    }
+    
+    /// Apps closed on activation
+    fn render_processes_tab(&self) -> Element<'_, Message> {
+        Column::new()
+            .spacing(15)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("processes-to-kill")).size(18))
+                    .push(
+                        Button::new(Text::new(t!("refresh")))
+                            .on_press(Message::RefreshProcesses)
+                            .padding(5)
+                    )
+                    .push(
+                        Button::new(Text::new(t!("import-kill-list")))
+                            .on_press(Message::ImportKillList)
+                            .padding(5)
+                    )
+            )
+            .push(Text::new(t!("processes-to-kill-hint")).size(12))
+            .push(
+                TextInput::new(&t!("process-filter-placeholder"), &self.process_filter)
+                    .on_input(Message::ProcessFilterChanged)
+                    .padding(8)
+                    .width(Length::Fill)
+            )
+            .push(self.render_process_selector())
+            .push(
+                Checkbox::new(t!("skip-kill-confirmation"), self.edit_skip_kill_confirmation)
+                    .on_toggle(Message::SkipKillConfirmationToggled)
+            )
+            .into()
+    }
+    
+    /// Crosshair image, position and overlay options
+    fn render_crosshair_tab(&self) -> Element<'_, Message> {
+        Column::new()
+            .spacing(15)
+            .push(Text::new(t!("crosshair-overlay")).size(18))
+            .push(Text::new(t!("crosshair-overlay-hint")).size(12))
+            .push_maybe(self.overlay_error.as_ref().map(|error| {
+                Text::new(format!("⚠️ {}", error))
+                    .size(12)
+                    .style(styles::ERROR_TEXT)
+            }))
+            
+            // Image selection row
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(
+                        Button::new(Text::new(t!("select-image")))
+                            .on_press(Message::SelectImage)
+                            .padding(10)
+                    )
+                    .push(
+                        if self.edit_image_path.is_some() {
+                            Button::new(Text::new(t!("clear")))
+                                .on_press(Message::ClearImage)
+                                .padding(10)
+                        } else {
+                            Button::new(Text::new(t!("clear"))).padding(10)
+                        }
+                    )
+                    .push(
+                        if let Some(ref path) = self.edit_image_path {
+                            Text::new(format!("✓ {}", path.split('\\').next_back().unwrap_or(path))).size(12)
+                        } else {
+                            Text::new(t!("crosshair-no-image")).size(12)
+                        }
+                    )
+            )
+            
+            // Share code import
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(
+                        TextInput::new(&t!("crosshair-code-placeholder"), &self.edit_crosshair_code)
+                            .on_input(Message::CrosshairCodeChanged)
+                            .on_submit(Message::ImportCrosshairCode)
+                            .padding(8)
+                            .width(Length::Fill)
+                    )
+                    .push(
+                        Button::new(Text::new(t!("import")))
+                            .on_press(Message::ImportCrosshairCode)
+                            .padding(8)
+                    )
+            )
+            
+            // Crosshair adjustment box
+            .push(
+                Container::new(
+                    Column::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(t!("position-adjustment")).size(14))
+                        .push(
+                            Row::new()
+                                .spacing(10)
+                                .align_items(Alignment::Center)
+                                .push(Space::new(Length::Fixed(40.0), Length::Shrink))
+                                .push(
+                                    Button::new(Text::new("▲").size(16))
+                                        .on_press(Message::CrosshairMoveUp)
+                                        .padding(8)
+                                        .width(Length::Fixed(40.0))
+                                )
+                                .push(Space::new(Length::Fixed(40.0), Length::Shrink))
+                        )
+                        .push(
+                            Row::new()
+                                .spacing(5)
+                                .align_items(Alignment::Center)
+                                .push(
+                                    Button::new(Text::new("◀").size(16))
+                                        .on_press(Message::CrosshairMoveLeft)
+                                        .padding(8)
+                                        .width(Length::Fixed(40.0))
+                                )
+                                .push(
+                                    Button::new(Text::new("⊙").size(14))
+                                        .on_press(Message::CrosshairCenter)
+                                        .padding(8)
+                                        .width(Length::Fixed(50.0))
+                                )
+                                .push(
+                                    Button::new(Text::new("▶").size(16))
+                                        .on_press(Message::CrosshairMoveRight)
+                                        .padding(8)
+                                        .width(Length::Fixed(40.0))
+                                )
+                        )
+                        .push(
+                            Row::new()
+                                .spacing(10)
+                                .align_items(Alignment::Center)
+                                .push(Space::new(Length::Fixed(40.0), Length::Shrink))
+                                .push(
+                                    Button::new(Text::new("▼").size(16))
+                                        .on_press(Message::CrosshairMoveDown)
+                                        .padding(8)
+                                        .width(Length::Fixed(40.0))
+                                )
+                                .push(Space::new(Length::Fixed(40.0), Length::Shrink))
+                        )
+                        .push(
+                            Text::new(t!("crosshair-offset", x = self.edit_x_offset, y = self.edit_y_offset)).size(12)
+                        )
+                )
+                .padding(15)
+                .width(Length::Fixed(200.0))
+            )
+            
+            // Manual offset input (for precise values)
+            .push(
+                Row::new()
+                    .spacing(15)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("manual")).size(12))
+                    .push(
+                        Row::new()
+                            .spacing(5)
+                            .align_items(Alignment::Center)
+                            .push(Text::new("X").size(12))
+                            .push(
+                                TextInput::new("0", &self.edit_x_offset)
+                                    .on_input(Message::CrosshairOffsetXChanged)
+                                    .width(Length::Fixed(60.0))
+                                    .padding(5)
+                            )
+                    )
+                    .push(
+                        Row::new()
+                            .spacing(5)
+                            .align_items(Alignment::Center)
+                            .push(Text::new("Y").size(12))
+                            .push(
+                                TextInput::new("0", &self.edit_y_offset)
+                                    .on_input(Message::CrosshairOffsetYChanged)
+                                    .width(Length::Fixed(60.0))
+                                    .padding(5)
+                            )
+                    )
+            )
+            .push(self.render_anchor_picker())
+            
+            .push(
+                Checkbox::new(t!("enable-crosshair-overlay"), self.edit_overlay_enabled)
+                    .on_toggle(Message::OverlayEnabledToggled)
+            )
+            .push(self.render_tint_picker())
+            .push(self.render_preset_list())
+            .push(self.render_offset_preset_list())
+            .push(self.render_note_editor())
+            .push(
+                Checkbox::new(t!("exclude-from-capture"), self.edit_exclude_from_capture)
+                    .on_toggle(Message::ExcludeFromCaptureToggled)
+            )
+            .into()
+    }
+    
+    /// Notifications, Windows game features and system tweaks
+    fn render_tweaks_tab(&self) -> Element<'_, Message> {
+        Column::new()
+            .spacing(15)
+            .push(
+                Row::new()
+                    .spacing(20)
+                    .align_items(Alignment::Center)
+                    .push(
+                        Checkbox::new(t!("do-not-disturb"), self.edit_do_not_disturb)
+                            .on_toggle(Message::DoNotDisturbToggled)
+                    )
+                    .push(
+                        if self.edit_do_not_disturb {
+                            Checkbox::new(t!("mute-notification-sounds"), self.edit_mute_notification_sounds)
+                                .on_toggle(Message::MuteNotificationSoundsToggled)
+                        } else {
+                            Checkbox::new(t!("mute-notification-sounds"), false)
+                        }
+                    )
+            )
+            .push(self.render_game_features_picker())
+            .push(
+                Checkbox::new(t!("high-precision-timer"), self.edit_high_precision_timer)
+                    .on_toggle(Message::HighPrecisionTimerToggled)
+            )
+            .push(
+                Checkbox::new(t!("best-performance-visuals"), self.edit_best_performance_visuals)
+                    .on_toggle(Message::BestPerformanceVisualsToggled)
+            )
+            .push(self.render_system_tweaks())
+            .into()
+    }
+    
+    /// Landing view: what's active, live load and one-click activation of favorite profiles
+    fn render_dashboard(&self) -> Element<'_, Message> {
+        let mut status = Column::new().spacing(6);
+        status = match self.active_profile_name {
+            Some(ref name) => status.push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("dashboard-active", profile = name)).size(18).width(Length::Fill))
+                    .push(Button::new(Text::new(t!("deactivate"))).on_press(Message::TrayDeactivate).padding(8))
+            ),
+            None => status.push(Text::new(t!("dashboard-inactive")).size(18)),
+        };
+        status = status.push(Text::new(match (&self.overlay_error, self.activation.system.overlay.is_some()) {
+            (Some(error), _) => format!("⚠️ {}", error),
+            (None, true) => t!("dashboard-overlay-on"),
+            (None, false) => t!("dashboard-overlay-off"),
+        }).size(14));
+        
+        let mut gauges = Column::new().spacing(6).push(Text::new(t!("dashboard-load")).size(18));
+        match self.hardware {
+            Some(ref snapshot) => {
+                let temp = |value: Option<f32>| value.map_or(String::new(), |t| format!(" · {:.0}°C", t));
+                let memory = match (snapshot.memory_used_kb, snapshot.memory_total_kb) {
+                    (Some(used), Some(total)) => format!(" · {:.1} / {:.1} GB", used as f32 / 1_048_576.0, total as f32 / 1_048_576.0),
+                    _ => String::new(),
+                };
+                let readings = [
+                    ("CPU", snapshot.cpu_load, temp(snapshot.cpu_temp)),
+                    ("GPU", snapshot.gpu_load, temp(snapshot.gpu_temp)),
+                    ("RAM", snapshot.memory_percent(), memory),
+                ];
+                for (label, percent, detail) in readings {
+                    let value = percent.map_or("–".to_string(), |percent| format!("{:.0}%", percent));
+                    gauges = gauges.push(
+                        Row::new()
+                            .spacing(10)
+                            .align_items(Alignment::Center)
+                            .push(Text::new(label).width(Length::Fixed(40.0)))
+                            .push(ProgressBar::new(0.0..=100.0, percent.unwrap_or(0.0)).height(Length::Fixed(12.0)).width(Length::Fill))
+                            .push(Text::new(format!("{}{}", value, detail)).size(14).width(Length::Fixed(170.0)))
+                    );
+                }
+            }
+            None => gauges = gauges.push(Text::new(t!("waiting-for-first-sample")).size(12)),
+        }
+        
+        let mut processes = Column::new().spacing(4).push(Text::new(t!("dashboard-top-processes")).size(18));
+        for process in self.hardware.iter().flat_map(|snapshot| &snapshot.top_processes) {
+            processes = processes.push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new(&process.name).size(14).width(Length::Fill))
+                    .push(Text::new(format!("{:.1}%", process.cpu_percent)).size(14).width(Length::Fixed(60.0)))
+                    .push(Text::new(t!("kill-confirm-memory", memory = process.memory_kb / 1024)).size(14).width(Length::Fixed(80.0)))
+            );
+        }
+        
+        let mut favorites = Row::new().spacing(10);
+        let mut has_favorites = false;
+        for profile in self.profiles.iter().filter(|profile| profile.favorite && !profile.archived) {
+            has_favorites = true;
+            let is_active = self.active_profile_name.as_ref() == Some(&profile.name);
+            let label = if is_active { format!("🟢 {}", profile.name) } else { format!("★ {}", profile.name) };
+            favorites = favorites.push(Button::new(Text::new(label)).on_press(Message::ActivateFavorite(profile.name.clone())).padding(12));
+        }
+        
+        fn boxed<'a>(content: Column<'a, Message>) -> Container<'a, Message> {
+            Container::new(content).width(Length::Fill).padding(8).style(iced::theme::Container::Box)
+        }
+        
+        Column::new()
+            .spacing(15)
+            .padding(20)
+            .push(Text::new(t!("dashboard")).size(24))
+            .push(boxed(status))
+            .push(
+                Column::new()
+                    .spacing(6)
+                    .push(Text::new(t!("dashboard-favorites")).size(18))
+                    .push(if has_favorites {
+                        Element::from(Scrollable::new(favorites).direction(scrollable::Direction::Horizontal(scrollable::Properties::default())))
+                    } else {
+                        Text::new(t!("dashboard-no-favorites")).size(12).into()
+                    })
+            )
+            .push(boxed(gauges))
+            .push(boxed(processes))
+            .into()
+    }
+    
+    fn render_stats(&self) -> Element<'_, Message> {
+        let week = sessions::weekly_summary(&self.sessions, sessions::unix_time());
+        let temp = |value: Option<f32>| value.map_or("–".to_string(), |t| format!("{:.0}°C", t));
+        let ping = |value: Option<f32>| value.map_or("–".to_string(), |ms| format!("{:.0} ms", ms));
+        
+        let mut content = Column::new()
+            .spacing(10)
+            .padding(20)
+            .push(Text::new(t!("session-stats")).size(24))
+            .push_maybe(self.session.as_ref().map(|session| {
+                Text::new(t!("stats-recording", profile = session.profile())).size(12)
+            }));
+        
+        content = content.push(
+            Container::new(
+                Column::new()
+                    .spacing(4)
+                    .push(Text::new(t!("last-7-days")).size(18))
+                    .push(Text::new(t!(
+                        "stats-week-summary",
+                        sessions = week.sessions,
+                        played = sessions::format_duration(week.total_secs),
+                        longest = sessions::format_duration(week.longest_secs)
+                    )))
+                    .push_maybe(week.most_played.map(|title| Text::new(t!("stats-most-played", game = title))))
+            )
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+        );
+        
+        let mut per_game = Column::new().spacing(4).push(Text::new(t!("per-game")).size(18));
+        let totals = sessions::per_game_totals(&self.sessions);
+        if totals.is_empty() {
+            per_game = per_game.push(Text::new(t!("stats-no-sessions")).size(12));
+        }
+        for total in totals {
+            per_game = per_game.push(
+                Row::new()
+                    .spacing(15)
+                    .push(Text::new(total.title).width(Length::FillPortion(3)))
+                    .push(Text::new(format!("{}×", total.sessions)).width(Length::FillPortion(1)))
+                    .push(Text::new(sessions::format_duration(total.total_secs)).width(Length::FillPortion(1)))
+                    .push(
+                        Text::new(format!("CPU {} / GPU {}", temp(total.avg_cpu_temp), temp(total.avg_gpu_temp)))
+                            .width(Length::FillPortion(2))
+                    )
+                    .push(Text::new(ping(total.avg_ping_ms)).width(Length::FillPortion(1)))
+                    .push(Text::new(t!("stats-processes-killed", count = total.processes_killed)).width(Length::FillPortion(1)))
+            );
+        }
+        content = content.push(per_game);
+        
+        let mut recent = Column::new().spacing(4).push(Text::new(t!("recent-sessions")).size(18));
+        for session in self.sessions.iter().rev().take(10) {
+            let load = |value: Option<f32>| value.map_or("–".to_string(), |l| format!("{:.0}%", l));
+            recent = recent.push(
+                Text::new(t!(
+                    "stats-session-line",
+                    profile = session.profile,
+                    game = session.title(),
+                    duration = sessions::format_duration(session.duration_secs),
+                    cpu_load = load(session.avg_cpu_load),
+                    cpu_temp = temp(session.avg_cpu_temp),
+                    gpu_load = load(session.avg_gpu_load),
+                    gpu_temp = temp(session.avg_gpu_temp),
+                    ping = ping(session.avg_ping_ms),
+                    jitter = ping(session.avg_jitter_ms)
+                ))
+                .size(12)
+            );
+        }
+        
+        let mut benchmarks = Column::new()
+            .spacing(4)
+            .push(Text::new(t!("benchmarks")).size(18))
+            .push(Text::new(t!("stats-benchmarks-hint", minutes = benchmark::SETTLE_SECS / 60)).size(12));
+        for result in self.benchmarks.iter().rev().take(5) {
+            let mut lines = Column::new().spacing(2).push(Text::new(&result.profile).size(14));
+            for line in result.comparison_lines() {
+                lines = lines.push(Text::new(line).size(12));
+            }
+            benchmarks = benchmarks.push(
+                Container::new(lines)
+                    .width(Length::Fill)
+                    .padding(8)
+                    .style(iced::theme::Container::Box)
+            );
+        }
+        
+        content.push(recent).push(benchmarks).into()
+    }
+    
+    fn render_update_settings(&self) -> Element<'_, Message> {
+        let status = if self.update_download.is_some() {
+            t!("update-downloading")
+        } else if self.update_check.is_some() {
+            t!("update-checking")
+        } else {
+            match self.available_update {
+                Some(ref release) => t!("update-available", version = release.tag),
+                None => t!("update-current-version", version = env!("CARGO_PKG_VERSION")),
+            }
+        };
+        
+        let mut actions = Row::new()
+            .spacing(5)
+            .push(
+                Button::new(Text::new(t!("check-now")).size(12))
+                    .on_press_maybe(self.update_check.is_none().then_some(Message::CheckForUpdates))
+                    .padding(4)
+            );
+        if self.available_update.is_some() {
+            actions = actions
+                .push(
+                    Button::new(Text::new(t!("install")).size(12))
+                        .on_press_maybe(self.update_download.is_none().then_some(Message::InstallUpdate))
+                        .padding(4)
+                )
+                .push(Button::new(Text::new(t!("release-notes")).size(12)).on_press(Message::OpenReleaseNotes).padding(4));
+        }
+        
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("updates")).size(16))
+                .push(
+                    Checkbox::new(t!("check-for-updates"), self.update_config.enabled)
+                        .on_toggle(Message::UpdateChecksToggled)
+                        .text_size(12)
+                )
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(
+                            PickList::new(&UpdateChannel::ALL[..], Some(self.update_config.channel), Message::UpdateChannelSelected)
+                                .text_size(12)
+                                .width(Length::Fixed(90.0))
+                        )
+                        .push(Text::new(t!("update-every")).size(12))
+                        .push(
+                            TextInput::new("24", &self.update_interval)
+                                .on_input(Message::UpdateIntervalChanged)
+                                .on_submit(Message::SaveUpdateSettings)
+                                .padding(4)
+                                .width(Length::Fixed(50.0))
+                        )
+                        .push(Text::new("h").size(12))
+                )
+                .push(Text::new(status).size(12))
+                .push(actions)
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_toast_settings(&self) -> Element<'_, Message> {
+        let mut content = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("notifications")).size(16));
+        for category in ToastCategory::ALL {
+            content = content.push(
+                Checkbox::new(category.to_string(), self.toast_config.is_enabled(category))
+                    .on_toggle(move |enabled| Message::ToastCategoryToggled(category, enabled))
+                    .text_size(12)
+            );
+        }
+        
+        Container::new(content)
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+    
+    fn render_remote_api_settings(&self) -> Element<'_, Message> {
+        let mut content = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("remote-api")).size(16))
+            .push(
+                Checkbox::new(t!("allow-remote-control"), self.api_config.enabled)
+                    .on_toggle(Message::RemoteApiToggled)
+                    .text_size(12)
+            )
+            .push(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("port")).size(12))
+                    .push(
+                        TextInput::new("8765", &self.api_port)
+                            .on_input(Message::RemoteApiPortChanged)
+                            .on_submit(Message::SaveRemoteApiSettings)
+                            .padding(4)
+                            .width(Length::Fixed(70.0))
+                    )
+                    .push(Button::new(Text::new(t!("save")).size(12)).on_press(Message::SaveRemoteApiSettings).padding(4))
+            );
+        if !self.api_config.token.is_empty() {
+            content = content
+                .push(Text::new(t!("remote-api-token", token = self.api_config.token)).size(11))
+                .push(Button::new(Text::new(t!("new-token")).size(12)).on_press(Message::NewRemoteApiToken).padding(4));
+        }
+        
+        Container::new(content.push(Text::new(t!("remote-api-hint")).size(11)))
+            .width(Length::Fill)
+            .padding(8)
+            .style(iced::theme::Container::Box)
+            .into()
+    }
+    
+    fn render_alert_settings(&self) -> Element<'_, Message> {
+        let mut choices = vec![SwitchProfileChoice::None];
+        choices.extend(self.profiles.iter().map(|p| SwitchProfileChoice::Profile(p.name.clone())));
+        let selected = self
+            .alert_config
+            .switch_to_profile
+            .clone()
+            .map_or(SwitchProfileChoice::None, SwitchProfileChoice::Profile);
+        
+        let limit = |label, value: &str, on_input: fn(String) -> Message| {
+            Row::new()
+                .spacing(5)
+                .align_items(Alignment::Center)
+                .push(Text::new(label).size(12).width(Length::Fixed(50.0)))
+                .push(
+                    TextInput::new(&t!("alert-limit-off"), value)
+                        .on_input(on_input)
+                        .on_submit(Message::SaveAlertSettings)
+                        .width(Length::Fixed(50.0))
+                        .padding(4)
+                )
+                .push(Text::new("°C").size(12))
+        };
+        
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("alerts")).size(16))
+                .push(
+                    Checkbox::new(t!("warn-when-too-hot"), self.alert_config.enabled)
+                        .on_toggle(Message::AlertsToggled)
+                )
+                .push(limit("CPU >", &self.alert_cpu_limit, Message::AlertCpuLimitChanged))
+                .push(limit("GPU >", &self.alert_gpu_limit, Message::AlertGpuLimitChanged))
+                .push(
+                    Checkbox::new(t!("max-fans"), self.alert_config.max_fans)
+                        .on_toggle(Message::AlertMaxFansToggled)
+                )
+                .push(Text::new(t!("switch-to-profile")).size(12))
+                .push(
+                    PickList::new(choices, Some(selected), Message::AlertSwitchProfileSelected)
+                        .padding(4)
+                )
+                .push(
+                    Button::new(Text::new(t!("save")).size(12))
+                        .on_press(Message::SaveAlertSettings)
+                        .padding(4)
+                )
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_hardware_monitor(&self) -> Element<'_, Message> {
+        let mut readings = Column::new().spacing(2);
+        match self.hardware {
+            Some(ref snapshot) => {
+                for line in snapshot.summary_lines() {
+                    readings = readings.push(Text::new(line).size(12));
+                }
+                if let Some(ref latency) = self.latency_stats {
+                    readings = readings.push(Text::new(latency.summary_line()).size(12)).push(
+                        Text::new(t!("latency-loss", endpoint = latency.endpoint, loss = format!("{:.0}", latency.loss_percent))).size(11)
+                    );
+                }
+                readings = readings.push(
+                    Text::new(match snapshot.provider {
+                        Some(provider) => t!("hardware-sensors", provider = provider),
+                        None => t!("hardware-basic-sensors"),
+                    })
+                    .size(11)
+                );
+            }
+            None => readings = readings.push(Text::new(t!("waiting-for-first-sample")).size(12)),
+        }
+        
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("hardware")).size(16))
+                .push(readings)
+                .push(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(t!("monitor-every")).size(12))
+                        .push(
+                            TextInput::new("2", &self.monitor_interval)
+                                .on_input(Message::MonitorIntervalChanged)
+                                .on_submit(Message::SaveMonitorSettings)
+                                .width(Length::Fixed(45.0))
+                                .padding(4)
+                        )
+                        .push(Text::new("s").size(12))
+                        .push(
+                            Button::new(Text::new(t!("save")).size(12))
+                                .on_press(Message::SaveMonitorSettings)
+                                .padding(4)
+                        )
+                )
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_game_features_picker(&self) -> Element<'_, Message> {
+        let features = [
+            (t!("game-mode"), GameFeature::GameMode, self.edit_game_features.game_mode),
+            (t!("game-bar"), GameFeature::GameBar, self.edit_game_features.game_bar),
+            (t!("background-recording"), GameFeature::BackgroundRecording, self.edit_game_features.background_recording),
+        ];
+        
+        let mut row = Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("game-features")).size(18));
+        
+        for (label, feature, value) in features {
+            row = row
+                .push(Text::new(label).size(12))
+                .push(
+                    PickList::new(
+                        &FeatureChoice::ALL[..],
+                        Some(FeatureChoice::from_option(value)),
+                        move |choice| Message::GameFeatureSelected(feature, choice),
+                    )
+                    .padding(5)
+                );
+        }
+        
+        Column::new()
+            .spacing(5)
+            .push(row)
+            .push(Text::new(t!("game-features-hint")).size(11))
+            .into()
+    }
+    
+    fn render_system_tweaks(&self) -> Element<'_, Message> {
+        let mut row = Row::new()
+            .spacing(15)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("tweaks")).size(18));
+        
+        for tweak in SystemTweak::ALL {
+            row = row.push(
+                Checkbox::new(tweak.to_string(), self.edit_system_tweaks.contains(&tweak))
+                    .on_toggle(move |enabled| Message::SystemTweakToggled(tweak, enabled))
+            );
+        }
+        let uses_admin = self.edit_system_tweaks.iter().any(|&tweak| AdminFeature::for_tweak(tweak).is_some());
+        
+        Column::new()
             .spacing(5)
-            .padding(10)
-            .push(Text::new("📋 Profiles").size(20))
-            .push(Space::new(Length::Fill, Length::Fixed(10.0)));
+            .push(row)
+            .push_maybe(self.render_admin_badge().filter(|_| uses_admin))
+            .push(Text::new(t!("tweaks-hint")).size(11))
+            .into()
+    }
+    
+    fn render_audio_editor(&self) -> Element<'_, Message> {
+        let mut list = Column::new()
+            .spacing(5)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("audio")).size(18))
+                    .push(Text::new(t!("master")).size(12))
+                    .push(
+                        TextInput::new(&t!("volume-unchanged"), &self.edit_master_volume)
+                            .on_input(Message::MasterVolumeChanged)
+                            .width(Length::Fixed(90.0))
+                            .padding(5)
+                    )
+            );
         
-        for (i, profile) in self.profiles.iter().enumerate() {
-            let is_selected = self.selected_profile_index == Some(i);
-            let is_active = self.active_profile_name.as_ref() == Some(&profile.name);
-            
-            let label = if is_active {
-                format!("🟢 {}", profile.name)
-            } else if is_selected {
-                format!("▶ {}", profile.name)
-            } else {
-                profile.name.clone()
-            };
-            
-            profile_list = profile_list.push(
-                Button::new(Text::new(label))
-                    .on_press(Message::ProfileSelected(i))
-                    .width(Length::Fill)
-                    .padding(8)
+        for (i, app) in self.edit_app_volumes.iter().enumerate() {
+            list = list.push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(format!("{} - {}%", app.process, app.percent)).size(12).width(Length::Fill))
+                    .push(
+                        Button::new(Text::new("🗑️").size(12))
+                            .on_press(Message::RemoveAppVolume(i))
+                            .padding(4)
+                    )
             );
         }
         
-        profile_list = profile_list
-            .push(Space::new(Length::Fill, Length::Fixed(10.0)))
+        list.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    TextInput::new(&t!("app-volume-placeholder"), &self.edit_app_volume_process)
+                        .on_input(Message::AppVolumeProcessChanged)
+                        .width(Length::Fixed(200.0))
+                        .padding(5)
+                )
+                .push(
+                    TextInput::new("%", &self.edit_app_volume_percent)
+                        .on_input(Message::AppVolumePercentChanged)
+                        .on_submit(Message::AddAppVolume)
+                        .width(Length::Fixed(60.0))
+                        .padding(5)
+                )
+                .push(
+                    Button::new(Text::new(t!("add-app-volume")).size(12))
+                        .on_press(Message::AddAppVolume)
+                        .padding(5)
+                )
+        )
+        .into()
+    }
+    
+    fn render_gpu_settings(&self) -> Element<'_, Message> {
+        let detected = if self.gpu_vendors.is_empty() {
+            t!("gpu-none-detected")
+        } else {
+            self.gpu_vendors.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+        };
+        
+        let mut row = Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("gpu")).size(18))
+            .push(Text::new(format!("({})", detected)).size(12))
             .push(
-                Button::new(Text::new("+ New Profile"))
-                    .on_press(Message::NewProfile)
-                    .width(Length::Fill)
-                    .padding(10)
+                Checkbox::new(t!("prefer-maximum-performance"), self.edit_gpu_prefer_max)
+                    .on_toggle(Message::GpuPreferMaxToggled)
             );
         
-        let left_panel = Container::new(
-            Scrollable::new(profile_list)
-        )
-        .width(Length::Fixed(200.0))
-        .height(Length::Fill)
-        .padding(10);
+        // Driver profiles only exist in the NVIDIA driver
+        if self.gpu_vendors.contains(&GpuVendor::Nvidia) || !self.edit_nvidia_profile.is_empty() {
+            row = row.push(
+                TextInput::new(&t!("nvidia-profile-placeholder"), &self.edit_nvidia_profile)
+                    .on_input(Message::NvidiaProfileChanged)
+                    .width(Length::Fixed(220.0))
+                    .padding(5)
+            );
+        }
         
-        // Right panel - Edit form
-        let edit_section = Column::new()
-            .spacing(15)
-            .padding(20)
-            .push(Text::new("✏️ Edit Profile").size(24))
-            
-            .push(Text::new("Profile Name"))
+        row.into()
+    }
+    
+    fn render_keyboard_settings(&self) -> Element<'_, Message> {
+        let mut choices = vec![KeyboardLayoutChoice::Unchanged];
+        choices.extend(self.keyboard_layouts.iter().cloned().map(KeyboardLayoutChoice::Layout));
+        
+        let selected = match self.edit_keyboard_layout {
+            None => KeyboardLayoutChoice::Unchanged,
+            Some(ref id) => KeyboardLayoutChoice::Layout(
+                self.keyboard_layouts
+                    .iter()
+                    .find(|layout| layout.id.eq_ignore_ascii_case(id))
+                    .cloned()
+                    .unwrap_or_else(|| KeyboardLayout { id: id.clone(), name: t!("keyboard-layout-not-installed") }),
+            ),
+        };
+        // Keep layouts that were removed since the profile was saved selectable
+        if !choices.contains(&selected) {
+            choices.push(selected.clone());
+        }
+        
+        Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("keyboard")).size(18))
+            .push(Text::new(t!("pin-layout")).size(12))
             .push(
-                TextInput::new("Enter profile name...", &self.edit_name)
-                    .on_input(Message::ProfileNameChanged)
-                    .padding(10)
-                    .width(Length::Fill)
+                PickList::new(choices, Some(selected), Message::KeyboardLayoutSelected)
+                    .padding(5)
             )
-            
-            .push(Space::new(Length::Fill, Length::Fixed(10.0)))
-            
+            .push(
+                Checkbox::new(t!("block-win-key"), self.edit_block_windows_key)
+                    .on_toggle_maybe((!self.edit_anti_cheat_safe).then_some(Message::BlockWindowsKeyToggled))
+            )
+            .push(
+                Checkbox::new(t!("no-sticky-keys-popup"), self.edit_suppress_sticky_keys)
+                    .on_toggle(Message::SuppressStickyKeysToggled)
+            )
+            .push_maybe(self.edit_anti_cheat_safe.then(|| Text::new(t!("keyboard-anti-cheat-safe")).size(12)))
+            .into()
+    }
+    
+    fn render_network_editor(&self) -> Element<'_, Message> {
+        let mut list = Column::new()
+            .spacing(5)
             .push(
                 Row::new()
-                    .spacing(20)
+                    .spacing(10)
                     .align_items(Alignment::Center)
-                    .push(Text::new("🌀 Fan Speed").size(18))
+                    .push(Text::new(t!("network")).size(18))
                     .push(
-                        Toggler::new(
-                            Some("Set to MAX when active".to_string()),
-                            self.edit_fan_speed_max,
-                            Message::FanSpeedMaxToggled
-                        )
-                        .width(Length::Shrink)
+                        Checkbox::new(t!("pause-delivery-optimization"), self.edit_pause_delivery_optimization)
+                            .on_toggle(Message::PauseDeliveryOptimizationToggled)
                     )
-            )
-            
-            .push(Space::new(Length::Fill, Length::Fixed(10.0)))
-            
-            .push(
+            );
+        
+        for (i, limit) in self.edit_bandwidth_limits.iter().enumerate() {
+            list = list.push(
                 Row::new()
                     .spacing(10)
                     .align_items(Alignment::Center)
-                    .push(Text::new("🔪 Processes to Kill").size(18))
+                    .push(Text::new(t!("bandwidth-limit-entry", process = limit.process, kbps = limit.limit_kbps)).size(12).width(Length::Fill))
                     .push(
-                        Button::new(Text::new("🔄 Refresh"))
-                            .on_press(Message::RefreshProcesses)
-                            .padding(5)
+                        Button::new(Text::new("🗑️").size(12))
+                            .on_press(Message::RemoveBandwidthLimit(i))
+                            .padding(4)
                     )
-            )
-            .push(Text::new("Select running applications to close when activating:").size(12))
-            .push(
-                TextInput::new("Filter processes...", &self.process_filter)
-                    .on_input(Message::ProcessFilterChanged)
-                    .padding(8)
-                    .width(Length::Fill)
-            )
-            .push(self.render_process_selector())
-            
-            .push(Space::new(Length::Fill, Length::Fixed(10.0)))
-            
-            .push(Text::new("🎯 Crosshair Overlay").size(18))
-            .push(Text::new("Crosshair will be centered on screen. Use arrows for pixel-perfect adjustment.").size(12))
-            
-            // Image selection row
+            );
+        }
+        
+        list.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    TextInput::new(&t!("bandwidth-limit-placeholder"), &self.edit_limit_process)
+                        .on_input(Message::LimitProcessChanged)
+                        .width(Length::Fixed(200.0))
+                        .padding(5)
+                )
+                .push(
+                    TextInput::new(&t!("kbps"), &self.edit_limit_kbps)
+                        .on_input(Message::LimitKbpsChanged)
+                        .on_submit(Message::AddBandwidthLimit)
+                        .width(Length::Fixed(80.0))
+                        .padding(5)
+                )
+                .push(
+                    Button::new(Text::new(t!("add-bandwidth-limit")).size(12))
+                        .on_press(Message::AddBandwidthLimit)
+                        .padding(5)
+                )
+        )
+        .push_maybe(self.render_admin_badge())
+        .into()
+    }
+    
+    fn render_storage_settings(&self) -> Element<'_, Message> {
+        Column::new()
+            .spacing(5)
             .push(
                 Row::new()
                     .spacing(10)
                     .align_items(Alignment::Center)
+                    .push(Text::new(t!("storage")).size(18))
                     .push(
-                        Button::new(Text::new("📁 Select Image"))
-                            .on_press(Message::SelectImage)
-                            .padding(10)
+                        Checkbox::new(t!("pause-search-indexing"), self.edit_storage.pause_search_indexing)
+                            .on_toggle(Message::PauseSearchIndexingToggled)
                     )
                     .push(
-                        if self.edit_image_path.is_some() {
-                            Button::new(Text::new("❌ Clear"))
-                                .on_press(Message::ClearImage)
-                                .padding(10)
-                        } else {
-                            Button::new(Text::new("❌ Clear")).padding(10)
-                        }
+                        Checkbox::new(t!("pause-defrag-and-maintenance-tasks"), self.edit_storage.pause_maintenance_tasks)
+                            .on_toggle(Message::PauseMaintenanceTasksToggled)
                     )
+            )
+            .push(Text::new(t!("storage-hint")).size(11))
+            .push_maybe(self.render_admin_badge())
+            .into()
+    }
+    
+    fn render_rgb_picker(&self) -> Element<'_, Message> {
+        let mut choices = vec![RgbSceneChoice::Unchanged, RgbSceneChoice::SolidColor];
+        choices.extend(self.openrgb_profiles.iter().cloned().map(RgbSceneChoice::Profile));
+        
+        let selected = match self.edit_rgb_scene {
+            None => RgbSceneChoice::Unchanged,
+            Some(RgbScene::Color(_)) => RgbSceneChoice::SolidColor,
+            Some(RgbScene::Profile(ref name)) => RgbSceneChoice::Profile(name.clone()),
+        };
+        // Keep the saved profile selectable before connecting to OpenRGB
+        if !choices.contains(&selected) {
+            choices.push(selected.clone());
+        }
+        
+        let mut row = Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("lighting")).size(18))
+            .push(
+                PickList::new(choices, Some(selected), Message::RgbSceneSelected)
+                    .padding(5)
+            );
+        
+        if let Some(RgbScene::Color([r, g, b])) = self.edit_rgb_scene {
+            row = row
+                .push(Text::new("■").size(18).style(iced::Color::from_rgb8(r, g, b)))
+                .push(
+                    TextInput::new("#RRGGBB", &self.edit_rgb_hex)
+                        .on_input(Message::RgbColorChanged)
+                        .width(Length::Fixed(100.0))
+                        .padding(5)
+                );
+        }
+        
+        if self.openrgb_profiles.is_empty() {
+            row = row.push(Text::new(t!("lighting-connect-hint")).size(11));
+        }
+        
+        row.into()
+    }
+    
+    fn render_openrgb_settings(&self) -> Element<'_, Message> {
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("openrgb")).size(16))
+                .push(
+                    TextInput::new(&t!("host"), &self.openrgb_host)
+                        .on_input(Message::OpenRgbHostChanged)
+                        .padding(4)
+                )
+                .push(
+                    TextInput::new(&t!("port"), &self.openrgb_port)
+                        .on_input(Message::OpenRgbPortChanged)
+                        .on_submit(Message::ConnectOpenRgb)
+                        .padding(4)
+                )
+                .push(
+                    Button::new(Text::new(t!("save-connect")).size(12))
+                        .on_press(Message::ConnectOpenRgb)
+                        .padding(4)
+                )
+                .push(Text::new(t!("openrgb-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_obs_picker(&self) -> Element<'_, Message> {
+        let mut choices = vec![ObsSceneChoice::Unchanged];
+        choices.extend(self.obs_scenes.iter().cloned().map(ObsSceneChoice::Scene));
+        
+        let selected = match self.edit_obs.scene {
+            None => ObsSceneChoice::Unchanged,
+            Some(ref name) => ObsSceneChoice::Scene(name.clone()),
+        };
+        // Keep the saved scene selectable before connecting to OBS
+        if !choices.contains(&selected) {
+            choices.push(selected.clone());
+        }
+        
+        let mut row = Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("obs-scene")).size(18))
+            .push(
+                PickList::new(choices, Some(selected), Message::ObsSceneSelected)
+                    .padding(5)
+            )
+            .push(
+                Checkbox::new(t!("obs-record"), self.edit_obs.record)
+                    .on_toggle(Message::ObsRecordToggled)
+            )
+            .push(
+                Checkbox::new(t!("obs-replay-buffer"), self.edit_obs.replay_buffer)
+                    .on_toggle(Message::ObsReplayBufferToggled)
+            );
+        
+        if self.obs_scenes.is_empty() {
+            row = row.push(Text::new(t!("obs-connect-hint")).size(11));
+        }
+        
+        row.into()
+    }
+    
+    fn render_capture_settings(&self) -> Element<'_, Message> {
+        let target = self.edit_captures.target_folder.clone().unwrap_or_else(|| t!("captures-next-to-them"));
+        let mut folder = Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("captures-folder", folder = target)).size(12))
+            .push(
+                Button::new(Text::new(t!("choose-folder")).size(12))
+                    .on_press(Message::ChooseCaptureFolder)
+                    .padding(4)
+            );
+        if self.edit_captures.target_folder.is_some() {
+            folder = folder.push(
+                Button::new(Text::new(t!("captures-folder-reset")).size(12))
+                    .on_press(Message::ClearCaptureFolder)
+                    .padding(4)
+            );
+        }
+        
+        let watched: Vec<String> = self.capture_folders.iter().map(|path| path.display().to_string()).collect();
+        let hint = if watched.is_empty() {
+            t!("captures-no-folders")
+        } else {
+            t!("captures-hint", folders = watched.join(", "))
+        };
+        
+        Column::new()
+            .spacing(5)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("captures")).size(18))
                     .push(
-                        if let Some(ref path) = self.edit_image_path {
-                            Text::new(format!("✓ {}", path.split('\\').last().unwrap_or(path))).size(12)
-                        } else {
-                            Text::new("No image (100x100 PNG recommended)").size(12)
-                        }
+                        Checkbox::new(t!("captures-sort"), self.edit_captures.sort)
+                            .on_toggle(Message::SortCapturesToggled)
                     )
             )
-            
-            // Crosshair adjustment box
-            .push(
-                Container::new(
-                    Column::new()
-                        .spacing(5)
-                        .align_items(Alignment::Center)
-                        .push(Text::new("Position Adjustment").size(14))
-                        .push(
-                            Row::new()
-                                .spacing(10)
-                                .align_items(Alignment::Center)
-                                .push(Space::new(Length::Fixed(40.0), Length::Shrink))
-                                .push(
-                                    Button::new(Text::new("▲").size(16))
-                                        .on_press(Message::CrosshairMoveUp)
-                                        .padding(8)
-                                        .width(Length::Fixed(40.0))
-                                )
-                                .push(Space::new(Length::Fixed(40.0), Length::Shrink))
-                        )
-                        .push(
-                            Row::new()
-                                .spacing(5)
-                                .align_items(Alignment::Center)
-                                .push(
-                                    Button::new(Text::new("◀").size(16))
-                                        .on_press(Message::CrosshairMoveLeft)
-                                        .padding(8)
-                                        .width(Length::Fixed(40.0))
-                                )
-                                .push(
-                                    Button::new(Text::new("⊙").size(14))
-                                        .on_press(Message::CrosshairCenter)
-                                        .padding(8)
-                                        .width(Length::Fixed(50.0))
-                                )
-                                .push(
-                                    Button::new(Text::new("▶").size(16))
-                                        .on_press(Message::CrosshairMoveRight)
-                                        .padding(8)
-                                        .width(Length::Fixed(40.0))
-                                )
-                        )
+            .push_maybe(self.edit_captures.sort.then_some(folder))
+            .push(Text::new(hint).size(11))
+            .into()
+    }
+    
+    fn render_obs_settings(&self) -> Element<'_, Message> {
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("obs")).size(16))
+                .push(
+                    TextInput::new(&t!("host"), &self.obs_host)
+                        .on_input(Message::ObsHostChanged)
+                        .padding(4)
+                )
+                .push(
+                    TextInput::new(&t!("port"), &self.obs_port)
+                        .on_input(Message::ObsPortChanged)
+                        .on_submit(Message::ConnectObs)
+                        .padding(4)
+                )
+                .push(
+                    TextInput::new(&t!("password"), &self.obs_password)
+                        .on_input(Message::ObsPasswordChanged)
+                        .on_submit(Message::ConnectObs)
+                        .secure(true)
+                        .padding(4)
+                )
+                .push(
+                    Button::new(Text::new(t!("save-connect")).size(12))
+                        .on_press(Message::ConnectObs)
+                        .padding(4)
+                )
+                .push(Text::new(t!("obs-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_discord_settings(&self) -> Element<'_, Message> {
+        let has_id = !self.discord_config.application_id.trim().is_empty();
+        
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("discord")).size(16))
+                .push(
+                    TextInput::new(&t!("application-id"), &self.discord_config.application_id)
+                        .on_input(Message::DiscordApplicationIdChanged)
+                        .on_submit(Message::SaveDiscordSettings)
+                        .padding(4)
+                )
+                .push(
+                    if has_id {
+                        Checkbox::new(t!("show-active-profile"), self.discord_config.enabled)
+                            .on_toggle(Message::DiscordPresenceToggled)
+                    } else {
+                        Checkbox::new(t!("show-active-profile"), false)
+                    }
+                )
+                .push(Text::new(t!("discord-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_event_log_settings(&self) -> Element<'_, Message> {
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("event-log")).size(16))
+                .push(Checkbox::new(t!("event-log-enabled"), self.event_log.enabled).on_toggle(Message::EventLogToggled))
+                .push(Text::new(t!("event-log-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_plugin_settings(&self) -> Element<'_, Message> {
+        let mut scripts = Column::new().spacing(3);
+        if self.plugin_files.is_empty() {
+            scripts = scripts.push(Text::new(t!("plugins-none")).size(12));
+        }
+        for file in &self.plugin_files {
+            let file_name = file.clone();
+            scripts = scripts.push(
+                Checkbox::new(file.as_str(), self.plugin_config.is_enabled(file))
+                    .on_toggle(move |enabled| Message::PluginToggled(file_name.clone(), enabled))
+                    .size(14)
+                    .text_size(12)
+            );
+        }
+        
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(Text::new(t!("plugins")).size(16))
+                .push(scripts)
+                .push(
+                    Row::new()
+                        .spacing(5)
                         .push(
-                            Row::new()
-                                .spacing(10)
-                                .align_items(Alignment::Center)
-                                .push(Space::new(Length::Fixed(40.0), Length::Shrink))
-                                .push(
-                                    Button::new(Text::new("▼").size(16))
-                                        .on_press(Message::CrosshairMoveDown)
-                                        .padding(8)
-                                        .width(Length::Fixed(40.0))
-                                )
-                                .push(Space::new(Length::Fixed(40.0), Length::Shrink))
+                            Button::new(Text::new(t!("plugins-open-folder")).size(12))
+                                .on_press(Message::OpenPluginsFolder)
+                                .padding(4)
                         )
                         .push(
-                            Text::new(format!("Offset: X={}, Y={}", self.edit_x_offset, self.edit_y_offset)).size(12)
+                            Button::new(Text::new(t!("plugins-reload")).size(12))
+                                .on_press(Message::ReloadPlugins)
+                                .padding(4)
                         )
                 )
-                .padding(15)
-                .width(Length::Fixed(200.0))
+                .push(Text::new(t!("plugins-hint")).size(11))
+        )
+        .width(Length::Fill)
+        .padding(8)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn render_display_mode_picker(&self) -> Element<'_, Message> {
+        let selected_monitor = self
+            .monitors
+            .iter()
+            .find(|monitor| Some(&monitor.device) == self.edit_display_device.as_ref())
+            .cloned();
+        
+        let mut choices = vec![DisplayModeChoice::Unchanged];
+        choices.extend(self.display_modes.iter().copied().map(DisplayModeChoice::Mode));
+        let selected = self.edit_display_mode.map_or(DisplayModeChoice::Unchanged, DisplayModeChoice::Mode);
+        // Keep a saved mode selectable even if the monitor no longer reports it
+        if !choices.contains(&selected) {
+            choices.push(selected);
+        }
+        
+        Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("display")).size(18))
+            .push(
+                PickList::new(self.monitors.clone(), selected_monitor, Message::DisplayMonitorSelected)
+                    .placeholder(t!("monitor-placeholder"))
+                    .padding(5)
+            )
+            .push(
+                PickList::new(choices, Some(selected), Message::DisplayModeSelected)
+                    .padding(5)
+            )
+            .into()
+    }
+    
+    fn render_power_plan_picker(&self) -> Element<'_, Message> {
+        let mut choices = vec![PowerPlanChoice::Unchanged];
+        choices.extend(self.power_plans.iter().cloned().map(PowerPlanChoice::Plan));
+        
+        let selected = match self.edit_power_plan {
+            None => PowerPlanChoice::Unchanged,
+            Some(ref guid) => PowerPlanChoice::Plan(PowerPlan {
+                guid: guid.clone(),
+                name: self.power_plan_name(guid),
+            }),
+        };
+        // Keep plans that were removed since the profile was saved selectable
+        if !choices.contains(&selected) {
+            choices.push(selected.clone());
+        }
+        
+        Row::new()
+            .spacing(20)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("power-plan")).size(18))
+            .push(
+                PickList::new(choices, Some(selected), Message::PowerPlanSelected)
+                    .padding(5)
+            )
+            .into()
+    }
+    
+    fn render_anchor_picker(&self) -> Element<'_, Message> {
+        let (anchor_x, anchor_y) = self.edit_anchor.percent();
+        let mut anchors: Vec<CrosshairAnchor> = ANCHOR_CHOICES.iter().copied().map(CrosshairAnchor::Named).collect();
+        anchors.push(match self.edit_anchor {
+            CrosshairAnchor::Percent { .. } => self.edit_anchor,
+            CrosshairAnchor::Named(_) => CrosshairAnchor::Percent { x: anchor_x, y: anchor_y },
+        });
+        
+        let mut row = Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(t!("anchor")).size(12))
+            .push(
+                PickList::new(anchors, Some(self.edit_anchor), Message::AnchorSelected)
+                    .padding(5)
+            );
+        
+        // Percentages keep the same spot across resolutions (e.g. 50% / 45%)
+        if matches!(self.edit_anchor, CrosshairAnchor::Percent { .. }) {
+            row = row
+                .push(Text::new("X %").size(12))
+                .push(
+                    TextInput::new("50", &self.edit_anchor_x)
+                        .on_input(Message::AnchorPercentXChanged)
+                        .width(Length::Fixed(60.0))
+                        .padding(5)
+                )
+                .push(Text::new("Y %").size(12))
+                .push(
+                    TextInput::new("50", &self.edit_anchor_y)
+                        .on_input(Message::AnchorPercentYChanged)
+                        .width(Length::Fixed(60.0))
+                        .padding(5)
+                );
+        }
+        
+        // Show where the current settings land on this monitor
+        let (screen_w, screen_h) = unsafe {
+            (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN))
+        };
+        let size = crosshair_gen::CROSSHAIR_SIZE as i32;
+        let (x, y) = self.edit_anchor.window_position(
+            screen_w,
+            screen_h,
+            size,
+            size,
+            self.edit_x_offset.parse().unwrap_or(0),
+            self.edit_y_offset.parse().unwrap_or(0),
+        );
+        row.push(
+            Text::new(t!("anchor-position", x = x + size / 2, y = y + size / 2, width = screen_w, height = screen_h))
+                .size(12)
+        )
+        .into()
+    }
+    
+    fn render_note_editor(&self) -> Element<'_, Message> {
+        Column::new()
+            .spacing(5)
+            .push(Text::new(t!("overlay-note-hint")).size(12))
+            .push(
+                text_editor(&self.edit_note_text)
+                    .on_action(Message::NoteTextEdited)
+                    .height(Length::Fixed(70.0))
             )
-            
-            // Manual offset input (for precise values)
             .push(
                 Row::new()
-                    .spacing(15)
+                    .spacing(10)
                     .align_items(Alignment::Center)
-                    .push(Text::new("Manual:").size(12))
+                    .push(Text::new(t!("size")).size(12))
                     .push(
-                        Row::new()
-                            .spacing(5)
-                            .align_items(Alignment::Center)
-                            .push(Text::new("X").size(12))
-                            .push(
-                                TextInput::new("0", &self.edit_x_offset)
-                                    .on_input(Message::CrosshairOffsetXChanged)
-                                    .width(Length::Fixed(60.0))
-                                    .padding(5)
-                            )
+                        TextInput::new("16", &self.edit_note_size)
+                            .on_input(Message::NoteSizeChanged)
+                            .width(Length::Fixed(50.0))
+                            .padding(5)
                     )
+                    .push(Text::new(t!("corner")).size(12))
                     .push(
-                        Row::new()
-                            .spacing(5)
-                            .align_items(Alignment::Center)
-                            .push(Text::new("Y").size(12))
-                            .push(
-                                TextInput::new("0", &self.edit_y_offset)
-                                    .on_input(Message::CrosshairOffsetYChanged)
-                                    .width(Length::Fixed(60.0))
-                                    .padding(5)
-                            )
+                        PickList::new(&Corner::ALL[..], Some(self.edit_note_corner), Message::NoteCornerSelected)
+                            .padding(5)
+                    )
+                    .push(Text::new(t!("note-color")).size(12))
+                    .push(
+                        TextInput::new("#RRGGBBAA", &self.edit_note_color_hex)
+                            .on_input(Message::NoteColorChanged)
+                            .width(Length::Fixed(100.0))
+                            .padding(5)
                     )
             )
-            
             .push(
-                Checkbox::new("Enable crosshair overlay", self.edit_overlay_enabled)
-                    .on_toggle(Message::OverlayEnabledToggled)
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(t!("overlay-hardware-readings")).size(12))
+                    .push(
+                        PickList::new(
+                            HudCornerChoice::all(),
+                            Some(self.edit_hardware_hud.map_or(HudCornerChoice::Off, HudCornerChoice::Corner)),
+                            Message::HardwareHudSelected,
+                        )
+                        .padding(5)
+                    )
             )
+            .push_maybe(self.edit_overlay_note().map(|note| {
+                // Preview rendered with the same rasterizer crosshair.exe uses
+                let bitmap = overlay_text::rasterize(&note.text, note.font_size, note.color);
+                let rgba: Vec<u8> = bitmap.pixels.into_iter().flatten().collect();
+                Container::new(Image::new(image::Handle::from_pixels(bitmap.width, bitmap.height, rgba)))
+                    .padding(8)
+                    .style(iced::theme::Container::Box)
+            }))
+            .into()
+    }
+    
+    fn render_preset_list(&self) -> Element<'_, Message> {
+        let mut list = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("weapon-presets")).size(14))
+            .push(Text::new(t!("weapon-presets-hint")).size(12));
+        
+        for (i, preset) in self.edit_presets.iter().enumerate() {
+            let image = preset
+                .image_path
+                .as_deref()
+                .map(|path| path.split('\\').next_back().unwrap_or(path))
+                .map_or_else(|| t!("preset-no-image"), str::to_string);
             
-            .push(Space::new(Length::Fill, Length::Fixed(20.0)))
-            
-            .push(
+            list = list.push(
                 Row::new()
                     .spacing(10)
+                    .align_items(Alignment::Center)
                     .push(
-                        Button::new(Text::new("💾 Save Profile"))
-                            .on_press(Message::SaveProfile)
-                            .padding(12)
+                        Text::new(format!("{} - {} ({}, {})", preset.name, image, preset.x_offset, preset.y_offset))
+                            .size(12)
+                            .width(Length::Fill)
                     )
                     .push(
-                        if self.selected_profile_index.is_some() {
-                            Button::new(Text::new("🗑️ Delete"))
-                                .on_press(Message::DeleteProfile)
-                                .padding(12)
-                        } else {
-                            Button::new(Text::new("🗑️ Delete")).padding(12)
-                        }
+                        Button::new(Text::new(t!("load")).size(12))
+                            .on_press(Message::LoadPreset(i))
+                            .padding(4)
                     )
                     .push(
-                        if self.selected_profile_index.is_some() {
-                            Button::new(Text::new("⚡ ACTIVATE"))
-                                .on_press(Message::ActivateProfile)
-                                .padding(12)
-                        } else {
-                            Button::new(Text::new("⚡ ACTIVATE")).padding(12)
-                        }
+                        Button::new(Text::new("🗑️").size(12))
+                            .on_press(Message::DeletePreset(i))
+                            .padding(4)
                     )
             );
+        }
         
-        let right_panel = Container::new(
-            Scrollable::new(edit_section)
+        list.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    TextInput::new(&t!("preset-name-placeholder"), &self.edit_preset_name)
+                        .on_input(Message::PresetNameChanged)
+                        .on_submit(Message::AddPreset)
+                        .width(Length::Fixed(200.0))
+                        .padding(5)
+                )
+                .push(
+                    Button::new(Text::new(t!("save-current-as-preset")).size(12))
+                        .on_press(Message::AddPreset)
+                        .padding(5)
+                )
+                .push(
+                    if self.active_profile_name.is_some() {
+                        Button::new(Text::new(t!("next")).size(12))
+                            .on_press(Message::CycleCrosshair)
+                            .padding(5)
+                    } else {
+                        Button::new(Text::new(t!("next")).size(12)).padding(5)
+                    }
+                )
         )
-        .width(Length::Fill)
-        .height(Length::Fill);
+        .into()
+    }
+    
+    fn render_offset_preset_list(&self) -> Element<'_, Message> {
+        let mut list = Column::new()
+            .spacing(5)
+            .push(Text::new(t!("offset-presets")).size(14))
+            .push(Text::new(t!("offset-presets-hint")).size(12));
         
-        let content = Column::new()
-            .push(
+        for (i, preset) in self.edit_offset_presets.iter().enumerate() {
+            let selected = self.edit_selected_offset_preset.as_ref() == Some(&preset.name);
+            let label = format!("{}{} ({}, {})", if selected { "✓ " } else { "" }, preset.name, preset.x_offset, preset.y_offset);
+            list = list.push(
                 Row::new()
-                    .push(left_panel)
-                    .push(right_panel)
-                    .height(Length::FillPortion(9))
-            )
-            .push(
-                Container::new(
-                    Row::new()
-                        .spacing(20)
-                        .push(Text::new(&self.status_message).size(14))
-                        .push(Space::new(Length::Fill, Length::Shrink))
-                        .push(
-                            if let Some(ref name) = self.active_profile_name {
-                                Text::new(format!("🟢 Active: {} | 📌 Tray", name)).size(14)
-                            } else {
-                                Text::new("No active profile | 📌 Tray").size(14)
-                            }
-                        )
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(label).size(12).width(Length::Fill))
+                    .push(
+                        Button::new(Text::new(if selected { t!("offset-preset-stop-using") } else { t!("offset-preset-use") }).size(12))
+                            .on_press(Message::UseOffsetPreset(i))
+                            .padding(4)
+                    )
+                    .push(
+                        Button::new(Text::new(t!("load")).size(12))
+                            .on_press(Message::LoadOffsetPreset(i))
+                            .padding(4)
+                    )
+                    .push(
+                        Button::new(Text::new("🗑️").size(12))
+                            .on_press(Message::DeleteOffsetPreset(i))
+                            .padding(4)
+                    )
+            );
+        }
+        
+        list.push(
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    TextInput::new(&t!("offset-preset-name-placeholder"), &self.edit_offset_preset_name)
+                        .on_input(Message::OffsetPresetNameChanged)
+                        .on_submit(Message::AddOffsetPreset)
+                        .width(Length::Fixed(200.0))
+                        .padding(5)
                 )
-                .width(Length::Fill)
-                .padding(10)
-                .height(Length::FillPortion(1))
+                .push(
+                    Button::new(Text::new(t!("save-offsets-as-preset")).size(12))
+                        .on_press(Message::AddOffsetPreset)
+                        .padding(5)
+                )
+        )
+        .into()
+    }
+    
+    /// Indices of the profiles matching `query`, best match first
+    /// Indices of the profiles matching `query`, best first, among the archived or the other ones
+    fn profile_matches(&self, query: &str, archived: bool) -> Vec<usize> {
+        let candidates: Vec<usize> = (0..self.profiles.len()).filter(|&i| self.profiles[i].archived == archived).collect();
+        quick_switch::rank(query, candidates.iter().map(|&i| self.profiles[i].name.as_str()))
+            .into_iter()
+            .map(|position| candidates[position])
+            .collect()
+    }
+    
+    /// Ctrl+K palette: search box and the matching profiles, each with activate and edit
+    fn render_quick_switch(&self, quick_switch: &QuickSwitch) -> Element<'_, Message> {
+        let mut results = Column::new().spacing(4);
+        let matches = self.profile_matches(&quick_switch.query, false);
+        if matches.is_empty() {
+            results = results.push(Text::new(t!("no-matching-profiles")).size(14));
+        }
+        for (position, &index) in matches.iter().enumerate().take(10) {
+            let profile = &self.profiles[index];
+            let label = if self.active_profile_name.as_ref() == Some(&profile.name) {
+                format!("🟢 {}", profile.name)
+            } else {
+                profile.name.clone()
+            };
+            let style = if position == quick_switch.selected {
+                iced::theme::Button::Primary
+            } else {
+                iced::theme::Button::Secondary
+            };
+            results = results.push(
+                Row::new()
+                    .spacing(6)
+                    .push(
+                        Button::new(Text::new(label))
+                            .on_press(Message::QuickSwitchPick { index, edit: false })
+                            .style(style)
+                            .width(Length::Fill)
+                            .padding(8)
+                    )
+                    .push(
+                        Button::new(Text::new(t!("quick-switch-edit")))
+                            .on_press(Message::QuickSwitchPick { index, edit: true })
+                            .style(iced::theme::Button::Secondary)
+                            .padding(8)
+                    )
             );
-
-        Container::new(content)
+        }
+        
+        let palette = Column::new()
+            .spacing(10)
+            .push(
+                TextInput::new(&t!("quick-switch-placeholder"), &quick_switch.query)
+                    .id(text_input::Id::new(QUICK_SWITCH_INPUT))
+                    .on_input(Message::QuickSwitchQueryChanged)
+                    .padding(10)
+                    .size(18)
+            )
+            .push(results)
+            .push(Text::new(t!("quick-switch-hint")).size(12));
+        
+        Container::new(Container::new(palette).width(Length::Fixed(480.0)).padding(20))
             .width(Length::Fill)
             .height(Length::Fill)
+            .center_x()
+            .padding(60)
             .into()
     }
-}
-
-impl GameOptimizer {
-    fn render_process_selector(&self) -> Element<Message> {
-        let filter_lower = self.process_filter.to_lowercase();
-        
-        let mut seen: HashSet<String> = HashSet::new();
-        let mut processes_to_show: Vec<(&str, &str, Option<f32>, Option<u64>)> = Vec::new();
+    
+    /// Preflight problems and running apps the pending activation would close, with
+    /// Activate/Cancel
+    fn render_kill_confirmation<'a>(&self, confirmation: &'a KillConfirmation) -> Element<'a, Message> {
+        let mut dialog = Column::new()
+            .spacing(15)
+            .push(Text::new(t!("kill-confirm-title", profile = confirmation.profile)).size(22));
         
-        for proc in &self.running_processes {
-            let name_lower = proc.name.to_lowercase();
-            if !seen.contains(&name_lower) {
-                if filter_lower.is_empty() || name_lower.contains(&filter_lower) {
-                    seen.insert(name_lower);
-                    processes_to_show.push((
-                        &proc.name,
-                        &proc.name,
-                        Some(proc.cpu_percent),
-                        Some(proc.memory_kb)
-                    ));
-                }
+        if !confirmation.problems.is_empty() {
+            let mut problems = Column::new().spacing(6).push(Text::new(t!("kill-confirm-problems")).size(14));
+            for problem in &confirmation.problems {
+                problems = problems.push(Text::new(format!("⚠ {}", problem.message())).size(14).style(styles::WARNING_TEXT));
             }
+            dialog = dialog.push(problems);
         }
         
-        for (name, exe) in COMMON_APPS.iter() {
-            let exe_lower = exe.to_lowercase();
-            if !seen.contains(&exe_lower) {
-                if self.process_selection.get(*exe).copied().unwrap_or(false) {
-                    if filter_lower.is_empty() || exe_lower.contains(&filter_lower) || name.to_lowercase().contains(&filter_lower) {
-                        seen.insert(exe_lower);
-                        processes_to_show.push((name, exe, None, None));
-                    }
-                }
+        if !confirmation.apps.is_empty() {
+            let mut apps = Column::new().spacing(6);
+            for app in &confirmation.apps {
+                let icon = self.process_icon(app.exe.as_deref());
+                let name = if app.instances > 1 {
+                    t!("kill-confirm-instances", process = app.name, count = app.instances)
+                } else {
+                    app.name.clone()
+                };
+                apps = apps.push(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(Alignment::Center)
+                        .push(icon)
+                        .push(Text::new(name).width(Length::Fill))
+                        .push(Text::new(t!("kill-confirm-memory", memory = app.memory_kb / 1024)).size(14))
+                );
             }
+            let total_mb: u64 = confirmation.apps.iter().map(|app| app.memory_kb).sum::<u64>() / 1024;
+            
+            dialog = dialog
+                .push(Text::new(t!("kill-confirm-hint")).size(14))
+                .push(
+                    // Rows are ~22px; long lists scroll
+                    Container::new(Scrollable::new(apps).height(Length::Fixed((confirmation.apps.len().min(12) * 22) as f32)))
+                        .width(Length::Fill)
+                        .padding(10)
+                        .style(iced::theme::Container::Box)
+                )
+                .push(Text::new(t!("kill-confirm-total", memory = total_mb)).size(14))
+                .push(
+                    Checkbox::new(t!("kill-confirm-dont-ask-again"), confirmation.dont_ask_again)
+                        .on_toggle(Message::KillDontAskAgainToggled)
+                );
         }
         
-        processes_to_show.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
+        let confirm = if confirmation.apps.is_empty() { t!("kill-confirm-activate-anyway") } else { t!("kill-confirm-close-and-activate") };
+        dialog = dialog.push(
+            Row::new()
+                .spacing(10)
+                .push(Space::new(Length::Fill, Length::Shrink))
+                .push(Button::new(Text::new(t!("cancel"))).on_press(Message::CancelKill).padding(10))
+                .push(Button::new(Text::new(confirm)).on_press(Message::ConfirmKill).padding(10))
+        );
+        
+        Container::new(Container::new(dialog).width(Length::Fixed(480.0)).padding(20))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+    
+    /// 16px icon of an executable, or a blank of the same size while it loads or if it has none
+    fn process_icon(&self, exe: Option<&std::path::Path>) -> Element<'static, Message> {
+        match exe.and_then(|exe| self.process_icons.get(exe)) {
+            Some(handle) => Image::new(handle.clone()).width(Length::Fixed(16.0)).height(Length::Fixed(16.0)).into(),
+            None => Space::new(Length::Fixed(16.0), Length::Fixed(16.0)).into(),
+        }
+    }
+    
+    /// Back to the first row after the list's rows changed
+    fn scroll_process_list_to_top(&mut self) -> Command<Message> {
+        self.process_list.scroll_offset = 0.0;
+        scrollable::snap_to(scrollable::Id::new(PROCESS_LIST_ID), scrollable::RelativeOffset::START)
+    }
+    
+    /// Sortable, filterable list of every process; only the rows in view are built
+    fn render_process_selector(&self) -> Element<Message> {
+        let rows = process_list::build_rows(&self.running_processes, &self.common_apps, &self.process_selection, &self.process_filter, &self.process_list);
+        let row_count = rows.len();
+        let items = process_list::with_headers(rows);
         
-        let mut grid = Column::new().spacing(3);
+        let column_button = |column: ProcessSort, width: Length| {
+            let arrow = match (self.process_list.sort == column, self.process_list.descending) {
+                (false, _) => "",
+                (true, false) => " ▲",
+                (true, true) => " ▼",
+            };
+            Button::new(Text::new(format!("{}{}", column, arrow)).size(12))
+                .on_press(Message::ProcessSortSelected(column))
+                .style(iced::theme::Button::Text)
+                .width(width)
+                .padding(4)
+        };
+        let header = Row::new()
+            .align_items(Alignment::Center)
+            .push(column_button(ProcessSort::Name, Length::Fill))
+            .push(column_button(ProcessSort::Cpu, Length::Fixed(70.0)))
+            .push(column_button(ProcessSort::Memory, Length::Fixed(90.0)));
         
-        if processes_to_show.is_empty() {
-            grid = grid.push(Text::new("No processes found matching filter").size(12));
+        let mut list = Column::new();
+        if items.is_empty() {
+            list = list.push(Text::new(t!("no-processes-found-matching-filter")).size(12));
         } else {
-            for (display_name, exe_name, cpu, mem) in processes_to_show.iter().take(50) {
-                let is_selected = self.process_selection.get(*exe_name).copied().unwrap_or(false);
-                let exe_string = exe_name.to_string();
-                
-                let info = match (cpu, mem) {
-                    (Some(c), Some(m)) => format!("{} - CPU: {:.1}% | {} MB", display_name, c, m / 1024),
-                    _ => format!("{} (not running)", display_name),
+            // Space stands in for the rows out of view, so the scrollbar covers the whole list
+            let visible = process_list::visible_rows(self.process_list.scroll_offset, items.len());
+            list = list.push(Space::new(Length::Fill, Length::Fixed(visible.start as f32 * process_list::ROW_HEIGHT)));
+            for item in &items[visible.clone()] {
+                let row = match item {
+                    ListItem::Header(category) => {
+                        list = list.push(
+                            Container::new(Text::new(category.to_string()).size(13))
+                                .height(Length::Fixed(process_list::ROW_HEIGHT))
+                                .align_y(iced::alignment::Vertical::Bottom)
+                        );
+                        continue;
+                    }
+                    ListItem::Row(row) => row,
                 };
-                
-                grid = grid.push(
-                    Checkbox::new(info, is_selected)
-                        .on_toggle(move |checked| Message::ProcessToggled(exe_string.clone(), checked))
-                        .width(Length::Fill)
-                );
-            }
-            
-            if processes_to_show.len() > 50 {
-                grid = grid.push(
-                    Text::new(format!("... and {} more (use filter)", processes_to_show.len() - 50)).size(12)
+                let is_selected = self.process_selection.get(row.exe).copied().unwrap_or(false);
+                let exe = row.exe.to_string();
+                let (cpu, memory) = match (row.cpu_percent, row.memory_kb) {
+                    (Some(cpu), Some(memory_kb)) => (format!("{:.1}%", cpu), format!("{} MB", memory_kb / 1024)),
+                    _ => (String::new(), t!("process-not-running-short")),
+                };
+                list = list.push(
+                    Row::new()
+                        .spacing(6)
+                        .align_items(Alignment::Center)
+                        .height(Length::Fixed(process_list::ROW_HEIGHT))
+                        .push(self.process_icon(row.exe_path))
+                        .push(
+                            Checkbox::new(row.label, is_selected)
+                                .on_toggle(move |checked| Message::ProcessToggled(exe.clone(), checked))
+                                .width(Length::Fill)
+                        )
+                        .push(Text::new(cpu).size(12).width(Length::Fixed(70.0)))
+                        .push(Text::new(memory).size(12).width(Length::Fixed(90.0)))
                 );
             }
+            let below = items.len() - visible.end;
+            list = list.push(Space::new(Length::Fill, Length::Fixed(below as f32 * process_list::ROW_HEIGHT)));
         }
         
-        Container::new(
-            Scrollable::new(grid).height(Length::Fixed(200.0))
-        )
-        .width(Length::Fill)
-        .into()
+        let selected_count = self.process_selection.values().filter(|&&selected| selected).count();
+        Column::new()
+            .spacing(5)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(
+                        Checkbox::new(t!("process-selected-only"), self.process_list.selected_only)
+                            .on_toggle(Message::ProcessSelectedOnlyToggled)
+                    )
+                    .push(Space::new(Length::Fill, Length::Shrink))
+                    .push(Text::new(t!("process-list-count", count = row_count, selected = selected_count)).size(12))
+            )
+            .push(header)
+            .push(
+                Container::new(
+                    Scrollable::new(list)
+                        .id(scrollable::Id::new(PROCESS_LIST_ID))
+                        .on_scroll(Message::ProcessListScrolled)
+                        .height(Length::Fixed(process_list::LIST_HEIGHT))
+                )
+                .width(Length::Fill)
+            )
+            .into()
     }
 }
 
-pub fn run() -> iced::Result {
-    println!("[GUI] Starting GUI with integrated tray...");
+/// `restore_profile` is activated once the window is up (set by a crash restart)
+pub fn run(restore_profile: Option<String>, minimized: bool) -> iced::Result {
+    info!("[GUI] Starting GUI with integrated tray...");
+    let layout = load_config().window;
     
     // Tray is created inside Application::new() on main thread
     let result = GameOptimizer::run(Settings {
         window: iced::window::Settings {
-            size: iced::Size::new(1000.0, 750.0),
-            min_size: Some(iced::Size::new(900.0, 650.0)),
+            size: iced::Size::new(layout.width.max(MIN_WINDOW_WIDTH), layout.height.max(MIN_WINDOW_HEIGHT)),
+            position: layout.position.map_or(iced::window::Position::Default, |(x, y)| {
+                iced::window::Position::Specific(iced::Point::new(x as f32, y as f32))
+            }),
+            min_size: Some(iced::Size::new(MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT)),
+            // Tray only until "Open Settings" or another launch brings the window up
+            visible: !minimized,
+            // Closing goes through WindowCloseRequested (unsaved edits, clean exit)
+            exit_on_close_request: false,
             ..Default::default()
         },
+        flags: restore_profile,
         ..Default::default()
     });
     
-    println!("[GUI] Iced returned: {:?}", result);
+    info!("[GUI] Iced returned: {:?}", result);
     result
 }
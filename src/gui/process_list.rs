@@ -0,0 +1,241 @@
+//! Rows of the "processes to kill" selector
+//!
+//! One row per running process name, plus selected common apps that aren't running so they
+//! can still be unticked. The rows are filtered, grouped by app category and sorted here; the
+//! GUI only builds widgets for the rows in view, so the full list scrolls smoothly however many
+//! processes run.
+
+use crate::common_apps::{AppCategory, CommonApp};
+use crate::i18n::t;
+use crate::process::ProcessInfo;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+/// Height of one row; the list pads the rows out of view with space of this size
+pub const ROW_HEIGHT: f32 = 28.0;
+/// Height of the visible part of the list
+pub const LIST_HEIGHT: f32 = 240.0;
+/// Rows built above and below the visible ones, so fast scrolling doesn't show gaps
+const OVERSCAN: usize = 4;
+
+/// Column the list is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessSort {
+    #[default]
+    Name,
+    Cpu,
+    Memory,
+}
+
+impl fmt::Display for ProcessSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessSort::Name => write!(f, "{}", t!("process-column-name")),
+            ProcessSort::Cpu => write!(f, "{}", t!("process-column-cpu")),
+            ProcessSort::Memory => write!(f, "{}", t!("process-column-memory")),
+        }
+    }
+}
+
+/// Sorting, the "selected only" toggle and the scroll position of the list
+#[derive(Debug, Clone, Default)]
+pub struct ProcessListState {
+    pub sort: ProcessSort,
+    pub descending: bool,
+    pub selected_only: bool,
+    pub scroll_offset: f32,
+}
+
+impl ProcessListState {
+    /// Sort by `column`; picking the current column again flips the direction. CPU and memory
+    /// start with the heaviest processes.
+    pub fn sort_by(&mut self, column: ProcessSort) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = column != ProcessSort::Name;
+        }
+    }
+}
+
+/// A row of the selector
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessRow<'a> {
+    /// Shown name (the app name for common apps)
+    pub label: &'a str,
+    /// Executable name stored in the profile
+    pub exe: &'a str,
+    /// Full executable path, for the icon
+    pub exe_path: Option<&'a Path>,
+    /// None when the app isn't running
+    pub cpu_percent: Option<f32>,
+    pub memory_kb: Option<u64>,
+    /// Group of the matching common app (Other for the rest)
+    pub category: AppCategory,
+}
+
+/// A line of the selector; headers take a row's height so the list can still be windowed
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListItem<'a> {
+    Header(AppCategory),
+    Row(ProcessRow<'a>),
+}
+
+/// Rows matching `filter` (case-insensitive), by category, then sorted as `state` says
+pub fn build_rows<'a>(
+    running: &'a [ProcessInfo],
+    common_apps: &'a [CommonApp],
+    selection: &HashMap<String, bool>,
+    filter: &str,
+    state: &ProcessListState,
+) -> Vec<ProcessRow<'a>> {
+    let filter = filter.to_lowercase();
+    let is_selected = |exe: &str| selection.get(exe).copied().unwrap_or(false);
+    let categories: HashMap<String, AppCategory> = common_apps
+        .iter()
+        .map(|app| (app.executable.to_lowercase(), app.category))
+        .collect();
+    let mut seen = HashSet::new();
+    let mut rows = Vec::new();
+
+    for process in running {
+        let name_lower = process.name.to_lowercase();
+        if seen.contains(&name_lower) || !name_lower.contains(&filter) {
+            continue;
+        }
+        let category = categories.get(&name_lower).copied().unwrap_or_default();
+        seen.insert(name_lower);
+        rows.push(ProcessRow {
+            label: &process.name,
+            exe: &process.name,
+            exe_path: process.exe.as_deref(),
+            cpu_percent: Some(process.cpu_percent),
+            memory_kb: Some(process.memory_kb),
+            category,
+        });
+    }
+    for app in common_apps {
+        let exe_lower = app.executable.to_lowercase();
+        let matches = exe_lower.contains(&filter) || app.name.to_lowercase().contains(&filter);
+        if !seen.contains(&exe_lower) && is_selected(&app.executable) && matches {
+            seen.insert(exe_lower);
+            rows.push(ProcessRow {
+                label: &app.name,
+                exe: &app.executable,
+                exe_path: None,
+                cpu_percent: None,
+                memory_kb: None,
+                category: app.category,
+            });
+        }
+    }
+    if state.selected_only {
+        rows.retain(|row| is_selected(row.exe));
+    }
+
+    rows.sort_by(|a, b| {
+        let name_order = a.label.to_lowercase().cmp(&b.label.to_lowercase());
+        let order = match state.sort {
+            ProcessSort::Name => name_order,
+            ProcessSort::Cpu => a.cpu_percent.unwrap_or(-1.0).total_cmp(&b.cpu_percent.unwrap_or(-1.0)),
+            ProcessSort::Memory => a.memory_kb.map_or(-1, |kb| kb as i64).cmp(&b.memory_kb.map_or(-1, |kb| kb as i64)),
+        };
+        let order = if state.descending { order.reverse() } else { order };
+        a.category.cmp(&b.category).then(order).then(name_order)
+    });
+    rows
+}
+
+/// `rows` (as sorted by `build_rows`) with a header before each category
+pub fn with_headers(rows: Vec<ProcessRow<'_>>) -> Vec<ListItem<'_>> {
+    let mut items = Vec::with_capacity(rows.len() + 8);
+    let mut current = None;
+    for row in rows {
+        if current != Some(row.category) {
+            current = Some(row.category);
+            items.push(ListItem::Header(row.category));
+        }
+        items.push(ListItem::Row(row));
+    }
+    items
+}
+
+/// Rows to build widgets for at `scroll_offset`, out of `total`
+pub fn visible_rows(scroll_offset: f32, total: usize) -> Range<usize> {
+    let first = (scroll_offset.max(0.0) / ROW_HEIGHT) as usize;
+    let last = ((scroll_offset.max(0.0) + LIST_HEIGHT) / ROW_HEIGHT).ceil() as usize;
+    first.saturating_sub(OVERSCAN).min(total)..(last + OVERSCAN).min(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(name: &str, cpu_percent: f32, memory_kb: u64) -> ProcessInfo {
+        ProcessInfo { pid: 0, name: name.to_string(), memory_kb, cpu_percent, exe: None }
+    }
+
+    fn names<'a>(rows: Vec<ProcessRow<'a>>) -> Vec<&'a str> {
+        rows.iter().map(|row| row.label).collect()
+    }
+
+    fn app(name: &str, executable: &str, category: AppCategory) -> CommonApp {
+        CommonApp { name: name.to_string(), executable: executable.to_string(), category }
+    }
+
+    #[test]
+    fn test_build_rows() {
+        let running = [process("steam.exe", 1.0, 300_000), process("Discord.exe", 5.0, 200_000), process("steam.exe", 0.0, 0)];
+        let common = [app("Discord", "Discord.exe", AppCategory::Other), app("OBS", "obs64.exe", AppCategory::Other), app("Zoom", "Zoom.exe", AppCategory::Other)];
+        let selection = HashMap::from([("obs64.exe".to_string(), true), ("steam.exe".to_string(), true)]);
+        let mut state = ProcessListState::default();
+
+        // Duplicates merged, running apps not listed twice, unselected common apps left out
+        assert_eq!(names(build_rows(&running, &common, &selection, "", &state)), ["Discord.exe", "OBS", "steam.exe"]);
+        assert_eq!(names(build_rows(&running, &common, &selection, "OB", &state)), ["OBS"]);
+
+        state.sort_by(ProcessSort::Memory);
+        assert_eq!(names(build_rows(&running, &common, &selection, "", &state)), ["steam.exe", "Discord.exe", "OBS"]);
+        state.sort_by(ProcessSort::Memory);
+        assert_eq!(names(build_rows(&running, &common, &selection, "", &state)), ["OBS", "Discord.exe", "steam.exe"]);
+
+        state.selected_only = true;
+        assert_eq!(names(build_rows(&running, &common, &selection, "", &state)), ["OBS", "steam.exe"]);
+    }
+
+    #[test]
+    fn test_category_groups() {
+        let running = [process("Steam.exe", 1.0, 300_000), process("notepad.exe", 9.0, 10_000), process("discord.exe", 5.0, 200_000)];
+        let common = [
+            app("Discord", "Discord.exe", AppCategory::Chat),
+            app("Steam", "Steam.exe", AppCategory::Launchers),
+            app("Epic Games Launcher", "EpicGamesLauncher.exe", AppCategory::Launchers),
+        ];
+        let selection = HashMap::from([("EpicGamesLauncher.exe".to_string(), true)]);
+        let mut state = ProcessListState::default();
+        state.sort_by(ProcessSort::Cpu);
+
+        // Categories keep their order; the sort applies within each
+        let rows = build_rows(&running, &common, &selection, "", &state);
+        assert_eq!(names(rows.clone()), ["Steam.exe", "Epic Games Launcher", "discord.exe", "notepad.exe"]);
+        let headers: Vec<AppCategory> = with_headers(rows)
+            .into_iter()
+            .filter_map(|item| match item {
+                ListItem::Header(category) => Some(category),
+                ListItem::Row(_) => None,
+            })
+            .collect();
+        assert_eq!(headers, [AppCategory::Launchers, AppCategory::Chat, AppCategory::Other]);
+    }
+
+    #[test]
+    fn test_visible_rows() {
+        assert_eq!(visible_rows(0.0, 1000), 0..13);
+        assert_eq!(visible_rows(ROW_HEIGHT * 100.0, 1000), 96..113);
+        assert_eq!(visible_rows(ROW_HEIGHT * 100.0, 105), 96..105);
+        assert_eq!(visible_rows(0.0, 3), 0..3);
+    }
+}
@@ -1,6 +1,7 @@
 /// Profile editor UI components
 use iced::{Element, widget::{Column, Row, Text, Button, TextInput, Checkbox, Space, Container}, Length, Alignment};
 use crate::gui::Message;
+use crate::i18n::t;
 use crate::profile::Profile;
 
 #[derive(Debug, Clone)]
@@ -41,17 +42,17 @@ pub fn render_editor(profile: &EditingProfile) -> Element<'static, Message> {
         .spacing(15)
         .padding(20)
         .push(
-            Text::new("Profile Name")
+            Text::new(t!("profile-name"))
                 .size(18)
         )
         .push(
-            TextInput::new("Enter profile name", &profile.name)
+            TextInput::new(&t!("editor-profile-name-placeholder"), &profile.name)
                 .on_input(Message::ProfileNameChanged)
                 .width(Length::Fill)
         )
         .push(Space::new(Length::Fill, Length::Fixed(10.0)))
         .push(
-            Text::new("Crosshair Settings")
+            Text::new(t!("crosshair-settings"))
                 .size(16)
         )
         .push(
@@ -61,9 +62,9 @@ pub fn render_editor(profile: &EditingProfile) -> Element<'static, Message> {
                 .push(
                     Column::new()
                         .spacing(10)
-                        .push(Text::new("X Offset (pixels)"))
+                        .push(Text::new(t!("x-offset-pixels")))
                         .push(
-                            TextInput::new("-500 to 500", &profile.crosshair_x_offset)
+                            TextInput::new(&t!("offset-range-placeholder"), &profile.crosshair_x_offset)
                                 .on_input(Message::CrosshairOffsetXChanged)
                                 .width(Length::Fixed(150.0))
                         )
@@ -71,9 +72,9 @@ pub fn render_editor(profile: &EditingProfile) -> Element<'static, Message> {
                 .push(
                     Column::new()
                         .spacing(10)
-                        .push(Text::new("Y Offset (pixels)"))
+                        .push(Text::new(t!("y-offset-pixels")))
                         .push(
-                            TextInput::new("-500 to 500", &profile.crosshair_y_offset)
+                            TextInput::new(&t!("offset-range-placeholder"), &profile.crosshair_y_offset)
                                 .on_input(Message::CrosshairOffsetYChanged)
                                 .width(Length::Fixed(150.0))
                         )
@@ -87,9 +88,9 @@ pub fn render_editor(profile: &EditingProfile) -> Element<'static, Message> {
                 .push(
                     Column::new()
                         .spacing(10)
-                        .push(Text::new("Crosshair Image (100x100 PNG)"))
+                        .push(Text::new(t!("crosshair-image-hint")))
                         .push(
-                            Button::new(Text::new("Select Image"))
+                            Button::new(Text::new(t!("editor-select-image")))
                                 .on_press(Message::SelectImage)
                                 .padding(10)
                         )
@@ -98,13 +99,13 @@ pub fn render_editor(profile: &EditingProfile) -> Element<'static, Message> {
                     if let Some(path) = &profile.crosshair_image_path {
                         Text::new(format!("✓ {}", path)).size(12)
                     } else {
-                        Text::new("No image selected").size(12)
+                        Text::new(t!("no-image-selected")).size(12)
                     }
                 )
         )
         .push(Space::new(Length::Fill, Length::Fixed(10.0)))
         .push(
-            Checkbox::new("Enable crosshair overlay", profile.overlay_enabled)
+            Checkbox::new(t!("enable-crosshair-overlay"), profile.overlay_enabled)
                 .on_toggle(Message::OverlayEnabledToggled)
         );
 
@@ -0,0 +1,72 @@
+//! Fuzzy profile search
+//!
+//! Used by the search box above the profile list and the Ctrl+K quick switcher. A name
+//! matches when the query's characters appear in it in order; consecutive characters and
+//! characters at the start of a word rank higher, so "cs" finds "Counter-Strike" before
+//! "Casual".
+
+/// Quick switcher state while it's open
+#[derive(Debug, Clone, Default)]
+pub struct QuickSwitch {
+    pub query: String,
+    /// Position in the ranked matches
+    pub selected: usize,
+}
+
+/// Score of `name` for `query` (higher is better), None if it doesn't match
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + name[position..].iter().position(|&c| c == query_char)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || matches!(name[found - 1], ' ' | '-' | '_' | '.') {
+            score += 8;
+        }
+        // Small penalty for skipped characters
+        score -= (found - position).min(3) as i32;
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+/// Indices of the matching `names`, best first (original order for an empty query or ties)
+pub fn rank<'a>(query: &str, names: impl IntoIterator<Item = &'a str>) -> Vec<usize> {
+    let mut matches: Vec<(usize, i32)> = names
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, name)| fuzzy_score(query, name).map(|score| (index, score)))
+        .collect();
+    matches.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    matches.into_iter().map(|(index, _)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("val", "Valorant").is_some());
+        assert!(fuzzy_score("VLR", "valorant").is_some());
+        assert!(fuzzy_score("rv", "Valorant").is_none());
+        assert_eq!(fuzzy_score("", "Anything"), Some(0));
+        // Word starts beat letters in the middle
+        assert!(fuzzy_score("cs", "Counter-Strike") > fuzzy_score("cs", "Casual"));
+    }
+
+    #[test]
+    fn test_rank() {
+        let names = ["Casual", "Counter-Strike", "Valorant"];
+        assert_eq!(rank("cs", names), vec![1, 0]);
+        assert_eq!(rank("", names), vec![0, 1, 2]);
+        assert!(rank("xyz", names).is_empty());
+    }
+}
@@ -1,5 +1,59 @@
 /// ICED theme and styling
+///
+/// The GUI follows the Windows "app mode" (dark/light) unless the user picks one, and uses
+/// the chosen accent color for buttons, toggles and selections.
 
-pub fn theme() -> iced::Theme {
-    iced::Theme::Dark
+pub use crate::theme::{system_prefers_dark, Accent, ThemeConfig, ThemeMode};
+use iced::theme::Palette;
+use iced::widget::container;
+use iced::{Color, Theme};
+
+/// Text color for inline errors
+pub const ERROR_TEXT: Color = Color::from_rgb(0.9, 0.3, 0.3);
+/// Text color for lint warnings
+pub const WARNING_TEXT: Color = Color::from_rgb(0.9, 0.6, 0.2);
+/// Background of the safe mode banner
+const BANNER: Color = Color::from_rgb(0.8, 0.45, 0.1);
+
+/// Orange strip with white text, for a state the user has to notice (safe mode)
+pub fn banner(_theme: &Theme) -> container::Appearance {
+    container::Appearance {
+        background: Some(BANNER.into()),
+        text_color: Some(Color::WHITE),
+        ..Default::default()
+    }
+}
+
+/// Color of an accent for widgets
+pub fn accent_color(accent: Accent) -> Color {
+    let [r, g, b] = accent.rgb();
+    Color::from_rgb8(r, g, b)
+}
+
+/// Build the theme for `config`; `system_dark` is the current Windows app mode
+pub fn theme(config: &ThemeConfig, system_dark: bool) -> Theme {
+    let dark = match config.mode {
+        ThemeMode::System => system_dark,
+        ThemeMode::Dark => true,
+        ThemeMode::Light => false,
+    };
+    let base = if dark { Palette::DARK } else { Palette::LIGHT };
+    let name = format!("{} {}", if dark { "Dark" } else { "Light" }, config.accent);
+    Theme::custom(name, Palette { primary: accent_color(config.accent), ..base })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_follows_mode() {
+        let system = ThemeConfig::default();
+        assert_eq!(theme(&system, true).palette().background, Palette::DARK.background);
+        assert_eq!(theme(&system, false).palette().background, Palette::LIGHT.background);
+
+        let light = ThemeConfig { mode: ThemeMode::Light, accent: Accent::Green };
+        assert_eq!(theme(&light, true).palette().background, Palette::LIGHT.background);
+        assert_eq!(theme(&light, true).palette().primary, accent_color(Accent::Green));
+    }
 }
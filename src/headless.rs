@@ -0,0 +1,631 @@
+//! --tray-only and --headless: profiles without the profile manager window
+//!
+//! Tray-only keeps the tray icon, its menu and the flyout; headless has neither and is driven
+//! by `gaming_optimizer <command>` and the remote API. Both apply profiles through the same
+//! `ActivationManager` as the window.
+
+use crate::activation::manager::{Activation, ActivationManager};
+use crate::activation::ExitBehavior;
+use crate::autostart::{self, AutostartConfig};
+use crate::cli::{self, CliCommand, CliReply, LiveStatus, OverlayState};
+use crate::config::{self, get_data_directory, load_config, read_config, save_config, SessionState};
+use crate::crash;
+use crate::dialogs;
+use crate::crosshair_overlay;
+use crate::file_watch::DataWatcher;
+use crate::gamepad::{self, GamepadAction, GamepadConfig, GamepadThread};
+use crate::game_knowledge::{self, GameEntry};
+use crate::game_watch::GameWatcher;
+use crate::preflight::{self, Environment};
+use crate::i18n::{self, t};
+use crate::integrations::event_log::{self, EventLogConfig};
+use crate::ipc::{self, DataFile, RequestReceiver, RequestSender, TrayToGui};
+use crate::logging;
+use crate::monitor::HardwareMonitor;
+use crate::obs::ObsConfig;
+use crate::openrgb::OpenRgbConfig;
+use crate::overlay_conflicts;
+use crate::plugins::{PluginConfig, PluginEvent, PluginHost, SystemContext};
+use crate::power::{self, PowerPlan};
+use crate::profile::{self, load_profiles, save_profiles, sync_profiles, Profile, ProfileUsage};
+use crate::remote_api::RemoteApi;
+use crate::sessions;
+use crate::toast_notifier;
+use crate::toasts::{ToastCategory, ToastConfig};
+use crate::tray_badge::TrayState;
+use crate::tray_flyout::{self, TrayFlyoutManager};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::{error, info, warn};
+
+struct Headless {
+    data_dir: PathBuf,
+    data_watcher: Option<DataWatcher>,
+    /// Running while "activate when a game starts" is on
+    game_watcher: Option<GameWatcher>,
+    games: Vec<GameEntry>,
+    profiles: Vec<Profile>,
+    power_plans: Vec<PowerPlan>,
+    openrgb: OpenRgbConfig,
+    obs: ObsConfig,
+    toast_config: ToastConfig,
+    event_log: EventLogConfig,
+    exit_behavior: ExitBehavior,
+    autostart: AutostartConfig,
+    profile_usage: HashMap<String, ProfileUsage>,
+    activation: ActivationManager,
+    active_profile: Option<String>,
+    crosshair_slot: usize,
+    activation_failed: bool,
+    paused: bool,
+    /// Last active profile, overlay and pause state written to config.json
+    saved_session: SessionState,
+    tray: Option<TrayFlyoutManager>,
+    /// Running while the tray tooltip shows hardware readings
+    hardware_monitor: Option<HardwareMonitor>,
+    /// Controller combos for the crosshair
+    gamepad_config: GamepadConfig,
+    gamepad: GamepadThread,
+    plugin_config: PluginConfig,
+    plugins: Option<PluginHost>,
+    /// Sends plugin commands to this loop, like the pipe and the remote API
+    cli_tx: RequestSender,
+}
+
+/// Run until the tray's Exit or `gaming_optimizer quit`. `restore_profile` is activated right
+/// away (set by a crash restart).
+pub fn run(tray: bool, restore_profile: Option<String>) -> Result<()> {
+    info!("[Headless] Starting {}", if tray { "tray-only" } else { "headless" });
+    let app_config = load_config();
+
+    // Pick up profiles edited on another machine first
+    let data_dir = get_data_directory()?;
+    if let Some(ref sync_dir) = app_config.sync_folder {
+        if let Err(e) = sync_profiles(&data_dir, sync_dir) {
+            warn!("[Sync] {}", e);
+        }
+    }
+    let profiles = load_profiles(&data_dir)?;
+
+    let (cli_tx, cli_rx) = ipc::request_channel();
+    cli::spawn_pipe_server(cli_tx.clone());
+    let _remote_api = app_config
+        .api
+        .enabled
+        .then(|| RemoteApi::spawn(&app_config.api, cli_tx.clone()))
+        .and_then(|started| started.map_err(|e| warn!("[API] {}", e)).ok());
+
+    let tray = if tray {
+        match TrayFlyoutManager::new(profiles.clone(), app_config.active_profile.clone()) {
+            Ok(mut tray) => {
+                tray.set_click_config(app_config.tray_clicks);
+                tray.set_autostart_checked(app_config.autostart.enabled);
+                Some(tray)
+            }
+            Err(e) => {
+                error!("[Headless] Failed to create tray: {}", e);
+                return Err(e);
+            }
+        }
+    } else {
+        None
+    };
+
+    let plugins = PluginHost::start(&data_dir, &app_config.plugins, &cli_tx);
+    let mut app = Headless {
+        data_watcher: DataWatcher::spawn(&data_dir).map_err(|e| warn!("[Watch] {}", e)).ok(),
+        game_watcher: app_config
+            .auto_activate_games
+            .then(GameWatcher::spawn)
+            .and_then(|started| started.map_err(|e| warn!("[Games] {}", e)).ok()),
+        games: game_knowledge::load_games(Some(&data_dir)),
+        data_dir,
+        profiles,
+        power_plans: power::list_power_plans().unwrap_or_default(),
+        openrgb: app_config.openrgb.clone(),
+        obs: app_config.obs.clone(),
+        toast_config: app_config.toasts.clone(),
+        event_log: app_config.event_log.clone(),
+        exit_behavior: app_config.exit_behavior,
+        autostart: app_config.autostart.clone(),
+        profile_usage: app_config.profile_usage.clone(),
+        activation: crosshair_overlay::activation_manager(),
+        active_profile: None,
+        crosshair_slot: 0,
+        activation_failed: false,
+        paused: false,
+        saved_session: app_config.session_state(),
+        hardware_monitor: None,
+        gamepad_config: app_config.gamepad.clone(),
+        gamepad: spawn_gamepad(&app_config.gamepad),
+        plugins,
+        plugin_config: app_config.plugins.clone(),
+        cli_tx,
+        tray,
+    };
+    app.set_hardware_monitor(&app_config);
+    crate::process::set_user_protected(&app_config.safety.protected_processes);
+    app.update_tray();
+
+    if config::is_safe_mode() {
+        info!("[Headless] {}", t!("status-safe-mode"));
+    } else if let Some(name) = restore_profile {
+        info!("[Headless] Reactivating {} after a crash", name);
+        if let Err(e) = app.activate(&name) {
+            warn!("[Headless] {}", e);
+        }
+    } else if app_config.resume_last_session {
+        app.resume_session(&app_config.session_state());
+    }
+
+    let deactivate = app.run_loop(&cli_rx);
+    // Saved as it was before shutting down, so it can be resumed
+    app.remember_session();
+    app.activation.shutdown(deactivate);
+    info!("[Headless] Exiting");
+    Ok(())
+}
+
+impl Headless {
+    /// Serve the tray and commands; returns whether to undo the profile on exit
+    fn run_loop(&mut self, cli_rx: &RequestReceiver) -> bool {
+        loop {
+            if tray_flyout::pump_messages() {
+                info!("[Headless] WM_QUIT received");
+                return self.exit_behavior != ExitBehavior::LeaveActive;
+            }
+
+            match self.tray.as_mut().and_then(|tray| tray.poll()) {
+                Some(TrayToGui::ActivateProfile(name)) => {
+                    if let Err(e) = self.activate(&name) {
+                        self.notify_error(&e);
+                    }
+                }
+                Some(TrayToGui::DeactivateProfile) => self.deactivate(),
+                Some(TrayToGui::ToggleOverlay) => self.toggle_overlay(),
+                Some(TrayToGui::CycleCrosshair) => self.cycle_crosshair(),
+                Some(TrayToGui::SelectOffsetPreset(preset)) => self.select_offset_preset(preset),
+                Some(TrayToGui::TogglePause) => self.set_paused(!self.paused),
+                Some(TrayToGui::SetAutostart(enabled)) => {
+                    let config = AutostartConfig { enabled, ..self.autostart.clone() };
+                    match autostart::save(&config) {
+                        Ok(()) => self.autostart = config,
+                        Err(e) => warn!("[Headless] {}", t!("status-autostart-failed", error = e)),
+                    }
+                    if let Some(ref tray) = self.tray {
+                        tray.set_autostart_checked(self.autostart.enabled);
+                    }
+                }
+                // Both need the window's hotkeys and editor
+                Some(TrayToGui::AdjustCrosshair) | Some(TrayToGui::OpenSettings) => {
+                    info!("[Headless] No window in tray-only mode - start without --tray-only for settings");
+                }
+                Some(TrayToGui::Exit) => {
+                    if let Some(deactivate) = dialogs::confirm_exit(self.exit_behavior, self.active_profile.as_deref()) {
+                        return deactivate;
+                    }
+                }
+                None => {}
+            }
+
+            for file in self.data_watcher.as_mut().map(DataWatcher::poll).unwrap_or_default() {
+                match file {
+                    DataFile::Profiles => self.reload_profiles(),
+                    DataFile::Config => self.reload_config(),
+                }
+            }
+
+            for action in self.gamepad.actions.try_iter().collect::<Vec<_>>() {
+                // Paused hides the crosshair, so the combos wait too
+                if self.paused {
+                    continue;
+                }
+                match action {
+                    GamepadAction::ToggleOverlay => self.toggle_overlay(),
+                    GamepadAction::CycleCrosshair => self.cycle_crosshair(),
+                }
+            }
+
+            for exe in self.game_watcher.as_ref().map(GameWatcher::poll).unwrap_or_default() {
+                let name = game_knowledge::profile_for_launch(&self.games, &self.profiles, &exe).map(|p| p.name.clone());
+                if let Some(game) = game_knowledge::game_for_exe(&self.games, &exe) {
+                    let event = PluginEvent::GameStarted { game: game.name.clone(), exe: exe.clone(), profile: name.clone() };
+                    self.publish_plugin_event(event);
+                }
+                let Some(name) = name else {
+                    continue;
+                };
+                if self.paused || self.active_profile.as_deref() == Some(name.as_str()) {
+                    continue;
+                }
+                info!("[Games] {} started, activating {}", exe, name);
+                if let Err(e) = self.activate(&name) {
+                    warn!("[Headless] {}", e);
+                }
+            }
+
+            while let Some(request) = cli_rx.try_recv() {
+                let quit = request.command == CliCommand::Quit;
+                let reply = self.handle_cli_command(request.command.clone());
+                request.answer(reply);
+                if quit {
+                    // Nobody may be at the screen to answer, so "Ask" undoes the profile
+                    return self.exit_behavior != ExitBehavior::LeaveActive;
+                }
+            }
+
+            self.remember_session();
+            if let (Some(ref mut tray), Some(ref monitor)) = (&mut self.tray, &self.hardware_monitor) {
+                tray.set_hardware(monitor.latest().as_ref());
+            }
+
+            // Window messages end the wait too, since the tray icon's arrive on this thread
+            let tray_busy = self.tray.as_ref().is_some_and(TrayFlyoutManager::is_busy);
+            let settling = self.data_watcher.as_ref().is_some_and(DataWatcher::is_settling);
+            ipc::wait_with_messages(if tray_busy || settling { ipc::BUSY_TICK } else { ipc::IDLE_TICK });
+        }
+    }
+
+    /// Answer a command from `gaming_optimizer <command>` or the remote API
+    fn handle_cli_command(&mut self, command: CliCommand) -> CliReply {
+        match command {
+            CliCommand::Activate { profile } => match self.activate(&profile) {
+                Ok(message) => CliReply::ok(message),
+                Err(e) => CliReply::error(e),
+            },
+            CliCommand::Deactivate => {
+                if self.active_profile.is_none() {
+                    return CliReply::ok("No profile is active");
+                }
+                self.deactivate();
+                CliReply::ok(t!("status-profile-deactivated"))
+            }
+            CliCommand::Show => CliReply::error("No window in tray-only or headless mode"),
+            CliCommand::List => {
+                let active = self.active_profile.as_deref();
+                CliReply::ok(cli::format_profile_list(&self.profiles, active))
+                    .with_data(cli::profile_entries(&self.profiles, active))
+            }
+            CliCommand::Status => {
+                let status = LiveStatus {
+                    active_profile: self.active_profile.clone(),
+                    overlay_visible: self.activation.system.overlay.is_some(),
+                    ..LiveStatus::default()
+                };
+                CliReply::ok(status.summary()).with_data(status)
+            }
+            CliCommand::Kill { profile, dry_run } => {
+                let active = self.active_profile.as_deref();
+                cli::kill_for_profile(self.activation.processes(), &self.profiles, profile.as_deref(), active, dry_run)
+            }
+            CliCommand::Overlay { state: OverlayState::Off } => {
+                self.activation.hide_crosshair();
+                self.update_tray();
+                CliReply::ok("Crosshair hidden")
+            }
+            CliCommand::Overlay { state: OverlayState::On } => {
+                let Some(profile) = self.active_profile().cloned() else {
+                    return CliReply::error("No profile is active");
+                };
+                if !profile.overlay_enabled {
+                    return CliReply::error(format!("Profile '{}' has no crosshair", profile.name));
+                }
+                match self.show_crosshair(&profile) {
+                    Ok(name) => CliReply::ok(format!("Crosshair shown: {}", name)),
+                    Err(e) => CliReply::error(e),
+                }
+            }
+            CliCommand::Quit => CliReply::ok("Gaming Optimizer closed"),
+        }
+    }
+
+    fn active_profile(&self) -> Option<&Profile> {
+        let name = self.active_profile.as_deref()?;
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// Apply a profile by name; the headline of its summary, or why it wasn't applied
+    fn activate(&mut self, name: &str) -> Result<String, String> {
+        let Some(profile) = self.profiles.iter().find(|p| p.name == name).cloned() else {
+            return Err(format!("No profile named '{}'", name));
+        };
+        self.set_paused(false);
+        // Nobody to ask here: the problems are logged and the profile applied as far as it goes
+        for problem in preflight::check(&profile, &self.games, &Environment::detect(&profile, &self.games)).problems {
+            warn!("[Headless] {}", problem.message());
+        }
+        let Activation { summary, kills, revert_errors } = self.activation.activate(&profile, &self.power_plans, &self.openrgb, &self.obs);
+        for error in revert_errors {
+            warn!("[Headless] {}", t!("status-previous-profile-error", error = error));
+        }
+
+        self.crosshair_slot = 0;
+        self.activation_failed = summary.failed_count() > 0;
+        self.log_event(event_log::Entry::activation(&summary));
+        self.publish_plugin_event(PluginEvent::Activated {
+            profile: summary.profile.clone(),
+            applied: summary.applied_count(),
+            failed: summary.failed_count(),
+            rolled_back: summary.rolled_back,
+        });
+        let result = if summary.rolled_back {
+            self.active_profile = None;
+            toast_notifier::notify(
+                &self.toast_config,
+                ToastCategory::ProfileActivated,
+                &t!("toast-not-activated-title", profile = summary.profile),
+                &t!("toast-not-activated-body", step = summary.failed_step().unwrap_or_default()),
+            );
+            Err(summary.headline())
+        } else {
+            self.active_profile = Some(name.to_string());
+            self.record_profile_use(name);
+            let running = self.activation.processes().list();
+            for injector in overlay_conflicts::detect(&profile, running.iter().map(|p| p.name.as_str())) {
+                warn!("[Headless] {}", t!("overlay-conflict", overlay = injector, hint = injector.hint()));
+            }
+            toast_notifier::notify(
+                &self.toast_config,
+                ToastCategory::ProfileActivated,
+                &t!("toast-activated-title", profile = summary.profile),
+                &summary.tray_notification(kills.as_ref()),
+            );
+            Ok(summary.headline())
+        };
+        if let Some(ref mut tray) = self.tray {
+            tray.set_activation_status(self.active_profile.as_ref().map(|_| summary.tray_status()));
+        }
+        self.update_tray();
+        result
+    }
+
+    fn deactivate(&mut self) {
+        let errors = self.activation.deactivate();
+        for error in &errors {
+            warn!("[Headless] {}", error);
+        }
+        if let Some(profile) = self.active_profile.take() {
+            self.log_event(event_log::Entry::deactivation(&profile, &errors));
+            self.publish_plugin_event(PluginEvent::Deactivated { profile });
+        }
+        self.activation_failed = false;
+        if let Some(ref mut tray) = self.tray {
+            tray.set_activation_status(None);
+        }
+        self.update_tray();
+    }
+
+    /// Write an activation entry to the Windows Event Log, if that's turned on
+    fn log_event(&self, entry: event_log::Entry) {
+        if self.event_log.enabled {
+            if let Err(e) = event_log::write(&entry) {
+                warn!("[EventLog] {}", e);
+            }
+        }
+    }
+
+    /// Hand an event to the enabled plugins
+    fn publish_plugin_event(&self, event: PluginEvent) {
+        if let Some(ref plugins) = self.plugins {
+            let hardware = self.hardware_monitor.as_ref().and_then(|monitor| monitor.latest());
+            plugins.publish(event, SystemContext { active_profile: self.active_profile.clone(), hardware });
+        }
+    }
+
+    fn show_crosshair(&mut self, profile: &Profile) -> Result<String, String> {
+        self.set_paused(false);
+        let name = self.activation.show_crosshair(profile, self.crosshair_slot)?;
+        self.update_tray();
+        Ok(name)
+    }
+
+    fn toggle_overlay(&mut self) {
+        if self.activation.system.overlay.is_some() {
+            self.activation.hide_crosshair();
+            self.update_tray();
+        } else if let Some(profile) = self.active_profile().filter(|p| p.overlay_enabled).cloned() {
+            if let Err(e) = self.show_crosshair(&profile) {
+                self.notify_error(&e);
+            }
+        }
+    }
+
+    fn cycle_crosshair(&mut self) {
+        let Some(profile) = self.active_profile().cloned() else {
+            return;
+        };
+        if !profile.overlay_enabled || profile.crosshair_slot_count() < 2 {
+            return;
+        }
+        self.crosshair_slot = (self.crosshair_slot + 1) % profile.crosshair_slot_count();
+        if let Err(e) = self.show_crosshair(&profile) {
+            self.notify_error(&e);
+        }
+    }
+
+    /// Switch the active profile to another offset preset (None = its own offsets), keep the
+    /// choice and move a crosshair that is showing the profile's own image
+    fn select_offset_preset(&mut self, preset: Option<String>) {
+        let active = self.active_profile.as_ref();
+        let Some(profile) = self.profiles.iter_mut().find(|profile| Some(&profile.name) == active) else {
+            return;
+        };
+        if !profile.select_offset_preset(preset.as_deref()) {
+            return;
+        }
+        let profile = profile.clone();
+        info!("[Headless] Crosshair offset of {}: {:?}", profile.name, profile.base_offset());
+        if let Err(e) = save_profiles(&self.profiles, &self.data_dir) {
+            warn!("[Headless] {}", t!("status-profiles-save-failed", error = e));
+        }
+        if let Some(ref mut tray) = self.tray {
+            tray.update_profiles(self.profiles.clone());
+        }
+        if self.activation.system.overlay.is_some() && self.crosshair_slot == 0 {
+            if let Err(e) = self.show_crosshair(&profile) {
+                self.notify_error(&e);
+            }
+        }
+    }
+
+    /// Write the active profile, overlay and pause state to config.json when they've changed
+    fn remember_session(&mut self) {
+        // Safe mode keeps the state of the last normal run for the next one
+        if config::is_safe_mode() {
+            return;
+        }
+        let session = SessionState {
+            active_profile: self.active_profile.clone(),
+            overlay_visible: self.activation.system.overlay.is_some(),
+            paused: self.paused,
+        };
+        if session == self.saved_session {
+            return;
+        }
+        match config::save_session_state(&session) {
+            Ok(()) => self.saved_session = session,
+            Err(e) => warn!("[Headless] Failed to save the session state: {}", e),
+        }
+    }
+
+    /// Bring back what was running when the app last closed
+    fn resume_session(&mut self, session: &SessionState) {
+        if let Some(ref name) = session.active_profile {
+            info!("[Headless] Resuming {}", name);
+            if let Err(e) = self.activate(name) {
+                warn!("[Headless] Can't resume: {}", e);
+                return;
+            }
+            if !session.overlay_visible {
+                self.activation.hide_crosshair();
+                self.update_tray();
+            }
+        }
+        if session.paused {
+            self.set_paused(true);
+        }
+    }
+
+    /// Hide the crosshair while paused; the profile's system changes stay
+    fn set_paused(&mut self, paused: bool) {
+        if self.paused == paused {
+            return;
+        }
+        self.paused = paused;
+        crosshair_overlay::set_overlay_visible(!paused);
+        if let Some(ref mut tray) = self.tray {
+            tray.set_paused(paused);
+        }
+        info!("[Headless] {}", if paused { t!("status-paused") } else { t!("status-resumed") });
+    }
+
+    fn notify_error(&self, message: &str) {
+        warn!("[Headless] {}", message);
+        toast_notifier::notify(&self.toast_config, ToastCategory::OverlayError, &t!("toast-crosshair-failed-title"), message);
+    }
+
+    /// Pick up the profile file after it was changed outside the app
+    fn reload_profiles(&mut self) {
+        match load_profiles(&self.data_dir) {
+            // Our own saves come back as change events too
+            Ok(profiles) if profiles == self.profiles => {}
+            Ok(profiles) => {
+                info!("[Headless] {}", t!("status-profiles-reloaded", count = profiles.len()));
+                self.profiles = profiles;
+                if let Some(ref mut tray) = self.tray {
+                    tray.update_profiles(self.profiles.clone());
+                }
+                self.update_tray();
+            }
+            Err(e) => warn!("[Headless] {}", t!("status-profiles-load-failed", error = e)),
+        }
+    }
+
+    /// Apply the settings from config.json this mode uses after it was changed outside the app
+    fn reload_config(&mut self) {
+        let app_config = match read_config() {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("[Headless] {}", t!("status-config-reload-failed", error = e));
+                return;
+            }
+        };
+        logging::set_level(app_config.log_level);
+        i18n::set_language(app_config.language.as_deref());
+        crate::process::set_user_protected(&app_config.safety.protected_processes);
+        self.set_hardware_monitor(&app_config);
+        self.openrgb = app_config.openrgb;
+        self.obs = app_config.obs;
+        self.toast_config = app_config.toasts;
+        self.event_log = app_config.event_log;
+        if app_config.gamepad != self.gamepad_config {
+            self.gamepad = spawn_gamepad(&app_config.gamepad);
+            self.gamepad_config = app_config.gamepad;
+        }
+        if app_config.plugins != self.plugin_config {
+            self.plugins = PluginHost::start(&self.data_dir, &app_config.plugins, &self.cli_tx);
+            self.plugin_config = app_config.plugins;
+        }
+        self.exit_behavior = app_config.exit_behavior;
+        self.profile_usage = app_config.profile_usage;
+        if app_config.auto_activate_games != self.game_watcher.is_some() {
+            self.game_watcher = app_config
+                .auto_activate_games
+                .then(GameWatcher::spawn)
+                .and_then(|started| started.map_err(|e| warn!("[Games] {}", e)).ok());
+        }
+        if let Some(ref mut tray) = self.tray {
+            tray.set_click_config(app_config.tray_clicks);
+            tray.set_tooltip_config(app_config.tray_tooltip.clone());
+        }
+        self.update_tray();
+    }
+
+    /// Sample the hardware only while the tray tooltip has readings to show
+    fn set_hardware_monitor(&mut self, app_config: &config::AppConfig) {
+        let wanted = self.tray.is_some() && app_config.tray_tooltip.uses_readings();
+        if !wanted {
+            self.hardware_monitor = None;
+        } else if let Some(ref monitor) = self.hardware_monitor {
+            monitor.set_config(&app_config.monitor);
+        } else {
+            self.hardware_monitor = Some(HardwareMonitor::spawn(&app_config.monitor));
+        }
+    }
+
+    /// Count an activation for the favorites and recent profiles in the tray
+    fn record_profile_use(&mut self, name: &str) {
+        let usage = self.profile_usage.entry(name.to_string()).or_default();
+        usage.activations += 1;
+        usage.last_activated = sessions::unix_time();
+
+        let mut app_config = load_config();
+        app_config.profile_usage = self.profile_usage.clone();
+        if let Err(e) = save_config(&app_config) {
+            warn!("[Headless] Failed to save profile usage: {}", e);
+        }
+    }
+
+    fn update_tray(&mut self) {
+        crash::set_active_profile(self.active_profile.as_deref());
+        let Some(ref mut tray) = self.tray else {
+            return;
+        };
+        tray.set_active_profile(self.active_profile.clone());
+        tray.set_quick_profiles(&profile::quick_profiles(&self.profiles, &self.profile_usage, tray_flyout::QUICK_PROFILES));
+        tray.set_badges(TrayState {
+            profile_active: self.active_profile.is_some(),
+            overlay_visible: self.activation.system.overlay.is_some(),
+            activation_failed: self.activation_failed,
+        });
+    }
+}
+
+/// Controller polling for `config`, or none when its combos are invalid
+fn spawn_gamepad(config: &GamepadConfig) -> GamepadThread {
+    gamepad::spawn_gamepad_thread(config).unwrap_or_else(|e| {
+        warn!("[Gamepad] {}", e);
+        GamepadThread::disabled()
+    })
+}
@@ -0,0 +1,206 @@
+//! Global hotkeys for in-game actions
+//!
+//! Hotkeys are registered on a dedicated thread with its own message queue so
+//! WM_HOTKEY isn't swallowed by the GUI's event loop. Bindings come from the settings
+//! (`hotkeys` in the core); dropping the `HotkeyThread` unregisters them, so new bindings take effect by respawning.
+//! While the crosshair is being adjusted from the tray, a second thread holds the arrow keys,
+//! Enter and Escape.
+
+use crate::hotkeys::{Hotkey, HotkeyConfig, MOD_SHIFT};
+use anyhow::{anyhow, Result};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread::JoinHandle;
+use tracing::{error, info};
+
+/// Actions that can be triggered by a global hotkey
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HotkeyAction {
+    /// Switch to the next crosshair preset of the active profile
+    CycleCrosshair,
+    /// Move the crosshair by (x, y) pixels while adjusting it
+    NudgeCrosshair(i32, i32),
+    /// Leave crosshair adjustment (Enter or Escape), keeping the new position
+    FinishAdjust,
+}
+
+/// Hotkey id for the cycle crosshair binding
+const HOTKEY_CYCLE_CROSSHAIR: i32 = 1;
+/// First hotkey id of the crosshair adjustment keys
+const HOTKEY_ADJUST_FIRST: i32 = 100;
+
+/// Pixels per arrow key press while adjusting (with Shift: `ADJUST_STEP_LARGE`)
+const ADJUST_STEP: i32 = 1;
+const ADJUST_STEP_LARGE: i32 = 10;
+
+// Virtual key codes of the adjustment keys
+const VK_RETURN: u32 = 0x0D;
+const VK_ESCAPE: u32 = 0x1B;
+const VK_LEFT: u32 = 0x25;
+const VK_UP: u32 = 0x26;
+const VK_RIGHT: u32 = 0x27;
+const VK_DOWN: u32 = 0x28;
+
+/// A hotkey to register and the action it sends
+#[derive(Debug, Clone, PartialEq)]
+struct Binding {
+    id: i32,
+    hotkey: Hotkey,
+    /// Send the action again while the key is held
+    repeat: bool,
+    action: HotkeyAction,
+    /// Shown in the log and in errors
+    label: String,
+}
+
+/// Arrow keys (Shift for bigger steps), Enter and Escape for crosshair adjustment
+fn adjust_bindings() -> Vec<Binding> {
+    let arrows = [("Left", VK_LEFT, (-1, 0)), ("Right", VK_RIGHT, (1, 0)), ("Up", VK_UP, (0, -1)), ("Down", VK_DOWN, (0, 1))];
+    let moves = [(0, "", ADJUST_STEP), (MOD_SHIFT, "Shift+", ADJUST_STEP_LARGE)]
+        .into_iter()
+        .flat_map(|(modifiers, prefix, step)| {
+            arrows.into_iter().map(move |(name, vk, (dx, dy))| {
+                (Hotkey { modifiers, vk }, true, HotkeyAction::NudgeCrosshair(dx * step, dy * step), format!("{}{}", prefix, name))
+            })
+        });
+    let finish = [("Enter", VK_RETURN), ("Escape", VK_ESCAPE)]
+        .into_iter()
+        .map(|(name, vk)| (Hotkey { modifiers: 0, vk }, false, HotkeyAction::FinishAdjust, name.to_string()));
+
+    moves
+        .chain(finish)
+        .enumerate()
+        .map(|(i, (hotkey, repeat, action, label))| Binding { id: HOTKEY_ADJUST_FIRST + i as i32, hotkey, repeat, action, label })
+        .collect()
+}
+
+/// Running hotkey thread; unregisters its hotkeys when dropped
+pub struct HotkeyThread {
+    pub actions: Receiver<HotkeyAction>,
+    thread_id: u32,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HotkeyThread {
+    /// No hotkeys (disabled in the settings or registration failed)
+    pub fn disabled() -> Self {
+        HotkeyThread { actions: channel().1, thread_id: 0, thread: None }
+    }
+}
+
+impl Drop for HotkeyThread {
+    fn drop(&mut self) {
+        if self.thread_id != 0 {
+            use windows::Win32::Foundation::{LPARAM, WPARAM};
+            use windows::Win32::UI::WindowsAndMessaging::{PostThreadMessageW, WM_QUIT};
+            unsafe {
+                let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+            }
+        }
+        // Wait for the unregistration so a respawn can take the same keys
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Start the hotkey thread; fails if a binding is invalid or taken by another app
+pub fn spawn_hotkey_thread(config: &HotkeyConfig) -> Result<HotkeyThread> {
+    if !config.enabled || crate::config::is_safe_mode() {
+        return Ok(HotkeyThread::disabled());
+    }
+    let hotkey = Hotkey::parse(&config.cycle_crosshair)?;
+    spawn_thread(vec![Binding {
+        id: HOTKEY_CYCLE_CROSSHAIR,
+        hotkey,
+        repeat: false,
+        action: HotkeyAction::CycleCrosshair,
+        label: config.cycle_crosshair.clone(),
+    }])
+}
+
+/// Take the arrow keys, Enter and Escape for crosshair adjustment until the thread is dropped
+pub fn spawn_adjust_hotkeys() -> Result<HotkeyThread> {
+    spawn_thread(adjust_bindings())
+}
+
+/// Register `bindings` on a new thread; fails (with none registered) if one is taken
+fn spawn_thread(bindings: Vec<Binding>) -> Result<HotkeyThread> {
+    let (tx, rx) = channel();
+    let (started_tx, started_rx) = channel();
+
+    let thread = std::thread::spawn(move || {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::System::Threading::GetCurrentThreadId;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_NOREPEAT,
+        };
+        use windows::Win32::UI::WindowsAndMessaging::{GetMessageW, MSG, WM_HOTKEY};
+
+        unsafe {
+            for (registered, binding) in bindings.iter().enumerate() {
+                let repeat = if binding.repeat { HOT_KEY_MODIFIERS(0) } else { MOD_NOREPEAT };
+                if let Err(e) = RegisterHotKey(
+                    HWND::default(),
+                    binding.id,
+                    HOT_KEY_MODIFIERS(binding.hotkey.modifiers) | repeat,
+                    binding.hotkey.vk,
+                ) {
+                    error!("[HOTKEY] Failed to register {}: {}", binding.label, e);
+                    for earlier in &bindings[..registered] {
+                        let _ = UnregisterHotKey(HWND::default(), earlier.id);
+                    }
+                    let _ = started_tx.send(Err(anyhow!("{} is already used by another app", binding.label)));
+                    return;
+                }
+                info!("[HOTKEY] Registered {} ({:?})", binding.label, binding.action);
+            }
+            let _ = started_tx.send(Ok(GetCurrentThreadId()));
+
+            // Ends with WM_QUIT from HotkeyThread::drop
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+                if msg.message != WM_HOTKEY {
+                    continue;
+                }
+                let Some(binding) = bindings.iter().find(|binding| binding.id == msg.wParam.0 as i32) else {
+                    continue;
+                };
+                if tx.send(binding.action).is_err() {
+                    // Receiver dropped, app is shutting down
+                    break;
+                }
+                crate::ipc::wake();
+            }
+
+            for binding in &bindings {
+                let _ = UnregisterHotKey(HWND::default(), binding.id);
+            }
+        }
+    });
+
+    let thread_id = started_rx
+        .recv()
+        .map_err(|_| anyhow!("Hotkey thread stopped unexpectedly"))??;
+    Ok(HotkeyThread { actions: rx, thread_id, thread: Some(thread) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjust_bindings() {
+        let bindings = adjust_bindings();
+        let action = |modifiers, vk| bindings.iter().find(|b| b.hotkey == Hotkey { modifiers, vk }).map(|b| b.action);
+
+        assert_eq!(bindings.len(), 10);
+        assert_eq!(action(0, VK_LEFT), Some(HotkeyAction::NudgeCrosshair(-ADJUST_STEP, 0)));
+        assert_eq!(action(MOD_SHIFT, VK_DOWN), Some(HotkeyAction::NudgeCrosshair(0, ADJUST_STEP_LARGE)));
+        assert_eq!(action(0, VK_ESCAPE), Some(HotkeyAction::FinishAdjust));
+        // Ids don't collide with the settings hotkeys and each is used once
+        let mut ids: Vec<i32> = bindings.iter().map(|b| b.id).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), 10);
+        assert!(!ids.contains(&HOTKEY_CYCLE_CROSSHAIR));
+    }
+}
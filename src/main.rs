@@ -1,76 +1,58 @@
 // #![windows_subsystem = "windows"]  // Temporarily disabled for debugging
 
-mod config;
-mod overlay;
-mod process;
-mod profile;
-mod tray;
-mod tray_flyout;
-mod gui;
-mod ipc;
-mod common_apps;
-mod image_picker;
 mod crosshair_overlay;
+mod dialogs;
 mod flyout;
+mod hotkey_thread;
+mod toast_notifier;
+mod tray_badge;
+mod tray_flyout;
+mod gui;
+mod headless;
+
+// The core modules, so `crate::config` and friends resolve the same here as in the library
+use gaming_optimizer_core::*;
 
 use anyhow::Result;
+use clap::Parser;
 
 fn main() -> Result<()> {
-    // Check command line arguments
-    let args: Vec<String> = std::env::args().collect();
-    
-    if args.len() > 1 && args[1] == "--tray-only" {
-        // Run in tray-only mode (no GUI)
-        run_tray_only()?;
-    } else {
-        // Run full GUI application with integrated tray
-        gui::run()?;
-    }
-    
-    Ok(())
-}
-
-/// Run in tray-only mode without GUI
-fn run_tray_only() -> Result<()> {
-    // Load configuration
+    let cli = cli::Cli::parse();
+    config::set_portable(cli.portable);
+    // CLI output goes to the console, so log lines only go to the file there
     let app_config = config::load_config();
+    logging::init(app_config.log_level, cli.command.is_none());
+    i18n::set_language(app_config.language.as_deref());
+    crash::install();
+
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(command));
+    }
     
-    // Load profiles  
-    let data_dir = config::get_data_directory()?;
-    let profiles = profile::load_profiles(&data_dir)?;
-    
-    // Create IPC channels using std::sync::mpsc
-    let (gui_to_tray_tx, gui_to_tray_rx) = std::sync::mpsc::channel();
-    let (tray_to_gui_tx, tray_to_gui_rx) = std::sync::mpsc::channel();
-    
-    let channels = ipc::TrayChannels {
-        to_gui: tray_to_gui_tx,
-        from_gui: gui_to_tray_rx,
-    };
-    
-    // Start tray thread with flyout
-    tray_flyout::run_tray_flyout_thread(
-        channels,
-        profiles,
-        app_config.active_profile,
-    );
+    // A second copy hands over to the running one instead of adding another tray icon
+    if !single_instance::acquire() {
+        let command = match cli.restore_profile {
+            Some(profile) => cli::CliCommand::Activate { profile },
+            // A logon start while the app is already up has nothing to do
+            None if cli.minimized => std::process::exit(0),
+            None => cli::CliCommand::Show,
+        };
+        std::process::exit(cli::forward(command));
+    }
+    // Detected once; admin-only features are badged and skipped from here on when not elevated
+    elevation::capabilities();
     
-    // Keep main thread alive
-    loop {
-        // Check for messages from tray
-        if let Ok(msg) = tray_to_gui_rx.recv_timeout(std::time::Duration::from_millis(100)) {
-            match msg {
-                ipc::TrayToGui::ActivateProfile(name) => {
-                    println!("Activating profile: {}", name);
-                    // TODO: Implement profile activation logic
-                }
-                ipc::TrayToGui::Exit => {
-                    println!("Exiting...");
-                    break;
-                }
-                _ => {}
-            }
-        }
+    if cli.safe_mode {
+        config::set_safe_mode(true);
+        tracing::warn!("[Main] Safe mode: hotkeys, file watching, remote API, keyboard hook, crosshair and automatic activation are off");
+    } else {
+        // Data written by an earlier version is brought up to date once, before anything loads it
+        migration::run();
+    }
+    match cli.mode() {
+        cli::AppMode::Gui => gui::run(cli.restore_profile, cli.minimized)?,
+        cli::AppMode::TrayOnly => headless::run(true, cli.restore_profile)?,
+        cli::AppMode::Headless => headless::run(false, cli.restore_profile)?,
     }
     
     Ok(())
@@ -0,0 +1,93 @@
+//! Toast notifications
+//!
+//! Shows key events (profile activated, processes closed, overlay errors, updates) as native
+//! Windows toasts, for the categories switched on in the settings (`toasts` in the core). The
+//! app registers its own AppUserModelID under HKCU so toasts work without an installer-created
+//! Start menu shortcut.
+
+use crate::toasts::{ToastCategory, ToastConfig};
+use anyhow::Result;
+use tracing::{info, warn};
+
+/// AppUserModelID toasts are shown under
+const APP_ID: &str = "GamingOptimizer.App";
+const APP_ID_KEY: &str = "Software\\Classes\\AppUserModelId\\GamingOptimizer.App";
+
+/// Escape text for the toast XML template
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn toast_xml(title: &str, body: &str) -> String {
+    format!(
+        "<toast><visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual></toast>",
+        escape_xml(title),
+        escape_xml(body)
+    )
+}
+
+/// Show a toast if its category is enabled. Runs on a background thread so a slow
+/// notification platform never stalls the caller.
+pub fn notify(config: &ToastConfig, category: ToastCategory, title: &str, body: &str) {
+    if !config.is_enabled(category) {
+        return;
+    }
+    info!("[Toasts] {}: {}", title, body);
+
+    let (title, body) = (title.to_string(), body.to_string());
+    std::thread::spawn(move || {
+        if let Err(e) = show_toast(&title, &body) {
+            warn!("[Toasts] Failed to show notification: {}", e);
+        }
+    });
+}
+
+#[cfg(windows)]
+fn show_toast(title: &str, body: &str) -> Result<()> {
+    use anyhow::anyhow;
+    use once_cell::sync::OnceCell;
+    use windows::core::HSTRING;
+    use windows::Data::Xml::Dom::XmlDocument;
+    use windows::UI::Notifications::{ToastNotification, ToastNotificationManager};
+
+    // Toasts from unpackaged apps need a registered AppUserModelID (only written once per run)
+    static REGISTERED: OnceCell<()> = OnceCell::new();
+    REGISTERED.get_or_try_init(|| {
+        crate::registry::write_string(crate::registry::Hive::CurrentUser, APP_ID_KEY, "DisplayName", "Gaming Optimizer")
+    })?;
+
+    let document = XmlDocument::new().map_err(|e| anyhow!("Failed to create toast XML: {}", e))?;
+    document
+        .LoadXml(&HSTRING::from(toast_xml(title, body)))
+        .map_err(|e| anyhow!("Failed to load toast XML: {}", e))?;
+    let toast = ToastNotification::CreateToastNotification(&document)
+        .map_err(|e| anyhow!("Failed to create toast: {}", e))?;
+
+    ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(APP_ID))
+        .and_then(|notifier| notifier.Show(&toast))
+        .map_err(|e| anyhow!("Failed to show toast: {}", e))
+}
+
+#[cfg(not(windows))]
+fn show_toast(_title: &str, _body: &str) -> Result<()> {
+    let _ = (APP_ID, APP_ID_KEY);
+    Err(anyhow::anyhow!("Toast notifications are only available on Windows"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toast_xml_escapes_text() {
+        assert_eq!(
+            toast_xml("FPS <Max>", "Tom & Jerry's \"game\""),
+            "<toast><visual><binding template=\"ToastGeneric\"><text>FPS &lt;Max&gt;</text>\
+             <text>Tom &amp; Jerry&apos;s &quot;game&quot;</text></binding></visual></toast>"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Tray icon with state badges
+//!
+//! The icon is favicon.ico with small marks drawn over it: a green dot while a profile is
+//! active, a crosshair while the overlay is shown and a red "!" when the last activation had
+//! failures. Used by both tray implementations (`tray` and `tray_flyout`).
+
+use anyhow::{anyhow, Result};
+use image::{imageops::FilterType, Rgba, RgbaImage};
+use std::path::PathBuf;
+use tray_icon::Icon;
+
+/// Icon size in pixels; Windows scales it down for 100% DPI trays
+pub const ICON_SIZE: u32 = 32;
+
+const ACTIVE_COLOR: Rgba<u8> = Rgba([0x2E, 0xCC, 0x40, 0xFF]);
+const CROSSHAIR_COLOR: Rgba<u8> = Rgba([0x3D, 0xA5, 0xFF, 0xFF]);
+const WARNING_COLOR: Rgba<u8> = Rgba([0xE0, 0x30, 0x30, 0xFF]);
+const OUTLINE_COLOR: Rgba<u8> = Rgba([0x10, 0x10, 0x10, 0xFF]);
+const WHITE: Rgba<u8> = Rgba([0xFF, 0xFF, 0xFF, 0xFF]);
+
+/// What the badges show
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrayState {
+    pub profile_active: bool,
+    pub overlay_visible: bool,
+    pub activation_failed: bool,
+}
+
+/// favicon.ico next to the exe (or in the working directory) at `ICON_SIZE`, or a plain green
+/// square when there is none
+pub fn load_base_icon() -> Result<RgbaImage> {
+    let paths_to_try = [
+        std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.join("favicon.ico"))),
+        Some(PathBuf::from("favicon.ico")),
+        Some(PathBuf::from("X:\\AI_and_Automation\\Gaming_optimizer\\favicon.ico")),
+    ];
+
+    for path in paths_to_try.into_iter().flatten() {
+        if path.exists() {
+            let icon_data = std::fs::read(&path).map_err(|e| anyhow!("Failed to read favicon.ico: {}", e))?;
+            let img = image::load_from_memory(&icon_data).map_err(|e| anyhow!("Failed to decode icon: {}", e))?;
+            return Ok(img.resize_exact(ICON_SIZE, ICON_SIZE, FilterType::Lanczos3).to_rgba8());
+        }
+    }
+
+    Ok(RgbaImage::from_pixel(ICON_SIZE, ICON_SIZE, Rgba([0x00, 0xAA, 0x00, 0xFF])))
+}
+
+/// `base` with the badges for `state`
+pub fn compose(base: &RgbaImage, state: TrayState) -> RgbaImage {
+    let mut img = base.clone();
+    let size = img.width().min(img.height()) as i32;
+    let radius = (size / 5).max(2);
+
+    if state.overlay_visible {
+        // Top left: crosshair with a gap in the middle
+        let center = radius + 1;
+        for offset in 2..=radius + 1 {
+            for (x, y) in [(center - offset, center), (center + offset, center), (center, center - offset), (center, center + offset)] {
+                put(&mut img, x, y, CROSSHAIR_COLOR);
+            }
+        }
+        put(&mut img, center, center, CROSSHAIR_COLOR);
+    }
+    if state.profile_active {
+        // Bottom right: green dot
+        let center = size - radius - 1;
+        fill_circle(&mut img, center, center, radius + 1, OUTLINE_COLOR);
+        fill_circle(&mut img, center, center, radius, ACTIVE_COLOR);
+    }
+    if state.activation_failed {
+        // Top right: red dot with an exclamation mark
+        let (cx, cy) = (size - radius - 1, radius + 1);
+        fill_circle(&mut img, cx, cy, radius + 1, OUTLINE_COLOR);
+        fill_circle(&mut img, cx, cy, radius, WARNING_COLOR);
+        for y in cy - radius / 2 - 1..cy + radius / 4 {
+            put(&mut img, cx, y, WHITE);
+        }
+        put(&mut img, cx, cy + radius / 2, WHITE);
+    }
+    img
+}
+
+/// Tray icon for `state` built on `base`
+pub fn render_icon(base: &RgbaImage, state: TrayState) -> Result<Icon> {
+    let img = compose(base, state);
+    let (width, height) = img.dimensions();
+    Icon::from_rgba(img.into_raw(), width, height).map_err(|e| anyhow!("Failed to create tray icon: {:?}", e))
+}
+
+fn put(img: &mut RgbaImage, x: i32, y: i32, color: Rgba<u8>) {
+    if x >= 0 && y >= 0 && (x as u32) < img.width() && (y as u32) < img.height() {
+        img.put_pixel(x as u32, y as u32, color);
+    }
+}
+
+fn fill_circle(img: &mut RgbaImage, cx: i32, cy: i32, radius: i32, color: Rgba<u8>) {
+    for y in cy - radius..=cy + radius {
+        for x in cx - radius..=cx + radius {
+            let (dx, dy) = (x - cx, y - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                put(img, x, y, color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose() {
+        let base = RgbaImage::from_pixel(ICON_SIZE, ICON_SIZE, Rgba([0, 0, 0, 0]));
+        let corner = |img: &RgbaImage, x: u32, y: u32| *img.get_pixel(x, y);
+        let radius = ICON_SIZE / 5;
+
+        assert_eq!(compose(&base, TrayState::default()), base);
+
+        let active = compose(&base, TrayState { profile_active: true, ..TrayState::default() });
+        assert_eq!(corner(&active, ICON_SIZE - radius - 1, ICON_SIZE - radius - 1), ACTIVE_COLOR);
+        assert_eq!(corner(&active, radius + 1, radius + 1), Rgba([0, 0, 0, 0]));
+
+        let all = compose(&base, TrayState { profile_active: true, overlay_visible: true, activation_failed: true });
+        assert_eq!(corner(&all, radius + 1, radius + 1), CROSSHAIR_COLOR);
+        assert_eq!(corner(&all, ICON_SIZE - 2, radius + 1), WARNING_COLOR);
+    }
+}
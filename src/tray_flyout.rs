@@ -1,116 +1,136 @@
 /// System tray with flyout menu integration
 /// 
 /// This module provides a simplified tray icon that spawns a custom flyout window
-/// instead of using native OS context menus.
+/// instead of using native OS context menus. Every mode with a tray (window, --tray-only)
+/// calls `poll` on its own thread and runs the `TrayToGui` actions it returns.
 
 use crate::flyout::FlyoutWindow;
-use crate::ipc::{TrayChannels, GuiToTray};
+use crate::i18n::t;
+use crate::ipc::{self, TrayToGui};
+use crate::monitor::HardwareSnapshot;
 use crate::profile::Profile;
+use crate::tray_badge::{self, TrayState};
+use crate::tray_clicks::{ClickTracker, TrayClick, TrayClickAction, TrayClickConfig};
+use crate::tray_tooltip::{self, TooltipValues, TrayTooltipConfig};
+use image::RgbaImage;
 use anyhow::{anyhow, Result};
-use std::sync::mpsc::{Sender, TryRecvError, Receiver, channel};
+use std::sync::mpsc::{Sender, Receiver, channel};
 use std::time::Instant;
-use tray_icon::{TrayIcon, TrayIconBuilder, TrayIconEvent, MouseButton, MouseButtonState, Icon, menu::MenuEvent};
-use tray_icon::menu::{Menu, MenuItem, MenuId, PredefinedMenuItem};
-
-/// Load application icon from favicon.ico file
-fn load_app_icon() -> Result<Icon> {
-    // Try multiple paths
-    let paths_to_try = vec![
-        std::env::current_exe().ok().and_then(|p| p.parent().map(|p| p.join("favicon.ico"))),
-        Some(std::path::PathBuf::from("favicon.ico")),
-        Some(std::path::PathBuf::from("X:\\AI_and_Automation\\Gaming_optimizer\\favicon.ico")),
-    ];
-    
-    for path_opt in paths_to_try {
-        if let Some(path) = path_opt {
-            if path.exists() {
-                let icon_data = std::fs::read(&path)
-                    .map_err(|e| anyhow!("Failed to read favicon.ico: {}", e))?;
-                
-                // Decode with image crate
-                let img = image::load_from_memory(&icon_data)
-                    .map_err(|e| anyhow!("Failed to decode icon: {}", e))?;
-                
-                let img = img.resize_exact(16, 16, image::imageops::FilterType::Lanczos3);
-                let rgba = img.to_rgba8();
-                
-                return Icon::from_rgba(rgba.into_raw(), 16, 16)
-                    .map_err(|e| anyhow!("Failed to create icon from image: {:?}", e));
-            }
-        }
-    }
-    
-    // Fallback: green square
-    let icon_rgba: Vec<u8> = (0..16*16).flat_map(|_| vec![0x00, 0xAA, 0x00, 0xFF]).collect();
-    Icon::from_rgba(icon_rgba, 16, 16)
-        .map_err(|e| anyhow!("Failed to create fallback icon: {:?}", e))
-}
+use tray_icon::{TrayIcon, TrayIconBuilder, TrayIconEvent, MouseButton, MouseButtonState, menu::MenuEvent};
+use tray_icon::menu::{CheckMenuItem, Menu, MenuItem, MenuId, PredefinedMenuItem, Submenu};
+use tracing::{debug, error, info, warn};
 
-/// Create a TrayToGui sender that forwards profile activations to a String channel
-fn create_profile_forwarder(profile_tx: Sender<String>) -> Sender<crate::ipc::TrayToGui> {
-    let (tx, rx) = channel::<crate::ipc::TrayToGui>();
-    
-    // Spawn a small thread to forward messages
-    std::thread::spawn(move || {
-        while let Ok(msg) = rx.recv() {
-            if let crate::ipc::TrayToGui::ActivateProfile(name) = msg {
-                let _ = profile_tx.send(name);
-            }
-        }
-    });
-    
-    tx
-}
+/// Favorite and recent profiles shown at the top of the tray menu
+pub const QUICK_PROFILES: usize = 3;
 
 /// Simplified tray manager that works with flyout
 pub struct TrayFlyoutManager {
     tray_icon: TrayIcon,
-    flyout: Option<FlyoutWindow>,
+    flyout: Option<Box<FlyoutWindow>>,
     profiles: Vec<Profile>,
     active_profile: Option<String>,
-    pub menu_item_settings: MenuId,
-    pub menu_item_docs: MenuId,
-    pub menu_item_bug_report: MenuId,
-    pub menu_item_exit: MenuId,
-    /// Channel to send profile activations to GUI
-    profile_tx: Sender<String>,
-    /// For --tray-only mode: track click timing
-    last_click_time: Option<Instant>,
-    pending_single_click: bool,
+    /// Result of the last activation shown in the tooltip (e.g. "12 applied, 1 failed")
+    activation_status: Option<String>,
+    /// Optimizer paused (shown in the tooltip)
+    paused: bool,
+    /// Tooltip template, the readings it was last filled with and the text currently shown
+    tooltip_config: TrayTooltipConfig,
+    hardware: Option<HardwareSnapshot>,
+    tooltip: String,
+    /// Icon without badges, and the badges currently drawn on it
+    base_icon: RgbaImage,
+    badges: TrayState,
+    menu_item_settings: MenuId,
+    menu_item_cycle_crosshair: MenuId,
+    menu_item_adjust_crosshair: MenuId,
+    menu_item_pause: MenuId,
+    menu_item_docs: MenuId,
+    menu_item_bug_report: MenuId,
+    menu_item_open_logs: MenuId,
+    menu_item_autostart: MenuId,
+    menu_item_exit: MenuId,
+    /// "Start with Windows" check mark (kept so the GUI switch can update it)
+    autostart_item: CheckMenuItem,
+    /// "Pause Optimizer" check mark
+    pause_item: CheckMenuItem,
+    /// Context menu, kept to swap the quick profile items at its top
+    menu: Menu,
+    /// Favorite and recent profiles at the top of the context menu, with their profile names
+    quick_items: Vec<(MenuItem, String)>,
+    /// Separator under the quick profile items (only in the menu while there are some)
+    quick_separator: PredefinedMenuItem,
+    /// "Crosshair Offset" submenu with the active profile's offset presets (only in the menu
+    /// while it has some), its items with the preset names (None = the profile's own offsets)
+    offset_menu: Submenu,
+    offset_items: Vec<(CheckMenuItem, Option<String>)>,
+    offset_menu_shown: bool,
+    /// Channel for flyout actions (activate/stop, crosshair toggle, open GUI, exit)
+    flyout_tx: Sender<TrayToGui>,
+    flyout_rx: Receiver<TrayToGui>,
+    /// Icon clicks and menu picks, forwarded by the tray-icon event handlers
+    event_rx: Receiver<TrayIconEvent>,
+    menu_rx: Receiver<MenuEvent>,
+    /// Single/double/middle click bindings and timing
+    clicks: ClickTracker,
+}
+
+/// The profiles the tray and the flyout offer
+fn unarchived(profiles: Vec<Profile>) -> Vec<Profile> {
+    profiles.into_iter().filter(|profile| !profile.archived).collect()
 }
 
 impl TrayFlyoutManager {
-    /// Create a new tray manager with event channels for main-thread integration
-    /// Returns the manager plus receivers for tray events, menu events, and profile activations
-    pub fn new_with_channels(
-        profiles: Vec<Profile>, 
-        active_profile: Option<String>
-    ) -> Result<(Self, Receiver<TrayIconEvent>, Receiver<MenuEvent>, Receiver<String>)> {
+    /// Create the tray icon and its context menu. Must be called on the thread that will
+    /// `poll` it (the icon's window lives there).
+    pub fn new(profiles: Vec<Profile>, active_profile: Option<String>) -> Result<Self> {
         let tooltip = if let Some(ref name) = active_profile {
-            format!("Gaming Optimizer - {}", name)
+            t!("tray-tooltip-active", profile = name)
         } else {
-            "Gaming Optimizer - Inactive".to_string()
+            t!("tray-tooltip-inactive")
         };
 
-        println!("[TRAY] Creating tray icon with {} profiles", profiles.len());
+        info!("[TRAY] Creating tray icon with {} profiles", profiles.len());
         
-        let icon = load_app_icon()?;
-        println!("[TRAY] Icon loaded");
+        let base_icon = tray_badge::load_base_icon()?;
+        let badges = TrayState { profile_active: active_profile.is_some(), ..TrayState::default() };
+        let icon = tray_badge::render_icon(&base_icon, badges)?;
+        info!("[TRAY] Icon loaded");
         
         // Create context menu (appears on right-click)
         let menu = Menu::new();
-        let settings_item = MenuItem::new("Open Settings", true, None);
-        let docs_item = MenuItem::new("Documentation", true, None);
-        let bug_item = MenuItem::new("Report Bug", true, None);
+        let settings_item = MenuItem::new(t!("tray-open-settings"), true, None);
+        let hotkeys = crate::config::load_config().hotkeys;
+        let cycle_label = if hotkeys.enabled {
+            t!("tray-next-crosshair-hotkey", hotkey = hotkeys.cycle_crosshair)
+        } else {
+            t!("tray-next-crosshair")
+        };
+        let cycle_item = MenuItem::new(cycle_label, true, None);
+        let adjust_item = MenuItem::new(t!("tray-adjust-crosshair"), true, None);
+        let pause_item = CheckMenuItem::new(t!("tray-pause"), true, false, None);
+        let docs_item = MenuItem::new(t!("tray-documentation"), true, None);
+        let bug_item = MenuItem::new(t!("tray-report-bug"), true, None);
+        let logs_item = MenuItem::new(t!("tray-open-logs"), true, None);
+        let autostart_item = CheckMenuItem::new(t!("tray-start-with-windows"), true, crate::config::load_config().autostart.enabled, None);
         let separator = PredefinedMenuItem::separator();
-        let exit_item = MenuItem::new("Exit", true, None);
+        let exit_item = MenuItem::new(t!("tray-exit"), true, None);
         
         menu.append(&settings_item)
             .map_err(|e| anyhow!("Failed to add settings item: {}", e))?;
+        menu.append(&cycle_item)
+            .map_err(|e| anyhow!("Failed to add crosshair item: {}", e))?;
+        menu.append(&adjust_item)
+            .map_err(|e| anyhow!("Failed to add adjust crosshair item: {}", e))?;
+        menu.append(&pause_item)
+            .map_err(|e| anyhow!("Failed to add pause item: {}", e))?;
         menu.append(&docs_item)
             .map_err(|e| anyhow!("Failed to add docs item: {}", e))?;
         menu.append(&bug_item)
             .map_err(|e| anyhow!("Failed to add bug report item: {}", e))?;
+        menu.append(&logs_item)
+            .map_err(|e| anyhow!("Failed to add logs item: {}", e))?;
+        menu.append(&autostart_item)
+            .map_err(|e| anyhow!("Failed to add autostart item: {}", e))?;
         menu.append(&separator)
             .map_err(|e| anyhow!("Failed to add separator: {}", e))?;
         menu.append(&exit_item)
@@ -118,78 +138,280 @@ impl TrayFlyoutManager {
         
         // Store menu IDs for event handling
         let menu_item_settings = settings_item.id().clone();
+        let menu_item_cycle_crosshair = cycle_item.id().clone();
+        let menu_item_adjust_crosshair = adjust_item.id().clone();
+        let menu_item_pause = pause_item.id().clone();
         let menu_item_docs = docs_item.id().clone();
         let menu_item_bug_report = bug_item.id().clone();
+        let menu_item_open_logs = logs_item.id().clone();
+        let menu_item_autostart = autostart_item.id().clone();
         let menu_item_exit = exit_item.id().clone();
         
         let tray_icon = TrayIconBuilder::new()
             .with_tooltip(&tooltip)
             .with_icon(icon)
-            .with_menu(Box::new(menu))
+            .with_menu(Box::new(menu.clone()))
             .build()
             .map_err(|e| anyhow!("Failed to create tray icon: {}", e))?;
         
-        println!("[TRAY] Tray icon created successfully with context menu");
+        info!("[TRAY] Tray icon created successfully with context menu");
 
         // Create channels for events
         let (event_tx, event_rx) = channel::<TrayIconEvent>();
         let (menu_tx, menu_rx) = channel::<MenuEvent>();
-        let (profile_tx, profile_rx) = channel::<String>();
+        let (flyout_tx, flyout_rx) = channel::<TrayToGui>();
         
         // Set up event handlers to forward events to channels
         // Use a delay flag to prevent events during initialization
         let startup_time = std::time::Instant::now();
         TrayIconEvent::set_event_handler(Some(move |event| {
             let elapsed = startup_time.elapsed().as_millis();
-            println!("[TRAY-HANDLER] Event received after {}ms: {:?}", elapsed, event);
+            debug!("[TRAY-HANDLER] Event received after {}ms: {:?}", elapsed, event);
             // Ignore events in first 500ms to let iced start up
             if elapsed > 500 {
                 let _ = event_tx.send(event);
+                ipc::wake();
             }
         }));
         
         let menu_startup = std::time::Instant::now();
         MenuEvent::set_event_handler(Some(move |event| {
             let elapsed = menu_startup.elapsed().as_millis();
-            println!("[MENU-HANDLER] Event received after {}ms: {:?}", elapsed, event);
+            debug!("[MENU-HANDLER] Event received after {}ms: {:?}", elapsed, event);
             if elapsed > 500 {
                 let _ = menu_tx.send(event);
+                ipc::wake();
             }
         }));
 
-        let manager = TrayFlyoutManager {
+        let app_config = crate::config::load_config();
+        let mut manager = TrayFlyoutManager {
             tray_icon,
             flyout: None,
-            profiles,
+            profiles: unarchived(profiles),
             active_profile,
+            activation_status: None,
+            paused: false,
+            tooltip_config: TrayTooltipConfig::default(),
+            hardware: None,
+            tooltip,
+            base_icon,
+            badges,
             menu_item_settings,
+            menu_item_cycle_crosshair,
+            menu_item_adjust_crosshair,
+            menu_item_pause,
             menu_item_docs,
             menu_item_bug_report,
+            menu_item_open_logs,
+            menu_item_autostart,
             menu_item_exit,
-            profile_tx,
-            last_click_time: None,
-            pending_single_click: false,
+            autostart_item,
+            pause_item,
+            menu,
+            quick_items: Vec::new(),
+            quick_separator: PredefinedMenuItem::separator(),
+            offset_menu: Submenu::new(t!("tray-crosshair-offset"), true),
+            offset_items: Vec::new(),
+            offset_menu_shown: false,
+            flyout_tx,
+            flyout_rx,
+            event_rx,
+            menu_rx,
+            clicks: ClickTracker::new(app_config.tray_clicks),
         };
+        manager.set_tooltip_config(app_config.tray_tooltip);
+        manager.refresh_offset_menu();
 
-        Ok((manager, event_rx, menu_rx, profile_rx))
+        Ok(manager)
     }
 
-    /// Create a new tray icon (legacy, for thread-based usage)
-    pub fn new(profiles: Vec<Profile>, active_profile: Option<String>) -> Result<Self> {
-        let (manager, _, _, _) = Self::new_with_channels(profiles, active_profile)?;
-        Ok(manager)
+    /// Next action for the app from a tray click, the context menu or the flyout. Opening the
+    /// flyout and the links in the menu are handled here.
+    pub fn poll(&mut self) -> Option<TrayToGui> {
+        let action = self.next_action();
+        if action.is_some() {
+            // More events may be queued behind this one
+            ipc::wake();
+        }
+        action
+    }
+    
+    /// Whether the app should keep polling quickly: a single click is waiting out the
+    /// double-click time, or the flyout is open
+    pub fn is_busy(&self) -> bool {
+        self.clicks.is_pending() || self.is_flyout_visible()
+    }
+    
+    fn next_action(&mut self) -> Option<TrayToGui> {
+        if let Ok(TrayIconEvent::Click { button, button_state: MouseButtonState::Up, .. }) = self.event_rx.try_recv() {
+            let click = match button {
+                MouseButton::Left => Some(TrayClick::Left),
+                MouseButton::Middle => Some(TrayClick::Middle),
+                _ => None,
+            };
+            if let Some(action) = click.and_then(|click| self.clicks.click(click, Instant::now())) {
+                return self.run_click_action(action);
+            }
+        }
+        
+        // A single click runs once the double-click time has passed without a second one
+        if let Some(action) = self.clicks.poll(Instant::now()) {
+            return self.run_click_action(action);
+        }
+        
+        if let Ok(message) = self.flyout_rx.try_recv() {
+            info!("[TRAY] Flyout action: {:?}", message);
+            return Some(message);
+        }
+        
+        let event = self.menu_rx.try_recv().ok()?;
+        debug!("[MENU] Processing menu event: {:?}", event);
+        if let Some((_, name)) = self.quick_items.iter().find(|(item, _)| *item.id() == event.id) {
+            return Some(TrayToGui::ActivateProfile(name.clone()));
+        }
+        if let Some((_, preset)) = self.offset_items.iter().find(|(item, _)| *item.id() == event.id) {
+            return Some(TrayToGui::SelectOffsetPreset(preset.clone()));
+        }
+        if event.id == self.menu_item_settings {
+            Some(TrayToGui::OpenSettings)
+        } else if event.id == self.menu_item_cycle_crosshair {
+            Some(TrayToGui::CycleCrosshair)
+        } else if event.id == self.menu_item_adjust_crosshair {
+            Some(TrayToGui::AdjustCrosshair)
+        } else if event.id == self.menu_item_pause {
+            Some(TrayToGui::TogglePause)
+        } else if event.id == self.menu_item_autostart {
+            // The menu has already flipped its check mark
+            Some(TrayToGui::SetAutostart(self.autostart_checked()))
+        } else if event.id == self.menu_item_docs {
+            if let Err(e) = open::that("https://github.com/yourusername/gaming_optimizer#readme") {
+                error!("[MENU] Failed to open documentation: {}", e);
+            }
+            None
+        } else if event.id == self.menu_item_bug_report {
+            if let Err(e) = open::that("https://github.com/yourusername/gaming_optimizer/issues/new") {
+                error!("[MENU] Failed to open bug report page: {}", e);
+            }
+            None
+        } else if event.id == self.menu_item_open_logs {
+            crate::logging::open_log_dir();
+            None
+        } else if event.id == self.menu_item_exit {
+            Some(TrayToGui::Exit)
+        } else {
+            None
+        }
+    }
+
+    /// Use a new tooltip template (changed in the settings)
+    pub fn set_tooltip_config(&mut self, config: TrayTooltipConfig) {
+        self.tooltip_config = config;
+        self.update_tooltip();
+    }
+
+    /// Refill the tooltip with the hardware monitor's latest readings, if the template shows any
+    pub fn set_hardware(&mut self, hardware: Option<&HardwareSnapshot>) {
+        if !self.tooltip_config.uses_readings() {
+            return;
+        }
+        self.hardware = hardware.cloned();
+        self.update_tooltip();
+    }
+
+    /// Use new click bindings (changed in the settings)
+    pub fn set_click_config(&mut self, config: TrayClickConfig) {
+        self.clicks.config = config;
+    }
+
+    /// Whether "Start with Windows" is ticked (the menu toggles it on click)
+    pub fn autostart_checked(&self) -> bool {
+        self.autostart_item.is_checked()
+    }
+
+    pub fn set_autostart_checked(&self, enabled: bool) {
+        self.autostart_item.set_checked(enabled);
+    }
+
+    /// Tick "Pause Optimizer" and mention the pause in the tooltip
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.pause_item.set_checked(paused);
+        self.update_tooltip();
+    }
+
+    /// Put one item per profile name at the top of the context menu, replacing the previous ones
+    pub fn set_quick_profiles(&mut self, names: &[String]) {
+        for (item, _) in self.quick_items.drain(..) {
+            let _ = self.menu.remove(&item);
+        }
+        let _ = self.menu.remove(&self.quick_separator);
+        
+        for (position, name) in names.iter().enumerate() {
+            let label = if self.active_profile.as_ref() == Some(name) { format!("✓ {}", name) } else { name.clone() };
+            let item = MenuItem::new(label, true, None);
+            match self.menu.insert(&item, position) {
+                Ok(()) => self.quick_items.push((item, name.clone())),
+                Err(e) => warn!("[TRAY] Failed to add quick profile item: {}", e),
+            }
+        }
+        if !self.quick_items.is_empty() {
+            let _ = self.menu.insert(&self.quick_separator, self.quick_items.len());
+        }
     }
 
-    /// Show the flyout menu (main-thread version, uses internal profile_tx)
+    /// List the active profile's offset presets under "Adjust Crosshair Position", ticking the
+    /// one in use; the submenu is left out when there are none
+    fn refresh_offset_menu(&mut self) {
+        for (item, _) in self.offset_items.drain(..) {
+            let _ = self.offset_menu.remove(&item);
+        }
+        if self.offset_menu_shown {
+            let _ = self.menu.remove(&self.offset_menu);
+            self.offset_menu_shown = false;
+        }
+
+        let active = self.active_profile.as_ref();
+        let Some(profile) = self.profiles.iter().find(|profile| Some(&profile.name) == active) else {
+            return;
+        };
+        if profile.offset_presets.is_empty() {
+            return;
+        }
+        let selected = profile.selected_offsets().map(|preset| preset.name.clone());
+        let (x, y) = (profile.crosshair_x_offset, profile.crosshair_y_offset);
+        let choices = std::iter::once((t!("tray-crosshair-offset-default", x = x, y = y), None)).chain(
+            profile
+                .offset_presets
+                .iter()
+                .map(|preset| (format!("{} ({}, {})", preset.name, preset.x_offset, preset.y_offset), Some(preset.name.clone()))),
+        );
+        for (label, name) in choices {
+            let item = CheckMenuItem::new(label, true, name == selected, None);
+            match self.offset_menu.append(&item) {
+                Ok(()) => self.offset_items.push((item, name)),
+                Err(e) => warn!("[TRAY] Failed to add offset preset item: {}", e),
+            }
+        }
+
+        // Settings, next crosshair and adjust crosshair come after the quick profiles
+        let position = self.quick_items.len() + usize::from(!self.quick_items.is_empty()) + 3;
+        match self.menu.insert(&self.offset_menu, position) {
+            Ok(()) => self.offset_menu_shown = true,
+            Err(e) => warn!("[TRAY] Failed to add offset preset menu: {}", e),
+        }
+    }
+
+    /// Show the flyout menu (main-thread version, actions go out on the flyout channel)
     pub fn show_flyout(&mut self) -> Result<()> {
-        println!("[FLYOUT] Attempting to show flyout menu");
+        info!("[FLYOUT] Attempting to show flyout menu");
         
         // Close existing flyout if any
         self.flyout = None;
 
         // Get tray icon rect for positioning
-        let _tray_rect = if let Some(rect) = self.tray_icon.rect() {
-            println!("[FLYOUT] Tray icon position: {:?}, size: {:?}", rect.position, rect.size);
+        let tray_rect = if let Some(rect) = self.tray_icon.rect() {
+            info!("[FLYOUT] Tray icon position: {:?}, size: {:?}", rect.position, rect.size);
             windows::Win32::Foundation::RECT {
                 left: rect.position.x as i32,
                 top: rect.position.y as i32,
@@ -197,44 +419,32 @@ impl TrayFlyoutManager {
                 bottom: (rect.position.y as i32 + rect.size.height as i32),
             }
         } else {
-            println!("[FLYOUT] Warning: Could not get tray rect, using screen corner");
-            use windows::Win32::UI::WindowsAndMessaging::*;
-            unsafe {
-                let screen_width = GetSystemMetrics(SM_CXSCREEN);
-                let screen_height = GetSystemMetrics(SM_CYSCREEN);
-                windows::Win32::Foundation::RECT {
-                    left: screen_width - 100,
-                    top: screen_height - 50,
-                    right: screen_width,
-                    bottom: screen_height,
-                }
-            }
+            warn!("[FLYOUT] Warning: Could not get tray rect, using screen corner");
+            crate::flyout::get_tray_rect()
         };
 
-        // Create IPC sender that forwards to profile_tx
-        let profile_tx = self.profile_tx.clone();
-        let ipc_sender = create_profile_forwarder(profile_tx);
-
         // Create and show flyout
-        println!("[FLYOUT] Creating flyout window with {} profiles", self.profiles.len());
+        info!("[FLYOUT] Creating flyout window with {} profiles", self.profiles.len());
         let flyout = FlyoutWindow::new(
-            _tray_rect,
-            self.profiles.clone(),
+            tray_rect,
+            &self.profiles,
+            crate::config::load_config().profile_usage,
             self.active_profile.clone(),
-            ipc_sender,
+            self.badges.overlay_visible,
+            self.flyout_tx.clone(),
         )?;
 
-        println!("[FLYOUT] Showing flyout window");
+        info!("[FLYOUT] Showing flyout window");
         flyout.show();
         self.flyout = Some(flyout);
-        println!("[FLYOUT] Flyout displayed successfully");
+        info!("[FLYOUT] Flyout displayed successfully");
 
         anyhow::Ok(())
     }
 
     /// Check if flyout is currently visible
     pub fn is_flyout_visible(&self) -> bool {
-        self.flyout.is_some()
+        self.flyout.as_ref().is_some_and(|flyout| flyout.is_open())
     }
 
     /// Hide the flyout menu
@@ -242,204 +452,119 @@ impl TrayFlyoutManager {
         self.flyout = None;
     }
 
-    /// Update tooltip based on active profile
+    /// Run the action bound to a tray click; the flyout opens and closes here
+    fn run_click_action(&mut self, action: TrayClickAction) -> Option<TrayToGui> {
+        info!("[TRAY] Click action: {:?}", action);
+        let message = match action {
+            TrayClickAction::Nothing => return None,
+            TrayClickAction::ShowFlyout => {
+                if self.is_flyout_visible() {
+                    self.hide_flyout();
+                } else if let Err(e) = self.show_flyout() {
+                    error!("[TRAY] Failed to show flyout: {}", e);
+                }
+                return None;
+            }
+            TrayClickAction::ShowWindow => TrayToGui::OpenSettings,
+            TrayClickAction::ToggleOverlay => TrayToGui::ToggleOverlay,
+            TrayClickAction::CycleCrosshair => TrayToGui::CycleCrosshair,
+            TrayClickAction::AdjustCrosshair => TrayToGui::AdjustCrosshair,
+            TrayClickAction::TogglePause => TrayToGui::TogglePause,
+            TrayClickAction::Deactivate => TrayToGui::DeactivateProfile,
+        };
+        Some(message)
+    }
+
+    /// Update tooltip based on active profile, or fill in the template with the latest readings
     fn update_tooltip(&mut self) {
-        let tooltip = if let Some(ref name) = self.active_profile {
-            format!("Gaming Optimizer - {}", name)
+        let tooltip = if self.paused {
+            t!("tray-tooltip-paused")
+        } else if self.tooltip_config.is_custom() {
+            let values = TooltipValues {
+                profile: self.active_profile.as_deref(),
+                status: self.activation_status.as_deref(),
+                hardware: self.hardware.as_ref(),
+            };
+            tray_tooltip::render(&self.tooltip_config.template, &values)
+        } else if let Some(ref name) = self.active_profile {
+            match self.activation_status {
+                Some(ref status) => t!("tray-tooltip-status", profile = name, status = status),
+                None => t!("tray-tooltip-active", profile = name),
+            }
         } else {
-            "Gaming Optimizer - Inactive".to_string()
+            t!("tray-tooltip-inactive")
         };
-        
-        self.tray_icon.set_tooltip(Some(&tooltip));
+
+        // Readings come in every few seconds and often don't change the text
+        if tooltip != self.tooltip {
+            if let Err(e) = self.tray_icon.set_tooltip(Some(&tooltip)) {
+                warn!("[TRAY] Failed to update tooltip: {}", e);
+            }
+            self.tooltip = tooltip;
+        }
     }
 
-    /// Update profiles list
+    /// Update profiles list (archived ones are left out)
     pub fn update_profiles(&mut self, profiles: Vec<Profile>) {
-        self.profiles = profiles;
+        self.profiles = unarchived(profiles);
+        self.refresh_offset_menu();
         if let Some(ref mut flyout) = self.flyout {
-            let _ = flyout.update_profiles(self.profiles.clone(), self.active_profile.clone());
+            let _ = flyout.update_profiles(&self.profiles, self.active_profile.clone());
         }
     }
 
+    /// Redraw the icon when the badges changed
+    pub fn set_badges(&mut self, badges: TrayState) {
+        if badges == self.badges {
+            return;
+        }
+        self.badges = badges;
+        if let Some(ref mut flyout) = self.flyout {
+            let _ = flyout.set_overlay_visible(badges.overlay_visible);
+        }
+        match tray_badge::render_icon(&self.base_icon, badges) {
+            Ok(icon) => {
+                if let Err(e) = self.tray_icon.set_icon(Some(icon)) {
+                    warn!("[TRAY] Failed to update icon: {}", e);
+                }
+            }
+            Err(e) => warn!("[TRAY] {}", e),
+        }
+    }
+
+    /// Set the result of the last activation (None = don't show one)
+    pub fn set_activation_status(&mut self, status: Option<String>) {
+        self.activation_status = status;
+        self.update_tooltip();
+    }
+
     /// Set active profile
     pub fn set_active_profile(&mut self, active: Option<String>) {
         self.active_profile = active;
         self.update_tooltip();
+        self.refresh_offset_menu();
         if let Some(ref mut flyout) = self.flyout {
-            let _ = flyout.update_profiles(self.profiles.clone(), self.active_profile.clone());
+            let _ = flyout.update_profiles(&self.profiles, self.active_profile.clone());
         }
     }
 }
 
-/// Run the tray with flyout on the main thread
-pub fn run_tray_flyout_thread(
-    channels: TrayChannels,
-    initial_profiles: Vec<Profile>,
-    active_profile: Option<String>,
-) {
-    use windows::Win32::UI::WindowsAndMessaging::*;
-    
-    println!("[TRAY] Starting tray flyout on main thread");
-    
-    // Create the tray manager
-    let mut tray = match TrayFlyoutManager::new(initial_profiles, active_profile) {
-        Ok(t) => t,
-        Err(e) => {
-            eprintln!("[TRAY] Failed to create tray: {}", e);
-            return;
-        }
-    };
-
-    println!("[TRAY] Setting up event handler");
-    
-    // Create channels for tray icon and menu events
-    let (event_tx, event_rx): (Sender<TrayIconEvent>, Receiver<TrayIconEvent>) = std::sync::mpsc::channel();
-    let (menu_tx, menu_rx): (Sender<MenuEvent>, Receiver<MenuEvent>) = std::sync::mpsc::channel();
-    
-    // Set up event handler to forward events to our channel
-    TrayIconEvent::set_event_handler(Some(move |event| {
-        println!("[TRAY] *** EVENT HANDLER CALLED: {:?} ***", event);
-        let _ = event_tx.send(event);
-    }));
+/// Dispatch the thread's pending window messages (the tray icon and flyout live on them).
+/// Returns true if WM_QUIT came along.
+pub fn pump_messages() -> bool {
+    use windows::Win32::UI::WindowsAndMessaging::{DispatchMessageW, PeekMessageW, TranslateMessage, MSG, PM_REMOVE, WM_QUIT};
     
-    // Set up menu event handler
-    MenuEvent::set_event_handler(Some(move |event| {
-        println!("[MENU] *** MENU EVENT: {:?} ***", event);
-        let _ = menu_tx.send(event);
-    }));
-
-    println!("[TRAY] Event handler set, entering Windows message loop");
-
-    // Windows message loop - required for tray icon events
+    let mut quit = false;
     unsafe {
         let mut msg = MSG::default();
-        loop {
-            // Process Windows messages (this enables tray icon events)
-            while PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE).as_bool() {
-                if msg.message == WM_QUIT {
-                    println!("[TRAY] WM_QUIT received, exiting");
-                    return;
-                }
-                TranslateMessage(&msg);
-                DispatchMessageW(&msg);
-            }
-
-            // Check for tray icon events
-            match event_rx.try_recv() {
-                Ok(event) => {
-                    println!("[TRAY] Processing event: {:?}", event);
-                    match event {
-                        TrayIconEvent::Click { button, button_state, .. } => {
-                            println!("[TRAY] Click - button: {:?}, state: {:?}", button, button_state);
-                            
-                            if button == MouseButton::Left && button_state == MouseButtonState::Up {
-                                let now = Instant::now();
-                                
-                                // Check for double-click (within 500ms of last click)
-                                if let Some(last_time) = tray.last_click_time {
-                                    if now.duration_since(last_time).as_millis() < 500 {
-                                        // Double-click detected!
-                                        println!("[TRAY] DOUBLE CLICK - opening full GUI");
-                                        tray.pending_single_click = false;
-                                        tray.last_click_time = None;
-                                        
-                                        // Send message to open GUI
-                                        let _ = channels.to_gui.send(crate::ipc::TrayToGui::OpenSettings);
-                                        continue;
-                                    }
-                                }
-                                
-                                // First click - start timer for single-click
-                                println!("[TRAY] First click detected, waiting for potential double-click");
-                                tray.last_click_time = Some(now);
-                                tray.pending_single_click = true;
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                Err(_) => {}
-            }
-            
-            // Check if single-click timer expired (500ms passed)
-            if tray.pending_single_click {
-                if let Some(last_time) = tray.last_click_time {
-                    if Instant::now().duration_since(last_time).as_millis() >= 500 {
-                        // Single click confirmed - show flyout
-                        println!("[TRAY] Single click confirmed - toggling flyout");
-                        tray.pending_single_click = false;
-                        
-                        if tray.flyout.is_some() {
-                            println!("[TRAY] Hiding existing flyout");
-                            tray.hide_flyout();
-                        } else {
-                            println!("[TRAY] Showing new flyout");
-                            if let Err(e) = tray.show_flyout() {
-                                eprintln!("[TRAY] Failed to show flyout: {}", e);
-                            }
-                        }
-                    }
-                }
+        while PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE).as_bool() {
+            if msg.message == WM_QUIT {
+                quit = true;
+                continue;
             }
-            
-            // Check for menu events
-            match menu_rx.try_recv() {
-                Ok(event) => {
-                    println!("[MENU] Processing menu event: {:?}", event);
-                    if event.id == tray.menu_item_settings {
-                        println!("[MENU] Open Settings clicked");
-                        let _ = channels.to_gui.send(crate::ipc::TrayToGui::OpenSettings);
-                    } else if event.id == tray.menu_item_docs {
-                        println!("[MENU] Documentation clicked");
-                        // Open documentation URL
-                        if let Err(e) = open::that("https://github.com/yourusername/gaming_optimizer#readme") {
-                            eprintln!("[MENU] Failed to open documentation: {}", e);
-                        }
-                    } else if event.id == tray.menu_item_bug_report {
-                        println!("[MENU] Report Bug clicked");
-                        // Open GitHub issues page
-                        if let Err(e) = open::that("https://github.com/yourusername/gaming_optimizer/issues/new") {
-                            eprintln!("[MENU] Failed to open bug report page: {}", e);
-                        }
-                    } else if event.id == tray.menu_item_exit {
-                        println!("[MENU] Exit clicked");
-                        let _ = channels.to_gui.send(crate::ipc::TrayToGui::Exit);
-                        break;
-                    }
-                }
-                Err(_) => {}
-            }
-
-            // Check for messages from GUI
-            match channels.from_gui.try_recv() {
-                Ok(msg) => match msg {
-                    GuiToTray::ProfilesUpdated(new_profiles) => {
-                        println!("[TRAY] Received ProfilesUpdated");
-                        tray.update_profiles(new_profiles);
-                    }
-                    GuiToTray::ActiveProfileChanged(new_active) => {
-                        println!("[TRAY] Received ActiveProfileChanged");
-                        tray.set_active_profile(new_active);
-                    }
-                    GuiToTray::OverlayVisibilityChanged(_visible) => {
-                        // Not used in flyout mode
-                    }
-                    GuiToTray::Shutdown => {
-                        println!("[TRAY] Received shutdown signal");
-                        break;
-                    }
-                },
-                Err(TryRecvError::Empty) => {}
-                Err(TryRecvError::Disconnected) => {
-                    println!("[TRAY] Channel disconnected, exiting");
-                    break;
-                }
-            }
-
-            // Small sleep to avoid busy-waiting
-            std::thread::sleep(std::time::Duration::from_millis(10));
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
         }
     }
-    
-    println!("[TRAY] Tray thread exiting");
+    quit
 }